@@ -17,6 +17,10 @@ object PrettyPrinter extends ByteStringPrettyPrinter {
     case Key.Value.Hash(Key.Hash(hash)) => s"Hash(${buildString(hash)})"
     case Key.Value.Local(Key.Local(hash)) =>
       s"Local(${buildString(hash)})"
+    case Key.Value.ContractPackage(Key.ContractPackage(hash)) =>
+      s"ContractPackage(${buildString(hash)})"
+    case Key.Value.DeployInfo(Key.DeployInfo(deployHash)) =>
+      s"DeployInfo(${buildString(deployHash)})"
   }
 
   def buildString(t: Transform): String = t.transformInstance match {
@@ -27,6 +31,7 @@ object PrettyPrinter extends ByteStringPrettyPrinter {
     case Transform.TransformInstance.AddKeys(TransformAddKeys(ks)) =>
       s"Insert(${ks.map(buildString).mkString(",")})"
     case Transform.TransformInstance.Failure(_)  => "TransformFailure"
+    case Transform.TransformInstance.Delete(_)   => "Delete"
     case Transform.TransformInstance.Identity(_) => "Read"
     case Transform.TransformInstance.Write(TransformWrite(mv)) =>
       mv match {
@@ -410,6 +410,17 @@ object Validation {
       case _                      => None
     }
 
+  /** Deploys may also be signed with secp256k1 account keys, which unlike validator IDs don't
+    * have to be 32 bytes long. */
+  private def deploySignatureVerifiers(
+      sigAlgorithm: String
+  ): Option[(Data, Signature, PublicKey) => Boolean] =
+    sigAlgorithm match {
+      case alg if alg == SignatureAlgorithm.Secp256k1.name =>
+        Some((data, sig, pub) => SignatureAlgorithm.Secp256k1.verify(data, sig, pub))
+      case other => signatureVerifiers(other)
+    }
+
   def signature(d: Data, sig: consensus.Signature, key: PublicKey): Boolean =
     signatureVerifiers(sig.sigAlgorithm).fold(false) { verify =>
       verify(d, Signature(sig.sig.toByteArray), key)
@@ -425,7 +436,7 @@ object Validation {
     } else {
       d.approvals.toList
         .traverse { a =>
-          signatureVerifiers(a.getSignature.sigAlgorithm)
+          deploySignatureVerifiers(a.getSignature.sigAlgorithm)
             .map { verify =>
               Try {
                 verify(
@@ -35,7 +35,7 @@ import io.casperlabs.casper.validation.{Validation, ValidationImpl}
 import io.casperlabs.catscontrib.{Fs2Compiler, MonadThrowable}
 import io.casperlabs.crypto.Keys.PrivateKey
 import io.casperlabs.crypto.codec.Base16
-import io.casperlabs.crypto.signatures.SignatureAlgorithm.Ed25519
+import io.casperlabs.crypto.signatures.SignatureAlgorithm.{Ed25519, Secp256k1}
 import io.casperlabs.ipc.ChainSpec.DeployConfig
 import io.casperlabs.models.{ArbitraryConsensus, Message}
 import io.casperlabs.mempool.DeployBuffer
@@ -283,6 +283,24 @@ class ValidationTest
     Validation.deploySignature[Task](deploy) shouldBeF true
   }
 
+  it should "return true for valid secp256k1 signatures" in withoutStorage {
+    val (sk, pk) = Secp256k1.newKeyPair
+    val deploy = {
+      val d   = sample(arbitrary[consensus.Deploy])
+      val sig = Secp256k1.sign(d.deployHash.toByteArray, sk)
+      d.withApprovals(
+        List(
+          Approval()
+            .withApproverPublicKey(ByteString.copyFrom(pk))
+            .withSignature(
+              Signature().withSigAlgorithm(Secp256k1.name).withSig(ByteString.copyFrom(sig))
+            )
+        )
+      )
+    }
+    Validation.deploySignature[Task](deploy) shouldBeF true
+  }
+
   it should "return false if a key in an approval is empty" in withoutStorage {
     val genDeploy = for {
       d <- arbitrary[consensus.Deploy]
@@ -34,7 +34,8 @@ def test_legacy_args_from_json():
     json_str = json.dumps(LEGACY_ARGS)
     args = ABI.args_from_json(json_str)
     assert args[0] == ABI.long_value("amount", long_value)
-    assert args[1] == ABI.account("account", account)
+    # Legacy args can't express the algorithm of a public key.
+    assert args[1] == ABI.bytes_value("account", account)
     assert args[2] == ABI.optional_value("main_purse", None)
     assert args[3] == ABI.big_int("number", big_int_value)
     assert args[4] == ABI.bytes_value("my_bytes", account)
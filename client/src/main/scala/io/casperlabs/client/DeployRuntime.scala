@@ -111,6 +111,30 @@ object DeployRuntime {
       )
     )
 
+  /** Public keys are passed to contracts tagged with their algorithm, as a `(u8, Vec<u8>)`. */
+  private def publicKeyArg(name: String, value: Array[Byte]) = {
+    val (tag, bytes) = cltype.PublicKey(value.toIndexedSeq) match {
+      case Some(cltype.PublicKey.Ed25519(key)) => (cltype.PublicKey.Ed25519.tag, key.bytes)
+      case Some(key: cltype.PublicKey.Secp256k1) => (cltype.PublicKey.Secp256k1.tag, key.bytes)
+      case None =>
+        throw new IllegalArgumentException(
+          s"$name must be a 32-byte Ed25519 or a 33- or 65-byte secp256k1 public key"
+        )
+    }
+    arg(
+      name,
+      cltype.protobuf.Mappings.toProto(
+        cltype.CLValueInstance.Tuple2(
+          cltype.CLValueInstance.U8(tag),
+          cltype.CLValueInstance
+            .List(bytes.map(cltype.CLValueInstance.U8.apply), cltype.CLType.U8)
+            .right
+            .get
+        )
+      )
+    )
+  }
+
   // This is true for any array but I didn't want to go as far as writing type classes.
   private def serializeArray(ba: Array[Byte]): Array[Byte] =
     ba
@@ -354,7 +378,7 @@ object DeployRuntime {
       maybeEitherPublicKey = senderPublicKey.asRight[String].some,
       maybeEitherPrivateKey = senderPrivateKey.asRight[String].some,
       List(
-        bytesArg("account", recipientPublicKey),
+        publicKeyArg("account", recipientPublicKey),
         bigIntArg("amount", amount)
       ),
       exit,
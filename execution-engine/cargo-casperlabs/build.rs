@@ -47,6 +47,15 @@ impl Package for StandardPaymentInstall {
     const WASM_FILENAME: &'static str = "standard_payment_install.wasm";
 }
 
+struct KeyRecoveryInstall;
+
+impl Package for KeyRecoveryInstall {
+    const ROOT: &'static str = "../contracts/system/key-recovery-install";
+    const CARGO_TOML: &'static str = "../contracts/system/key-recovery-install/Cargo.toml";
+    const MAIN_RS: &'static str = "../contracts/system/key-recovery-install/src/main.rs";
+    const WASM_FILENAME: &'static str = "key_recovery_install.wasm";
+}
+
 const TARGET_DIR_FOR_WASM: &str = "target/built-contracts";
 const ORIGINAL_WASM_DIR: &str = "wasm32-unknown-unknown/release";
 const NEW_WASM_DIR: &str = "wasm";
@@ -112,26 +121,30 @@ fn main() {
     let standard_payment_source_exists = Path::new(StandardPayment::CARGO_TOML).is_file();
     let standard_payment_install_source_exists =
         Path::new(StandardPaymentInstall::CARGO_TOML).is_file();
+    let key_recovery_install_source_exists = Path::new(KeyRecoveryInstall::CARGO_TOML).is_file();
 
     match (
         mint_install_source_exists,
         pos_install_source_exists,
         standard_payment_source_exists,
         standard_payment_install_source_exists,
+        key_recovery_install_source_exists,
     ) {
-        (true, true, true, true) => {
+        (true, true, true, true, true) => {
             // We're building from within CasperLabs repo - build the contracts.
             build_package::<MintInstall>();
             build_package::<PosInstall>();
             build_package::<StandardPayment>();
             build_package::<StandardPaymentInstall>();
+            build_package::<KeyRecoveryInstall>();
         }
-        (false, false, false, false) => {
+        (false, false, false, false, false) => {
             // We're outside the CasperLabs repo - the compiled contracts should exist locally.
             assert_wasm_file_exists::<MintInstall>();
             assert_wasm_file_exists::<PosInstall>();
             assert_wasm_file_exists::<StandardPayment>();
             assert_wasm_file_exists::<StandardPaymentInstall>();
+            assert_wasm_file_exists::<KeyRecoveryInstall>();
         }
         _ => panic!("Some, but not all required contract sources exist locally."),
     }
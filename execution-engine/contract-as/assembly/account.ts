@@ -3,6 +3,9 @@ import {arrayToTyped} from "./utils";
 import {UREF_SERIALIZED_LENGTH} from "./constants";
 import {URef} from "./uref";
 import {PublicKey, PUBLIC_KEY_ED25519_ID} from "./key";
+import {readHostBuffer} from "./index";
+import {Error, ErrorCode} from "./error";
+import {fromBytesArray} from "./bytesrepr";
 
 /**
  * Enum representing the possible results of adding an associated key to an account.
@@ -167,6 +170,36 @@ export function removeAssociatedKey(publicKey: PublicKey): RemoveKeyFailure {
     return <RemoveKeyFailure>ret;
 }
 
+/**
+ * Checks whether the keys which signed the current deploy carry enough weight to manage the
+ * associated keys of the deploy's account. See [[setActionThreshold]] for more info.
+ *
+ * @returns `true` if the key management threshold is met.
+ */
+export function canManageKeys(): bool {
+    return externals.can_manage_keys() != 0;
+}
+
+/**
+ * Grants or revokes the ability of an account to store new contracts. Running session code and
+ * calling existing contracts are unaffected.
+ *
+ * Only the system account, e.g. while running an upgrade installer, is permitted to call this.
+ *
+ * @param publicKey The account whose permission is being set.
+ * @param allowed Whether the account may store new contracts.
+ * @returns An [[Error]] if the caller isn't permitted or the account doesn't exist, otherwise null.
+ */
+export function setContractInstallPermission(publicKey: PublicKey, allowed: bool): Error | null {
+    const publicKeyBytes = publicKey.toBytes();
+    const ret = externals.set_contract_install_permission(
+        publicKeyBytes.dataStart,
+        publicKeyBytes.length,
+        allowed ? 1 : 0,
+    );
+    return Error.fromResult(<u32>ret);
+}
+
 /**
  * Gets the [[URef]] representing the main purse of the account.
  *
@@ -179,3 +212,33 @@ export function getMainPurse(): URef {
     let urefResult = URef.fromBytes(data);
     return urefResult.unwrap();
 }
+
+/**
+ * Lists the purses created by the account, in order of creation. The account's main purse is not
+ * included.
+ *
+ * @returns The [[URef]]s of the purses, with their access rights removed.
+ */
+export function listPurses(): Array<URef> {
+    let totalPurses = new Uint32Array(1);
+    let resultSize = new Uint32Array(1);
+
+    const ret = externals.list_purses(totalPurses.dataStart, resultSize.dataStart);
+    const error = Error.fromResult(ret);
+    if (error !== null) {
+        error.revert();
+        return <Array<URef>>unreachable();
+    }
+
+    if (totalPurses[0] == 0) {
+        return new Array<URef>();
+    }
+
+    let pursesBytes = readHostBuffer(resultSize[0]);
+    let maybePurses = fromBytesArray<URef>(pursesBytes, URef.fromBytes);
+    if (maybePurses.hasError()) {
+        Error.fromErrorCode(ErrorCode.Deserialize).revert();
+        return <Array<URef>>unreachable();
+    }
+    return maybePurses.value;
+}
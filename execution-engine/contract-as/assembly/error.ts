@@ -4,7 +4,7 @@ import * as externals from "./externals";
  * Offset of a reserved range dedicated for system contract errors.
  * @internal
  */
-const SYSTEM_CONTRACT_ERROR_CODE_OFFSET: u32 = 65024;
+const SYSTEM_CONTRACT_ERROR_CODE_OFFSET: u32 = 64768;
 
 /**
  * Offset of user errors
@@ -96,7 +96,8 @@ export const enum ErrorCode {
  *
  * | Inclusive range | Variant(s)                                   |
  * | ----------------| ---------------------------------------------|
- * | [1, 65023]      | all except `Mint`, `ProofOfStake` and `User`. Can be created with [[Error.fromErrorCode]] |
+ * | [1, 64767]      | all except `KeyRecovery`, `Mint`, `ProofOfStake` and `User`. Can be created with [[Error.fromErrorCode]] |
+ * | [64768, 65023]  | `KeyRecovery` errors |
  * | [65024, 65279]  | `Mint` - instantiation currently unsupported |
  * | [65280, 65535]  | `ProofOfStake` errors |
  * | [65536, 131071] | User error codes created with [[Error.fromUserError]] |
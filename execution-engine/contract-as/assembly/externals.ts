@@ -29,6 +29,9 @@ export declare function store_function_at_hash(function_name_ptr: usize, functio
 @external("env", "load_named_keys")
 export declare function load_named_keys(total_keys: usize, result_size: usize): i32;
 /** @hidden */
+@external("env", "list_purses")
+export declare function list_purses(total_purses: usize, result_size: usize): i32;
+/** @hidden */
 @external("env", "get_arg")
 export declare function get_arg(index: u32, dest_ptr: usize, dest_size: u32): i32;
 /** @hidden */
@@ -77,9 +80,36 @@ export declare function update_associated_key(public_key_ptr: usize, public_key_
 @external("env", "set_action_threshold")
 export declare function set_action_threshold(permission_level: u32, threshold: i32): i32;
 /** @hidden */
+@external("env", "update_associated_keys")
+export declare function update_associated_keys(updates_ptr: usize, updates_size: usize): i32;
+/** @hidden */
+@external("env", "can_manage_keys")
+export declare function can_manage_keys(): i32;
+/** @hidden */
+@external("env", "rotate_associated_key")
+export declare function rotate_associated_key(account_ptr: usize, account_size: usize, old_key_ptr: usize, old_key_size: usize, new_key_ptr: usize, new_key_size: usize): i32;
+/** @hidden */
+@external("env", "set_contract_install_permission")
+export declare function set_contract_install_permission(account_ptr: usize, account_size: usize, allowed: u32): i32;
+/** @hidden */
 @external("env", "get_blocktime")
 export declare function get_blocktime(dest_ptr: usize): void;
 /** @hidden */
+@external("env", "get_block_height")
+export declare function get_block_height(dest_ptr: usize): void;
+/** @hidden */
+@external("env", "random_bytes")
+export declare function random_bytes(dest_ptr: usize): void;
+/** @hidden */
+@external("env", "blake2b")
+export declare function blake2b(in_ptr: usize, in_size: usize, out_ptr: usize, out_size: usize): i32;
+/** @hidden */
+@external("env", "verify_ed25519")
+export declare function verify_ed25519(signature_ptr: usize, signature_size: usize, message_ptr: usize, message_size: usize, public_key_ptr: usize, public_key_size: usize): i32;
+/** @hidden */
+@external("env", "verify_secp256k1")
+export declare function verify_secp256k1(signature_ptr: usize, signature_size: usize, message_ptr: usize, message_size: usize, public_key_ptr: usize, public_key_size: usize): i32;
+/** @hidden */
 @external("env", "get_caller")
 export declare function get_caller(output_size: usize): i32;
 /** @hidden */
@@ -114,6 +144,21 @@ export declare function transfer_from_purse_to_purse(
     amount_size: u32,
 ): i32;
 /** @hidden */
+@external("env", "transfer_from_purse_to_purse_with_proof")
+export declare function transfer_from_purse_to_purse_with_proof(
+    source_ptr: usize,
+    source_size: u32,
+    target_ptr: usize,
+    target_size: u32,
+    amount_ptr: usize,
+    amount_size: u32,
+    receipt_ptr: usize,
+    receipt_size: u32,
+): i32;
+/** @hidden */
+@external("env", "burn")
+export declare function burn(purse_ptr: usize, purse_size: u32, amount_ptr: usize, amount_size: u32): i32;
+/** @hidden */
 @external("env", "get_balance")
 export declare function get_balance(purse_ptr: usize, purse_size: usize, result_size: u32): i32;
 /** @hidden */
@@ -136,3 +181,12 @@ export declare function get_main_purse(dest_ptr: usize): void;
 /** @hidden */
 @external("env", "read_host_buffer")
 export declare function read_host_buffer(dest_ptr: usize, dest_size: u32, bytes_written: usize): i32;
+/** @hidden */
+@external("env", "guard_reentrancy")
+export declare function guard_reentrancy(): void;
+/** @hidden */
+@external("env", "delete")
+export declare function delete_value(key_ptr: usize, key_size: usize): void;
+/** @hidden */
+@external("env", "list_local")
+export declare function list_local(cursor_ptr: usize, cursor_size: usize, limit: u32, total_entries: usize, result_size: usize): i32;
@@ -38,6 +38,10 @@ export const enum SystemContract {
    * Standard Payment contract.
    */
   StandardPayment = 2,
+  /**
+   * Key Recovery contract.
+   */
+  KeyRecovery = 3,
 }
 
 /**
@@ -210,6 +214,14 @@ export function callContract(key: Key, args: CLValue[]): Uint8Array {
   }
 }
 
+/**
+ * Stops execution if the currently running stored contract is already being executed further up
+ * the call stack, i.e. it has been re-entered via [[callContract]].
+ */
+export function guardReentrancy(): void {
+  externals.guard_reentrancy();
+}
+
 /**
  * Stores the given [[Key]] under a given name in the current context's named keys.
  *
@@ -302,6 +314,78 @@ export function getBlockTime(): u64 {
   return <u64>bytes[0];
 }
 
+/**
+ * Returns the height of the block in which the deploy is being executed.
+ */
+export function getBlockHeight(): u64 {
+  let bytes = new Uint64Array(1);
+  externals.get_block_height(bytes.dataStart);
+  return <u64>bytes[0];
+}
+
+/**
+ * The number of bytes returned by [[randomBytes]].
+ */
+export const RANDOM_BYTES_COUNT: u32 = 32;
+
+/**
+ * Returns bytes which can't be predicted before the deploy is made, but which
+ * are the same wherever the deploy is executed.  Each call within a deploy
+ * returns different bytes.
+ */
+export function randomBytes(): Uint8Array {
+  let bytes = new Uint8Array(RANDOM_BYTES_COUNT);
+  externals.random_bytes(bytes.dataStart);
+  return bytes;
+}
+
+/**
+ * The number of bytes in a digest returned by [[blake2b]].
+ */
+export const BLAKE2B_DIGEST_LENGTH: u32 = 32;
+
+/**
+ * Returns the Blake2b-256 digest of `input`, computed on the host.
+ */
+export function blake2b(input: Uint8Array): Uint8Array {
+  let digest = new Uint8Array(BLAKE2B_DIGEST_LENGTH);
+  let ret = externals.blake2b(input.dataStart, input.length, digest.dataStart, digest.length);
+  const error = Error.fromResult(ret);
+  if (error !== null) {
+    error.revert();
+    return <Uint8Array>unreachable();
+  }
+  return digest;
+}
+
+/**
+ * Returns whether `signature` is a valid Ed25519 signature of `message` by
+ * `publicKey`.  Malformed signatures and public keys are reported as invalid.
+ */
+export function verifyEd25519(signature: Uint8Array, message: Uint8Array, publicKey: Uint8Array): bool {
+  let ret = externals.verify_ed25519(
+    signature.dataStart, signature.length,
+    message.dataStart, message.length,
+    publicKey.dataStart, publicKey.length,
+  );
+  return ret != 0;
+}
+
+/**
+ * Returns whether `signature` is a valid compact secp256k1 signature of the
+ * Blake2b-256 digest of `message` by `publicKey`, which may be either
+ * compressed or uncompressed.  Malformed signatures and public keys are
+ * reported as invalid.
+ */
+export function verifySecp256k1(signature: Uint8Array, message: Uint8Array, publicKey: Uint8Array): bool {
+  let ret = externals.verify_secp256k1(
+    signature.dataStart, signature.length,
+    message.dataStart, message.length,
+    publicKey.dataStart, publicKey.length,
+  );
+  return ret != 0;
+}
+
 /**
  * Returns the caller of the current context, i.e. the [[PublicKey]] of the
  * account which made the deploy request.
@@ -5,7 +5,7 @@ import {URef} from "./uref";
 import {CLValue} from "./clvalue";
 import {Error, ErrorCode} from "./error";
 import {checkTypedArrayEqual, typedToArray} from "./utils";
-import {Result, Ref, Error as BytesreprError} from "./bytesrepr";
+import {Result, Ref, Error as BytesreprError, fromBytesU32, toBytesArrayU8} from "./bytesrepr";
 
 /**
  * Enum representing a variant of a [[Key]] - Account, Hash or URef.
@@ -24,6 +24,17 @@ export enum KeyVariant {
  */
 export const PUBLIC_KEY_ED25519_ID: u8 = 0;
 
+/**
+ * The ID of a compressed secp256k1 public key.
+ */
+export const PUBLIC_KEY_SECP256K1_ID: u8 = 1;
+
+/** The length in bytes of an ED25519 public key. */
+export const ED25519_LENGTH = 32;
+
+/** The length in bytes of a compressed secp256k1 public key. */
+export const SECP256K1_LENGTH = 33;
+
 /** A cryptographic public key. */
 export class PublicKey {
     /**
@@ -46,21 +57,53 @@ export class PublicKey {
         return !this.equalsTo(other);
     }
 
-    /** Deserializes a `PublicKey` from an array of bytes. */
+    /**
+     * Deserializes a `PublicKey` from an array of bytes, i.e. its variant ID followed by the
+     * length-prefixed bytes of the key.
+     */
     static fromBytes(bytes: Uint8Array): Result<PublicKey> {
-        if (bytes.length < 32) {
+        if (bytes.length < 1) {
+            return new Result<PublicKey>(null, BytesreprError.EarlyEndOfStream, 0);
+        }
+        let variant = bytes[0];
+        let currentPos = 1;
+
+        let lengthResult = fromBytesU32(bytes.subarray(currentPos));
+        if (lengthResult.hasError()) {
+            return new Result<PublicKey>(null, lengthResult.error, 0);
+        }
+        let length = <i32>lengthResult.value;
+        currentPos += lengthResult.position;
+
+        if (variant == PUBLIC_KEY_ED25519_ID) {
+            if (length != ED25519_LENGTH) {
+                return new Result<PublicKey>(null, BytesreprError.FormattingError, 0);
+            }
+        }
+        else if (variant == PUBLIC_KEY_SECP256K1_ID) {
+            if (length != SECP256K1_LENGTH) {
+                return new Result<PublicKey>(null, BytesreprError.FormattingError, 0);
+            }
+        }
+        else {
+            return new Result<PublicKey>(null, BytesreprError.FormattingError, 0);
+        }
+
+        if (bytes.length < currentPos + length) {
             return new Result<PublicKey>(null, BytesreprError.EarlyEndOfStream, 0);
         }
 
-        let publicKeyBytes = bytes.subarray(0, 32);
-        let publicKey = new PublicKey(PUBLIC_KEY_ED25519_ID, publicKeyBytes);
+        let publicKeyBytes = bytes.subarray(currentPos, currentPos + length);
+        let publicKey = new PublicKey(variant, publicKeyBytes);
         let ref = new Ref<PublicKey>(publicKey);
-        return new Result<PublicKey>(ref, BytesreprError.Ok, 32);
+        return new Result<PublicKey>(ref, BytesreprError.Ok, currentPos + length);
     }
 
     /** Serializes a `PublicKey` into an array of bytes. */
     toBytes(): Array<u8> {
-        return typedToArray(this.bytes);
+        let bytes = new Array<u8>(1);
+        bytes[0] = this.variant;
+        return bytes.concat(toBytesArrayU8(typedToArray(this.bytes)));
     }
 }
 
@@ -240,6 +283,12 @@ export class Key {
         );
     }
 
+    /** Removes the value stored under this `Key`. */
+    delete(): void {
+        const keyBytes = this.toBytes();
+        externals.delete_value(keyBytes.dataStart, keyBytes.length);
+    }
+
     /** Checks whether two `Key`s are equal. */
     @operator("==")
     equalsTo(other: Key): bool {
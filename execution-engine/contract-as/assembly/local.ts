@@ -51,3 +51,34 @@ export function addLocal(local: Uint8Array, value: CLValue): void {
         valueBytes.length
     );
 }
+
+/**
+ * Returns up to `limit` entries of the context-local partition of global state, ordered by key and
+ * starting after the serialized `Option<Key>` passed as `cursor`.
+ *
+ * At most 100 entries are returned per call regardless of `limit`.
+ *
+ * @category Storage
+ * @returns Returns the serialized list of `(Key, CLValue)` pairs, or null if there are no more
+ * entries.
+ */
+export function listLocal(cursor: Uint8Array, limit: u32): Uint8Array | null {
+    let totalEntries = new Uint32Array(1);
+    let resultSize = new Uint32Array(1);
+    const ret = externals.list_local(
+        cursor.dataStart,
+        cursor.length,
+        limit,
+        totalEntries.dataStart,
+        resultSize.dataStart
+    );
+    const error = Error.fromResult(ret);
+    if (error != null) {
+        error.revert();
+        return <Uint8Array>unreachable();
+    }
+    if (totalEntries[0] == 0) {
+        return null;
+    }
+    return readHostBuffer(resultSize[0]);
+}
@@ -77,6 +77,24 @@ export function getPurseBalance(purse: URef): U512 | null {
     return balanceResult.unwrap();
 }
 
+/**
+ * Burns `amount` of motes from the given purse, removing them from the total supply.
+ *
+ * @returns An [[Error]] if the purse isn't writeable or doesn't hold enough motes, otherwise null.
+ */
+export function burn(purse: URef, amount: U512): Error | null {
+    let purseBytes = purse.toBytes();
+    let amountBytes = amount.toBytes();
+
+    let ret = externals.burn(
+        purseBytes.dataStart,
+        purseBytes.length,
+        amountBytes.dataStart,
+        amountBytes.length,
+    );
+    return Error.fromResult(<u32>ret);
+}
+
 /**
  * Transfers `amount` of motes from `source` purse to `target` account.
  * If `target` does not exist it will be created.
@@ -132,3 +150,37 @@ export function transferFromPurseToPurse(sourcePurse: URef, targetPurse: URef, a
     );
     return ret;
 }
+
+/**
+ * Transfers `amount` of motes from `source` purse to `target` purse, and stores a receipt of the
+ * transfer (deploy hash, source, target and amount) under a new read-only [[URef]].
+ *
+ * @returns The [[URef]] of the receipt, or a null in case of transfer error.
+ */
+export function transferWithProof(sourcePurse: URef, targetPurse: URef, amount: U512): URef | null {
+    let sourceBytes = sourcePurse.toBytes();
+    let targetBytes = targetPurse.toBytes();
+    let amountBytes = amount.toBytes();
+    let receiptBytes = new Uint8Array(UREF_SERIALIZED_LENGTH);
+
+    let ret = externals.transfer_from_purse_to_purse_with_proof(
+        sourceBytes.dataStart,
+        sourceBytes.length,
+        targetBytes.dataStart,
+        targetBytes.length,
+        amountBytes.dataStart,
+        amountBytes.length,
+        receiptBytes.dataStart,
+        receiptBytes.length,
+    );
+    if (ret != 0) {
+        return null;
+    }
+
+    let urefResult = URef.fromBytes(receiptBytes);
+    if (urefResult.hasError()) {
+        Error.fromErrorCode(ErrorCode.Transfer).revert();
+        return <URef>unreachable();
+    }
+    return urefResult.value;
+}
@@ -8,7 +8,7 @@ import { fromBytesU64, toBytesU64,
          toBytesVecT,
          Error } from "../../assembly/bytesrepr";
 import { CLValue } from "../../assembly/clvalue";
-import { Key, KeyVariant, PublicKey, PUBLIC_KEY_ED25519_ID } from "../../assembly/key";
+import { Key, KeyVariant, PublicKey, PUBLIC_KEY_ED25519_ID, PUBLIC_KEY_SECP256K1_ID, SECP256K1_LENGTH } from "../../assembly/key";
 import { URef, AccessRights } from "../../assembly/uref";
 import { Option } from "../../assembly/option";
 import { hex2bin } from "../utils/helpers";
@@ -293,7 +293,7 @@ export function testDecodedOptionalIsSome(): bool {
 export function testDeserMapOfNamedKeys(): bool {
 
     let extraBytes = "fffefd";
-    let truthBytes = "0300000001000000410001010101010101010101010101010101010101010101010101010101010101010200000042420202020202020202020202020202020202020202020202020202020202020202020703000000434343010303030303030303030303030303030303030303030303030303030303030303";
+    let truthBytes = "03000000010000004100002000000001010101010101010101010101010101010101010101010101010101010101010200000042420202020202020202020202020202020202020202020202020202020202020202020703000000434343010303030303030303030303030303030303030303030303030303030303030303";
 
     let truth = hex2bin(truthBytes + extraBytes);
 
@@ -363,3 +363,23 @@ export function testDeserMapOfNamedKeys(): bool {
 
     return true;
 }
+
+export function testPublicKeyRoundTrip(): bool {
+    let keyBytes = new Array<u8>(SECP256K1_LENGTH);
+    keyBytes.fill(2);
+    let publicKey = new PublicKey(PUBLIC_KEY_SECP256K1_ID, arrayToTyped(keyBytes));
+
+    let bytes = publicKey.toBytes();
+    assert(bytes.length == 1 + 4 + SECP256K1_LENGTH);
+    assert(bytes[0] == PUBLIC_KEY_SECP256K1_ID);
+
+    let deser = PublicKey.fromBytes(arrayToTyped(bytes));
+    assert(deser.error == Error.Ok);
+    assert(deser.position == bytes.length);
+    assert(deser.value == publicKey);
+
+    // An ED25519 key with a secp256k1 length is malformed.
+    bytes[0] = PUBLIC_KEY_ED25519_ID;
+    assert(PublicKey.fromBytes(arrayToTyped(bytes)).error == Error.FormattingError);
+    return true;
+}
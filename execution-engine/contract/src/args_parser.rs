@@ -23,6 +23,12 @@ impl ArgsParser for () {
     }
 }
 
+impl ArgsParser for Vec<CLValue> {
+    fn parse(self) -> Result<Vec<CLValue>, CLValueError> {
+        Ok(self)
+    }
+}
+
 macro_rules! impl_argsparser_tuple {
     ( $($name:ident)+) => (
         impl<$($name: CLTyped + ToBytes),*> ArgsParser for ($($name,)*) {
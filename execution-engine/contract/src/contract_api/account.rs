@@ -1,21 +1,30 @@
 //! Functions for managing accounts.
 
 use alloc::vec::Vec;
-use core::convert::TryFrom;
+use core::{convert::TryFrom, mem::MaybeUninit};
 
 use casperlabs_types::{
     account::{
-        ActionType, AddKeyFailure, PublicKey, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        ActionType, AddKeyFailure, AssociatedKeysUpdate, PublicKey, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
-    bytesrepr, URef, UREF_SERIALIZED_LENGTH,
+    api_error, bytesrepr, ApiError, URef, UREF_SERIALIZED_LENGTH,
 };
 
 use super::to_ptr;
-use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
+use crate::{
+    contract_api::{self, runtime},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
 
 /// Retrieves the ID of the account's main purse.
 pub fn get_main_purse() -> URef {
+    try_get_main_purse().unwrap_or_revert()
+}
+
+/// As [`get_main_purse`], but returns any error instead of reverting.
+pub fn try_get_main_purse() -> Result<URef, ApiError> {
     let dest_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
     let bytes = unsafe {
         ext_ffi::get_main_purse(dest_non_null_ptr.as_ptr());
@@ -25,7 +34,35 @@ pub fn get_main_purse() -> URef {
             UREF_SERIALIZED_LENGTH,
         )
     };
-    bytesrepr::deserialize(bytes).unwrap_or_revert()
+    Ok(bytesrepr::deserialize(bytes)?)
+}
+
+/// Returns the purses created by the account, in order of creation.
+///
+/// The account's main purse is not included.  The purses are returned with their access rights
+/// removed, so they can be passed to [`get_balance`](crate::contract_api::system::get_balance) but
+/// can't be used to withdraw from.
+pub fn list_purses() -> Vec<URef> {
+    try_list_purses().unwrap_or_revert()
+}
+
+/// As [`list_purses`], but returns any error instead of reverting.
+pub fn try_list_purses() -> Result<Vec<URef>, ApiError> {
+    let (total_purses, result_size) = {
+        let mut total_purses = MaybeUninit::uninit();
+        let mut result_size = 0;
+        let ret = unsafe {
+            ext_ffi::list_purses(total_purses.as_mut_ptr(), &mut result_size as *mut usize)
+        };
+        api_error::result_from(ret)?;
+        let total_purses = unsafe { total_purses.assume_init() };
+        (total_purses, result_size)
+    };
+    if total_purses == 0 {
+        return Ok(Vec::new());
+    }
+    let bytes = runtime::read_host_buffer(result_size)?;
+    Ok(bytesrepr::deserialize(bytes)?)
 }
 
 /// Sets the given [`ActionType`]'s threshold to the provided value.
@@ -84,3 +121,60 @@ pub fn update_associated_key(
         Err(UpdateKeyFailure::try_from(result).unwrap_or_revert())
     }
 }
+
+/// Applies `updates` to the account's associated keys and action thresholds as a single change.
+///
+/// The thresholds are only checked once all of `updates` have been applied, so the account may
+/// pass through states which the individual key management functions would reject.  If any update
+/// fails, or the final thresholds can't be met, none of them are applied.
+pub fn update_associated_keys(updates: Vec<AssociatedKeysUpdate>) -> Result<(), ApiError> {
+    let (updates_ptr, updates_size, _bytes) = to_ptr(updates);
+    let result = unsafe { ext_ffi::update_associated_keys(updates_ptr, updates_size) };
+    api_error::result_from(result)
+}
+
+/// Returns `true` if the keys which signed the current deploy meet the key management threshold
+/// of the deploy's account.
+pub fn can_manage_keys() -> bool {
+    let result = unsafe { ext_ffi::can_manage_keys() };
+    result != 0
+}
+
+/// Replaces `old_key` in the associated keys of `account` with `new_key`, which receives the same
+/// [`Weight`].
+///
+/// Only the Key Recovery system contract is permitted to call this; any other caller receives
+/// [`ApiError::PermissionDenied`].
+pub fn rotate_associated_key(
+    account: PublicKey,
+    old_key: PublicKey,
+    new_key: PublicKey,
+) -> Result<(), ApiError> {
+    let (account_ptr, account_size, _bytes1) = to_ptr(account);
+    let (old_key_ptr, old_key_size, _bytes2) = to_ptr(old_key);
+    let (new_key_ptr, new_key_size, _bytes3) = to_ptr(new_key);
+    let result = unsafe {
+        ext_ffi::rotate_associated_key(
+            account_ptr,
+            account_size,
+            old_key_ptr,
+            old_key_size,
+            new_key_ptr,
+            new_key_size,
+        )
+    };
+    api_error::result_from(result)
+}
+
+/// Grants or revokes the ability of `account` to store new contracts.  Running session code and
+/// calling existing contracts are unaffected.
+///
+/// Only the system account, e.g. while running an upgrade installer, is permitted to call this;
+/// any other caller receives [`ApiError::PermissionDenied`].
+pub fn set_contract_install_permission(account: PublicKey, allowed: bool) -> Result<(), ApiError> {
+    let (account_ptr, account_size, _bytes) = to_ptr(account);
+    let result = unsafe {
+        ext_ffi::set_contract_install_permission(account_ptr, account_size, allowed as u32)
+    };
+    api_error::result_from(result)
+}
@@ -0,0 +1,126 @@
+//! Functions for splitting a workflow across several deploys.
+//!
+//! A contract can [`schedule`] a continuation, i.e. a call to an entry point of a stored contract
+//! along with the arguments to pass to it.  The continuation is persisted in the context-local
+//! partition of global state under a key derived from its name, so a later deploy running in the
+//! same context can execute it via [`resume`].
+//!
+//! Stored contracts conventionally select the entry point via their first argument, so resuming a
+//! continuation calls the stored contract with the entry point name followed by the scheduled
+//! arguments.
+//!
+//! # Example
+//!
+//! The following stored contract schedules its `"finish"` entry point when called with `"start"`
+//! and exposes a `"resume"` driver entry point which runs the continuation in a later deploy.
+//!
+//! ```rust,no_run
+//! #![no_std]
+//!
+//! use casperlabs_contract::{
+//!     contract_api::{continuation, runtime, storage},
+//!     unwrap_or_revert::UnwrapOrRevert,
+//! };
+//! use casperlabs_types::{ApiError, ContractRef, Key};
+//!
+//! const CONTINUATION_NAME: &str = "pending";
+//!
+//! #[no_mangle]
+//! pub extern "C" fn call() {
+//!     let method: alloc::string::String = runtime::get_arg(0)
+//!         .unwrap_or_revert_with(ApiError::MissingArgument)
+//!         .unwrap_or_revert_with(ApiError::InvalidArgument);
+//!     match method.as_str() {
+//!         "start" => {
+//!             // The caller passes in the key of this stored contract.
+//!             let this: ContractRef = runtime::get_arg::<Key>(1)
+//!                 .unwrap_or_revert_with(ApiError::MissingArgument)
+//!                 .unwrap_or_revert_with(ApiError::InvalidArgument)
+//!                 .to_contract_ref()
+//!                 .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+//!             continuation::schedule(CONTINUATION_NAME, this, "finish", (42u64,));
+//!         }
+//!         "resume" => continuation::resume::<()>(CONTINUATION_NAME),
+//!         "finish" => storage::write_local(1u8, 42u64),
+//!         _ => runtime::revert(ApiError::InvalidArgument),
+//!     }
+//! }
+//! # extern crate alloc;
+//! # fn main() {}
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casperlabs_types::{
+    bytesrepr::{self, FromBytes},
+    ApiError, CLTyped, CLValue, ContractRef, Key,
+};
+
+use crate::{
+    args_parser::ArgsParser,
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+/// Prefix of the context-local keys under which continuations are stored, to keep them apart from
+/// other context-local values.
+const CONTINUATION_KEY_PREFIX: &str = "continuation:";
+
+/// A persisted continuation: the stored contract to call, the entry point and the serialized
+/// arguments.
+type Record = (Key, String, Vec<u8>);
+
+fn continuation_key(name: &str) -> String {
+    format!("{}{}", CONTINUATION_KEY_PREFIX, name)
+}
+
+fn read_record(name: &str) -> Option<Record> {
+    storage::read_local::<_, Option<Record>>(&continuation_key(name))
+        .unwrap_or_revert_with(ApiError::Read)
+        .and_then(|record| record)
+}
+
+/// Persists a continuation named `name` which, when resumed, calls `entry_point` of the stored
+/// contract `contract` with the given arguments.
+///
+/// Any continuation previously scheduled under `name` in the current context is replaced.
+pub fn schedule<A: ArgsParser>(name: &str, contract: ContractRef, entry_point: &str, args: A) {
+    let args: Vec<CLValue> = args.parse().unwrap_or_revert();
+    let serialized_args = bytesrepr::serialize(args).unwrap_or_revert();
+    let record: Record = (contract.into(), entry_point.to_string(), serialized_args);
+    storage::write_local(continuation_key(name), Some(record));
+}
+
+/// Returns `true` if a continuation named `name` is pending in the current context.
+pub fn is_scheduled(name: &str) -> bool {
+    read_record(name).is_some()
+}
+
+/// Discards the continuation named `name` in the current context without executing it.
+pub fn cancel(name: &str) {
+    storage::write_local(continuation_key(name), Option::<Record>::None);
+}
+
+/// Executes the continuation named `name` which was scheduled in the current context, returning
+/// the value returned by the called entry point.
+///
+/// The continuation is consumed before it is executed, so it runs at most once unless the called
+/// entry point schedules it again.  Reverts with [`ApiError::ValueNotFound`] if no such
+/// continuation is pending.
+pub fn resume<T: CLTyped + FromBytes>(name: &str) -> T {
+    let (contract_key, entry_point, serialized_args) =
+        read_record(name).unwrap_or_revert_with(ApiError::ValueNotFound);
+    cancel(name);
+
+    let contract = contract_key
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    let mut args = Vec::new();
+    args.push(CLValue::from_t(entry_point).unwrap_or_revert());
+    args.extend(bytesrepr::deserialize::<Vec<CLValue>>(serialized_args).unwrap_or_revert());
+    runtime::call_contract(contract, args)
+}
@@ -0,0 +1,84 @@
+//! Functions for checked and saturating arithmetic on [`U256`] and [`U512`], computed on the host.
+//!
+//! Big-number arithmetic compiled to Wasm is charged per instruction and so costs far more gas than
+//! these functions, which are charged a fixed cost per operation.  The checked functions return
+//! [`ApiError::ArithmeticOverflow`] or [`ApiError::DivisionByZero`] where the corresponding
+//! operator would panic.
+
+use casperlabs_types::{api_error, ApiError, BigNumOp, U256, U512};
+
+use crate::{ext_ffi, unwrap_or_revert::UnwrapOrRevert};
+
+/// An unsigned integer type whose arithmetic can be computed on the host.
+pub trait HostArithmetic: Sized {
+    #[doc(hidden)]
+    fn host_op(op: BigNumOp, lhs: Self, rhs: Self) -> Result<Self, ApiError>;
+}
+
+macro_rules! impl_host_arithmetic {
+    ($uint:ty, $width:expr) => {
+        impl HostArithmetic for $uint {
+            fn host_op(op: BigNumOp, lhs: Self, rhs: Self) -> Result<Self, ApiError> {
+                let mut lhs_bytes = [0u8; $width];
+                let mut rhs_bytes = [0u8; $width];
+                let mut output = [0u8; $width];
+                lhs.to_little_endian(&mut lhs_bytes);
+                rhs.to_little_endian(&mut rhs_bytes);
+                let ret = unsafe {
+                    ext_ffi::bignum_op(
+                        op.into(),
+                        $width,
+                        lhs_bytes.as_ptr(),
+                        rhs_bytes.as_ptr(),
+                        output.as_mut_ptr(),
+                    )
+                };
+                api_error::result_from(ret)?;
+                Ok(<$uint>::from_little_endian(&output))
+            }
+        }
+    };
+}
+
+impl_host_arithmetic!(U256, 32);
+impl_host_arithmetic!(U512, 64);
+
+/// Returns `lhs + rhs`, or [`ApiError::ArithmeticOverflow`] if it overflows.
+pub fn checked_add<T: HostArithmetic>(lhs: T, rhs: T) -> Result<T, ApiError> {
+    T::host_op(BigNumOp::CheckedAdd, lhs, rhs)
+}
+
+/// Returns `lhs - rhs`, or [`ApiError::ArithmeticOverflow`] if it underflows.
+pub fn checked_sub<T: HostArithmetic>(lhs: T, rhs: T) -> Result<T, ApiError> {
+    T::host_op(BigNumOp::CheckedSub, lhs, rhs)
+}
+
+/// Returns `lhs * rhs`, or [`ApiError::ArithmeticOverflow`] if it overflows.
+pub fn checked_mul<T: HostArithmetic>(lhs: T, rhs: T) -> Result<T, ApiError> {
+    T::host_op(BigNumOp::CheckedMul, lhs, rhs)
+}
+
+/// Returns `lhs / rhs`, or [`ApiError::DivisionByZero`] if `rhs` is zero.
+pub fn checked_div<T: HostArithmetic>(lhs: T, rhs: T) -> Result<T, ApiError> {
+    T::host_op(BigNumOp::CheckedDiv, lhs, rhs)
+}
+
+/// Returns `lhs % rhs`, or [`ApiError::DivisionByZero`] if `rhs` is zero.
+pub fn checked_rem<T: HostArithmetic>(lhs: T, rhs: T) -> Result<T, ApiError> {
+    T::host_op(BigNumOp::CheckedRem, lhs, rhs)
+}
+
+/// Returns `lhs + rhs`, saturating at the maximum value of the type.
+pub fn saturating_add<T: HostArithmetic>(lhs: T, rhs: T) -> T {
+    T::host_op(BigNumOp::SaturatingAdd, lhs, rhs).unwrap_or_revert()
+}
+
+/// Returns `lhs - rhs`, saturating at zero.
+pub fn saturating_sub<T: HostArithmetic>(lhs: T, rhs: T) -> T {
+    T::host_op(BigNumOp::SaturatingSub, lhs, rhs).unwrap_or_revert()
+}
+
+/// Returns `lhs * rhs`, saturating at the maximum value of the type.
+pub fn saturating_mul<T: HostArithmetic>(lhs: T, rhs: T) -> T {
+    T::host_op(BigNumOp::SaturatingMul, lhs, rhs).unwrap_or_revert()
+}
@@ -1,9 +1,18 @@
 //! Contains support for writing smart contracts.
+//!
+//! Functions which revert when the host reports an error have a `try_` counterpart returning
+//! `Result<T, ApiError>` instead, so contracts can recover from the error or revert with their own.
+//! Host functions such as [`storage::write`] which abort execution on the host side, rather than
+//! reporting an error, have no such counterpart.
 
 pub mod account;
+pub mod continuation;
+pub mod math;
 pub mod runtime;
 pub mod storage;
+pub mod stored;
 pub mod system;
+pub mod token;
 
 use alloc::{
     alloc::{alloc, Layout},
@@ -7,15 +7,19 @@ use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use core::mem::MaybeUninit;
 
 use casperlabs_types::{
-    account::PublicKey,
+    account::{AccountInfo, PublicKey},
     api_error,
-    bytesrepr::{self, FromBytes},
-    ApiError, BlockTime, CLTyped, CLValue, ContractRef, Key, Phase, URef,
-    BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    bytesrepr::{self, FromBytes, U64_SERIALIZED_LENGTH},
+    AccessRights, ApiError, BlockTime, CLTyped, CLValue, CLValueError, ChainConfigKey, ContractAbi,
+    ContractRef, Key, Phase, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH,
+    PHASE_SERIALIZED_LENGTH, UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{args_parser::ArgsParser, contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
 
+/// The number of bytes returned by [`random_bytes`].
+pub const RANDOM_BYTES_COUNT: usize = 32;
+
 /// Returns the given [`CLValue`] to the host, terminating the currently running module.
 ///
 /// Note this function is only relevant to contracts stored on chain which are invoked via
@@ -41,15 +45,26 @@ pub fn revert<T: Into<ApiError>>(error: T) -> ! {
 /// Calls the given stored contract, passing the given arguments to it.
 ///
 /// If the stored contract calls [`ret`], then that value is returned from `call_contract`.  If the
-/// stored contract calls [`revert`], then execution stops and `call_contract` doesn't return.
-/// Otherwise `call_contract` returns `()`.
+/// stored contract calls [`revert`], then execution stops and `call_contract` doesn't return; use
+/// [`try_call_contract`] to handle that case instead.  Otherwise `call_contract` returns `()`.
 #[allow(clippy::ptr_arg)]
 pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef, args: A) -> T {
+    try_call_contract(c_ptr, args).unwrap_or_revert()
+}
+
+/// Calls the given stored contract, passing the given arguments to it, and returns the error it
+/// reverted with, if any.
+///
+/// Any changes to global state made by the stored contract (and the contracts it called in turn)
+/// are discarded if it reverts, while those made by the current contract are kept.  An `Err` is
+/// also returned if the call couldn't be made or its result couldn't be read.
+pub fn try_call_contract<A: ArgsParser, T: CLTyped + FromBytes>(
+    c_ptr: ContractRef,
+    args: A,
+) -> Result<T, ApiError> {
     let contract_key: Key = c_ptr.into();
     let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
-    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args)
-        .map(contract_api::to_ptr)
-        .unwrap_or_revert();
+    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args).map(contract_api::to_ptr)?;
 
     let bytes_written = {
         let mut bytes_written = MaybeUninit::uninit();
@@ -62,7 +77,7 @@ pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef,
                 bytes_written.as_mut_ptr(),
             )
         };
-        api_error::result_from(ret).unwrap_or_revert();
+        api_error::result_from(ret)?;
         unsafe { bytes_written.assume_init() }
     };
 
@@ -76,11 +91,115 @@ pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef,
         let mut dest: Vec<u8> = unsafe {
             Vec::from_raw_parts(bytes_non_null_ptr.as_ptr(), bytes_written, bytes_written)
         };
-        read_host_buffer_into(&mut dest).unwrap_or_revert();
+        read_host_buffer_into(&mut dest)?;
         dest
     };
 
-    bytesrepr::deserialize(serialized_result).unwrap_or_revert()
+    bytesrepr::deserialize(serialized_result).map_err(ApiError::from)
+}
+
+/// Registers a call to the given stored contract with the given arguments, to be made by the system
+/// as the current account in the first block ending an era whose time is at least
+/// `earliest_blocktime`.
+///
+/// The call may use up to `gas_budget` gas, all of which is charged to the current deploy now.  Its
+/// return value is discarded, as are its changes to global state if it fails.
+pub fn schedule_call<A: ArgsParser>(
+    c_ptr: ContractRef,
+    args: A,
+    earliest_blocktime: BlockTime,
+    gas_budget: u64,
+) -> Result<(), ApiError> {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
+    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args).map(contract_api::to_ptr)?;
+    let (blocktime_ptr, blocktime_size, _bytes3) = contract_api::to_ptr(earliest_blocktime);
+    let (gas_budget_ptr, gas_budget_size, _bytes4) = contract_api::to_ptr(gas_budget);
+    let ret = unsafe {
+        ext_ffi::schedule_call(
+            key_ptr,
+            key_size,
+            args_ptr,
+            args_size,
+            blocktime_ptr,
+            blocktime_size,
+            gas_budget_ptr,
+            gas_budget_size,
+        )
+    };
+    api_error::result_from(ret)
+}
+
+/// Calls the entry point named `entry_point` of the given stored contract, passing the given
+/// arguments to it.
+///
+/// The contract must have been stored with [`storage::store_versioned_contract`], and the caller
+/// must be allowed to call `entry_point`, otherwise execution stops.  The value returned is as for
+/// [`call_contract`].
+///
+/// [`storage::store_versioned_contract`]: crate::contract_api::storage::store_versioned_contract
+pub fn call_versioned_contract<A: ArgsParser, T: CLTyped + FromBytes>(
+    contract: ContractRef,
+    entry_point: &str,
+    args: A,
+) -> T {
+    try_call_versioned_contract(contract, entry_point, args).unwrap_or_revert()
+}
+
+/// Calls the entry point named `entry_point` of the given stored contract, passing the given
+/// arguments to it, and returns the error it reverted with, if any.
+///
+/// Reverts are handled as for [`try_call_contract`].
+pub fn try_call_versioned_contract<A: ArgsParser, T: CLTyped + FromBytes>(
+    contract: ContractRef,
+    entry_point: &str,
+    args: A,
+) -> Result<T, ApiError> {
+    let contract_key: Key = contract.into();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
+    let (entry_point_ptr, entry_point_size, _bytes2) = contract_api::to_ptr(entry_point);
+    let (args_ptr, args_size, _bytes3) = ArgsParser::parse(args).map(contract_api::to_ptr)?;
+
+    let bytes_written = {
+        let mut bytes_written = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::call_versioned_contract(
+                key_ptr,
+                key_size,
+                entry_point_ptr,
+                entry_point_size,
+                args_ptr,
+                args_size,
+                bytes_written.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret)?;
+        unsafe { bytes_written.assume_init() }
+    };
+
+    let serialized_result = if bytes_written == 0 {
+        vec![]
+    } else {
+        // NOTE: as in `try_call_contract()`, `read_host_buffer()` isn't called directly here.
+        let bytes_non_null_ptr = contract_api::alloc_bytes(bytes_written);
+        let mut dest: Vec<u8> = unsafe {
+            Vec::from_raw_parts(bytes_non_null_ptr.as_ptr(), bytes_written, bytes_written)
+        };
+        read_host_buffer_into(&mut dest)?;
+        dest
+    };
+
+    bytesrepr::deserialize(serialized_result).map_err(ApiError::from)
+}
+
+/// Stops execution if the currently running stored contract is already being executed further up
+/// the call stack.
+///
+/// Calling this at the start of a stored contract makes it non-reentrant: if the contract is
+/// re-entered via [`call_contract`], whether directly or through other contracts, the whole
+/// execution fails.
+pub fn guard_reentrancy() {
+    unsafe { ext_ffi::guard_reentrancy() }
 }
 
 /// Takes the name of a (non-mangled) `extern "C"` function to store as a contract under the given
@@ -90,25 +209,43 @@ pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef,
 /// the original contract's named_keys, the current protocol version, and the newly created bytes of
 /// the stored function.
 pub fn upgrade_contract_at_uref(name: &str, uref: URef) {
+    try_upgrade_contract_at_uref(name, uref).unwrap_or_revert()
+}
+
+/// As [`upgrade_contract_at_uref`], but returns the error if the contract couldn't be upgraded.
+pub fn try_upgrade_contract_at_uref(name: &str, uref: URef) -> Result<(), ApiError> {
     let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
     let key: Key = uref.into();
     let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
     let result_value =
         unsafe { ext_ffi::upgrade_contract_at_uref(name_ptr, name_size, key_ptr, key_size) };
-    match api_error::result_from(result_value) {
-        Ok(()) => (),
-        Err(error) => revert(error),
-    }
+    api_error::result_from(result_value)
 }
 
-fn get_arg_size(i: u32) -> Option<usize> {
+fn get_arg_size(i: u32) -> Result<Option<usize>, ApiError> {
     let mut arg_size: usize = 0;
     let ret = unsafe { ext_ffi::get_arg_size(i as usize, &mut arg_size as *mut usize) };
     match api_error::result_from(ret) {
-        Ok(_) => Some(arg_size),
-        Err(ApiError::MissingArgument) => None,
-        Err(e) => revert(e),
+        Ok(_) => Ok(Some(arg_size)),
+        Err(ApiError::MissingArgument) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn get_arg_bytes(i: u32) -> Result<Option<Vec<u8>>, ApiError> {
+    let arg_size = match get_arg_size(i)? {
+        Some(arg_size) => arg_size,
+        None => return Ok(None),
+    };
+    if arg_size == 0 {
+        // Avoids allocation with 0 bytes and a call to get_arg
+        return Ok(Some(Vec::new()));
     }
+    let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+    let ret = unsafe { ext_ffi::get_arg(i as usize, data_non_null_ptr.as_ptr(), arg_size) };
+    let data = unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+    api_error::result_from(ret)?;
+    Ok(Some(data))
 }
 
 /// Returns the i-th argument passed to the host for the current module invocation.
@@ -116,39 +253,208 @@ fn get_arg_size(i: u32) -> Option<usize> {
 /// Note that this is only relevant to contracts stored on-chain since a contract deployed directly
 /// is not invoked with any arguments.
 pub fn get_arg<T: FromBytes>(i: u32) -> Option<Result<T, bytesrepr::Error>> {
-    let arg_size = get_arg_size(i)?;
-    let arg_bytes = if arg_size > 0 {
-        let res = {
-            let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
-            let ret = unsafe { ext_ffi::get_arg(i as usize, data_non_null_ptr.as_ptr(), arg_size) };
-            let data =
-                unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
-            api_error::result_from(ret).map(|_| data)
-        };
-        // Assumed to be safe as `get_arg_size` checks the argument already
-        res.unwrap_or_revert()
-    } else {
-        // Avoids allocation with 0 bytes and a call to get_arg
-        Vec::new()
-    };
+    let arg_bytes = get_arg_bytes(i).unwrap_or_revert()?;
     Some(bytesrepr::deserialize(arg_bytes))
 }
 
+/// Returns the i-th argument passed to the host for the current module invocation.
+///
+/// Returns [`ApiError::MissingArgument`] if there is no such argument, or
+/// [`ApiError::InvalidArgument`] if it can't be deserialized as a `T`.
+pub fn try_get_arg<T: FromBytes>(i: u32) -> Result<T, ApiError> {
+    let arg_bytes = get_arg_bytes(i)?.ok_or(ApiError::MissingArgument)?;
+    bytesrepr::deserialize(arg_bytes).map_err(|_| ApiError::InvalidArgument)
+}
+
+fn get_named_arg_size(name: &str) -> Result<Option<usize>, ApiError> {
+    let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+    let mut arg_size: usize = 0;
+    let ret =
+        unsafe { ext_ffi::get_named_arg_size(name_ptr, name_size, &mut arg_size as *mut usize) };
+    match api_error::result_from(ret) {
+        Ok(_) => Ok(Some(arg_size)),
+        Err(ApiError::MissingArgument) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn get_named_arg_value(name: &str) -> Result<Option<Result<CLValue, bytesrepr::Error>>, ApiError> {
+    let arg_size = match get_named_arg_size(name)? {
+        Some(arg_size) => arg_size,
+        None => return Ok(None),
+    };
+    let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+    let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+    let ret = unsafe {
+        ext_ffi::get_named_arg(name_ptr, name_size, data_non_null_ptr.as_ptr(), arg_size)
+    };
+    let data = unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+    api_error::result_from(ret)?;
+    Ok(Some(bytesrepr::deserialize(data)))
+}
+
+/// Returns the argument called `name` passed to the host for the current module invocation.
+///
+/// Returns `None` if there is no such argument, including when the module was invoked with
+/// positional arguments.  Unlike [`get_arg`], the argument's type is checked against `T`.
+pub fn get_named_arg<T: CLTyped + FromBytes>(name: &str) -> Option<Result<T, CLValueError>> {
+    let cl_value = get_named_arg_value(name).unwrap_or_revert()?;
+    let result = cl_value
+        .map_err(CLValueError::Serialization)
+        .and_then(CLValue::into_t);
+    Some(result)
+}
+
+/// Returns the argument called `name` passed to the host for the current module invocation.
+///
+/// Returns [`ApiError::MissingArgument`] if there is no such argument, or
+/// [`ApiError::InvalidArgument`] if it isn't a `T`.
+pub fn try_get_named_arg<T: CLTyped + FromBytes>(name: &str) -> Result<T, ApiError> {
+    get_named_arg_value(name)?
+        .ok_or(ApiError::MissingArgument)?
+        .map_err(CLValueError::Serialization)
+        .and_then(CLValue::into_t)
+        .map_err(|_| ApiError::InvalidArgument)
+}
+
+/// Returns the ABI descriptor stored with `contract`, or `None` if it was stored without one.
+pub fn get_contract_abi(contract: ContractRef) -> Option<ContractAbi> {
+    try_get_contract_abi(contract).unwrap_or_revert()
+}
+
+/// As [`get_contract_abi`], but returns any error instead of reverting.
+pub fn try_get_contract_abi(contract: ContractRef) -> Result<Option<ContractAbi>, ApiError> {
+    let contract_key: Key = contract.into();
+    let (key_ptr, key_size, _bytes) = contract_api::to_ptr(contract_key);
+
+    let result_size = {
+        let mut result_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_contract_abi(key_ptr, key_size, result_size.as_mut_ptr()) };
+        match api_error::result_from(ret) {
+            Ok(_) => unsafe { result_size.assume_init() },
+            Err(ApiError::ValueNotFound) => return Ok(None),
+            Err(error) => return Err(error),
+        }
+    };
+    let bytes = read_host_buffer(result_size)?;
+    Ok(Some(bytesrepr::deserialize(bytes)?))
+}
+
 /// Returns the caller of the current context, i.e. the [`PublicKey`] of the account which made the
 /// deploy request.
 pub fn get_caller() -> PublicKey {
+    try_get_caller().unwrap_or_revert()
+}
+
+/// As [`get_caller`], but returns any error instead of reverting.
+pub fn try_get_caller() -> Result<PublicKey, ApiError> {
     let output_size = {
         let mut output_size = MaybeUninit::uninit();
         let ret = unsafe { ext_ffi::get_caller(output_size.as_mut_ptr()) };
-        api_error::result_from(ret).unwrap_or_revert();
+        api_error::result_from(ret)?;
+        unsafe { output_size.assume_init() }
+    };
+    let buf = read_host_buffer(output_size)?;
+    Ok(bytesrepr::deserialize(buf)?)
+}
+
+/// Returns the key of the account or stored contract which invoked the currently running module.
+///
+/// Session code is invoked by the account which made the deploy request, while a stored contract
+/// is invoked by whichever account or contract called it.  Unlike [`get_caller`], this lets a
+/// stored contract tell whether it was called directly or through another contract.  Contract
+/// [`URef`]s are returned with their access rights removed.
+pub fn get_immediate_caller() -> Key {
+    try_get_immediate_caller().unwrap_or_revert()
+}
+
+/// As [`get_immediate_caller`], but returns any error instead of reverting.
+pub fn try_get_immediate_caller() -> Result<Key, ApiError> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_immediate_caller(output_size.as_mut_ptr()) };
+        api_error::result_from(ret)?;
         unsafe { output_size.assume_init() }
     };
-    let buf = read_host_buffer(output_size).unwrap_or_revert();
-    bytesrepr::deserialize(buf).unwrap_or_revert()
+    let buf = read_host_buffer(output_size)?;
+    Ok(bytesrepr::deserialize(buf)?)
+}
+
+/// Returns the keys of the accounts and stored contracts on the call stack, starting with the
+/// account which made the deploy request and ending with the currently running module.
+///
+/// Contract [`URef`]s are returned with their access rights removed.
+pub fn get_call_stack() -> Vec<Key> {
+    try_get_call_stack().unwrap_or_revert()
+}
+
+/// As [`get_call_stack`], but returns any error instead of reverting.
+pub fn try_get_call_stack() -> Result<Vec<Key>, ApiError> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_call_stack(output_size.as_mut_ptr()) };
+        api_error::result_from(ret)?;
+        unsafe { output_size.assume_init() }
+    };
+    let buf = read_host_buffer(output_size)?;
+    Ok(bytesrepr::deserialize(buf)?)
+}
+
+/// Returns the publicly visible configuration of the account with the given public key: its
+/// associated keys, action thresholds and main purse, or `None` if there is no such account.
+///
+/// The main purse is returned with its access rights removed.  Reading the account is charged for
+/// like any other read of global state.
+pub fn get_account_info(public_key: PublicKey) -> Option<AccountInfo> {
+    try_get_account_info(public_key).unwrap_or_revert()
+}
+
+/// As [`get_account_info`], but returns any error instead of reverting.
+pub fn try_get_account_info(public_key: PublicKey) -> Result<Option<AccountInfo>, ApiError> {
+    let (public_key_ptr, public_key_size, _bytes) = contract_api::to_ptr(public_key);
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::get_account_info(public_key_ptr, public_key_size, output_size.as_mut_ptr())
+        };
+        match api_error::result_from(ret) {
+            Ok(()) => {}
+            Err(ApiError::ValueNotFound) => return Ok(None),
+            Err(error) => return Err(error),
+        }
+        unsafe { output_size.assume_init() }
+    };
+    let buf = read_host_buffer(output_size)?;
+    Ok(Some(bytesrepr::deserialize(buf)?))
+}
+
+/// Returns the chain-wide configuration value identified by `key`, so contracts needn't hardcode
+/// values which differ between networks.
+///
+/// `T` must be the type documented for `key` in [`ChainConfigKey`], otherwise execution stops.
+pub fn get_chain_config<T: FromBytes>(key: ChainConfigKey) -> T {
+    try_get_chain_config(key).unwrap_or_revert()
+}
+
+/// As [`get_chain_config`], but returns any error instead of reverting.
+pub fn try_get_chain_config<T: FromBytes>(key: ChainConfigKey) -> Result<T, ApiError> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_chain_config(key.into(), output_size.as_mut_ptr()) };
+        api_error::result_from(ret)?;
+        unsafe { output_size.assume_init() }
+    };
+    let buf = read_host_buffer(output_size)?;
+    Ok(bytesrepr::deserialize(buf)?)
 }
 
 /// Returns the current [`BlockTime`].
 pub fn get_blocktime() -> BlockTime {
+    try_get_blocktime().unwrap_or_revert()
+}
+
+/// As [`get_blocktime`], but returns any error instead of reverting.
+pub fn try_get_blocktime() -> Result<BlockTime, ApiError> {
     let dest_non_null_ptr = contract_api::alloc_bytes(BLOCKTIME_SERIALIZED_LENGTH);
     let bytes = unsafe {
         ext_ffi::get_blocktime(dest_non_null_ptr.as_ptr());
@@ -158,11 +464,103 @@ pub fn get_blocktime() -> BlockTime {
             BLOCKTIME_SERIALIZED_LENGTH,
         )
     };
-    bytesrepr::deserialize(bytes).unwrap_or_revert()
+    Ok(bytesrepr::deserialize(bytes)?)
+}
+
+/// Returns the height of the block in which the deploy is being executed.
+pub fn get_block_height() -> u64 {
+    try_get_block_height().unwrap_or_revert()
+}
+
+/// As [`get_block_height`], but returns any error instead of reverting.
+pub fn try_get_block_height() -> Result<u64, ApiError> {
+    let dest_non_null_ptr = contract_api::alloc_bytes(U64_SERIALIZED_LENGTH);
+    let bytes = unsafe {
+        ext_ffi::get_block_height(dest_non_null_ptr.as_ptr());
+        Vec::from_raw_parts(
+            dest_non_null_ptr.as_ptr(),
+            U64_SERIALIZED_LENGTH,
+            U64_SERIALIZED_LENGTH,
+        )
+    };
+    Ok(bytesrepr::deserialize(bytes)?)
+}
+
+/// Returns bytes which can't be predicted before the deploy is made, but which are the same
+/// wherever the deploy is executed.
+///
+/// The bytes are drawn from a stream seeded with the deploy hash, so each call within a deploy
+/// returns different bytes.  They are suitable for generating IDs, but not for anything which a
+/// deployer could gain from by choosing their deploy hash.
+pub fn random_bytes() -> [u8; RANDOM_BYTES_COUNT] {
+    let mut bytes = [0u8; RANDOM_BYTES_COUNT];
+    unsafe { ext_ffi::random_bytes(bytes.as_mut_ptr()) };
+    bytes
+}
+
+/// Returns the Blake2b-256 digest of `input`, computed on the host.
+pub fn blake2b<T: AsRef<[u8]>>(input: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    try_blake2b(input).unwrap_or_revert()
+}
+
+/// As [`blake2b`], but returns any error instead of reverting.
+pub fn try_blake2b<T: AsRef<[u8]>>(input: T) -> Result<[u8; BLAKE2B_DIGEST_LENGTH], ApiError> {
+    let input = input.as_ref();
+    let mut digest = [0u8; BLAKE2B_DIGEST_LENGTH];
+    let ret = unsafe {
+        ext_ffi::blake2b(
+            input.as_ptr(),
+            input.len(),
+            digest.as_mut_ptr(),
+            BLAKE2B_DIGEST_LENGTH,
+        )
+    };
+    api_error::result_from(ret)?;
+    Ok(digest)
+}
+
+/// Returns whether `signature` is a valid Ed25519 signature of `message` by `public_key`.
+///
+/// Malformed signatures and public keys are reported as invalid.
+pub fn verify_ed25519(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+    let ret = unsafe {
+        ext_ffi::verify_ed25519(
+            signature.as_ptr(),
+            signature.len(),
+            message.as_ptr(),
+            message.len(),
+            public_key.as_ptr(),
+            public_key.len(),
+        )
+    };
+    ret != 0
+}
+
+/// Returns whether `signature` is a valid compact secp256k1 signature of the Blake2b-256 digest of
+/// `message` by `public_key`, which may be either compressed or uncompressed.
+///
+/// Malformed signatures and public keys are reported as invalid.
+pub fn verify_secp256k1(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+    let ret = unsafe {
+        ext_ffi::verify_secp256k1(
+            signature.as_ptr(),
+            signature.len(),
+            message.as_ptr(),
+            message.len(),
+            public_key.as_ptr(),
+            public_key.len(),
+        )
+    };
+    ret != 0
 }
 
 /// Returns the current [`Phase`].
 pub fn get_phase() -> Phase {
+    try_get_phase().unwrap_or_revert()
+}
+
+/// As [`get_phase`], but returns any error instead of reverting.
+pub fn try_get_phase() -> Result<Phase, ApiError> {
     let dest_non_null_ptr = contract_api::alloc_bytes(PHASE_SERIALIZED_LENGTH);
     unsafe { ext_ffi::get_phase(dest_non_null_ptr.as_ptr()) };
     let bytes = unsafe {
@@ -172,7 +570,7 @@ pub fn get_phase() -> Phase {
             PHASE_SERIALIZED_LENGTH,
         )
     };
-    bytesrepr::deserialize(bytes).unwrap_or_revert()
+    Ok(bytesrepr::deserialize(bytes)?)
 }
 
 /// Returns the requested named [`Key`] from the current context.
@@ -180,6 +578,11 @@ pub fn get_phase() -> Phase {
 /// The current context is either the caller's account or a stored contract depending on whether the
 /// currently-executing module is a direct call or a sub-call respectively.
 pub fn get_key(name: &str) -> Option<Key> {
+    try_get_key(name).unwrap_or_revert()
+}
+
+/// As [`get_key`], but returns any error instead of reverting.
+pub fn try_get_key(name: &str) -> Result<Option<Key>, ApiError> {
     let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
     let mut key_bytes = vec![0u8; Key::max_serialized_length()];
     let mut total_bytes: usize = 0;
@@ -194,12 +597,12 @@ pub fn get_key(name: &str) -> Option<Key> {
     };
     match api_error::result_from(ret) {
         Ok(_) => {}
-        Err(ApiError::MissingKey) => return None,
-        Err(e) => revert(e),
+        Err(ApiError::MissingKey) => return Ok(None),
+        Err(e) => return Err(e),
     }
     key_bytes.truncate(total_bytes);
-    let key: Key = bytesrepr::deserialize(key_bytes).unwrap_or_revert();
-    Some(key)
+    let key: Key = bytesrepr::deserialize(key_bytes)?;
+    Ok(Some(key))
 }
 
 /// Returns `true` if `name` exists in the current context's named keys.
@@ -236,21 +639,26 @@ pub fn remove_key(name: &str) {
 /// The current context is either the caller's account or a stored contract depending on whether the
 /// currently-executing module is a direct call or a sub-call respectively.
 pub fn list_named_keys() -> BTreeMap<String, Key> {
+    try_list_named_keys().unwrap_or_revert()
+}
+
+/// As [`list_named_keys`], but returns any error instead of reverting.
+pub fn try_list_named_keys() -> Result<BTreeMap<String, Key>, ApiError> {
     let (total_keys, result_size) = {
         let mut total_keys = MaybeUninit::uninit();
         let mut result_size = 0;
         let ret = unsafe {
             ext_ffi::load_named_keys(total_keys.as_mut_ptr(), &mut result_size as *mut usize)
         };
-        api_error::result_from(ret).unwrap_or_revert();
+        api_error::result_from(ret)?;
         let total_keys = unsafe { total_keys.assume_init() };
         (total_keys, result_size)
     };
     if total_keys == 0 {
-        return BTreeMap::new();
+        return Ok(BTreeMap::new());
     }
-    let bytes = read_host_buffer(result_size).unwrap_or_revert();
-    bytesrepr::deserialize(bytes).unwrap_or_revert()
+    let bytes = read_host_buffer(result_size)?;
+    Ok(bytesrepr::deserialize(bytes)?)
 }
 
 /// Validates uref against named keys.
@@ -260,6 +668,30 @@ pub fn is_valid_uref(uref: URef) -> bool {
     result != 0
 }
 
+/// Returns `uref` keeping only those of its access rights which are also in `access_rights`, e.g.
+/// to pass a read-only `URef` to another contract.
+///
+/// The current context must hold `uref`, otherwise execution stops.
+pub fn attenuate_uref(uref: URef, access_rights: AccessRights) -> URef {
+    try_attenuate_uref(uref, access_rights).unwrap_or_revert()
+}
+
+/// As [`attenuate_uref`], but returns any error instead of reverting.
+pub fn try_attenuate_uref(uref: URef, access_rights: AccessRights) -> Result<URef, ApiError> {
+    let (uref_ptr, uref_size, _bytes) = contract_api::to_ptr(uref);
+    let mut dest = [0u8; UREF_SERIALIZED_LENGTH];
+    let ret = unsafe {
+        ext_ffi::attenuate_uref(
+            uref_ptr,
+            uref_size,
+            access_rights.bits().into(),
+            dest.as_mut_ptr(),
+        )
+    };
+    api_error::result_from(ret)?;
+    Ok(bytesrepr::deserialize(dest.to_vec())?)
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
@@ -6,7 +6,8 @@ use core::{convert::From, mem::MaybeUninit};
 use casperlabs_types::{
     api_error,
     bytesrepr::{self, FromBytes, ToBytes},
-    AccessRights, ApiError, CLTyped, CLValue, ContractRef, Key, URef, UREF_SERIALIZED_LENGTH,
+    AccessRights, ApiError, CLTyped, CLValue, ContractAbi, ContractRef, Key, URef,
+    UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -15,23 +16,49 @@ use crate::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 
-/// Reads value under `uref` in the global state.
-pub fn read<T: CLTyped + FromBytes>(uref: URef) -> Result<Option<T>, bytesrepr::Error> {
+fn read_bytes<T: CLTyped>(uref: URef) -> Result<Option<Vec<u8>>, ApiError> {
     let key: Key = uref.into();
-    let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
+    let (cl_type_ptr, cl_type_size, _bytes2) = contract_api::to_ptr(T::cl_type());
 
     let value_size = {
         let mut value_size = MaybeUninit::uninit();
-        let ret = unsafe { ext_ffi::read_value(key_ptr, key_size, value_size.as_mut_ptr()) };
+        let ret = unsafe {
+            ext_ffi::read_value_typed(
+                key_ptr,
+                key_size,
+                cl_type_ptr,
+                cl_type_size,
+                value_size.as_mut_ptr(),
+            )
+        };
         match api_error::result_from(ret) {
             Ok(_) => unsafe { value_size.assume_init() },
             Err(ApiError::ValueNotFound) => return Ok(None),
-            Err(e) => runtime::revert(e),
+            Err(e) => return Err(e),
         }
     };
 
-    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
-    Ok(Some(bytesrepr::deserialize(value_bytes)?))
+    runtime::read_host_buffer(value_size).map(Some)
+}
+
+/// Reads value under `uref` in the global state.
+///
+/// Execution stops with [`ApiError::CLTypeMismatch`] if the value stored under `uref` isn't a `T`.
+pub fn read<T: CLTyped + FromBytes>(uref: URef) -> Result<Option<T>, bytesrepr::Error> {
+    match read_bytes::<T>(uref).unwrap_or_revert() {
+        Some(value_bytes) => Ok(Some(bytesrepr::deserialize(value_bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// As [`read`], but returns any error instead of reverting, including
+/// [`ApiError::CLTypeMismatch`] if the value isn't a `T`.
+pub fn try_read<T: CLTyped + FromBytes>(uref: URef) -> Result<Option<T>, ApiError> {
+    match read_bytes::<T>(uref)? {
+        Some(value_bytes) => Ok(Some(bytesrepr::deserialize(value_bytes)?)),
+        None => Ok(None),
+    }
 }
 
 /// Reads value under `uref` in the global state, reverts if value not found or is not `T`.
@@ -41,12 +68,7 @@ pub fn read_or_revert<T: CLTyped + FromBytes>(uref: URef) -> T {
         .unwrap_or_revert_with(ApiError::ValueNotFound)
 }
 
-/// Reads the value under `key` in the context-local partition of global state.
-pub fn read_local<K: ToBytes, V: CLTyped + FromBytes>(
-    key: &K,
-) -> Result<Option<V>, bytesrepr::Error> {
-    let key_bytes = key.to_bytes()?;
-
+fn read_local_bytes(key_bytes: &[u8]) -> Result<Option<Vec<u8>>, ApiError> {
     let value_size = {
         let mut value_size = MaybeUninit::uninit();
         let ret = unsafe {
@@ -55,15 +77,36 @@ pub fn read_local<K: ToBytes, V: CLTyped + FromBytes>(
         match api_error::result_from(ret) {
             Ok(_) => unsafe { value_size.assume_init() },
             Err(ApiError::ValueNotFound) => return Ok(None),
-            Err(e) => runtime::revert(e),
+            Err(e) => return Err(e),
         }
     };
 
-    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
-    Ok(Some(bytesrepr::deserialize(value_bytes)?))
+    runtime::read_host_buffer(value_size).map(Some)
+}
+
+/// Reads the value under `key` in the context-local partition of global state.
+pub fn read_local<K: ToBytes, V: CLTyped + FromBytes>(
+    key: &K,
+) -> Result<Option<V>, bytesrepr::Error> {
+    let key_bytes = key.to_bytes()?;
+    match read_local_bytes(&key_bytes).unwrap_or_revert() {
+        Some(value_bytes) => Ok(Some(bytesrepr::deserialize(value_bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// As [`read_local`], but returns any error instead of reverting.
+pub fn try_read_local<K: ToBytes, V: CLTyped + FromBytes>(key: &K) -> Result<Option<V>, ApiError> {
+    let key_bytes = key.to_bytes()?;
+    match read_local_bytes(&key_bytes)? {
+        Some(value_bytes) => Ok(Some(bytesrepr::deserialize(value_bytes)?)),
+        None => Ok(None),
+    }
 }
 
 /// Writes `value` under `uref` in the global state.
+///
+/// Execution stops if a value of a type other than `T` is already stored under `uref`.
 pub fn write<T: CLTyped + ToBytes>(uref: URef, value: T) {
     let key = Key::from(uref);
     let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
@@ -76,6 +119,18 @@ pub fn write<T: CLTyped + ToBytes>(uref: URef, value: T) {
     }
 }
 
+/// Removes the value under `uref` from global state.
+///
+/// The caller must hold write access to `uref`, and a value must currently be stored under it.
+pub fn delete(uref: URef) {
+    let key = Key::from(uref);
+    let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
+
+    unsafe {
+        ext_ffi::delete(key_ptr, key_size);
+    }
+}
+
 /// Writes `value` under `key` in the context-local partition of global state.
 pub fn write_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
@@ -114,6 +169,44 @@ pub fn add_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     }
 }
 
+/// Returns up to `limit` entries of the context-local partition of global state, ordered by key.
+///
+/// If `cursor` is given, only entries after it are returned, so passing the last key of one page
+/// as the cursor of the next call enumerates all entries.  At most [`MAX_LIST_LOCAL_ENTRIES`]
+/// entries are returned per call regardless of `limit`.
+///
+/// [`MAX_LIST_LOCAL_ENTRIES`]: casperlabs_types::MAX_LIST_LOCAL_ENTRIES
+pub fn list_local(cursor: Option<Key>, limit: u32) -> Vec<(Key, CLValue)> {
+    try_list_local(cursor, limit).unwrap_or_revert()
+}
+
+/// As [`list_local`], but returns any error instead of reverting.
+pub fn try_list_local(cursor: Option<Key>, limit: u32) -> Result<Vec<(Key, CLValue)>, ApiError> {
+    let (cursor_ptr, cursor_size, _bytes) = contract_api::to_ptr(cursor);
+
+    let (total_entries, result_size) = {
+        let mut total_entries = MaybeUninit::uninit();
+        let mut result_size = 0;
+        let ret = unsafe {
+            ext_ffi::list_local(
+                cursor_ptr,
+                cursor_size,
+                limit,
+                total_entries.as_mut_ptr(),
+                &mut result_size as *mut usize,
+            )
+        };
+        api_error::result_from(ret)?;
+        let total_entries = unsafe { total_entries.assume_init() };
+        (total_entries, result_size)
+    };
+    if total_entries == 0 {
+        return Ok(Vec::new());
+    }
+    let bytes = runtime::read_host_buffer(result_size)?;
+    Ok(bytesrepr::deserialize(bytes)?)
+}
+
 /// Stores the serialized bytes of an exported, non-mangled `extern "C"` function as a new contract
 /// under a [`URef`] generated by the host.
 pub fn store_function(name: &str, named_keys: BTreeMap<String, Key>) -> ContractRef {
@@ -138,10 +231,89 @@ pub fn store_function_at_hash(name: &str, named_keys: BTreeMap<String, Key>) ->
     ContractRef::Hash(addr)
 }
 
+/// Stores the serialized bytes of an exported, non-mangled `extern "C"` function as a new contract
+/// under a [`URef`] generated by the host, along with `abi` describing how to call it.
+pub fn store_function_with_abi(
+    name: &str,
+    named_keys: BTreeMap<String, Key>,
+    abi: ContractAbi,
+) -> ContractRef {
+    let (fn_ptr, fn_size, _bytes1) = contract_api::to_ptr(name);
+    let (keys_ptr, keys_size, _bytes2) = contract_api::to_ptr(named_keys);
+    let (abi_ptr, abi_size, _bytes3) = contract_api::to_ptr(abi);
+    let mut addr = [0u8; 32];
+    unsafe {
+        ext_ffi::store_function_with_abi(
+            fn_ptr,
+            fn_size,
+            keys_ptr,
+            keys_size,
+            abi_ptr,
+            abi_size,
+            addr.as_mut_ptr(),
+        );
+    }
+    ContractRef::URef(URef::new(addr, AccessRights::READ_ADD_WRITE))
+}
+
+/// Stores the serialized bytes of an exported, non-mangled `extern "C"` function as a new contract
+/// at an immutable address generated by the host, along with `abi` describing how to call it.
+pub fn store_function_at_hash_with_abi(
+    name: &str,
+    named_keys: BTreeMap<String, Key>,
+    abi: ContractAbi,
+) -> ContractRef {
+    let (fn_ptr, fn_size, _bytes1) = contract_api::to_ptr(name);
+    let (keys_ptr, keys_size, _bytes2) = contract_api::to_ptr(named_keys);
+    let (abi_ptr, abi_size, _bytes3) = contract_api::to_ptr(abi);
+    let mut addr = [0u8; 32];
+    unsafe {
+        ext_ffi::store_function_at_hash_with_abi(
+            fn_ptr,
+            fn_size,
+            keys_ptr,
+            keys_size,
+            abi_ptr,
+            abi_size,
+            addr.as_mut_ptr(),
+        );
+    }
+    ContractRef::Hash(addr)
+}
+
+/// Stores the current module as a new contract at an immutable address generated by the host.
+///
+/// Unlike [`store_function`], the contract keeps every function exported under the name of one of
+/// `abi`'s entry points, and is called with [`runtime::call_versioned_contract`], which checks
+/// each entry point's [`EntryPointAccess`](casperlabs_types::EntryPointAccess).
+pub fn store_versioned_contract(
+    named_keys: BTreeMap<String, Key>,
+    abi: ContractAbi,
+) -> ContractRef {
+    let (keys_ptr, keys_size, _bytes1) = contract_api::to_ptr(named_keys);
+    let (abi_ptr, abi_size, _bytes2) = contract_api::to_ptr(abi);
+    let mut addr = [0u8; 32];
+    unsafe {
+        ext_ffi::store_versioned_contract(
+            keys_ptr,
+            keys_size,
+            abi_ptr,
+            abi_size,
+            addr.as_mut_ptr(),
+        );
+    }
+    ContractRef::Hash(addr)
+}
+
 /// Returns a new unforgeable pointer, where the value is initialized to `init`.
 pub fn new_uref<T: CLTyped + ToBytes>(init: T) -> URef {
+    try_new_uref(init).unwrap_or_revert()
+}
+
+/// As [`new_uref`], but returns any error instead of reverting.
+pub fn try_new_uref<T: CLTyped + ToBytes>(init: T) -> Result<URef, ApiError> {
     let uref_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
-    let cl_value = CLValue::from_t(init).unwrap_or_revert();
+    let cl_value = CLValue::from_t(init)?;
     let (cl_value_ptr, cl_value_size, _cl_value_bytes) = contract_api::to_ptr(cl_value);
     let bytes = unsafe {
         ext_ffi::new_uref(uref_non_null_ptr.as_ptr(), cl_value_ptr, cl_value_size); // URef has `READ_ADD_WRITE`
@@ -151,5 +323,5 @@ pub fn new_uref<T: CLTyped + ToBytes>(init: T) -> URef {
             UREF_SERIALIZED_LENGTH,
         )
     };
-    bytesrepr::deserialize(bytes).unwrap_or_revert()
+    Ok(bytesrepr::deserialize(bytes)?)
 }
@@ -0,0 +1,396 @@
+//! Typed wrappers over global state which read lazily and cache writes.
+//!
+//! * [`Singleton`] holds a single value under a [`URef`].
+//! * [`StoredVec`] and [`StoredMap`] are collections living in the context-local partition of
+//!   global state, with every element stored under its own local key.  Only the elements which are
+//!   actually accessed are read, and only the modified ones are written back.
+//!
+//! Values are read at most once and modifications are held in memory until [`flush`] is called or
+//! the wrapper is dropped.  Note that [`runtime::ret`] and [`runtime::revert`] never return, so
+//! wrappers still alive at that point are not dropped; call `flush` explicitly before returning a
+//! value from a stored contract.
+//!
+//! Collections too large to process within a single deploy's gas limit can be walked in bounded
+//! batches via `iter_from`, with the cursor persisted between deploys using [`save_cursor`] and
+//! [`load_cursor`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! #![no_std]
+//!
+//! use casperlabs_contract::contract_api::stored::{StoredMap, StoredVec};
+//!
+//! #[no_mangle]
+//! pub extern "C" fn call() {
+//!     let mut log = StoredVec::<u64>::new("log");
+//!     let mut totals = StoredMap::<u8, u64>::new("totals");
+//!
+//!     log.push(5);
+//!     let total = totals.get(&1).copied().unwrap_or_default();
+//!     totals.insert(1, total + 5);
+//!     // Both collections are flushed when they go out of scope.
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! [`flush`]: StoredVec::flush
+//! [`runtime::ret`]: crate::contract_api::runtime::ret
+//! [`runtime::revert`]: crate::contract_api::runtime::revert
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::String,
+    vec::Vec,
+};
+use core::cmp;
+
+use casperlabs_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, CLTyped, Key, URef, MAX_LIST_LOCAL_ENTRIES,
+};
+
+use crate::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+/// Prefix of the context-local keys under which [`StoredVec`]s are stored.
+const VEC_KEY_PREFIX: &str = "vec:";
+/// Prefix of the context-local keys under which [`StoredMap`]s are stored.
+const MAP_KEY_PREFIX: &str = "map:";
+/// Prefix of the context-local keys under which iteration cursors are stored.
+const CURSOR_KEY_PREFIX: &str = "cursor:";
+
+/// A single value stored under a [`URef`].
+pub struct Singleton<T: CLTyped + ToBytes + FromBytes> {
+    uref: URef,
+    value: Option<T>,
+    loaded: bool,
+    dirty: bool,
+}
+
+impl<T: CLTyped + ToBytes + FromBytes> Singleton<T> {
+    /// Constructs a `Singleton` holding the value under `uref`.  Nothing is read until the value is
+    /// first accessed.
+    pub fn new(uref: URef) -> Self {
+        Singleton {
+            uref,
+            value: None,
+            loaded: false,
+            dirty: false,
+        }
+    }
+
+    /// Returns the underlying `URef`.
+    pub fn uref(&self) -> URef {
+        self.uref
+    }
+
+    /// Returns the value, reading it from global state on first access.
+    pub fn get(&mut self) -> Option<&T> {
+        if !self.loaded {
+            self.value = storage::read(self.uref).unwrap_or_revert_with(ApiError::Read);
+            self.loaded = true;
+        }
+        self.value.as_ref()
+    }
+
+    /// Replaces the value.  It is written to global state when flushed.
+    pub fn set(&mut self, value: T) {
+        self.value = Some(value);
+        self.loaded = true;
+        self.dirty = true;
+    }
+
+    /// Writes the value to global state if it has been modified.
+    pub fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        if let Some(value) = self.value.take() {
+            storage::write(self.uref, value);
+            // Re-read lazily rather than requiring `T: Clone`.
+            self.loaded = false;
+        }
+        self.dirty = false;
+    }
+}
+
+impl<T: CLTyped + ToBytes + FromBytes> Drop for Singleton<T> {
+    fn drop(&mut self) {
+        self.flush()
+    }
+}
+
+/// A growable sequence of values in the context-local partition of global state.
+///
+/// The length and each element are stored under separate local keys derived from the collection's
+/// name.
+pub struct StoredVec<T: CLTyped + ToBytes + FromBytes> {
+    name: String,
+    len: Option<u64>,
+    len_dirty: bool,
+    cache: BTreeMap<u64, T>,
+    dirty: BTreeSet<u64>,
+}
+
+impl<T: CLTyped + ToBytes + FromBytes> StoredVec<T> {
+    /// Constructs a `StoredVec` named `name` in the current context.  It is empty if nothing has
+    /// been stored under that name yet.
+    pub fn new(name: &str) -> Self {
+        StoredVec {
+            name: format!("{}{}", VEC_KEY_PREFIX, name),
+            len: None,
+            len_dirty: false,
+            cache: BTreeMap::new(),
+            dirty: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&mut self) -> u64 {
+        match self.len {
+            Some(len) => len,
+            None => {
+                let len = storage::read_local(&self.name)
+                    .unwrap_or_revert_with(ApiError::Read)
+                    .unwrap_or_default();
+                self.len = Some(len);
+                len
+            }
+        }
+    }
+
+    /// Returns `true` if there are no elements.
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of bounds.
+    pub fn get(&mut self, index: u64) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        let name = self.name.as_str();
+        let value = self.cache.entry(index).or_insert_with(|| {
+            storage::read_local(&(name, index))
+                .unwrap_or_revert_with(ApiError::Read)
+                .unwrap_or_revert_with(ApiError::ValueNotFound)
+        });
+        Some(value)
+    }
+
+    /// Replaces the element at `index`.
+    ///
+    /// Reverts with [`ApiError::InvalidArgument`] if `index` is out of bounds.
+    pub fn set(&mut self, index: u64, value: T) {
+        if index >= self.len() {
+            runtime::revert(ApiError::InvalidArgument);
+        }
+        self.cache.insert(index, value);
+        self.dirty.insert(index);
+    }
+
+    /// Appends an element.
+    pub fn push(&mut self, value: T) {
+        let index = self.len();
+        self.cache.insert(index, value);
+        self.dirty.insert(index);
+        self.len = Some(index + 1);
+        self.len_dirty = true;
+    }
+
+    /// Removes the last element and returns it, or `None` if empty.
+    ///
+    /// The removed element is left in global state, but is no longer reachable.
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        let index = len - 1;
+        self.get(index);
+        self.dirty.remove(&index);
+        self.len = Some(index);
+        self.len_dirty = true;
+        self.cache.remove(&index)
+    }
+
+    /// Returns up to `limit` elements starting at index `cursor`.
+    pub fn iter_from(&mut self, cursor: u64, limit: u64) -> Page<u64, T>
+    where
+        T: Clone,
+    {
+        let len = self.len();
+        let end = cmp::min(cursor.saturating_add(limit), len);
+        let mut items = Vec::new();
+        for index in cursor..end {
+            items.extend(self.get(index).cloned());
+        }
+        let next = if end < len { Some(end) } else { None };
+        Page { items, next }
+    }
+
+    /// Writes the modified elements and the length to global state.
+    pub fn flush(&mut self) {
+        for index in self.dirty.iter() {
+            if let Some(value) = self.cache.remove(index) {
+                storage::write_local((self.name.as_str(), *index), value);
+            }
+        }
+        self.dirty.clear();
+        if let (true, Some(len)) = (self.len_dirty, self.len) {
+            storage::write_local(self.name.as_str(), len);
+            self.len_dirty = false;
+        }
+    }
+}
+
+impl<T: CLTyped + ToBytes + FromBytes> Drop for StoredVec<T> {
+    fn drop(&mut self) {
+        self.flush()
+    }
+}
+
+/// A map from keys to values in the context-local partition of global state.
+///
+/// Each entry is stored under a separate local key derived from the map's name and the entry's key.
+/// Since local keys are hashed, entries also record the map's name and their key so that
+/// [`iter_from`](StoredMap::iter_from) can recognize them when scanning the local state.
+pub struct StoredMap<K, V>
+where
+    K: CLTyped + ToBytes + FromBytes + Ord + Clone,
+    V: CLTyped + ToBytes + FromBytes,
+{
+    name: String,
+    cache: BTreeMap<K, Option<V>>,
+    dirty: BTreeSet<K>,
+}
+
+/// A stored map entry: the map's name, the key and the value, or `None` if it has been removed.
+type MapEntry<K, V> = (String, K, Option<V>);
+
+impl<K, V> StoredMap<K, V>
+where
+    K: CLTyped + ToBytes + FromBytes + Ord + Clone,
+    V: CLTyped + ToBytes + FromBytes,
+{
+    /// Constructs a `StoredMap` named `name` in the current context.  It is empty if nothing has
+    /// been stored under that name yet.
+    pub fn new(name: &str) -> Self {
+        StoredMap {
+            name: format!("{}{}", MAP_KEY_PREFIX, name),
+            cache: BTreeMap::new(),
+            dirty: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the value under `key`, reading it from global state on first access.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let name = self.name.as_str();
+        self.cache
+            .entry(key.clone())
+            .or_insert_with(|| {
+                storage::read_local::<_, MapEntry<K, V>>(&(name, key.clone()))
+                    .unwrap_or_revert_with(ApiError::Read)
+                    .and_then(|(_, _, value)| value)
+            })
+            .as_ref()
+    }
+
+    /// Returns `true` if there is a value under `key`.
+    pub fn contains_key(&mut self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts `value` under `key`, replacing any existing value.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.dirty.insert(key.clone());
+        self.cache.insert(key, Some(value));
+    }
+
+    /// Removes the value under `key`.
+    pub fn remove(&mut self, key: &K) {
+        self.dirty.insert(key.clone());
+        self.cache.insert(key.clone(), None);
+    }
+
+    /// Returns the entries found among the next `limit` entries of the context-local state after
+    /// `cursor`, flushing any pending modifications first.
+    ///
+    /// The local state is shared by every collection in the context, so a page may hold fewer than
+    /// `limit` entries of this map, or none at all, while more remain.  Iteration is complete once
+    /// the returned page has no `next` cursor.  Entries are returned in an unspecified order, and
+    /// at most [`MAX_LIST_LOCAL_ENTRIES`] are scanned per call.
+    pub fn iter_from(&mut self, cursor: Option<Key>, limit: u32) -> Page<Key, (K, V)> {
+        self.flush();
+
+        let entries = storage::list_local(cursor, limit);
+        let next = if entries.len() as u32 == cmp::min(limit, MAX_LIST_LOCAL_ENTRIES) {
+            entries.last().map(|(key, _)| *key)
+        } else {
+            None
+        };
+
+        let entry_type = MapEntry::<K, V>::cl_type();
+        let items = entries
+            .into_iter()
+            .filter(|(_, cl_value)| *cl_value.cl_type() == entry_type)
+            .filter_map(|(_, cl_value)| {
+                let (name, key, value): MapEntry<K, V> = cl_value.into_t().unwrap_or_revert();
+                if name == self.name {
+                    value.map(|value| (key, value))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Page { items, next }
+    }
+
+    /// Writes the modified entries to global state.
+    pub fn flush(&mut self) {
+        for key in self.dirty.iter() {
+            if let Some(value) = self.cache.remove(key) {
+                let entry: MapEntry<K, V> = (self.name.clone(), key.clone(), value);
+                storage::write_local((self.name.as_str(), key.clone()), entry);
+            }
+        }
+        self.dirty.clear();
+    }
+}
+
+impl<K, V> Drop for StoredMap<K, V>
+where
+    K: CLTyped + ToBytes + FromBytes + Ord + Clone,
+    V: CLTyped + ToBytes + FromBytes,
+{
+    fn drop(&mut self) {
+        self.flush()
+    }
+}
+
+/// A bounded batch of items returned by `iter_from`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Page<C, T> {
+    /// The items in this batch.
+    pub items: Vec<T>,
+    /// The cursor to continue from, or `None` if the iteration is complete.
+    pub next: Option<C>,
+}
+
+/// Persists `cursor` under `name` in the context-local partition of global state, so that an
+/// iteration can be continued by a later deploy.
+pub fn save_cursor<C: CLTyped + ToBytes>(name: &str, cursor: Option<C>) {
+    storage::write_local(format!("{}{}", CURSOR_KEY_PREFIX, name), cursor);
+}
+
+/// Returns the cursor persisted under `name` by [`save_cursor`], or `None` if there is none.
+pub fn load_cursor<C: CLTyped + FromBytes>(name: &str) -> Option<C> {
+    storage::read_local::<_, Option<C>>(&format!("{}{}", CURSOR_KEY_PREFIX, name))
+        .unwrap_or_revert_with(ApiError::Read)
+        .and_then(|cursor| cursor)
+}
@@ -19,35 +19,36 @@ pub const MINT_NAME: &str = "mint";
 /// Name of the reference to the Proof of Stake contract in the named keys.
 pub const POS_NAME: &str = "pos";
 
-fn get_system_contract(system_contract: SystemContractType) -> ContractRef {
+fn get_system_contract(system_contract: SystemContractType) -> Result<ContractRef, ApiError> {
     let system_contract_index = system_contract.into();
     let uref: URef = {
-        let result = {
-            let mut uref_data_raw = [0u8; UREF_SERIALIZED_LENGTH];
-            let value = unsafe {
-                ext_ffi::get_system_contract(
-                    system_contract_index,
-                    uref_data_raw.as_mut_ptr(),
-                    uref_data_raw.len(),
-                )
-            };
-            api_error::result_from(value).map(|_| uref_data_raw)
+        let mut uref_data_raw = [0u8; UREF_SERIALIZED_LENGTH];
+        let value = unsafe {
+            ext_ffi::get_system_contract(
+                system_contract_index,
+                uref_data_raw.as_mut_ptr(),
+                uref_data_raw.len(),
+            )
         };
-        // Revert for any possible error that happened on host side
-        let uref_bytes = result.unwrap_or_else(|e| runtime::revert(e));
+        api_error::result_from(value)?;
         // Deserializes a valid URef passed from the host side
-        bytesrepr::deserialize(uref_bytes.to_vec()).unwrap_or_revert()
+        bytesrepr::deserialize(uref_data_raw.to_vec())?
     };
     if uref.access_rights().is_none() {
-        runtime::revert(ApiError::NoAccessRights);
+        return Err(ApiError::NoAccessRights);
     }
-    ContractRef::URef(uref)
+    Ok(ContractRef::URef(uref))
 }
 
 /// Returns a read-only pointer to the Mint contract.
 ///
 /// Any failure will trigger [`revert`](runtime::revert) with an appropriate [`ApiError`].
 pub fn get_mint() -> ContractRef {
+    get_system_contract(SystemContractType::Mint).unwrap_or_revert()
+}
+
+/// As [`get_mint`], but returns any error instead of reverting.
+pub fn try_get_mint() -> Result<ContractRef, ApiError> {
     get_system_contract(SystemContractType::Mint)
 }
 
@@ -55,6 +56,11 @@ pub fn get_mint() -> ContractRef {
 ///
 /// Any failure will trigger [`revert`](runtime::revert) with an appropriate [`ApiError`].
 pub fn get_proof_of_stake() -> ContractRef {
+    get_system_contract(SystemContractType::ProofOfStake).unwrap_or_revert()
+}
+
+/// As [`get_proof_of_stake`], but returns any error instead of reverting.
+pub fn try_get_proof_of_stake() -> Result<ContractRef, ApiError> {
     get_system_contract(SystemContractType::ProofOfStake)
 }
 
@@ -62,29 +68,67 @@ pub fn get_proof_of_stake() -> ContractRef {
 ///
 /// Any failure will trigger [`revert`](runtime::revert) with an appropriate [`ApiError`].
 pub fn get_standard_payment() -> ContractRef {
+    get_system_contract(SystemContractType::StandardPayment).unwrap_or_revert()
+}
+
+/// As [`get_standard_payment`], but returns any error instead of reverting.
+pub fn try_get_standard_payment() -> Result<ContractRef, ApiError> {
     get_system_contract(SystemContractType::StandardPayment)
 }
 
+/// Returns a read-only pointer to the Key Recovery contract.
+///
+/// Any failure will trigger [`revert`](runtime::revert) with an appropriate [`ApiError`].
+pub fn get_key_recovery() -> ContractRef {
+    get_system_contract(SystemContractType::KeyRecovery).unwrap_or_revert()
+}
+
+/// As [`get_key_recovery`], but returns any error instead of reverting.
+pub fn try_get_key_recovery() -> Result<ContractRef, ApiError> {
+    get_system_contract(SystemContractType::KeyRecovery)
+}
+
 /// Creates a new empty purse and returns its [`URef`].
 pub fn create_purse() -> URef {
+    try_create_purse().unwrap_or_revert()
+}
+
+/// As [`create_purse`], but returns [`ApiError::PurseNotCreated`] instead of reverting if the
+/// purse couldn't be created.
+pub fn try_create_purse() -> Result<URef, ApiError> {
     let purse_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
     unsafe {
         let ret = ext_ffi::create_purse(purse_non_null_ptr.as_ptr(), UREF_SERIALIZED_LENGTH);
+        let bytes = Vec::from_raw_parts(
+            purse_non_null_ptr.as_ptr(),
+            UREF_SERIALIZED_LENGTH,
+            UREF_SERIALIZED_LENGTH,
+        );
         if ret == 0 {
-            let bytes = Vec::from_raw_parts(
-                purse_non_null_ptr.as_ptr(),
-                UREF_SERIALIZED_LENGTH,
-                UREF_SERIALIZED_LENGTH,
-            );
-            bytesrepr::deserialize(bytes).unwrap_or_revert()
+            Ok(bytesrepr::deserialize(bytes)?)
         } else {
-            runtime::revert(ApiError::PurseNotCreated)
+            Err(ApiError::PurseNotCreated)
         }
     }
 }
 
+/// Burns `amount` of motes from `purse`, removing them from the total supply.
+///
+/// `purse` must be writeable by the caller; otherwise [`ApiError::InvalidPurse`] is returned.
+pub fn burn(purse: URef, amount: U512) -> Result<(), ApiError> {
+    let (purse_ptr, purse_size, _bytes1) = contract_api::to_ptr(purse);
+    let (amount_ptr, amount_size, _bytes2) = contract_api::to_ptr(amount);
+    let result = unsafe { ext_ffi::burn(purse_ptr, purse_size, amount_ptr, amount_size) };
+    api_error::result_from(result)
+}
+
 /// Returns the balance in motes of the given purse.
 pub fn get_balance(purse: URef) -> Option<U512> {
+    try_get_balance(purse).unwrap_or_revert()
+}
+
+/// As [`get_balance`], but returns any error instead of reverting.
+pub fn try_get_balance(purse: URef) -> Result<Option<U512>, ApiError> {
     let (purse_ptr, purse_size, _bytes) = contract_api::to_ptr(purse);
 
     let value_size = {
@@ -92,13 +136,61 @@ pub fn get_balance(purse: URef) -> Option<U512> {
         let ret = unsafe { ext_ffi::get_balance(purse_ptr, purse_size, output_size.as_mut_ptr()) };
         match api_error::result_from(ret) {
             Ok(_) => unsafe { output_size.assume_init() },
-            Err(ApiError::InvalidPurse) => return None,
-            Err(error) => runtime::revert(error),
+            Err(ApiError::InvalidPurse) => return Ok(None),
+            Err(error) => return Err(error),
         }
     };
-    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
-    let value: U512 = bytesrepr::deserialize(value_bytes).unwrap_or_revert();
-    Some(value)
+    let value_bytes = runtime::read_host_buffer(value_size)?;
+    let value: U512 = bytesrepr::deserialize(value_bytes)?;
+    Ok(Some(value))
+}
+
+/// Returns the balance in motes of the given purse, which only needs to be held with READ rights.
+///
+/// Reverts with [`ApiError::Mint`] if `purse` lacks READ rights.
+pub fn get_balance_of(purse: URef) -> Option<U512> {
+    try_get_balance_of(purse).unwrap_or_revert()
+}
+
+/// As [`get_balance_of`], but returns any error instead of reverting.
+pub fn try_get_balance_of(purse: URef) -> Result<Option<U512>, ApiError> {
+    let (purse_ptr, purse_size, _bytes) = contract_api::to_ptr(purse);
+
+    let value_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret =
+            unsafe { ext_ffi::get_balance_of(purse_ptr, purse_size, output_size.as_mut_ptr()) };
+        match api_error::result_from(ret) {
+            Ok(_) => unsafe { output_size.assume_init() },
+            Err(ApiError::InvalidPurse) => return Ok(None),
+            Err(error) => return Err(error),
+        }
+    };
+    let value_bytes = runtime::read_host_buffer(value_size)?;
+    let value: U512 = bytesrepr::deserialize(value_bytes)?;
+    Ok(Some(value))
+}
+
+/// Returns the main purse of the account the deploy runs as and the fewest motes which may be left
+/// in it, or `None` unless session code is running and a minimum is configured.
+///
+/// Only the mint may call this; anyone else gets [`ApiError::PermissionDenied`].
+#[doc(hidden)]
+pub fn get_min_main_purse_balance() -> Option<(URef, U512)> {
+    try_get_min_main_purse_balance().unwrap_or_revert()
+}
+
+/// As [`get_min_main_purse_balance`], but returns any error instead of reverting.
+#[doc(hidden)]
+pub fn try_get_min_main_purse_balance() -> Result<Option<(URef, U512)>, ApiError> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_min_main_purse_balance(output_size.as_mut_ptr()) };
+        api_error::result_from(ret)?;
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = runtime::read_host_buffer(output_size)?;
+    Ok(bytesrepr::deserialize(bytes)?)
 }
 
 /// Transfers `amount` of motes from the default purse of the account to `target`
@@ -160,3 +252,49 @@ pub fn transfer_from_purse_to_purse(
         Err(ApiError::Transfer)
     }
 }
+
+/// Applies each `(source, target, amount)` transfer in order within a single call to the mint.
+///
+/// The transfers are all-or-nothing: if any of them fails, none of the purses are changed and
+/// [`ApiError::Transfer`] is returned.
+pub fn batch_transfer(transfers: Vec<(URef, URef, U512)>) -> Result<(), ApiError> {
+    let (transfers_ptr, transfers_size, _bytes) = contract_api::to_ptr(transfers);
+    let result = unsafe { ext_ffi::batch_transfer(transfers_ptr, transfers_size) };
+    api_error::result_from(result).map_err(|_| ApiError::Transfer)
+}
+
+/// Transfers `amount` of motes from `source` purse to `target` purse, as per
+/// [`transfer_from_purse_to_purse`].  On success, a [`TransferReceipt`] recording the deploy hash,
+/// source, target and amount is stored in global state and a read-only [`URef`] to it is returned.
+///
+/// The receipt can't be modified, so the returned `URef` can be passed to other contracts or saved
+/// under a named key as proof that the payment was made.
+///
+/// [`TransferReceipt`]: casperlabs_types::TransferReceipt
+pub fn transfer_with_proof(source: URef, target: URef, amount: U512) -> Result<URef, ApiError> {
+    let (source_ptr, source_size, _bytes1) = contract_api::to_ptr(source);
+    let (target_ptr, target_size, _bytes2) = contract_api::to_ptr(target);
+    let (amount_ptr, amount_size, _bytes3) = contract_api::to_ptr(amount);
+    let receipt_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
+    let result = unsafe {
+        ext_ffi::transfer_from_purse_to_purse_with_proof(
+            source_ptr,
+            source_size,
+            target_ptr,
+            target_size,
+            amount_ptr,
+            amount_size,
+            receipt_non_null_ptr.as_ptr(),
+            UREF_SERIALIZED_LENGTH,
+        )
+    };
+    let bytes = unsafe {
+        Vec::from_raw_parts(
+            receipt_non_null_ptr.as_ptr(),
+            UREF_SERIALIZED_LENGTH,
+            UREF_SERIALIZED_LENGTH,
+        )
+    };
+    api_error::result_from(result)?;
+    Ok(bytesrepr::deserialize(bytes)?)
+}
@@ -0,0 +1,60 @@
+//! Functions for calling fungible-token contracts.
+//!
+//! A token contract following this standard is a single stored entry point which takes the name of
+//! the method to run as its first argument, followed by that method's arguments.  Balances and
+//! amounts are [`U512`]s and holders are identified by their [`PublicKey`].  The caller of the
+//! token contract is taken to be the sender, owner or spender as appropriate.
+//!
+//! The reference implementation is the `erc20-smart-contract` example in the contracts tree.
+//!
+//! A method that fails (e.g. a transfer exceeding the sender's balance) reverts, so the helpers
+//! below only return on success.
+
+use casperlabs_types::{account::PublicKey, ContractRef, U512};
+
+use crate::contract_api::runtime;
+
+/// Method name for moving tokens from the caller to a recipient.
+pub const TRANSFER: &str = "transfer";
+/// Method name for moving tokens from an owner to a recipient using the caller's allowance.
+pub const TRANSFER_FROM: &str = "transfer_from";
+/// Method name for setting the amount a spender may transfer on the caller's behalf.
+pub const APPROVE: &str = "approve";
+/// Method name for querying the amount a spender may transfer on an owner's behalf.
+pub const ALLOWANCE: &str = "allowance";
+/// Method name for querying the balance of a holder.
+pub const BALANCE_OF: &str = "balance_of";
+/// Method name for querying the total amount of tokens in circulation.
+pub const TOTAL_SUPPLY: &str = "total_supply";
+
+/// Transfers `amount` tokens of `token` from the caller to `recipient`.
+pub fn transfer(token: ContractRef, recipient: PublicKey, amount: U512) {
+    runtime::call_contract(token, (TRANSFER, recipient, amount))
+}
+
+/// Transfers `amount` tokens of `token` from `owner` to `recipient`, deducting `amount` from the
+/// caller's allowance.
+pub fn transfer_from(token: ContractRef, owner: PublicKey, recipient: PublicKey, amount: U512) {
+    runtime::call_contract(token, (TRANSFER_FROM, owner, recipient, amount))
+}
+
+/// Allows `spender` to transfer up to `amount` tokens of `token` on the caller's behalf, replacing
+/// any previous allowance.
+pub fn approve(token: ContractRef, spender: PublicKey, amount: U512) {
+    runtime::call_contract(token, (APPROVE, spender, amount))
+}
+
+/// Returns the amount of tokens of `token` which `spender` may transfer on `owner`'s behalf.
+pub fn allowance(token: ContractRef, owner: PublicKey, spender: PublicKey) -> U512 {
+    runtime::call_contract(token, (ALLOWANCE, owner, spender))
+}
+
+/// Returns the balance of `holder` in `token`.
+pub fn balance_of(token: ContractRef, holder: PublicKey) -> U512 {
+    runtime::call_contract(token, (BALANCE_OF, holder))
+}
+
+/// Returns the total supply of `token`.
+pub fn total_supply(token: ContractRef) -> U512 {
+    runtime::call_contract(token, (TOTAL_SUPPLY,))
+}
@@ -4,6 +4,13 @@
 //! high-level bindings suitable for writing smart contracts.
 extern "C" {
     pub fn read_value(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32;
+    pub fn read_value_typed(
+        key_ptr: *const u8,
+        key_size: usize,
+        cl_type_ptr: *const u8,
+        cl_type_size: usize,
+        output_size: *mut usize,
+    ) -> i32;
     pub fn read_value_local(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32;
     pub fn write(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
     pub fn write_local(
@@ -29,9 +36,52 @@ extern "C" {
         named_keys_size: usize,
         hash_ptr: *const u8,
     );
+    pub fn store_function_with_abi(
+        function_name_ptr: *const u8,
+        function_name_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        abi_ptr: *const u8,
+        abi_size: usize,
+        uref_addr_ptr: *const u8,
+    );
+    pub fn store_function_at_hash_with_abi(
+        function_name_ptr: *const u8,
+        function_name_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        abi_ptr: *const u8,
+        abi_size: usize,
+        hash_ptr: *const u8,
+    );
+    pub fn get_contract_abi(key_ptr: *const u8, key_size: usize, result_size: *mut usize) -> i32;
+    pub fn store_versioned_contract(
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        abi_ptr: *const u8,
+        abi_size: usize,
+        hash_ptr: *const u8,
+    );
+    pub fn call_versioned_contract(
+        key_ptr: *const u8,
+        key_size: usize,
+        entry_point_ptr: *const u8,
+        entry_point_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
     pub fn load_named_keys(total_keys: *mut usize, result_size: *mut usize) -> i32;
+    pub fn list_purses(total_purses: *mut usize, result_size: *mut usize) -> i32;
     pub fn get_arg(index: usize, dest_ptr: *mut u8, dest_size: usize) -> i32;
     pub fn get_arg_size(index: usize, dest_size: *mut usize) -> i32;
+    pub fn get_named_arg(
+        name_ptr: *const u8,
+        name_size: usize,
+        dest_ptr: *mut u8,
+        dest_size: usize,
+    ) -> i32;
+    pub fn get_named_arg_size(name_ptr: *const u8, name_size: usize, dest_size: *mut usize) -> i32;
     pub fn ret(value_ptr: *const u8, value_size: usize) -> !;
     pub fn call_contract(
         key_ptr: *const u8,
@@ -40,6 +90,16 @@ extern "C" {
         args_size: usize,
         result_size: *mut usize,
     ) -> i32;
+    pub fn schedule_call(
+        key_ptr: *const u8,
+        key_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        blocktime_ptr: *const u8,
+        blocktime_size: usize,
+        gas_budget_ptr: *const u8,
+        gas_budget_size: usize,
+    ) -> i32;
     pub fn get_key(
         name_ptr: *const u8,
         name_size: usize,
@@ -52,6 +112,12 @@ extern "C" {
     pub fn remove_key(name_ptr: *const u8, name_size: usize);
     pub fn revert(status: u32) -> !;
     pub fn is_valid_uref(uref_ptr: *const u8, uref_size: usize) -> i32;
+    pub fn attenuate_uref(
+        uref_ptr: *const u8,
+        uref_size: usize,
+        access_rights: u32,
+        dest_ptr: *mut u8,
+    ) -> i32;
     pub fn add_associated_key(
         public_key_ptr: *const u8,
         public_key_size: usize,
@@ -64,8 +130,57 @@ extern "C" {
         weight: i32,
     ) -> i32;
     pub fn set_action_threshold(permission_level: u32, threshold: i32) -> i32;
+    pub fn update_associated_keys(updates_ptr: *const u8, updates_size: usize) -> i32;
+    pub fn can_manage_keys() -> i32;
+    pub fn rotate_associated_key(
+        account_ptr: *const u8,
+        account_size: usize,
+        old_key_ptr: *const u8,
+        old_key_size: usize,
+        new_key_ptr: *const u8,
+        new_key_size: usize,
+    ) -> i32;
+    pub fn set_contract_install_permission(
+        account_ptr: *const u8,
+        account_size: usize,
+        allowed: u32,
+    ) -> i32;
     pub fn get_caller(output_size: *mut usize) -> i32;
+    pub fn get_immediate_caller(output_size: *mut usize) -> i32;
+    pub fn get_call_stack(output_size: *mut usize) -> i32;
+    pub fn get_account_info(
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+        output_size: *mut usize,
+    ) -> i32;
+    pub fn get_chain_config(key: u32, output_size: *mut usize) -> i32;
     pub fn get_blocktime(dest_ptr: *const u8);
+    pub fn get_block_height(dest_ptr: *const u8);
+    pub fn random_bytes(dest_ptr: *mut u8);
+    pub fn blake2b(in_ptr: *const u8, in_size: usize, out_ptr: *mut u8, out_size: usize) -> i32;
+    pub fn verify_ed25519(
+        signature_ptr: *const u8,
+        signature_size: usize,
+        message_ptr: *const u8,
+        message_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> i32;
+    pub fn verify_secp256k1(
+        signature_ptr: *const u8,
+        signature_size: usize,
+        message_ptr: *const u8,
+        message_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> i32;
+    pub fn bignum_op(
+        op: u32,
+        width: usize,
+        lhs_ptr: *const u8,
+        rhs_ptr: *const u8,
+        out_ptr: *mut u8,
+    ) -> i32;
     pub fn create_purse(purse_ptr: *const u8, purse_size: usize) -> i32;
     pub fn transfer_to_account(
         target_ptr: *const u8,
@@ -89,7 +204,26 @@ extern "C" {
         amount_ptr: *const u8,
         amount_size: usize,
     ) -> i32;
+    pub fn batch_transfer(transfers_ptr: *const u8, transfers_size: usize) -> i32;
+    pub fn transfer_from_purse_to_purse_with_proof(
+        source_ptr: *const u8,
+        source_size: usize,
+        target_ptr: *const u8,
+        target_size: usize,
+        amount_ptr: *const u8,
+        amount_size: usize,
+        receipt_ptr: *const u8,
+        receipt_size: usize,
+    ) -> i32;
+    pub fn burn(
+        purse_ptr: *const u8,
+        purse_size: usize,
+        amount_ptr: *const u8,
+        amount_size: usize,
+    ) -> i32;
     pub fn get_balance(purse_ptr: *const u8, purse_size: usize, result_size: *mut usize) -> i32;
+    pub fn get_balance_of(purse_ptr: *const u8, purse_size: usize, result_size: *mut usize) -> i32;
+    pub fn get_min_main_purse_balance(output_size: *mut usize) -> i32;
     pub fn get_phase(dest_ptr: *mut u8);
     pub fn upgrade_contract_at_uref(
         name_ptr: *const u8,
@@ -104,6 +238,15 @@ extern "C" {
     ) -> i32;
     pub fn get_main_purse(dest_ptr: *mut u8);
     pub fn read_host_buffer(dest_ptr: *mut u8, dest_size: usize, bytes_written: *mut usize) -> i32;
+    pub fn guard_reentrancy();
+    pub fn delete(key_ptr: *const u8, key_size: usize);
+    pub fn list_local(
+        cursor_ptr: *const u8,
+        cursor_size: usize,
+        limit: u32,
+        total_entries: *mut usize,
+        result_size: *mut usize,
+    ) -> i32;
     #[cfg(feature = "test-support")]
     pub fn print(text_ptr: *const u8, text_size: usize);
 }
@@ -3,20 +3,17 @@ import {getKey, callContract, putKey} from "../../../../contract-as/assembly";
 import {CLValue} from "../../../../contract-as/assembly/clvalue";
 import {Key} from "../../../../contract-as/assembly/key";
 import {fromBytesStringList} from "../../../../contract-as/assembly/bytesrepr";
-import {Option} from "../../../../contract-as/assembly/option";
 
 const MAIL_FEED_KEY = "mail_feed";
 const MAILING_KEY = "mailing";
 const PUB_METHOD = "pub";
+const READ_METHOD = "read";
 const SUB_METHOD = "sub";
 
 enum UserError {
-    GetKeyNameURef = 0,
-    BadSubKey = 1,
-    GetMessagesURef = 2,
-    FindMessagesURef = 3,
-    NoMessages = 4,
-    NoSubKey = 5,
+    NoMessages = 0,
+    GetMessages = 1,
+    CreateMailFeed = 2,
 }
 
 export function call(): void {
@@ -27,60 +24,37 @@ export function call(): void {
     }
 
     let name = "CasperLabs";
-    let maybeSubKeyBytes = callContract(<Key>contractKey, [
+    callContract(<Key>contractKey, [
         CLValue.fromString(SUB_METHOD),
         CLValue.fromString(name),
     ]);
 
-    const maybeSubKey = Option.fromBytes(<Uint8Array>maybeSubKeyBytes);
-    if (maybeSubKey === null || maybeSubKey.isNone()) {
-        Error.fromUserError(<u16>UserError.NoSubKey).revert();
-        return;
-    }
-
-    let subKeyBytes = <Uint8Array>maybeSubKey.unwrap();
-    let subKeyResult = Key.fromBytes(subKeyBytes);
-    if (subKeyResult.hasError()) {
-        Error.fromUserError(<u16>UserError.NoSubKey).revert();
-        return;
-    }
-    let subKey = subKeyResult.unwrap();
-    putKey(MAIL_FEED_KEY, subKey);
-
-    let maybeMailFeedKey = getKey(MAIL_FEED_KEY);
-    if (maybeMailFeedKey === null) {
-        Error.fromUserError(<u16>UserError.GetKeyNameURef).revert();
-        return;
-    }
-
-    const mailFeedKey = <Key>maybeMailFeedKey;
-
-    if (subKey != mailFeedKey) {
-        Error.fromUserError(<u16>UserError.BadSubKey).revert();
-        return;
-    }
-
     let message = "Hello, World!";
     callContract(<Key>contractKey, [
         CLValue.fromString(PUB_METHOD),
         CLValue.fromString(message),
     ]);
 
-    const maybeMessagesBytes = subKey.read();
-    if (maybeMessagesBytes === null) {
-        Error.fromUserError(<u16>UserError.GetMessagesURef)
-        return;
-    }
-
-    const messageBytes = <Uint8Array>maybeMessagesBytes;
-    const messagesResult = fromBytesStringList(messageBytes);
+    const messagesBytes = callContract(<Key>contractKey, [
+        CLValue.fromString(READ_METHOD),
+        CLValue.fromString(name),
+    ]);
+    const messagesResult = fromBytesStringList(messagesBytes);
     if (messagesResult.hasError()) {
-        Error.fromUserError(<u16>UserError.FindMessagesURef).revert();
+        Error.fromUserError(<u16>UserError.GetMessages).revert();
         return;
     }
     let messages = messagesResult.value;
 
     if (messages.length == 0) {
         Error.fromUserError(<u16>UserError.NoMessages).revert();
+        return;
+    }
+
+    const mailFeedKey = Key.create(CLValue.fromStringList(messages));
+    if (mailFeedKey === null) {
+        Error.fromUserError(<u16>UserError.CreateMailFeed).revert();
+        return;
     }
+    putKey(MAIL_FEED_KEY, <Key>mailFeedKey);
 }
@@ -1,15 +1,13 @@
 //@ts-nocheck
 import {Error, ErrorCode} from "../../../../contract-as/assembly/error";
-import {putKey, getKey, hasKey, getArg, storeFunctionAtHash, ret} from "../../../../contract-as/assembly";
-import {Key} from "../../../../contract-as/assembly/key";
-import {CLValue, CLTypeTag} from "../../../../contract-as/assembly/clvalue";
-import {toBytesString, toBytesMap, toBytesPair} from "../../../../contract-as/assembly/bytesrepr";
-import {fromBytesString, fromBytesI32, fromBytesU32, fromBytesStringList} from "../../../../contract-as/assembly/bytesrepr";
-import {U512} from "../../../../contract-as/assembly/bignum";
-import {Option} from "../../../../contract-as/assembly/option";
+import {putKey, getArg, storeFunctionAtHash, ret} from "../../../../contract-as/assembly";
+import {CLValue} from "../../../../contract-as/assembly/clvalue";
+import {toBytesString, toBytesMap} from "../../../../contract-as/assembly/bytesrepr";
+import {fromBytesString, fromBytesStringList} from "../../../../contract-as/assembly/bytesrepr";
+import {readLocal, writeLocal} from "../../../../contract-as/assembly/local";
 import {arrayToTyped} from "../../../../contract-as/assembly/utils";
 
-const LIST_KEY = "list";
+const SUBSCRIBERS_KEY = "subscribers";
 const MAILING_KEY = "mailing";
 const MAILING_LIST_EXT = "mailing_list_ext";
 
@@ -20,90 +18,62 @@ enum Arg {
 
 enum UserError {
     UnknownMethodName = 0,
+    NotSubscribed = 1,
 }
 
-function getListKey(name: String): Key {
-    const key = getKey(name);
-    if (key === null) {
-        Error.fromErrorCode(ErrorCode.GetKey).revert();
-        unreachable();
-    }
-    if (!(<Key>key).isURef()) {
-        Error.fromErrorCode(ErrorCode.UnexpectedKeyVariant).revert();
-        unreachable();
-    }
-    return <Key>key;
+function localKey(name: String): Uint8Array {
+    return arrayToTyped(toBytesString(name));
 }
 
-function updateList(name: String): void {
-    const listKey = getListKey(LIST_KEY);
-    const listBytes = listKey.read();
-    if (listBytes === null) {
-        Error.fromErrorCode(ErrorCode.Read).revert();
-        return;
+function readStringList(name: String): Array<String> | null {
+    const bytes = readLocal(localKey(name));
+    if (bytes === null) {
+        return null;
     }
-
-    let lstResult = fromBytesStringList(<Uint8Array>listBytes);
-    if (lstResult.hasError()) {
+    let listResult = fromBytesStringList(<Uint8Array>bytes);
+    if (listResult.hasError()) {
         Error.fromErrorCode(ErrorCode.ValueNotFound).revert();
-        return;
+        return null;
     }
-
-    let lst = lstResult.value;
-    lst.push(name);
-
-    listKey.write(CLValue.fromStringList(lst));
+    return listResult.value;
 }
 
-function sub(name: String): Key | null {
-    if (hasKey(name)) {
-        const initMessage = CLValue.fromStringList(["Hello again!"]);
-        return Key.create(initMessage);
-    }
-    else {
-        const initMessage = CLValue.fromStringList(["Welcome!"]);
-        let newKey = Key.create(initMessage);
-        if (newKey === null) {
-            return null;
-        }
-        putKey(name, newKey);
-        updateList(name);
-        return newKey;
+// Local keys are hashed, so the subscriber names are kept alongside the message lists to allow
+// iterating over them.
+function subscribers(): Array<String> {
+    let subscribers = readStringList(SUBSCRIBERS_KEY);
+    if (subscribers === null) {
+        return new Array<String>(0);
     }
-
+    return <Array<String>>subscribers;
 }
 
-function publish(msg: String): void {
-    const listKey = getListKey(LIST_KEY);
-    const listBytes = listKey.read();
-    if (listBytes === null) {
-        Error.fromErrorCode(ErrorCode.Read).revert();
-        return;
+function messages(name: String): Array<String> {
+    let messages = readStringList(name);
+    if (messages === null) {
+        Error.fromUserError(<u16>UserError.NotSubscribed).revert();
+        return <Array<String>>unreachable();
     }
+    return <Array<String>>messages;
+}
 
-    let lstResult = fromBytesStringList(<Uint8Array>listBytes);
-    if (lstResult.hasError()) {
-        Error.fromErrorCode(ErrorCode.ValueNotFound).revert();
+function sub(name: String): void {
+    let lst = subscribers();
+    if (lst.includes(name)) {
         return;
     }
-    let lst = lstResult.value;
+    writeLocal(localKey(name), CLValue.fromStringList(["Welcome!"]));
+    lst.push(name);
+    writeLocal(localKey(SUBSCRIBERS_KEY), CLValue.fromStringList(lst));
+}
 
+function publish(msg: String): void {
+    let lst = subscribers();
     for (let i = 0; i < lst.length; i++) {
         const name = lst[i];
-        let key = getListKey(name);
-        let messagesBytes = key.read();
-        if (messagesBytes === null) {
-            Error.fromErrorCode(ErrorCode.Read).revert();
-            return;
-        }
-        let messagesResult = fromBytesStringList(messagesBytes);
-        if (messagesResult.hasError()) {
-            Error.fromErrorCode(ErrorCode.ValueNotFound).revert();
-            return;
-        }
-        let messages = messagesResult.value;
-        messages.push(msg);
-        key.write(CLValue.fromStringList(messages));
+        let msgs = messages(name);
+        msgs.push(msg);
+        writeLocal(localKey(name), CLValue.fromStringList(msgs));
     }
 }
 
@@ -135,38 +105,20 @@ export function mailing_list_ext(): void {
     let arg1 = arg1Result.value;
 
     if (methodName == "sub") {
-        let maybeKey = sub(arg1);
-        if (maybeKey === null) {
-            let result = new Option(null);
-            ret(CLValue.fromOption(result, CLTypeTag.Key));
-            return;
-        }
-
-        let key = <Key>maybeKey;
-        let keyBytes = key.toBytes();
-        let opt = new Option(arrayToTyped(keyBytes));
-        ret(CLValue.fromOption(opt, CLTypeTag.Key));
+        sub(arg1);
     }
     else if (methodName == "pub") {
         publish(arg1);
     }
+    else if (methodName == "read") {
+        ret(CLValue.fromStringList(messages(arg1)));
+    }
     else {
         Error.fromUserError(<u16>UserError.UnknownMethodName).revert();
     }
 }
 
 export function call(): void {
-    let init = CLValue.fromStringList([]);
-    const maybeListKey = Key.create(init);
-    if (maybeListKey === null) {
-        Error.fromUserError(1).revert();
-        return;
-    }
-    const counterLocalKey = <Key>maybeListKey;
-
-    let namedKeys = toBytesMap([
-        toBytesPair(toBytesString(LIST_KEY), counterLocalKey.toBytes()),
-    ]);
-    var pointer = storeFunctionAtHash(MAILING_LIST_EXT, namedKeys);
+    var pointer = storeFunctionAtHash(MAILING_LIST_EXT, toBytesMap([]));
     putKey(MAILING_KEY, pointer);
 }
@@ -0,0 +1,19 @@
+import * as CL from "../../../../contract-as/assembly";
+import {Error, ErrorCode} from "../../../../contract-as/assembly/error";
+import {fromBytesU64} from "../../../../contract-as/assembly/bytesrepr";
+
+export function call(): void {
+  const knownBlockHeightBytes = CL.getArg(0);
+  if (knownBlockHeightBytes === null) {
+    Error.fromErrorCode(ErrorCode.MissingArgument).revert();
+    return;
+  }
+  const knownBlockHeight = fromBytesU64(knownBlockHeightBytes);
+  if (knownBlockHeight.hasError()) {
+    Error.fromErrorCode(ErrorCode.InvalidArgument).revert();
+    return;
+  }
+
+  const blockHeight = CL.getBlockHeight();
+  assert(blockHeight == knownBlockHeight.value);
+}
@@ -0,0 +1,52 @@
+import * as CL from "../../../../contract-as/assembly";
+import {Error, ErrorCode} from "../../../../contract-as/assembly/error";
+import {fromBytesU32} from "../../../../contract-as/assembly/bytesrepr";
+
+function bytesEqual(left: Uint8Array, right: Uint8Array): bool {
+  if (left.length != right.length) {
+    return false;
+  }
+  for (let i = 0; i < left.length; i++) {
+    if (left[i] != right[i]) {
+      return false;
+    }
+  }
+  return true;
+}
+
+// Returns the elements of a serialized `Vec<u8>` argument, without the length prefix.
+function getBytesArg(i: u32): Uint8Array {
+  const argBytes = CL.getArg(i);
+  if (argBytes === null) {
+    Error.fromErrorCode(ErrorCode.MissingArgument).revert();
+    return <Uint8Array>unreachable();
+  }
+  const length = fromBytesU32(argBytes);
+  if (length.hasError() || <i32>length.value != argBytes.length - 4) {
+    Error.fromErrorCode(ErrorCode.InvalidArgument).revert();
+    return <Uint8Array>unreachable();
+  }
+  return argBytes.subarray(4);
+}
+
+export function call(): void {
+  const message = getBytesArg(0);
+  const expectedDigest = CL.getArg(1);
+  if (expectedDigest === null) {
+    Error.fromErrorCode(ErrorCode.MissingArgument).revert();
+    return;
+  }
+  const ed25519Signature = getBytesArg(2);
+  const ed25519PublicKey = getBytesArg(3);
+  const secp256k1Signature = getBytesArg(4);
+  const secp256k1PublicKey = getBytesArg(5);
+  const tamperedMessage = message.subarray(1);
+
+  assert(bytesEqual(CL.blake2b(message), expectedDigest));
+
+  assert(CL.verifyEd25519(ed25519Signature, message, ed25519PublicKey));
+  assert(!CL.verifyEd25519(ed25519Signature, tamperedMessage, ed25519PublicKey));
+
+  assert(CL.verifySecp256k1(secp256k1Signature, message, secp256k1PublicKey));
+  assert(!CL.verifySecp256k1(secp256k1Signature, tamperedMessage, secp256k1PublicKey));
+}
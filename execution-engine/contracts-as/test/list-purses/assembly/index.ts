@@ -0,0 +1,37 @@
+//@ts-nocheck
+import * as CL from "../../../../contract-as/assembly";
+import {Error} from "../../../../contract-as/assembly/error";
+import {Key} from "../../../../contract-as/assembly/key";
+import {listPurses} from "../../../../contract-as/assembly/account";
+import {createPurse} from "../../../../contract-as/assembly/purse";
+import {AccessRights, URef} from "../../../../contract-as/assembly/uref";
+
+const PURSE_1 = "purse_1";
+const PURSE_2 = "purse_2";
+
+enum CustomError {
+    UnexpectedPurses = 0,
+}
+
+export function call(): void {
+    const pursesBefore = listPurses();
+
+    const purse1 = createPurse();
+    CL.putKey(PURSE_1, Key.fromURef(purse1));
+    const purse2 = createPurse();
+    CL.putKey(PURSE_2, Key.fromURef(purse2));
+
+    const pursesAfter = listPurses();
+    if (pursesAfter.length != pursesBefore.length + 2) {
+        Error.fromUserError(<u16>CustomError.UnexpectedPurses).revert();
+        return;
+    }
+    const last = pursesAfter.length - 1;
+    // Registered purses have their access rights removed.
+    const expected1 = new URef(purse1.getBytes(), AccessRights.NONE);
+    const expected2 = new URef(purse2.getBytes(), AccessRights.NONE);
+    if (pursesAfter[last - 1] != expected1 || pursesAfter[last] != expected2) {
+        Error.fromUserError(<u16>CustomError.UnexpectedPurses).revert();
+        return;
+    }
+}
@@ -0,0 +1,25 @@
+//@ts-nocheck
+import * as CL from "../../../../contract-as/assembly";
+import {Error, ErrorCode} from "../../../../contract-as/assembly/error";
+import {U512} from "../../../../contract-as/assembly/bignum";
+import {getMainPurse} from "../../../../contract-as/assembly/account";
+import {burn} from "../../../../contract-as/assembly/purse";
+
+export function call(): void {
+    const amountArg = CL.getArg(0);
+    if (amountArg === null) {
+        Error.fromErrorCode(ErrorCode.MissingArgument).revert();
+        return;
+    }
+    const amountResult = U512.fromBytes(amountArg);
+    if (amountResult.hasError()) {
+        Error.fromErrorCode(ErrorCode.InvalidArgument).revert();
+        return;
+    }
+
+    const error = burn(getMainPurse(), amountResult.value);
+    if (error !== null) {
+        error.revert();
+        return;
+    }
+}
@@ -0,0 +1,31 @@
+import * as CL from "../../../../contract-as/assembly";
+import {Error, ErrorCode} from "../../../../contract-as/assembly/error";
+
+function bytesEqual(left: Uint8Array, right: Uint8Array): bool {
+  if (left.length != right.length) {
+    return false;
+  }
+  for (let i = 0; i < left.length; i++) {
+    if (left[i] != right[i]) {
+      return false;
+    }
+  }
+  return true;
+}
+
+export function call(): void {
+  const expectedBytes = CL.getArg(0);
+  if (expectedBytes === null) {
+    Error.fromErrorCode(ErrorCode.MissingArgument).revert();
+    return;
+  }
+  if (expectedBytes.length != CL.RANDOM_BYTES_COUNT) {
+    Error.fromErrorCode(ErrorCode.InvalidArgument).revert();
+    return;
+  }
+
+  const firstBytes = CL.randomBytes();
+  const secondBytes = CL.randomBytes();
+  assert(bytesEqual(firstBytes, expectedBytes));
+  assert(!bytesEqual(firstBytes, secondBytes));
+}
@@ -0,0 +1,58 @@
+//@ts-nocheck
+import * as CL from "../../../../contract-as/assembly";
+import {Error, ErrorCode} from "../../../../contract-as/assembly/error";
+import {U512} from "../../../../contract-as/assembly/bignum";
+import {getMainPurse} from "../../../../contract-as/assembly/account";
+import {Key} from "../../../../contract-as/assembly/key";
+import {URef} from "../../../../contract-as/assembly/uref";
+import {createPurse, transferWithProof} from "../../../../contract-as/assembly/purse";
+import {UREF_SERIALIZED_LENGTH} from "../../../../contract-as/assembly/constants";
+
+const PURSE_SECONDARY = "purse:secondary";
+const TRANSFER_RECEIPT = "transfer_receipt";
+const DEPLOY_HASH_LENGTH = 32;
+
+enum CustomError {
+    MissingReceipt = 0,
+    UnexpectedAmount = 1,
+}
+
+export function call(): void {
+    const amountArg = CL.getArg(0);
+    if (amountArg === null) {
+        Error.fromErrorCode(ErrorCode.MissingArgument).revert();
+        return;
+    }
+    const amountResult = U512.fromBytes(amountArg);
+    if (amountResult.hasError()) {
+        Error.fromErrorCode(ErrorCode.InvalidArgument).revert();
+        return;
+    }
+    const amount = amountResult.value;
+
+    const mainPurse = getMainPurse();
+    const secondaryPurse = createPurse();
+    CL.putKey(PURSE_SECONDARY, Key.fromURef(secondaryPurse));
+
+    const receiptURef = transferWithProof(mainPurse, secondaryPurse, amount);
+    if (receiptURef === null) {
+        Error.fromErrorCode(ErrorCode.Transfer).revert();
+        return;
+    }
+    const receiptKey = Key.fromURef(<URef>receiptURef);
+
+    const receiptBytes = receiptKey.read();
+    if (receiptBytes === null) {
+        Error.fromUserError(<u16>CustomError.MissingReceipt).revert();
+        return;
+    }
+    // The amount follows the deploy hash, and the source and target purses.
+    const amountOffset = DEPLOY_HASH_LENGTH + 2 * UREF_SERIALIZED_LENGTH;
+    const receiptAmount = U512.fromBytes(receiptBytes.subarray(amountOffset)).unwrap();
+    if (receiptAmount != amount) {
+        Error.fromUserError(<u16>CustomError.UnexpectedAmount).revert();
+        return;
+    }
+
+    CL.putKey(TRANSFER_RECEIPT, receiptKey);
+}
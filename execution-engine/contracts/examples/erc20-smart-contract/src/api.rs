@@ -1,5 +1,8 @@
 use alloc::string::String;
 
+pub use contract::contract_api::token::{
+    ALLOWANCE, APPROVE, BALANCE_OF, TOTAL_SUPPLY, TRANSFER, TRANSFER_FROM,
+};
 use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 use types::{account::PublicKey, bytesrepr::FromBytes, CLTyped, ContractRef, URef, U512};
 
@@ -7,15 +10,9 @@ use crate::error::Error;
 
 pub const DEPLOY: &str = "deploy";
 pub const INIT_ERC20: &str = "init_erc20";
-pub const BALANCE_OF: &str = "balance_of";
-pub const TOTAL_SUPPLY: &str = "total_supply";
-pub const TRANSFER: &str = "transfer";
-pub const TRANSFER_FROM: &str = "transfer_from";
-pub const APPROVE: &str = "approve";
 pub const ASSERT_BALANCE: &str = "assert_balance";
 pub const ASSERT_TOTAL_SUPPLY: &str = "assert_total_supply";
 pub const ASSERT_ALLOWANCE: &str = "assert_allowance";
-pub const ALLOWANCE: &str = "allowance";
 pub const BUY_PROXY: &str = "buy_proxy";
 pub const BUY: &str = "buy";
 pub const SELL_PROXY: &str = "sell_proxy";
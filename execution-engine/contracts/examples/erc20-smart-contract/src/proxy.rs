@@ -3,43 +3,31 @@ use crate::{
     error::Error,
 };
 use contract::{
-    contract_api::{account, runtime, system},
+    contract_api::{account, runtime, system, token},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::U512;
 
 #[no_mangle]
 pub extern "C" fn erc20_proxy() {
     let token_ref = Api::destination_contract();
     match Api::from_args_in_proxy() {
-        Api::Transfer(recipient, amount) => {
-            runtime::call_contract::<_, ()>(token_ref, (api::TRANSFER, recipient, amount));
-        }
+        Api::Transfer(recipient, amount) => token::transfer(token_ref, recipient, amount),
         Api::TransferFrom(owner, recipient, amount) => {
-            runtime::call_contract::<_, ()>(
-                token_ref,
-                (api::TRANSFER_FROM, owner, recipient, amount),
-            );
-        }
-        Api::Approve(spender, amount) => {
-            runtime::call_contract::<_, ()>(token_ref, (api::APPROVE, spender, amount));
+            token::transfer_from(token_ref, owner, recipient, amount)
         }
+        Api::Approve(spender, amount) => token::approve(token_ref, spender, amount),
         Api::AssertBalance(address, expected_amount) => {
-            let balance = runtime::call_contract::<_, U512>(token_ref, (api::BALANCE_OF, address));
-            if expected_amount != balance {
+            if expected_amount != token::balance_of(token_ref, address) {
                 runtime::revert(Error::BalanceAssertionFailure)
             }
         }
         Api::AssertTotalSupply(expected_total_supply) => {
-            let total_supply = runtime::call_contract::<_, U512>(token_ref, (api::TOTAL_SUPPLY,));
-            if expected_total_supply != total_supply {
+            if expected_total_supply != token::total_supply(token_ref) {
                 runtime::revert(Error::TotalSupplyAssertionFailure)
             }
         }
         Api::AssertAllowance(owner, spender, expected_amount) => {
-            let allowance =
-                runtime::call_contract::<_, U512>(token_ref, (api::ALLOWANCE, owner, spender));
-            if expected_amount != allowance {
+            if expected_amount != token::allowance(token_ref, owner, spender) {
                 runtime::revert(Error::AllowanceAssertionFailure)
             }
         }
@@ -4,27 +4,23 @@
 extern crate alloc;
 
 use alloc::{string::String, vec::Vec};
-use core::convert::{From, TryInto};
+use core::convert::From;
 
 use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, Key, URef};
+use types::ApiError;
 
 const MAIL_FEED_KEY: &str = "mail_feed";
 const MAILING_KEY: &str = "mailing";
 const PUB_METHOD: &str = "pub";
+const READ_METHOD: &str = "read";
 const SUB_METHOD: &str = "sub";
 
 #[repr(u16)]
 enum Error {
-    GetKeyNameURef = 0,
-    BadSubKey,
-    GetMessagesURef,
-    FindMessagesURef,
-    NoMessages,
-    NoSubKey,
+    NoMessages = 0,
 }
 
 impl From<Error> for ApiError {
@@ -41,28 +37,15 @@ pub extern "C" fn call() {
         .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
 
     let name = "CasperLabs";
-    let args = (SUB_METHOD, name);
-    let sub_key = runtime::call_contract::<_, Option<Key>>(contract_ref.clone(), args)
-        .unwrap_or_revert_with(Error::NoSubKey);
-
-    runtime::put_key(MAIL_FEED_KEY, sub_key);
-
-    let key_name_uref =
-        runtime::get_key(MAIL_FEED_KEY).unwrap_or_revert_with(Error::GetKeyNameURef);
-    if sub_key != key_name_uref {
-        runtime::revert(Error::BadSubKey);
-    }
+    runtime::call_contract::<_, ()>(contract_ref.clone(), (SUB_METHOD, name));
 
     let message = "Hello, World!";
-    let args = (PUB_METHOD, message);
-    runtime::call_contract::<_, ()>(contract_ref, args);
-
-    let list_uref: URef = sub_key.try_into().unwrap_or_revert();
-    let messages: Vec<String> = storage::read(list_uref)
-        .unwrap_or_revert_with(Error::GetMessagesURef)
-        .unwrap_or_revert_with(Error::FindMessagesURef);
+    runtime::call_contract::<_, ()>(contract_ref.clone(), (PUB_METHOD, message));
 
+    let messages: Vec<String> = runtime::call_contract(contract_ref, (READ_METHOD, name));
     if messages.is_empty() {
         runtime::revert(Error::NoMessages);
     }
+
+    runtime::put_key(MAIL_FEED_KEY, storage::new_uref(messages).into());
 }
@@ -7,17 +7,17 @@ extern crate alloc;
 #[rustfmt::skip]
 use alloc::vec;
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
-use core::convert::TryInto;
 
 use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, CLValue, Key, URef};
+use types::{ApiError, CLValue, Key};
 
-const LIST_KEY: &str = "list";
 const MAILING_KEY: &str = "mailing";
 const MAILING_LIST_EXT: &str = "mailing_list_ext";
+const PUBLISHER_KEY: &str = "publisher";
+const SUBSCRIBERS_KEY: &str = "subscribers";
 
 enum Arg {
     MethodName = 0,
@@ -27,6 +27,8 @@ enum Arg {
 #[repr(u16)]
 enum Error {
     UnknownMethodName = 0,
+    NotSubscribed,
+    NotPublisher,
 }
 
 impl Into<ApiError> for Error {
@@ -35,38 +37,33 @@ impl Into<ApiError> for Error {
     }
 }
 
-fn get_list_key(name: &str) -> URef {
-    let key = runtime::get_key(name).unwrap_or_revert_with(ApiError::GetKey);
-    key.try_into().unwrap_or_revert()
+// Local keys are hashed, so the subscriber names are kept alongside the message lists to allow
+// iterating over them.
+fn subscribers() -> Vec<String> {
+    storage::read_local(&SUBSCRIBERS_KEY)
+        .unwrap_or_revert()
+        .unwrap_or_default()
 }
 
-fn update_list(name: String) {
-    let list_key = get_list_key(LIST_KEY);
-    let mut list: Vec<String> = storage::read_or_revert(list_key);
-    list.push(name);
-    storage::write(list_key, list);
+fn messages(name: &str) -> Option<Vec<String>> {
+    storage::read_local(&name).unwrap_or_revert()
 }
 
-fn sub(name: String) -> Option<URef> {
-    if runtime::has_key(&name) {
-        let init_message = vec![String::from("Hello again!")];
-        Some(storage::new_uref(init_message))
-    } else {
-        let init_message = vec![String::from("Welcome!")];
-        let new_key = storage::new_uref(init_message);
-        runtime::put_key(&name, new_key.clone().into());
-        update_list(name);
-        Some(new_key)
+fn sub(name: String) {
+    let mut subscribers = subscribers();
+    if subscribers.contains(&name) {
+        return;
     }
+    storage::write_local(name.as_str(), vec![String::from("Welcome!")]);
+    subscribers.push(name);
+    storage::write_local(SUBSCRIBERS_KEY, subscribers);
 }
 
 fn publish(msg: String) {
-    let curr_list: Vec<String> = storage::read_or_revert(get_list_key(LIST_KEY));
-    for name in curr_list.iter() {
-        let uref: URef = get_list_key(name);
-        let mut messages: Vec<String> = storage::read_or_revert(uref);
+    for name in subscribers() {
+        let mut messages = messages(&name).unwrap_or_revert_with(Error::NotSubscribed);
         messages.push(msg.clone());
-        storage::write(uref, messages);
+        storage::write_local(name.as_str(), messages);
     }
 }
 
@@ -79,37 +76,31 @@ pub extern "C" fn mailing_list_ext() {
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
     match method_name.as_str() {
-        "sub" => match sub(arg1) {
-            Some(uref) => {
-                let return_value = CLValue::from_t(Some(Key::from(uref))).unwrap_or_revert();
-                runtime::ret(return_value);
-            }
-            _ => {
-                let return_value = CLValue::from_t(Option::<Key>::None).unwrap_or_revert();
-                runtime::ret(return_value)
-            }
-        },
-        //Note that this is totally insecure. In reality
-        //the pub method would be only available under an
-        //unforgable reference because otherwise anyone could
-        //spam the mailing list.
+        "sub" => sub(arg1),
+        // Only the account which installed the mailing list may publish to it, and only directly
+        // rather than through another contract.
         "pub" => {
+            let publisher = runtime::get_key(PUBLISHER_KEY).unwrap_or_revert_with(ApiError::GetKey);
+            if runtime::get_immediate_caller() != publisher {
+                runtime::revert(Error::NotPublisher);
+            }
             publish(arg1);
         }
+        "read" => {
+            let messages = messages(&arg1).unwrap_or_revert_with(Error::NotSubscribed);
+            runtime::ret(CLValue::from_t(messages).unwrap_or_revert())
+        }
         _ => runtime::revert(Error::UnknownMethodName),
     }
 }
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let init_list: Vec<String> = Vec::new();
-    let list_key = storage::new_uref(init_list);
-
-    //create map of references for stored contract
-    let mut mailing_list_urefs: BTreeMap<String, Key> = BTreeMap::new();
-    let key_name = String::from(LIST_KEY);
-    mailing_list_urefs.insert(key_name, list_key.into());
-
-    let pointer = storage::store_function_at_hash(MAILING_LIST_EXT, mailing_list_urefs);
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(
+        String::from(PUBLISHER_KEY),
+        Key::Account(runtime::get_caller()),
+    );
+    let pointer = storage::store_function_at_hash(MAILING_LIST_EXT, named_keys);
     runtime::put_key(MAILING_KEY, pointer.into())
 }
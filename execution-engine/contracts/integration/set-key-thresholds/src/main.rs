@@ -29,12 +29,8 @@ impl Into<ApiError> for Error {
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let km_weight: u32 = runtime::get_arg(Arg::KeyManagement as u32)
-        .unwrap_or_revert_with(ApiError::MissingArgument)
-        .unwrap_or_revert_with(ApiError::InvalidArgument);
-    let dep_weight: u32 = runtime::get_arg(Arg::Deploy as u32)
-        .unwrap_or_revert_with(ApiError::MissingArgument)
-        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let km_weight: u32 = runtime::try_get_arg(Arg::KeyManagement as u32).unwrap_or_revert();
+    let dep_weight: u32 = runtime::try_get_arg(Arg::Deploy as u32).unwrap_or_revert();
     let key_management_threshold = Weight::new(km_weight as u8);
     let deploy_threshold = Weight::new(dep_weight as u8);
 
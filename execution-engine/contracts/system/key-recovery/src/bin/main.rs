@@ -0,0 +1,7 @@
+#![no_std]
+#![no_main]
+
+#[no_mangle]
+pub extern "C" fn call() {
+    key_recovery::delegate();
+}
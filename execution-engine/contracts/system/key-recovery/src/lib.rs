@@ -0,0 +1,181 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec, vec::Vec};
+
+use contract::{
+    contract_api::{account, runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::PublicKey,
+    system_contract_errors::key_recovery::{Error, Result},
+    ApiError,
+};
+
+const METHOD_CONFIGURE: &str = "configure";
+const METHOD_REQUEST: &str = "request";
+const METHOD_CANCEL: &str = "cancel";
+const METHOD_FINALIZE: &str = "finalize";
+
+const CONFIG_KEY: u8 = 1;
+const REQUEST_KEY: u8 = 2;
+
+/// The recovery keys of an account, how many of them must approve a rotation, and how many
+/// milliseconds must pass after a rotation is requested before it can be finalized.
+type Config = (Vec<PublicKey>, u32, u64);
+
+/// The key to be replaced and its replacement, the recovery keys which have approved the rotation
+/// so far, and the block time at which it was first requested.
+type Request = ((PublicKey, PublicKey), (Vec<PublicKey>, u64));
+
+fn read_config(account: PublicKey) -> Option<Config> {
+    storage::read_local(&(CONFIG_KEY, account)).unwrap_or_revert_with(ApiError::Read)
+}
+
+fn read_request(account: PublicKey) -> Option<Request> {
+    storage::read_local::<_, Option<Request>>(&(REQUEST_KEY, account))
+        .unwrap_or_revert_with(ApiError::Read)
+        .and_then(|maybe_request| maybe_request)
+}
+
+fn write_request(account: PublicKey, request: Option<Request>) {
+    storage::write_local((REQUEST_KEY, account), request)
+}
+
+/// Sets the recovery keys of the calling account, replacing any previous configuration and
+/// discarding any pending request.
+///
+/// The deploy must meet the account's key management threshold.
+pub fn configure(recovery_keys: Vec<PublicKey>, threshold: u32, delay: u64) -> Result<()> {
+    if !account::can_manage_keys() {
+        return Err(Error::KeyManagementThreshold);
+    }
+
+    let mut recovery_keys = recovery_keys;
+    recovery_keys.sort();
+    recovery_keys.dedup();
+    if threshold == 0 || threshold as usize > recovery_keys.len() {
+        return Err(Error::InvalidThreshold);
+    }
+
+    let account = runtime::get_caller();
+    storage::write_local((CONFIG_KEY, account), (recovery_keys, threshold, delay));
+    // Approvals given under the previous configuration no longer apply.
+    write_request(account, None);
+    Ok(())
+}
+
+/// Requests, or approves an already pending request, that `old_key` of `account` be replaced by
+/// `new_key`.
+///
+/// The caller must be one of the account's recovery keys.
+pub fn request(account: PublicKey, old_key: PublicKey, new_key: PublicKey) -> Result<()> {
+    let (recovery_keys, _threshold, _delay) = read_config(account).ok_or(Error::NotConfigured)?;
+
+    let caller = runtime::get_caller();
+    if recovery_keys.binary_search(&caller).is_err() {
+        return Err(Error::NotRecoveryKey);
+    }
+
+    let request = match read_request(account) {
+        None => {
+            let requested_at = runtime::get_blocktime().into();
+            ((old_key, new_key), (vec![caller], requested_at))
+        }
+        Some((rotation, (mut approvals, requested_at))) => {
+            if rotation != (old_key, new_key) {
+                return Err(Error::ConflictingRequest);
+            }
+            if !approvals.contains(&caller) {
+                approvals.push(caller);
+            }
+            (rotation, (approvals, requested_at))
+        }
+    };
+
+    write_request(account, Some(request));
+    Ok(())
+}
+
+/// Discards the pending request against the calling account.
+///
+/// The deploy must meet the account's key management threshold.
+pub fn cancel() -> Result<()> {
+    if !account::can_manage_keys() {
+        return Err(Error::KeyManagementThreshold);
+    }
+
+    let account = runtime::get_caller();
+    if read_request(account).is_none() {
+        return Err(Error::RequestNotFound);
+    }
+    write_request(account, None);
+    Ok(())
+}
+
+/// Carries out the pending request against `account` once enough recovery keys have approved it
+/// and the configured delay has elapsed.  Anyone may call this.
+pub fn finalize(account: PublicKey) -> Result<()> {
+    let (_recovery_keys, threshold, delay) = read_config(account).ok_or(Error::NotConfigured)?;
+    let ((old_key, new_key), (approvals, requested_at)) =
+        read_request(account).ok_or(Error::RequestNotFound)?;
+
+    if (approvals.len() as u32) < threshold {
+        return Err(Error::InsufficientApprovals);
+    }
+
+    let now: u64 = runtime::get_blocktime().into();
+    if now < requested_at.saturating_add(delay) {
+        return Err(Error::DelayNotElapsed);
+    }
+
+    account::rotate_associated_key(account, old_key, new_key).map_err(|error| match error {
+        ApiError::MissingKey => Error::MissingKey,
+        ApiError::DuplicateKey => Error::DuplicateKey,
+        _ => Error::RotationFailed,
+    })?;
+
+    write_request(account, None);
+    Ok(())
+}
+
+fn get_arg<T: types::bytesrepr::FromBytes>(index: u32) -> T {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+pub fn delegate() {
+    let method_name: String = get_arg(0);
+
+    match method_name.as_str() {
+        // Type of this method: `fn configure(recovery_keys: Vec<PublicKey>, threshold: u32,
+        // delay: u64)`
+        METHOD_CONFIGURE => {
+            let recovery_keys: Vec<PublicKey> = get_arg(1);
+            let threshold: u32 = get_arg(2);
+            let delay: u64 = get_arg(3);
+            configure(recovery_keys, threshold, delay).unwrap_or_revert();
+        }
+        // Type of this method: `fn request(account: PublicKey, old_key: PublicKey,
+        // new_key: PublicKey)`
+        METHOD_REQUEST => {
+            let account: PublicKey = get_arg(1);
+            let old_key: PublicKey = get_arg(2);
+            let new_key: PublicKey = get_arg(3);
+            request(account, old_key, new_key).unwrap_or_revert();
+        }
+        // Type of this method: `fn cancel()`
+        METHOD_CANCEL => {
+            cancel().unwrap_or_revert();
+        }
+        // Type of this method: `fn finalize(account: PublicKey)`
+        METHOD_FINALIZE => {
+            let account: PublicKey = get_arg(1);
+            finalize(account).unwrap_or_revert();
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
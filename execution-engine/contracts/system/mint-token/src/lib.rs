@@ -2,10 +2,10 @@
 
 extern crate alloc;
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 use contract::{
-    contract_api::{runtime, storage},
+    contract_api::{runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use mint::{Mint, RuntimeProvider, StorageProvider};
@@ -13,13 +13,17 @@ use types::{
     account::PublicKey,
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::mint::Error,
-    ApiError, CLTyped, CLValue, Key, URef, U512,
+    ApiError, BlockTime, CLTyped, CLValue, ChainConfigKey, Key, URef, VestingSchedule, U512,
 };
 
 const METHOD_MINT: &str = "mint";
 const METHOD_CREATE: &str = "create";
 const METHOD_BALANCE: &str = "balance";
 const METHOD_TRANSFER: &str = "transfer";
+const METHOD_BURN: &str = "burn";
+const METHOD_BALANCE_OF: &str = "balance_of";
+const METHOD_BATCH_TRANSFER: &str = "batch_transfer";
+const METHOD_SET_VESTING_SCHEDULE: &str = "set_vesting_schedule";
 
 pub struct MintContract;
 
@@ -28,9 +32,21 @@ impl RuntimeProvider for MintContract {
         runtime::get_caller()
     }
 
+    fn get_system_account(&self) -> PublicKey {
+        runtime::get_chain_config(ChainConfigKey::SystemAccount)
+    }
+
     fn put_key(&mut self, name: &str, key: Key) {
         runtime::put_key(name, key)
     }
+
+    fn get_block_time(&self) -> BlockTime {
+        runtime::get_blocktime()
+    }
+
+    fn get_min_main_purse_balance(&self) -> Option<(URef, U512)> {
+        system::get_min_main_purse_balance()
+    }
 }
 
 impl StorageProvider for MintContract {
@@ -113,6 +129,49 @@ pub fn delegate() {
             let ret = CLValue::from_t(result).unwrap_or_revert();
             runtime::ret(ret);
         }
+        // Type: `fn burn(purse: URef, amount: U512) -> Result<(), Error>`
+        METHOD_BURN => {
+            let purse: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let amount: U512 = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let result: Result<(), Error> = mint_contract.burn(purse, amount);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
+            runtime::ret(ret);
+        }
+        // Type: `fn balance_of(purse: URef) -> Result<Option<U512>, Error>`
+        METHOD_BALANCE_OF => {
+            let purse: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let result: Result<Option<U512>, Error> = mint_contract.balance_of(purse);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
+            runtime::ret(ret);
+        }
+        // Type: `fn batch_transfer(transfers: Vec<(URef, URef, U512)>) -> Result<(), Error>`
+        METHOD_BATCH_TRANSFER => {
+            let transfers: Vec<(URef, URef, U512)> = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let result: Result<(), Error> = mint_contract.batch_transfer(transfers);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
+            runtime::ret(ret);
+        }
+        // Type: `fn set_vesting_schedule(purse: URef, schedule: VestingSchedule) -> Result<(),
+        // Error>`
+        METHOD_SET_VESTING_SCHEDULE => {
+            let purse: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let schedule: VestingSchedule = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let result: Result<(), Error> = mint_contract.set_vesting_schedule(purse, schedule);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
+            runtime::ret(ret);
+        }
 
         _ => panic!("Unknown method name!"),
     }
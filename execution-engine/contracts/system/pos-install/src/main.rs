@@ -9,7 +9,7 @@ use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use proof_of_stake::Stakes;
+use proof_of_stake::{Stakes, UNBONDING_DELAY_KEY};
 use types::{
     account::PublicKey, system_contract_errors::mint, AccessRights, ApiError, CLValue, ContractRef,
     Key, URef, U512,
@@ -25,6 +25,7 @@ const POS_FUNCTION_NAME: &str = "pos_ext";
 enum Args {
     MintURef = 0,
     GenesisValidators = 1,
+    UnbondingDelay = 2,
 }
 
 #[no_mangle]
@@ -44,12 +45,16 @@ pub extern "C" fn call() {
             .unwrap_or_revert_with(ApiError::MissingArgument)
             .unwrap_or_revert_with(ApiError::InvalidArgument);
 
+    let unbonding_delay: u64 = runtime::get_arg(Args::UnbondingDelay as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
     let stakes = Stakes::new(genesis_validators);
 
     // Add genesis validators to PoS contract object.
     // For now, we are storing validators in `named_keys` map of the PoS contract
     // in the form: key: "v_{validator_pk}_{validator_stake}", value: doesn't
-    // matter.
+    // matter, where `validator_pk` is `PublicKey::to_tagged_hex`.
     let mut named_keys: BTreeMap<String, Key> =
         stakes.strings().map(|key| (key, PLACEHOLDER_KEY)).collect();
 
@@ -70,6 +75,12 @@ pub extern "C" fn call() {
         named_keys.insert(String::from(*name), Key::URef(*uref));
     });
 
+    let unbonding_delay_uref = storage::new_uref(unbonding_delay);
+    named_keys.insert(
+        String::from(UNBONDING_DELAY_KEY),
+        Key::URef(unbonding_delay_uref),
+    );
+
     let uref: URef = storage::store_function(POS_FUNCTION_NAME, named_keys)
         .into_uref()
         .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
@@ -2,33 +2,34 @@
 
 extern crate alloc;
 
-use alloc::{
-    collections::{BTreeMap, BTreeSet},
-    string::String,
-};
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
 
 use contract::{
     contract_api::{runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    Delegations, DelegationsProvider, MintProvider, ProofOfStake, Queue, QueueProvider,
+    RuntimeProvider, Stakes, StakesProvider, BONDING_KEY, UNBONDING_DELAY_KEY, UNBONDING_KEY,
 };
 use types::{
-    account::PublicKey, system_contract_errors::pos::Error, ApiError, BlockTime, CLValue, Key,
-    Phase, TransferResult, URef, U512,
+    account::PublicKey, system_contract_errors::pos::Error, ApiError, BlockTime, CLValue,
+    ChainConfigKey, Key, Phase, TransferResult, URef, U512,
 };
 
 const METHOD_BOND: &str = "bond";
 const METHOD_UNBOND: &str = "unbond";
+const METHOD_WITHDRAW: &str = "withdraw";
+const METHOD_STEP: &str = "step";
+const METHOD_DELEGATE: &str = "delegate";
+const METHOD_UNDELEGATE: &str = "undelegate";
+const METHOD_SLASH: &str = "slash";
+const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
 const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
 const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
 const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
 const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
 
-const BONDING_KEY: u8 = 1;
-const UNBONDING_KEY: u8 = 2;
-
 pub struct ProofOfStakeContract;
 
 impl MintProvider for ProofOfStakeContract {
@@ -79,6 +80,18 @@ impl QueueProvider for ProofOfStakeContract {
     fn write_unbonding(&mut self, queue: Queue) {
         storage::write_local(UNBONDING_KEY, queue);
     }
+
+    /// Reads the unbonding delay set up by the installer. Defaults to zero if it has not been
+    /// set.
+    fn read_unbonding_delay(&mut self) -> u64 {
+        match runtime::get_key(UNBONDING_DELAY_KEY) {
+            Some(Key::URef(uref)) => storage::read(uref)
+                .unwrap_or_revert()
+                .unwrap_or_revert_with(Error::UnbondingDelayNotFound),
+            Some(_) => runtime::revert(Error::UnbondingDelayKeyUnexpectedType),
+            None => 0,
+        }
+    }
 }
 
 impl RuntimeProvider for ProofOfStakeContract {
@@ -105,38 +118,17 @@ impl RuntimeProvider for ProofOfStakeContract {
     fn get_caller(&self) -> PublicKey {
         runtime::get_caller()
     }
+
+    fn get_system_account(&self) -> PublicKey {
+        runtime::get_chain_config(ChainConfigKey::SystemAccount)
+    }
 }
 
 impl StakesProvider for ProofOfStakeContract {
     /// Reads the current stakes from the contract's known urefs.
     fn read(&self) -> Result<Stakes, Error> {
-        let mut stakes = BTreeMap::new();
-        for (name, _) in runtime::list_named_keys() {
-            let mut split_name = name.split('_');
-            if Some("v") != split_name.next() {
-                continue;
-            }
-            let hex_key = split_name
-                .next()
-                .ok_or(Error::StakesKeyDeserializationFailed)?;
-            if hex_key.len() != 64 {
-                return Err(Error::StakesKeyDeserializationFailed);
-            }
-            let mut key_bytes = [0u8; 32];
-            let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
-                .map_err(|_| Error::StakesKeyDeserializationFailed)?;
-            debug_assert!(_bytes_written == key_bytes.len());
-            let pub_key = PublicKey::ed25519_from(key_bytes);
-            let balance = split_name
-                .next()
-                .and_then(|b| U512::from_dec_str(b).ok())
-                .ok_or(Error::StakesDeserializationFailed)?;
-            stakes.insert(pub_key, balance);
-        }
-        if stakes.is_empty() {
-            return Err(Error::StakesNotFound);
-        }
-        Ok(Stakes(stakes))
+        let named_keys = runtime::list_named_keys();
+        Stakes::from_names(named_keys.keys().map(String::as_str))
     }
 
     /// Writes the current stakes to the contract's known urefs.
@@ -155,6 +147,27 @@ impl StakesProvider for ProofOfStakeContract {
     }
 }
 
+impl DelegationsProvider for ProofOfStakeContract {
+    /// Reads the current delegations from the contract's known urefs.
+    fn read_delegations(&self) -> Result<Delegations, Error> {
+        let named_keys = runtime::list_named_keys();
+        Delegations::from_names(named_keys.keys().map(String::as_str))
+    }
+
+    /// Writes the current delegations to the contract's known urefs.
+    fn write_delegations(&mut self, delegations: &Delegations) {
+        let mut new_urefs: BTreeSet<String> = delegations.strings().collect();
+        for (name, _) in runtime::list_named_keys() {
+            if name.starts_with("d_") && !new_urefs.remove(&name) {
+                runtime::remove_key(&name);
+            }
+        }
+        for name in new_urefs {
+            runtime::put_key(&name, Key::Hash([0; 32]));
+        }
+    }
+}
+
 impl ProofOfStake for ProofOfStakeContract {}
 
 pub fn delegate() {
@@ -196,6 +209,55 @@ pub fn delegate() {
                 .unbond(validator, maybe_amount)
                 .unwrap_or_revert();
         }
+        // Type of this method: `fn withdraw()`
+        METHOD_WITHDRAW => {
+            if !cfg!(feature = "enable-bonding") {
+                runtime::revert(ApiError::Unhandled)
+            }
+
+            pos_contract.withdraw().unwrap_or_revert();
+        }
+        // Type of this method: `fn step()`
+        METHOD_STEP => {
+            pos_contract.step().unwrap_or_revert();
+        }
+        // Type of this method: `fn delegate(validator: PublicKey, amount: U512, purse: URef)`
+        METHOD_DELEGATE => {
+            if !cfg!(feature = "enable-bonding") {
+                runtime::revert(ApiError::Unhandled)
+            }
+
+            let delegator = runtime::get_caller();
+            let validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let amount: U512 = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let source_purse: URef = runtime::get_arg(3)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract
+                .delegate(delegator, validator, amount, source_purse)
+                .unwrap_or_revert();
+        }
+        // Type of this method: `fn undelegate(validator: PublicKey, amount: Option<U512>)`
+        METHOD_UNDELEGATE => {
+            if !cfg!(feature = "enable-bonding") {
+                runtime::revert(ApiError::Unhandled)
+            }
+
+            let delegator = runtime::get_caller();
+            let validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let maybe_amount = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract
+                .undelegate(delegator, validator, maybe_amount)
+                .unwrap_or_revert();
+        }
         // Type of this method: `fn get_payment_purse() -> URef`
         METHOD_GET_PAYMENT_PURSE => {
             let rights_controlled_purse = pos_contract.get_payment_purse().unwrap_or_revert();
@@ -228,10 +290,31 @@ pub fn delegate() {
             let account: PublicKey = runtime::get_arg(2)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let refund_ratio: u8 = runtime::get_arg(3)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
             pos_contract
-                .finalize_payment(amount_spent, account)
+                .finalize_payment(amount_spent, account, refund_ratio)
                 .unwrap_or_revert();
         }
+        // Type of this method: `fn slash(validator: PublicKey) -> U512`
+        METHOD_SLASH => {
+            let validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let slashed = pos_contract.slash(validator).unwrap_or_revert();
+            let return_value = CLValue::from_t(slashed).unwrap_or_revert();
+            runtime::ret(return_value);
+        }
+        // Type of this method: `fn distribute_rewards(excluded: Vec<PublicKey>) -> U512`
+        METHOD_DISTRIBUTE_REWARDS => {
+            let excluded: Vec<PublicKey> = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let distributed = pos_contract.distribute_rewards(excluded).unwrap_or_revert();
+            let return_value = CLValue::from_t(distributed).unwrap_or_revert();
+            runtime::ret(return_value);
+        }
         _ => {}
     }
 }
@@ -10,6 +10,7 @@ use contract::{
 use types::{ApiError, URef, U512};
 
 const GET_PAYMENT_PURSE: &str = "get_payment_purse";
+const SET_REFUND_PURSE: &str = "set_refund_purse";
 
 struct StandardPaymentContract;
 
@@ -36,6 +37,11 @@ impl ProofOfStakeProvider for StandardPaymentContract {
         let payment_purse = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
         Ok(payment_purse)
     }
+
+    fn set_refund_purse(&mut self, purse: URef) -> Result<(), ApiError> {
+        let pos_pointer = system::get_proof_of_stake();
+        runtime::call_contract(pos_pointer, (SET_REFUND_PURSE, purse))
+    }
 }
 
 impl StandardPayment for StandardPaymentContract {}
@@ -47,5 +53,12 @@ pub fn delegate() {
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
 
-    standard_payment_contract.pay(amount).unwrap_or_revert();
+    let result = match runtime::get_arg(1) {
+        Some(source) => {
+            let source: URef = source.unwrap_or_revert_with(ApiError::InvalidArgument);
+            standard_payment_contract.pay_from(source, amount)
+        }
+        None => standard_payment_contract.pay(amount),
+    };
+    result.unwrap_or_revert();
 }
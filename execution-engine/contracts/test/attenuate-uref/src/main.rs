@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::contract_api::{runtime, storage};
+use types::AccessRights;
+
+const VALUE: &str = "value";
+const READ_ONLY_VALUE: &str = "read_only_value";
+const CONTRACT: &str = "contract";
+const NOOP: &str = "noop_ext";
+
+#[no_mangle]
+pub extern "C" fn noop_ext() {}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value = storage::new_uref(0u64);
+    runtime::put_key(VALUE, value.into());
+
+    let read_only_value = runtime::attenuate_uref(value, AccessRights::READ);
+    runtime::put_key(READ_ONLY_VALUE, read_only_value.into());
+
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(String::from(VALUE), read_only_value.into());
+    let contract = storage::store_function_at_hash(NOOP, named_keys);
+    runtime::put_key(CONTRACT, contract.into());
+}
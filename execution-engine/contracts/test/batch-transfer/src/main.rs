@@ -0,0 +1,40 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec;
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, U512};
+
+const PURSE_1: &str = "purse_1";
+const PURSE_2: &str = "purse_2";
+const SUCCEEDED: &str = "succeeded";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount_1: U512 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let amount_2: U512 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let main_purse = account::get_main_purse();
+    let purse_1 = system::create_purse();
+    let purse_2 = system::create_purse();
+    runtime::put_key(PURSE_1, purse_1.into());
+    runtime::put_key(PURSE_2, purse_2.into());
+
+    // The second transfer spends motes received in the first one.
+    let transfers = vec![
+        (main_purse, purse_1, amount_1),
+        (purse_1, purse_2, amount_2),
+    ];
+    let succeeded = system::batch_transfer(transfers).is_ok();
+    runtime::put_key(SUCCEEDED, storage::new_uref(succeeded).into());
+}
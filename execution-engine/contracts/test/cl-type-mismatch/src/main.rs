@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const READ_ERROR: &str = "read_error";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let overwrite: bool = runtime::try_get_arg(0).unwrap_or_revert();
+
+    let uref = storage::new_uref::<Vec<String>>(vec![String::from("Welcome!")]);
+    if overwrite {
+        // Fails on the host side, since a `Vec<String>` is stored under `uref`.
+        storage::write(uref, 42u64);
+        return;
+    }
+
+    let error = storage::try_read::<u64>(uref)
+        .err()
+        .unwrap_or_revert_with(ApiError::Unhandled);
+    runtime::put_key(READ_ERROR, storage::new_uref(u32::from(error)).into());
+}
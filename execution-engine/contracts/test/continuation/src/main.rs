@@ -0,0 +1,89 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{continuation, runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, ContractRef, Key, URef};
+
+const CONTRACT_NAME: &str = "continuation";
+const RESULT_KEY: &str = "result";
+const CONTINUATION_NAME: &str = "pending";
+
+const METHOD_INSTALL: &str = "install";
+const METHOD_START: &str = "start";
+const METHOD_RESUME: &str = "resume";
+const METHOD_FINISH: &str = "finish";
+
+fn get_method() -> String {
+    runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn get_value() -> u64 {
+    runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn get_contract_key() -> Key {
+    runtime::get_key(CONTRACT_NAME).unwrap_or_revert_with(ApiError::GetKey)
+}
+
+fn to_contract_ref(key: Key) -> ContractRef {
+    key.to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+#[no_mangle]
+pub extern "C" fn continuation_ext() {
+    match get_method().as_str() {
+        METHOD_START => {
+            let contract_key: Key = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let contract = to_contract_ref(contract_key);
+            continuation::schedule(CONTINUATION_NAME, contract, METHOD_FINISH, (get_value(),));
+        }
+        METHOD_RESUME => continuation::resume::<()>(CONTINUATION_NAME),
+        METHOD_FINISH => {
+            let result_uref: URef = runtime::get_key(RESULT_KEY)
+                .unwrap_or_revert_with(ApiError::GetKey)
+                .into_uref()
+                .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+            storage::write(result_uref, get_value());
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
+
+/// Installs a stored contract which schedules a continuation in one deploy and runs it in another.
+#[no_mangle]
+pub extern "C" fn call() {
+    match get_method().as_str() {
+        METHOD_INSTALL => {
+            let mut named_keys = BTreeMap::new();
+            named_keys.insert(String::from(RESULT_KEY), storage::new_uref(0u64).into());
+            let contract_key: Key =
+                storage::store_function_at_hash("continuation_ext", named_keys).into();
+            runtime::put_key(CONTRACT_NAME, contract_key);
+        }
+        METHOD_START => {
+            let contract_key = get_contract_key();
+            runtime::call_contract::<_, ()>(
+                to_contract_ref(contract_key),
+                (METHOD_START, get_value(), contract_key),
+            );
+        }
+        METHOD_RESUME => {
+            runtime::call_contract::<_, ()>(to_contract_ref(get_contract_key()), (METHOD_RESUME,))
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
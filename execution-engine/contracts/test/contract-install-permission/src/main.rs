@@ -0,0 +1,25 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError};
+
+enum Arg {
+    Account = 0,
+    Allowed = 1,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let account: PublicKey = runtime::get_arg(Arg::Account as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let allowed: bool = runtime::get_arg(Arg::Allowed as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    account::set_contract_install_permission(account, allowed).unwrap_or_revert();
+}
@@ -0,0 +1,44 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef};
+
+const DATA_KEY: &str = "data";
+const VALUE: u64 = 42;
+
+const METHOD_WRITE: &str = "write";
+const METHOD_DELETE: &str = "delete";
+const METHOD_WRITE_AND_DELETE: &str = "write_and_delete";
+
+fn get_data_uref() -> URef {
+    runtime::get_key(DATA_KEY)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method.as_str() {
+        METHOD_WRITE => runtime::put_key(DATA_KEY, storage::new_uref(VALUE).into()),
+        METHOD_DELETE => storage::delete(get_data_uref()),
+        METHOD_WRITE_AND_DELETE => {
+            let uref = storage::new_uref(VALUE);
+            runtime::put_key(DATA_KEY, uref.into());
+            storage::delete(uref);
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
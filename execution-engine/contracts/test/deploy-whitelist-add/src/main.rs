@@ -0,0 +1,39 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError, URef};
+
+const DEPLOY_WHITELIST_NAME: &str = "deploy_whitelist";
+
+enum Arg {
+    Account = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let account: PublicKey = runtime::get_arg(Arg::Account as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let whitelist_uref: URef = runtime::get_key(DEPLOY_WHITELIST_NAME)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .try_into()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+
+    let mut whitelist: Vec<PublicKey> = storage::read(whitelist_uref)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    if !whitelist.contains(&account) {
+        whitelist.push(account);
+    }
+    storage::write(whitelist_uref, whitelist);
+}
@@ -37,17 +37,16 @@ pub extern "C" fn call() {
 
         let payment_purse: URef = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
 
-        system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert()
+        system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert();
+
+        // Payment code may not create URefs
+        return;
     }
 
-    let value: Option<&str> = {
-        match phase {
-            Phase::Payment => Some("payment"),
-            Phase::Session => Some("session"),
-            _ => None,
-        }
-    };
-    let value = value.unwrap_or_revert_with(ApiError::User(Error::InvalidPhase as u16));
+    if phase != Phase::Session {
+        runtime::revert(ApiError::User(Error::InvalidPhase as u16));
+    }
+    let value = "session";
     let result_key = storage::new_uref(value.to_string()).into();
     let mut uref_name: String = NEW_UREF_RESULT_UREF_NAME.to_string();
     uref_name.push_str("-");
@@ -0,0 +1,48 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError};
+
+const ASSOCIATED_KEYS: &str = "associated_keys";
+const DEPLOYMENT_THRESHOLD: &str = "deployment_threshold";
+const KEY_MANAGEMENT_THRESHOLD: &str = "key_management_threshold";
+const MAIN_PURSE_ADDR: &str = "main_purse_addr";
+const MISSING_ACCOUNT_FOUND: &str = "missing_account_found";
+
+fn get_public_key(index: u32) -> PublicKey {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let account_info =
+        runtime::get_account_info(get_public_key(0)).unwrap_or_revert_with(ApiError::ValueNotFound);
+    runtime::put_key(
+        ASSOCIATED_KEYS,
+        storage::new_uref(account_info.associated_keys().clone()).into(),
+    );
+    runtime::put_key(
+        DEPLOYMENT_THRESHOLD,
+        storage::new_uref(account_info.deployment_threshold()).into(),
+    );
+    runtime::put_key(
+        KEY_MANAGEMENT_THRESHOLD,
+        storage::new_uref(account_info.key_management_threshold()).into(),
+    );
+    runtime::put_key(
+        MAIN_PURSE_ADDR,
+        storage::new_uref(account_info.main_purse().addr()).into(),
+    );
+
+    let missing_account_found = runtime::get_account_info(get_public_key(1)).is_some();
+    runtime::put_key(
+        MISSING_ACCOUNT_FOUND,
+        storage::new_uref(missing_account_found).into(),
+    );
+}
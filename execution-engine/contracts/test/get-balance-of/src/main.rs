@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{AccessRights, ApiError, URef};
+
+const BALANCE: &str = "balance";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let readable: bool = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let main_purse = account::get_main_purse();
+    let purse = if readable {
+        main_purse.into_read()
+    } else {
+        URef::new(main_purse.addr(), AccessRights::ADD)
+    };
+
+    let balance = system::get_balance_of(purse).unwrap_or_revert_with(ApiError::InvalidPurse);
+    if Some(balance) != system::get_balance(main_purse) {
+        runtime::revert(ApiError::User(0));
+    }
+
+    runtime::put_key(BALANCE, storage::new_uref(balance).into());
+}
@@ -0,0 +1,18 @@
+#![no_std]
+#![no_main]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::ApiError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let known_block_height: u64 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let actual_block_height: u64 = runtime::get_block_height();
+
+    assert_eq!(
+        actual_block_height, known_block_height,
+        "actual block height not known block height"
+    );
+}
@@ -0,0 +1,44 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{CLValue, Key};
+
+const CONTRACT_NAME: &str = "call_stack_contract";
+const CONTRACT_FUNCTION_NAME: &str = "call_stack_ext";
+const SESSION_CALLER: &str = "session_caller";
+const SESSION_CALL_STACK: &str = "session_call_stack";
+const CONTRACT_CALLER: &str = "contract_caller";
+const CONTRACT_CALL_STACK: &str = "contract_call_stack";
+
+#[no_mangle]
+pub extern "C" fn call_stack_ext() {
+    let result = (runtime::get_immediate_caller(), runtime::get_call_stack());
+    runtime::ret(CLValue::from_t(result).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    runtime::put_key(
+        SESSION_CALLER,
+        storage::new_uref(runtime::get_immediate_caller()).into(),
+    );
+    runtime::put_key(
+        SESSION_CALL_STACK,
+        storage::new_uref(runtime::get_call_stack()).into(),
+    );
+
+    let contract = storage::store_function_at_hash(CONTRACT_FUNCTION_NAME, BTreeMap::new());
+    runtime::put_key(CONTRACT_NAME, contract.clone().into());
+
+    let (caller, call_stack): (Key, Vec<Key>) = runtime::call_contract(contract, ());
+    runtime::put_key(CONTRACT_CALLER, storage::new_uref(caller).into());
+    runtime::put_key(CONTRACT_CALL_STACK, storage::new_uref(call_stack).into());
+}
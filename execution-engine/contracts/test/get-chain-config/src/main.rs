@@ -0,0 +1,34 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::contract_api::{runtime, storage};
+use types::{account::PublicKey, ChainConfigKey, U512};
+
+const CHAIN_NAME: &str = "chain_name";
+const CONV_RATE: &str = "conv_rate";
+const MAX_PAYMENT: &str = "max_payment";
+const PROTOCOL_VERSION: &str = "protocol_version";
+const SYSTEM_ACCOUNT: &str = "system_account";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let chain_name: String = runtime::get_chain_config(ChainConfigKey::ChainName);
+    runtime::put_key(CHAIN_NAME, storage::new_uref(chain_name).into());
+
+    let conv_rate: u64 = runtime::get_chain_config(ChainConfigKey::ConvRate);
+    runtime::put_key(CONV_RATE, storage::new_uref(conv_rate).into());
+
+    let max_payment: U512 = runtime::get_chain_config(ChainConfigKey::MaxPayment);
+    runtime::put_key(MAX_PAYMENT, storage::new_uref(max_payment).into());
+
+    let protocol_version: (u32, u32, u32) =
+        runtime::get_chain_config(ChainConfigKey::ProtocolVersion);
+    runtime::put_key(PROTOCOL_VERSION, storage::new_uref(protocol_version).into());
+
+    let system_account: PublicKey = runtime::get_chain_config(ChainConfigKey::SystemAccount);
+    runtime::put_key(SYSTEM_ACCOUNT, storage::new_uref(system_account).into());
+}
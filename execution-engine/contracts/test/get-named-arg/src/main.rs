@@ -0,0 +1,20 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const ARG_VALUE: &str = "value";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value: u64 = runtime::get_named_arg(ARG_VALUE)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let value_uref = storage::new_uref(value);
+    runtime::put_key(ARG_VALUE, value_uref.into());
+}
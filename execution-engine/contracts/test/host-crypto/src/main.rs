@@ -0,0 +1,54 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{bytesrepr::FromBytes, ApiError, CLTyped, BLAKE2B_DIGEST_LENGTH};
+
+fn get_arg<T: CLTyped + FromBytes>(index: u32) -> T {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let message: Vec<u8> = get_arg(0);
+    let expected_digest: [u8; BLAKE2B_DIGEST_LENGTH] = get_arg(1);
+    let ed25519_signature: Vec<u8> = get_arg(2);
+    let ed25519_public_key: Vec<u8> = get_arg(3);
+    let secp256k1_signature: Vec<u8> = get_arg(4);
+    let secp256k1_public_key: Vec<u8> = get_arg(5);
+    let tampered_message = &message[1..];
+
+    assert_eq!(
+        runtime::blake2b(&message),
+        expected_digest,
+        "digest not expected digest"
+    );
+
+    assert!(
+        runtime::verify_ed25519(&ed25519_signature, &message, &ed25519_public_key),
+        "ed25519 signature should be valid"
+    );
+    assert!(
+        !runtime::verify_ed25519(&ed25519_signature, tampered_message, &ed25519_public_key),
+        "ed25519 signature of other message should be invalid"
+    );
+
+    assert!(
+        runtime::verify_secp256k1(&secp256k1_signature, &message, &secp256k1_public_key),
+        "secp256k1 signature should be valid"
+    );
+    assert!(
+        !runtime::verify_secp256k1(
+            &secp256k1_signature,
+            tampered_message,
+            &secp256k1_public_key
+        ),
+        "secp256k1 signature of other message should be invalid"
+    );
+}
@@ -0,0 +1,41 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::math;
+use types::{ApiError, U256, U512};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let (lhs, rhs) = (U512::from(7), U512::from(2));
+    assert_eq!(math::checked_add(lhs, rhs), Ok(U512::from(9)));
+    assert_eq!(math::checked_sub(lhs, rhs), Ok(U512::from(5)));
+    assert_eq!(math::checked_mul(lhs, rhs), Ok(U512::from(14)));
+    assert_eq!(math::checked_div(lhs, rhs), Ok(U512::from(3)));
+    assert_eq!(math::checked_rem(lhs, rhs), Ok(U512::from(1)));
+
+    assert_eq!(
+        math::checked_add(U512::max_value(), U512::one()),
+        Err(ApiError::ArithmeticOverflow)
+    );
+    assert_eq!(
+        math::checked_sub(U256::zero(), U256::one()),
+        Err(ApiError::ArithmeticOverflow)
+    );
+    assert_eq!(
+        math::checked_div(U256::one(), U256::zero()),
+        Err(ApiError::DivisionByZero)
+    );
+
+    assert_eq!(
+        math::saturating_add(U256::max_value(), U256::one()),
+        U256::max_value()
+    );
+    assert_eq!(
+        math::saturating_sub(U512::zero(), U512::one()),
+        U512::zero()
+    );
+    assert_eq!(
+        math::saturating_mul(U512::max_value(), U512::from(2)),
+        U512::max_value()
+    );
+}
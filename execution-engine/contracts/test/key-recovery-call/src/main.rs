@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError};
+
+const COMMAND_CONFIGURE: &str = "configure";
+const COMMAND_REQUEST: &str = "request";
+const COMMAND_CANCEL: &str = "cancel";
+const COMMAND_FINALIZE: &str = "finalize";
+const COMMAND_ROTATE_DIRECTLY: &str = "rotate_directly";
+
+fn get_arg<T: types::bytesrepr::FromBytes>(index: u32) -> T {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let key_recovery = system::get_key_recovery();
+
+    let command: String = get_arg(0);
+    match command.as_str() {
+        COMMAND_CONFIGURE => {
+            let recovery_keys: Vec<PublicKey> = get_arg(1);
+            let threshold: u32 = get_arg(2);
+            let delay: u64 = get_arg(3);
+            runtime::call_contract::<_, ()>(
+                key_recovery,
+                (COMMAND_CONFIGURE, recovery_keys, threshold, delay),
+            );
+        }
+        COMMAND_REQUEST => {
+            let account: PublicKey = get_arg(1);
+            let old_key: PublicKey = get_arg(2);
+            let new_key: PublicKey = get_arg(3);
+            runtime::call_contract::<_, ()>(
+                key_recovery,
+                (COMMAND_REQUEST, account, old_key, new_key),
+            );
+        }
+        COMMAND_CANCEL => {
+            runtime::call_contract::<_, ()>(key_recovery, (COMMAND_CANCEL,));
+        }
+        COMMAND_FINALIZE => {
+            let account: PublicKey = get_arg(1);
+            runtime::call_contract::<_, ()>(key_recovery, (COMMAND_FINALIZE, account));
+        }
+        COMMAND_ROTATE_DIRECTLY => {
+            let account: PublicKey = get_arg(1);
+            let old_key: PublicKey = get_arg(2);
+            let new_key: PublicKey = get_arg(3);
+            account::rotate_associated_key(account, old_key, new_key).unwrap_or_revert();
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
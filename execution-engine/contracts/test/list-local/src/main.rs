@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key};
+
+const LISTED_KEY: &str = "listed";
+const ENTRY_COUNT: u8 = 5;
+const PAGE_SIZE: u32 = 2;
+
+const METHOD_WRITE: &str = "write";
+const METHOD_LIST: &str = "list";
+const METHOD_WRITE_AND_LIST: &str = "write_and_list";
+
+fn write() {
+    for index in 0..ENTRY_COUNT {
+        storage::write_local(index, u64::from(index) * 10);
+    }
+}
+
+// Pages through the whole local state and stores the values found under a named key.
+fn list() {
+    let mut values = Vec::new();
+    let mut cursor: Option<Key> = None;
+    loop {
+        let page = storage::list_local(cursor, PAGE_SIZE);
+        if page.is_empty() {
+            break;
+        }
+        if page.len() > PAGE_SIZE as usize {
+            runtime::revert(ApiError::User(0));
+        }
+        cursor = page.last().map(|(key, _)| *key);
+        for (_, cl_value) in page {
+            let value: u64 = cl_value.into_t().unwrap_or_revert();
+            values.push(value);
+        }
+    }
+    values.sort();
+    runtime::put_key(LISTED_KEY, storage::new_uref(values).into());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method.as_str() {
+        METHOD_WRITE => write(),
+        METHOD_LIST => list(),
+        METHOD_WRITE_AND_LIST => {
+            write();
+            list();
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,35 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::contract_api::{account, runtime, system};
+use types::{ApiError, URef};
+
+const PURSE_1: &str = "purse_1";
+const PURSE_2: &str = "purse_2";
+
+#[repr(u16)]
+enum Error {
+    UnexpectedPurses = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let purses_before = account::list_purses();
+
+    let purse_1 = system::create_purse();
+    runtime::put_key(PURSE_1, purse_1.into());
+    let purse_2 = system::create_purse();
+    runtime::put_key(PURSE_2, purse_2.into());
+
+    let mut expected_purses: Vec<URef> = purses_before;
+    expected_purses.push(purse_1.remove_access_rights());
+    expected_purses.push(purse_2.remove_access_rights());
+
+    if account::list_purses() != expected_purses {
+        runtime::revert(ApiError::User(Error::UnexpectedPurses as u16));
+    }
+}
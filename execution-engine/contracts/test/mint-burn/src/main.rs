@@ -0,0 +1,17 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, U512};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    system::burn(account::get_main_purse(), amount).unwrap_or_revert();
+}
@@ -0,0 +1,23 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{system_contract_errors::mint, ApiError, U512};
+
+/// Transfers the given amount out of the account's main purse by calling the mint's `transfer`
+/// method directly rather than through the transfer host functions.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let source = account::get_main_purse();
+    let target = system::create_purse();
+    let result: Result<(), mint::Error> =
+        runtime::call_contract(system::get_mint(), ("transfer", source, target, amount));
+    result.unwrap_or_revert();
+}
@@ -0,0 +1,55 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, ApiError, Key, URef, U512};
+
+const GET_PAYMENT_PURSE: &str = "get_payment_purse";
+const PUT_KEY: &str = "put_key";
+const NEW_UREF: &str = "new_uref";
+const TRANSFER_TO_ACCOUNT: &str = "transfer_to_account";
+const PAYMENT_NAMED_KEY: &str = "payment_named_key";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+
+#[repr(u16)]
+enum Error {
+    UnknownAction = 0,
+}
+
+enum Arg {
+    Amount = 0,
+    Action,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(Arg::Amount as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let action: String = runtime::get_arg(Arg::Action as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let main_purse: URef = account::get_main_purse();
+    let pos_pointer = system::get_proof_of_stake();
+    let payment_purse: URef = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+    system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert();
+
+    match action.as_str() {
+        PUT_KEY => runtime::put_key(PAYMENT_NAMED_KEY, Key::Account(ACCOUNT_1_ADDR)),
+        NEW_UREF => {
+            storage::new_uref(action.clone());
+        }
+        TRANSFER_TO_ACCOUNT => {
+            system::transfer_to_account(ACCOUNT_1_ADDR, U512::one()).unwrap_or_revert();
+        }
+        _ => runtime::revert(ApiError::User(Error::UnknownAction as u16)),
+    }
+}
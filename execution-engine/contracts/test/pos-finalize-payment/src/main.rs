@@ -7,6 +7,8 @@ use contract::{
 };
 use types::{account::PublicKey, ApiError, ContractRef, Key, URef, U512};
 
+const FULL_REFUND_RATIO: u8 = 100;
+
 fn set_refund_purse(pos: &ContractRef, p: &URef) {
     runtime::call_contract(pos.clone(), ("set_refund_purse", *p))
 }
@@ -22,7 +24,10 @@ fn submit_payment(pos: &ContractRef, amount: U512) {
 }
 
 fn finalize_payment(pos: &ContractRef, amount_spent: U512, account: PublicKey) {
-    runtime::call_contract(pos.clone(), ("finalize_payment", amount_spent, account))
+    runtime::call_contract(
+        pos.clone(),
+        ("finalize_payment", amount_spent, account, FULL_REFUND_RATIO),
+    )
 }
 
 #[no_mangle]
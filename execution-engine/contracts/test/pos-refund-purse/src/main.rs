@@ -5,7 +5,7 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, ContractRef, URef, U512};
+use types::{ApiError, ContractRef, Key, URef, U512};
 
 #[repr(u16)]
 enum Error {
@@ -13,6 +13,17 @@ enum Error {
     NotFound,
     Invalid,
     IncorrectAccessRights,
+    MissingRefundPurse,
+}
+
+// Payment code can't create purses, so the refund purses are created beforehand
+const REFUND_PURSE_1: &str = "refund_purse_1";
+const REFUND_PURSE_2: &str = "refund_purse_2";
+
+fn get_named_purse(name: &str) -> URef {
+    runtime::get_key(name)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert_with(ApiError::User(Error::MissingRefundPurse as u16))
 }
 
 fn set_refund_purse(pos: &ContractRef, p: &URef) {
@@ -37,7 +48,7 @@ fn submit_payment(pos: &ContractRef, amount: U512) {
 pub extern "C" fn call() {
     let pos_pointer = system::get_proof_of_stake();
 
-    let refund_purse = system::create_purse();
+    let refund_purse = get_named_purse(REFUND_PURSE_1);
     {
         // get_refund_purse should return None before setting it
         let refund_result = get_refund_purse(&pos_pointer);
@@ -59,7 +70,7 @@ pub extern "C" fn call() {
         }
     }
     {
-        let refund_purse = system::create_purse();
+        let refund_purse = get_named_purse(REFUND_PURSE_2);
         // get_refund_purse should return correct value after setting a second time
         set_refund_purse(&pos_pointer, &refund_purse);
         match get_refund_purse(&pos_pointer) {
@@ -0,0 +1,24 @@
+#![no_std]
+#![no_main]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::ApiError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let expected_bytes: [u8; 32] = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let first_bytes = runtime::random_bytes();
+    let second_bytes = runtime::random_bytes();
+
+    assert_eq!(
+        first_bytes, expected_bytes,
+        "random bytes not expected random bytes"
+    );
+    assert_ne!(
+        first_bytes, second_bytes,
+        "consecutive random bytes should differ"
+    );
+}
@@ -0,0 +1,59 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, ContractRef, Key};
+
+fn recurse(contract_key: Key, depth: u32, guarded: bool) {
+    let contract_hash = contract_key
+        .into_hash()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    runtime::call_contract::<_, ()>(
+        ContractRef::Hash(contract_hash),
+        (contract_key, depth, guarded),
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn recurse_ext() {
+    let contract_key: Key = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let depth: u32 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let guarded: bool = runtime::get_arg(2)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    if guarded {
+        runtime::guard_reentrancy();
+    }
+
+    if depth > 1 {
+        recurse(contract_key, depth - 1, guarded);
+    }
+}
+
+/// Calls a stored contract which calls itself until `depth` nested calls have been made.
+#[no_mangle]
+pub extern "C" fn call() {
+    let depth: u32 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let guarded: bool = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let contract_key: Key = storage::store_function_at_hash("recurse_ext", BTreeMap::new()).into();
+    if depth > 0 {
+        recurse(contract_key, depth, guarded);
+    }
+}
@@ -0,0 +1,54 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, BlockTime, URef};
+
+const VALUE: &str = "value";
+const CONTRACT: &str = "contract";
+const SET_VALUE: &str = "set_value_ext";
+const SCHEDULED_VALUE: u64 = 42;
+
+#[no_mangle]
+pub extern "C" fn set_value_ext() {
+    let value: u64 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let value_uref: URef = runtime::get_key(VALUE)
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(ApiError::GetKey);
+    storage::write(value_uref, value);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let earliest_blocktime: u64 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let gas_budget: u64 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let value = storage::new_uref(0u64);
+    runtime::put_key(VALUE, value.into());
+
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(String::from(VALUE), value.into());
+    let contract = storage::store_function_at_hash(SET_VALUE, named_keys);
+    runtime::put_key(CONTRACT, contract.clone().into());
+
+    runtime::schedule_call(
+        contract,
+        (SCHEDULED_VALUE,),
+        BlockTime::new(earliest_blocktime),
+        gas_budget,
+    )
+    .unwrap_or_revert();
+}
@@ -0,0 +1,77 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLType, CLTyped, ContractAbi, EntryPoint, Parameter, U512};
+
+const CONTRACT_WITH_ABI_NAME: &str = "contract_with_abi";
+const CONTRACT_WITHOUT_ABI_NAME: &str = "contract_without_abi";
+const DESTINATION_HASH: &str = "hash";
+const DESTINATION_UREF: &str = "uref";
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+
+#[repr(u16)]
+enum Error {
+    UnknownDestination = 1,
+    AbiMismatch = 2,
+    UnexpectedAbi = 3,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+enum Arg {
+    Destination = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn delegate() {}
+
+fn abi() -> ContractAbi {
+    let deposit = EntryPoint::new(
+        String::from("deposit"),
+        vec![Parameter::new(String::from("amount"), U512::cl_type())],
+        CLType::Unit,
+    );
+    let balance = EntryPoint::new(String::from("balance"), vec![], U512::cl_type());
+    ContractAbi::new(vec![deposit, balance])
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let destination: String = runtime::get_arg(Arg::Destination as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let (with_abi, without_abi) = match destination.as_str() {
+        DESTINATION_HASH => (
+            storage::store_function_at_hash_with_abi(ENTRY_FUNCTION_NAME, BTreeMap::new(), abi()),
+            storage::store_function_at_hash(ENTRY_FUNCTION_NAME, BTreeMap::new()),
+        ),
+        DESTINATION_UREF => (
+            storage::store_function_with_abi(ENTRY_FUNCTION_NAME, BTreeMap::new(), abi()),
+            storage::store_function(ENTRY_FUNCTION_NAME, BTreeMap::new()),
+        ),
+        _ => runtime::revert(Error::UnknownDestination),
+    };
+
+    if runtime::get_contract_abi(with_abi.clone()) != Some(abi()) {
+        runtime::revert(Error::AbiMismatch);
+    }
+    if runtime::get_contract_abi(without_abi.clone()).is_some() {
+        runtime::revert(Error::UnexpectedAbi);
+    }
+
+    runtime::put_key(CONTRACT_WITH_ABI_NAME, with_abi.into());
+    runtime::put_key(CONTRACT_WITHOUT_ABI_NAME, without_abi.into());
+}
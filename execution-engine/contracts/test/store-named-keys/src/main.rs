@@ -0,0 +1,34 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, format};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key};
+
+const CONTRACT_NAME: &str = "stored_with_named_keys";
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+
+#[no_mangle]
+pub extern "C" fn delegate() {}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let count: u32 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let mut named_keys = BTreeMap::new();
+    for index in 0..count {
+        let key: Key = storage::new_uref(index).into();
+        named_keys.insert(format!("key_{}", index), key);
+    }
+
+    let key = storage::store_function_at_hash(ENTRY_FUNCTION_NAME, named_keys).into();
+    runtime::put_key(CONTRACT_NAME, key);
+}
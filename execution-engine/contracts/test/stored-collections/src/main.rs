@@ -0,0 +1,130 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{
+        runtime, storage,
+        stored::{self, Singleton, StoredMap, StoredVec},
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{bytesrepr::FromBytes, ApiError, CLTyped, Key, URef};
+
+const VEC_NAME: &str = "log";
+const MAP_NAME: &str = "totals";
+const SINGLETON_KEY: &str = "singleton";
+
+const METHOD_WRITE: &str = "write";
+const METHOD_UPDATE: &str = "update";
+const METHOD_PAGE: &str = "page";
+
+const PAGE_SIZE: u32 = 2;
+const LOG_SUM_KEY: &str = "log_sum";
+const TOTALS_SUM_KEY: &str = "totals_sum";
+const LOG_DONE_KEY: &str = "log_done";
+const TOTALS_DONE_KEY: &str = "totals_done";
+
+#[repr(u16)]
+enum Error {
+    PopMismatch = 0,
+    SingletonMismatch,
+    MissingMapValue,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+fn singleton() -> Singleton<u64> {
+    let uref: URef = runtime::get_key(SINGLETON_KEY)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    Singleton::new(uref)
+}
+
+fn write() {
+    let mut log = StoredVec::<u64>::new(VEC_NAME);
+    for value in 1..=3 {
+        log.push(value);
+    }
+
+    let mut totals = StoredMap::<String, u64>::new(MAP_NAME);
+    totals.insert(String::from("a"), 1);
+    totals.insert(String::from("b"), 2);
+
+    runtime::put_key(SINGLETON_KEY, storage::new_uref(0u64).into());
+    singleton().set(7);
+}
+
+fn update() {
+    let mut log = StoredVec::<u64>::new(VEC_NAME);
+    if log.pop() != Some(3) {
+        runtime::revert(Error::PopMismatch);
+    }
+    log.set(0, 10);
+
+    let mut totals = StoredMap::<String, u64>::new(MAP_NAME);
+    totals.remove(&String::from("a"));
+    let b = *totals
+        .get(&String::from("b"))
+        .unwrap_or_revert_with(Error::MissingMapValue);
+    totals.insert(String::from("b"), b + 1);
+
+    let mut singleton = singleton();
+    if singleton.get() != Some(&7) {
+        runtime::revert(Error::SingletonMismatch);
+    }
+    singleton.set(8);
+}
+
+fn read_local_or_default<T: Default + CLTyped + FromBytes>(key: &str) -> T {
+    storage::read_local(&key)
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+// Sums a bounded batch of each collection, persisting the cursors so that a later deploy carries on
+// where this one stopped.
+fn page() {
+    if !read_local_or_default::<bool>(LOG_DONE_KEY) {
+        let mut log = StoredVec::<u64>::new(VEC_NAME);
+        let cursor = stored::load_cursor::<u64>(VEC_NAME).unwrap_or_default();
+        let page = log.iter_from(cursor, u64::from(PAGE_SIZE));
+        let sum = read_local_or_default::<u64>(LOG_SUM_KEY) + page.items.iter().sum::<u64>();
+        storage::write_local(LOG_SUM_KEY, sum);
+        storage::write_local(LOG_DONE_KEY, page.next.is_none());
+        stored::save_cursor(VEC_NAME, page.next);
+    }
+
+    if !read_local_or_default::<bool>(TOTALS_DONE_KEY) {
+        let mut totals = StoredMap::<String, u64>::new(MAP_NAME);
+        let cursor = stored::load_cursor::<Key>(MAP_NAME);
+        let page = totals.iter_from(cursor, PAGE_SIZE);
+        let sum = read_local_or_default::<u64>(TOTALS_SUM_KEY)
+            + page.items.iter().map(|(_, value)| value).sum::<u64>();
+        storage::write_local(TOTALS_SUM_KEY, sum);
+        storage::write_local(TOTALS_DONE_KEY, page.next.is_none());
+        stored::save_cursor(MAP_NAME, page.next);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method.as_str() {
+        METHOD_WRITE => write(),
+        METHOD_UPDATE => update(),
+        METHOD_PAGE => page(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,40 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, TransferReceipt, U512};
+
+const PURSE_SECONDARY: &str = "purse:secondary";
+const TRANSFER_RECEIPT: &str = "transfer_receipt";
+
+#[repr(u16)]
+enum CustomError {
+    MissingReceipt = 0,
+    UnexpectedAmount = 1,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let main_purse = account::get_main_purse();
+    let secondary_purse = system::create_purse();
+    runtime::put_key(PURSE_SECONDARY, secondary_purse.into());
+
+    let receipt_uref =
+        system::transfer_with_proof(main_purse, secondary_purse, amount).unwrap_or_revert();
+
+    let receipt: TransferReceipt = storage::read(receipt_uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingReceipt as u16));
+    if receipt.amount() != amount {
+        runtime::revert(ApiError::User(CustomError::UnexpectedAmount as u16));
+    }
+
+    runtime::put_key(TRANSFER_RECEIPT, receipt_uref.into());
+}
@@ -0,0 +1,49 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef};
+
+const VALUE: &str = "value";
+const REVERT_ERROR: &str = "revert_error";
+const WRITE_AND_REVERT: &str = "write_and_revert_ext";
+const ADD_AND_RETURN: &str = "add_and_return_ext";
+const REVERT_CODE: u16 = 42;
+
+fn get_uref() -> URef {
+    runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+#[no_mangle]
+pub extern "C" fn write_and_revert_ext() {
+    storage::write(get_uref(), 1u64);
+    runtime::revert(ApiError::User(REVERT_CODE))
+}
+
+#[no_mangle]
+pub extern "C" fn add_and_return_ext() {
+    storage::add(get_uref(), 10u64);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value = storage::new_uref(0u64);
+    runtime::put_key(VALUE, value.into());
+
+    let reverting = storage::store_function_at_hash(WRITE_AND_REVERT, BTreeMap::new());
+    let result: Result<(), ApiError> = runtime::try_call_contract(reverting, (value,));
+    let error = result.err().unwrap_or_revert_with(ApiError::Unhandled);
+    runtime::put_key(REVERT_ERROR, storage::new_uref(u32::from(error)).into());
+
+    let returning = storage::store_function_at_hash(ADD_AND_RETURN, BTreeMap::new());
+    runtime::try_call_contract::<_, ()>(returning, (value,)).unwrap_or_revert();
+}
@@ -0,0 +1,28 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const INVALID_ARG_ERROR: &str = "invalid_arg_error";
+const MISSING_ARG_ERROR: &str = "missing_arg_error";
+const FALLBACK: &str = "fallback";
+const DEFAULT_VALUE: u64 = 7;
+
+fn put_error(name: &str, result: Result<u64, ApiError>) {
+    let error = result.err().unwrap_or_revert_with(ApiError::Unhandled);
+    runtime::put_key(name, storage::new_uref(u32::from(error)).into());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // The first argument is a `String`, and there's no second argument.
+    put_error(INVALID_ARG_ERROR, runtime::try_get_arg(0));
+    put_error(MISSING_ARG_ERROR, runtime::try_get_arg(1));
+
+    let value: u64 = runtime::try_get_arg(1).unwrap_or(DEFAULT_VALUE);
+    runtime::put_key(FALLBACK, storage::new_uref(value).into());
+}
@@ -0,0 +1,64 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec};
+
+use contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{ActionType, AssociatedKeysUpdate, PublicKey, Weight},
+    ApiError,
+};
+
+const COMMAND_ROTATE: &str = "rotate";
+const COMMAND_REMOVE_ALL: &str = "remove_all";
+const COMMAND_PARTIAL: &str = "partial";
+
+const MISSING_KEY: PublicKey = PublicKey::ed25519_from([255; 32]);
+
+#[repr(u16)]
+enum Error {
+    UnexpectedResult = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let new_key: PublicKey = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let caller = runtime::get_caller();
+
+    match command.as_str() {
+        // Replaces the caller's key and raises both thresholds, passing through states where the
+        // account has no keys and where the deployment threshold exceeds key management
+        COMMAND_ROTATE => account::update_associated_keys(vec![
+            AssociatedKeysUpdate::Remove(caller),
+            AssociatedKeysUpdate::SetThreshold(ActionType::Deployment, Weight::new(2)),
+            AssociatedKeysUpdate::SetThreshold(ActionType::KeyManagement, Weight::new(3)),
+            AssociatedKeysUpdate::Add(new_key, Weight::new(3)),
+        ])
+        .unwrap_or_revert(),
+        COMMAND_REMOVE_ALL => {
+            account::update_associated_keys(vec![AssociatedKeysUpdate::Remove(caller)])
+                .unwrap_or_revert()
+        }
+        // Fails on the second update; the first must not be applied either
+        COMMAND_PARTIAL => {
+            let result = account::update_associated_keys(vec![
+                AssociatedKeysUpdate::Add(new_key, Weight::new(1)),
+                AssociatedKeysUpdate::Remove(MISSING_KEY),
+            ]);
+            if result != Err(ApiError::MissingKey) {
+                runtime::revert(ApiError::User(Error::UnexpectedResult as u16));
+            }
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
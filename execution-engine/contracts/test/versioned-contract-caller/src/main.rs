@@ -0,0 +1,47 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, Key};
+
+#[repr(u16)]
+enum Error {
+    UnexpectedValue = 1,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+enum Arg {
+    ContractKey = 0,
+    EntryPoint = 1,
+    ExpectedValue = 2,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_key: Key = runtime::get_arg(Arg::ContractKey as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let entry_point: String = runtime::get_arg(Arg::EntryPoint as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let expected_value: u64 = runtime::get_arg(Arg::ExpectedValue as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let contract = contract_key
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    let value: u64 = runtime::call_versioned_contract(contract, &entry_point, ());
+    if value != expected_value {
+        runtime::revert(Error::UnexpectedValue);
+    }
+}
@@ -0,0 +1,66 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::PublicKey, ApiError, CLType, CLValue, ContractAbi, EntryPoint, EntryPointAccess,
+};
+
+const CONTRACT_NAME: &str = "versioned_contract";
+const ADMINS_GROUP: &str = "admins";
+const PUBLIC_ENTRY_POINT: &str = "public_value";
+const ADMINS_ENTRY_POINT: &str = "admins_value";
+const INSTALLER_ENTRY_POINT: &str = "installer_value";
+
+enum Arg {
+    Admin = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn public_value() {
+    runtime::ret(CLValue::from_t(1u64).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn admins_value() {
+    runtime::ret(CLValue::from_t(2u64).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn installer_value() {
+    runtime::ret(CLValue::from_t(3u64).unwrap_or_revert())
+}
+
+fn entry_point(name: &str, access: EntryPointAccess) -> EntryPoint {
+    EntryPoint::new(String::from(name), vec![], CLType::U64).with_access(access)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let admin: PublicKey = runtime::get_arg(Arg::Admin as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let installer = runtime::get_caller();
+
+    let entry_points = vec![
+        entry_point(PUBLIC_ENTRY_POINT, EntryPointAccess::Public),
+        entry_point(
+            ADMINS_ENTRY_POINT,
+            EntryPointAccess::Groups(vec![String::from(ADMINS_GROUP)]),
+        ),
+        entry_point(INSTALLER_ENTRY_POINT, EntryPointAccess::Caller(installer)),
+    ];
+    let mut groups = BTreeMap::new();
+    groups.insert(String::from(ADMINS_GROUP), vec![installer, admin]);
+    let abi = ContractAbi::new(entry_points).with_groups(groups);
+
+    let contract = storage::store_versioned_contract(BTreeMap::new(), abi);
+    runtime::put_key(CONTRACT_NAME, contract.into());
+}
@@ -20,6 +20,15 @@ impl Package for StandardPayment {
     const WASM_FILENAME: &'static str = "standard_payment_install.wasm";
 }
 
+struct KeyRecovery;
+
+impl Package for KeyRecovery {
+    const ROOT: &'static str = "../contracts/system/key-recovery-install";
+    const CARGO_TOML: &'static str = "../contracts/system/key-recovery-install/Cargo.toml";
+    const MAIN_RS: &'static str = "../contracts/system/key-recovery-install/src/main.rs";
+    const WASM_FILENAME: &'static str = "key_recovery_install.wasm";
+}
+
 const TARGET_DIR_FOR_WASM: &str = "target/built-contracts";
 const ORIGINAL_WASM_DIR: &str = "wasm32-unknown-unknown/release";
 const NEW_WASM_DIR: &str = "wasm";
@@ -87,4 +96,12 @@ fn main() {
     } else {
         assert_wasm_file_exists::<StandardPayment>();
     }
+
+    let key_recovery_source_exists = Path::new(KeyRecovery::CARGO_TOML).is_file();
+
+    if key_recovery_source_exists {
+        build_package::<KeyRecovery>();
+    } else {
+        assert_wasm_file_exists::<KeyRecovery>();
+    }
 }
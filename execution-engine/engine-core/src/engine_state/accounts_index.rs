@@ -0,0 +1,331 @@
+//! A secondary index mapping each account to the balance of its main purse.
+//!
+//! The index is a trie of its own, kept in the same trie store as global state, with two kinds of
+//! entries:
+//!
+//! * `Key::Account(public_key)` maps to the balance of the account's main purse, and
+//! * the balance key of that purse maps back to `public_key`, so that a commit which only touches a
+//!   balance can be traced to the account owning it.
+//!
+//! Its root hash is recorded in global state under [`ACCOUNTS_INDEX_ROOT_KEY`], so a light client
+//! holding a trusted post-state hash can verify a balance with an [`AccountBalanceProof`]: a proof
+//! of the index root in global state followed by a proof of the balance in the index, neither of
+//! which depends on how deeply nested the account's named keys are.
+//!
+//! Index tries are not reachable from global state tries, so they are not reported by
+//! `missing_trie_keys`.
+
+use std::{collections::BTreeSet, convert::TryFrom};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_storage::{
+    global_state::{CommitResult, StateProvider, StateReader},
+    trie::merkle_proof::TrieMerkleProof,
+};
+use types::{account::PublicKey, CLValue, Key, URef, KEY_HASH_LENGTH, U512};
+
+use crate::{
+    engine_state::Error,
+    execution,
+    tracking_copy::{TrackingCopy, TrackingCopyExt},
+};
+
+/// The global state key under which the root hash of the accounts index is stored.
+pub const ACCOUNTS_INDEX_ROOT_KEY: Key = Key::Hash([255; KEY_HASH_LENGTH]);
+
+/// Proof that an account's main purse held a given balance at a given post-state hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountBalanceProof {
+    index_root_proof: TrieMerkleProof<Key, StoredValue>,
+    balance_proof: TrieMerkleProof<Key, StoredValue>,
+}
+
+impl AccountBalanceProof {
+    pub fn new(
+        index_root_proof: TrieMerkleProof<Key, StoredValue>,
+        balance_proof: TrieMerkleProof<Key, StoredValue>,
+    ) -> Self {
+        AccountBalanceProof {
+            index_root_proof,
+            balance_proof,
+        }
+    }
+
+    /// Proof of the accounts index root hash within global state.
+    pub fn index_root_proof(&self) -> &TrieMerkleProof<Key, StoredValue> {
+        &self.index_root_proof
+    }
+
+    /// Proof of the account's balance within the accounts index.
+    pub fn balance_proof(&self) -> &TrieMerkleProof<Key, StoredValue> {
+        &self.balance_proof
+    }
+
+    /// Checks the proof against a trusted `state_hash` and returns the proven balance of
+    /// `account`'s main purse.
+    pub fn verify(&self, state_hash: Blake2bHash, account: PublicKey) -> Result<U512, Error> {
+        if *self.index_root_proof.key() != ACCOUNTS_INDEX_ROOT_KEY
+            || self.index_root_proof.compute_state_hash()? != state_hash
+        {
+            return Err(Error::InvalidAccountBalanceProof);
+        }
+        let index_root: [u8; KEY_HASH_LENGTH] = cl_value(self.index_root_proof.value())?;
+
+        if *self.balance_proof.key() != Key::Account(account)
+            || self.balance_proof.compute_state_hash()? != index_root.into()
+        {
+            return Err(Error::InvalidAccountBalanceProof);
+        }
+        cl_value(self.balance_proof.value())
+    }
+}
+
+fn cl_value<T: types::CLTyped + types::bytesrepr::FromBytes>(
+    stored_value: &StoredValue,
+) -> Result<T, Error> {
+    let cl_value =
+        CLValue::try_from(stored_value.clone()).map_err(execution::Error::TypeMismatch)?;
+    Ok(cl_value.into_t().map_err(execution::Error::from)?)
+}
+
+fn write_cl_value<T: types::CLTyped + types::bytesrepr::ToBytes>(
+    value: T,
+) -> Result<Transform, Error> {
+    let cl_value = CLValue::from_t(value).map_err(execution::Error::from)?;
+    Ok(Transform::Write(StoredValue::CLValue(cl_value)))
+}
+
+fn read<R>(
+    correlation_id: CorrelationId,
+    reader: &R,
+    key: &Key,
+) -> Result<Option<StoredValue>, Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    reader
+        .read(correlation_id, key)
+        .map_err(|error| Error::Exec(error.into()))
+}
+
+/// Commits `effects` to `pre_state_hash` and brings the accounts index up to date with the
+/// accounts and balances they touch.
+///
+/// The index is left untouched if `mint` is `None`, i.e. when the protocol data is unknown.
+pub fn commit<S>(
+    state: &S,
+    correlation_id: CorrelationId,
+    mint: Option<URef>,
+    pre_state_hash: Blake2bHash,
+    effects: AdditiveMap<Key, Transform>,
+) -> Result<CommitResult, Error>
+where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+{
+    let touched_keys: Vec<Key> = effects.keys().map(|key| key.normalize()).collect();
+
+    let (state_root, bonded_validators, mut stats) = match state
+        .commit(correlation_id, pre_state_hash, effects)
+        .map_err(|error| Error::Exec(error.into()))?
+    {
+        CommitResult::Success {
+            state_root,
+            bonded_validators,
+            stats,
+        } => (state_root, bonded_validators, stats),
+        commit_result => return Ok(commit_result),
+    };
+
+    let mint = match mint {
+        Some(mint) => mint,
+        None => {
+            return Ok(CommitResult::Success {
+                state_root,
+                bonded_validators,
+                stats,
+            })
+        }
+    };
+
+    // The stats cover the index bookkeeping too, as it also grows the global state
+    match update(state, correlation_id, mint, state_root, &touched_keys)? {
+        CommitResult::Success {
+            state_root,
+            stats: index_stats,
+            ..
+        } => {
+            stats.accumulate(index_stats);
+            Ok(CommitResult::Success {
+                state_root,
+                bonded_validators,
+                stats,
+            })
+        }
+        commit_result => Ok(commit_result),
+    }
+}
+
+fn update<S>(
+    state: &S,
+    correlation_id: CorrelationId,
+    mint: URef,
+    state_root: Blake2bHash,
+    touched_keys: &[Key],
+) -> Result<CommitResult, Error>
+where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+{
+    let reader = match state
+        .checkout(state_root)
+        .map_err(|error| Error::Exec(error.into()))?
+    {
+        Some(reader) => reader,
+        None => return Ok(CommitResult::RootNotFound),
+    };
+    let index_root: Blake2bHash = match read(correlation_id, &reader, &ACCOUNTS_INDEX_ROOT_KEY)? {
+        Some(stored_value) => cl_value::<[u8; KEY_HASH_LENGTH]>(&stored_value)?.into(),
+        None => state.empty_root(),
+    };
+    let index = match state
+        .checkout(index_root)
+        .map_err(|error| Error::Exec(error.into()))?
+    {
+        Some(index) => index,
+        None => return Ok(CommitResult::RootNotFound),
+    };
+
+    let mut accounts = BTreeSet::new();
+    for key in touched_keys {
+        match key {
+            Key::Account(public_key) => {
+                accounts.insert(*public_key);
+            }
+            _ => {
+                if let Some(stored_value) = read(correlation_id, &index, key)? {
+                    accounts.insert(cl_value::<PublicKey>(&stored_value)?);
+                }
+            }
+        }
+    }
+    if accounts.is_empty() {
+        return Ok(CommitResult::Success {
+            state_root,
+            bonded_validators: Default::default(),
+            stats: Default::default(),
+        });
+    }
+
+    let mut tracking_copy = TrackingCopy::new(reader);
+    let mut index_effects = AdditiveMap::new();
+    for public_key in accounts {
+        let account = match tracking_copy
+            .get(correlation_id, &Key::Account(public_key))
+            .map_err(|error| Error::Exec(error.into()))?
+        {
+            Some(StoredValue::Account(account)) => account,
+            _ => continue,
+        };
+        let balance_key = match tracking_copy.get_purse_balance_key(
+            correlation_id,
+            mint,
+            Key::URef(account.main_purse()),
+        ) {
+            Ok(balance_key) => balance_key.normalize(),
+            // The main purse is not (yet) known to the mint.
+            Err(execution::Error::URefNotFound(_)) => continue,
+            Err(error) => return Err(error.into()),
+        };
+        let balance = tracking_copy.get_purse_balance(correlation_id, balance_key)?;
+
+        index_effects.insert(Key::Account(public_key), write_cl_value(balance.value())?);
+        index_effects.insert(balance_key, write_cl_value(public_key)?);
+    }
+
+    let (index_root, mut stats) = match state
+        .commit(correlation_id, index_root, index_effects)
+        .map_err(|error| Error::Exec(error.into()))?
+    {
+        CommitResult::Success {
+            state_root, stats, ..
+        } => (state_root, stats),
+        commit_result => return Ok(commit_result),
+    };
+
+    let mut root_effects = AdditiveMap::new();
+    root_effects.insert(ACCOUNTS_INDEX_ROOT_KEY, write_cl_value(index_root.value())?);
+    match state
+        .commit(correlation_id, state_root, root_effects)
+        .map_err(|error| Error::Exec(error.into()))?
+    {
+        CommitResult::Success {
+            state_root,
+            bonded_validators,
+            stats: root_stats,
+        } => {
+            stats.accumulate(root_stats);
+            Ok(CommitResult::Success {
+                state_root,
+                bonded_validators,
+                stats,
+            })
+        }
+        commit_result => Ok(commit_result),
+    }
+}
+
+/// Builds a proof of the balance of `account`'s main purse at `state_hash`.
+///
+/// Returns `None` if the account is not in the index.
+pub fn prove_balance<S>(
+    state: &S,
+    correlation_id: CorrelationId,
+    state_hash: Blake2bHash,
+    account: PublicKey,
+) -> Result<Option<AccountBalanceProof>, Error>
+where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+{
+    if state
+        .checkout(state_hash)
+        .map_err(|error| Error::Exec(error.into()))?
+        .is_none()
+    {
+        return Err(Error::MissingRoot(state_hash));
+    }
+    let index_root_proof =
+        match read_with_proof(state, correlation_id, state_hash, &ACCOUNTS_INDEX_ROOT_KEY)? {
+            Some(proof) => proof,
+            None => return Ok(None),
+        };
+    let index_root: Blake2bHash =
+        cl_value::<[u8; KEY_HASH_LENGTH]>(index_root_proof.value())?.into();
+
+    Ok(
+        read_with_proof(state, correlation_id, index_root, &Key::Account(account))?
+            .map(|balance_proof| AccountBalanceProof::new(index_root_proof, balance_proof)),
+    )
+}
+
+fn read_with_proof<S>(
+    state: &S,
+    correlation_id: CorrelationId,
+    state_hash: Blake2bHash,
+    key: &Key,
+) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Error>
+where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+{
+    state
+        .read_with_proof(correlation_id, state_hash, key)
+        .map_err(|error| Error::Exec(error.into()))
+}
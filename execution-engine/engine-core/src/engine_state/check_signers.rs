@@ -0,0 +1,294 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+};
+
+use hex_fmt::HexFmt;
+
+use engine_shared::{account::Account, newtypes::Blake2bHash};
+use types::account::{PublicKey, Weight};
+
+pub enum CheckSignersResult {
+    RootNotFound,
+    AccountNotFound(PublicKey),
+    Success(SignersCheck),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckSignersRequest {
+    state_hash: Blake2bHash,
+    account: PublicKey,
+    signers: BTreeSet<PublicKey>,
+}
+
+impl CheckSignersRequest {
+    pub fn new(state_hash: Blake2bHash, account: PublicKey, signers: BTreeSet<PublicKey>) -> Self {
+        CheckSignersRequest {
+            state_hash,
+            account,
+            signers,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn account(&self) -> PublicKey {
+        self.account
+    }
+
+    pub fn signers(&self) -> &BTreeSet<PublicKey> {
+        &self.signers
+    }
+}
+
+/// How a candidate set of signing keys measures up against an account's action thresholds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignersCheck {
+    total_weight: Weight,
+    deployment_threshold: Weight,
+    key_management_threshold: Weight,
+    unknown_keys: BTreeSet<PublicKey>,
+    authorized: bool,
+}
+
+impl SignersCheck {
+    pub fn new(account: &Account, signers: &BTreeSet<PublicKey>) -> Self {
+        let total_weight = account.calculate_keys_weight(signers);
+        let action_thresholds = account.action_thresholds();
+        let unknown_keys = signers
+            .iter()
+            .filter(|key| account.get_associated_key_weight(**key).is_none())
+            .cloned()
+            .collect();
+
+        SignersCheck {
+            total_weight,
+            deployment_threshold: *action_thresholds.deployment(),
+            key_management_threshold: *action_thresholds.key_management(),
+            unknown_keys,
+            authorized: account.can_authorize(signers),
+        }
+    }
+
+    /// The combined weight of the signers which are associated keys of the account.
+    pub fn total_weight(&self) -> Weight {
+        self.total_weight
+    }
+
+    pub fn deployment_threshold(&self) -> Weight {
+        self.deployment_threshold
+    }
+
+    pub fn key_management_threshold(&self) -> Weight {
+        self.key_management_threshold
+    }
+
+    /// Signers which are not associated keys of the account.  A deploy signed by any of these is
+    /// rejected regardless of the combined weight.
+    pub fn unknown_keys(&self) -> &BTreeSet<PublicKey> {
+        &self.unknown_keys
+    }
+
+    /// Whether a deploy signed by exactly these keys would pass the deployment threshold.
+    pub fn can_deploy(&self) -> bool {
+        self.authorized && self.total_weight >= self.deployment_threshold
+    }
+
+    /// Whether a deploy signed by exactly these keys would be permitted to manage the account's
+    /// associated keys and thresholds.
+    pub fn can_manage_keys(&self) -> bool {
+        self.can_deploy() && self.total_weight >= self.key_management_threshold
+    }
+}
+
+/// Why the keys a deploy was signed with failed to authorize it, reported as a precondition
+/// failure so that a multisig user can see which co-signers are missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationFailure {
+    recognized_keys: BTreeMap<PublicKey, Weight>,
+    unknown_keys: BTreeSet<PublicKey>,
+    total_weight: Weight,
+    required_weight: Weight,
+}
+
+impl AuthorizationFailure {
+    pub fn new(account: &Account, authorization_keys: &BTreeSet<PublicKey>) -> Self {
+        let mut recognized_keys = BTreeMap::new();
+        let mut unknown_keys = BTreeSet::new();
+        for key in authorization_keys {
+            match account.get_associated_key_weight(*key) {
+                Some(weight) => {
+                    recognized_keys.insert(*key, *weight);
+                }
+                None => {
+                    unknown_keys.insert(*key);
+                }
+            }
+        }
+
+        AuthorizationFailure {
+            recognized_keys,
+            unknown_keys,
+            total_weight: account.calculate_keys_weight(authorization_keys),
+            required_weight: *account.action_thresholds().deployment(),
+        }
+    }
+
+    /// Signing keys which are associated keys of the account, with their weights.
+    pub fn recognized_keys(&self) -> &BTreeMap<PublicKey, Weight> {
+        &self.recognized_keys
+    }
+
+    /// Signing keys which are not associated keys of the account.  Any of these causes the deploy
+    /// to be rejected regardless of weight.
+    pub fn unknown_keys(&self) -> &BTreeSet<PublicKey> {
+        &self.unknown_keys
+    }
+
+    /// The combined weight of the recognized keys.
+    pub fn total_weight(&self) -> Weight {
+        self.total_weight
+    }
+
+    /// The account's deployment threshold.
+    pub fn required_weight(&self) -> Weight {
+        self.required_weight
+    }
+
+    /// How much more weight the deploy needs to be signed with to meet the threshold.
+    pub fn shortfall(&self) -> Weight {
+        Weight::new(
+            self.required_weight
+                .value()
+                .saturating_sub(self.total_weight.value()),
+        )
+    }
+
+    /// Whether the failure is due to unknown keys or no keys at all, rather than only to
+    /// insufficient weight.
+    pub fn is_unauthorized_keys(&self) -> bool {
+        !self.unknown_keys.is_empty() || self.recognized_keys.is_empty()
+    }
+}
+
+impl Display for AuthorizationFailure {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.is_unauthorized_keys() {
+            write!(f, "Authorization failure: not authorized.")?;
+        } else {
+            write!(f, "Deployment authorization failure.")?;
+        }
+        write!(
+            f,
+            " Signed with weight {} of required {} (short by {}); recognized keys: [",
+            self.total_weight.value(),
+            self.required_weight.value(),
+            self.shortfall().value()
+        )?;
+        for (index, (key, weight)) in self.recognized_keys.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", HexFmt(key.as_bytes()), weight.value())?;
+        }
+        write!(f, "]; unknown keys: [")?;
+        for (index, key) in self.unknown_keys.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", HexFmt(key.as_bytes()))?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::FromIterator;
+
+    use engine_shared::account::{ActionThresholds, AssociatedKeys};
+    use types::{account::ActionType, AccessRights, URef};
+
+    use super::*;
+
+    const KEY_1: PublicKey = PublicKey::ed25519_from([1; 32]);
+    const KEY_2: PublicKey = PublicKey::ed25519_from([2; 32]);
+    const KEY_3: PublicKey = PublicKey::ed25519_from([3; 32]);
+
+    fn account() -> Account {
+        let mut associated_keys = AssociatedKeys::new(KEY_1, Weight::new(2));
+        associated_keys.add_key(KEY_2, Weight::new(3)).unwrap();
+        Account::new(
+            KEY_1,
+            Default::default(),
+            URef::new([0; 32], AccessRights::READ_ADD_WRITE),
+            associated_keys,
+            ActionThresholds::new(Weight::new(2), Weight::new(5)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn should_meet_deployment_threshold_only() {
+        let check = SignersCheck::new(&account(), &BTreeSet::from_iter(vec![KEY_1]));
+        assert_eq!(check.total_weight(), Weight::new(2));
+        assert!(check.unknown_keys().is_empty());
+        assert!(check.can_deploy());
+        assert!(!check.can_manage_keys());
+    }
+
+    #[test]
+    fn should_meet_key_management_threshold_with_combined_weight() {
+        let check = SignersCheck::new(&account(), &BTreeSet::from_iter(vec![KEY_1, KEY_2]));
+        assert_eq!(check.total_weight(), Weight::new(5));
+        assert!(check.can_deploy());
+        assert!(check.can_manage_keys());
+    }
+
+    #[test]
+    fn should_reject_unknown_signer() {
+        let check = SignersCheck::new(&account(), &BTreeSet::from_iter(vec![KEY_1, KEY_2, KEY_3]));
+        assert_eq!(check.total_weight(), Weight::new(5));
+        assert_eq!(check.unknown_keys(), &BTreeSet::from_iter(vec![KEY_3]));
+        assert!(!check.can_deploy());
+        assert!(!check.can_manage_keys());
+    }
+
+    #[test]
+    fn should_reject_empty_signers() {
+        let check = SignersCheck::new(&account(), &BTreeSet::new());
+        assert_eq!(check.total_weight(), Weight::new(0));
+        assert!(!check.can_deploy());
+    }
+
+    #[test]
+    fn should_report_recognized_and_unknown_keys() {
+        let failure =
+            AuthorizationFailure::new(&account(), &BTreeSet::from_iter(vec![KEY_2, KEY_3]));
+        assert_eq!(
+            failure.recognized_keys(),
+            &BTreeMap::from_iter(vec![(KEY_2, Weight::new(3))])
+        );
+        assert_eq!(failure.unknown_keys(), &BTreeSet::from_iter(vec![KEY_3]));
+        assert_eq!(failure.total_weight(), Weight::new(3));
+        assert_eq!(failure.shortfall(), Weight::new(0));
+        assert!(failure.is_unauthorized_keys());
+    }
+
+    #[test]
+    fn should_report_shortfall() {
+        let mut account = account();
+        account
+            .set_action_threshold(ActionType::Deployment, Weight::new(4))
+            .unwrap();
+        let failure = AuthorizationFailure::new(&account, &BTreeSet::from_iter(vec![KEY_1]));
+        assert_eq!(failure.total_weight(), Weight::new(2));
+        assert_eq!(failure.required_weight(), Weight::new(4));
+        assert_eq!(failure.shortfall(), Weight::new(2));
+        assert!(!failure.is_unauthorized_keys());
+        assert!(failure
+            .to_string()
+            .starts_with("Deployment authorization failure."));
+    }
+}
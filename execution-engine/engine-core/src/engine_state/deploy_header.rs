@@ -0,0 +1,371 @@
+//! Validation of deploy headers and the record of executed deploys used for replay protection.
+//!
+//! A deploy may carry a [`DeployHeader`], which is checked against the block it is included in
+//! before any code runs.  Independently of the header, when replay protection is enabled the hash
+//! of every deploy which gets as far as payment is recorded under a [`Key::Local`] derived from the
+//! deploying account, and any later deploy with the same hash from that account is rejected.
+
+use std::collections::{BTreeSet, HashMap};
+
+use failure::Fail;
+use hex_fmt::HexFmt;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::PublicKey, BlockTime, Key, KEY_LOCAL_SEED_LENGTH};
+
+use crate::DeployHash;
+
+/// The longest time to live a deploy may request, in milliseconds (one day).
+pub const MAX_TTL_MILLIS: u64 = 24 * 60 * 60 * 1_000;
+/// The time to live applied to a deploy whose header leaves it unset.
+pub const DEFAULT_TTL_MILLIS: u64 = MAX_TTL_MILLIS;
+/// The most dependencies a deploy may declare.
+pub const MAX_DEPENDENCIES: usize = 10;
+/// The largest combined size in bytes of a deploy's session and payment.
+pub const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+const EXECUTED_DEPLOYS_PREFIX: &[u8] = b"executed_deploys";
+
+#[derive(Fail, Debug, Clone, PartialEq, Eq)]
+pub enum DeployHeaderError {
+    #[fail(
+        display = "Deploy timestamp {} is later than block time {}",
+        timestamp, block_time
+    )]
+    TimestampInFuture { timestamp: u64, block_time: u64 },
+    #[fail(
+        display = "Deploy ttl {}ms exceeds the maximum of {}ms",
+        ttl_millis, max
+    )]
+    ExcessiveTtl { ttl_millis: u64, max: u64 },
+    #[fail(
+        display = "Deploy expired at {} which is before block time {}",
+        expired_at, block_time
+    )]
+    Expired { expired_at: u64, block_time: u64 },
+    #[fail(
+        display = "Deploy declares {} dependencies, more than the maximum of {}",
+        count, max
+    )]
+    ExcessiveDependencies { count: usize, max: usize },
+    #[fail(
+        display = "Deploy body of {} bytes exceeds the maximum of {}",
+        size, max
+    )]
+    ExcessiveBodySize { size: usize, max: usize },
+    #[fail(display = "Deploy dependency {} has not been executed", _0)]
+    MissingDependency(String),
+    #[fail(display = "Deploy {} is part of or depends on a dependency cycle", _0)]
+    CyclicDependency(String),
+}
+
+/// The part of a deploy which bounds when and after what it may be executed.  Corresponds to the
+/// similarly-named ipc protobuf message.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeployHeader {
+    /// When the deploy was created, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// How long after `timestamp` the deploy may still be executed, in milliseconds.  Zero means
+    /// [`DEFAULT_TTL_MILLIS`].
+    pub ttl_millis: u64,
+    /// Hashes of deploys from the same account which must have been executed before this one,
+    /// either in an earlier block or earlier in the same batch.  A deploy executes against the
+    /// prestate of the batch with the effects of the deploys of the batch it depends on applied.
+    pub dependencies: Vec<DeployHash>,
+}
+
+impl DeployHeader {
+    pub fn new(timestamp: u64, ttl_millis: u64, dependencies: Vec<DeployHash>) -> Self {
+        DeployHeader {
+            timestamp,
+            ttl_millis,
+            dependencies,
+        }
+    }
+
+    /// The time to live of the deploy, with the default applied.
+    pub fn effective_ttl_millis(&self) -> u64 {
+        if self.ttl_millis == 0 {
+            DEFAULT_TTL_MILLIS
+        } else {
+            self.ttl_millis
+        }
+    }
+
+    /// Checks the parts of the header which don't depend on global state against the time of the
+    /// block the deploy is executed in and the size of its body.
+    pub fn validate(
+        &self,
+        blocktime: BlockTime,
+        body_size: usize,
+    ) -> Result<(), DeployHeaderError> {
+        let block_time: u64 = blocktime.into();
+
+        if self.timestamp > block_time {
+            return Err(DeployHeaderError::TimestampInFuture {
+                timestamp: self.timestamp,
+                block_time,
+            });
+        }
+
+        let ttl_millis = self.effective_ttl_millis();
+        if ttl_millis > MAX_TTL_MILLIS {
+            return Err(DeployHeaderError::ExcessiveTtl {
+                ttl_millis,
+                max: MAX_TTL_MILLIS,
+            });
+        }
+
+        let expired_at = self.timestamp.saturating_add(ttl_millis);
+        if expired_at < block_time {
+            return Err(DeployHeaderError::Expired {
+                expired_at,
+                block_time,
+            });
+        }
+
+        if self.dependencies.len() > MAX_DEPENDENCIES {
+            return Err(DeployHeaderError::ExcessiveDependencies {
+                count: self.dependencies.len(),
+                max: MAX_DEPENDENCIES,
+            });
+        }
+
+        if body_size > MAX_BODY_SIZE {
+            return Err(DeployHeaderError::ExcessiveBodySize {
+                size: body_size,
+                max: MAX_BODY_SIZE,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the key under which the execution of `deploy_hash` by `account` is recorded.
+///
+/// The seed is hashed from the account rather than being the account itself, so the record can't
+/// be reached through the account's own local state.
+pub fn executed_deploy_key(account: PublicKey, deploy_hash: &DeployHash) -> Key {
+    let seed: [u8; KEY_LOCAL_SEED_LENGTH] = {
+        let mut bytes = EXECUTED_DEPLOYS_PREFIX.to_vec();
+        bytes.extend_from_slice(account.as_bytes());
+        Blake2bHash::new(&bytes).into()
+    };
+    Key::local(seed, deploy_hash)
+}
+
+pub(crate) fn missing_dependency(deploy_hash: &DeployHash) -> DeployHeaderError {
+    DeployHeaderError::MissingDependency(format!("{}", HexFmt(deploy_hash)))
+}
+
+pub(crate) fn cyclic_dependency(deploy_hash: &DeployHash) -> DeployHeaderError {
+    DeployHeaderError::CyclicDependency(format!("{}", HexFmt(deploy_hash)))
+}
+
+/// The order in which the deploys of a batch are executed.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ExecutionOrder {
+    /// Indices of the deploys to execute, each after every deploy in the batch it depends on.
+    pub sequence: Vec<usize>,
+    /// Indices of the deploys which are part of, or depend on, a dependency cycle, and so can't
+    /// be executed.
+    pub cyclic: Vec<usize>,
+}
+
+/// Orders a batch of deploys, given as their account, hash and dependencies, so that each is
+/// executed after the deploys of the batch it depends on.  Deploys are otherwise kept in their
+/// original order.  `None` entries have no dependencies and nothing depends on them.
+pub(crate) fn execution_order(
+    deploys: &[Option<(PublicKey, DeployHash, &[DeployHash])>],
+) -> ExecutionOrder {
+    let mut indices: HashMap<(PublicKey, DeployHash), Vec<usize>> = HashMap::new();
+    for (index, deploy) in deploys.iter().enumerate() {
+        if let Some((account, deploy_hash, _)) = deploy {
+            indices
+                .entry((*account, *deploy_hash))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut dependents = vec![Vec::new(); deploys.len()];
+    let mut pending_dependencies = vec![0usize; deploys.len()];
+    for (index, deploy) in deploys.iter().enumerate() {
+        if let Some((account, _, dependencies)) = deploy {
+            for dependency in dependencies.iter() {
+                let dependency_indices = indices
+                    .get(&(*account, *dependency))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for dependency_index in dependency_indices {
+                    dependents[*dependency_index].push(index);
+                    pending_dependencies[index] += 1;
+                }
+            }
+        }
+    }
+
+    let mut ready: BTreeSet<usize> = (0..deploys.len())
+        .filter(|index| pending_dependencies[*index] == 0)
+        .collect();
+    let mut order = ExecutionOrder::default();
+    while let Some(index) = ready.iter().next().copied() {
+        ready.remove(&index);
+        order.sequence.push(index);
+        for dependent in &dependents[index] {
+            pending_dependencies[*dependent] -= 1;
+            if pending_dependencies[*dependent] == 0 {
+                ready.insert(*dependent);
+            }
+        }
+    }
+    order.cyclic = (0..deploys.len())
+        .filter(|index| pending_dependencies[*index] > 0)
+        .collect();
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{account::PublicKey, BlockTime};
+
+    use super::*;
+
+    const BLOCK_TIME: u64 = 1_000_000;
+
+    fn validate(header: &DeployHeader) -> Result<(), DeployHeaderError> {
+        header.validate(BlockTime::new(BLOCK_TIME), 0)
+    }
+
+    #[test]
+    fn should_accept_deploy_within_ttl() {
+        let header = DeployHeader::new(BLOCK_TIME - 10, 10, vec![]);
+        assert_eq!(validate(&header), Ok(()));
+    }
+
+    #[test]
+    fn should_apply_default_ttl() {
+        let header = DeployHeader::new(BLOCK_TIME, 0, vec![]);
+        assert_eq!(header.effective_ttl_millis(), DEFAULT_TTL_MILLIS);
+        assert_eq!(validate(&header), Ok(()));
+    }
+
+    #[test]
+    fn should_reject_deploy_from_the_future() {
+        let header = DeployHeader::new(BLOCK_TIME + 1, 10, vec![]);
+        assert_eq!(
+            validate(&header),
+            Err(DeployHeaderError::TimestampInFuture {
+                timestamp: BLOCK_TIME + 1,
+                block_time: BLOCK_TIME
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_expired_deploy() {
+        let header = DeployHeader::new(BLOCK_TIME - 11, 10, vec![]);
+        assert_eq!(
+            validate(&header),
+            Err(DeployHeaderError::Expired {
+                expired_at: BLOCK_TIME - 1,
+                block_time: BLOCK_TIME
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_excessive_ttl() {
+        let header = DeployHeader::new(BLOCK_TIME, MAX_TTL_MILLIS + 1, vec![]);
+        assert_eq!(
+            validate(&header),
+            Err(DeployHeaderError::ExcessiveTtl {
+                ttl_millis: MAX_TTL_MILLIS + 1,
+                max: MAX_TTL_MILLIS
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_excessive_dependencies() {
+        let header = DeployHeader::new(BLOCK_TIME, 0, vec![[1u8; 32]; MAX_DEPENDENCIES + 1]);
+        assert_eq!(
+            validate(&header),
+            Err(DeployHeaderError::ExcessiveDependencies {
+                count: MAX_DEPENDENCIES + 1,
+                max: MAX_DEPENDENCIES
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_excessive_body_size() {
+        let header = DeployHeader::new(BLOCK_TIME, 0, vec![]);
+        assert_eq!(
+            header.validate(BlockTime::new(BLOCK_TIME), MAX_BODY_SIZE + 1),
+            Err(DeployHeaderError::ExcessiveBodySize {
+                size: MAX_BODY_SIZE + 1,
+                max: MAX_BODY_SIZE
+            })
+        );
+    }
+
+    #[test]
+    fn executed_deploy_keys_should_differ_by_account_and_hash() {
+        let alice = PublicKey::ed25519_from([1u8; 32]);
+        let bob = PublicKey::ed25519_from([2u8; 32]);
+        let deploy_hash = [3u8; 32];
+
+        let key = executed_deploy_key(alice, &deploy_hash);
+        assert_eq!(key, executed_deploy_key(alice, &deploy_hash));
+        assert_ne!(key, executed_deploy_key(bob, &deploy_hash));
+        assert_ne!(key, executed_deploy_key(alice, &[4u8; 32]));
+        assert_ne!(key, Key::local(alice.value(), &deploy_hash));
+    }
+
+    #[test]
+    fn execution_order_should_keep_independent_deploys_in_place() {
+        let account = PublicKey::ed25519_from([1u8; 32]);
+        let deploys = vec![
+            Some((account, [1u8; 32], &[][..])),
+            None,
+            Some((account, [2u8; 32], &[][..])),
+        ];
+        let order = execution_order(&deploys);
+        assert_eq!(order.sequence, vec![0, 1, 2]);
+        assert!(order.cyclic.is_empty());
+    }
+
+    #[test]
+    fn execution_order_should_put_dependencies_first() {
+        let alice = PublicKey::ed25519_from([1u8; 32]);
+        let bob = PublicKey::ed25519_from([2u8; 32]);
+        let first = [1u8; 32];
+        let second = [2u8; 32];
+        let deploys = vec![
+            Some((alice, second, &[first][..])),
+            Some((bob, [3u8; 32], &[first][..])),
+            Some((alice, first, &[][..])),
+        ];
+        let order = execution_order(&deploys);
+        // Bob's deploy depends on a deploy of his own, which isn't in the batch.
+        assert_eq!(order.sequence, vec![1, 2, 0]);
+        assert!(order.cyclic.is_empty());
+    }
+
+    #[test]
+    fn execution_order_should_exclude_cycles_and_their_dependents() {
+        let account = PublicKey::ed25519_from([1u8; 32]);
+        let deploys = vec![
+            Some((account, [1u8; 32], &[[2u8; 32]][..])),
+            Some((account, [2u8; 32], &[[1u8; 32]][..])),
+            Some((account, [3u8; 32], &[[2u8; 32]][..])),
+            Some((account, [4u8; 32], &[[4u8; 32]][..])),
+            Some((account, [5u8; 32], &[][..])),
+        ];
+        let order = execution_order(&deploys);
+        assert_eq!(order.sequence, vec![4]);
+        assert_eq!(order.cyclic, vec![0, 1, 2, 3]);
+    }
+}
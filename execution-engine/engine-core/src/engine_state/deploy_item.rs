@@ -1,8 +1,11 @@
 use std::collections::BTreeSet;
 
-use types::account::PublicKey;
+use types::{account::PublicKey, ProtocolVersion, URef};
 
-use crate::{engine_state::executable_deploy_item::ExecutableDeployItem, DeployHash};
+use crate::{
+    engine_state::{deploy_header::DeployHeader, executable_deploy_item::ExecutableDeployItem},
+    DeployHash,
+};
 
 type GasPrice = u64;
 
@@ -15,10 +18,18 @@ pub struct DeployItem {
     pub gas_price: GasPrice,
     pub authorization_keys: BTreeSet<PublicKey>,
     pub deploy_hash: DeployHash,
+    pub header: Option<DeployHeader>,
+    /// When set, the deploy is only executed if this is the account's next sequence number.
+    pub sequence_number: Option<u64>,
+    /// When set, payment is funded from this purse rather than the account's main purse.
+    pub payment_source: Option<URef>,
+    /// When set, the deploy is only executed under this protocol version.
+    pub protocol_version: Option<ProtocolVersion>,
 }
 
 impl DeployItem {
     /// Creates a [`DeployItem`].
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         address: PublicKey,
         session: ExecutableDeployItem,
@@ -26,6 +37,10 @@ impl DeployItem {
         gas_price: GasPrice,
         authorization_keys: BTreeSet<PublicKey>,
         deploy_hash: DeployHash,
+        header: Option<DeployHeader>,
+        sequence_number: Option<u64>,
+        payment_source: Option<URef>,
+        protocol_version: Option<ProtocolVersion>,
     ) -> Self {
         DeployItem {
             address,
@@ -34,6 +49,15 @@ impl DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            header,
+            sequence_number,
+            payment_source,
+            protocol_version,
         }
     }
+
+    /// The combined size in bytes of the deploy's session and payment.
+    pub fn body_size(&self) -> usize {
+        self.session.size() + self.payment.size()
+    }
 }
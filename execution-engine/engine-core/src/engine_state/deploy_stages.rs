@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use engine_shared::{
+    logging::{Stage, StageLogger, StageOutcome},
+    newtypes::CorrelationId,
+};
+
+use super::{execution_result::ExecutionResult, RootNotFound};
+
+/// Tracks the stage a deploy is in, reporting each one to a [`StageLogger`] as it ends.
+pub(crate) struct DeployStages<'a> {
+    correlation_id: CorrelationId,
+    logger: &'a dyn StageLogger,
+    current: Option<(Stage, Instant)>,
+}
+
+impl<'a> DeployStages<'a> {
+    pub(crate) fn new(correlation_id: CorrelationId, logger: &'a dyn StageLogger) -> Self {
+        DeployStages {
+            correlation_id,
+            logger,
+            current: None,
+        }
+    }
+
+    /// Ends the current stage, if any, with `StageOutcome::Success` and starts `stage`.
+    pub(crate) fn enter(&mut self, stage: Stage) {
+        self.end(StageOutcome::Success);
+        self.current = Some((stage, Instant::now()));
+    }
+
+    /// Ends the current stage, if any, with `outcome`.
+    pub(crate) fn end(&mut self, outcome: StageOutcome) {
+        if let Some((stage, start)) = self.current.take() {
+            self.logger
+                .log_stage(self.correlation_id, stage, start.elapsed(), &outcome);
+        }
+    }
+
+    /// Ends the current stage, if any, with the outcome of `result`. Used once the deploy is
+    /// done, to close a stage left open by an early return.
+    pub(crate) fn finish(&mut self, result: &Result<ExecutionResult, RootNotFound>) {
+        let outcome = match result {
+            Ok(execution_result) => outcome(execution_result),
+            Err(error) => StageOutcome::Failure(format!("{:?}", error)),
+        };
+        self.end(outcome)
+    }
+}
+
+pub(crate) fn outcome(execution_result: &ExecutionResult) -> StageOutcome {
+    match execution_result.as_error() {
+        Some(error) => StageOutcome::Failure(error.to_string()),
+        None => StageOutcome::Success,
+    }
+}
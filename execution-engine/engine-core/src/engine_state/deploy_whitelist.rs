@@ -0,0 +1,55 @@
+//! The list of accounts a permissioned network allows to send deploys or install contracts.
+//!
+//! When genesis configures a whitelist admin, a `Vec<PublicKey>` of the genesis accounts is
+//! stored under a new `URef`.  The `URef` is recorded in the [`ProtocolData`] so the engine can
+//! find the list, and is added with full access rights to the admin's named keys under
+//! [`DEPLOY_WHITELIST_NAME`] so the admin can update it with an ordinary deploy.  Which actions
+//! the list restricts is chosen per node by [`WhitelistMode`](super::engine_config::WhitelistMode).
+
+use engine_shared::stored_value::StoredValue;
+use engine_storage::protocol_data::ProtocolData;
+use types::{account::PublicKey, Key};
+
+/// The name under which the whitelist admin holds the whitelist `URef`.
+pub const DEPLOY_WHITELIST_NAME: &str = "deploy_whitelist";
+
+/// Returns the key of the whitelist recorded in `protocol_data`, if there is one.
+pub fn whitelist_key(protocol_data: &ProtocolData) -> Option<Key> {
+    protocol_data.deploy_whitelist().map(Key::URef)
+}
+
+/// Returns whether `account` is on the whitelist read from global state.  A missing or malformed
+/// whitelist admits nobody.
+pub fn is_whitelisted(whitelist: Option<StoredValue>, account: PublicKey) -> bool {
+    match whitelist {
+        Some(StoredValue::CLValue(cl_value)) => cl_value
+            .into_t::<Vec<PublicKey>>()
+            .map(|accounts| accounts.contains(&account))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::CLValue;
+
+    use super::*;
+
+    const ACCOUNT_1: PublicKey = PublicKey::ed25519_from([1; 32]);
+    const ACCOUNT_2: PublicKey = PublicKey::ed25519_from([2; 32]);
+
+    #[test]
+    fn should_only_admit_listed_accounts() {
+        let whitelist = StoredValue::CLValue(CLValue::from_t(vec![ACCOUNT_1]).unwrap());
+        assert!(is_whitelisted(Some(whitelist.clone()), ACCOUNT_1));
+        assert!(!is_whitelisted(Some(whitelist), ACCOUNT_2));
+    }
+
+    #[test]
+    fn should_admit_nobody_without_a_valid_whitelist() {
+        assert!(!is_whitelisted(None, ACCOUNT_1));
+        let malformed = StoredValue::CLValue(CLValue::from_t(1u64).unwrap());
+        assert!(!is_whitelisted(Some(malformed), ACCOUNT_1));
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+use crate::engine_state::Error;
+
+/// Confirms that a commit has been flushed to durable storage.
+///
+/// Commits made with [`EngineConfig::deferred_fsync`](super::EngineConfig::deferred_fsync) set
+/// return before they are flushed; the flush happens on the engine's sync worker thread, which the
+/// handle can wait for.  Dropping the handle doesn't cancel the flush.
+#[derive(Debug)]
+pub struct DurabilityHandle {
+    maybe_receiver: Option<Receiver<Result<(), Error>>>,
+}
+
+impl DurabilityHandle {
+    /// Returns a handle for a commit which was already flushed when it returned.
+    pub(crate) fn durable() -> Self {
+        DurabilityHandle {
+            maybe_receiver: None,
+        }
+    }
+
+    /// Returns a handle for a commit being flushed in the background, along with the sender
+    /// through which the flush reports its outcome.
+    pub(crate) fn pending() -> (Sender<Result<(), Error>>, Self) {
+        let (sender, receiver) = mpsc::channel();
+        let handle = DurabilityHandle {
+            maybe_receiver: Some(receiver),
+        };
+        (sender, handle)
+    }
+
+    /// Blocks until the commit has been flushed to durable storage.
+    pub fn wait(self) -> Result<(), Error> {
+        match self.maybe_receiver {
+            None => Ok(()),
+            Some(receiver) => receiver.recv().unwrap_or(Err(Error::SyncInterrupted)),
+        }
+    }
+}
+
+/// The background thread which flushes deferred commits to durable storage, one flush at a time.
+///
+/// A flush covers every commit made before it starts, so the worker answers all the requests
+/// queued up by then with a single flush.  The thread exits once the worker is dropped.
+#[derive(Debug)]
+pub(crate) struct SyncWorker {
+    sender: Sender<Sender<Result<(), Error>>>,
+}
+
+impl SyncWorker {
+    /// Spawns the worker thread, which flushes by calling `sync`.
+    pub(crate) fn spawn<F>(sync: F) -> Self
+    where
+        F: Fn() -> Result<(), Error> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<Sender<Result<(), Error>>>();
+        thread::spawn(move || {
+            while let Ok(first_request) = receiver.recv() {
+                let mut requests = vec![first_request];
+                requests.extend(receiver.try_iter());
+                let result = sync();
+                for request in requests {
+                    // The handle may have been dropped, in which case nobody is waiting for the
+                    // outcome.
+                    let _ = request.send(result.clone());
+                }
+            }
+        });
+        SyncWorker { sender }
+    }
+
+    /// Queues a flush of everything committed so far, returning a handle which reports when it
+    /// is done.
+    pub(crate) fn request_sync(&self) -> DurabilityHandle {
+        let (sender, durability_handle) = DurabilityHandle::pending();
+        // Should the worker thread have stopped, the handle reports the flush as interrupted.
+        let _ = self.sender.send(sender);
+        durability_handle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_answer_every_queued_request() {
+        let flushes = Arc::new(AtomicUsize::new(0));
+        let sync_worker = {
+            let flushes = Arc::clone(&flushes);
+            SyncWorker::spawn(move || {
+                flushes.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        };
+
+        let handles: Vec<DurabilityHandle> = (0..3).map(|_| sync_worker.request_sync()).collect();
+        for handle in handles {
+            assert!(handle.wait().is_ok());
+        }
+        // Requests queued while a flush is running are covered by a single later flush.
+        assert!((1..=3).contains(&flushes.load(Ordering::SeqCst)));
+    }
+
+    #[test]
+    fn should_report_failed_flush() {
+        let sync_worker = SyncWorker::spawn(|| Err(Error::Authorization));
+        match sync_worker.request_sync().wait() {
+            Err(Error::Authorization) => (),
+            other => panic!("unexpected flush result: {:?}", other),
+        }
+    }
+}
@@ -1,9 +1,42 @@
+use std::time::Duration;
+
+use types::U512;
+
+/// Which actions a permissioned network restricts to the accounts on its deploy whitelist.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WhitelistMode {
+    /// Any account may send deploys and install contracts.
+    Disabled,
+    /// Only whitelisted accounts may send deploys.
+    Deploys,
+    /// Any account may send deploys, but only whitelisted accounts may install contracts.
+    ContractInstalls,
+}
+
+impl Default for WhitelistMode {
+    fn default() -> Self {
+        WhitelistMode::Disabled
+    }
+}
+
 /// The runtime configuration of the execution engine
 #[derive(Debug, Copy, Clone, Default)]
 pub struct EngineConfig {
     // feature flags go here
     use_system_contracts: bool,
     enable_bonding: bool,
+    enable_replay_protection: bool,
+    enable_determinism_checks: bool,
+    execution_time_limit: Option<Duration>,
+    enable_tracing: bool,
+    whitelist_mode: WhitelistMode,
+    deferred_fsync: bool,
+    enable_execution_result_cache: bool,
+    enable_effects_journal: bool,
+    max_args_bytes: Option<usize>,
+    max_module_bytes: Option<usize>,
+    min_account_creation_balance: Option<U512>,
+    min_main_purse_balance: Option<U512>,
 }
 
 impl EngineConfig {
@@ -29,4 +62,148 @@ impl EngineConfig {
         self.enable_bonding = enable_bonding;
         self
     }
+
+    pub fn enable_replay_protection(self) -> bool {
+        self.enable_replay_protection
+    }
+
+    pub fn with_enable_replay_protection(mut self, enable_replay_protection: bool) -> EngineConfig {
+        self.enable_replay_protection = enable_replay_protection;
+        self
+    }
+
+    pub fn enable_determinism_checks(self) -> bool {
+        self.enable_determinism_checks
+    }
+
+    pub fn with_enable_determinism_checks(
+        mut self,
+        enable_determinism_checks: bool,
+    ) -> EngineConfig {
+        self.enable_determinism_checks = enable_determinism_checks;
+        self
+    }
+
+    /// The longest wall-clock time a single phase of a deploy may run for, regardless of how much
+    /// gas it has left.  `None` means no limit.
+    pub fn execution_time_limit(self) -> Option<Duration> {
+        self.execution_time_limit
+    }
+
+    pub fn with_execution_time_limit(
+        mut self,
+        execution_time_limit: Option<Duration>,
+    ) -> EngineConfig {
+        self.execution_time_limit = execution_time_limit;
+        self
+    }
+
+    /// Whether every host function call and global state access made by a deploy is recorded in
+    /// the trace attached to its execution result.
+    pub fn enable_tracing(self) -> bool {
+        self.enable_tracing
+    }
+
+    pub fn with_enable_tracing(mut self, enable_tracing: bool) -> EngineConfig {
+        self.enable_tracing = enable_tracing;
+        self
+    }
+
+    /// Which actions are restricted to the accounts on the deploy whitelist set up at genesis.  A
+    /// chain with no whitelist rejects every restricted action.
+    pub fn whitelist_mode(self) -> WhitelistMode {
+        self.whitelist_mode
+    }
+
+    pub fn with_whitelist_mode(mut self, whitelist_mode: WhitelistMode) -> EngineConfig {
+        self.whitelist_mode = whitelist_mode;
+        self
+    }
+
+    /// Whether commits return before their data is flushed to disk, leaving the flush to a
+    /// background thread.  A crash may then lose the most recent commits.
+    pub fn deferred_fsync(self) -> bool {
+        self.deferred_fsync
+    }
+
+    pub fn with_deferred_fsync(mut self, deferred_fsync: bool) -> EngineConfig {
+        self.deferred_fsync = deferred_fsync;
+        self
+    }
+
+    /// Whether the results of executing deploys are cached by prestate hash and deploy hash, so
+    /// that executing the same block again skips the deploys already run.
+    pub fn enable_execution_result_cache(self) -> bool {
+        self.enable_execution_result_cache
+    }
+
+    pub fn with_enable_execution_result_cache(
+        mut self,
+        enable_execution_result_cache: bool,
+    ) -> EngineConfig {
+        self.enable_execution_result_cache = enable_execution_result_cache;
+        self
+    }
+
+    /// Whether the execution effects of every deploy are recorded in the storage layer, where
+    /// they can be looked up by deploy hash after the deploy has been executed.
+    pub fn enable_effects_journal(self) -> bool {
+        self.enable_effects_journal
+    }
+
+    pub fn with_enable_effects_journal(mut self, enable_effects_journal: bool) -> EngineConfig {
+        self.enable_effects_journal = enable_effects_journal;
+        self
+    }
+
+    /// The most bytes of serialized args, positional and named together, that the session or
+    /// payment code of a deploy may carry.  `None` means no limit.
+    pub fn max_args_bytes(self) -> Option<usize> {
+        self.max_args_bytes
+    }
+
+    pub fn with_max_args_bytes(mut self, max_args_bytes: Option<usize>) -> EngineConfig {
+        self.max_args_bytes = max_args_bytes;
+        self
+    }
+
+    /// The most bytes of wasm that the session or payment code of a deploy may carry.  `None`
+    /// means no limit beyond the module size limit applied during preprocessing.
+    pub fn max_module_bytes(self) -> Option<usize> {
+        self.max_module_bytes
+    }
+
+    pub fn with_max_module_bytes(mut self, max_module_bytes: Option<usize>) -> EngineConfig {
+        self.max_module_bytes = max_module_bytes;
+        self
+    }
+
+    /// The fewest motes a transfer to a public key with no account may carry, as the new account
+    /// is created with a main purse holding them.  `None` means any amount creates the account.
+    pub fn min_account_creation_balance(self) -> Option<U512> {
+        self.min_account_creation_balance
+    }
+
+    pub fn with_min_account_creation_balance(
+        mut self,
+        min_account_creation_balance: Option<U512>,
+    ) -> EngineConfig {
+        self.min_account_creation_balance = min_account_creation_balance;
+        self
+    }
+
+    /// The fewest motes session code may leave in the main purse of the account it runs as, so
+    /// that the account can still pay for later deploys.  The payment code isn't held to it.
+    /// `None` means the main purse may be emptied.
+    pub fn min_main_purse_balance(self) -> Option<U512> {
+        self.min_main_purse_balance
+    }
+
+    pub fn with_min_main_purse_balance(
+        mut self,
+        min_main_purse_balance: Option<U512>,
+    ) -> EngineConfig {
+        self.min_main_purse_balance = min_main_purse_balance;
+        self
+    }
 }
@@ -0,0 +1,60 @@
+use std::fmt;
+
+use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{account::PublicKey, bytesrepr, Key};
+
+use crate::engine_state::{error::Error, execution_effect::ExecutionEffect};
+
+/// What happened during an era, as far as it affects the validators' rewards.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EraReport {
+    /// Validators which were slashed during the era and forfeit their share of the rewards.
+    pub equivocators: Vec<PublicKey>,
+}
+
+pub enum RewardsResult {
+    RootNotFound,
+    /// The Proof of Stake contract failed to pay out the rewards.
+    Failure(Error),
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    },
+}
+
+impl fmt::Display for RewardsResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            RewardsResult::RootNotFound => write!(f, "Root not found"),
+            RewardsResult::Failure(error) => write!(f, "Failure: {:?}", error),
+            RewardsResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            RewardsResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            RewardsResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            RewardsResult::Success {
+                post_state_hash,
+                effect,
+            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+        }
+    }
+}
+
+impl RewardsResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => RewardsResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => RewardsResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => RewardsResult::TypeMismatch(type_mismatch),
+            CommitResult::Serialization(error) => RewardsResult::Serialization(error),
+            CommitResult::Success { state_root, .. } => RewardsResult::Success {
+                post_state_hash: state_root,
+                effect,
+            },
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Snapshots of the validators' stakes taken at era boundaries.
+//!
+//! At genesis, and at the end of every era in [`EngineState::step`](super::EngineState::step),
+//! the engine records the stakes bonded in PoS, together with the delegations to each validator,
+//! under a key of their own for the era starting then.  An era's validators and their weights stay
+//! fixed while it is in progress, even as stakes are bonded and withdrawn in PoS, and past eras
+//! stay available for as long as the state they were recorded in.
+
+use std::collections::{BTreeMap, HashMap};
+
+use engine_shared::{stored_value::StoredValue, TypeMismatch};
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key, KEY_LOCAL_SEED_LENGTH, U512,
+};
+
+use crate::{engine_state::utils, execution};
+
+/// The number of eras which have ended before an era.  The genesis era is era 0.
+pub type EraId = u64;
+
+/// The validators bonded during an era, and their stakes.
+pub type EraValidators = BTreeMap<PublicKey, ValidatorStake>;
+
+/// The seed of the local state partition holding the snapshots.  It is not the seed of any
+/// account or contract, so the snapshots can't be written to by Wasm.
+const ERA_VALIDATORS_SEED: [u8; KEY_LOCAL_SEED_LENGTH] = [253; KEY_LOCAL_SEED_LENGTH];
+const CURRENT_ERA_ID_NAME: &str = "current_era_id";
+
+/// A validator's own bonded stake and the amounts each delegator has delegated to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidatorStake {
+    bonded_amount: U512,
+    delegations: BTreeMap<PublicKey, U512>,
+}
+
+impl ValidatorStake {
+    pub fn new(bonded_amount: U512) -> Self {
+        ValidatorStake {
+            bonded_amount,
+            delegations: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the amount the validator has bonded itself.
+    pub fn bonded_amount(&self) -> U512 {
+        self.bonded_amount
+    }
+
+    /// Returns the amounts delegated to the validator, by delegator.
+    pub fn delegations(&self) -> &BTreeMap<PublicKey, U512> {
+        &self.delegations
+    }
+
+    /// Returns the validator's vote weight: its own stake plus everything delegated to it.
+    pub fn weight(&self) -> U512 {
+        self.delegations
+            .values()
+            .fold(self.bonded_amount, |total, amount| total + amount)
+    }
+
+    fn delegate(&mut self, delegator: PublicKey, amount: U512) {
+        *self.delegations.entry(delegator).or_default() += amount;
+    }
+}
+
+impl CLTyped for ValidatorStake {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ValidatorStake {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.bonded_amount.to_bytes()?);
+        result.append(&mut self.delegations.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.bonded_amount.serialized_length() + self.delegations.serialized_length()
+    }
+}
+
+impl FromBytes for ValidatorStake {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (bonded_amount, rem) = FromBytes::from_bytes(bytes)?;
+        let (delegations, rem) = FromBytes::from_bytes(rem)?;
+        let stake = ValidatorStake {
+            bonded_amount,
+            delegations,
+        };
+        Ok((stake, rem))
+    }
+}
+
+/// Returns the key under which the id of the era in progress is stored.
+pub fn current_era_id_key() -> Key {
+    Key::local(ERA_VALIDATORS_SEED, CURRENT_ERA_ID_NAME.as_bytes())
+}
+
+/// Returns the key under which the snapshot of the validators bonded during `era_id` is stored.
+pub fn era_validators_key(era_id: EraId) -> Key {
+    let key_bytes = era_id.to_bytes().expect("should serialize era id");
+    Key::local(ERA_VALIDATORS_SEED, &key_bytes)
+}
+
+/// Returns the id of the era in progress read from global state.  A state without one is still in
+/// the genesis era.
+pub fn era_id_from(stored_value: Option<StoredValue>) -> Result<EraId, execution::Error> {
+    match stored_value {
+        None => Ok(0),
+        Some(StoredValue::CLValue(cl_value)) => Ok(cl_value.into_t()?),
+        Some(other) => Err(type_mismatch(other)),
+    }
+}
+
+/// Returns the snapshot read from global state, or `None` if none was taken for the era.
+pub fn era_validators_from(
+    stored_value: Option<StoredValue>,
+) -> Result<Option<EraValidators>, execution::Error> {
+    match stored_value {
+        None => Ok(None),
+        Some(StoredValue::CLValue(cl_value)) => Ok(Some(cl_value.into_t()?)),
+        Some(other) => Err(type_mismatch(other)),
+    }
+}
+
+/// Builds a snapshot from the PoS contract's named keys, under which it records the stakes and
+/// delegations.  Delegations to validators which are no longer bonded are left out.
+pub fn era_validators_from_pos(pos_named_keys: &BTreeMap<String, Key>) -> EraValidators {
+    let mut era_validators: EraValidators = pos_named_keys
+        .keys()
+        .filter_map(|name| utils::pos_validator_key_name_to_tuple(name))
+        .map(|(validator, bonded_amount)| (validator, ValidatorStake::new(bonded_amount)))
+        .collect();
+
+    for (delegator, validator, amount) in pos_named_keys
+        .keys()
+        .filter_map(|name| utils::pos_delegation_key_name_to_tuple(name))
+    {
+        if let Some(stake) = era_validators.get_mut(&validator) {
+            stake.delegate(delegator, amount);
+        }
+    }
+
+    era_validators
+}
+
+/// Returns the vote weights of the validators in `era_validators`.
+pub fn weights(era_validators: &EraValidators) -> HashMap<PublicKey, U512> {
+    era_validators
+        .iter()
+        .map(|(validator, stake)| (*validator, stake.weight()))
+        .collect()
+}
+
+fn type_mismatch(stored_value: StoredValue) -> execution::Error {
+    execution::Error::TypeMismatch(TypeMismatch::new(
+        "CLValue".to_string(),
+        stored_value.type_name(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{AccessRights, CLValue, URef};
+
+    use super::*;
+
+    const VALIDATOR_1: PublicKey = PublicKey::ed25519_from([1; 32]);
+    const VALIDATOR_2: PublicKey = PublicKey::ed25519_from([2; 32]);
+    const DELEGATOR: PublicKey = PublicKey::ed25519_from([3; 32]);
+
+    fn name_of(prefix: &str, keys: &[PublicKey], amount: u64) -> String {
+        let hex_keys: Vec<String> = keys.iter().map(PublicKey::to_tagged_hex).collect();
+        format!("{}_{}_{}", prefix, hex_keys.join("_"), amount)
+    }
+
+    #[test]
+    fn should_snapshot_stakes_and_delegations() {
+        let uref = Key::URef(URef::new([0; 32], AccessRights::READ));
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert(name_of("v", &[VALIDATOR_1], 100), uref);
+        named_keys.insert(name_of("v", &[VALIDATOR_2], 50), uref);
+        named_keys.insert(name_of("d", &[DELEGATOR, VALIDATOR_1], 7), uref);
+        // Delegation to a validator which has since unbonded.
+        named_keys.insert(name_of("d", &[DELEGATOR, DELEGATOR], 9), uref);
+        named_keys.insert("pos_bonding_purse".to_string(), uref);
+
+        let era_validators = era_validators_from_pos(&named_keys);
+
+        let mut stake_1 = ValidatorStake::new(U512::from(100));
+        stake_1.delegate(DELEGATOR, U512::from(7));
+        let mut expected = EraValidators::new();
+        expected.insert(VALIDATOR_1, stake_1);
+        expected.insert(VALIDATOR_2, ValidatorStake::new(U512::from(50)));
+        assert_eq!(era_validators, expected);
+
+        let mut expected_weights = HashMap::new();
+        expected_weights.insert(VALIDATOR_1, U512::from(107));
+        expected_weights.insert(VALIDATOR_2, U512::from(50));
+        assert_eq!(weights(&era_validators), expected_weights);
+    }
+
+    #[test]
+    fn should_roundtrip_era_validators() {
+        let mut stake = ValidatorStake::new(U512::from(100));
+        stake.delegate(DELEGATOR, U512::from(7));
+        let mut era_validators = EraValidators::new();
+        era_validators.insert(VALIDATOR_1, stake);
+
+        let stored_value = StoredValue::CLValue(CLValue::from_t(era_validators.clone()).unwrap());
+        assert_eq!(
+            era_validators_from(Some(stored_value)).unwrap(),
+            Some(era_validators)
+        );
+        assert_eq!(era_validators_from(None).unwrap(), None);
+        assert_eq!(era_id_from(None).unwrap(), 0);
+    }
+
+    #[test]
+    fn should_use_distinct_keys_per_era() {
+        assert_ne!(era_validators_key(0), era_validators_key(1));
+        assert_ne!(era_validators_key(0), current_era_id_key());
+    }
+}
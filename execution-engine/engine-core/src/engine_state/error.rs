@@ -1,12 +1,18 @@
 use failure::Fail;
 
-use engine_shared::newtypes::Blake2bHash;
-use types::{bytesrepr, system_contract_errors::mint};
+use engine_shared::{gas::Gas, motes::Motes, newtypes::Blake2bHash};
+use types::{account::PublicKey, bytesrepr, system_contract_errors::mint, URef, U512};
 
-use crate::execution;
+use crate::{
+    engine_state::{
+        check_signers::AuthorizationFailure, deploy_header::DeployHeaderError,
+        era_validators::EraId,
+    },
+    execution,
+};
 use types::ProtocolVersion;
 
-#[derive(Fail, Debug)]
+#[derive(Fail, Debug, Clone)]
 pub enum Error {
     #[fail(display = "Invalid hash length: expected {}, actual {}", _0, _1)]
     InvalidHashLength { expected: usize, actual: usize },
@@ -14,6 +20,14 @@ pub enum Error {
     InvalidPublicKeyLength { expected: usize, actual: usize },
     #[fail(display = "Invalid protocol version: {}", _0)]
     InvalidProtocolVersion(ProtocolVersion),
+    #[fail(
+        display = "Protocol version mismatch: deploy was created for {} but is executed under {}",
+        declared, expected
+    )]
+    ProtocolVersionMismatch {
+        declared: ProtocolVersion,
+        expected: ProtocolVersion,
+    },
     #[fail(display = "Invalid upgrade config")]
     InvalidUpgradeConfig,
     #[fail(display = "Wasm preprocessing error: {}", _0)]
@@ -26,10 +40,23 @@ pub enum Error {
     Storage(engine_storage::error::Error),
     #[fail(display = "Authorization failure: not authorized.")]
     Authorization,
+    #[fail(display = "{}", _0)]
+    AuthorizationFailure(AuthorizationFailure),
     #[fail(display = "Insufficient payment")]
     InsufficientPayment,
+    #[fail(
+        display = "Payment failure: {}; {} motes taken, {} gas consumed by payment",
+        error, amount_taken, payment_gas
+    )]
+    PaymentFailure {
+        error: Box<Error>,
+        amount_taken: Motes,
+        payment_gas: Gas,
+    },
     #[fail(display = "Deploy error")]
     Deploy,
+    #[fail(display = "Native key management can only be used as session code")]
+    NativePayment,
     #[fail(display = "Payment finalization error")]
     Finalization,
     #[fail(display = "Missing system contract association: {}", _0)]
@@ -38,6 +65,52 @@ pub enum Error {
     Serialization(bytesrepr::Error),
     #[fail(display = "Mint error: {}", _0)]
     Mint(mint::Error),
+    #[fail(display = "Missing root hash: {}", _0)]
+    MissingRoot(Blake2bHash),
+    #[fail(display = "Missing total supply record")]
+    MissingTotalSupply,
+    #[fail(display = "Missing validators of era {}", _0)]
+    MissingEraValidators(EraId),
+    #[fail(
+        display = "Total supply mismatch: recorded {} but purses hold {}",
+        recorded, actual
+    )]
+    TotalSupplyMismatch { recorded: U512, actual: U512 },
+    #[fail(display = "Invalid account balance proof")]
+    InvalidAccountBalanceProof,
+    #[fail(
+        display = "Bonding purse holds {} but stakes and pending requests require {}",
+        held, required
+    )]
+    BondingPurseDeficit { required: U512, held: U512 },
+    #[fail(display = "Invalid deploy header: {}", _0)]
+    DeployHeader(DeployHeaderError),
+    #[fail(display = "Deploy has already been executed")]
+    ReplayedDeploy,
+    #[fail(
+        display = "Invalid sequence number: expected {} but deploy has {}",
+        expected, actual
+    )]
+    InvalidSequenceNumber { expected: u64, actual: u64 },
+    #[fail(display = "Invalid payment source: {}", _0)]
+    InvalidPaymentSource(URef),
+    #[fail(display = "Account {} is not on the deploy whitelist", _0)]
+    NotWhitelisted(PublicKey),
+    #[fail(display = "Deploy args of {} bytes exceed the limit of {}", size, max)]
+    ArgsTooLarge { size: usize, max: usize },
+    #[fail(
+        display = "Deploy module of {} bytes exceeds the limit of {}",
+        size, max
+    )]
+    ModuleTooLarge { size: usize, max: usize },
+    #[fail(display = "Flushing committed state to disk was interrupted")]
+    SyncInterrupted,
+}
+
+impl From<DeployHeaderError> for Error {
+    fn from(error: DeployHeaderError) -> Self {
+        Error::DeployHeader(error)
+    }
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
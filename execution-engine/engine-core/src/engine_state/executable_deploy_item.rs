@@ -1,21 +1,42 @@
+use types::{
+    account::{AssociatedKeysUpdate, PublicKey},
+    bytesrepr::ToBytes,
+    U512,
+};
+
+/// Session or payment code of a deploy.
+///
+/// Except for native deploys, each item carries its arguments either as serialized
+/// positional `args` or as serialized `named_args`, the other being left empty.
 #[derive(Clone, PartialEq, Eq)]
 pub enum ExecutableDeployItem {
     ModuleBytes {
         module_bytes: Vec<u8>,
         args: Vec<u8>,
+        named_args: Vec<u8>,
     },
     StoredContractByHash {
         hash: Vec<u8>,
         args: Vec<u8>,
+        named_args: Vec<u8>,
     },
     StoredContractByName {
         name: String,
         args: Vec<u8>,
+        named_args: Vec<u8>,
     },
     StoredContractByURef {
         uref: Vec<u8>,
         args: Vec<u8>,
+        named_args: Vec<u8>,
     },
+    /// Changes to the deploying account's associated keys and action thresholds, applied by the
+    /// host for a fixed cost per update instead of by executing wasm. Only valid as session code.
+    NativeKeyManagement { updates: Vec<AssociatedKeysUpdate> },
+    /// A transfer of `amount` motes from the deploying account's main purse to the account under
+    /// `target`, which is created if it doesn't exist, applied by the host for a fixed cost
+    /// instead of by executing wasm. Only valid as session code.
+    NativeTransfer { target: PublicKey, amount: U512 },
 }
 
 impl ExecutableDeployItem {
@@ -25,6 +46,81 @@ impl ExecutableDeployItem {
             ExecutableDeployItem::StoredContractByHash { args, .. } => args,
             ExecutableDeployItem::StoredContractByName { args, .. } => args,
             ExecutableDeployItem::StoredContractByURef { args, .. } => args,
+            ExecutableDeployItem::NativeKeyManagement { .. }
+            | ExecutableDeployItem::NativeTransfer { .. } => Vec::new(),
+        }
+    }
+
+    /// The serialized named args of the item, empty if it takes positional args.
+    pub fn named_args(&self) -> &[u8] {
+        match self {
+            ExecutableDeployItem::ModuleBytes { named_args, .. }
+            | ExecutableDeployItem::StoredContractByHash { named_args, .. }
+            | ExecutableDeployItem::StoredContractByName { named_args, .. }
+            | ExecutableDeployItem::StoredContractByURef { named_args, .. } => named_args,
+            ExecutableDeployItem::NativeKeyManagement { .. }
+            | ExecutableDeployItem::NativeTransfer { .. } => &[],
+        }
+    }
+
+    /// The number of bytes of serialized args, positional and named, the item carries.
+    pub fn args_size(&self) -> usize {
+        match self {
+            ExecutableDeployItem::ModuleBytes {
+                args, named_args, ..
+            }
+            | ExecutableDeployItem::StoredContractByHash {
+                args, named_args, ..
+            }
+            | ExecutableDeployItem::StoredContractByName {
+                args, named_args, ..
+            }
+            | ExecutableDeployItem::StoredContractByURef {
+                args, named_args, ..
+            } => args.len() + named_args.len(),
+            ExecutableDeployItem::NativeKeyManagement { .. }
+            | ExecutableDeployItem::NativeTransfer { .. } => 0,
+        }
+    }
+
+    /// The number of bytes of wasm the item carries, zero unless it is `ModuleBytes`.
+    pub fn module_size(&self) -> usize {
+        match self {
+            ExecutableDeployItem::ModuleBytes { module_bytes, .. } => module_bytes.len(),
+            _ => 0,
+        }
+    }
+
+    /// The number of bytes the item contributes to the size of a deploy's body.
+    pub fn size(&self) -> usize {
+        match self {
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes,
+                args,
+                named_args,
+            } => module_bytes.len() + args.len() + named_args.len(),
+            ExecutableDeployItem::StoredContractByHash {
+                hash,
+                args,
+                named_args,
+            } => hash.len() + args.len() + named_args.len(),
+            ExecutableDeployItem::StoredContractByName {
+                name,
+                args,
+                named_args,
+            } => name.len() + args.len() + named_args.len(),
+            ExecutableDeployItem::StoredContractByURef {
+                uref,
+                args,
+                named_args,
+            } => uref.len() + args.len() + named_args.len(),
+            ExecutableDeployItem::NativeKeyManagement { updates } => updates
+                .iter()
+                .map(|update| update.serialized_length())
+                .sum(),
+            ExecutableDeployItem::NativeTransfer { target, amount } => {
+                target.serialized_length() + amount.serialized_length()
+            }
         }
     }
 }
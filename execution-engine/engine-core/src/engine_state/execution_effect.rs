@@ -1,16 +1,253 @@
-use engine_shared::{additive_map::AdditiveMap, transform::Transform};
-use types::Key;
-
-use super::op::Op;
-
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub struct ExecutionEffect {
-    pub ops: AdditiveMap<Key, Op>,
-    pub transforms: AdditiveMap<Key, Transform>,
-}
-
-impl ExecutionEffect {
-    pub fn new(ops: AdditiveMap<Key, Op>, transforms: AdditiveMap<Key, Transform>) -> Self {
-        ExecutionEffect { ops, transforms }
-    }
-}
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use engine_shared::{
+    additive_map::AdditiveMap, newtypes::CorrelationId, stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_storage::global_state::StateReader;
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, Key,
+};
+
+use super::op::Op;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionEffect {
+    pub ops: AdditiveMap<Key, Op>,
+    pub transforms: AdditiveMap<Key, Transform>,
+}
+
+impl ExecutionEffect {
+    pub fn new(ops: AdditiveMap<Key, Op>, transforms: AdditiveMap<Key, Transform>) -> Self {
+        ExecutionEffect { ops, transforms }
+    }
+
+    /// Describes each transform against the values currently held by `reader`, which should be a
+    /// view of the pre-state the effects were produced on. Entries are ordered by key.
+    pub fn diff<R>(
+        &self,
+        correlation_id: CorrelationId,
+        reader: &R,
+    ) -> Result<Vec<EffectDiff>, R::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+    {
+        let mut diffs = self
+            .transforms
+            .iter()
+            .map(|(key, transform)| {
+                let old_type = reader
+                    .read(correlation_id, key)?
+                    .map(|stored_value| stored_value.type_name());
+                Ok(EffectDiff {
+                    key: *key,
+                    old_type,
+                    change: Change::from(transform),
+                })
+            })
+            .collect::<Result<Vec<_>, R::Error>>()?;
+        diffs.sort_by_key(|diff| diff.key);
+        Ok(diffs)
+    }
+}
+
+impl ExecutionEffect {
+    /// The ops and transforms ordered by key, so that equal effects serialize identically.
+    fn sorted(&self) -> (BTreeMap<Key, Op>, BTreeMap<Key, Transform>) {
+        let ops = self.ops.iter().map(|(key, op)| (*key, *op)).collect();
+        let transforms = self
+            .transforms
+            .iter()
+            .map(|(key, transform)| (*key, transform.clone()))
+            .collect();
+        (ops, transforms)
+    }
+}
+
+impl ToBytes for ExecutionEffect {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let (ops, transforms) = self.sorted();
+        let mut result = ops.to_bytes()?;
+        result.append(&mut transforms.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        let (ops, transforms) = self.sorted();
+        ops.serialized_length() + transforms.serialized_length()
+    }
+}
+
+impl FromBytes for ExecutionEffect {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (ops, remainder): (BTreeMap<Key, Op>, _) = FromBytes::from_bytes(bytes)?;
+        let (transforms, remainder): (BTreeMap<Key, Transform>, _) =
+            FromBytes::from_bytes(remainder)?;
+        let effect =
+            ExecutionEffect::new(ops.into_iter().collect(), transforms.into_iter().collect());
+        Ok((effect, remainder))
+    }
+}
+
+/// A display-oriented description of a single transform.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectDiff {
+    pub key: Key,
+    /// Type of the value held under `key` before the transform, or `None` if there was no value.
+    pub old_type: Option<String>,
+    pub change: Change,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    Unchanged,
+    Write {
+        new_type: String,
+    },
+    /// A numeric addition of `delta`, rendered in decimal, to a value of type `cl_type`.
+    Add {
+        cl_type: CLType,
+        delta: String,
+    },
+    AddKeys {
+        names: Vec<String>,
+    },
+    Delete,
+    Failure(String),
+}
+
+impl From<&Transform> for Change {
+    fn from(transform: &Transform) -> Self {
+        let add = |cl_type, delta: String| Change::Add { cl_type, delta };
+        match transform {
+            Transform::Identity => Change::Unchanged,
+            Transform::Write(stored_value) => Change::Write {
+                new_type: stored_value.type_name(),
+            },
+            Transform::AddInt32(delta) => add(CLType::I32, delta.to_string()),
+            Transform::AddUInt64(delta) => add(CLType::U64, delta.to_string()),
+            Transform::AddUInt128(delta) => add(CLType::U128, delta.to_string()),
+            Transform::AddUInt256(delta) => add(CLType::U256, delta.to_string()),
+            Transform::AddUInt512(delta) => add(CLType::U512, delta.to_string()),
+            Transform::AddKeys(named_keys) => Change::AddKeys {
+                names: named_keys.keys().cloned().collect(),
+            },
+            Transform::Delete => Change::Delete,
+            Transform::Failure(error) => Change::Failure(format!("{:?}", error)),
+        }
+    }
+}
+
+impl Display for EffectDiff {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let old_type = self.old_type.as_deref().unwrap_or("None");
+        match &self.change {
+            Change::Unchanged => write!(f, "{}: {} unchanged", self.key, old_type),
+            Change::Write { new_type } => write!(f, "{}: {} -> {}", self.key, old_type, new_type),
+            Change::Add { cl_type, delta } => {
+                write!(f, "{}: {} += {} ({:?})", self.key, old_type, delta, cl_type)
+            }
+            Change::AddKeys { names } => write!(
+                f,
+                "{}: {} += named keys [{}]",
+                self.key,
+                old_type,
+                names.join(", ")
+            ),
+            Change::Delete => write!(f, "{}: {} deleted", self.key, old_type),
+            Change::Failure(error) => write!(f, "{}: {} failed: {}", self.key, old_type, error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+    use types::{account::PublicKey, CLValue, U512};
+
+    use super::*;
+
+    #[test]
+    fn serialization_roundtrip() {
+        let key = Key::Hash([1; 32]);
+        let mut ops = AdditiveMap::new();
+        ops.insert(key, Op::Add);
+        let mut transforms = AdditiveMap::new();
+        transforms.insert(key, Transform::AddUInt512(U512::from(5)));
+        let effect = ExecutionEffect::new(ops, transforms);
+
+        bytesrepr::test_serialization_roundtrip(&effect);
+    }
+
+    #[test]
+    fn diff_should_describe_transforms_against_pre_state() {
+        let correlation_id = CorrelationId::new();
+        let balance_key = Key::Hash([1; 32]);
+        let counter_key = Key::Hash([2; 32]);
+        let new_key = Key::Account(PublicKey::ed25519_from([3; 32]));
+
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[
+                (
+                    balance_key,
+                    StoredValue::CLValue(CLValue::from_t(U512::from(10)).unwrap()),
+                ),
+                (
+                    counter_key,
+                    StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+                ),
+            ],
+        )
+        .unwrap();
+        let reader = state.checkout(root_hash).unwrap().unwrap();
+
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert("purse".to_string(), balance_key);
+        let mut transforms = AdditiveMap::new();
+        transforms.insert(balance_key, Transform::AddUInt512(U512::from(5)));
+        transforms.insert(counter_key, Transform::AddInt32(-1));
+        transforms.insert(new_key, Transform::AddKeys(named_keys));
+        let effect = ExecutionEffect::new(AdditiveMap::new(), transforms);
+
+        let diffs = effect.diff(correlation_id, &reader).unwrap();
+        assert_eq!(
+            diffs,
+            vec![
+                EffectDiff {
+                    key: new_key,
+                    old_type: None,
+                    change: Change::AddKeys {
+                        names: vec!["purse".to_string()]
+                    },
+                },
+                EffectDiff {
+                    key: balance_key,
+                    old_type: Some("U512".to_string()),
+                    change: Change::Add {
+                        cl_type: CLType::U512,
+                        delta: "5".to_string()
+                    },
+                },
+                EffectDiff {
+                    key: counter_key,
+                    old_type: Some("I32".to_string()),
+                    change: Change::Add {
+                        cl_type: CLType::I32,
+                        delta: "-1".to_string()
+                    },
+                },
+            ]
+        );
+        assert_eq!(
+            diffs[2].to_string(),
+            format!("{}: I32 += -1 (I32)", counter_key)
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use super::{error, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
+use crate::{execution::DeployTrace, tracking_copy::CacheStats};
 use engine_shared::{
     additive_map::AdditiveMap, gas::Gas, motes::Motes, newtypes::CorrelationId,
     stored_value::StoredValue, transform::Transform,
@@ -38,16 +39,27 @@ fn make_payment_error_effects(
     ExecutionEffect::new(ops, transforms)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ExecutionResult {
     /// An error condition that happened during execution
     Failure {
         error: error::Error,
         effect: ExecutionEffect,
         cost: Gas,
+        trace: Option<DeployTrace>,
+        cache_stats: CacheStats,
+        /// The most 64KiB pages of wasm memory any contract executed held at once.
+        peak_memory_pages: u32,
     },
     /// Execution was finished successfully
-    Success { effect: ExecutionEffect, cost: Gas },
+    Success {
+        effect: ExecutionEffect,
+        cost: Gas,
+        trace: Option<DeployTrace>,
+        cache_stats: CacheStats,
+        /// The most 64KiB pages of wasm memory any contract executed held at once.
+        peak_memory_pages: u32,
+    },
 }
 
 pub enum ForcedTransferResult {
@@ -66,6 +78,9 @@ impl ExecutionResult {
             error,
             effect: Default::default(),
             cost: Gas::default(),
+            trace: None,
+            cache_stats: Default::default(),
+            peak_memory_pages: 0,
         }
     }
 
@@ -106,25 +121,196 @@ impl ExecutionResult {
         }
     }
 
+    /// The record of the execution, if tracing was enabled.
+    pub fn trace(&self) -> Option<&DeployTrace> {
+        match self {
+            ExecutionResult::Failure { trace, .. } => trace.as_ref(),
+            ExecutionResult::Success { trace, .. } => trace.as_ref(),
+        }
+    }
+
+    /// The hits and misses of the deploy's read cache.
+    pub fn cache_stats(&self) -> CacheStats {
+        match self {
+            ExecutionResult::Failure { cache_stats, .. } => *cache_stats,
+            ExecutionResult::Success { cache_stats, .. } => *cache_stats,
+        }
+    }
+
+    /// The most 64KiB pages of wasm memory any contract executed held at once.
+    pub fn peak_memory_pages(&self) -> u32 {
+        match self {
+            ExecutionResult::Failure {
+                peak_memory_pages, ..
+            } => *peak_memory_pages,
+            ExecutionResult::Success {
+                peak_memory_pages, ..
+            } => *peak_memory_pages,
+        }
+    }
+
     pub fn with_cost(self, cost: Gas) -> Self {
         match self {
-            ExecutionResult::Failure { error, effect, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+                ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+            },
+            ExecutionResult::Success {
+                effect,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
             },
-            ExecutionResult::Success { effect, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
     pub fn with_effect(self, effect: ExecutionEffect) -> Self {
         match self {
-            ExecutionResult::Failure { error, cost, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+            },
+            ExecutionResult::Success {
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+            },
+        }
+    }
+
+    pub fn with_trace(self, trace: Option<DeployTrace>) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                cache_stats,
+                peak_memory_pages,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                cache_stats,
+                peak_memory_pages,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+            },
+        }
+    }
+
+    pub fn with_cache_stats(self, cache_stats: CacheStats) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                trace,
+                peak_memory_pages,
+                ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
+                peak_memory_pages,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+            },
+        }
+    }
+
+    pub fn with_peak_memory_pages(self, peak_memory_pages: u32) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                trace,
+                cache_stats,
+                peak_memory_pages,
             },
-            ExecutionResult::Success { cost, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
@@ -191,6 +377,9 @@ impl ExecutionResult {
             error,
             effect,
             cost,
+            trace: None,
+            cache_stats: Default::default(),
+            peak_memory_pages: 0,
         }
     }
 }
@@ -266,10 +455,15 @@ impl ExecutionResultBuilder {
         let cost = self.total_cost();
         let mut ops = AdditiveMap::new();
         let mut transforms = AdditiveMap::new();
+        let mut trace: Option<DeployTrace> = None;
+        let mut peak_memory_pages = 0;
 
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
             cost,
+            trace: None,
+            cache_stats: Default::default(),
+            peak_memory_pages: 0,
         };
 
         match self.payment_execution_result {
@@ -278,6 +472,8 @@ impl ExecutionResultBuilder {
                     return Ok(result);
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_trace(&mut trace, &result);
+                    peak_memory_pages = peak_memory_pages.max(result.peak_memory_pages());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -287,6 +483,8 @@ impl ExecutionResultBuilder {
         // exec error
         match self.session_execution_result {
             Some(result) => {
+                Self::add_trace(&mut trace, &result);
+                peak_memory_pages = peak_memory_pages.max(result.peak_memory_pages());
                 if result.is_failure() {
                     ret = result.with_cost(cost);
                 } else {
@@ -305,6 +503,8 @@ impl ExecutionResultBuilder {
                     ));
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_trace(&mut trace, &result);
+                    peak_memory_pages = peak_memory_pages.max(result.peak_memory_pages());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
@@ -313,7 +513,18 @@ impl ExecutionResultBuilder {
         // Remove redundant writes to allow more opportunity to commute
         let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
 
-        Ok(ret.with_effect(reduced_effect))
+        Ok(ret
+            .with_effect(reduced_effect)
+            .with_trace(trace)
+            .with_peak_memory_pages(peak_memory_pages))
+    }
+
+    fn add_trace(trace: &mut Option<DeployTrace>, result: &ExecutionResult) {
+        if let Some(result_trace) = result.trace() {
+            trace
+                .get_or_insert_with(DeployTrace::new)
+                .extend(result_trace.clone());
+        }
     }
 
     fn add_effects(
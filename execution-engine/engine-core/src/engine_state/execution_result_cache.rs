@@ -0,0 +1,226 @@
+use std::sync::{Arc, Mutex};
+
+use linked_hash_map::LinkedHashMap;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{BlockTime, ProtocolVersion};
+
+use crate::{engine_state::execution_result::ExecutionResult, DeployHash};
+
+/// The default limit on the number of results held by an [`ExecutionResultCache`].
+pub const DEFAULT_MAX_EXECUTION_RESULT_CACHE_ENTRIES: usize = 10_000;
+
+/// Everything besides global state and the deploy itself which the result of executing a deploy
+/// depends on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    prestate_hash: Blake2bHash,
+    deploy_hash: DeployHash,
+    protocol_version: ProtocolVersion,
+    block_time: BlockTime,
+    block_height: u64,
+}
+
+struct Entries {
+    max_entries: usize,
+    results: LinkedHashMap<CacheKey, ExecutionResult>,
+}
+
+/// A cache of the results of executing deploys, keyed by the prestate hash they were executed
+/// against and their deploy hash, so that re-executing the same block skips deploys which have
+/// already been run.
+///
+/// Since the deploy hash stands in for the deploy's contents, the cache should only be enabled
+/// when deploy hashes are unique, as they are for signed deploys sent by the node.
+#[derive(Clone)]
+pub struct ExecutionResultCache(Arc<Mutex<Entries>>);
+
+impl ExecutionResultCache {
+    /// Creates an empty cache holding at most `max_entries` results, above which the
+    /// least-recently-used results are evicted.
+    pub fn new(max_entries: usize) -> Self {
+        let entries = Entries {
+            max_entries,
+            results: LinkedHashMap::new(),
+        };
+        ExecutionResultCache(Arc::new(Mutex::new(entries)))
+    }
+
+    /// Returns a copy of the result of executing the deploy with `deploy_hash` against
+    /// `prestate_hash` in the given block, if cached.
+    pub fn get(
+        &self,
+        prestate_hash: Blake2bHash,
+        deploy_hash: DeployHash,
+        protocol_version: ProtocolVersion,
+        block_time: BlockTime,
+        block_height: u64,
+    ) -> Option<ExecutionResult> {
+        let key = CacheKey {
+            prestate_hash,
+            deploy_hash,
+            protocol_version,
+            block_time,
+            block_height,
+        };
+        self.0.lock().unwrap().results.get_refresh(&key).cloned()
+    }
+
+    /// Caches `result` as the result of executing the deploy with `deploy_hash` against
+    /// `prestate_hash` in the given block.
+    pub fn insert(
+        &self,
+        prestate_hash: Blake2bHash,
+        deploy_hash: DeployHash,
+        protocol_version: ProtocolVersion,
+        block_time: BlockTime,
+        block_height: u64,
+        result: ExecutionResult,
+    ) {
+        let key = CacheKey {
+            prestate_hash,
+            deploy_hash,
+            protocol_version,
+            block_time,
+            block_height,
+        };
+        let mut entries = self.0.lock().unwrap();
+        entries.results.insert(key, result);
+        while entries.results.len() > entries.max_entries {
+            entries.results.pop_front();
+        }
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().results.len()
+    }
+
+    /// Returns `true` if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for ExecutionResultCache {
+    fn default() -> Self {
+        ExecutionResultCache::new(DEFAULT_MAX_EXECUTION_RESULT_CACHE_ENTRIES)
+    }
+}
+
+impl std::fmt::Debug for ExecutionResultCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ExecutionResultCache")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::{gas::Gas, newtypes::Blake2bHash};
+    use types::{BlockTime, ProtocolVersion};
+
+    use super::ExecutionResultCache;
+    use crate::engine_state::execution_result::ExecutionResult;
+
+    fn result_with_cost(cost: u64) -> ExecutionResult {
+        ExecutionResult::Success {
+            effect: Default::default(),
+            cost: Gas::new(cost.into()),
+            trace: None,
+            cache_stats: Default::default(),
+            peak_memory_pages: 0,
+        }
+    }
+
+    #[test]
+    fn should_only_serve_results_for_the_same_prestate_and_block() {
+        let cache = ExecutionResultCache::new(10);
+        let prestate_hash = Blake2bHash::new(b"prestate");
+        let other_prestate_hash = Blake2bHash::new(b"other prestate");
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let block_time = BlockTime::new(10);
+
+        cache.insert(
+            prestate_hash,
+            [1; 32],
+            protocol_version,
+            block_time,
+            1,
+            result_with_cost(5),
+        );
+
+        let cached = cache
+            .get(prestate_hash, [1; 32], protocol_version, block_time, 1)
+            .expect("should be cached");
+        assert_eq!(cached.cost(), Gas::new(5.into()));
+
+        assert!(cache
+            .get(
+                other_prestate_hash,
+                [1; 32],
+                protocol_version,
+                block_time,
+                1
+            )
+            .is_none());
+        assert!(cache
+            .get(prestate_hash, [2; 32], protocol_version, block_time, 1)
+            .is_none());
+        assert!(cache
+            .get(
+                prestate_hash,
+                [1; 32],
+                protocol_version,
+                BlockTime::new(11),
+                1
+            )
+            .is_none());
+        assert!(cache
+            .get(prestate_hash, [1; 32], protocol_version, block_time, 2)
+            .is_none());
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_result() {
+        let cache = ExecutionResultCache::new(2);
+        let prestate_hash = Blake2bHash::new(b"prestate");
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let block_time = BlockTime::new(10);
+
+        for deploy_hash in &[[1; 32], [2; 32]] {
+            cache.insert(
+                prestate_hash,
+                *deploy_hash,
+                protocol_version,
+                block_time,
+                1,
+                result_with_cost(1),
+            );
+        }
+        // Touching the first result makes the second one the least recently used.
+        assert!(cache
+            .get(prestate_hash, [1; 32], protocol_version, block_time, 1)
+            .is_some());
+        cache.insert(
+            prestate_hash,
+            [3; 32],
+            protocol_version,
+            block_time,
+            1,
+            result_with_cost(1),
+        );
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache
+            .get(prestate_hash, [1; 32], protocol_version, block_time, 1)
+            .is_some());
+        assert!(cache
+            .get(prestate_hash, [2; 32], protocol_version, block_time, 1)
+            .is_none());
+        assert!(cache
+            .get(prestate_hash, [3; 32], protocol_version, block_time, 1)
+            .is_some());
+    }
+}
@@ -1,4 +1,6 @@
-use std::{fmt, iter};
+pub mod accounts_file;
+
+use std::{collections::BTreeSet, fmt, iter};
 
 use num_traits::Zero;
 use rand::{
@@ -9,9 +11,17 @@ use rand::{
 use engine_shared::{motes::Motes, newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
 use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
+use types::{
+    account::PublicKey, bytesrepr, BlockTime, Key, ProtocolVersion, VestingSchedule, U512,
+};
 
-use crate::engine_state::execution_effect::ExecutionEffect;
+use crate::{
+    engine_state::{
+        deploy_whitelist::DEPLOY_WHITELIST_NAME, execution_effect::ExecutionEffect,
+        SYSTEM_ACCOUNT_ADDR,
+    },
+    execution::{MINT_NAME, POS_NAME},
+};
 
 pub const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
 pub const POS_BONDING_PURSE: &str = "pos_bonding_purse";
@@ -20,6 +30,8 @@ pub const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
 
 pub enum GenesisResult {
     RootNotFound,
+    /// The genesis config was rejected before any installer was executed.
+    InvalidConfig(Vec<GenesisConfigError>),
     KeyNotFound(Key),
     TypeMismatch(TypeMismatch),
     Serialization(bytesrepr::Error),
@@ -33,6 +45,13 @@ impl fmt::Display for GenesisResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
             GenesisResult::RootNotFound => write!(f, "Root not found"),
+            GenesisResult::InvalidConfig(errors) => {
+                write!(f, "Invalid genesis config:")?;
+                for error in errors {
+                    write!(f, " {};", error)?;
+                }
+                Ok(())
+            }
             GenesisResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
             GenesisResult::TypeMismatch(type_mismatch) => {
                 write!(f, "Type mismatch: {:?}", type_mismatch)
@@ -61,11 +80,83 @@ impl GenesisResult {
     }
 }
 
+/// A problem found by [`ExecConfig::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenesisConfigError {
+    /// The same public key is used by more than one genesis account.
+    DuplicateAccount(PublicKey),
+    /// A genesis account uses the public key reserved for the system account.
+    SystemAccountKey,
+    /// A genesis account bonds more motes than it holds.
+    BondExceedsBalance {
+        public_key: PublicKey,
+        balance: Motes,
+        bonded_amount: Motes,
+    },
+    /// A genesis account's vesting schedule locks more motes than it holds.
+    LockExceedsBalance {
+        public_key: PublicKey,
+        balance: Motes,
+        locked_amount: Motes,
+    },
+    /// An installer is not a valid Wasm module.
+    MalformedInstaller { name: String, error: String },
+    /// An extra installer's name is already taken by a system contract or another installer.
+    DuplicateInstallerName(String),
+    /// The deploy whitelist admin is not one of the genesis accounts.
+    UnknownWhitelistAdmin(PublicKey),
+}
+
+impl fmt::Display for GenesisConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            GenesisConfigError::DuplicateAccount(public_key) => {
+                write!(f, "duplicate account {}", public_key)
+            }
+            GenesisConfigError::SystemAccountKey => {
+                write!(f, "account uses the system account's public key")
+            }
+            GenesisConfigError::BondExceedsBalance {
+                public_key,
+                balance,
+                bonded_amount,
+            } => write!(
+                f,
+                "account {} bonds {} motes but only holds {}",
+                public_key,
+                bonded_amount.value(),
+                balance.value()
+            ),
+            GenesisConfigError::LockExceedsBalance {
+                public_key,
+                balance,
+                locked_amount,
+            } => write!(
+                f,
+                "account {} locks {} motes but only holds {}",
+                public_key,
+                locked_amount.value(),
+                balance.value()
+            ),
+            GenesisConfigError::MalformedInstaller { name, error } => {
+                write!(f, "malformed {} installer: {}", name, error)
+            }
+            GenesisConfigError::DuplicateInstallerName(name) => {
+                write!(f, "duplicate installer name {}", name)
+            }
+            GenesisConfigError::UnknownWhitelistAdmin(public_key) => {
+                write!(f, "whitelist admin {} is not a genesis account", public_key)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct GenesisAccount {
     public_key: PublicKey,
     balance: Motes,
     bonded_amount: Motes,
+    vesting_schedule: Option<VestingSchedule>,
 }
 
 impl GenesisAccount {
@@ -74,9 +165,18 @@ impl GenesisAccount {
             public_key,
             balance,
             bonded_amount,
+            vesting_schedule: None,
         }
     }
 
+    /// Locks part of the account's balance until the schedule's cliff, after which it is released
+    /// linearly.  The mint refuses transfers out of the main purse which would dip below the amount
+    /// still locked.
+    pub fn with_vesting_schedule(mut self, vesting_schedule: VestingSchedule) -> Self {
+        self.vesting_schedule = Some(vesting_schedule);
+        self
+    }
+
     pub fn public_key(&self) -> PublicKey {
         self.public_key
     }
@@ -88,6 +188,10 @@ impl GenesisAccount {
     pub fn bonded_amount(&self) -> Motes {
         self.bonded_amount
     }
+
+    pub fn vesting_schedule(&self) -> Option<VestingSchedule> {
+        self.vesting_schedule
+    }
 }
 
 impl Distribution<GenesisAccount> for Standard {
@@ -101,10 +205,78 @@ impl Distribution<GenesisAccount> for Standard {
         rng.fill_bytes(u512_array.as_mut());
         let bonded_amount = Motes::new(U512::from(u512_array.as_ref()));
 
+        let vesting_schedule = if rng.gen() {
+            rng.fill_bytes(u512_array.as_mut());
+            let locked_amount = U512::from(u512_array.as_ref());
+            Some(VestingSchedule::new(
+                locked_amount,
+                BlockTime::new(rng.gen()),
+                rng.gen(),
+            ))
+        } else {
+            None
+        };
+
         GenesisAccount {
             public_key,
             balance,
             bonded_amount,
+            vesting_schedule,
+        }
+    }
+}
+
+/// A contract installed at genesis after the system contracts.
+///
+/// The installer is executed as the system account and must return a `URef`, which is added to
+/// the named keys of every genesis account under `name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisInstaller {
+    name: String,
+    installer_bytes: Vec<u8>,
+    args: Vec<u8>,
+}
+
+impl GenesisInstaller {
+    pub fn new(name: String, installer_bytes: Vec<u8>, args: Vec<u8>) -> Self {
+        GenesisInstaller {
+            name,
+            installer_bytes,
+            args,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn installer_bytes(&self) -> &[u8] {
+        self.installer_bytes.as_slice()
+    }
+
+    pub fn args(&self) -> &[u8] {
+        self.args.as_slice()
+    }
+}
+
+impl Distribution<GenesisInstaller> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GenesisInstaller {
+        let mut count = rng.gen_range(1, 100);
+        let name = iter::repeat(())
+            .map(|_| rng.gen::<char>())
+            .take(count)
+            .collect();
+
+        count = rng.gen_range(1000, 10_000);
+        let installer_bytes = iter::repeat(()).map(|_| rng.gen()).take(count).collect();
+
+        count = rng.gen_range(0, 100);
+        let args = iter::repeat(()).map(|_| rng.gen()).take(count).collect();
+
+        GenesisInstaller {
+            name,
+            installer_bytes,
+            args,
         }
     }
 }
@@ -156,6 +328,12 @@ impl GenesisConfig {
     pub fn take_ee_config(self) -> ExecConfig {
         self.ee_config
     }
+
+    /// Checks the config for problems which would otherwise only surface part way through
+    /// `commit_genesis`.
+    pub fn validate(&self) -> Result<(), Vec<GenesisConfigError>> {
+        self.ee_config.validate()
+    }
 }
 
 impl Distribution<GenesisConfig> for Standard {
@@ -186,8 +364,14 @@ pub struct ExecConfig {
     mint_installer_bytes: Vec<u8>,
     proof_of_stake_installer_bytes: Vec<u8>,
     standard_payment_installer_bytes: Vec<u8>,
+    key_recovery_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    unbonding_delay: u64,
+    extra_installers: Vec<GenesisInstaller>,
+    system_account: PublicKey,
+    whitelist_admin: Option<PublicKey>,
+    chain_name: String,
 }
 
 impl ExecConfig {
@@ -195,17 +379,26 @@ impl ExecConfig {
         mint_installer_bytes: Vec<u8>,
         proof_of_stake_installer_bytes: Vec<u8>,
         standard_payment_installer_bytes: Vec<u8>,
+        key_recovery_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        unbonding_delay: u64,
     ) -> ExecConfig {
         ExecConfig {
             mint_installer_bytes,
             proof_of_stake_installer_bytes,
             standard_payment_installer_bytes,
+            key_recovery_installer_bytes,
             accounts,
             wasm_costs,
+            unbonding_delay,
+            extra_installers: Vec::new(),
+            system_account: SYSTEM_ACCOUNT_ADDR,
+            whitelist_admin: None,
+            chain_name: String::new(),
         }
     }
+
     pub fn mint_installer_bytes(&self) -> &[u8] {
         self.mint_installer_bytes.as_slice()
     }
@@ -218,10 +411,19 @@ impl ExecConfig {
         self.standard_payment_installer_bytes.as_slice()
     }
 
+    pub fn key_recovery_installer_bytes(&self) -> &[u8] {
+        self.key_recovery_installer_bytes.as_slice()
+    }
+
     pub fn wasm_costs(&self) -> WasmCosts {
         self.wasm_costs
     }
 
+    /// The time, in block time units, from an unbonding request until its funds are paid out.
+    pub fn unbonding_delay(&self) -> u64 {
+        self.unbonding_delay
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (PublicKey, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
@@ -243,6 +445,120 @@ impl ExecConfig {
     pub fn push_account(&mut self, account: GenesisAccount) {
         self.accounts.push(account)
     }
+
+    /// Additional installers, run in order after the system contracts have been installed.
+    pub fn extra_installers(&self) -> &[GenesisInstaller] {
+        self.extra_installers.as_slice()
+    }
+
+    pub fn push_installer(&mut self, installer: GenesisInstaller) {
+        self.extra_installers.push(installer)
+    }
+
+    /// The public key of the system account, which defaults to [`SYSTEM_ACCOUNT_ADDR`].
+    pub fn system_account(&self) -> PublicKey {
+        self.system_account
+    }
+
+    pub fn set_system_account(&mut self, system_account: PublicKey) {
+        self.system_account = system_account
+    }
+
+    /// The genesis account which controls the deploy whitelist, if the chain has one.  The
+    /// whitelist initially holds all genesis accounts.
+    pub fn whitelist_admin(&self) -> Option<PublicKey> {
+        self.whitelist_admin
+    }
+
+    pub fn set_whitelist_admin(&mut self, whitelist_admin: Option<PublicKey>) {
+        self.whitelist_admin = whitelist_admin
+    }
+
+    /// The name of the chain, readable by contracts.  Empty if the chainspec doesn't name it.
+    pub fn chain_name(&self) -> &str {
+        self.chain_name.as_str()
+    }
+
+    pub fn set_chain_name(&mut self, chain_name: String) {
+        self.chain_name = chain_name
+    }
+
+    /// Checks the config for problems which would otherwise only surface part way through
+    /// `commit_genesis`, returning all of them rather than just the first.
+    pub fn validate(&self) -> Result<(), Vec<GenesisConfigError>> {
+        let mut errors = Vec::new();
+
+        let mut public_keys = BTreeSet::new();
+        for account in self.accounts.iter() {
+            let public_key = account.public_key();
+            if public_key == self.system_account {
+                errors.push(GenesisConfigError::SystemAccountKey);
+            } else if !public_keys.insert(public_key) {
+                errors.push(GenesisConfigError::DuplicateAccount(public_key));
+            }
+            if account.bonded_amount() > account.balance() {
+                errors.push(GenesisConfigError::BondExceedsBalance {
+                    public_key,
+                    balance: account.balance(),
+                    bonded_amount: account.bonded_amount(),
+                });
+            }
+            if let Some(vesting_schedule) = account.vesting_schedule() {
+                let locked_amount = Motes::new(vesting_schedule.locked_amount());
+                if locked_amount > account.balance() {
+                    errors.push(GenesisConfigError::LockExceedsBalance {
+                        public_key,
+                        balance: account.balance(),
+                        locked_amount,
+                    });
+                }
+            }
+        }
+
+        let mut installers: Vec<(&str, &[u8])> = vec![
+            ("mint", self.mint_installer_bytes()),
+            ("proof of stake", self.proof_of_stake_installer_bytes()),
+            ("standard payment", self.standard_payment_installer_bytes()),
+        ];
+        // Empty key recovery installer bytes fall back to the bundled installer
+        if !self.key_recovery_installer_bytes.is_empty() {
+            installers.push(("key recovery", self.key_recovery_installer_bytes()));
+        }
+        if let Some(whitelist_admin) = self.whitelist_admin {
+            if !public_keys.contains(&whitelist_admin) {
+                errors.push(GenesisConfigError::UnknownWhitelistAdmin(whitelist_admin));
+            }
+        }
+
+        // Extra installers share the accounts' named keys with the mint and PoS, and with the
+        // whitelist in the case of the admin
+        let mut installer_names: BTreeSet<&str> = vec![MINT_NAME, POS_NAME].into_iter().collect();
+        if self.whitelist_admin.is_some() {
+            installer_names.insert(DEPLOY_WHITELIST_NAME);
+        }
+        for installer in self.extra_installers.iter() {
+            if !installer_names.insert(installer.name()) {
+                errors.push(GenesisConfigError::DuplicateInstallerName(
+                    installer.name().to_string(),
+                ));
+            }
+            installers.push((installer.name(), installer.installer_bytes()));
+        }
+        for (name, bytes) in installers {
+            if let Err(error) = engine_wasm_prep::deserialize(bytes) {
+                errors.push(GenesisConfigError::MalformedInstaller {
+                    name: name.to_string(),
+                    error: error.to_string(),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
 
 impl Distribution<ExecConfig> for Standard {
@@ -258,8 +574,13 @@ impl Distribution<ExecConfig> for Standard {
         let standard_payment_installer_bytes =
             iter::repeat(()).map(|_| rng.gen()).take(count).collect();
 
+        count = rng.gen_range(1000, 10_000);
+        let key_recovery_installer_bytes =
+            iter::repeat(()).map(|_| rng.gen()).take(count).collect();
+
         count = rng.gen_range(1, 10);
-        let accounts = iter::repeat(()).map(|_| rng.gen()).take(count).collect();
+        let accounts: Vec<GenesisAccount> =
+            iter::repeat(()).map(|_| rng.gen()).take(count).collect();
 
         let wasm_costs = WasmCosts {
             regular: rng.gen(),
@@ -272,14 +593,179 @@ impl Distribution<ExecConfig> for Standard {
             max_stack_height: rng.gen(),
             opcodes_mul: rng.gen(),
             opcodes_div: rng.gen(),
+            max_memory_pages: rng.gen(),
+            max_table_size: rng.gen(),
+            hash_per_byte: rng.gen(),
+            verify_ed25519: rng.gen(),
+            verify_secp256k1: rng.gen(),
+            bignum_op: rng.gen(),
+            storage_bucket_size: rng.gen(),
+            storage_read_per_bucket: rng.gen(),
+            storage_write_per_bucket: rng.gen(),
+            max_module_size: rng.gen(),
+            max_function_count: rng.gen(),
+            max_named_keys_per_write: rng.gen(),
         };
 
+        let unbonding_delay = rng.gen();
+
+        count = rng.gen_range(0, 3);
+        let extra_installers = iter::repeat(()).map(|_| rng.gen()).take(count).collect();
+
+        let system_account = PublicKey::ed25519_from(rng.gen());
+
+        let whitelist_admin = if rng.gen() {
+            accounts.first().map(GenesisAccount::public_key)
+        } else {
+            None
+        };
+
+        count = rng.gen_range(0, 20);
+        let chain_name = iter::repeat(())
+            .map(|_| rng.gen::<char>())
+            .take(count)
+            .collect();
+
         ExecConfig {
             mint_installer_bytes,
             proof_of_stake_installer_bytes,
             standard_payment_installer_bytes,
+            key_recovery_installer_bytes,
             accounts,
             wasm_costs,
+            unbonding_delay,
+            extra_installers,
+            system_account,
+            whitelist_admin,
+            chain_name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The smallest valid Wasm module: just the magic number and version
+    const EMPTY_MODULE: &[u8] = b"\0asm\x01\0\0\0";
+    const PUBLIC_KEY: PublicKey = PublicKey::ed25519_from([1; 32]);
+
+    fn exec_config(accounts: Vec<GenesisAccount>) -> ExecConfig {
+        ExecConfig::new(
+            EMPTY_MODULE.to_vec(),
+            EMPTY_MODULE.to_vec(),
+            EMPTY_MODULE.to_vec(),
+            Vec::new(),
+            accounts,
+            WasmCosts::default(),
+            0,
+        )
+    }
+
+    fn account(public_key: PublicKey, balance: u64, bonded_amount: u64) -> GenesisAccount {
+        GenesisAccount::new(
+            public_key,
+            Motes::new(balance.into()),
+            Motes::new(bonded_amount.into()),
+        )
+    }
+
+    #[test]
+    fn should_accept_valid_config() {
+        let mut exec_config = exec_config(vec![account(PUBLIC_KEY, 10, 10)]);
+        exec_config.push_installer(GenesisInstaller::new(
+            "extra".to_string(),
+            EMPTY_MODULE.to_vec(),
+            Vec::new(),
+        ));
+        assert_eq!(exec_config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_report_all_account_problems() {
+        let exec_config = exec_config(vec![
+            account(PUBLIC_KEY, 10, 0),
+            account(PUBLIC_KEY, 10, 11),
+            account(SYSTEM_ACCOUNT_ADDR, 10, 0),
+        ]);
+        assert_eq!(
+            exec_config.validate(),
+            Err(vec![
+                GenesisConfigError::DuplicateAccount(PUBLIC_KEY),
+                GenesisConfigError::BondExceedsBalance {
+                    public_key: PUBLIC_KEY,
+                    balance: Motes::new(10.into()),
+                    bonded_amount: Motes::new(11.into()),
+                },
+                GenesisConfigError::SystemAccountKey,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_reject_lock_exceeding_balance() {
+        let vesting_schedule = VestingSchedule::new(11.into(), BlockTime::new(100), 10);
+        let exec_config = exec_config(vec![
+            account(PUBLIC_KEY, 10, 0).with_vesting_schedule(vesting_schedule)
+        ]);
+        assert_eq!(
+            exec_config.validate(),
+            Err(vec![GenesisConfigError::LockExceedsBalance {
+                public_key: PUBLIC_KEY,
+                balance: Motes::new(10.into()),
+                locked_amount: Motes::new(11.into()),
+            }])
+        );
+    }
+
+    #[test]
+    fn should_reserve_configured_system_account() {
+        let mut exec_config = exec_config(vec![account(SYSTEM_ACCOUNT_ADDR, 10, 0)]);
+        exec_config.set_system_account(PUBLIC_KEY);
+        assert_eq!(exec_config.validate(), Ok(()));
+
+        exec_config.push_account(account(PUBLIC_KEY, 10, 0));
+        assert_eq!(
+            exec_config.validate(),
+            Err(vec![GenesisConfigError::SystemAccountKey])
+        );
+    }
+
+    #[test]
+    fn should_require_whitelist_admin_to_be_a_genesis_account() {
+        let mut exec_config = exec_config(vec![account(PUBLIC_KEY, 10, 0)]);
+        exec_config.set_whitelist_admin(Some(PUBLIC_KEY));
+        assert_eq!(exec_config.validate(), Ok(()));
+
+        let unknown_admin = PublicKey::ed25519_from([2; 32]);
+        exec_config.set_whitelist_admin(Some(unknown_admin));
+        assert_eq!(
+            exec_config.validate(),
+            Err(vec![GenesisConfigError::UnknownWhitelistAdmin(
+                unknown_admin
+            )])
+        );
+    }
+
+    #[test]
+    fn should_report_installer_problems() {
+        let mut exec_config = exec_config(Vec::new());
+        exec_config.mint_installer_bytes = vec![1, 2, 3];
+        exec_config.push_installer(GenesisInstaller::new(
+            MINT_NAME.to_string(),
+            EMPTY_MODULE.to_vec(),
+            Vec::new(),
+        ));
+
+        let errors = exec_config.validate().expect_err("should be invalid");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            GenesisConfigError::DuplicateInstallerName(MINT_NAME.to_string())
+        );
+        match &errors[1] {
+            GenesisConfigError::MalformedInstaller { name, .. } => assert_eq!(name, "mint"),
+            error => panic!("unexpected error {:?}", error),
         }
     }
 }
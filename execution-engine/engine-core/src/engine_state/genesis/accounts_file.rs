@@ -0,0 +1,192 @@
+//! Loading of the genesis accounts from `accounts.csv` style files.
+//!
+//! Each non-empty line holds `<public key>,<balance>,<bonded amount>`, where the public key is an
+//! Ed25519 key in either Base64 or Base16 and both amounts are in motes, written in decimal.  A
+//! bonded amount of zero means the account is not a validator.  Lines starting with `#` are
+//! ignored.
+
+use std::{collections::BTreeSet, fmt, fs, path::Path};
+
+use engine_shared::motes::Motes;
+use types::{account::PublicKey, U512};
+
+use super::GenesisAccount;
+
+const FIELD_COUNT: usize = 3;
+const COMMENT_PREFIX: char = '#';
+const BASE16_PUBLIC_KEY_LENGTH: usize = 64;
+
+/// An error found while loading an accounts file.  Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountsFileError {
+    Io(String),
+    WrongFieldCount { line: usize, count: usize },
+    InvalidPublicKey { line: usize, value: String },
+    InvalidAmount { line: usize, value: String },
+    DuplicateAccount { line: usize, public_key: PublicKey },
+}
+
+impl fmt::Display for AccountsFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            AccountsFileError::Io(error) => write!(f, "Failed to read accounts file: {}", error),
+            AccountsFileError::WrongFieldCount { line, count } => write!(
+                f,
+                "Line {}: expected {} fields but found {}",
+                line, FIELD_COUNT, count
+            ),
+            AccountsFileError::InvalidPublicKey { line, value } => {
+                write!(f, "Line {}: invalid public key {:?}", line, value)
+            }
+            AccountsFileError::InvalidAmount { line, value } => {
+                write!(f, "Line {}: invalid amount {:?}", line, value)
+            }
+            AccountsFileError::DuplicateAccount { line, public_key } => {
+                write!(f, "Line {}: duplicate account {}", line, public_key)
+            }
+        }
+    }
+}
+
+/// Reads and parses the accounts file at `path`.
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<GenesisAccount>, AccountsFileError> {
+    let contents =
+        fs::read_to_string(path).map_err(|error| AccountsFileError::Io(error.to_string()))?;
+    parse(&contents)
+}
+
+/// Parses the contents of an accounts file, keeping the accounts in the order they are listed.
+pub fn parse(contents: &str) -> Result<Vec<GenesisAccount>, AccountsFileError> {
+    let mut accounts = Vec::new();
+    let mut public_keys = BTreeSet::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(COMMENT_PREFIX) {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() != FIELD_COUNT {
+            return Err(AccountsFileError::WrongFieldCount {
+                line: line_number,
+                count: fields.len(),
+            });
+        }
+
+        let public_key =
+            parse_public_key(fields[0]).ok_or_else(|| AccountsFileError::InvalidPublicKey {
+                line: line_number,
+                value: fields[0].to_string(),
+            })?;
+        if !public_keys.insert(public_key) {
+            return Err(AccountsFileError::DuplicateAccount {
+                line: line_number,
+                public_key,
+            });
+        }
+        let balance = parse_motes(fields[1], line_number)?;
+        let bonded_amount = parse_motes(fields[2], line_number)?;
+
+        accounts.push(GenesisAccount::new(public_key, balance, bonded_amount));
+    }
+
+    Ok(accounts)
+}
+
+fn parse_public_key(value: &str) -> Option<PublicKey> {
+    let bytes = if value.len() == BASE16_PUBLIC_KEY_LENGTH {
+        base16::decode(value).ok()?
+    } else {
+        base64::decode(value).ok()?
+    };
+    PublicKey::ed25519_try_from(&bytes).ok()
+}
+
+fn parse_motes(value: &str, line: usize) -> Result<Motes, AccountsFileError> {
+    U512::from_dec_str(value)
+        .map(Motes::new)
+        .map_err(|_| AccountsFileError::InvalidAmount {
+            line,
+            value: value.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE64_KEY: &str = "o8C2vZUXgaDKX3pfXmSJxeNfkHueLMrgiP1wIbSYHvo=";
+    const BASE16_KEY: &str = "6ddcc278909af5b923d9eb88e043d4349dc0034ad95fdc2b4692b67395adfd16";
+
+    fn base64_public_key() -> PublicKey {
+        let bytes = base64::decode(BASE64_KEY).expect("should decode");
+        PublicKey::ed25519_try_from(&bytes).expect("should be a public key")
+    }
+
+    fn base16_public_key() -> PublicKey {
+        let bytes = base16::decode(BASE16_KEY).expect("should decode");
+        PublicKey::ed25519_try_from(&bytes).expect("should be a public key")
+    }
+
+    #[test]
+    fn should_parse_accounts_in_order() {
+        let contents = format!(
+            "# validators\n{},0,100\n\n {} , 1 , 0 \n",
+            BASE64_KEY, BASE16_KEY
+        );
+        let accounts = parse(&contents).expect("should parse");
+        assert_eq!(
+            accounts,
+            vec![
+                GenesisAccount::new(
+                    base64_public_key(),
+                    Motes::new(0.into()),
+                    Motes::new(100.into())
+                ),
+                GenesisAccount::new(
+                    base16_public_key(),
+                    Motes::new(1.into()),
+                    Motes::new(0.into())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_line_of_first_error() {
+        let contents = format!("{},0,100\n{},1\n", BASE64_KEY, BASE16_KEY);
+        assert_eq!(
+            parse(&contents),
+            Err(AccountsFileError::WrongFieldCount { line: 2, count: 2 })
+        );
+
+        let contents = format!("{},0,100\nnot-a-key,1,0\n", BASE64_KEY);
+        assert_eq!(
+            parse(&contents),
+            Err(AccountsFileError::InvalidPublicKey {
+                line: 2,
+                value: "not-a-key".to_string()
+            })
+        );
+
+        let contents = format!("{},-1,100\n", BASE64_KEY);
+        assert_eq!(
+            parse(&contents),
+            Err(AccountsFileError::InvalidAmount {
+                line: 1,
+                value: "-1".to_string()
+            })
+        );
+
+        let contents = format!("{},0,100\n{},1,0\n", BASE64_KEY, BASE64_KEY);
+        assert_eq!(
+            parse(&contents),
+            Err(AccountsFileError::DuplicateAccount {
+                line: 2,
+                public_key: base64_public_key()
+            })
+        );
+    }
+}
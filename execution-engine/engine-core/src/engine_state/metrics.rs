@@ -0,0 +1,89 @@
+//! Instrumentation points of the deploy pipeline.
+
+use std::{fmt::Debug, time::Duration};
+
+use engine_shared::{
+    logging::{log_duration, log_metric},
+    newtypes::CorrelationId,
+};
+
+/// Time spent preprocessing a deploy's Wasm module, in seconds.
+pub const PREPROCESS_DURATION: &str = "preprocess_duration";
+/// Time spent executing a deploy's payment code, in seconds.
+pub const PAYMENT_DURATION: &str = "payment_duration";
+/// Time spent executing a deploy's session code, in seconds.
+pub const SESSION_DURATION: &str = "session_duration";
+/// Time spent running the finalization of a deploy's payment, in seconds.
+pub const FINALIZE_DURATION: &str = "finalize_duration";
+/// Gas used by a deploy.
+pub const GAS_USED: &str = "gas_used";
+/// Reads served by the per-deploy read caches.
+pub const DEPLOY_CACHE_HITS: &str = "deploy_cache_hits";
+/// Reads which missed the per-deploy read caches.
+pub const DEPLOY_CACHE_MISSES: &str = "deploy_cache_misses";
+/// Deploys whose result was served by the execution result cache instead of executing them.
+pub const EXECUTION_RESULT_CACHE_HITS: &str = "execution_result_cache_hits";
+/// Reads served by the block cache.
+pub const BLOCK_CACHE_HITS: &str = "block_cache_hits";
+/// Reads which missed the block cache and went to the trie store.
+pub const TRIE_READS: &str = "trie_reads";
+/// Keys written, added to or deleted by a commit.
+pub const TRIE_WRITES: &str = "trie_writes";
+/// Serialized size of the trie nodes put by a commit, in bytes.
+pub const TRIE_BYTES_WRITTEN: &str = "trie_bytes_written";
+
+/// A sink for the counters and histograms recorded by [`EngineState`](super::EngineState).
+pub trait Metrics: Debug + Send + Sync {
+    /// Increases the counter `name` by `value`.
+    fn increment_counter(&self, correlation_id: CorrelationId, name: &'static str, value: u64);
+
+    /// Records a single observation of the histogram `name`.
+    fn record_histogram(&self, correlation_id: CorrelationId, name: &'static str, value: f64);
+
+    /// Records `duration` in seconds as an observation of the histogram `name`.
+    fn record_duration(
+        &self,
+        correlation_id: CorrelationId,
+        name: &'static str,
+        duration: Duration,
+    ) {
+        self.record_histogram(correlation_id, name, duration.as_secs_f64())
+    }
+}
+
+/// Discards everything recorded.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {
+    fn increment_counter(&self, _correlation_id: CorrelationId, _name: &'static str, _value: u64) {}
+
+    fn record_histogram(&self, _correlation_id: CorrelationId, _name: &'static str, _value: f64) {}
+}
+
+/// Emits everything recorded as metric log records.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogMetrics;
+
+impl LogMetrics {
+    const TAG: &'static str = "engine";
+}
+
+impl Metrics for LogMetrics {
+    fn increment_counter(&self, correlation_id: CorrelationId, name: &'static str, value: u64) {
+        log_metric(correlation_id, name, Self::TAG, "count", value as f64)
+    }
+
+    fn record_histogram(&self, correlation_id: CorrelationId, name: &'static str, value: f64) {
+        log_metric(correlation_id, name, Self::TAG, "value", value)
+    }
+
+    fn record_duration(
+        &self,
+        correlation_id: CorrelationId,
+        name: &'static str,
+        duration: Duration,
+    ) {
+        log_duration(correlation_id, name, Self::TAG, duration)
+    }
+}
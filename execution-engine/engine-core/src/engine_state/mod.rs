@@ -1,22 +1,40 @@
+pub mod accounts_index;
+pub mod check_signers;
+pub mod deploy_header;
 pub mod deploy_item;
+mod deploy_stages;
+pub mod deploy_whitelist;
+pub mod durability;
 pub mod engine_config;
+pub mod era_rewards;
+pub mod era_validators;
 mod error;
 pub mod executable_deploy_item;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
+pub mod execution_result_cache;
 pub mod genesis;
+pub mod metrics;
 pub mod op;
+pub mod pos_view;
 pub mod query;
 pub mod run_genesis_request;
+pub mod scheduled_call;
+pub mod slashing;
+pub mod step;
 pub mod system_contract_cache;
 pub mod upgrade;
 pub mod utils;
 
 use std::{
     cell::RefCell,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    convert::TryInto,
+    iter,
     rc::Rc,
+    sync::{Arc, Mutex},
+    time::Instant,
 };
 
 use num_traits::Zero;
@@ -27,6 +45,7 @@ use engine_shared::{
     account::Account,
     additive_map::AdditiveMap,
     gas::Gas,
+    logging::{DefaultStageLogger, Stage, StageLogger, StageOutcome},
     motes::Motes,
     newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
@@ -35,35 +54,65 @@ use engine_shared::{
 };
 use engine_storage::{
     global_state::{CommitResult, StateProvider, StateReader},
-    protocol_data::ProtocolData,
+    protocol_data::{
+        ProtocolData, DEFAULT_MAX_CALL_DEPTH, DEFAULT_REFUND_RATIO, DEFAULT_SYSTEM_ACCOUNT,
+        MAX_REFUND_RATIO,
+    },
 };
-use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_features::WasmFeatures, Preprocessor};
 use types::{
-    account::PublicKey, bytesrepr::ToBytes, system_contract_errors::mint,
-    system_contract_type::PROOF_OF_STAKE, AccessRights, BlockTime, Key, Phase, ProtocolVersion,
-    URef, KEY_HASH_LENGTH, U512, UREF_ADDR_LENGTH,
+    account::{AssociatedKeysUpdate, PublicKey},
+    bytesrepr::{self, ToBytes},
+    system_contract_errors::mint,
+    system_contract_type::PROOF_OF_STAKE,
+    AccessRights, ApiError, BlockTime, CLValue, Key, Phase, ProtocolVersion, URef, KEY_HASH_LENGTH,
+    U512, UREF_ADDR_LENGTH,
 };
 
 pub use self::{
-    engine_config::EngineConfig,
+    durability::DurabilityHandle,
+    engine_config::{EngineConfig, WhitelistMode},
     error::{Error, RootNotFound},
 };
 use crate::{
     engine_state::{
+        accounts_index::{AccountBalanceProof, ACCOUNTS_INDEX_ROOT_KEY},
+        check_signers::{
+            AuthorizationFailure, CheckSignersRequest, CheckSignersResult, SignersCheck,
+        },
+        deploy_header,
         deploy_item::DeployItem,
+        deploy_stages::DeployStages,
+        deploy_whitelist::{self, DEPLOY_WHITELIST_NAME},
+        durability::SyncWorker,
+        era_rewards::{EraReport, RewardsResult},
+        era_validators::{self, EraId, EraValidators, ValidatorStake},
         error::Error::MissingSystemContract,
         executable_deploy_item::ExecutableDeployItem,
         execute_request::ExecuteRequest,
+        execution_effect::ExecutionEffect,
         execution_result::{ExecutionResult, ForcedTransferResult},
+        execution_result_cache::ExecutionResultCache,
         genesis::{
-            ExecConfig, GenesisAccount, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE,
+            ExecConfig, GenesisAccount, GenesisResult, POS_BONDING_PURSE, POS_PAYMENT_PURSE,
+            POS_REWARDS_PURSE,
         },
+        metrics::Metrics,
+        op::Op,
+        pos_view::{PendingUnbond, PosView, PosViewRequest, PosViewResult},
         query::{QueryRequest, QueryResult},
+        scheduled_call::{self, ScheduledCall},
+        slashing::{EquivocationEvidence, SlashingResult},
+        step::{StepRequest, StepResult},
         system_contract_cache::SystemContractCache,
         upgrade::{UpgradeConfig, UpgradeResult},
     },
-    execution::{self, AddressGenerator, AddressGeneratorBuilder, Executor, MINT_NAME, POS_NAME},
-    tracking_copy::{TrackingCopy, TrackingCopyExt},
+    execution::{
+        self, AddressGenerator, AddressGeneratorBuilder, CancellationToken, Executor, MINT_NAME,
+        POS_NAME,
+    },
+    resolvers,
+    tracking_copy::{BlockCache, TrackingCopy, TrackingCopyExt},
     KnownKeys,
 };
 
@@ -71,17 +120,50 @@ use crate::{
 // TBD gas * CONV_RATE = motes
 pub const MAX_PAYMENT: u64 = 10_000_000;
 pub const CONV_RATE: u64 = 10;
+/// The gas charged for each update applied by a native key management deploy.
+pub const NATIVE_KEY_MANAGEMENT_UPDATE_COST: u64 = 10_000;
+/// The gas charged for a native transfer deploy.
+pub const NATIVE_TRANSFER_COST: u64 = 10_000;
 
-pub const SYSTEM_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([0u8; 32]);
+/// The public key of the system account unless the chain's genesis config sets another one; the
+/// configured key is held in each protocol version's [`ProtocolData`].
+pub const SYSTEM_ACCOUNT_ADDR: PublicKey = DEFAULT_SYSTEM_ACCOUNT;
 
 const GENESIS_INITIAL_BLOCKTIME: u64 = 0;
+const GENESIS_INITIAL_BLOCK_HEIGHT: u64 = 0;
 const MINT_METHOD_NAME: &str = "mint";
+const MINT_SET_VESTING_SCHEDULE_METHOD_NAME: &str = "set_vesting_schedule";
+/// The fraction of commits after which debug builds verify that the total supply is conserved.
+#[cfg(debug_assertions)]
+const TOTAL_SUPPLY_CHECK_PROBABILITY: f64 = 0.05;
 
+/// The engine's shared state. Cloning is cheap: clones share the global state and caches, so a
+/// frontend can serve queries and execution from multiple threads against the same engine.
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
     system_contract_cache: SystemContractCache,
-    state: S,
+    block_cache: BlockCache,
+    execution_result_cache: ExecutionResultCache,
+    metrics: Arc<dyn Metrics>,
+    stage_logger: Arc<dyn StageLogger>,
+    state: Arc<S>,
+    sync_worker: Arc<Mutex<Option<SyncWorker>>>,
+}
+
+impl<S> Clone for EngineState<S> {
+    fn clone(&self) -> Self {
+        EngineState {
+            config: self.config,
+            system_contract_cache: self.system_contract_cache.clone(),
+            block_cache: self.block_cache.clone(),
+            execution_result_cache: self.execution_result_cache.clone(),
+            metrics: Arc::clone(&self.metrics),
+            stage_logger: Arc::clone(&self.stage_logger),
+            state: Arc::clone(&self.state),
+            sync_worker: Arc::clone(&self.sync_worker),
+        }
+    }
 }
 
 impl<S> EngineState<S>
@@ -89,19 +171,55 @@ where
     S: StateProvider,
     S::Error: Into<execution::Error>,
 {
-    pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
+    pub fn new(
+        state: S,
+        config: EngineConfig,
+        metrics: Arc<dyn Metrics>,
+    ) -> Result<EngineState<S>, Error> {
+        Self::from_shared(Arc::new(state), config, metrics)
+    }
+
+    /// Creates an engine over a global state which may also be shared with other components.
+    pub fn from_shared(
+        state: Arc<S>,
+        config: EngineConfig,
+        metrics: Arc<dyn Metrics>,
+    ) -> Result<EngineState<S>, Error> {
+        if config.deferred_fsync() {
+            state
+                .set_deferred_sync(true)
+                .map_err(|error| Error::Exec(error.into()))?;
+        }
         let system_contract_cache = Default::default();
-        EngineState {
+        let block_cache = Default::default();
+        let execution_result_cache = Default::default();
+        Ok(EngineState {
             config,
             system_contract_cache,
+            block_cache,
+            execution_result_cache,
+            metrics,
+            stage_logger: Arc::new(DefaultStageLogger),
             state,
-        }
+            sync_worker: Default::default(),
+        })
+    }
+
+    /// Replaces the logger which receives an event as each stage of a deploy or commit ends.
+    pub fn with_stage_logger(mut self, stage_logger: Arc<dyn StageLogger>) -> Self {
+        self.stage_logger = stage_logger;
+        self
     }
 
     pub fn config(&self) -> &EngineConfig {
         &self.config
     }
 
+    /// Returns the global state the engine executes against.
+    pub fn global_state(&self) -> &S {
+        &self.state
+    }
+
     pub fn wasm_costs(
         &self,
         protocol_version: ProtocolVersion,
@@ -123,6 +241,71 @@ where
         }
     }
 
+    /// Returns the execution effects recorded for the deploy with hash `deploy_hash` by its most
+    /// recent execution, if the effects journal was enabled at the time.
+    pub fn get_deploy_effects(
+        &self,
+        deploy_hash: [u8; 32],
+    ) -> Result<Option<ExecutionEffect>, Error> {
+        let effect_bytes = match self.state.get_deploy_effects(deploy_hash) {
+            Ok(Some(effect_bytes)) => effect_bytes,
+            Ok(None) => return Ok(None),
+            Err(error) => return Err(Error::Exec(error.into())),
+        };
+        let effect = bytesrepr::deserialize(effect_bytes)?;
+        Ok(Some(effect))
+    }
+
+    /// Adds each of `deploy_hashes` to the activity index of every account whose key appears in
+    /// the deploy's recorded effects.  Deploys with no recorded effects, because the effects
+    /// journal was disabled when they were executed, are skipped.
+    #[cfg(feature = "account-activity-index")]
+    pub fn index_account_activity(&self, deploy_hashes: &[[u8; 32]]) -> Result<(), Error> {
+        for deploy_hash in deploy_hashes {
+            let effect = match self.get_deploy_effects(*deploy_hash)? {
+                Some(effect) => effect,
+                None => continue,
+            };
+            let accounts: Vec<PublicKey> = effect
+                .transforms
+                .keys()
+                .filter_map(|key| key.into_account())
+                .collect();
+            self.state
+                .record_account_activity(*deploy_hash, &accounts)
+                .map_err(|error| Error::Exec(error.into()))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the hashes of the deploys which touched the account `account`, in the order they
+    /// were indexed.
+    #[cfg(feature = "account-activity-index")]
+    pub fn get_account_activity(&self, account: PublicKey) -> Result<Vec<[u8; 32]>, Error> {
+        self.state
+            .get_account_activity(account)
+            .map_err(|error| Error::Exec(error.into()))
+    }
+
+    /// Records `effect` as the execution effects of the deploy with hash `deploy_hash`, if the
+    /// effects journal is enabled.  Failing to record them doesn't fail the deploy.
+    fn record_deploy_effects(&self, deploy_hash: [u8; 32], effect: &ExecutionEffect) {
+        if !self.config.enable_effects_journal() {
+            return;
+        }
+        let recorded: Result<(), execution::Error> = effect
+            .to_bytes()
+            .map_err(Into::into)
+            .and_then(|effect_bytes| {
+                self.state
+                    .put_deploy_effects(deploy_hash, &effect_bytes)
+                    .map_err(Into::into)
+            });
+        if let Err(error) = recorded {
+            log::warn!("Unable to record the effects of a deploy: {}", error);
+        }
+    }
+
     pub fn commit_genesis(
         &self,
         correlation_id: CorrelationId,
@@ -130,22 +313,32 @@ where
         protocol_version: ProtocolVersion,
         ee_config: &ExecConfig,
     ) -> Result<GenesisResult, Error> {
+        // Reject a bad config up front rather than part way through executing installers
+        if let Err(errors) = ee_config.validate() {
+            return Ok(GenesisResult::InvalidConfig(errors));
+        }
+
         // Preliminaries
         let executor = Executor::new(self.config);
         let blocktime = BlockTime::new(GENESIS_INITIAL_BLOCKTIME);
+        let block_height = GENESIS_INITIAL_BLOCK_HEIGHT;
         let gas_limit = Gas::new(std::u64::MAX.into());
         let phase = Phase::System;
 
-        let initial_base_key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+        let system_account_addr = ee_config.system_account();
+        let initial_base_key = Key::Account(system_account_addr);
         let initial_root_hash = self.state.empty_root();
         let wasm_costs = ee_config.wasm_costs();
-        let preprocessor = Preprocessor::new(wasm_costs);
+        // Post-MVP Wasm features can only be enabled by a subsequent upgrade
+        let wasm_features = WasmFeatures::default();
+        let preprocessor = Preprocessor::new(wasm_costs, wasm_features)
+            .with_import_whitelist(resolvers::is_host_function);
 
         // Spec #3: Create "virtual system account" object.
         let virtual_system_account = {
             let named_keys = BTreeMap::new();
             let purse = URef::new(Default::default(), AccessRights::READ_ADD_WRITE);
-            Account::create(SYSTEM_ACCOUNT_ADDR, named_keys, purse)
+            Account::create(system_account_addr, named_keys, purse)
         };
 
         // Spec #4: Create a runtime.
@@ -157,7 +350,7 @@ where
 
         // Persist the "virtual system account".  It will get overwritten with the actual system
         // account below.
-        let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+        let key = Key::Account(system_account_addr);
         let value = {
             let virtual_system_account = virtual_system_account.clone();
             StoredValue::Account(virtual_system_account)
@@ -194,6 +387,7 @@ where
                 &virtual_system_account,
                 authorization_keys,
                 blocktime,
+                block_height,
                 install_deploy_hash,
                 gas_limit,
                 address_generator,
@@ -201,7 +395,7 @@ where
                 correlation_id,
                 tracking_copy,
                 phase,
-                ProtocolData::default(),
+                ProtocolData::default().with_system_account(system_account_addr),
                 system_contract_cache,
             )?
         };
@@ -223,13 +417,18 @@ where
 
             // Constructs a partial protocol data with already known uref to pass the validation
             // step
-            let partial_protocol_data = ProtocolData::partial_with_mint(mint_reference);
+            let partial_protocol_data = ProtocolData::partial_with_mint(mint_reference)
+                .with_system_account(system_account_addr);
 
             let proof_of_stake_installer_bytes = ee_config.proof_of_stake_installer_bytes();
             let proof_of_stake_installer_module =
                 preprocessor.preprocess(proof_of_stake_installer_bytes)?;
             let args = {
-                let args = (mint_reference, bonded_validators);
+                let args = (
+                    mint_reference,
+                    bonded_validators,
+                    ee_config.unbonding_delay(),
+                );
                 ArgsParser::parse(args)
                     .expect("args should convert to `Vec<CLValue>`")
                     .into_bytes()
@@ -246,6 +445,7 @@ where
                 &virtual_system_account,
                 authorization_keys,
                 blocktime,
+                block_height,
                 install_deploy_hash,
                 gas_limit,
                 address_generator,
@@ -266,7 +466,8 @@ where
             wasm_costs,
             mint_reference,
             proof_of_stake_reference,
-        );
+        )
+        .with_system_account(system_account_addr);
 
         let standard_payment_reference: URef = {
             let standard_payment_installer_bytes =
@@ -298,6 +499,59 @@ where
                 &virtual_system_account,
                 authorization_keys,
                 blocktime,
+                block_height,
+                install_deploy_hash,
+                gas_limit,
+                address_generator,
+                protocol_version,
+                correlation_id,
+                tracking_copy,
+                phase,
+                protocol_data,
+                system_contract_cache,
+            )?
+        };
+
+        // Execute key recovery installer wasm code
+        let protocol_data = ProtocolData::partial_without_key_recovery(
+            wasm_costs,
+            mint_reference,
+            proof_of_stake_reference,
+            standard_payment_reference,
+        )
+        .with_system_account(system_account_addr);
+
+        let key_recovery_reference: URef = {
+            let key_recovery_installer_bytes =
+                if ee_config.key_recovery_installer_bytes().is_empty() {
+                    // The Node does not yet pass these bytes, so fall back to the bundled copy
+                    include_bytes!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/wasm/key_recovery_install.wasm"
+                    ))
+                } else {
+                    ee_config.key_recovery_installer_bytes()
+                };
+
+            let key_recovery_installer_module =
+                preprocessor.preprocess(key_recovery_installer_bytes)?;
+            let args = Vec::new();
+            let mut named_keys = BTreeMap::new();
+            let authorization_keys = BTreeSet::new();
+            let install_deploy_hash = genesis_config_hash.into();
+            let address_generator = Rc::clone(&address_generator);
+            let tracking_copy = Rc::clone(&tracking_copy);
+            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+            executor.exec_system(
+                key_recovery_installer_module,
+                args,
+                &mut named_keys,
+                initial_base_key,
+                &virtual_system_account,
+                authorization_keys,
+                blocktime,
+                block_height,
                 install_deploy_hash,
                 gas_limit,
                 address_generator,
@@ -310,18 +564,110 @@ where
             )?
         };
 
+        // Create the deploy whitelist, initially holding all genesis accounts, if the chain has
+        // an admin to control it
+        let deploy_whitelist: Option<URef> = ee_config.whitelist_admin().map(|_| {
+            let address = address_generator.borrow_mut().create_address();
+            let uref = URef::new(address, AccessRights::READ_ADD_WRITE);
+            let accounts: Vec<PublicKey> = ee_config
+                .accounts()
+                .iter()
+                .map(GenesisAccount::public_key)
+                .collect();
+            let cl_value = CLValue::from_t(accounts).expect("should convert accounts to CLValue");
+            tracking_copy
+                .borrow_mut()
+                .write(Key::URef(uref), StoredValue::CLValue(cl_value));
+            uref
+        });
+
+        // Store the chain name for contracts to read, if the chainspec gives one
+        let chain_name: Option<URef> = if ee_config.chain_name().is_empty() {
+            None
+        } else {
+            let address = address_generator.borrow_mut().create_address();
+            let uref = URef::new(address, AccessRights::READ);
+            let cl_value = CLValue::from_t(ee_config.chain_name().to_string())
+                .expect("should convert chain name to CLValue");
+            tracking_copy
+                .borrow_mut()
+                .write(Key::URef(uref), StoredValue::CLValue(cl_value));
+            Some(uref)
+        };
+
+        // Record the initially bonded validators as the stakes of the genesis era
+        {
+            let genesis_validators: EraValidators = ee_config
+                .get_bonded_validators()
+                .map(|(k, v)| (k, ValidatorStake::new(v.value())))
+                .collect();
+            let cl_value = CLValue::from_t(genesis_validators)
+                .expect("should convert era validators to CLValue");
+            let mut tracking_copy = tracking_copy.borrow_mut();
+            tracking_copy.write(
+                era_validators::era_validators_key(0),
+                StoredValue::CLValue(cl_value),
+            );
+            let cl_value = CLValue::from_t::<EraId>(0).expect("should convert era id to CLValue");
+            tracking_copy.write(
+                era_validators::current_era_id_key(),
+                StoredValue::CLValue(cl_value),
+            );
+        }
+
         // Spec #2: Associate given CostTable with given ProtocolVersion.
         let protocol_data = ProtocolData::new(
             wasm_costs,
             mint_reference,
             proof_of_stake_reference,
             standard_payment_reference,
-        );
+            key_recovery_reference,
+            DEFAULT_MAX_CALL_DEPTH,
+            wasm_features,
+            DEFAULT_REFUND_RATIO,
+            system_account_addr,
+        )
+        .with_deploy_whitelist(deploy_whitelist)
+        .with_chain_name(chain_name);
 
         self.state
             .put_protocol_data(protocol_version, &protocol_data)
             .map_err(Into::into)?;
 
+        // Execute any additional installers given in the chainspec
+        let mut extra_references: Vec<(String, URef)> = Vec::new();
+        for installer in ee_config.extra_installers() {
+            let installer_module = preprocessor.preprocess(installer.installer_bytes())?;
+            let args = installer.args().to_vec();
+            let mut named_keys = BTreeMap::new();
+            let authorization_keys = BTreeSet::new();
+            let install_deploy_hash = genesis_config_hash.into();
+            let address_generator = Rc::clone(&address_generator);
+            let tracking_copy = Rc::clone(&tracking_copy);
+            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+            let reference: URef = executor.exec_system(
+                installer_module,
+                args,
+                &mut named_keys,
+                initial_base_key,
+                &virtual_system_account,
+                authorization_keys,
+                blocktime,
+                block_height,
+                install_deploy_hash,
+                gas_limit,
+                address_generator,
+                protocol_version,
+                correlation_id,
+                tracking_copy,
+                phase,
+                protocol_data,
+                system_contract_cache,
+            )?;
+            extra_references.push((installer.name().to_string(), reference));
+        }
+
         //
         // NOTE: The following stanzas deviate from the implementation strategy described in the
         // original specification.
@@ -342,6 +688,10 @@ where
             let p_attenuated = URef::new(proof_of_stake_reference.addr(), AccessRights::READ);
             ret.insert(MINT_NAME.to_string(), Key::URef(m_attenuated));
             ret.insert(POS_NAME.to_string(), Key::URef(p_attenuated));
+            for (name, reference) in extra_references.iter() {
+                let attenuated = URef::new(reference.addr(), AccessRights::READ);
+                ret.insert(name.clone(), Key::URef(attenuated));
+            }
             ret
         };
 
@@ -350,6 +700,9 @@ where
             let mut ret = BTreeMap::new();
             ret.insert(MINT_NAME.to_string(), Key::URef(mint_reference));
             ret.insert(POS_NAME.to_string(), Key::URef(proof_of_stake_reference));
+            for (name, reference) in extra_references.into_iter() {
+                ret.insert(name, Key::URef(reference));
+            }
             ret
         };
 
@@ -362,10 +715,21 @@ where
                     .accounts()
                     .to_vec()
                     .into_iter()
-                    .map(|account| (account, account_named_keys.clone()))
+                    .map(|account| {
+                        let mut named_keys = account_named_keys.clone();
+                        if ee_config.whitelist_admin() == Some(account.public_key()) {
+                            if let Some(deploy_whitelist) = deploy_whitelist {
+                                named_keys.insert(
+                                    DEPLOY_WHITELIST_NAME.to_string(),
+                                    Key::URef(deploy_whitelist),
+                                );
+                            }
+                        }
+                        (account, named_keys)
+                    })
                     .collect();
                 let system_account =
-                    GenesisAccount::new(SYSTEM_ACCOUNT_ADDR, Motes::zero(), Motes::zero());
+                    GenesisAccount::new(system_account_addr, Motes::zero(), Motes::zero());
                 ret.push((system_account, system_account_named_keys));
                 ret
             };
@@ -396,8 +760,8 @@ where
                 let base_key = Key::URef(mint_reference);
                 let authorization_keys: BTreeSet<PublicKey> = BTreeSet::new();
                 let account_public_key = account.public_key();
-                // NOTE: As Ed25519 keys are currently supported by chainspec, PublicKey::value
-                // returns raw bytes of it
+                // NOTE: PublicKey::value is the 32-byte account address, i.e. the raw bytes of
+                // an Ed25519 key or the hash of a secp256k1 key
                 let purse_creation_deploy_hash = account_public_key.value();
                 let address_generator = {
                     let generator = AddressGeneratorBuilder::new()
@@ -421,6 +785,7 @@ where
                         &virtual_system_account,
                         authorization_keys,
                         blocktime,
+                        block_height,
                         purse_creation_deploy_hash,
                         gas_limit,
                         address_generator,
@@ -432,10 +797,33 @@ where
                         system_contract_cache,
                     )?;
 
-                    runtime
+                    let mint_result = runtime
                         .call_contract(mint_reference.into(), args)?
                         .into_t::<Result<URef, mint::Error>>()
-                        .expect("should convert")
+                        .expect("should convert");
+
+                    match (mint_result, account.vesting_schedule()) {
+                        (Ok(main_purse), Some(vesting_schedule)) => {
+                            // ...lock the tokens covered by its vesting schedule...
+                            let args = {
+                                let args = (
+                                    MINT_SET_VESTING_SCHEDULE_METHOD_NAME,
+                                    main_purse,
+                                    vesting_schedule,
+                                );
+                                ArgsParser::parse(args)
+                                    .expect("args should convert to `Vec<CLValue>`")
+                                    .into_bytes()
+                                    .expect("args should serialize")
+                            };
+                            runtime
+                                .call_contract(mint_reference.into(), args)?
+                                .into_t::<Result<(), mint::Error>>()
+                                .expect("should convert")
+                                .map(|_| main_purse)
+                        }
+                        (mint_result, _) => mint_result,
+                    }
                 };
 
                 // ...and write that account to global state...
@@ -455,14 +843,13 @@ where
         // Spec #15: Commit the transforms.
         let effects = tracking_copy.borrow().effect();
 
-        let commit_result = self
-            .state
-            .commit(
-                correlation_id,
-                initial_root_hash,
-                effects.transforms.to_owned(),
-            )
-            .map_err(Into::into)?;
+        let commit_result = accounts_index::commit(
+            &*self.state,
+            correlation_id,
+            Some(mint_reference),
+            initial_root_hash,
+            effects.transforms.to_owned(),
+        )?;
 
         // Return the result
         let genesis_result = GenesisResult::from_commit_result(commit_result, effects);
@@ -515,13 +902,36 @@ where
             None => *current_protocol_data.wasm_costs(),
         };
 
+        let new_max_call_depth = upgrade_config
+            .max_call_depth()
+            .unwrap_or_else(|| current_protocol_data.max_call_depth());
+
+        let new_wasm_features = upgrade_config
+            .wasm_features()
+            .unwrap_or_else(|| current_protocol_data.wasm_features());
+
+        let new_refund_ratio = match upgrade_config.refund_ratio() {
+            Some(refund_ratio) if refund_ratio > MAX_REFUND_RATIO => {
+                return Err(Error::InvalidUpgradeConfig)
+            }
+            Some(refund_ratio) => refund_ratio,
+            None => current_protocol_data.refund_ratio(),
+        };
+
         // 3.1.2.2 persist wasm CostTable
         let new_protocol_data = ProtocolData::new(
             new_wasm_costs,
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
             current_protocol_data.standard_payment(),
-        );
+            current_protocol_data.key_recovery(),
+            new_max_call_depth,
+            new_wasm_features,
+            new_refund_ratio,
+            current_protocol_data.system_account(),
+        )
+        .with_deploy_whitelist(current_protocol_data.deploy_whitelist())
+        .with_chain_name(current_protocol_data.chain_name());
 
         self.state
             .put_protocol_data(new_protocol_version, &new_protocol_data)
@@ -541,7 +951,8 @@ where
 
                 // preprocess installer module
                 let upgrade_installer_module = {
-                    let preprocessor = Preprocessor::new(new_wasm_costs);
+                    let preprocessor = Preprocessor::new(new_wasm_costs, new_wasm_features)
+                        .with_import_whitelist(resolvers::is_host_function);
                     preprocessor.preprocess(bytes)?
                 };
 
@@ -554,7 +965,7 @@ where
 
                 // execute as system account
                 let system_account = {
-                    let key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+                    let key = Key::Account(current_protocol_data.system_account());
                     match tracking_copy.borrow_mut().read(correlation_id, &key) {
                         Ok(Some(StoredValue::Account(account))) => account,
                         Ok(_) => panic!("system account must exist"),
@@ -564,14 +975,15 @@ where
 
                 let mut keys = BTreeMap::new();
 
-                let initial_base_key = Key::Account(SYSTEM_ACCOUNT_ADDR);
+                let initial_base_key = Key::Account(current_protocol_data.system_account());
                 let authorization_keys = {
                     let mut ret = BTreeSet::new();
-                    ret.insert(SYSTEM_ACCOUNT_ADDR);
+                    ret.insert(current_protocol_data.system_account());
                     ret
                 };
 
                 let blocktime = BlockTime::default();
+                let block_height = 0;
 
                 let deploy_hash = {
                     // seeds address generator w/ protocol version
@@ -603,6 +1015,7 @@ where
                     &system_account,
                     authorization_keys,
                     blocktime,
+                    block_height,
                     deploy_hash,
                     gas_limit,
                     address_generator,
@@ -616,122 +1029,1211 @@ where
             }
         }
 
+        // States committed before validators were recorded per era have none for the era in
+        // progress, so the stakes bonded now are recorded as those of the first era
+        let era_id_recorded = tracking_copy
+            .borrow_mut()
+            .read(correlation_id, &era_validators::current_era_id_key())
+            .map_err(|error| Error::Exec(error.into()))?
+            .is_some();
+        if !era_id_recorded {
+            Self::record_era_validators(correlation_id, new_protocol_data, &tracking_copy, 0)?;
+        }
+
         let effects = tracking_copy.borrow().effect();
 
         // commit
-        let commit_result = self
-            .state
-            .commit(
-                correlation_id,
-                pre_state_hash,
-                effects.transforms.to_owned(),
-            )
-            .map_err(Into::into)?;
+        let commit_result = accounts_index::commit(
+            &*self.state,
+            correlation_id,
+            Some(new_protocol_data.mint()),
+            pre_state_hash,
+            effects.transforms.to_owned(),
+        )?;
 
         // return result and effects
         Ok(UpgradeResult::from_commit_result(commit_result, effects))
     }
 
-    pub fn tracking_copy(
+    /// Slashes the bond of the validator who equivocated according to `evidence` by calling the
+    /// Proof of Stake contract's `slash` method as the system account, and commits the result on
+    /// top of `pre_state_hash`.
+    ///
+    /// The slashed motes are moved to the rewards purse, so they are paid out to the remaining
+    /// validators rather than burned.
+    pub fn commit_slashing(
         &self,
-        hash: Blake2bHash,
-    ) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
-        match self.state.checkout(hash).map_err(Into::into)? {
-            Some(tc) => Ok(Some(TrackingCopy::new(tc))),
-            None => Ok(None),
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        evidence: EquivocationEvidence,
+    ) -> Result<SlashingResult, Error> {
+        if let Err(error) = evidence.validate() {
+            return Ok(SlashingResult::InvalidEvidence(error));
         }
-    }
 
-    pub fn run_query(
-        &self,
-        correlation_id: CorrelationId,
-        query_request: QueryRequest,
-    ) -> Result<QueryResult, Error> {
-        let tracking_copy = match self.tracking_copy(query_request.state_hash())? {
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
             Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
-            None => return Ok(QueryResult::RootNotFound),
+            None => return Ok(SlashingResult::RootNotFound),
         };
 
-        let tracking_copy = tracking_copy.borrow();
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => return Err(Error::InvalidProtocolVersion(protocol_version)),
+            Err(error) => return Err(Error::Exec(error.into())),
+        };
 
-        Ok(tracking_copy
-            .query(correlation_id, query_request.key(), query_request.path())
-            .map_err(|err| Error::Exec(err.into()))?
-            .into())
+        let args = ArgsParser::parse(("slash", evidence.validator()))
+            .expect("args should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("args should serialize");
+
+        // seeds address generator w/ the evidence, so that slashing is deterministic
+        let deploy_hash = evidence.hash().value();
+
+        let result = self.exec_proof_of_stake_as_system(
+            correlation_id,
+            protocol_version,
+            protocol_data,
+            Rc::clone(&tracking_copy),
+            args,
+            deploy_hash,
+            BlockTime::default(),
+        )?;
+
+        if let Some(error) = result.take_error() {
+            return Ok(SlashingResult::Failure(error));
+        }
+
+        let effects = tracking_copy.borrow().effect();
+
+        let commit_result = accounts_index::commit(
+            &*self.state,
+            correlation_id,
+            Some(protocol_data.mint()),
+            pre_state_hash,
+            effects.transforms.to_owned(),
+        )?;
+
+        Ok(SlashingResult::from_commit_result(commit_result, effects))
     }
 
-    pub fn run_execute(
+    /// Pays out the Proof of Stake rewards purse accumulated during an era to the bonded
+    /// validators pro rata to their stakes, by calling the contract's `distribute_rewards` method
+    /// as the system account, and commits the result on top of `pre_state_hash`.
+    ///
+    /// The validators listed as equivocators in `era_report` get no share.
+    pub fn distribute_rewards(
         &self,
         correlation_id: CorrelationId,
-        mut exec_request: ExecuteRequest,
-    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
-        // TODO: do not unwrap
-        let wasm_costs = self
-            .wasm_costs(exec_request.protocol_version)
-            .unwrap()
-            .unwrap();
-        let executor = Executor::new(self.config);
-        let preprocessor = Preprocessor::new(wasm_costs);
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        era_report: EraReport,
+    ) -> Result<RewardsResult, Error> {
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(RewardsResult::RootNotFound),
+        };
 
-        let mut results = Vec::new();
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => return Err(Error::InvalidProtocolVersion(protocol_version)),
+            Err(error) => return Err(Error::Exec(error.into())),
+        };
 
-        for deploy_item in exec_request.take_deploys() {
-            let result = match deploy_item {
-                Ok(deploy_item) => self.deploy(
-                    correlation_id,
-                    &executor,
-                    &preprocessor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                ),
-                Err(exec_result) => Ok(exec_result), /* this will get pushed into the results vec
-                                                      * below */
-            };
-            match result {
-                Ok(result) => results.push(result),
-                Err(error) => {
-                    return Err(error);
-                }
-            };
+        let equivocators = era_report.equivocators;
+
+        // seeds address generator w/ the prestate and the report, as paying out to an account
+        // which doesn't exist yet creates it
+        let deploy_hash = {
+            let mut bytes = pre_state_hash.to_vec();
+            bytes.append(&mut equivocators.to_bytes()?);
+            Blake2bHash::new(&bytes).value()
+        };
+
+        let args = ArgsParser::parse(("distribute_rewards", equivocators))
+            .expect("args should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("args should serialize");
+
+        let result = self.exec_proof_of_stake_as_system(
+            correlation_id,
+            protocol_version,
+            protocol_data,
+            Rc::clone(&tracking_copy),
+            args,
+            deploy_hash,
+            BlockTime::default(),
+        )?;
+
+        if let Some(error) = result.take_error() {
+            return Ok(RewardsResult::Failure(error));
         }
 
-        Ok(results)
+        let effects = tracking_copy.borrow().effect();
+
+        let commit_result = accounts_index::commit(
+            &*self.state,
+            correlation_id,
+            Some(protocol_data.mint()),
+            pre_state_hash,
+            effects.transforms.to_owned(),
+        )?;
+
+        Ok(RewardsResult::from_commit_result(commit_result, effects))
     }
 
-    pub fn get_module(
+    /// Runs the system bookkeeping due once per block on top of `pre_state_hash`, outside of any
+    /// deploy, and commits the result.
+    ///
+    /// The Proof of Stake contract is called as the system account to pay out the unbonding
+    /// requests whose delay has passed by the block's time and, if the block ends an era, to pay
+    /// out the era's rewards as [`distribute_rewards`](EngineState::distribute_rewards) does, make
+    /// the [scheduled calls](scheduled_call) which have become due and then start the next era,
+    /// recording its [validators](era_validators).
+    pub fn step(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
-        deploy_item: &ExecutableDeployItem,
-        account: &Account,
         correlation_id: CorrelationId,
-        preprocessor: &Preprocessor,
-        protocol_version: &ProtocolVersion,
-    ) -> Result<Module, error::Error> {
-        let stored_contract_key = match deploy_item {
-            ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
-                let module = preprocessor.preprocess(&module_bytes)?;
-                return Ok(module);
-            }
-            ExecutableDeployItem::StoredContractByHash { hash, .. } => {
-                let hash_len = hash.len();
-                if hash_len != KEY_HASH_LENGTH {
-                    return Err(error::Error::InvalidHashLength {
-                        expected: KEY_HASH_LENGTH,
-                        actual: hash_len,
-                    });
-                }
-                let mut arr = [0u8; KEY_HASH_LENGTH];
-                arr.copy_from_slice(&hash);
-                Key::Hash(arr)
-            }
-            ExecutableDeployItem::StoredContractByName { name, .. } => {
-                let stored_contract_key = account.named_keys().get(name).ok_or_else(|| {
-                    error::Error::Exec(execution::Error::URefNotFound(name.to_string()))
-                })?;
-                if let Key::URef(uref) = stored_contract_key {
-                    if !uref.is_readable() {
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        step_request: StepRequest,
+    ) -> Result<StepResult, Error> {
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(StepResult::RootNotFound),
+        };
+
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => return Err(Error::InvalidProtocolVersion(protocol_version)),
+            Err(error) => return Err(Error::Exec(error.into())),
+        };
+
+        let StepRequest { blocktime, era_end } = step_request;
+
+        // seeds address generator w/ the prestate and the block time, as paying out to an account
+        // which doesn't exist yet creates it
+        let deploy_hash = {
+            let mut bytes = pre_state_hash.to_vec();
+            bytes.append(&mut blocktime.to_bytes()?);
+            Blake2bHash::new(&bytes).value()
+        };
+
+        let args = ArgsParser::parse(("step",))
+            .expect("args should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("args should serialize");
+
+        let result = self.exec_proof_of_stake_as_system(
+            correlation_id,
+            protocol_version,
+            protocol_data,
+            Rc::clone(&tracking_copy),
+            args,
+            deploy_hash,
+            blocktime,
+        )?;
+
+        if let Some(error) = result.take_error() {
+            return Ok(StepResult::Failure(error));
+        }
+
+        if let Some(era_report) = era_end {
+            let equivocators = era_report.equivocators;
+
+            // the rewards are paid out with a different seed, so that the addresses they generate
+            // don't clash with those of the unbonding payouts
+            let deploy_hash = {
+                let mut bytes = deploy_hash.to_vec();
+                bytes.append(&mut equivocators.to_bytes()?);
+                Blake2bHash::new(&bytes).value()
+            };
+
+            let args = ArgsParser::parse(("distribute_rewards", equivocators))
+                .expect("args should convert to `Vec<CLValue>`")
+                .into_bytes()
+                .expect("args should serialize");
+
+            let result = self.exec_proof_of_stake_as_system(
+                correlation_id,
+                protocol_version,
+                protocol_data,
+                Rc::clone(&tracking_copy),
+                args,
+                deploy_hash,
+                blocktime,
+            )?;
+
+            if let Some(error) = result.take_error() {
+                return Ok(StepResult::Failure(error));
+            }
+
+            self.run_scheduled_calls(
+                correlation_id,
+                protocol_version,
+                protocol_data,
+                Rc::clone(&tracking_copy),
+                deploy_hash,
+                blocktime,
+            )?;
+
+            let era_id = {
+                let stored_value = tracking_copy
+                    .borrow_mut()
+                    .read(correlation_id, &era_validators::current_era_id_key())
+                    .map_err(|error| Error::Exec(error.into()))?;
+                era_validators::era_id_from(stored_value)?
+            };
+            Self::record_era_validators(correlation_id, protocol_data, &tracking_copy, era_id + 1)?;
+        }
+
+        let effects = tracking_copy.borrow().effect();
+
+        let commit_result = accounts_index::commit(
+            &*self.state,
+            correlation_id,
+            Some(protocol_data.mint()),
+            pre_state_hash,
+            effects.transforms.to_owned(),
+        )?;
+
+        Ok(StepResult::from_commit_result(commit_result, effects))
+    }
+
+    /// Starts era `era_id`, recording the stakes bonded in PoS at its start as its
+    /// [validators](era_validators).
+    fn record_era_validators(
+        correlation_id: CorrelationId,
+        protocol_data: ProtocolData,
+        tracking_copy: &Rc<RefCell<TrackingCopy<S::Reader>>>,
+        era_id: EraId,
+    ) -> Result<(), Error> {
+        let mut tracking_copy = tracking_copy.borrow_mut();
+        let validators = match tracking_copy
+            .read(
+                correlation_id,
+                &Key::URef(protocol_data.proof_of_stake()).normalize(),
+            )
+            .map_err(|error| Error::Exec(error.into()))?
+        {
+            Some(StoredValue::Contract(contract)) => {
+                era_validators::era_validators_from_pos(contract.named_keys())
+            }
+            _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
+        };
+        tracking_copy.write(
+            era_validators::era_validators_key(era_id),
+            StoredValue::CLValue(CLValue::from_t(validators).map_err(execution::Error::from)?),
+        );
+        tracking_copy.write(
+            era_validators::current_era_id_key(),
+            StoredValue::CLValue(CLValue::from_t(era_id).map_err(execution::Error::from)?),
+        );
+        Ok(())
+    }
+
+    /// Removes the scheduled calls due by `blocktime` and makes each of them in turn, keeping the
+    /// changes of those which succeed.
+    fn run_scheduled_calls(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
+        deploy_hash: [u8; 32],
+        blocktime: BlockTime,
+    ) -> Result<(), Error> {
+        let scheduled_calls =
+            Self::read_scheduled_calls(correlation_id, &mut tracking_copy.borrow_mut())?;
+        let due = scheduled_call::take_due(scheduled_calls, blocktime);
+        if due.is_empty() {
+            return Ok(());
+        }
+        for (key, _call) in &due {
+            tracking_copy.borrow_mut().delete(*key);
+        }
+
+        let preprocessor =
+            Preprocessor::new(*protocol_data.wasm_costs(), protocol_data.wasm_features());
+        let module = wasm::do_nothing_module(&preprocessor)?;
+        let executor = Executor::new(self.config);
+
+        for (index, (_key, call)) in due.into_iter().enumerate() {
+            let account = tracking_copy
+                .borrow_mut()
+                .get_account(correlation_id, call.registrant())?;
+            let authorization_keys = {
+                let mut ret = BTreeSet::new();
+                ret.insert(call.registrant());
+                ret
+            };
+
+            // each call is seeded differently, so that the addresses they generate don't clash
+            let deploy_hash = {
+                let mut bytes = deploy_hash.to_vec();
+                bytes.append(&mut (index as u64).to_bytes()?);
+                Blake2bHash::new(&bytes).value()
+            };
+
+            tracking_copy.borrow_mut().begin_scope();
+            let result = executor.exec_scheduled_call(
+                module.clone(),
+                call.contract_key(),
+                call.args().to_vec(),
+                &account,
+                authorization_keys,
+                blocktime,
+                0,
+                deploy_hash,
+                Gas::new(U512::from(call.gas_budget())),
+                protocol_version,
+                correlation_id,
+                Rc::clone(&tracking_copy),
+                protocol_data,
+                SystemContractCache::clone(&self.system_contract_cache),
+            );
+            if result.is_success() {
+                tracking_copy.borrow_mut().commit_scope();
+            } else {
+                tracking_copy.borrow_mut().rollback_scope();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns every scheduled call stored in `tracking_copy`, with the key it is stored under.
+    fn read_scheduled_calls(
+        correlation_id: CorrelationId,
+        tracking_copy: &mut TrackingCopy<S::Reader>,
+    ) -> Result<Vec<(Key, ScheduledCall)>, Error> {
+        let keys = tracking_copy
+            .keys_with_prefix(correlation_id, &scheduled_call::scheduled_calls_prefix())
+            .map_err(|error| Error::Exec(error.into()))?;
+        let mut scheduled_calls = Vec::with_capacity(keys.len());
+        for key in keys {
+            let stored_value = tracking_copy
+                .read(correlation_id, &key)
+                .map_err(|error| Error::Exec(error.into()))?;
+            if let Some(call) = scheduled_call::scheduled_call_from(stored_value)? {
+                scheduled_calls.push((key, call));
+            }
+        }
+        Ok(scheduled_calls)
+    }
+
+    /// Executes the Proof of Stake contract with `args` as the system account on top of
+    /// `tracking_copy` at `blocktime`, without any gas limit.
+    #[allow(clippy::too_many_arguments)]
+    fn exec_proof_of_stake_as_system(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
+        args: Vec<u8>,
+        deploy_hash: [u8; 32],
+        blocktime: BlockTime,
+    ) -> Result<ExecutionResult, Error> {
+        let proof_of_stake_reference = protocol_data.proof_of_stake();
+        let proof_of_stake_contract = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, Key::URef(proof_of_stake_reference))?;
+        let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
+
+        let proof_of_stake_module = match self.system_contract_cache.get(&proof_of_stake_reference)
+        {
+            Some(module) => module,
+            None => {
+                let module = if self.config.use_system_contracts() {
+                    engine_wasm_prep::deserialize(proof_of_stake_contract.bytes())?
+                } else {
+                    let preprocessor = Preprocessor::new(
+                        *protocol_data.wasm_costs(),
+                        protocol_data.wasm_features(),
+                    );
+                    wasm::do_nothing_module(&preprocessor)?
+                };
+                self.system_contract_cache
+                    .insert(proof_of_stake_reference, module.clone());
+                module
+            }
+        };
+
+        // execute as system account
+        let system_account = {
+            let key = Key::Account(protocol_data.system_account());
+            match tracking_copy.borrow_mut().read(correlation_id, &key) {
+                Ok(Some(StoredValue::Account(account))) => account,
+                Ok(_) => panic!("system account must exist"),
+                Err(error) => return Err(Error::Exec(error.into())),
+            }
+        };
+
+        let authorization_keys = {
+            let mut ret = BTreeSet::new();
+            ret.insert(protocol_data.system_account());
+            ret
+        };
+
+        let base_key = Key::from(proof_of_stake_reference);
+        let gas_limit = Gas::new(U512::from(std::u64::MAX));
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+        let executor = Executor::new(self.config);
+
+        Ok(executor.exec_finalize(
+            proof_of_stake_module,
+            args,
+            &mut proof_of_stake_keys,
+            base_key,
+            &system_account,
+            authorization_keys,
+            blocktime,
+            0,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            Phase::System,
+            protocol_data,
+            system_contract_cache,
+        ))
+    }
+
+    pub fn tracking_copy(
+        &self,
+        hash: Blake2bHash,
+    ) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
+        match self.state.checkout(hash).map_err(Into::into)? {
+            Some(tc) => Ok(Some(TrackingCopy::new(tc))),
+            None => Ok(None),
+        }
+    }
+
+    /// Like `tracking_copy`, but the returned `TrackingCopy` shares reads of global state under
+    /// `hash` with the other deploys executed against it through the block cache.
+    fn cached_tracking_copy(
+        &self,
+        hash: Blake2bHash,
+    ) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
+        match self.state.checkout(hash).map_err(Into::into)? {
+            Some(tc) => Ok(Some(TrackingCopy::with_block_cache(
+                tc,
+                BlockCache::clone(&self.block_cache),
+                hash,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the hashes of any tries reachable from `trie_keys` which are missing from global
+    /// state or whose contents don't match their hash.
+    pub fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Error> {
+        self.state
+            .missing_trie_keys(correlation_id, trie_keys)
+            .map_err(|error| Error::Exec(error.into()))
+    }
+
+    /// Returns the total supply of motes recorded by the mint at `root_hash`.
+    ///
+    /// Unlike `check_total_supply`, this reads the mint's counter directly rather than summing
+    /// every purse.
+    pub fn get_total_supply(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        root_hash: Blake2bHash,
+    ) -> Result<U512, Error> {
+        let mint_contract_uref = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data.mint(),
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(Error::MissingRoot(root_hash)),
+        };
+        match tracking_copy.get_total_supply(correlation_id, mint_contract_uref) {
+            Ok(total_supply) => Ok(total_supply.value()),
+            Err(execution::Error::KeyNotFound(_)) => Err(Error::MissingTotalSupply),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Returns the id of the era in progress at `root_hash`.
+    pub fn get_current_era_id(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<EraId, Error> {
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(Error::MissingRoot(root_hash)),
+        };
+        let stored_value = tracking_copy
+            .read(correlation_id, &era_validators::current_era_id_key())
+            .map_err(|error| Error::Exec(error.into()))?;
+        Ok(era_validators::era_id_from(stored_value)?)
+    }
+
+    /// Returns the validators of era `era_id` and their stakes, as recorded at the start of the
+    /// era, or `None` if the era hadn't started by `root_hash`.
+    pub fn get_era_validators(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        era_id: EraId,
+    ) -> Result<Option<EraValidators>, Error> {
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(Error::MissingRoot(root_hash)),
+        };
+        let stored_value = tracking_copy
+            .read(correlation_id, &era_validators::era_validators_key(era_id))
+            .map_err(|error| Error::Exec(error.into()))?;
+        Ok(era_validators::era_validators_from(stored_value)?)
+    }
+
+    /// Returns the calls scheduled at `root_hash` which haven't been made yet, in the order they
+    /// would be made once due.
+    pub fn get_scheduled_calls(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<Vec<ScheduledCall>, Error> {
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(Error::MissingRoot(root_hash)),
+        };
+        let mut scheduled_calls = Self::read_scheduled_calls(correlation_id, &mut tracking_copy)?;
+        scheduled_call::sort_in_call_order(&mut scheduled_calls);
+        Ok(scheduled_calls
+            .into_iter()
+            .map(|(_key, call)| call)
+            .collect())
+    }
+
+    /// Returns the purses of `account` at `root_hash` with their balances: its main purse followed
+    /// by every purse it has created, in order of creation.
+    ///
+    /// The purses are returned with their access rights removed.
+    pub fn get_account_purses(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        root_hash: Blake2bHash,
+        account: PublicKey,
+    ) -> Result<Vec<(URef, U512)>, Error> {
+        let mint_contract_uref = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data.mint(),
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(Error::MissingRoot(root_hash)),
+        };
+
+        let main_purse = tracking_copy
+            .get_account(correlation_id, account)?
+            .main_purse()
+            .remove_access_rights();
+        let mut purses = vec![main_purse];
+        purses.extend(tracking_copy.get_registered_purses(correlation_id, account)?);
+
+        purses
+            .into_iter()
+            .map(|purse| -> Result<(URef, U512), Error> {
+                let balance_key = tracking_copy.get_purse_balance_key(
+                    correlation_id,
+                    mint_contract_uref,
+                    Key::URef(purse),
+                )?;
+                let balance = tracking_copy.get_purse_balance(correlation_id, balance_key)?;
+                Ok((purse, balance.value()))
+            })
+            .collect()
+    }
+
+    /// Returns the accounts and stored contracts at `root_hash` which hold a `URef` with the
+    /// address `uref_addr`, along with the access rights they hold it with.
+    ///
+    /// A context holds a `URef` if it's under one of the context's named keys or, for an account,
+    /// if it's the account's main purse.  Every account and stored contract is read, so this is
+    /// intended for auditing rather than for use while executing deploys.
+    pub fn get_uref_holders(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+        uref_addr: [u8; UREF_ADDR_LENGTH],
+    ) -> Result<BTreeMap<Key, BTreeSet<AccessRights>>, Error> {
+        let tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(Error::MissingRoot(root_hash)),
+        };
+        let reader = tracking_copy.reader();
+
+        // Contracts can be stored under both hashes and URefs.
+        let prefixes = [
+            utils::key_variant_prefix(Key::Account(PublicKey::ed25519_from([0; 32]))),
+            utils::key_variant_prefix(Key::Hash([0; KEY_HASH_LENGTH])),
+            utils::key_variant_prefix(Key::URef(URef::new(
+                [0; UREF_ADDR_LENGTH],
+                AccessRights::NONE,
+            ))),
+        ];
+        let mut holders = BTreeMap::new();
+        for prefix in prefixes.iter() {
+            let keys = reader
+                .keys_with_prefix(correlation_id, prefix)
+                .map_err(|error| Error::Exec(error.into()))?;
+            for key in keys {
+                let held_urefs: Vec<URef> = match reader
+                    .read(correlation_id, &key)
+                    .map_err(|error| Error::Exec(error.into()))?
+                {
+                    Some(StoredValue::Account(account)) => iter::once(account.main_purse())
+                        .chain(
+                            account
+                                .named_keys()
+                                .values()
+                                .filter_map(|key| key.into_uref()),
+                        )
+                        .collect(),
+                    Some(StoredValue::Contract(contract)) => contract
+                        .named_keys()
+                        .values()
+                        .filter_map(|key| key.into_uref())
+                        .collect(),
+                    _ => continue,
+                };
+                let access_rights: BTreeSet<AccessRights> = held_urefs
+                    .into_iter()
+                    .filter(|uref| uref.addr() == uref_addr)
+                    .map(|uref| uref.access_rights())
+                    .collect();
+                if !access_rights.is_empty() {
+                    holders.insert(key, access_rights);
+                }
+            }
+        }
+        Ok(holders)
+    }
+
+    /// Sums the balances of all purses known to the mint at `root_hash` and checks the result
+    /// against the total supply recorded by the mint.
+    ///
+    /// Returns the total supply if the two agree.
+    pub fn check_total_supply(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        root_hash: Blake2bHash,
+    ) -> Result<U512, Error> {
+        let mint_contract_uref = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data.mint(),
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(Error::MissingRoot(root_hash)),
+        };
+
+        let total_supply_key = Key::local(
+            mint_contract_uref.addr(),
+            &::mint::TOTAL_SUPPLY_KEY.to_bytes()?,
+        );
+        let mint_local_keys = tracking_copy
+            .reader()
+            .keys_with_prefix(
+                correlation_id,
+                &utils::local_key_prefix(mint_contract_uref.addr()),
+            )
+            .map_err(|error| Error::Exec(error.into()))?;
+
+        let mut recorded = None;
+        let mut actual = U512::zero();
+        for key in mint_local_keys {
+            let cl_value: CLValue = match tracking_copy
+                .read(correlation_id, &key)
+                .map_err(|error| Error::Exec(error.into()))?
+            {
+                Some(stored_value) => stored_value
+                    .try_into()
+                    .map_err(execution::Error::TypeMismatch)?,
+                None => return Err(execution::Error::KeyNotFound(key).into()),
+            };
+            if key == total_supply_key {
+                recorded = Some(cl_value.into_t().map_err(execution::Error::from)?);
+            } else {
+                // All other entries map a purse to the key of its balance.
+                let balance_key: Key = cl_value.into_t().map_err(execution::Error::from)?;
+                actual += tracking_copy
+                    .get_purse_balance(correlation_id, balance_key)?
+                    .value();
+            }
+        }
+
+        match recorded {
+            Some(recorded) if recorded == actual => Ok(recorded),
+            Some(recorded) => Err(Error::TotalSupplyMismatch { recorded, actual }),
+            None => Err(Error::MissingTotalSupply),
+        }
+    }
+
+    pub fn run_query(
+        &self,
+        correlation_id: CorrelationId,
+        query_request: QueryRequest,
+    ) -> Result<QueryResult, Error> {
+        let tracking_copy = match self.tracking_copy(query_request.state_hash())? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(QueryResult::RootNotFound),
+        };
+
+        let tracking_copy = tracking_copy.borrow();
+
+        Ok(tracking_copy
+            .query(correlation_id, query_request.key(), query_request.path())
+            .map_err(|err| Error::Exec(err.into()))?
+            .into())
+    }
+
+    /// Returns a proof of the balance of `account`'s main purse at `state_hash` which can be
+    /// checked against the state hash alone, or `None` if the account is not in the accounts
+    /// index.
+    pub fn get_account_balance_proof(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        account: PublicKey,
+    ) -> Result<Option<AccountBalanceProof>, Error> {
+        accounts_index::prove_balance(&*self.state, correlation_id, state_hash, account)
+    }
+
+    pub fn run_check_signers(
+        &self,
+        correlation_id: CorrelationId,
+        check_signers_request: CheckSignersRequest,
+    ) -> Result<CheckSignersResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(check_signers_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(CheckSignersResult::RootNotFound),
+        };
+
+        let account_addr = check_signers_request.account();
+        let account = match tracking_copy
+            .get(correlation_id, &Key::Account(account_addr))
+            .map_err(|err| Error::Exec(err.into()))?
+        {
+            Some(StoredValue::Account(account)) => account,
+            _ => return Ok(CheckSignersResult::AccountNotFound(account_addr)),
+        };
+
+        Ok(CheckSignersResult::Success(SignersCheck::new(
+            &account,
+            check_signers_request.signers(),
+        )))
+    }
+
+    /// Returns a read-only view of the Proof of Stake contract's bids, era validators and
+    /// unbonding queue, so that callers don't need to know how the contract stores them.
+    pub fn run_pos_view(
+        &self,
+        correlation_id: CorrelationId,
+        pos_view_request: PosViewRequest,
+    ) -> Result<PosViewResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let protocol_version = pos_view_request.protocol_version();
+        let protocol_data = match self.state.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let proof_of_stake = protocol_data.proof_of_stake();
+        let mut tracking_copy = match self.tracking_copy(pos_view_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(PosViewResult::RootNotFound),
+        };
+
+        let contract = match tracking_copy
+            .read(correlation_id, &Key::URef(proof_of_stake).normalize())
+            .map_err(|error| Error::Exec(error.into()))?
+        {
+            Some(StoredValue::Contract(contract)) => contract,
+            _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
+        };
+        let unbonding_queue = Self::read_pos_queue(
+            correlation_id,
+            &mut tracking_copy,
+            proof_of_stake,
+            ::proof_of_stake::UNBONDING_KEY,
+        )?
+        .map(|queue| {
+            queue
+                .0
+                .into_iter()
+                .map(|entry| PendingUnbond {
+                    public_key: entry.validator,
+                    amount: entry.amount,
+                    timestamp: entry.timestamp,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+        Ok(PosViewResult::Success(PosView::new(
+            contract.named_keys(),
+            unbonding_queue,
+        )))
+    }
+
+    pub fn run_execute(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
+        let mut results = Vec::new();
+        self.execute_deploys(correlation_id, exec_request, None, &mut |result| {
+            results.push(result)
+        })?;
+        Ok(results)
+    }
+
+    /// Executes the deploys of `exec_request` as `run_execute` does, but passes each result to
+    /// `on_result` as soon as it and the results of all the deploys before it in the request are
+    /// known.  A missing parent state is reported before any result is passed.
+    pub fn run_execute_streaming<F: FnMut(ExecutionResult)>(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+        mut on_result: F,
+    ) -> Result<(), RootNotFound> {
+        let parent_state_hash = exec_request.parent_state_hash;
+        if let Ok(None) = self.state.checkout(parent_state_hash) {
+            return Err(RootNotFound::new(parent_state_hash));
+        }
+        self.execute_deploys(correlation_id, exec_request, None, &mut on_result)
+    }
+
+    /// Executes the deploys of `exec_request` until `cancellation_token` is cancelled.  The wasm
+    /// being executed then stops at its next host function call, and that deploy and those not
+    /// yet started fail with [`execution::Error::Cancelled`] without being charged or cached.
+    pub fn run_execute_with_cancellation(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+        cancellation_token: CancellationToken,
+    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
+        let mut results = Vec::new();
+        self.execute_deploys(
+            correlation_id,
+            exec_request,
+            Some(cancellation_token),
+            &mut |result| results.push(result),
+        )?;
+        Ok(results)
+    }
+
+    /// Executes the deploys of `exec_request`, passing their results to `on_result` in the order of
+    /// the request.
+    fn execute_deploys(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+        cancellation_token: Option<CancellationToken>,
+        on_result: &mut dyn FnMut(ExecutionResult),
+    ) -> Result<(), RootNotFound> {
+        // Wasm costs and features are those of the protocol version the block is executed under,
+        // so blocks either side of an upgrade are each executed by their own rules
+        let protocol_data = match self.get_protocol_data(exec_request.protocol_version) {
+            Ok(Some(protocol_data)) => Ok(protocol_data),
+            Ok(None) => Err(Error::InvalidProtocolVersion(exec_request.protocol_version)),
+            Err(error) => Err(error),
+        };
+        let protocol_data = match protocol_data {
+            Ok(protocol_data) => protocol_data,
+            Err(error) => {
+                for result in Self::fail_all_deploys(exec_request, error) {
+                    on_result(result);
+                }
+                return Ok(());
+            }
+        };
+        let executor = match cancellation_token {
+            Some(cancellation_token) => {
+                Executor::new(self.config).with_cancellation_token(cancellation_token)
+            }
+            None => Executor::new(self.config),
+        };
+        let cancelled =
+            || ExecutionResult::precondition_failure(Error::Exec(execution::Error::Cancelled));
+        let preprocessor =
+            Preprocessor::new(*protocol_data.wasm_costs(), protocol_data.wasm_features())
+                .with_determinism_checks(self.config.enable_determinism_checks())
+                .with_import_whitelist(resolvers::is_host_function);
+
+        self.block_cache.checkout(exec_request.parent_state_hash);
+
+        let use_result_cache = self.config.enable_execution_result_cache();
+        let block_time = BlockTime::new(exec_request.block_time);
+        let mut result_cache_hits = 0;
+
+        // Deploys are executed after the deploys of the batch they depend on, while their results
+        // are returned in the order of the request.
+        let deploys = exec_request.take_deploys();
+        let order = {
+            let dependencies: Vec<_> = deploys
+                .iter()
+                .map(|deploy_item| {
+                    deploy_item.as_ref().ok().map(|deploy_item| {
+                        let dependencies = deploy_item
+                            .header
+                            .as_ref()
+                            .map(|header| header.dependencies.as_slice())
+                            .unwrap_or_default();
+                        (deploy_item.address, deploy_item.deploy_hash, dependencies)
+                    })
+                })
+                .collect();
+            deploy_header::execution_order(&dependencies)
+        };
+        let mut deploys: Vec<_> = deploys.into_iter().map(Some).collect();
+        let mut results: Vec<Option<ExecutionResult>> = deploys.iter().map(|_| None).collect();
+
+        for index in order.cyclic {
+            if let Some(Ok(deploy_item)) = deploys[index].take() {
+                let error = deploy_header::cyclic_dependency(&deploy_item.deploy_hash);
+                results[index] = Some(ExecutionResult::precondition_failure(error.into()));
+            }
+        }
+
+        let mut executed_in_batch = HashSet::new();
+        // The effects of each deploy of the batch whose execution was recorded, in the order they
+        // were executed, together with its executed deploy key and those of its dependencies.
+        let mut batch_effects: Vec<(Key, Vec<Key>, AdditiveMap<Key, Transform>)> = Vec::new();
+
+        let mut emit = |result: ExecutionResult| {
+            let cache_stats = result.cache_stats();
+            self.metrics.increment_counter(
+                correlation_id,
+                metrics::DEPLOY_CACHE_HITS,
+                cache_stats.hits,
+            );
+            self.metrics.increment_counter(
+                correlation_id,
+                metrics::DEPLOY_CACHE_MISSES,
+                cache_stats.misses,
+            );
+            on_result(result)
+        };
+        // The index of the first result of the request not yet emitted.
+        let mut next_index = 0;
+
+        for index in order.sequence {
+            while let Some(result) = results.get_mut(next_index).and_then(Option::take) {
+                emit(result);
+                next_index += 1;
+            }
+            let deploy_item = match deploys[index].take() {
+                Some(deploy_item) => deploy_item,
+                None => continue,
+            };
+            if executor.is_cancelled() {
+                results[index] = Some(match deploy_item {
+                    Ok(_) => cancelled(),
+                    Err(exec_result) => exec_result,
+                });
+                continue;
+            }
+            let deploy_hash = deploy_item
+                .as_ref()
+                .ok()
+                .map(|deploy_item| deploy_item.deploy_hash);
+            let executed_deploy_key = deploy_item.as_ref().ok().map(|deploy_item| {
+                deploy_header::executed_deploy_key(deploy_item.address, &deploy_item.deploy_hash)
+            });
+            let dependency_keys: Vec<Key> = deploy_item
+                .as_ref()
+                .ok()
+                .and_then(|deploy_item| {
+                    let header = deploy_item.header.as_ref()?;
+                    let dependency_keys = header.dependencies.iter().map(|dependency| {
+                        deploy_header::executed_deploy_key(deploy_item.address, dependency)
+                    });
+                    Some(dependency_keys.collect())
+                })
+                .unwrap_or_default();
+            // A deploy sees the effects of the deploys of the batch it depends on, directly or
+            // through other deploys of the batch, as though they had already been committed.
+            let dependency_effects = Self::dependency_effects(&batch_effects, &dependency_keys);
+            // The result of a deploy which depends on other deploys, or which repeats one executed
+            // earlier in the batch, depends on the rest of the batch as well as on the cache key,
+            // so it is never taken from or put into the cache.
+            let use_result_cache = use_result_cache
+                && deploy_item.as_ref().ok().map_or(false, |deploy_item| {
+                    deploy_item
+                        .header
+                        .as_ref()
+                        .map_or(true, |header| header.dependencies.is_empty())
+                })
+                && executed_deploy_key.map_or(true, |key| !executed_in_batch.contains(&key));
+            let result = match deploy_item {
+                Ok(deploy_item) if use_result_cache => {
+                    let deploy_hash = deploy_item.deploy_hash;
+                    let maybe_cached = self.execution_result_cache.get(
+                        exec_request.parent_state_hash,
+                        deploy_hash,
+                        exec_request.protocol_version,
+                        block_time,
+                        exec_request.block_height,
+                    );
+                    match maybe_cached {
+                        Some(result) => {
+                            result_cache_hits += 1;
+                            Ok(result)
+                        }
+                        None => self
+                            .deploy(
+                                correlation_id,
+                                &executor,
+                                &preprocessor,
+                                exec_request.protocol_version,
+                                exec_request.parent_state_hash,
+                                block_time,
+                                exec_request.block_height,
+                                &executed_in_batch,
+                                &dependency_effects,
+                                deploy_item,
+                            )
+                            .map(|result| {
+                                if executor.is_cancelled() {
+                                    return result;
+                                }
+                                self.execution_result_cache.insert(
+                                    exec_request.parent_state_hash,
+                                    deploy_hash,
+                                    exec_request.protocol_version,
+                                    block_time,
+                                    exec_request.block_height,
+                                    result.clone(),
+                                );
+                                result
+                            }),
+                    }
+                }
+                Ok(deploy_item) => self.deploy(
+                    correlation_id,
+                    &executor,
+                    &preprocessor,
+                    exec_request.protocol_version,
+                    exec_request.parent_state_hash,
+                    block_time,
+                    exec_request.block_height,
+                    &executed_in_batch,
+                    &dependency_effects,
+                    deploy_item,
+                ),
+                Err(exec_result) => Ok(exec_result), /* this will get pushed into the results vec
+                                                      * below */
+            };
+            match result {
+                // The result of a deploy cut short is incomplete, so it's dropped
+                Ok(_) if executor.is_cancelled() => results[index] = Some(cancelled()),
+                Ok(result) => {
+                    self.metrics.record_histogram(
+                        correlation_id,
+                        metrics::GAS_USED,
+                        result.cost().value().low_u64() as f64,
+                    );
+                    // Only deploys which got as far as recording their execution satisfy the
+                    // dependencies of later deploys in the batch.
+                    if let Some(executed_deploy_key) = executed_deploy_key {
+                        if result
+                            .effect()
+                            .transforms
+                            .get(&executed_deploy_key)
+                            .is_some()
+                        {
+                            executed_in_batch.insert(executed_deploy_key);
+                            batch_effects.push((
+                                executed_deploy_key,
+                                dependency_keys,
+                                result.effect().transforms.clone(),
+                            ));
+                        }
+                    }
+                    if let Some(deploy_hash) = deploy_hash {
+                        self.record_deploy_effects(deploy_hash, result.effect());
+                    }
+                    results[index] = Some(result)
+                }
+                Err(error) => {
+                    return Err(error);
+                }
+            };
+        }
+
+        results.into_iter().flatten().for_each(emit);
+
+        self.metrics.increment_counter(
+            correlation_id,
+            metrics::EXECUTION_RESULT_CACHE_HITS,
+            result_cache_hits,
+        );
+
+        let (hits, misses) = self.block_cache.take_hit_counts();
+        self.metrics
+            .increment_counter(correlation_id, metrics::BLOCK_CACHE_HITS, hits);
+        self.metrics
+            .increment_counter(correlation_id, metrics::TRIE_READS, misses);
+
+        Ok(())
+    }
+
+    pub fn get_module(
+        &self,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        deploy_item: &ExecutableDeployItem,
+        account: &Account,
+        correlation_id: CorrelationId,
+        preprocessor: &Preprocessor,
+        protocol_version: &ProtocolVersion,
+    ) -> Result<Module, error::Error> {
+        let stored_contract_key = match deploy_item {
+            ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
+                let start = Instant::now();
+                let module = preprocessor.preprocess(&module_bytes)?;
+                self.metrics.record_duration(
+                    correlation_id,
+                    metrics::PREPROCESS_DURATION,
+                    start.elapsed(),
+                );
+                return Ok(module);
+            }
+            ExecutableDeployItem::StoredContractByHash { hash, .. } => {
+                let hash_len = hash.len();
+                if hash_len != KEY_HASH_LENGTH {
+                    return Err(error::Error::InvalidHashLength {
+                        expected: KEY_HASH_LENGTH,
+                        actual: hash_len,
+                    });
+                }
+                let mut arr = [0u8; KEY_HASH_LENGTH];
+                arr.copy_from_slice(&hash);
+                Key::Hash(arr)
+            }
+            ExecutableDeployItem::StoredContractByName { name, .. } => {
+                let stored_contract_key = account.named_keys().get(name).ok_or_else(|| {
+                    error::Error::Exec(execution::Error::URefNotFound(name.to_string()))
+                })?;
+                if let Key::URef(uref) = stored_contract_key {
+                    if !uref.is_readable() {
                         return Err(error::Error::Exec(execution::Error::ForgedReference(*uref)));
                     }
                 }
@@ -777,6 +2279,12 @@ where
                     }
                 }
             }
+            // There is no module to run; the session is applied by the host and native payment
+            // is not supported.
+            ExecutableDeployItem::NativeKeyManagement { .. }
+            | ExecutableDeployItem::NativeTransfer { .. } => {
+                return Err(error::Error::NativePayment);
+            }
         };
         self.get_module_from_key(
             tracking_copy,
@@ -808,6 +2316,14 @@ where
             return Err(error::Error::Exec(exec_error));
         }
 
+        // A contract with named entry points has no `call` export for a deploy to run.
+        if contract.has_entry_points() {
+            let exec_error = execution::Error::FunctionNotFound(String::from(
+                "contract must be called through one of its entry points",
+            ));
+            return Err(error::Error::Exec(exec_error));
+        }
+
         let (ret, _, _) = contract.destructure();
         let module = engine_wasm_prep::deserialize(&ret)?;
         Ok(module)
@@ -822,24 +2338,150 @@ where
         protocol_version: ProtocolVersion,
         prestate_hash: Blake2bHash,
         blocktime: BlockTime,
+        block_height: u64,
+        executed_in_batch: &HashSet<Key>,
+        dependency_effects: &AdditiveMap<Key, Transform>,
+        deploy_item: DeployItem,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        let mut stages = DeployStages::new(correlation_id, &*self.stage_logger);
+        stages.enter(Stage::Preconditions);
+        // Create tracking copy (which functions as a deploy context)
+        // validation_spec_2: prestate_hash check
+        let result = match self.cached_tracking_copy(prestate_hash) {
+            Err(error) => Ok(ExecutionResult::precondition_failure(error)),
+            Ok(None) => Err(RootNotFound::new(prestate_hash)),
+            Ok(Some(mut tracking_copy)) => {
+                match tracking_copy.apply_transforms(correlation_id, dependency_effects) {
+                    Ok(Ok(())) => (),
+                    Ok(Err(error)) => {
+                        let error = Error::Exec(error.into());
+                        return Ok(ExecutionResult::precondition_failure(error));
+                    }
+                    Err(error) => {
+                        let error = Error::Exec(error.into());
+                        return Ok(ExecutionResult::precondition_failure(error));
+                    }
+                }
+                let tracking_copy = Rc::new(RefCell::new(tracking_copy));
+                self.deploy_in_stages(
+                    correlation_id,
+                    executor,
+                    preprocessor,
+                    protocol_version,
+                    Rc::clone(&tracking_copy),
+                    blocktime,
+                    block_height,
+                    executed_in_batch,
+                    deploy_item,
+                    &mut stages,
+                )
+                .map(|result| result.with_cache_stats(tracking_copy.borrow().cache_stats()))
+            }
+        };
+        stages.finish(&result);
+        result
+    }
+
+    /// Combines, in the order they were executed, the effects of the deploys in `batch_effects`
+    /// which are named by `dependency_keys` or which those deploys depend on in turn.
+    fn dependency_effects(
+        batch_effects: &[(Key, Vec<Key>, AdditiveMap<Key, Transform>)],
+        dependency_keys: &[Key],
+    ) -> AdditiveMap<Key, Transform> {
+        let mut dependencies: HashSet<Key> = dependency_keys.iter().copied().collect();
+        for (executed_deploy_key, dependency_keys, _) in batch_effects.iter().rev() {
+            if dependencies.contains(executed_deploy_key) {
+                dependencies.extend(dependency_keys.iter().copied());
+            }
+        }
+        let mut effects = AdditiveMap::new();
+        for (executed_deploy_key, _, transforms) in batch_effects {
+            if dependencies.contains(executed_deploy_key) {
+                for (key, transform) in transforms.iter() {
+                    effects.insert_add(*key, transform.to_owned());
+                }
+            }
+        }
+        effects
+    }
+
+    /// Fails every deploy of `exec_request` which hasn't already failed as a precondition with
+    /// `error`.
+    fn fail_all_deploys(mut exec_request: ExecuteRequest, error: Error) -> Vec<ExecutionResult> {
+        exec_request
+            .take_deploys()
+            .into_iter()
+            .map(|deploy_item| match deploy_item {
+                Ok(_) => ExecutionResult::precondition_failure(error.clone()),
+                Err(exec_result) => exec_result,
+            })
+            .collect()
+    }
+
+    /// Checks the session and payment code of a deploy against the configured limits on the size
+    /// of their args and modules.
+    fn check_deploy_item_sizes(&self, deploy_item: &DeployItem) -> Result<(), Error> {
+        for item in &[&deploy_item.session, &deploy_item.payment] {
+            if let Some(max) = self.config.max_args_bytes() {
+                let size = item.args_size();
+                if size > max {
+                    return Err(Error::ArgsTooLarge { size, max });
+                }
+            }
+            if let Some(max) = self.config.max_module_bytes() {
+                let size = item.module_size();
+                if size > max {
+                    return Err(Error::ModuleTooLarge { size, max });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn deploy_in_stages(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        blocktime: BlockTime,
+        block_height: u64,
+        executed_in_batch: &HashSet<Key>,
         deploy_item: DeployItem,
+        stages: &mut DeployStages,
     ) -> Result<ExecutionResult, RootNotFound> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
+        // Reject oversized args and modules before anything reads or preprocesses them
+        // validation_spec_4: deploy validity
+        if let Err(error) = self.check_deploy_item_sizes(&deploy_item) {
+            return Ok(ExecutionResult::precondition_failure(error));
+        }
+
+        // A deploy created for another protocol version would be priced and preprocessed by rules
+        // its sender didn't sign up for
+        if let Some(declared) = deploy_item.protocol_version {
+            if declared != protocol_version {
+                let error = Error::ProtocolVersionMismatch {
+                    declared,
+                    expected: protocol_version,
+                };
+                return Ok(ExecutionResult::precondition_failure(error));
+            }
+        }
+
+        let body_size = deploy_item.body_size();
+        let header = deploy_item.header;
+        let sequence_number = deploy_item.sequence_number;
+        let payment_source = deploy_item.payment_source;
         let session = deploy_item.session;
         let payment = deploy_item.payment;
         let address = Key::Account(deploy_item.address);
         let authorization_keys = deploy_item.authorization_keys;
         let deploy_hash = deploy_item.deploy_hash;
 
-        // Create tracking copy (which functions as a deploy context)
-        // validation_spec_2: prestate_hash check
-        let tracking_copy = match self.tracking_copy(prestate_hash) {
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
-            Ok(None) => return Err(RootNotFound::new(prestate_hash)),
-            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
-        };
-
         // Get addr bytes from `address` (which is actually a Key)
         // validation_spec_3: account validity
         let account_addr = match address.into_account() {
@@ -865,37 +2507,122 @@ where
             }
         };
 
-        // Authorize using provided authorization keys
+        // Authorize using provided authorization keys and check their total weight against the
+        // deploy threshold, reporting which keys fell short
         // validation_spec_3: account validity
-        if !account.can_authorize(&authorization_keys) {
+        // validation_spec_4: deploy validity
+        if !account.can_authorize(&authorization_keys)
+            || !account.can_deploy_with(&authorization_keys)
+        {
             return Ok(ExecutionResult::precondition_failure(
-                crate::engine_state::error::Error::Authorization,
+                Error::AuthorizationFailure(AuthorizationFailure::new(
+                    &account,
+                    &authorization_keys,
+                )),
             ));
         }
 
-        // Check total key weight against deploy threshold
+        // Sequenced deploys must carry the account's next sequence number
         // validation_spec_4: deploy validity
-        if !account.can_deploy_with(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
-                // TODO?:this doesn't happen in execution any longer, should error variant be moved
-                execution::Error::DeploymentAuthorizationFailure.into(),
-            ));
+        if let Some(sequence_number) = sequence_number {
+            let expected = account.next_sequence_number();
+            if sequence_number != expected {
+                return Ok(ExecutionResult::precondition_failure(
+                    Error::InvalidSequenceNumber {
+                        expected,
+                        actual: sequence_number,
+                    },
+                ));
+            }
+        }
+
+        // Check the deploy header against the block being executed
+        // validation_spec_4: deploy validity
+        if let Some(ref header) = header {
+            if let Err(error) = header.validate(blocktime, body_size) {
+                return Ok(ExecutionResult::precondition_failure(error.into()));
+            }
         }
 
+        // Reject replays and deploys whose dependencies haven't been executed.  Executions are
+        // only recorded with replay protection enabled, so dependencies can't be checked without
+        // it.
+        // validation_spec_4: deploy validity
+        let executed_deploy_key = if self.config.enable_replay_protection() {
+            let executed_deploy_key =
+                deploy_header::executed_deploy_key(account_addr, &deploy_hash);
+            if executed_in_batch.contains(&executed_deploy_key) {
+                return Ok(ExecutionResult::precondition_failure(Error::ReplayedDeploy));
+            }
+            let dependencies = header
+                .as_ref()
+                .map(|header| header.dependencies.as_slice())
+                .unwrap_or_default();
+            for dependency in dependencies {
+                let dependency_key = deploy_header::executed_deploy_key(account_addr, dependency);
+                if executed_in_batch.contains(&dependency_key) {
+                    continue;
+                }
+                match tracking_copy
+                    .borrow_mut()
+                    .read(correlation_id, &dependency_key)
+                {
+                    Ok(Some(_)) => (),
+                    Ok(None) => {
+                        return Ok(ExecutionResult::precondition_failure(
+                            deploy_header::missing_dependency(dependency).into(),
+                        ))
+                    }
+                    Err(error) => {
+                        return Ok(ExecutionResult::precondition_failure(Error::Exec(
+                            error.into(),
+                        )))
+                    }
+                }
+            }
+            match tracking_copy
+                .borrow_mut()
+                .read(correlation_id, &executed_deploy_key)
+            {
+                Ok(None) => (),
+                Ok(Some(_)) => {
+                    return Ok(ExecutionResult::precondition_failure(Error::ReplayedDeploy))
+                }
+                Err(error) => {
+                    return Ok(ExecutionResult::precondition_failure(Error::Exec(
+                        error.into(),
+                    )))
+                }
+            }
+            Some(executed_deploy_key)
+        } else {
+            None
+        };
+        // Once payment has been attempted the deploy counts as executed, whatever the outcome
+        let executed_deploy_record = {
+            let block_time: u64 = blocktime.into();
+            let cl_value = CLValue::from_t(block_time).expect("should convert u64 to CLValue");
+            StoredValue::CLValue(cl_value)
+        };
+
         // Create session code `A` from provided session bytes
         // validation_spec_1: valid wasm bytes
-        let session_module = match self.get_module(
-            Rc::clone(&tracking_copy),
-            &session,
-            &account,
-            correlation_id,
-            preprocessor,
-            &protocol_version,
-        ) {
-            Ok(module) => module,
-            Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error));
-            }
+        let maybe_session_module = match session {
+            ExecutableDeployItem::NativeKeyManagement { .. }
+            | ExecutableDeployItem::NativeTransfer { .. } => None,
+            _ => match self.get_module(
+                Rc::clone(&tracking_copy),
+                &session,
+                &account,
+                correlation_id,
+                preprocessor,
+                &protocol_version,
+            ) {
+                Ok(module) => Some(module),
+                Err(error) => {
+                    return Ok(ExecutionResult::precondition_failure(error));
+                }
+            },
         };
 
         // Obtain current protocol data for given version
@@ -912,13 +2639,38 @@ where
             }
         };
 
+        // On a permissioned network only whitelisted accounts may send deploys
+        // validation_spec_3: account validity
+        if self.config.whitelist_mode() == WhitelistMode::Deploys {
+            let whitelist = match deploy_whitelist::whitelist_key(&protocol_data) {
+                Some(whitelist_key) => match tracking_copy
+                    .borrow_mut()
+                    .read(correlation_id, &whitelist_key)
+                {
+                    Ok(whitelist) => whitelist,
+                    Err(error) => {
+                        return Ok(ExecutionResult::precondition_failure(Error::Exec(
+                            error.into(),
+                        )))
+                    }
+                },
+                None => None,
+            };
+            if !deploy_whitelist::is_whitelisted(whitelist, account_addr) {
+                return Ok(ExecutionResult::precondition_failure(
+                    Error::NotWhitelisted(account_addr),
+                ));
+            }
+        }
+
         let max_payment_cost: Motes = Motes::new(U512::from(MAX_PAYMENT));
 
         // Get mint system contract details
         // payment_code_spec_6: system contract validity
         let mint_reference = {
-            // Get mint system contract URef from account (an account on a different network
-            // may have a mint contract other than the CLMint)
+            // Get mint system contract URef from the protocol data of the deploy's protocol
+            // version, never from the account's named keys, so an account can't substitute its
+            // own mint
             // payment_code_spec_6: system contract validity
             let mint_reference = protocol_data.mint();
 
@@ -950,8 +2702,8 @@ where
             mint_reference
         };
 
-        // Get proof of stake system contract URef from account (an account on a
-        // different network may have a pos contract other than the CLPoS)
+        // Get proof of stake system contract URef from the protocol data of the deploy's
+        // protocol version
         // payment_code_spec_6: system contract validity
         let (
             proof_of_stake_reference,
@@ -1037,14 +2789,37 @@ where
             )
         };
 
-        // Get account main purse balance key
+        // Get the purse funding payment: the account's main purse unless the deploy names another
+        // purse, which the account must hold write access to
+        // validation_spec_5: account main purse minimum balance
+        let payment_source_purse: URef = match payment_source {
+            None => account.main_purse(),
+            Some(purse) if purse.addr() == account.main_purse().addr() => account.main_purse(),
+            Some(purse) => {
+                let normalized_purse = Key::URef(purse).normalize();
+                let maybe_named_key = account
+                    .named_keys()
+                    .values()
+                    .find(|&named_key| named_key.normalize() == normalized_purse);
+                match maybe_named_key {
+                    Some(Key::URef(uref)) if uref.is_writeable() => *uref,
+                    _ => {
+                        return Ok(ExecutionResult::precondition_failure(
+                            Error::InvalidPaymentSource(purse),
+                        ))
+                    }
+                }
+            }
+        };
+
+        // Get payment source purse balance key
         // validation_spec_5: account main purse minimum balance
-        let account_main_purse_balance_key: Key = {
-            let account_key = Key::URef(account.main_purse());
+        let payment_source_balance_key: Key = {
+            let payment_source_key = Key::URef(payment_source_purse);
             match tracking_copy.borrow_mut().get_purse_balance_key(
                 correlation_id,
                 mint_reference,
-                account_key,
+                payment_source_key,
             ) {
                 Ok(key) => key,
                 Err(error) => {
@@ -1053,11 +2828,11 @@ where
             }
         };
 
-        // Get account main purse balance to enforce precondition and in case of forced
+        // Get payment source purse balance to enforce precondition and in case of forced
         // transfer validation_spec_5: account main purse minimum balance
-        let account_main_purse_balance: Motes = match tracking_copy
+        let payment_source_balance: Motes = match tracking_copy
             .borrow_mut()
-            .get_purse_balance(correlation_id, account_main_purse_balance_key)
+            .get_purse_balance(correlation_id, payment_source_balance_key)
         {
             Ok(balance) => balance,
             Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
@@ -1065,7 +2840,7 @@ where
 
         // Enforce minimum main purse balance validation
         // validation_spec_5: account main purse minimum balance
-        if account_main_purse_balance < max_payment_cost {
+        if payment_source_balance < max_payment_cost {
             return Ok(ExecutionResult::precondition_failure(
                 Error::InsufficientPayment,
             ));
@@ -1074,7 +2849,7 @@ where
         // Finalization is executed by system account (currently genesis account)
         // payment_code_spec_5: system executes finalization
         let system_account = Account::new(
-            SYSTEM_ACCOUNT_ADDR,
+            protocol_data.system_account(),
             Default::default(),
             URef::new(Default::default(), AccessRights::READ_ADD_WRITE),
             Default::default(),
@@ -1085,6 +2860,8 @@ where
         let mut execution_result_builder = execution_result::ExecutionResultBuilder::new();
 
         // Execute provided payment code
+        stages.enter(Stage::Payment);
+        let payment_start = Instant::now();
         let payment_result = {
             // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
             // conv_rate)
@@ -1138,6 +2915,27 @@ where
             };
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
+            let payment_named_args = payment.named_args().to_vec();
+            // Standard payment is told to pay from the chosen purse when it isn't the main purse
+            let payment_args = match payment_source {
+                Some(_) if module_bytes_is_empty => {
+                    let mut args: Vec<CLValue> = match bytesrepr::deserialize(payment.take_args()) {
+                        Ok(args) => args,
+                        Err(error) => {
+                            return Ok(ExecutionResult::precondition_failure(error.into()))
+                        }
+                    };
+                    args.push(CLValue::from_t(payment_source_purse).expect("should convert URef"));
+                    match args.to_bytes() {
+                        Ok(bytes) => bytes,
+                        Err(error) => {
+                            return Ok(ExecutionResult::precondition_failure(error.into()))
+                        }
+                    }
+                }
+                _ => payment.take_args(),
+            };
+
             // payment_code_spec_2: execute payment code
             let phase = Phase::Payment;
             if !self.config.use_system_contracts() && module_bytes_is_empty {
@@ -1146,12 +2944,13 @@ where
 
                 let mut runtime = match executor.create_runtime(
                     payment_module,
-                    payment.take_args(),
+                    payment_args,
                     &mut named_keys,
                     address,
                     &account,
                     authorization_keys.clone(),
                     blocktime,
+                    block_height,
                     deploy_hash,
                     pay_gas_limit,
                     Rc::new(RefCell::new(address_generator)),
@@ -1173,21 +2972,29 @@ where
                     Ok(()) => ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        trace: runtime.take_trace(),
+                        cache_stats: Default::default(),
+                        peak_memory_pages: 0,
                     },
                     Err(error) => ExecutionResult::Failure {
                         error: error.into(),
                         effect: effects_snapshot,
                         cost: runtime.context().gas_counter(),
+                        trace: runtime.take_trace(),
+                        cache_stats: Default::default(),
+                        peak_memory_pages: 0,
                     },
                 }
             } else {
                 executor.exec(
                     payment_module,
-                    payment.take_args(),
+                    payment_args,
+                    payment_named_args,
                     address,
                     &account,
                     authorization_keys.clone(),
                     blocktime,
+                    block_height,
                     deploy_hash,
                     pay_gas_limit,
                     protocol_version,
@@ -1200,6 +3007,13 @@ where
             }
         };
 
+        self.metrics.record_duration(
+            correlation_id,
+            metrics::PAYMENT_DURATION,
+            payment_start.elapsed(),
+        );
+        stages.end(deploy_stages::outcome(&payment_result));
+
         let payment_result_cost = payment_result.cost();
 
         // payment_code_spec_3: fork based upon payment purse balance and cost of
@@ -1228,25 +3042,49 @@ where
         };
 
         if let Some(forced_transfer) = payment_result.check_forced_transfer(payment_purse_balance) {
-            let error = match forced_transfer {
+            let cause = match forced_transfer {
                 ForcedTransferResult::InsufficientPayment => Error::InsufficientPayment,
                 ForcedTransferResult::PaymentFailure => payment_result.take_error().unwrap(),
             };
-            return Ok(ExecutionResult::new_payment_code_error(
+            let error = Error::PaymentFailure {
+                error: Box::new(cause),
+                amount_taken: max_payment_cost,
+                payment_gas: payment_result_cost,
+            };
+            let result = ExecutionResult::new_payment_code_error(
                 error,
                 max_payment_cost,
-                account_main_purse_balance,
-                account_main_purse_balance_key,
+                payment_source_balance,
+                payment_source_balance_key,
                 rewards_purse_balance_key,
-            ));
+            );
+            let mut effect = result.effect().clone();
+            if let Some(key) = executed_deploy_key {
+                effect.ops.insert(key, Op::Write);
+                effect
+                    .transforms
+                    .insert(key, Transform::Write(executed_deploy_record));
+            }
+            if let Some(sequence_number) = sequence_number {
+                let mut account = account.clone();
+                account.set_sequence_number(sequence_number);
+                let key = Key::Account(account_addr);
+                effect.ops.insert(key, Op::Write);
+                effect
+                    .transforms
+                    .insert(key, Transform::Write(StoredValue::Account(account)));
+            }
+            return Ok(result.with_effect(effect));
         }
 
         execution_result_builder.set_payment_execution_result(payment_result);
 
-        let post_payment_tc = tracking_copy.borrow();
-        let session_tc = Rc::new(RefCell::new(post_payment_tc.fork()));
+        // Session runs in its own scope so that its effects can be dropped if it fails.
+        tracking_copy.borrow_mut().begin_scope();
 
         // session_code_spec_2: execute session code
+        stages.enter(Stage::Session);
+        let session_start = Instant::now();
         let session_result = {
             // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
             // payment code execution) * conv_rate, yes session
@@ -1257,45 +3095,135 @@ where
                 - payment_result_cost;
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
-            executor.exec(
-                session_module,
-                session.take_args(),
-                address,
-                &account,
-                authorization_keys.clone(),
-                blocktime,
-                deploy_hash,
-                session_gas_limit,
-                protocol_version,
-                correlation_id,
-                Rc::clone(&session_tc),
-                Phase::Session,
-                protocol_data,
-                system_contract_cache,
-            )
+            match (session, maybe_session_module) {
+                (ExecutableDeployItem::NativeKeyManagement { updates }, _) => {
+                    Self::exec_native_key_management(
+                        correlation_id,
+                        &updates,
+                        account_addr,
+                        &authorization_keys,
+                        session_gas_limit,
+                        Rc::clone(&tracking_copy),
+                    )
+                }
+                (ExecutableDeployItem::NativeTransfer { target, amount }, _) => {
+                    let cost = Gas::new(U512::from(NATIVE_TRANSFER_COST));
+                    if cost > session_gas_limit {
+                        ExecutionResult::Failure {
+                            error: Error::Exec(execution::Error::GasLimit),
+                            effect: Default::default(),
+                            cost: session_gas_limit,
+                            trace: None,
+                            cache_stats: Default::default(),
+                            peak_memory_pages: 0,
+                        }
+                    } else {
+                        match wasm::do_nothing_module(preprocessor) {
+                            Ok(module) => executor.exec_native_transfer(
+                                module,
+                                target,
+                                amount,
+                                address,
+                                &account,
+                                authorization_keys.clone(),
+                                blocktime,
+                                block_height,
+                                deploy_hash,
+                                session_gas_limit,
+                                cost,
+                                protocol_version,
+                                correlation_id,
+                                Rc::clone(&tracking_copy),
+                                protocol_data,
+                                system_contract_cache,
+                            ),
+                            Err(error) => ExecutionResult::precondition_failure(error.into()),
+                        }
+                    }
+                }
+                (session, Some(session_module)) => {
+                    let named_args = session.named_args().to_vec();
+                    executor.exec(
+                        session_module,
+                        session.take_args(),
+                        named_args,
+                        address,
+                        &account,
+                        authorization_keys.clone(),
+                        blocktime,
+                        block_height,
+                        deploy_hash,
+                        session_gas_limit,
+                        protocol_version,
+                        correlation_id,
+                        Rc::clone(&tracking_copy),
+                        Phase::Session,
+                        protocol_data,
+                        system_contract_cache,
+                    )
+                }
+                (_, None) => unreachable!("session module is only skipped for native deploys"),
+            }
         };
 
-        let post_session_rc = if session_result.is_failure() {
+        self.metrics.record_duration(
+            correlation_id,
+            metrics::SESSION_DURATION,
+            session_start.elapsed(),
+        );
+        stages.end(deploy_stages::outcome(&session_result));
+
+        if session_result.is_failure() {
             // If session code fails we do not include its effects,
             // so we start again from the post-payment state.
-            Rc::new(RefCell::new(post_payment_tc.fork()))
+            tracking_copy.borrow_mut().rollback_scope();
         } else {
-            session_tc
-        };
+            tracking_copy.borrow_mut().commit_scope();
+        }
 
         // NOTE: session_code_spec_3: (do not include session execution effects in
         // results) is enforced in execution_result_builder.build()
         execution_result_builder.set_session_execution_result(session_result);
 
         // payment_code_spec_5: run finalize process
+        stages.enter(Stage::Finalize);
+        let finalize_start = Instant::now();
         let finalize_result = {
-            let post_session_tc = post_session_rc.borrow();
-            let finalization_tc = Rc::new(RefCell::new(post_session_tc.fork()));
+            tracking_copy.borrow_mut().begin_scope();
+            let finalization_tc = Rc::clone(&tracking_copy);
+
+            if let Some(key) = executed_deploy_key {
+                finalization_tc
+                    .borrow_mut()
+                    .write(key, executed_deploy_record);
+            }
+
+            // Session may have changed the account, so it's read again before advancing its
+            // sequence
+            if let Some(sequence_number) = sequence_number {
+                let maybe_account = finalization_tc
+                    .borrow_mut()
+                    .get_account(correlation_id, account_addr);
+                match maybe_account {
+                    Ok(mut account) => {
+                        account.set_sequence_number(sequence_number);
+                        finalization_tc
+                            .borrow_mut()
+                            .write(Key::Account(account_addr), StoredValue::Account(account));
+                    }
+                    Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                }
+            }
 
             let proof_of_stake_args = {
                 //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
                 let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
-                let args = ("finalize_payment", finalize_cost_motes.value(), account_addr);
+                let args = (
+                    "finalize_payment",
+                    finalize_cost_motes.value(),
+                    account_addr,
+                    protocol_data.refund_ratio(),
+                );
                 ArgsParser::parse(args)
                     .expect("args should convert to `Vec<CLValue>`")
                     .into_bytes()
@@ -1315,7 +3243,8 @@ where
             let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
 
             let base_key = Key::from(proof_of_stake_reference);
-            let gas_limit = Gas::new(U512::from(std::u64::MAX));
+            // Finalization isn't metered; the runtime bounds it by a host call budget instead.
+            let gas_limit = Gas::default();
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
             executor.exec_finalize(
@@ -1326,6 +3255,7 @@ where
                 &system_account,
                 authorization_keys,
                 blocktime,
+                block_height,
                 deploy_hash,
                 gas_limit,
                 protocol_version,
@@ -1337,6 +3267,14 @@ where
             )
         };
 
+        self.metrics.record_duration(
+            correlation_id,
+            metrics::FINALIZE_DURATION,
+            finalize_start.elapsed(),
+        );
+        stages.end(deploy_stages::outcome(&finalize_result));
+
+        tracking_copy.borrow_mut().commit_scope();
         execution_result_builder.set_finalize_execution_result(finalize_result);
 
         // We panic here to indicate that the builder was not used properly.
@@ -1350,6 +3288,70 @@ where
         Ok(ret)
     }
 
+    /// Applies the session of a native key management deploy to the deploying account,
+    /// charging `NATIVE_KEY_MANAGEMENT_UPDATE_COST` per update.
+    ///
+    /// The updates are applied as a batch with the same semantics as the
+    /// `update_associated_keys` host function, so a rejected batch reverts with its `ApiError`.
+    fn exec_native_key_management(
+        correlation_id: CorrelationId,
+        updates: &[AssociatedKeysUpdate],
+        account_addr: PublicKey,
+        authorization_keys: &BTreeSet<PublicKey>,
+        gas_limit: Gas,
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
+    ) -> ExecutionResult {
+        let cost = Gas::new(U512::from(
+            NATIVE_KEY_MANAGEMENT_UPDATE_COST * updates.len() as u64,
+        ));
+        if cost > gas_limit {
+            return ExecutionResult::Failure {
+                error: Error::Exec(execution::Error::GasLimit),
+                effect: Default::default(),
+                cost: gas_limit,
+                trace: None,
+                cache_stats: Default::default(),
+                peak_memory_pages: 0,
+            };
+        }
+
+        let result = tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, account_addr)
+            .and_then(|mut account| {
+                if !account.can_manage_keys_with(authorization_keys) {
+                    return Err(execution::Error::Revert(ApiError::PermissionDenied));
+                }
+                account
+                    .update_associated_keys(updates)
+                    .map_err(execution::Error::Revert)?;
+                Ok(account)
+            });
+
+        match result {
+            Ok(account) => {
+                tracking_copy
+                    .borrow_mut()
+                    .write(Key::Account(account_addr), StoredValue::Account(account));
+                ExecutionResult::Success {
+                    effect: tracking_copy.borrow().effect(),
+                    cost,
+                    trace: None,
+                    cache_stats: Default::default(),
+                    peak_memory_pages: 0,
+                }
+            }
+            Err(error) => ExecutionResult::Failure {
+                error: Error::Exec(error),
+                effect: Default::default(),
+                cost,
+                trace: None,
+                cache_stats: Default::default(),
+                peak_memory_pages: 0,
+            },
+        }
+    }
+
     pub fn apply_effect(
         &self,
         correlation_id: CorrelationId,
@@ -1360,29 +3362,136 @@ where
     where
         Error: From<S::Error>,
     {
-        match self.state.commit(correlation_id, pre_state_hash, effects)? {
-            CommitResult::Success { state_root, .. } => {
-                let bonded_validators =
-                    self.get_bonded_validators(correlation_id, protocol_version, state_root)?;
+        let mint = self
+            .state
+            .get_protocol_data(protocol_version)?
+            .map(|protocol_data| protocol_data.mint());
+        let mut touched_keys: Vec<Key> = effects.keys().cloned().collect();
+        touched_keys.push(ACCOUNTS_INDEX_ROOT_KEY);
+        let commit_start = Instant::now();
+        let commit_result =
+            accounts_index::commit(&*self.state, correlation_id, mint, pre_state_hash, effects);
+        let outcome = match &commit_result {
+            Ok(CommitResult::Success { .. }) => StageOutcome::Success,
+            Ok(commit_result) => StageOutcome::Failure(commit_result.to_string()),
+            Err(error) => StageOutcome::Failure(error.to_string()),
+        };
+        self.stage_logger.log_stage(
+            correlation_id,
+            Stage::Commit,
+            commit_start.elapsed(),
+            &outcome,
+        );
+        match commit_result? {
+            CommitResult::Success {
+                state_root, stats, ..
+            } => {
+                self.block_cache
+                    .advance(pre_state_hash, state_root, &touched_keys);
+                self.metrics.increment_counter(
+                    correlation_id,
+                    metrics::TRIE_WRITES,
+                    u64::from(stats.writes + stats.adds + stats.deletes),
+                );
+                self.metrics.increment_counter(
+                    correlation_id,
+                    metrics::TRIE_BYTES_WRITTEN,
+                    stats.bytes_written,
+                );
+
+                #[cfg(debug_assertions)]
+                self.maybe_check_total_supply(correlation_id, protocol_version, state_root);
+
+                let bonded_validators = self.get_bonded_validators(correlation_id, state_root)?;
+                self.check_bonding_purse(correlation_id, protocol_version, state_root)?;
                 Ok(CommitResult::Success {
                     state_root,
                     bonded_validators,
+                    stats,
                 })
             }
             commit_result => Ok(commit_result),
         }
     }
 
-    /// Calculates bonded validators at `root_hash` state.
+    /// Like [`apply_effect`](EngineState::apply_effect), but also returns a handle confirming
+    /// when the commit reaches disk.
+    ///
+    /// With [`EngineConfig::deferred_fsync`] set, the commit is flushed by the engine's sync
+    /// worker thread, started by the first such commit, rather than before returning.
+    pub fn apply_effect_with_durability(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<(CommitResult, DurabilityHandle), Error>
+    where
+        S: Send + Sync + 'static,
+        Error: From<S::Error>,
+    {
+        let commit_result =
+            self.apply_effect(correlation_id, protocol_version, pre_state_hash, effects)?;
+        if !self.config.deferred_fsync() {
+            return Ok((commit_result, DurabilityHandle::durable()));
+        }
+        let mut sync_worker = self.sync_worker.lock().expect("sync worker lock poisoned");
+        let sync_worker = sync_worker.get_or_insert_with(|| {
+            let state = Arc::clone(&self.state);
+            SyncWorker::spawn(move || state.sync().map_err(Error::from))
+        });
+        Ok((commit_result, sync_worker.request_sync()))
+    }
+
+    /// Runs `check_total_supply` on a random sample of commits.
+    ///
+    /// Panics if the check finds that the commit created or destroyed motes.
+    #[cfg(debug_assertions)]
+    fn maybe_check_total_supply(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        state_root: Blake2bHash,
+    ) {
+        if !rand::Rng::gen_bool(&mut rand::thread_rng(), TOTAL_SUPPLY_CHECK_PROBABILITY) {
+            return;
+        }
+        match self.check_total_supply(correlation_id, protocol_version, state_root) {
+            Ok(_) => {}
+            Err(error @ Error::TotalSupplyMismatch { .. }) => {
+                panic!("{} at post-state hash {}", error, state_root)
+            }
+            Err(error) => log::warn!("Unable to check total supply: {}", error),
+        }
+    }
+
+    /// Returns the vote weights of the [validators of the era in
+    /// progress](EngineState::get_era_validators) at `root_hash`.
     ///
-    /// Should only be called with a valid root hash after a successful call to
-    /// [`StateProvider::commit`]. Will panic if called with an invalid root hash.
+    /// An era's validators are fixed when it starts, so stakes bonded or unbonded in PoS since
+    /// then only count once [`step`](EngineState::step) ends the era.
     fn get_bonded_validators(
+        &self,
+        correlation_id: CorrelationId,
+        root_hash: Blake2bHash,
+    ) -> Result<HashMap<PublicKey, U512>, Error> {
+        let era_id = self.get_current_era_id(correlation_id, root_hash)?;
+        match self.get_era_validators(correlation_id, root_hash, era_id)? {
+            Some(era_validators) => Ok(era_validators::weights(&era_validators)),
+            None => Err(Error::MissingEraValidators(era_id)),
+        }
+    }
+
+    /// Checks that the PoS bonding purse at `root_hash` holds at least the weights (stakes and
+    /// delegations) of the validators currently bonded in PoS plus the amounts of all pending
+    /// bonding and unbonding requests, i.e. that no withdrawal has been paid out before leaving the
+    /// unbonding queue.
+    fn check_bonding_purse(
         &self,
         correlation_id: CorrelationId,
         protocol_version: ProtocolVersion,
         root_hash: Blake2bHash,
-    ) -> Result<HashMap<PublicKey, U512>, Error>
+    ) -> Result<(), Error>
     where
         Error: From<S::Error>,
     {
@@ -1390,28 +3499,96 @@ where
             Some(protocol_data) => protocol_data,
             None => return Err(Error::InvalidProtocolVersion(protocol_version)),
         };
-
-        let proof_of_stake = {
-            let tmp = protocol_data.proof_of_stake();
-            Key::URef(tmp).normalize()
-        };
-
-        let reader = match self.state.checkout(root_hash)? {
-            Some(reader) => reader,
-            None => panic!("get_bonded_validators called with an invalid root hash"),
+        let proof_of_stake = protocol_data.proof_of_stake();
+        let mut tracking_copy = match self.tracking_copy(root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Err(Error::MissingRoot(root_hash)),
         };
 
-        let contract = match reader.read(correlation_id, &proof_of_stake)? {
+        let contract = match tracking_copy
+            .read(correlation_id, &Key::URef(proof_of_stake).normalize())
+            .map_err(|error| Error::Exec(error.into()))?
+        {
             Some(StoredValue::Contract(contract)) => contract,
             _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
         };
-
-        let bonded_validators = contract
+        let bonding_purse = contract
             .named_keys()
-            .keys()
-            .filter_map(|entry| utils::pos_validator_key_name_to_tuple(entry))
-            .collect::<HashMap<PublicKey, U512>>();
+            .get(POS_BONDING_PURSE)
+            .cloned()
+            .ok_or_else(|| execution::Error::URefNotFound(POS_BONDING_PURSE.to_string()))?;
+        let balance_key = tracking_copy.get_purse_balance_key(
+            correlation_id,
+            protocol_data.mint(),
+            bonding_purse,
+        )?;
+        let held = tracking_copy
+            .get_purse_balance(correlation_id, balance_key)?
+            .value();
+
+        let mut required = era_validators::era_validators_from_pos(contract.named_keys())
+            .values()
+            .fold(U512::zero(), |total, stake| total + stake.weight());
+        for queue_key in &[
+            ::proof_of_stake::BONDING_KEY,
+            ::proof_of_stake::UNBONDING_KEY,
+        ] {
+            let queue = match Self::read_pos_queue(
+                correlation_id,
+                &mut tracking_copy,
+                proof_of_stake,
+                *queue_key,
+            )? {
+                Some(queue) => queue,
+                None => continue,
+            };
+            required = queue
+                .0
+                .iter()
+                .fold(required, |total, entry| total + entry.amount);
+        }
+
+        if held < required {
+            return Err(Error::BondingPurseDeficit { required, held });
+        }
+        Ok(())
+    }
+
+    /// Reads the PoS queue stored under `queue_key` (`BONDING_KEY` or `UNBONDING_KEY`) in the
+    /// local state of `proof_of_stake`, or `None` if it has never been written.
+    fn read_pos_queue(
+        correlation_id: CorrelationId,
+        tracking_copy: &mut TrackingCopy<S::Reader>,
+        proof_of_stake: URef,
+        queue_key: u8,
+    ) -> Result<Option<::proof_of_stake::Queue>, Error> {
+        let key = Key::local(proof_of_stake.addr(), &queue_key.to_bytes()?);
+        match tracking_copy
+            .read(correlation_id, &key)
+            .map_err(|error| Error::Exec(error.into()))?
+        {
+            Some(stored_value) => {
+                let cl_value: CLValue = stored_value
+                    .try_into()
+                    .map_err(execution::Error::TypeMismatch)?;
+                Ok(Some(cl_value.into_t().map_err(execution::Error::from)?))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_storage::global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState};
+
+    use super::EngineState;
+
+    fn assert_send_sync<T: Send + Sync>() {}
 
-        Ok(bonded_validators)
+    #[test]
+    fn engine_state_should_be_send_and_sync() {
+        assert_send_sync::<EngineState<InMemoryGlobalState>>();
+        assert_send_sync::<EngineState<LmdbGlobalState>>();
     }
 }
@@ -1,45 +1,77 @@
-use std::{
-    default::Default,
-    fmt::{self, Display, Formatter},
-    ops::{Add, AddAssign},
-};
-
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
-pub enum Op {
-    Read,
-    Write,
-    Add,
-    NoOp,
-}
-
-impl Add for Op {
-    type Output = Op;
-
-    fn add(self, other: Op) -> Op {
-        match (self, other) {
-            (a, Op::NoOp) => a,
-            (Op::NoOp, b) => b,
-            (Op::Read, Op::Read) => Op::Read,
-            (Op::Add, Op::Add) => Op::Add,
-            _ => Op::Write,
-        }
-    }
-}
-
-impl AddAssign for Op {
-    fn add_assign(&mut self, other: Self) {
-        *self = *self + other;
-    }
-}
-
-impl Display for Op {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-impl Default for Op {
-    fn default() -> Self {
-        Op::NoOp
-    }
-}
+use std::{
+    default::Default,
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign},
+};
+
+use types::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Op {
+    Read,
+    Write,
+    Add,
+    NoOp,
+}
+
+impl Add for Op {
+    type Output = Op;
+
+    fn add(self, other: Op) -> Op {
+        match (self, other) {
+            (a, Op::NoOp) => a,
+            (Op::NoOp, b) => b,
+            (Op::Read, Op::Read) => Op::Read,
+            (Op::Add, Op::Add) => Op::Add,
+            _ => Op::Write,
+        }
+    }
+}
+
+impl AddAssign for Op {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Default for Op {
+    fn default() -> Self {
+        Op::NoOp
+    }
+}
+
+impl ToBytes for Op {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag: u8 = match self {
+            Op::Read => 0,
+            Op::Write => 1,
+            Op::Add => 2,
+            Op::NoOp => 3,
+        };
+        Ok(vec![tag])
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for Op {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let op = match tag {
+            0 => Op::Read,
+            1 => Op::Write,
+            2 => Op::Add,
+            3 => Op::NoOp,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((op, remainder))
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::PublicKey, BlockTime, Key, ProtocolVersion, U512};
+
+use crate::engine_state::utils;
+
+pub enum PosViewResult {
+    RootNotFound,
+    Success(PosView),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PosViewRequest {
+    state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+}
+
+impl PosViewRequest {
+    pub fn new(state_hash: Blake2bHash, protocol_version: ProtocolVersion) -> Self {
+        PosViewRequest {
+            state_hash,
+            protocol_version,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+}
+
+/// A request waiting in the PoS unbonding queue to be paid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingUnbond {
+    pub public_key: PublicKey,
+    pub amount: U512,
+    pub timestamp: BlockTime,
+}
+
+/// A read-only snapshot of the Proof of Stake contract's state, decoded from its storage layout.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PosView {
+    bids: BTreeMap<PublicKey, U512>,
+    unbonding_queue: Vec<PendingUnbond>,
+}
+
+impl PosView {
+    pub fn new(
+        pos_named_keys: &BTreeMap<String, Key>,
+        unbonding_queue: Vec<PendingUnbond>,
+    ) -> Self {
+        let bids = pos_named_keys
+            .keys()
+            .filter_map(|entry| utils::pos_validator_key_name_to_tuple(entry))
+            .collect();
+        PosView {
+            bids,
+            unbonding_queue,
+        }
+    }
+
+    /// The stakes the validators have bonded themselves.
+    pub fn bids(&self) -> &BTreeMap<PublicKey, U512> {
+        &self.bids
+    }
+
+    /// Unbonding and undelegation requests which have not been paid out yet, oldest first.
+    pub fn unbonding_queue(&self) -> &[PendingUnbond] {
+        &self.unbonding_queue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{AccessRights, URef};
+
+    use super::*;
+
+    const VALIDATOR_1: PublicKey = PublicKey::ed25519_from([1; 32]);
+    const VALIDATOR_2: PublicKey = PublicKey::ed25519_from([2; 32]);
+    const DELEGATOR: PublicKey = PublicKey::ed25519_from([3; 32]);
+
+    fn name_of(prefix: &str, keys: &[PublicKey], amount: u64) -> String {
+        let hex_keys: Vec<String> = keys.iter().map(PublicKey::to_tagged_hex).collect();
+        format!("{}_{}_{}", prefix, hex_keys.join("_"), amount)
+    }
+
+    #[test]
+    fn should_leave_delegations_out_of_bids() {
+        let uref = Key::URef(URef::new([0; 32], AccessRights::READ));
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert(name_of("v", &[VALIDATOR_1], 100), uref);
+        named_keys.insert(name_of("v", &[VALIDATOR_2], 50), uref);
+        named_keys.insert(name_of("d", &[DELEGATOR, VALIDATOR_1], 7), uref);
+        // Delegation to a validator which has since unbonded.
+        named_keys.insert(name_of("d", &[DELEGATOR, DELEGATOR], 9), uref);
+        named_keys.insert("pos_bonding_purse".to_string(), uref);
+
+        let view = PosView::new(&named_keys, Vec::new());
+
+        let mut expected_bids = BTreeMap::new();
+        expected_bids.insert(VALIDATOR_1, U512::from(100));
+        expected_bids.insert(VALIDATOR_2, U512::from(50));
+        assert_eq!(view.bids(), &expected_bids);
+        assert!(view.unbonding_queue().is_empty());
+    }
+}
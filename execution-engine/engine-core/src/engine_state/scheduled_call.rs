@@ -0,0 +1,237 @@
+//! Calls to stored contracts which were registered by a deploy to run at a later block.
+//!
+//! A contract registers a call with the `schedule_call` host function, which charges the call's
+//! whole gas budget to the registering deploy up front and stores a [`ScheduledCall`] under a key
+//! of its own, so that the deploy also pays for writing just that entry.  The scheduled calls are
+//! made by [`EngineState::step`](super::EngineState::step) in blocks which end an era: each call
+//! whose earliest block time has been reached is removed and made as its registrant, limited to its
+//! pre-paid budget, and its changes are discarded if it fails.  Any unused budget is not refunded.
+
+use engine_shared::{stored_value::StoredValue, TypeMismatch};
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, FromBytes, ToBytes},
+    BlockTime, CLType, CLTyped, Key, KEY_LOCAL_SEED_LENGTH,
+};
+
+use crate::{engine_state::utils, execution};
+
+/// The seed of the local state partition holding the scheduled calls.  It is not the seed of any
+/// account or contract, so the calls can't be written to by Wasm.
+const SCHEDULED_CALLS_SEED: [u8; KEY_LOCAL_SEED_LENGTH] = [252; KEY_LOCAL_SEED_LENGTH];
+
+/// A call to the contract under `contract_key` with the serialized `args`, to be made as
+/// `registrant` once the block time reaches `earliest_blocktime`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledCall {
+    registrant: PublicKey,
+    contract_key: Key,
+    args: Vec<u8>,
+    earliest_blocktime: BlockTime,
+    gas_budget: u64,
+}
+
+impl ScheduledCall {
+    pub fn new(
+        registrant: PublicKey,
+        contract_key: Key,
+        args: Vec<u8>,
+        earliest_blocktime: BlockTime,
+        gas_budget: u64,
+    ) -> Self {
+        ScheduledCall {
+            registrant,
+            contract_key,
+            args,
+            earliest_blocktime,
+            gas_budget,
+        }
+    }
+
+    /// Returns the account which registered the call and as which it is made.
+    pub fn registrant(&self) -> PublicKey {
+        self.registrant
+    }
+
+    /// Returns the key of the contract to call.
+    pub fn contract_key(&self) -> Key {
+        self.contract_key
+    }
+
+    /// Returns the serialized arguments to call the contract with.
+    pub fn args(&self) -> &[u8] {
+        &self.args
+    }
+
+    /// Returns the earliest block time at which the call may be made.
+    pub fn earliest_blocktime(&self) -> BlockTime {
+        self.earliest_blocktime
+    }
+
+    /// Returns the gas limit of the call, which the registrant has already paid for.
+    pub fn gas_budget(&self) -> u64 {
+        self.gas_budget
+    }
+
+    fn is_due(&self, blocktime: BlockTime) -> bool {
+        let earliest: u64 = self.earliest_blocktime.into();
+        let now: u64 = blocktime.into();
+        earliest <= now
+    }
+}
+
+impl CLTyped for ScheduledCall {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ScheduledCall {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.registrant.to_bytes()?);
+        result.append(&mut self.contract_key.to_bytes()?);
+        result.append(&mut self.args.to_bytes()?);
+        result.append(&mut self.earliest_blocktime.to_bytes()?);
+        result.append(&mut self.gas_budget.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.registrant.serialized_length()
+            + self.contract_key.serialized_length()
+            + self.args.serialized_length()
+            + self.earliest_blocktime.serialized_length()
+            + self.gas_budget.serialized_length()
+    }
+}
+
+impl FromBytes for ScheduledCall {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (registrant, rem) = FromBytes::from_bytes(bytes)?;
+        let (contract_key, rem) = FromBytes::from_bytes(rem)?;
+        let (args, rem) = FromBytes::from_bytes(rem)?;
+        let (earliest_blocktime, rem) = FromBytes::from_bytes(rem)?;
+        let (gas_budget, rem) = FromBytes::from_bytes(rem)?;
+        let scheduled_call = ScheduledCall {
+            registrant,
+            contract_key,
+            args,
+            earliest_blocktime,
+            gas_budget,
+        };
+        Ok((scheduled_call, rem))
+    }
+}
+
+/// Returns the key under which a call registered by the deploy with hash `deploy_hash` is stored.
+/// `call_address` is an address generated for the call, telling apart the calls registered by the
+/// same deploy.
+pub fn scheduled_call_key(
+    earliest_blocktime: BlockTime,
+    deploy_hash: [u8; 32],
+    call_address: [u8; 32],
+) -> Key {
+    let key_bytes = (earliest_blocktime, deploy_hash, call_address)
+        .to_bytes()
+        .expect("should serialize scheduled call key");
+    Key::local(SCHEDULED_CALLS_SEED, &key_bytes)
+}
+
+/// Returns the prefix which the serialized key of every scheduled call starts with.
+pub fn scheduled_calls_prefix() -> Vec<u8> {
+    utils::local_key_prefix(SCHEDULED_CALLS_SEED)
+}
+
+/// Returns the scheduled call read from global state.
+pub fn scheduled_call_from(
+    stored_value: Option<StoredValue>,
+) -> Result<Option<ScheduledCall>, execution::Error> {
+    match stored_value {
+        None => Ok(None),
+        Some(StoredValue::CLValue(cl_value)) => Ok(Some(cl_value.into_t()?)),
+        Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+            "CLValue".to_string(),
+            other.type_name(),
+        ))),
+    }
+}
+
+/// Sorts `scheduled_calls` into the order they are to be made: by earliest block time, then by
+/// key.
+pub fn sort_in_call_order(scheduled_calls: &mut [(Key, ScheduledCall)]) {
+    scheduled_calls.sort_by_key(|(key, call)| (u64::from(call.earliest_blocktime), *key));
+}
+
+/// Returns the calls among `scheduled_calls` which are due by `blocktime`, in the order they are to
+/// be made.
+pub fn take_due(
+    scheduled_calls: Vec<(Key, ScheduledCall)>,
+    blocktime: BlockTime,
+) -> Vec<(Key, ScheduledCall)> {
+    let mut due: Vec<(Key, ScheduledCall)> = scheduled_calls
+        .into_iter()
+        .filter(|(_key, call)| call.is_due(blocktime))
+        .collect();
+    sort_in_call_order(&mut due);
+    due
+}
+
+#[cfg(test)]
+mod tests {
+    use types::CLValue;
+
+    use super::*;
+
+    const REGISTRANT: PublicKey = PublicKey::ed25519_from([1; 32]);
+
+    fn scheduled_call(earliest_blocktime: u64) -> ScheduledCall {
+        ScheduledCall::new(
+            REGISTRANT,
+            Key::Hash([2; KEY_HASH_LENGTH]),
+            vec![3, 4, 5],
+            BlockTime::new(earliest_blocktime),
+            1_000,
+        )
+    }
+
+    #[test]
+    fn should_roundtrip_scheduled_call() {
+        let call = scheduled_call(10);
+        let stored_value = StoredValue::CLValue(CLValue::from_t(call.clone()).unwrap());
+        assert_eq!(scheduled_call_from(Some(stored_value)).unwrap(), Some(call));
+        assert_eq!(scheduled_call_from(None).unwrap(), None);
+    }
+
+    #[test]
+    fn should_store_each_call_under_its_own_key() {
+        let key = scheduled_call_key(BlockTime::new(10), [1; 32], [2; 32]);
+        assert_ne!(
+            key,
+            scheduled_call_key(BlockTime::new(10), [1; 32], [3; 32])
+        );
+        assert_ne!(
+            key,
+            scheduled_call_key(BlockTime::new(10), [4; 32], [2; 32])
+        );
+        assert!(key
+            .to_bytes()
+            .unwrap()
+            .starts_with(&scheduled_calls_prefix()));
+    }
+
+    #[test]
+    fn should_only_take_calls_which_are_due() {
+        let key = |byte: u8| scheduled_call_key(BlockTime::new(0), [byte; 32], [byte; 32]);
+        let scheduled_calls = vec![
+            (key(1), scheduled_call(30)),
+            (key(2), scheduled_call(20)),
+            (key(3), scheduled_call(10)),
+        ];
+        let due = take_due(scheduled_calls, BlockTime::new(20));
+        assert_eq!(
+            due,
+            vec![(key(3), scheduled_call(10)), (key(2), scheduled_call(20))]
+        );
+    }
+}
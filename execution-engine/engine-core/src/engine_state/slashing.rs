@@ -0,0 +1,186 @@
+use std::fmt;
+
+use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{account::PublicKey, bytesrepr, Key};
+
+use crate::engine_state::{error::Error, execution_effect::ExecutionEffect};
+
+/// The header of a block or ballot signed by a validator, as far as it is needed to detect
+/// equivocation.
+///
+/// Signatures are checked by the consensus layer before the evidence is submitted; the engine only
+/// checks that the two messages contradict each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedMessageHeader {
+    pub message_hash: Blake2bHash,
+    pub validator: PublicKey,
+    /// The position of the message in the sequence of messages created by `validator`.
+    pub validator_seq_num: u64,
+}
+
+/// Proof that a validator equivocated, i.e. created two different messages at the same position of
+/// its sequence of messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EquivocationEvidence {
+    first: SignedMessageHeader,
+    second: SignedMessageHeader,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidEvidence {
+    DifferentValidators,
+    DifferentSequenceNumbers,
+    SameMessage,
+}
+
+impl fmt::Display for InvalidEvidence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            InvalidEvidence::DifferentValidators => {
+                write!(f, "Messages were created by different validators")
+            }
+            InvalidEvidence::DifferentSequenceNumbers => {
+                write!(f, "Messages have different sequence numbers")
+            }
+            InvalidEvidence::SameMessage => write!(f, "Messages are identical"),
+        }
+    }
+}
+
+impl EquivocationEvidence {
+    pub fn new(first: SignedMessageHeader, second: SignedMessageHeader) -> Self {
+        EquivocationEvidence { first, second }
+    }
+
+    /// The validator accused of equivocating.
+    pub fn validator(&self) -> PublicKey {
+        self.first.validator
+    }
+
+    /// Checks that the two messages do prove an equivocation.
+    pub fn validate(&self) -> Result<(), InvalidEvidence> {
+        if self.first.validator != self.second.validator {
+            return Err(InvalidEvidence::DifferentValidators);
+        }
+        if self.first.validator_seq_num != self.second.validator_seq_num {
+            return Err(InvalidEvidence::DifferentSequenceNumbers);
+        }
+        if self.first.message_hash == self.second.message_hash {
+            return Err(InvalidEvidence::SameMessage);
+        }
+        Ok(())
+    }
+
+    /// A hash identifying the evidence regardless of the order of the two messages, used to seed
+    /// the address generator of the slashing execution.
+    pub fn hash(&self) -> Blake2bHash {
+        let (lower, higher) = if self.first.message_hash <= self.second.message_hash {
+            (self.first.message_hash, self.second.message_hash)
+        } else {
+            (self.second.message_hash, self.first.message_hash)
+        };
+        let mut bytes = lower.to_vec();
+        bytes.extend_from_slice(&higher.to_vec());
+        Blake2bHash::new(&bytes)
+    }
+}
+
+pub enum SlashingResult {
+    RootNotFound,
+    InvalidEvidence(InvalidEvidence),
+    /// The Proof of Stake contract refused to slash, e.g. because the validator is not bonded.
+    Failure(Error),
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    },
+}
+
+impl fmt::Display for SlashingResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SlashingResult::RootNotFound => write!(f, "Root not found"),
+            SlashingResult::InvalidEvidence(error) => write!(f, "Invalid evidence: {}", error),
+            SlashingResult::Failure(error) => write!(f, "Failure: {:?}", error),
+            SlashingResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            SlashingResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            SlashingResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            SlashingResult::Success {
+                post_state_hash,
+                effect,
+            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+        }
+    }
+}
+
+impl SlashingResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => SlashingResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => SlashingResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => {
+                SlashingResult::TypeMismatch(type_mismatch)
+            }
+            CommitResult::Serialization(error) => SlashingResult::Serialization(error),
+            CommitResult::Success { state_root, .. } => SlashingResult::Success {
+                post_state_hash: state_root,
+                effect,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALIDATOR_1: PublicKey = PublicKey::ed25519_from([1; 32]);
+    const VALIDATOR_2: PublicKey = PublicKey::ed25519_from([2; 32]);
+
+    fn header(message: u8, validator: PublicKey, validator_seq_num: u64) -> SignedMessageHeader {
+        SignedMessageHeader {
+            message_hash: Blake2bHash::new(&[message]),
+            validator,
+            validator_seq_num,
+        }
+    }
+
+    #[test]
+    fn should_accept_conflicting_messages() {
+        let evidence =
+            EquivocationEvidence::new(header(1, VALIDATOR_1, 5), header(2, VALIDATOR_1, 5));
+        assert_eq!(evidence.validate(), Ok(()));
+        assert_eq!(evidence.validator(), VALIDATOR_1);
+
+        let swapped =
+            EquivocationEvidence::new(header(2, VALIDATOR_1, 5), header(1, VALIDATOR_1, 5));
+        assert_eq!(evidence.hash(), swapped.hash());
+    }
+
+    #[test]
+    fn should_reject_non_conflicting_messages() {
+        let different_validators =
+            EquivocationEvidence::new(header(1, VALIDATOR_1, 5), header(2, VALIDATOR_2, 5));
+        assert_eq!(
+            different_validators.validate(),
+            Err(InvalidEvidence::DifferentValidators)
+        );
+
+        let different_seq_nums =
+            EquivocationEvidence::new(header(1, VALIDATOR_1, 5), header(2, VALIDATOR_1, 6));
+        assert_eq!(
+            different_seq_nums.validate(),
+            Err(InvalidEvidence::DifferentSequenceNumbers)
+        );
+
+        let same_message =
+            EquivocationEvidence::new(header(1, VALIDATOR_1, 5), header(1, VALIDATOR_1, 5));
+        assert_eq!(same_message.validate(), Err(InvalidEvidence::SameMessage));
+    }
+}
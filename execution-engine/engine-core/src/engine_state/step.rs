@@ -0,0 +1,80 @@
+use std::fmt;
+
+use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{bytesrepr, BlockTime, Key};
+
+use crate::engine_state::{
+    era_rewards::EraReport, error::Error, execution_effect::ExecutionEffect,
+};
+
+/// The system bookkeeping to run once per block, independently of the block's deploys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepRequest {
+    /// The time of the block, against which unbonding delays are checked.
+    pub blocktime: BlockTime,
+    /// The report of the era which the block ends, if any, used to pay out the era's rewards.
+    pub era_end: Option<EraReport>,
+}
+
+impl StepRequest {
+    /// Creates a request for a block which doesn't end an era.
+    pub fn new(blocktime: BlockTime) -> Self {
+        StepRequest {
+            blocktime,
+            era_end: None,
+        }
+    }
+
+    /// Marks the block as the last of its era, described by `era_report`.
+    pub fn with_era_end(mut self, era_report: EraReport) -> Self {
+        self.era_end = Some(era_report);
+        self
+    }
+}
+
+pub enum StepResult {
+    RootNotFound,
+    /// One of the Proof of Stake contract's bookkeeping methods failed.
+    Failure(Error),
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    },
+}
+
+impl fmt::Display for StepResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            StepResult::RootNotFound => write!(f, "Root not found"),
+            StepResult::Failure(error) => write!(f, "Failure: {:?}", error),
+            StepResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            StepResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            StepResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            StepResult::Success {
+                post_state_hash,
+                effect,
+            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+        }
+    }
+}
+
+impl StepResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => StepResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => StepResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => StepResult::TypeMismatch(type_mismatch),
+            CommitResult::Serialization(error) => StepResult::Serialization(error),
+            CommitResult::Success { state_root, .. } => StepResult::Success {
+                post_state_hash: state_root,
+                effect,
+            },
+        }
+    }
+}
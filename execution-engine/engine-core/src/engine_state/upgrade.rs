@@ -2,7 +2,7 @@ use std::fmt;
 
 use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_features::WasmFeatures};
 use types::{bytesrepr, Key, ProtocolVersion};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
@@ -61,9 +61,13 @@ pub struct UpgradeConfig {
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_costs: Option<WasmCosts>,
     activation_point: Option<ActivationPoint>,
+    max_call_depth: Option<u32>,
+    wasm_features: Option<WasmFeatures>,
+    refund_ratio: Option<u8>,
 }
 
 impl UpgradeConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pre_state_hash: Blake2bHash,
         current_protocol_version: ProtocolVersion,
@@ -72,6 +76,9 @@ impl UpgradeConfig {
         upgrade_installer_bytes: Option<Vec<u8>>,
         wasm_costs: Option<WasmCosts>,
         activation_point: Option<ActivationPoint>,
+        max_call_depth: Option<u32>,
+        wasm_features: Option<WasmFeatures>,
+        refund_ratio: Option<u8>,
     ) -> Self {
         UpgradeConfig {
             pre_state_hash,
@@ -81,6 +88,9 @@ impl UpgradeConfig {
             upgrade_installer_bytes,
             wasm_costs,
             activation_point,
+            max_call_depth,
+            wasm_features,
+            refund_ratio,
         }
     }
 
@@ -113,4 +123,16 @@ impl UpgradeConfig {
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
+
+    pub fn max_call_depth(&self) -> Option<u32> {
+        self.max_call_depth
+    }
+
+    pub fn wasm_features(&self) -> Option<WasmFeatures> {
+        self.wasm_features
+    }
+
+    pub fn refund_ratio(&self) -> Option<u8> {
+        self.refund_ratio
+    }
 }
@@ -1,21 +1,16 @@
-use types::{account::PublicKey, U512};
+use types::{
+    account::PublicKey, bytesrepr::ToBytes, Key, BLAKE2B_DIGEST_LENGTH, KEY_LOCAL_SEED_LENGTH, U512,
+};
 
 /// In PoS, the validators are stored under named keys with names formatted as
-/// "v_<hex-formatted-PublicKey>_<bond-amount>".  This function attempts to parse such a string back
-/// into the `PublicKey` and bond amount.
+/// "v_<tagged-hex-formatted-PublicKey>_<bond-amount>".  This function attempts to parse such a
+/// string back into the `PublicKey` and bond amount.
 pub fn pos_validator_key_name_to_tuple(pos_key_name: &str) -> Option<(PublicKey, U512)> {
     let mut split_bond = pos_key_name.split('_'); // expected format is "v_{public_key}_{bond}".
     if Some("v") != split_bond.next() {
         None
     } else {
-        let hex_key: &str = split_bond.next()?;
-        if hex_key.len() != 64 {
-            return None;
-        }
-        let mut key_bytes = [0u8; 32];
-        let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes).ok()?;
-        debug_assert!(_bytes_written == key_bytes.len());
-        let pub_key = PublicKey::ed25519_from(key_bytes);
+        let pub_key = PublicKey::from_tagged_hex(split_bond.next()?)?;
         let balance = split_bond.next().and_then(|b| {
             if b.is_empty() {
                 None
@@ -27,60 +22,170 @@ pub fn pos_validator_key_name_to_tuple(pos_key_name: &str) -> Option<(PublicKey,
     }
 }
 
+/// In PoS, delegations are stored under named keys with names formatted as
+/// "d_<tagged-hex-formatted-delegator>_<tagged-hex-formatted-validator>_<amount>".  This function
+/// attempts to parse such a string back into the delegator's and validator's `PublicKey`s and the
+/// delegated amount.
+pub fn pos_delegation_key_name_to_tuple(
+    pos_key_name: &str,
+) -> Option<(PublicKey, PublicKey, U512)> {
+    // expected format is "d_{delegator}_{validator}_{amount}".
+    let mut split_name = pos_key_name.split('_');
+    if Some("d") != split_name.next() {
+        return None;
+    }
+    let delegator = PublicKey::from_tagged_hex(split_name.next()?)?;
+    let validator = PublicKey::from_tagged_hex(split_name.next()?)?;
+    let amount = split_name.next().and_then(|a| {
+        if a.is_empty() {
+            None
+        } else {
+            U512::from_dec_str(a).ok()
+        }
+    })?;
+    Some((delegator, validator, amount))
+}
+
+/// Returns the serialized prefix shared by all local keys with the given seed, i.e. by every entry
+/// of a single contract's local state.
+pub fn local_key_prefix(seed: [u8; KEY_LOCAL_SEED_LENGTH]) -> Vec<u8> {
+    let mut key_bytes = Key::local(seed, &[])
+        .to_bytes()
+        .expect("should serialize local key");
+    key_bytes.truncate(key_bytes.len() - BLAKE2B_DIGEST_LENGTH);
+    key_bytes
+}
+
+/// Returns the prefix which the serialized form of every key of the same variant as `key` starts
+/// with.
+pub fn key_variant_prefix(key: Key) -> Vec<u8> {
+    let mut key_bytes = key.to_bytes().expect("should serialize key");
+    key_bytes.truncate(1);
+    key_bytes
+}
+
+/// The seed of the local state partition holding the purse registry of every account.  It is not
+/// the seed of any account or contract, so the registry can't be written to by Wasm.
+pub const PURSE_REGISTRY_SEED: [u8; KEY_LOCAL_SEED_LENGTH] = [254; KEY_LOCAL_SEED_LENGTH];
+
+/// Returns the key under which the purses created by the account at `public_key` are listed.
+pub fn purse_registry_key(public_key: PublicKey) -> Key {
+    let key_bytes = public_key.to_bytes().expect("should serialize public key");
+    Key::local(PURSE_REGISTRY_SEED, &key_bytes)
+}
+
 #[cfg(test)]
 mod tests {
-    use hex_fmt::HexFmt;
-
     use types::{account::PublicKey, U512};
 
-    use super::pos_validator_key_name_to_tuple;
+    use super::{pos_delegation_key_name_to_tuple, pos_validator_key_name_to_tuple};
 
     #[test]
     fn should_parse_string_to_validator_tuple() {
-        let public_key = PublicKey::ed25519_from([1u8; 32]);
         let stake = U512::from(100);
-        let named_key_name = format!("v_{}_{}", HexFmt(&public_key.as_bytes()), stake);
-
-        let parsed = pos_validator_key_name_to_tuple(&named_key_name);
-        assert!(parsed.is_some());
-        let (parsed_public_key, parsed_stake) = parsed.unwrap();
-        assert_eq!(parsed_public_key, public_key);
-        assert_eq!(parsed_stake, stake);
+        for public_key in &[
+            PublicKey::ed25519_from([1u8; 32]),
+            PublicKey::secp256k1_from([2u8; 33]),
+        ] {
+            let named_key_name = format!("v_{}_{}", public_key.to_tagged_hex(), stake);
+
+            let parsed = pos_validator_key_name_to_tuple(&named_key_name);
+            assert_eq!(parsed, Some((*public_key, stake)));
+        }
     }
 
     #[test]
     fn should_not_parse_string_to_validator_tuple() {
         let public_key = PublicKey::ed25519_from([1u8; 32]);
+        let hex_key = public_key.to_tagged_hex();
         let stake = U512::from(100);
 
-        let bad_prefix = format!("a_{}_{}", HexFmt(&public_key.as_bytes()), stake);
+        let bad_prefix = format!("a_{}_{}", hex_key, stake);
         assert!(pos_validator_key_name_to_tuple(&bad_prefix).is_none());
 
-        let no_prefix = format!("_{}_{}", HexFmt(&public_key.as_bytes()), stake);
+        let no_prefix = format!("_{}_{}", hex_key, stake);
         assert!(pos_validator_key_name_to_tuple(&no_prefix).is_none());
 
-        let short_key = format!("v_{}_{}", HexFmt(&[1u8; 31]), stake);
+        let untagged_key = format!("v_{}_{}", "01".repeat(32), stake);
+        assert!(pos_validator_key_name_to_tuple(&untagged_key).is_none());
+
+        let short_key = format!("v_00{}_{}", "01".repeat(31), stake);
         assert!(pos_validator_key_name_to_tuple(&short_key).is_none());
 
-        let long_key = format!("v_{}00_{}", HexFmt(&public_key.as_bytes()), stake);
+        let long_key = format!("v_{}00_{}", hex_key, stake);
         assert!(pos_validator_key_name_to_tuple(&long_key).is_none());
 
-        let bad_key = format!("v_{}0g_{}", HexFmt(&[1u8; 31]), stake);
+        let bad_key = format!("v_00{}0g_{}", "01".repeat(31), stake);
         assert!(pos_validator_key_name_to_tuple(&bad_key).is_none());
 
+        let unknown_tag = format!("v_02{}_{}", "01".repeat(32), stake);
+        assert!(pos_validator_key_name_to_tuple(&unknown_tag).is_none());
+
         let no_key = format!("v__{}", stake);
         assert!(pos_validator_key_name_to_tuple(&no_key).is_none());
 
         let no_key = format!("v_{}", stake);
         assert!(pos_validator_key_name_to_tuple(&no_key).is_none());
 
-        let bad_stake = format!("v_{}_a", HexFmt(&public_key.as_bytes()));
+        let bad_stake = format!("v_{}_a", hex_key);
         assert!(pos_validator_key_name_to_tuple(&bad_stake).is_none());
 
-        let no_stake = format!("v_{}_", HexFmt(&public_key.as_bytes()));
+        let no_stake = format!("v_{}_", hex_key);
         assert!(pos_validator_key_name_to_tuple(&no_stake).is_none());
 
-        let no_stake = format!("v_{}", HexFmt(&public_key.as_bytes()));
+        let no_stake = format!("v_{}", hex_key);
         assert!(pos_validator_key_name_to_tuple(&no_stake).is_none());
     }
+
+    #[test]
+    fn should_parse_string_to_delegation_tuple() {
+        let delegator = PublicKey::secp256k1_from([3u8; 33]);
+        let validator = PublicKey::ed25519_from([2u8; 32]);
+        let amount = U512::from(100);
+        let named_key_name = format!(
+            "d_{}_{}_{}",
+            delegator.to_tagged_hex(),
+            validator.to_tagged_hex(),
+            amount
+        );
+
+        let parsed = pos_delegation_key_name_to_tuple(&named_key_name);
+        assert_eq!(parsed, Some((delegator, validator, amount)));
+    }
+
+    #[test]
+    fn should_not_parse_string_to_delegation_tuple() {
+        let delegator = PublicKey::ed25519_from([1u8; 32]);
+        let validator = PublicKey::ed25519_from([2u8; 32]);
+        let amount = U512::from(100);
+
+        let validator_name = format!("v_{}_{}", validator.to_tagged_hex(), amount);
+        assert!(pos_delegation_key_name_to_tuple(&validator_name).is_none());
+
+        let no_validator = format!("d_{}_{}", delegator.to_tagged_hex(), amount);
+        assert!(pos_delegation_key_name_to_tuple(&no_validator).is_none());
+
+        let short_key = format!(
+            "d_00{}_{}_{}",
+            "01".repeat(31),
+            validator.to_tagged_hex(),
+            amount
+        );
+        assert!(pos_delegation_key_name_to_tuple(&short_key).is_none());
+
+        let untagged_key = format!(
+            "d_{}_{}_{}",
+            "01".repeat(32),
+            validator.to_tagged_hex(),
+            amount
+        );
+        assert!(pos_delegation_key_name_to_tuple(&untagged_key).is_none());
+
+        let no_amount = format!(
+            "d_{}_{}_",
+            delegator.to_tagged_hex(),
+            validator.to_tagged_hex()
+        );
+        assert!(pos_delegation_key_name_to_tuple(&no_amount).is_none());
+    }
 }
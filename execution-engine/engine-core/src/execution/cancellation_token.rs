@@ -0,0 +1,44 @@
+//! Cooperative cancellation of deploy execution.
+//!
+//! A [`CancellationToken`] is given to an [`Executor`](crate::execution::Executor) via
+//! [`Executor::with_cancellation_token`](crate::execution::Executor::with_cancellation_token).
+//! Cancelling it from any thread stops the Wasm being executed at its next host function call,
+//! which includes the gas charges injected into each block of code.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A shared flag which aborts execution once set.  Clones refer to the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Requests that execution using this token stops as soon as possible.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_share_cancellation_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+}
@@ -0,0 +1,234 @@
+//! Hooks for stepping through the execution of a deploy.
+//!
+//! A [`Debugger`] is given to an [`Executor`](crate::execution::Executor) via
+//! [`Executor::with_debugger`](crate::execution::Executor::with_debugger), and is called
+//! synchronously before every step the executed Wasm takes.  Execution is paused for as long as the
+//! debugger takes to return, so an interactive front end can simply block in [`Debugger::step`]
+//! until the user asks to continue.
+
+use std::collections::BTreeSet;
+
+use wasmi::RuntimeValue;
+
+use engine_shared::gas::Gas;
+use types::Key;
+
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+/// A step of execution about to be taken.
+#[derive(Debug)]
+pub enum Step<'a> {
+    /// A basic block of Wasm is about to run, at the cost of `gas`.
+    BasicBlock { gas: Gas },
+    /// A host function is about to be called.
+    HostCall {
+        function: FunctionIndex,
+        args: &'a [RuntimeValue],
+    },
+    /// A value in global state is about to be read.
+    Read(Key),
+    /// A value in global state is about to be written.
+    Write(Key),
+    /// A value in global state is about to be added to.
+    Add(Key),
+    /// A value in global state is about to be removed.
+    Delete(Key),
+}
+
+impl<'a> Step<'a> {
+    /// The key the step accesses, if any.
+    pub fn key(&self) -> Option<&Key> {
+        match self {
+            Step::Read(key) | Step::Write(key) | Step::Add(key) | Step::Delete(key) => Some(key),
+            Step::BasicBlock { .. } | Step::HostCall { .. } => None,
+        }
+    }
+}
+
+/// What execution should do after a step has been reported.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DebugAction {
+    Continue,
+    /// Stops execution with [`Error::DebuggerAbort`](crate::execution::Error::DebuggerAbort).
+    Abort,
+}
+
+pub trait Debugger {
+    /// Called before each step.  Execution doesn't proceed until this returns.
+    fn step(&mut self, step: &Step) -> DebugAction;
+}
+
+/// Where [`Stepper`] next pauses.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StepMode {
+    /// Pause before every basic block, host call and global state access.
+    BasicBlock,
+    /// Pause before every host call and global state access.
+    HostCall,
+    /// Pause only at breakpoints.
+    Continue,
+}
+
+/// What [`Stepper`]'s callback wants done next.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Run to the next step at the given granularity, or to the next breakpoint.
+    Resume(StepMode),
+    Abort,
+}
+
+/// The places [`Stepper`] pauses regardless of its [`StepMode`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Breakpoints {
+    keys: BTreeSet<Key>,
+    functions: Vec<FunctionIndex>,
+}
+
+impl Breakpoints {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Breaks before any access to `key`.
+    pub fn with_key(mut self, key: Key) -> Self {
+        self.keys.insert(key);
+        self
+    }
+
+    /// Breaks before any call to `function`.
+    pub fn with_function(mut self, function: FunctionIndex) -> Self {
+        if !self.functions.contains(&function) {
+            self.functions.push(function);
+        }
+        self
+    }
+
+    pub fn is_hit(&self, step: &Step) -> bool {
+        match step {
+            Step::HostCall { function, .. } => self.functions.contains(function),
+            step => step.key().map_or(false, |key| self.keys.contains(key)),
+        }
+    }
+}
+
+/// A [`Debugger`] which pauses at a chosen granularity or at breakpoints, handing each paused step
+/// to a callback which decides how to proceed.
+pub struct Stepper<F> {
+    mode: StepMode,
+    breakpoints: Breakpoints,
+    on_pause: F,
+}
+
+impl<F> Stepper<F>
+where
+    F: FnMut(&Step) -> Command,
+{
+    pub fn new(mode: StepMode, breakpoints: Breakpoints, on_pause: F) -> Self {
+        Stepper {
+            mode,
+            breakpoints,
+            on_pause,
+        }
+    }
+
+    fn should_pause(&self, step: &Step) -> bool {
+        match (self.mode, step) {
+            (StepMode::BasicBlock, _) => true,
+            (StepMode::HostCall, Step::BasicBlock { .. }) => false,
+            (StepMode::HostCall, _) => true,
+            (StepMode::Continue, _) => false,
+        }
+    }
+}
+
+impl<F> Debugger for Stepper<F>
+where
+    F: FnMut(&Step) -> Command,
+{
+    fn step(&mut self, step: &Step) -> DebugAction {
+        if !self.should_pause(step) && !self.breakpoints.is_hit(step) {
+            return DebugAction::Continue;
+        }
+        match (self.on_pause)(step) {
+            Command::Resume(mode) => {
+                self.mode = mode;
+                DebugAction::Continue
+            }
+            Command::Abort => DebugAction::Abort,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use types::U512;
+
+    use super::*;
+
+    fn steps() -> Vec<Step<'static>> {
+        vec![
+            Step::BasicBlock {
+                gas: Gas::new(U512::from(1)),
+            },
+            Step::HostCall {
+                function: FunctionIndex::WriteFuncIndex,
+                args: &[],
+            },
+            Step::Write(Key::Hash([1; 32])),
+            Step::BasicBlock {
+                gas: Gas::new(U512::from(1)),
+            },
+            Step::Read(Key::Hash([2; 32])),
+        ]
+    }
+
+    fn paused_at(mode: StepMode, breakpoints: Breakpoints) -> Vec<usize> {
+        let mut paused = Vec::new();
+        let index = Cell::new(0);
+        {
+            let mut stepper = Stepper::new(mode, breakpoints, |_step: &Step| {
+                paused.push(index.get());
+                Command::Resume(mode)
+            });
+            for step in steps() {
+                assert_eq!(stepper.step(&step), DebugAction::Continue);
+                index.set(index.get() + 1);
+            }
+        }
+        paused
+    }
+
+    #[test]
+    fn should_pause_at_every_step_in_basic_block_mode() {
+        assert_eq!(
+            paused_at(StepMode::BasicBlock, Breakpoints::new()),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn should_skip_basic_blocks_in_host_call_mode() {
+        assert_eq!(
+            paused_at(StepMode::HostCall, Breakpoints::new()),
+            vec![1, 2, 4]
+        );
+    }
+
+    #[test]
+    fn should_pause_only_at_breakpoints_when_continuing() {
+        let breakpoints = Breakpoints::new()
+            .with_function(FunctionIndex::WriteFuncIndex)
+            .with_key(Key::Hash([2; 32]));
+        assert_eq!(paused_at(StepMode::Continue, breakpoints), vec![1, 4]);
+    }
+
+    #[test]
+    fn should_abort_when_asked() {
+        let mut stepper = Stepper::new(StepMode::BasicBlock, Breakpoints::new(), |_step: &Step| {
+            Command::Abort
+        });
+        assert_eq!(stepper.step(&steps()[0]), DebugAction::Abort);
+    }
+}
@@ -1,10 +1,11 @@
 use failure::Fail;
 use parity_wasm::elements;
 
-use engine_shared::TypeMismatch;
+use engine_shared::{transform, TypeMismatch};
 use types::{
     account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
-    bytesrepr, system_contract_errors, AccessRights, ApiError, CLValueError, Key, URef,
+    bytesrepr, system_contract_errors, AccessRights, ApiError, CLValueError, Key, NamedArgsError,
+    URef,
 };
 
 use crate::resolvers::error::ResolverError;
@@ -73,6 +74,35 @@ pub enum Error {
     HostBufferEmpty,
     #[fail(display = "Unsupported WASM start")]
     UnsupportedWasmStart,
+    #[fail(display = "Call depth exceeded the limit of {}", _0)]
+    CallDepthExceeded(u32),
+    #[fail(display = "Reentrant call into {}", _0)]
+    Reentrancy(Key),
+    #[fail(display = "Execution exceeded the time limit of {}ms", _0)]
+    ExecutionTimeout(u128),
+    #[fail(display = "Execution aborted by debugger")]
+    DebuggerAbort,
+    #[fail(display = "Execution cancelled")]
+    Cancelled,
+    #[fail(display = "Invalid named args: {}", _0)]
+    NamedArgs(NamedArgsError),
+    #[fail(display = "Both positional and named args were given")]
+    PositionalAndNamedArgs,
+    #[fail(display = "Caller is not allowed to call entry point {}", _0)]
+    EntryPointAccessDenied(String),
+    #[fail(
+        display = "Payment code may only transfer into the payment purse and add named keys to its account"
+    )]
+    PaymentSandboxViolation,
+    #[fail(display = "Writing {} named keys exceeds the limit of {}", count, max)]
+    TooManyNamedKeys { count: usize, max: usize },
+    #[fail(
+        display = "Call to {} is not permitted during payment finalization",
+        _0
+    )]
+    FinalizationCallDenied(String),
+    #[fail(display = "Execution exceeded the host call budget of {}", _0)]
+    HostCallBudgetExceeded(u32),
 }
 
 impl wasmi::HostError for Error {}
@@ -107,6 +137,21 @@ impl From<bytesrepr::Error> for Error {
     }
 }
 
+impl From<transform::Error> for Error {
+    fn from(error: transform::Error) -> Self {
+        match error {
+            transform::Error::Serialization(error) => Error::BytesRepr(error),
+            transform::Error::TypeMismatch(type_mismatch) => Error::TypeMismatch(type_mismatch),
+        }
+    }
+}
+
+impl From<NamedArgsError> for Error {
+    fn from(e: NamedArgsError) -> Self {
+        Error::NamedArgs(e)
+    }
+}
+
 impl From<elements::Error> for Error {
     fn from(e: elements::Error) -> Self {
         Error::ParityWasm(e)
@@ -5,7 +5,6 @@ use std::{
 };
 
 use parity_wasm::elements::Module;
-use wasmi::ModuleRef;
 
 use engine_shared::{
     account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
@@ -14,17 +13,21 @@ use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::PublicKey,
     bytesrepr::{self, FromBytes},
-    BlockTime, CLTyped, CLValue, Key, Phase, ProtocolVersion,
+    BlockTime, CLTyped, CLValue, Key, NamedArgs, Phase, ProtocolVersion, RuntimeArgs, U512,
 };
 
 use crate::{
     engine_state::{
         execution_result::ExecutionResult, system_contract_cache::SystemContractCache, EngineConfig,
     },
-    execution::{address_generator::AddressGenerator, Error, FN_STORE_ID_INITIAL},
-    runtime::{extract_access_rights_from_keys, instance_and_memory, Runtime},
+    execution::{
+        address_generator::AddressGenerator, CancellationToken, Debugger, Error,
+        FN_STORE_ID_INITIAL,
+    },
+    runtime::{extract_access_rights_from_keys, Runtime, PROOF_OF_STAKE_FINALIZATION_METHODS},
     runtime_context::{self, RuntimeContext},
     tracking_copy::TrackingCopy,
+    wasm_backend::{self, Instance, WasmInstance},
 };
 
 macro_rules! on_fail_charge {
@@ -48,11 +51,17 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: Default::default(),
                     cost: $cost,
+                    trace: None,
+                    cache_stats: Default::default(),
+                    peak_memory_pages: 0,
                 };
             }
         }
     };
     ($fn:expr, $cost:expr, $effect:expr) => {
+        on_fail_charge!($fn, $cost, $effect, None, 0)
+    };
+    ($fn:expr, $cost:expr, $effect:expr, $trace:expr, $peak_memory_pages:expr) => {
         match $fn {
             Ok(res) => res,
             Err(e) => {
@@ -62,6 +71,9 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: $effect,
                     cost: $cost,
+                    trace: $trace,
+                    cache_stats: Default::default(),
+                    peak_memory_pages: $peak_memory_pages,
                 };
             }
         }
@@ -70,12 +82,43 @@ macro_rules! on_fail_charge {
 
 pub struct Executor {
     config: EngineConfig,
+    debugger: Option<Rc<RefCell<dyn Debugger>>>,
+    cancellation_token: Option<CancellationToken>,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            debugger: None,
+            cancellation_token: None,
+        }
+    }
+
+    /// Stops the execution of deploys with [`Error::Cancelled`] once `cancellation_token` is
+    /// cancelled.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    /// Whether the cancellation token given to this executor, if any, has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token
+            .as_ref()
+            .map_or(false, CancellationToken::is_cancelled)
+    }
+
+    /// Executes deploys one step at a time under the control of `debugger`.
+    ///
+    /// Passing the resulting executor to
+    /// [`EngineState::deploy`](crate::engine_state::EngineState::deploy) runs the deploy against a
+    /// tracking copy forked from the given prestate, so nothing is written to global state unless
+    /// the returned effects are committed.
+    pub fn with_debugger(mut self, debugger: Rc<RefCell<dyn Debugger>>) -> Self {
+        self.debugger = Some(debugger);
+        self
     }
 
     pub fn config(&self) -> EngineConfig {
@@ -86,10 +129,12 @@ impl Executor {
         &self,
         parity_module: Module,
         args: Vec<u8>,
+        named_args: Vec<u8>,
         base_key: Key,
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         protocol_version: ProtocolVersion,
@@ -103,19 +148,25 @@ impl Executor {
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
     {
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
+        let (instance, memory) = on_fail_charge!(wasm_backend::instantiate(
+            parity_module.clone(),
+            protocol_version,
+            protocol_data.wasm_costs().max_memory_pages,
+        ));
 
         let mut named_keys = account.named_keys().clone();
 
-        let access_rights =
-            {
-                let mut keys: Vec<Key> = named_keys.values().cloned().collect();
-                keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
-                    Key::from(runtime_context::attenuate_uref_for_account(account, uref))
-                }));
-                extract_access_rights_from_keys(keys)
-            };
+        let access_rights = {
+            let mut keys: Vec<Key> = named_keys.values().cloned().collect();
+            keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
+                Key::from(runtime_context::attenuate_uref_for_account(
+                    account,
+                    protocol_data.system_account(),
+                    uref,
+                ))
+            }));
+            extract_access_rights_from_keys(keys)
+        };
 
         let address_generator = AddressGenerator::new(&deploy_hash, phase);
         let gas_counter: Gas = Gas::default();
@@ -124,15 +175,18 @@ impl Executor {
         // only nonce update can be returned.
         let effects_snapshot = tc.borrow().effect();
 
-        let args: Vec<CLValue> = if args.is_empty() {
-            Vec::new()
+        let args: RuntimeArgs = if args.is_empty() && named_args.is_empty() {
+            RuntimeArgs::default()
         } else {
             // TODO: figure out how this works with the cost model
             // https://casperlabs.atlassian.net/browse/EE-239
-            let gas = Gas::new(args.len().into());
-            on_fail_charge!(bytesrepr::deserialize(args), gas, effects_snapshot)
+            let gas = Gas::new((args.len() + named_args.len()).into());
+            on_fail_charge!(parse_args(args, named_args), gas, effects_snapshot)
         };
 
+        // Global state accessed while setting up the execution is not charged to the contract.
+        tc.borrow_mut().take_storage_usage();
+
         let context = RuntimeContext::new(
             tc,
             &mut named_keys,
@@ -142,6 +196,7 @@ impl Executor {
             &account,
             base_key,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -159,9 +214,12 @@ impl Executor {
             memory,
             parity_module,
             context,
-        );
+        )
+        .with_debugger(self.debugger.clone())
+        .with_cancellation_token(self.cancellation_token.clone());
 
         if !self.config.use_system_contracts() {
+            let args: Vec<CLValue> = args.values().cloned().collect();
             if runtime.is_mint(base_key) {
                 match runtime.call_host_mint(
                     protocol_version,
@@ -173,6 +231,9 @@ impl Executor {
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
+                            trace: runtime.take_trace(),
+                            cache_stats: Default::default(),
+                            peak_memory_pages: runtime.peak_memory_pages(),
                         }
                     }
                     Err(error) => {
@@ -180,6 +241,9 @@ impl Executor {
                             error: error.into(),
                             effect: effects_snapshot,
                             cost: runtime.context().gas_counter(),
+                            trace: runtime.take_trace(),
+                            cache_stats: Default::default(),
+                            peak_memory_pages: runtime.peak_memory_pages(),
                         }
                     }
                 }
@@ -194,6 +258,9 @@ impl Executor {
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
+                            trace: runtime.take_trace(),
+                            cache_stats: Default::default(),
+                            peak_memory_pages: runtime.peak_memory_pages(),
                         }
                     }
                     Err(error) => {
@@ -201,21 +268,249 @@ impl Executor {
                             error: error.into(),
                             effect: effects_snapshot,
                             cost: runtime.context().gas_counter(),
+                            trace: runtime.take_trace(),
+                            cache_stats: Default::default(),
+                            peak_memory_pages: runtime.peak_memory_pages(),
                         }
                     }
                 }
             }
         }
 
+        let result = instance.invoke_export("call", &mut runtime);
+        // Memory may have grown since the last host function call, and memory never shrinks
+        runtime.record_memory_pages();
         on_fail_charge!(
-            instance.invoke_export("call", &[], &mut runtime),
+            result,
             runtime.context().gas_counter(),
-            effects_snapshot
+            effects_snapshot,
+            runtime.take_trace(),
+            runtime.peak_memory_pages()
         );
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
             cost: runtime.context().gas_counter(),
+            trace: runtime.take_trace(),
+            cache_stats: Default::default(),
+            peak_memory_pages: runtime.peak_memory_pages(),
+        }
+    }
+
+    /// Transfers `amount` motes from the main purse of `account` to the account under `target`,
+    /// creating it if it doesn't exist, without invoking any wasm.  `parity_module` only backs the
+    /// runtime; the transfer is charged the fixed `cost` whatever the mint calls it makes use of
+    /// `gas_limit`.
+    pub fn exec_native_transfer<R>(
+        &self,
+        parity_module: Module,
+        target: PublicKey,
+        amount: U512,
+        base_key: Key,
+        account: &Account,
+        authorized_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        block_height: u64,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        cost: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tc: Rc<RefCell<TrackingCopy<R>>>,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        let (_instance, memory) = on_fail_charge!(wasm_backend::instantiate(
+            parity_module.clone(),
+            protocol_version,
+            protocol_data.wasm_costs().max_memory_pages,
+        ));
+
+        let mut named_keys = account.named_keys().clone();
+
+        let access_rights = {
+            let mut keys: Vec<Key> = named_keys.values().cloned().collect();
+            keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
+                Key::from(runtime_context::attenuate_uref_for_account(
+                    account,
+                    protocol_data.system_account(),
+                    uref,
+                ))
+            }));
+            extract_access_rights_from_keys(keys)
+        };
+
+        let address_generator = AddressGenerator::new(&deploy_hash, Phase::Session);
+
+        // Snapshot of effects before execution, so in case of error
+        // only nonce update can be returned.
+        let effects_snapshot = tc.borrow().effect();
+
+        // Global state accessed while setting up the execution is not charged to the transfer.
+        tc.borrow_mut().take_storage_usage();
+
+        let context = RuntimeContext::new(
+            tc,
+            &mut named_keys,
+            access_rights,
+            RuntimeArgs::default(),
+            authorized_keys,
+            &account,
+            base_key,
+            blocktime,
+            block_height,
+            deploy_hash,
+            gas_limit,
+            Gas::default(),
+            FN_STORE_ID_INITIAL,
+            Rc::new(RefCell::new(address_generator)),
+            protocol_version,
+            correlation_id,
+            Phase::Session,
+            protocol_data,
+        );
+
+        let mut runtime = Runtime::new(
+            self.config,
+            system_contract_cache,
+            memory,
+            parity_module,
+            context,
+        )
+        .with_debugger(self.debugger.clone())
+        .with_cancellation_token(self.cancellation_token.clone());
+
+        let error = match runtime.transfer_to_account(target, amount) {
+            Ok(Ok(_)) => {
+                return ExecutionResult::Success {
+                    effect: runtime.context().effect(),
+                    cost,
+                    trace: runtime.take_trace(),
+                    cache_stats: Default::default(),
+                    peak_memory_pages: runtime.peak_memory_pages(),
+                }
+            }
+            Ok(Err(api_error)) => Error::Revert(api_error),
+            Err(error) => error,
+        };
+        log::warn!("Execution failed: {:?}", error);
+        ExecutionResult::Failure {
+            error: error.into(),
+            effect: effects_snapshot,
+            cost,
+            trace: runtime.take_trace(),
+            cache_stats: Default::default(),
+            peak_memory_pages: runtime.peak_memory_pages(),
+        }
+    }
+
+    /// Calls the contract under `contract_key` with `args` as `account`, without invoking any wasm
+    /// of its own.  `parity_module` only backs the runtime; the call is limited to `gas_limit`,
+    /// which the account paid for when scheduling it.
+    pub fn exec_scheduled_call<R>(
+        &self,
+        parity_module: Module,
+        contract_key: Key,
+        args: Vec<u8>,
+        account: &Account,
+        authorized_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        block_height: u64,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tc: Rc<RefCell<TrackingCopy<R>>>,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        let (_instance, memory) = on_fail_charge!(wasm_backend::instantiate(
+            parity_module.clone(),
+            protocol_version,
+            protocol_data.wasm_costs().max_memory_pages,
+        ));
+
+        let mut named_keys = account.named_keys().clone();
+
+        let access_rights = {
+            let mut keys: Vec<Key> = named_keys.values().cloned().collect();
+            keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
+                Key::from(runtime_context::attenuate_uref_for_account(
+                    account,
+                    protocol_data.system_account(),
+                    uref,
+                ))
+            }));
+            extract_access_rights_from_keys(keys)
+        };
+
+        let address_generator = AddressGenerator::new(&deploy_hash, Phase::Session);
+
+        // Snapshot of effects before execution, so in case of error
+        // only nonce update can be returned.
+        let effects_snapshot = tc.borrow().effect();
+
+        // Global state accessed while setting up the execution is not charged to the call.
+        tc.borrow_mut().take_storage_usage();
+
+        let context = RuntimeContext::new(
+            tc,
+            &mut named_keys,
+            access_rights,
+            RuntimeArgs::default(),
+            authorized_keys,
+            &account,
+            Key::Account(account.public_key()),
+            blocktime,
+            block_height,
+            deploy_hash,
+            gas_limit,
+            Gas::default(),
+            FN_STORE_ID_INITIAL,
+            Rc::new(RefCell::new(address_generator)),
+            protocol_version,
+            correlation_id,
+            Phase::Session,
+            protocol_data,
+        );
+
+        let mut runtime = Runtime::new(
+            self.config,
+            system_contract_cache,
+            memory,
+            parity_module,
+            context,
+        )
+        .with_debugger(self.debugger.clone())
+        .with_cancellation_token(self.cancellation_token.clone());
+
+        match runtime.call_contract(contract_key, args) {
+            Ok(_) => ExecutionResult::Success {
+                effect: runtime.context().effect(),
+                cost: runtime.context().gas_counter(),
+                trace: runtime.take_trace(),
+                cache_stats: Default::default(),
+                peak_memory_pages: runtime.peak_memory_pages(),
+            },
+            Err(error) => {
+                log::warn!("Scheduled call failed: {:?}", error);
+                ExecutionResult::Failure {
+                    error: error.into(),
+                    effect: effects_snapshot,
+                    cost: runtime.context().gas_counter(),
+                    trace: runtime.take_trace(),
+                    cache_stats: Default::default(),
+                    peak_memory_pages: runtime.peak_memory_pages(),
+                }
+            }
         }
     }
 
@@ -228,6 +523,7 @@ impl Executor {
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         protocol_version: ProtocolVersion,
@@ -246,14 +542,17 @@ impl Executor {
         }
 
         let mut named_keys = named_keys.clone();
-        let access_rights =
-            {
-                let mut keys: Vec<Key> = named_keys.values().cloned().collect();
-                keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
-                    Key::from(runtime_context::attenuate_uref_for_account(account, uref))
-                }));
-                extract_access_rights_from_keys(keys)
-            };
+        let access_rights = {
+            let mut keys: Vec<Key> = named_keys.values().cloned().collect();
+            keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
+                Key::from(runtime_context::attenuate_uref_for_account(
+                    account,
+                    protocol_data.system_account(),
+                    uref,
+                ))
+            }));
+            extract_access_rights_from_keys(keys)
+        };
 
         let address_generator = {
             let address_generator = AddressGenerator::new(&deploy_hash, phase);
@@ -271,16 +570,27 @@ impl Executor {
             let gas = Gas::new(args.len().into());
             on_fail_charge!(bytesrepr::deserialize(args), gas, effects_snapshot)
         };
+        if phase == Phase::FinalizePayment {
+            on_fail_charge!(
+                check_finalization_entry_point(&args),
+                Gas::default(),
+                effects_snapshot
+            );
+        }
+
+        // Global state accessed while setting up the execution is not charged to the contract.
+        state.borrow_mut().take_storage_usage();
 
         let context = RuntimeContext::new(
             state,
             &mut named_keys,
             access_rights,
-            args.clone(),
+            args.clone().into(),
             authorization_keys,
             &account,
             base_key,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -292,8 +602,11 @@ impl Executor {
             protocol_data,
         );
 
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
+        let (instance, memory) = on_fail_charge!(wasm_backend::instantiate(
+            parity_module.clone(),
+            protocol_version,
+            protocol_data.wasm_costs().max_memory_pages,
+        ));
 
         let mut runtime = Runtime::new(
             self.config,
@@ -301,7 +614,9 @@ impl Executor {
             memory,
             parity_module,
             context,
-        );
+        )
+        .with_debugger(self.debugger.clone())
+        .with_cancellation_token(self.cancellation_token.clone());
 
         if !self.config.use_system_contracts() {
             match runtime.call_host_proof_of_stake(
@@ -314,6 +629,9 @@ impl Executor {
                     return ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        trace: runtime.take_trace(),
+                        cache_stats: Default::default(),
+                        peak_memory_pages: runtime.peak_memory_pages(),
                     }
                 }
                 Err(error) => {
@@ -321,51 +639,30 @@ impl Executor {
                         error: error.into(),
                         effect: effects_snapshot,
                         cost: runtime.context().gas_counter(),
+                        trace: runtime.take_trace(),
+                        cache_stats: Default::default(),
+                        peak_memory_pages: runtime.peak_memory_pages(),
                     }
                 }
             }
         }
 
-        let error = match instance.invoke_export("call", &[], &mut runtime) {
-            Err(error) => error,
-            Ok(_) => {
-                return ExecutionResult::Success {
-                    effect: runtime.context().effect(),
-                    cost: runtime.context().gas_counter(),
-                }
-            }
-        };
-
-        if let Some(host_error) = error.as_host_error() {
-            let downcasted_error = host_error.downcast_ref::<Error>().unwrap();
-            match downcasted_error {
-                Error::Ret(ref _ret_urefs) => {
-                    return ExecutionResult::Success {
-                        effect: runtime.context().effect(),
-                        cost: runtime.context().gas_counter(),
-                    };
-                }
-                Error::Revert(status) => {
-                    return ExecutionResult::Failure {
-                        error: Error::Revert(*status).into(),
-                        effect: effects_snapshot,
-                        cost: runtime.context().gas_counter(),
-                    };
-                }
-                error => {
-                    return ExecutionResult::Failure {
-                        error: error.clone().into(),
-                        effect: effects_snapshot,
-                        cost: runtime.context().gas_counter(),
-                    }
-                }
-            }
-        }
-
-        ExecutionResult::Failure {
-            error: Error::Interpreter(error.into()).into(),
-            effect: effects_snapshot,
-            cost: runtime.context().gas_counter(),
+        match instance.invoke_export("call", &mut runtime) {
+            Ok(()) | Err(Error::Ret(_)) => ExecutionResult::Success {
+                effect: runtime.context().effect(),
+                cost: runtime.context().gas_counter(),
+                trace: runtime.take_trace(),
+                cache_stats: Default::default(),
+                peak_memory_pages: runtime.peak_memory_pages(),
+            },
+            Err(error) => ExecutionResult::Failure {
+                error: error.into(),
+                effect: effects_snapshot,
+                cost: runtime.context().gas_counter(),
+                trace: runtime.take_trace(),
+                cache_stats: Default::default(),
+                peak_memory_pages: runtime.peak_memory_pages(),
+            },
         }
     }
 
@@ -378,6 +675,7 @@ impl Executor {
         account: &'a Account,
         authorization_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         address_generator: Rc<RefCell<AddressGenerator>>,
@@ -387,19 +685,22 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
-    ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
+    ) -> Result<(Instance, Runtime<'a, R>), Error>
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
     {
-        let access_rights =
-            {
-                let mut keys: Vec<Key> = keys.values().cloned().collect();
-                keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
-                    Key::from(runtime_context::attenuate_uref_for_account(account, uref))
-                }));
-                extract_access_rights_from_keys(keys)
-            };
+        let access_rights = {
+            let mut keys: Vec<Key> = keys.values().cloned().collect();
+            keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
+                Key::from(runtime_context::attenuate_uref_for_account(
+                    account,
+                    protocol_data.system_account(),
+                    uref,
+                ))
+            }));
+            extract_access_rights_from_keys(keys)
+        };
 
         let args: Vec<CLValue> = if args.is_empty() {
             Vec::new()
@@ -409,15 +710,19 @@ impl Executor {
 
         let gas_counter = Gas::default();
 
+        // Global state accessed while setting up the execution is not charged to the contract.
+        state.borrow_mut().take_storage_usage();
+
         let runtime_context = RuntimeContext::new(
             state,
             keys,
             access_rights,
-            args,
+            args.into(),
             authorization_keys,
             account,
             base_key,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -429,7 +734,11 @@ impl Executor {
             protocol_data,
         );
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
+        let (instance, memory) = wasm_backend::instantiate(
+            module.clone(),
+            protocol_version,
+            protocol_data.wasm_costs().max_memory_pages,
+        )?;
 
         let runtime = Runtime::new(
             self.config,
@@ -437,7 +746,9 @@ impl Executor {
             memory,
             module,
             runtime_context,
-        );
+        )
+        .with_debugger(self.debugger.clone())
+        .with_cancellation_token(self.cancellation_token.clone());
 
         Ok((instance, runtime))
     }
@@ -451,6 +762,7 @@ impl Executor {
         account: &Account,
         authorization_keys: BTreeSet<PublicKey>,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         address_generator: Rc<RefCell<AddressGenerator>>,
@@ -474,6 +786,7 @@ impl Executor {
             account,
             authorization_keys,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             address_generator,
@@ -485,34 +798,78 @@ impl Executor {
             system_contract_cache,
         )?;
 
-        let error: wasmi::Error = match instance.invoke_export("call", &[], &mut runtime) {
-            Err(error) => error,
-            Ok(_) => {
+        let return_value: CLValue = match instance.invoke_export("call", &mut runtime) {
+            Ok(()) => {
                 // This duplicates the behavior of sub_call, but is admittedly rather questionable.
                 //
                 // If `instance.invoke_export` returns `Ok` and the `host_buffer` is `None`, the
                 // contract's execution succeeded but did not explicitly call `runtime::ret()`.
                 // Treat as though the execution returned the unit type `()` as per Rust functions
                 // which don't specify a return value.
-                let result = runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?);
-                let ret = result.into_t()?;
-                return Ok(ret);
+                runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?)
             }
-        };
-
-        let return_value: CLValue = match error
-            .as_host_error()
-            .and_then(|host_error| host_error.downcast_ref::<Error>())
-        {
-            Some(Error::Ret(_)) => runtime
+            Err(Error::Ret(_)) => runtime
                 .take_host_buffer()
                 .ok_or(Error::ExpectedReturnValue)?,
-            Some(Error::Revert(code)) => return Err(Error::Revert(*code)),
-            Some(error) => return Err(error.clone()),
-            _ => return Err(Error::Interpreter(error.into())),
+            Err(error) => return Err(error),
         };
 
         let ret = return_value.into_t()?;
         Ok(ret)
     }
 }
+
+/// Deserializes the positional `args` or the `named_args` of a deploy item, at most one of which
+/// may be given.
+fn parse_args(args: Vec<u8>, named_args: Vec<u8>) -> Result<RuntimeArgs, Error> {
+    if named_args.is_empty() {
+        let args: Vec<CLValue> = bytesrepr::deserialize(args)?;
+        return Ok(args.into());
+    }
+    if !args.is_empty() {
+        return Err(Error::PositionalAndNamedArgs);
+    }
+    let named_args: NamedArgs = bytesrepr::deserialize(named_args)?;
+    Ok(RuntimeArgs::named(named_args)?)
+}
+
+/// Checks that the Proof of Stake contract is called to finalize the payment for a deploy with one
+/// of the entry points designated for it.
+fn check_finalization_entry_point(args: &[CLValue]) -> Result<(), Error> {
+    let method_name: String = match args.first().map(|arg| arg.to_owned().into_t()) {
+        Some(Ok(method_name)) => method_name,
+        _ => {
+            return Err(Error::FinalizationCallDenied(String::from(
+                "proof of stake",
+            )))
+        }
+    };
+    if !PROOF_OF_STAKE_FINALIZATION_METHODS.contains(&method_name.as_str()) {
+        return Err(Error::FinalizationCallDenied(method_name));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use types::CLValue;
+
+    use super::check_finalization_entry_point;
+    use crate::execution::Error;
+
+    #[test]
+    fn should_permit_finalize_payment_entry_point() {
+        let args = vec![CLValue::from_t(String::from("finalize_payment")).unwrap()];
+        assert!(check_finalization_entry_point(&args).is_ok());
+    }
+
+    #[test]
+    fn should_deny_other_entry_points() {
+        let args = vec![CLValue::from_t(String::from("bond")).unwrap()];
+        match check_finalization_entry_point(&args) {
+            Err(Error::FinalizationCallDenied(method_name)) => assert_eq!(method_name, "bond"),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert!(check_finalization_entry_point(&[]).is_err());
+    }
+}
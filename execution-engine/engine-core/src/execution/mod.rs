@@ -1,14 +1,21 @@
 mod address_generator;
+mod cancellation_token;
+mod debugger;
 mod error;
 #[macro_use]
 mod executor;
 #[cfg(test)]
 mod tests;
+mod trace;
 
+pub(crate) use self::trace::args_digest;
 pub use self::{
     address_generator::{AddressGenerator, AddressGeneratorBuilder},
+    cancellation_token::CancellationToken,
+    debugger::{Breakpoints, Command, DebugAction, Debugger, Step, StepMode, Stepper},
     error::Error,
     executor::Executor,
+    trace::{DeployTrace, TraceEntry},
 };
 
 pub const MINT_NAME: &str = "mint";
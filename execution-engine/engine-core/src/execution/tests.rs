@@ -15,6 +15,9 @@ fn on_fail_charge_test_helper<T>(
     ExecutionResult::Success {
         effect: Default::default(),
         cost: success_cost,
+        trace: None,
+        cache_stats: Default::default(),
+        peak_memory_pages: 0,
     }
 }
 
@@ -55,6 +58,9 @@ fn on_fail_charge_with_action() {
         ExecutionResult::Success {
             effect: Default::default(),
             cost: Gas::default(),
+            trace: None,
+            cache_stats: Default::default(),
+            peak_memory_pages: 0,
         }
     };
     match f() {
@@ -0,0 +1,119 @@
+//! An opt-in record of what a deploy did while executing, for debugging contracts.
+//!
+//! Tracing is enabled via [`EngineConfig::with_enable_tracing`](crate::engine_state::EngineConfig)
+//! and the resulting [`DeployTrace`] is attached to the deploy's
+//! [`ExecutionResult`](crate::engine_state::execution_result::ExecutionResult).
+
+use wasmi::RuntimeValue;
+
+use engine_shared::{gas::Gas, newtypes::Blake2bHash};
+use types::Key;
+
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+/// A single step of a deploy's execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceEntry {
+    /// A call to a host function.  The gas includes that charged to any contract it called, whose
+    /// steps follow this entry.  The per-block gas charges injected by the preprocessor are not
+    /// traced.
+    HostCall {
+        function: FunctionIndex,
+        args_digest: Blake2bHash,
+        gas: Gas,
+    },
+    /// A read from global state.
+    Read(Key),
+    /// A write to global state.
+    Write(Key),
+    /// An addition to a value in global state.
+    Add(Key),
+    /// A removal from global state.
+    Delete(Key),
+}
+
+/// The steps of a deploy's execution, in the order they happened.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeployTrace {
+    entries: Vec<TraceEntry>,
+}
+
+impl DeployTrace {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// Appends the steps of a later phase of the same deploy.
+    pub fn extend(&mut self, other: DeployTrace) {
+        self.entries.extend(other.entries)
+    }
+
+    /// Adds `entry`, returning its position so its gas can be filled in later.
+    pub(crate) fn push(&mut self, entry: TraceEntry) -> usize {
+        self.entries.push(entry);
+        self.entries.len() - 1
+    }
+
+    /// Sets the gas of the host call at `position`.
+    pub(crate) fn set_gas(&mut self, position: usize, new_gas: Gas) {
+        if let Some(TraceEntry::HostCall { gas, .. }) = self.entries.get_mut(position) {
+            *gas = new_gas;
+        }
+    }
+}
+
+/// Returns a digest of the values a host function was called with.
+pub(crate) fn args_digest(args: &[RuntimeValue]) -> Blake2bHash {
+    let mut bytes = Vec::with_capacity(args.len() * 8);
+    for arg in args {
+        match arg {
+            RuntimeValue::I32(value) => bytes.extend_from_slice(&value.to_le_bytes()),
+            RuntimeValue::I64(value) => bytes.extend_from_slice(&value.to_le_bytes()),
+            RuntimeValue::F32(value) => bytes.extend_from_slice(&value.to_bits().to_le_bytes()),
+            RuntimeValue::F64(value) => bytes.extend_from_slice(&value.to_bits().to_le_bytes()),
+        }
+    }
+    Blake2bHash::new(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use types::U512;
+
+    use super::*;
+
+    #[test]
+    fn should_set_gas_of_host_call() {
+        let mut trace = DeployTrace::new();
+        let position = trace.push(TraceEntry::HostCall {
+            function: FunctionIndex::WriteFuncIndex,
+            args_digest: args_digest(&[RuntimeValue::I32(1)]),
+            gas: Gas::default(),
+        });
+        trace.push(TraceEntry::Write(Key::Hash([1; 32])));
+        trace.set_gas(position, Gas::new(U512::from(10)));
+
+        match &trace.entries()[0] {
+            TraceEntry::HostCall { gas, .. } => assert_eq!(*gas, Gas::new(U512::from(10))),
+            entry => panic!("unexpected entry {:?}", entry),
+        }
+        assert_eq!(trace.entries()[1], TraceEntry::Write(Key::Hash([1; 32])));
+    }
+
+    #[test]
+    fn args_digest_should_depend_on_values() {
+        let digest = args_digest(&[RuntimeValue::I32(1), RuntimeValue::I32(2)]);
+        assert_eq!(
+            digest,
+            args_digest(&[RuntimeValue::I32(1), RuntimeValue::I32(2)])
+        );
+        assert_ne!(
+            digest,
+            args_digest(&[RuntimeValue::I32(2), RuntimeValue::I32(1)])
+        );
+    }
+}
@@ -6,6 +6,7 @@ pub mod resolvers;
 pub mod runtime;
 pub mod runtime_context;
 pub(crate) mod tracking_copy;
+pub mod wasm_backend;
 
 use std::collections::BTreeMap;
 
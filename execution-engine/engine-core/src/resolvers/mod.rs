@@ -3,7 +3,7 @@ pub mod memory_resolver;
 pub mod v1_function_index;
 mod v1_resolver;
 
-use wasmi::ModuleImportResolver;
+use wasmi::{ModuleImportResolver, Signature};
 
 use types::ProtocolVersion;
 
@@ -13,22 +13,43 @@ use crate::resolvers::memory_resolver::MemoryResolver;
 /// Creates a module resolver for given protocol version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
+/// * `max_memory_pages` Largest memory, in 64 KiB pages, a module may import.
 pub fn create_module_resolver(
     protocol_version: ProtocolVersion,
+    max_memory_pages: u32,
 ) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
     // TODO: revisit how protocol_version check here is meant to combine with upgrade
     if protocol_version >= ProtocolVersion::V1_0_0 {
-        return Ok(v1_resolver::RuntimeModuleImportResolver::default());
+        return Ok(v1_resolver::RuntimeModuleImportResolver::new(
+            max_memory_pages,
+        ));
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
+/// Returns `true` if `name` is a host function which modules may import.  The signature isn't
+/// checked, as that's left to instantiation.
+pub fn is_host_function(name: &str) -> bool {
+    let signature = Signature::new(&[][..], None);
+    v1_resolver::RuntimeModuleImportResolver::new(0)
+        .resolve_func(name, &signature)
+        .is_ok()
+}
+
 #[test]
 fn resolve_invalid_module() {
-    assert!(create_module_resolver(ProtocolVersion::default()).is_err());
+    assert!(create_module_resolver(ProtocolVersion::default(), 64).is_err());
 }
 
 #[test]
 fn protocol_version_1_always_resolves() {
-    assert!(create_module_resolver(ProtocolVersion::V1_0_0).is_ok());
+    assert!(create_module_resolver(ProtocolVersion::V1_0_0, 64).is_ok());
+}
+
+#[test]
+fn should_recognize_host_functions() {
+    assert!(is_host_function("read_value"));
+    assert!(is_host_function("get_account_info"));
+    assert!(!is_host_function("memory"));
+    assert!(!is_host_function("launch_missiles"));
 }
@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
 
-#[derive(Debug, PartialEq, FromPrimitive, ToPrimitive, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, FromPrimitive, ToPrimitive, Clone, Copy)]
 #[repr(usize)]
 pub enum FunctionIndex {
     WriteFuncIndex,
@@ -43,6 +43,39 @@ pub enum FunctionIndex {
     GetMainPurseIndex,
     GetArgSizeFuncIndex,
     ReadHostBufferIndex,
+    GuardReentrancyIndex,
+    DeleteFuncIndex,
+    ListLocalFuncIndex,
+    CanManageKeysIndex,
+    RotateAssociatedKeyFuncIndex,
+    UpdateAssociatedKeysFuncIndex,
+    GetBlockHeightIndex,
+    RandomBytesIndex,
+    Blake2bFuncIndex,
+    VerifyEd25519FuncIndex,
+    VerifySecp256k1FuncIndex,
+    TransferFromPurseToPurseWithProofIndex,
+    BurnIndex,
+    ListPursesIndex,
+    SetContractInstallPermissionIndex,
+    GetNamedArgSizeFuncIndex,
+    GetNamedArgFuncIndex,
+    StoreFnWithAbiIndex,
+    StoreFnAtHashWithAbiIndex,
+    GetContractAbiIndex,
+    StoreVersionedContractIndex,
+    CallVersionedContractIndex,
+    GetImmediateCallerIndex,
+    GetCallStackIndex,
+    BigNumOpIndex,
+    GetAccountInfoIndex,
+    ReadTypedFuncIndex,
+    AttenuateURefIndex,
+    GetChainConfigIndex,
+    GetBalanceOfIndex,
+    BatchTransferIndex,
+    ScheduleCallIndex,
+    GetMinMainPurseBalanceIndex,
     #[cfg(feature = "test-support")]
     PrintIndex,
 }
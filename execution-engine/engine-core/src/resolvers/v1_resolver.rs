@@ -14,11 +14,12 @@ pub struct RuntimeModuleImportResolver {
     max_memory: u32,
 }
 
-impl Default for RuntimeModuleImportResolver {
-    fn default() -> Self {
+impl RuntimeModuleImportResolver {
+    /// Creates a resolver which rejects memories of more than `max_memory` pages.
+    pub fn new(max_memory: u32) -> Self {
         RuntimeModuleImportResolver {
             memory: RefCell::new(None),
-            max_memory: 64,
+            max_memory,
         }
     }
 }
@@ -44,6 +45,10 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
                 FunctionIndex::ReadFuncIndex.into(),
             ),
+            "read_value_typed" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
+                FunctionIndex::ReadTypedFuncIndex.into(),
+            ),
             "read_value_local" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
                 FunctionIndex::ReadLocalFuncIndex.into(),
@@ -80,6 +85,14 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
                 FunctionIndex::GetArgFuncIndex.into(),
             ),
+            "get_named_arg_size" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::GetNamedArgSizeFuncIndex.into(),
+            ),
+            "get_named_arg" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::GetNamedArgFuncIndex.into(),
+            ),
             "ret" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 2][..], None),
                 FunctionIndex::RetFuncIndex.into(),
@@ -112,6 +125,46 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 5][..], None),
                 FunctionIndex::StoreFnAtHashIndex.into(),
             ),
+            "store_function_with_abi" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], None),
+                FunctionIndex::StoreFnWithAbiIndex.into(),
+            ),
+            "store_function_at_hash_with_abi" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], None),
+                FunctionIndex::StoreFnAtHashWithAbiIndex.into(),
+            ),
+            "get_contract_abi" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::GetContractAbiIndex.into(),
+            ),
+            "store_versioned_contract" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], None),
+                FunctionIndex::StoreVersionedContractIndex.into(),
+            ),
+            "call_versioned_contract" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], Some(ValueType::I32)),
+                FunctionIndex::CallVersionedContractIndex.into(),
+            ),
+            "get_immediate_caller" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
+                FunctionIndex::GetImmediateCallerIndex.into(),
+            ),
+            "get_call_stack" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
+                FunctionIndex::GetCallStackIndex.into(),
+            ),
+            "get_account_info" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::GetAccountInfoIndex.into(),
+            ),
+            "attenuate_uref" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::AttenuateURefIndex.into(),
+            ),
+            "get_chain_config" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::GetChainConfigIndex.into(),
+            ),
             "is_valid_uref" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
                 FunctionIndex::IsValidURefFnIndex.into(),
@@ -148,6 +201,26 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 1][..], None),
                 FunctionIndex::GetBlocktimeIndex.into(),
             ),
+            "random_bytes" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::RandomBytesIndex.into(),
+            ),
+            "blake2b" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::Blake2bFuncIndex.into(),
+            ),
+            "verify_ed25519" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::VerifyEd25519FuncIndex.into(),
+            ),
+            "verify_secp256k1" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::VerifySecp256k1FuncIndex.into(),
+            ),
+            "bignum_op" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
+                FunctionIndex::BigNumOpIndex.into(),
+            ),
             "create_purse" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
                 FunctionIndex::CreatePurseIndex.into(),
@@ -164,10 +237,42 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
                 FunctionIndex::TransferFromPurseToPurseIndex.into(),
             ),
+            "batch_transfer" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::BatchTransferIndex.into(),
+            ),
+            "transfer_from_purse_to_purse_with_proof" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
+                FunctionIndex::TransferFromPurseToPurseWithProofIndex.into(),
+            ),
+            "burn" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::BurnIndex.into(),
+            ),
+            "list_purses" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::ListPursesIndex.into(),
+            ),
+            "set_contract_install_permission" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::SetContractInstallPermissionIndex.into(),
+            ),
             "get_balance" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
                 FunctionIndex::GetBalanceIndex.into(),
             ),
+            "schedule_call" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
+                FunctionIndex::ScheduleCallIndex.into(),
+            ),
+            "get_balance_of" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::GetBalanceOfIndex.into(),
+            ),
+            "get_min_main_purse_balance" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
+                FunctionIndex::GetMinMainPurseBalanceIndex.into(),
+            ),
             "get_phase" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 1][..], None),
                 FunctionIndex::GetPhaseIndex.into(),
@@ -188,6 +293,34 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
                 FunctionIndex::ReadHostBufferIndex.into(),
             ),
+            "guard_reentrancy" => FuncInstance::alloc_host(
+                Signature::new(&[][..], None),
+                FunctionIndex::GuardReentrancyIndex.into(),
+            ),
+            "delete" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], None),
+                FunctionIndex::DeleteFuncIndex.into(),
+            ),
+            "list_local" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
+                FunctionIndex::ListLocalFuncIndex.into(),
+            ),
+            "can_manage_keys" => FuncInstance::alloc_host(
+                Signature::new(&[][..], Some(ValueType::I32)),
+                FunctionIndex::CanManageKeysIndex.into(),
+            ),
+            "rotate_associated_key" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::RotateAssociatedKeyFuncIndex.into(),
+            ),
+            "update_associated_keys" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::UpdateAssociatedKeysFuncIndex.into(),
+            ),
+            "get_block_height" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::GetBlockHeightIndex.into(),
+            ),
             #[cfg(feature = "test-support")]
             "print" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 2][..], None),
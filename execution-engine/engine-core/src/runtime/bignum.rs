@@ -0,0 +1,127 @@
+//! Arithmetic on `U256` and `U512` backing the `bignum_op` host function.
+//!
+//! Operands and results are passed as little-endian byte arrays of the width of their type, so
+//! neither side needs to serialize them.
+
+use types::{ApiError, BigNumOp, U256, U512};
+
+/// The width in bytes of a `U256`.
+pub(crate) const U256_WIDTH: usize = 32;
+/// The width in bytes of a `U512`.
+pub(crate) const U512_WIDTH: usize = 64;
+
+macro_rules! apply_op {
+    ($uint:ty, $op:expr, $lhs:expr, $rhs:expr) => {{
+        let lhs = <$uint>::from_little_endian($lhs);
+        let rhs = <$uint>::from_little_endian($rhs);
+        let result = match $op {
+            BigNumOp::CheckedAdd => lhs.checked_add(rhs).ok_or(ApiError::ArithmeticOverflow)?,
+            BigNumOp::CheckedSub => lhs.checked_sub(rhs).ok_or(ApiError::ArithmeticOverflow)?,
+            BigNumOp::CheckedMul => lhs.checked_mul(rhs).ok_or(ApiError::ArithmeticOverflow)?,
+            BigNumOp::CheckedDiv => lhs.checked_div(rhs).ok_or(ApiError::DivisionByZero)?,
+            BigNumOp::CheckedRem => lhs.checked_rem(rhs).ok_or(ApiError::DivisionByZero)?,
+            BigNumOp::SaturatingAdd => lhs.saturating_add(rhs),
+            BigNumOp::SaturatingSub => lhs.saturating_sub(rhs),
+            BigNumOp::SaturatingMul => lhs.saturating_mul(rhs),
+        };
+        let mut output = vec![0u8; $lhs.len()];
+        result.to_little_endian(&mut output);
+        Ok(output)
+    }};
+}
+
+/// Applies `op` to the little-endian operands `lhs` and `rhs`, which must both be
+/// [`U256_WIDTH`] or [`U512_WIDTH`] bytes long, returning the result in the same form.
+pub(crate) fn apply(op: BigNumOp, lhs: &[u8], rhs: &[u8]) -> Result<Vec<u8>, ApiError> {
+    if lhs.len() != rhs.len() {
+        return Err(ApiError::InvalidArgument);
+    }
+    match lhs.len() {
+        U256_WIDTH => apply_op!(U256, op, lhs, rhs),
+        U512_WIDTH => apply_op!(U512, op, lhs, rhs),
+        _ => Err(ApiError::InvalidArgument),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u512_op(op: BigNumOp, lhs: U512, rhs: U512) -> Result<U512, ApiError> {
+        let mut lhs_bytes = [0u8; U512_WIDTH];
+        let mut rhs_bytes = [0u8; U512_WIDTH];
+        lhs.to_little_endian(&mut lhs_bytes);
+        rhs.to_little_endian(&mut rhs_bytes);
+        apply(op, &lhs_bytes, &rhs_bytes).map(|output| U512::from_little_endian(&output))
+    }
+
+    fn u256_op(op: BigNumOp, lhs: U256, rhs: U256) -> Result<U256, ApiError> {
+        let mut lhs_bytes = [0u8; U256_WIDTH];
+        let mut rhs_bytes = [0u8; U256_WIDTH];
+        lhs.to_little_endian(&mut lhs_bytes);
+        rhs.to_little_endian(&mut rhs_bytes);
+        apply(op, &lhs_bytes, &rhs_bytes).map(|output| U256::from_little_endian(&output))
+    }
+
+    #[test]
+    fn should_apply_checked_ops() {
+        let (lhs, rhs) = (U512::from(7), U512::from(2));
+        assert_eq!(u512_op(BigNumOp::CheckedAdd, lhs, rhs), Ok(U512::from(9)));
+        assert_eq!(u512_op(BigNumOp::CheckedSub, lhs, rhs), Ok(U512::from(5)));
+        assert_eq!(u512_op(BigNumOp::CheckedMul, lhs, rhs), Ok(U512::from(14)));
+        assert_eq!(u512_op(BigNumOp::CheckedDiv, lhs, rhs), Ok(U512::from(3)));
+        assert_eq!(u512_op(BigNumOp::CheckedRem, lhs, rhs), Ok(U512::from(1)));
+    }
+
+    #[test]
+    fn should_report_overflow_and_division_by_zero() {
+        assert_eq!(
+            u512_op(BigNumOp::CheckedAdd, U512::max_value(), U512::one()),
+            Err(ApiError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            u256_op(BigNumOp::CheckedSub, U256::zero(), U256::one()),
+            Err(ApiError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            u256_op(BigNumOp::CheckedMul, U256::max_value(), U256::from(2)),
+            Err(ApiError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            u512_op(BigNumOp::CheckedDiv, U512::one(), U512::zero()),
+            Err(ApiError::DivisionByZero)
+        );
+        assert_eq!(
+            u512_op(BigNumOp::CheckedRem, U512::one(), U512::zero()),
+            Err(ApiError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn should_saturate() {
+        assert_eq!(
+            u256_op(BigNumOp::SaturatingAdd, U256::max_value(), U256::one()),
+            Ok(U256::max_value())
+        );
+        assert_eq!(
+            u512_op(BigNumOp::SaturatingSub, U512::zero(), U512::one()),
+            Ok(U512::zero())
+        );
+        assert_eq!(
+            u512_op(BigNumOp::SaturatingMul, U512::max_value(), U512::from(2)),
+            Ok(U512::max_value())
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_widths() {
+        assert_eq!(
+            apply(BigNumOp::CheckedAdd, &[0; 16], &[0; 16]),
+            Err(ApiError::InvalidArgument)
+        );
+        assert_eq!(
+            apply(BigNumOp::CheckedAdd, &[0; U256_WIDTH], &[0; U512_WIDTH]),
+            Err(ApiError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Signature verification backing the `verify_ed25519` and `verify_secp256k1` host functions.
+//!
+//! Malformed signatures and public keys are treated as failing verification rather than as errors,
+//! so a contract only ever learns whether `signature` is a valid signature of `message`.
+
+use engine_shared::newtypes::Blake2bHash;
+
+/// A signature scheme which contracts can verify signatures of.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum SignatureScheme {
+    Ed25519,
+    Secp256k1,
+}
+
+impl SignatureScheme {
+    pub(crate) fn verify(self, signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+        match self {
+            SignatureScheme::Ed25519 => verify_ed25519(signature, message, public_key),
+            SignatureScheme::Secp256k1 => verify_secp256k1(signature, message, public_key),
+        }
+    }
+}
+
+/// Verifies an Ed25519 `signature` of `message` by `public_key`.
+fn verify_ed25519(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+    let signature = match ed25519_dalek::Signature::from_bytes(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let public_key = match ed25519_dalek::PublicKey::from_bytes(public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    public_key.verify(message, &signature).is_ok()
+}
+
+/// Verifies a compact secp256k1 `signature` of the Blake2b-256 digest of `message` by
+/// `public_key`, which may be either compressed or uncompressed.
+fn verify_secp256k1(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+    let signature = match secp256k1::Signature::parse_slice(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let public_key = match secp256k1::PublicKey::parse_slice(public_key, None) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    let digest = secp256k1::Message::parse(&Blake2bHash::new(message).value());
+    secp256k1::verify(&digest, &signature, &public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &[u8] = b"message";
+    const SECRET_KEY: [u8; 32] = [7u8; 32];
+
+    fn ed25519_signature(message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let secret_key = ed25519_dalek::SecretKey::from_bytes(&SECRET_KEY).unwrap();
+        let public_key = ed25519_dalek::PublicKey::from(&secret_key);
+        let signature =
+            ed25519_dalek::ExpandedSecretKey::from(&secret_key).sign(message, &public_key);
+        (
+            signature.to_bytes().to_vec(),
+            public_key.to_bytes().to_vec(),
+        )
+    }
+
+    fn secp256k1_signature(message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let secret_key = secp256k1::SecretKey::parse(&SECRET_KEY).unwrap();
+        let public_key = secp256k1::PublicKey::from_secret_key(&secret_key);
+        let digest = secp256k1::Message::parse(&Blake2bHash::new(message).value());
+        let (signature, _) = secp256k1::sign(&digest, &secret_key).unwrap();
+        (
+            signature.serialize().to_vec(),
+            public_key.serialize_compressed().to_vec(),
+        )
+    }
+
+    #[test]
+    fn should_verify_ed25519_signature() {
+        let (signature, public_key) = ed25519_signature(MESSAGE);
+        assert!(verify_ed25519(&signature, MESSAGE, &public_key));
+        assert!(!verify_ed25519(&signature, b"other message", &public_key));
+    }
+
+    #[test]
+    fn should_reject_malformed_ed25519_input() {
+        let (signature, public_key) = ed25519_signature(MESSAGE);
+        assert!(!verify_ed25519(&signature[1..], MESSAGE, &public_key));
+        assert!(!verify_ed25519(&signature, MESSAGE, &public_key[1..]));
+    }
+
+    #[test]
+    fn should_verify_secp256k1_signature() {
+        let (signature, public_key) = secp256k1_signature(MESSAGE);
+        assert!(verify_secp256k1(&signature, MESSAGE, &public_key));
+        assert!(!verify_secp256k1(&signature, b"other message", &public_key));
+    }
+
+    #[test]
+    fn should_reject_malformed_secp256k1_input() {
+        let (signature, public_key) = secp256k1_signature(MESSAGE);
+        assert!(!verify_secp256k1(&signature[1..], MESSAGE, &public_key));
+        assert!(!verify_secp256k1(&signature, MESSAGE, &public_key[1..]));
+    }
+}
@@ -12,8 +12,12 @@ use types::{
 use engine_shared::{gas::Gas, stored_value::StoredValue};
 use engine_storage::global_state::StateReader;
 
-use super::{args::Args, scoped_timer::ScopedTimer, Error, Runtime};
-use crate::resolvers::v1_function_index::FunctionIndex;
+use super::{args::Args, scoped_timer::ScopedTimer, Error, Runtime, SignatureScheme};
+use crate::{
+    execution::{self, Step, TraceEntry},
+    resolvers::v1_function_index::FunctionIndex,
+    wasm_backend::WasmMemory,
+};
 
 impl<'a, R> Externals for Runtime<'a, R>
 where
@@ -26,6 +30,67 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+        self.check_deadline()?;
+        self.check_cancelled()?;
+        self.spend_host_call()?;
+        self.record_memory_pages();
+        if self.debugger.is_some() {
+            let step = if func == FunctionIndex::GasFuncIndex {
+                let gas_arg: u32 = args.nth_checked(0)?;
+                Step::BasicBlock {
+                    gas: Gas::new(gas_arg.into()),
+                }
+            } else {
+                Step::HostCall {
+                    function: func,
+                    args: args.as_ref(),
+                }
+            };
+            self.debug_step(step)?;
+        }
+
+        if self.trace.is_none() || func == FunctionIndex::GasFuncIndex {
+            return self.invoke_and_charge_storage(func, args);
+        }
+
+        let gas_before = self.context.gas_counter();
+        let position = self.record_trace(TraceEntry::HostCall {
+            function: func,
+            args_digest: execution::args_digest(args.as_ref()),
+            gas: Gas::default(),
+        });
+        let result = self.invoke_and_charge_storage(func, args);
+        let gas_after = self.context.gas_counter();
+        if let (Some(trace), Some(position)) = (self.trace.as_ref(), position) {
+            if gas_after > gas_before {
+                trace.borrow_mut().set_gas(position, gas_after - gas_before);
+            }
+        }
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    /// Invokes the host function, then charges for the global state it read and wrote.
+    fn invoke_and_charge_storage(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        let result = self.invoke_host_function(func, args)?;
+        self.charge_storage()?;
+        Ok(result)
+    }
+
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         let mut scoped_timer = ScopedTimer::new(func);
         match func {
             FunctionIndex::ReadFuncIndex => {
@@ -37,6 +102,24 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::ReadTypedFuncIndex => {
+                // args(0) = pointer to key in Wasm memory
+                // args(1) = size of key in Wasm memory
+                // args(2) = pointer to serialized expected `CLType` in Wasm memory
+                // args(3) = size of serialized expected `CLType`
+                // args(4) = pointer to output size (output param)
+                let (key_ptr, key_size, cl_type_ptr, cl_type_size, output_size_ptr) =
+                    Args::parse(args)?;
+                let ret = self.read_typed(
+                    key_ptr,
+                    key_size,
+                    cl_type_ptr,
+                    cl_type_size,
+                    output_size_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::ReadLocalFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
                 // args(1) = size of key in Wasm memory
@@ -130,6 +213,27 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::GetNamedArgSizeFuncIndex => {
+                // args(0) = pointer to the name of the host runtime arg in Wasm memory
+                // args(1) = size of the name
+                // args(2) = pointer to a argument size (output)
+                let (name_ptr, name_size, size_ptr): (_, u32, _) = Args::parse(args)?;
+                let ret = self.get_named_arg_size(name_ptr, name_size, size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetNamedArgFuncIndex => {
+                // args(0) = pointer to the name of the host runtime arg in Wasm memory
+                // args(1) = size of the name
+                // args(2) = pointer to destination in Wasm memory
+                // args(3) = size of destination pointer memory
+                let (name_ptr, name_size, dest_ptr, dest_size): (_, u32, _, u32) =
+                    Args::parse(args)?;
+                scoped_timer.add_property("dest_size", dest_size.to_string());
+                let ret = self.get_named_arg(name_ptr, name_size, dest_ptr, dest_size as usize)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::RetFuncIndex => {
                 // args(0) = pointer to value
                 // args(1) = size of value
@@ -153,6 +257,40 @@ where
 
                 let ret = self.call_contract_host_buffer(
                     key_contract,
+                    None,
+                    args_bytes,
+                    result_size_ptr,
+                    &mut scoped_timer,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::CallVersionedContractIndex => {
+                // args(0) = pointer to serialized contract key in Wasm memory
+                // args(1) = size of the key
+                // args(2) = pointer to entry point name in Wasm memory
+                // args(3) = size of the name
+                // args(4) = pointer to function arguments in Wasm memory
+                // args(5) = size of arguments
+                // args(6) = pointer to result size (output)
+                let (
+                    key_ptr,
+                    key_size,
+                    entry_point_ptr,
+                    entry_point_size,
+                    args_ptr,
+                    args_size,
+                    result_size_ptr,
+                ): (_, _, _, _, _, u32, _) = Args::parse(args)?;
+                scoped_timer.add_property("args_size", args_size.to_string());
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+
+                let ret = self.call_contract_host_buffer(
+                    key_contract,
+                    Some(entry_point),
                     args_bytes,
                     result_size_ptr,
                     &mut scoped_timer,
@@ -220,6 +358,29 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::GetImmediateCallerIndex => {
+                // args(0) = pointer where a size of serialized bytes will be stored
+                let output_size = Args::parse(args)?;
+                let ret = self.get_immediate_caller(output_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetCallStackIndex => {
+                // args(0) = pointer where a size of serialized bytes will be stored
+                let output_size = Args::parse(args)?;
+                let ret = self.get_call_stack(output_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetAccountInfoIndex => {
+                // args(0) = pointer to serialized public key in Wasm memory
+                // args(1) = size of the public key
+                // args(2) = pointer where a size of serialized bytes will be stored
+                let (public_key_ptr, public_key_size, output_size) = Args::parse(args)?;
+                let ret = self.get_account_info(public_key_ptr, public_key_size, output_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::GetBlocktimeIndex => {
                 // args(0) = pointer to Wasm memory where to write.
                 let dest_ptr = Args::parse(args)?;
@@ -227,6 +388,76 @@ where
                 Ok(None)
             }
 
+            FunctionIndex::RandomBytesIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.random_bytes(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::Blake2bFuncIndex => {
+                // args(0) = pointer to input in Wasm memory
+                // args(1) = size of input
+                // args(2) = pointer to output buffer in Wasm memory
+                // args(3) = size of output buffer
+                let (in_ptr, in_size, out_ptr, out_size) = Args::parse(args)?;
+                let ret = self.blake2b(in_ptr, in_size, out_ptr, out_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::VerifyEd25519FuncIndex => {
+                // args(0) = pointer to signature in Wasm memory
+                // args(1) = size of signature
+                // args(2) = pointer to message in Wasm memory
+                // args(3) = size of message
+                // args(4) = pointer to public key in Wasm memory
+                // args(5) = size of public key
+                let (signature_ptr, signature_size, message_ptr, message_size, key_ptr, key_size) =
+                    Args::parse(args)?;
+                let is_valid = self.verify_signature(
+                    SignatureScheme::Ed25519,
+                    signature_ptr,
+                    signature_size,
+                    message_ptr,
+                    message_size,
+                    key_ptr,
+                    key_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(i32::from(is_valid))))
+            }
+
+            FunctionIndex::VerifySecp256k1FuncIndex => {
+                // args(0) = pointer to signature in Wasm memory
+                // args(1) = size of signature
+                // args(2) = pointer to message in Wasm memory
+                // args(3) = size of message
+                // args(4) = pointer to public key in Wasm memory
+                // args(5) = size of public key
+                let (signature_ptr, signature_size, message_ptr, message_size, key_ptr, key_size) =
+                    Args::parse(args)?;
+                let is_valid = self.verify_signature(
+                    SignatureScheme::Secp256k1,
+                    signature_ptr,
+                    signature_size,
+                    message_ptr,
+                    message_size,
+                    key_ptr,
+                    key_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(i32::from(is_valid))))
+            }
+
+            FunctionIndex::BigNumOpIndex => {
+                // args(0) = the operation, as a `u32`
+                // args(1) = width in bytes of each operand and of the result
+                // args(2) = pointer to left operand in Wasm memory
+                // args(3) = pointer to right operand in Wasm memory
+                // args(4) = pointer to output buffer in Wasm memory
+                let (op, width, lhs_ptr, rhs_ptr, out_ptr) = Args::parse(args)?;
+                let ret = self.bignum_op(op, width, lhs_ptr, rhs_ptr, out_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::GasFuncIndex => {
                 let gas_arg: u32 = Args::parse(args)?;
                 self.gas(Gas::new(gas_arg.into()))?;
@@ -251,13 +482,10 @@ where
                 let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
                 let contract_size = named_keys_size as usize + fn_bytes.len();
                 scoped_timer.add_property("contract_size", contract_size.to_string());
-                let named_keys_bytes = self
-                    .memory
-                    .get(named_keys_ptr, named_keys_size as usize)
-                    .map_err(|e| Error::Interpreter(e.into()))?;
+                let named_keys_bytes = self.memory.get(named_keys_ptr, named_keys_size as usize)?;
                 let named_keys =
                     bytesrepr::deserialize(named_keys_bytes).map_err(Error::BytesRepr)?;
-                let contract_hash = self.store_function(fn_bytes, named_keys)?;
+                let contract_hash = self.store_function(fn_bytes, named_keys, None)?;
                 self.function_address(contract_hash, uref_addr_ptr)?;
                 Ok(None)
             }
@@ -280,13 +508,79 @@ where
                 let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
                 let contract_size = named_keys_size as usize + fn_bytes.len();
                 scoped_timer.add_property("contract_size", contract_size.to_string());
-                let named_keys_bytes = self
-                    .memory
-                    .get(named_keys_ptr, named_keys_size as usize)
-                    .map_err(|e| Error::Interpreter(e.into()))?;
+                let named_keys_bytes = self.memory.get(named_keys_ptr, named_keys_size as usize)?;
+                let named_keys =
+                    bytesrepr::deserialize(named_keys_bytes).map_err(Error::BytesRepr)?;
+                let contract_hash = self.store_function_at_hash(fn_bytes, named_keys, None)?;
+                self.function_address(contract_hash, hash_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::StoreFnWithAbiIndex | FunctionIndex::StoreFnAtHashWithAbiIndex => {
+                // args(0) = pointer to function name in Wasm memory
+                // args(1) = size of the name
+                // args(2) = pointer to named keys to be saved with the function body
+                // args(3) = size of the named keys
+                // args(4) = pointer to the ABI descriptor to be saved with the function body
+                // args(5) = size of the ABI descriptor
+                // args(6) = pointer to a Wasm memory where we will save
+                //           uref address or hash of the new function
+                let (
+                    name_ptr,
+                    name_size,
+                    named_keys_ptr,
+                    named_keys_size,
+                    abi_ptr,
+                    abi_size,
+                    addr_ptr,
+                ): (_, u32, _, u32, _, u32, _) = Args::parse(args)?;
+                scoped_timer.add_property("name_size", name_size.to_string());
+                let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
+                let contract_size = named_keys_size as usize + abi_size as usize + fn_bytes.len();
+                scoped_timer.add_property("contract_size", contract_size.to_string());
+                let named_keys_bytes = self.memory.get(named_keys_ptr, named_keys_size as usize)?;
                 let named_keys =
                     bytesrepr::deserialize(named_keys_bytes).map_err(Error::BytesRepr)?;
-                let contract_hash = self.store_function_at_hash(fn_bytes, named_keys)?;
+                let abi_bytes = self.memory.get(abi_ptr, abi_size as usize)?;
+                let abi = bytesrepr::deserialize(abi_bytes).map_err(Error::BytesRepr)?;
+                let contract_hash = if func == FunctionIndex::StoreFnWithAbiIndex {
+                    self.store_function(fn_bytes, named_keys, Some(abi))?
+                } else {
+                    self.store_function_at_hash(fn_bytes, named_keys, Some(abi))?
+                };
+                self.function_address(contract_hash, addr_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetContractAbiIndex => {
+                // args(0) = pointer to serialized contract key in Wasm memory
+                // args(1) = size of the key
+                // args(2) = pointer to the size of the ABI descriptor (output)
+                let (key_ptr, key_size, result_size_ptr): (_, u32, _) = Args::parse(args)?;
+                let ret = self.get_contract_abi(key_ptr, key_size, result_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::StoreVersionedContractIndex => {
+                // args(0) = pointer to named keys to be saved with the contract
+                // args(1) = size of the named keys
+                // args(2) = pointer to the ABI descriptor naming the contract's entry points
+                // args(3) = size of the ABI descriptor
+                // args(4) = pointer to a Wasm memory where we will save
+                //           hash of the new contract
+                let (named_keys_ptr, named_keys_size, abi_ptr, abi_size, hash_ptr): (
+                    _,
+                    u32,
+                    _,
+                    u32,
+                    _,
+                ) = Args::parse(args)?;
+                let named_keys_bytes = self.memory.get(named_keys_ptr, named_keys_size as usize)?;
+                let named_keys =
+                    bytesrepr::deserialize(named_keys_bytes).map_err(Error::BytesRepr)?;
+                let abi_bytes = self.memory.get(abi_ptr, abi_size as usize)?;
+                let abi = bytesrepr::deserialize(abi_bytes).map_err(Error::BytesRepr)?;
+                let contract_hash = self.store_versioned_contract(named_keys, abi)?;
                 self.function_address(contract_hash, hash_ptr)?;
                 Ok(None)
             }
@@ -301,6 +595,24 @@ where
                 ))))
             }
 
+            FunctionIndex::AttenuateURefIndex => {
+                // args(0) = pointer to serialized URef in Wasm memory
+                // args(1) = size of serialized URef
+                // args(2) = bits of the access rights to keep
+                // args(3) = pointer to Wasm memory where to write the attenuated URef
+                let (uref_ptr, uref_size, access_rights, dest_ptr) = Args::parse(args)?;
+                let ret = self.attenuate_uref(uref_ptr, uref_size, access_rights, dest_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetChainConfigIndex => {
+                // args(0) = the chain configuration key, as a u32
+                // args(1) = pointer where a size of serialized bytes will be stored
+                let (key, output_size) = Args::parse(args)?;
+                let ret = self.get_chain_config(key, output_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::RevertFuncIndex => {
                 // args(0) = status u32
                 let status = Args::parse(args)?;
@@ -359,9 +671,7 @@ where
                 let purse = self.create_purse()?;
                 let purse_bytes = purse.into_bytes().map_err(Error::BytesRepr)?;
                 assert_eq!(dest_size, purse_bytes.len() as u32);
-                self.memory
-                    .set(dest_ptr, &purse_bytes)
-                    .map_err(|e| Error::Interpreter(e.into()))?;
+                self.memory.set(dest_ptr, &purse_bytes)?;
                 Ok(Some(RuntimeValue::I32(0)))
             }
 
@@ -436,6 +746,77 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::BatchTransferIndex => {
+                // args(0) = pointer to array of bytes in Wasm memory of a list of transfers
+                // args(1) = length of array of bytes in Wasm memory of a list of transfers
+                let (transfers_ptr, transfers_size) = Args::parse(args)?;
+                let ret = self.batch_transfer(transfers_ptr, transfers_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::TransferFromPurseToPurseWithProofIndex => {
+                // args(0) = pointer to array of bytes in Wasm memory of a source purse
+                // args(1) = length of array of bytes in Wasm memory of a source purse
+                // args(2) = pointer to array of bytes in Wasm memory of a target purse
+                // args(3) = length of array of bytes in Wasm memory of a target purse
+                // args(4) = pointer to array of bytes in Wasm memory of an amount
+                // args(5) = length of array of bytes in Wasm memory of an amount
+                // args(6) = pointer to array for the receipt URef return value
+                // args(7) = length of array for the receipt URef return value
+                let (
+                    source_ptr,
+                    source_size,
+                    target_ptr,
+                    target_size,
+                    amount_ptr,
+                    amount_size,
+                    dest_ptr,
+                    dest_size,
+                ) = Args::parse(args)?;
+                let ret = self.transfer_from_purse_to_purse_with_proof(
+                    source_ptr,
+                    source_size,
+                    target_ptr,
+                    target_size,
+                    amount_ptr,
+                    amount_size,
+                    dest_ptr,
+                    dest_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::BurnIndex => {
+                // args(0) = pointer to array of bytes in Wasm memory of a purse
+                // args(1) = length of array of bytes in Wasm memory of a purse
+                // args(2) = pointer to array of bytes in Wasm memory of an amount
+                // args(3) = length of array of bytes in Wasm memory of an amount
+                let (purse_ptr, purse_size, amount_ptr, amount_size) = Args::parse(args)?;
+                let ret = self.burn(purse_ptr, purse_size, amount_ptr, amount_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::ListPursesIndex => {
+                // args(0) = pointer to amount of purses (output)
+                // args(1) = pointer to amount of serialized bytes (output)
+                let (total_purses_ptr, result_size_ptr) = Args::parse(args)?;
+                let ret = self.list_purses(total_purses_ptr, result_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::SetContractInstallPermissionIndex => {
+                // args(0) = pointer to array of bytes in Wasm memory of a public key
+                // args(1) = length of array of bytes in Wasm memory of a public key
+                // args(2) = non-zero to allow installs, zero to revoke them
+                let (public_key_ptr, public_key_size, allowed): (_, _, u32) = Args::parse(args)?;
+                let ret = self.set_contract_install_permission(
+                    public_key_ptr,
+                    public_key_size,
+                    allowed != 0,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::GetBalanceIndex => {
                 // args(0) = pointer to purse input
                 // args(1) = length of purse
@@ -445,6 +826,54 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::ScheduleCallIndex => {
+                // args(0) = pointer to contract key in Wasm memory
+                // args(1) = size of contract key in Wasm memory
+                // args(2) = pointer to function arguments in Wasm memory
+                // args(3) = size of function arguments in Wasm memory
+                // args(4) = pointer to earliest block time in Wasm memory
+                // args(5) = size of earliest block time in Wasm memory
+                // args(6) = pointer to gas budget in Wasm memory
+                // args(7) = size of gas budget in Wasm memory
+                let (
+                    key_ptr,
+                    key_size,
+                    args_ptr,
+                    args_size,
+                    blocktime_ptr,
+                    blocktime_size,
+                    gas_budget_ptr,
+                    gas_budget_size,
+                ) = Args::parse(args)?;
+                let ret = self.schedule_call(
+                    key_ptr,
+                    key_size,
+                    args_ptr,
+                    args_size,
+                    blocktime_ptr,
+                    blocktime_size,
+                    gas_budget_ptr,
+                    gas_budget_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetBalanceOfIndex => {
+                // args(0) = pointer to purse input
+                // args(1) = length of purse
+                // args(2) = pointer to output size (output)
+                let (purse_ptr, purse_size, output_size_ptr) = Args::parse(args)?;
+                let ret = self.get_balance_of(purse_ptr, purse_size, output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetMinMainPurseBalanceIndex => {
+                // args(0) = pointer to output size (output)
+                let output_size_ptr = Args::parse(args)?;
+                let ret = self.get_min_main_purse_balance(output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::GetPhaseIndex => {
                 // args(0) = pointer to Wasm memory where to write.
                 let dest_ptr = Args::parse(args)?;
@@ -493,6 +922,82 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::GuardReentrancyIndex => {
+                self.guard_reentrancy()?;
+                Ok(None)
+            }
+
+            FunctionIndex::DeleteFuncIndex => {
+                // args(0) = pointer to key in Wasm memory
+                // args(1) = size of key
+                let (key_ptr, key_size) = Args::parse(args)?;
+                self.delete(key_ptr, key_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::ListLocalFuncIndex => {
+                // args(0) = pointer to serialized cursor in Wasm memory
+                // args(1) = size of serialized cursor
+                // args(2) = maximum number of entries to return
+                // args(3) = pointer to amount of entries (output)
+                // args(4) = pointer to amount of serialized bytes (output)
+                let (cursor_ptr, cursor_size, limit, total_entries_ptr, result_size_ptr) =
+                    Args::parse(args)?;
+                let ret = self.list_local(
+                    cursor_ptr,
+                    cursor_size,
+                    limit,
+                    total_entries_ptr,
+                    result_size_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::UpdateAssociatedKeysFuncIndex => {
+                // args(0) = pointer to serialized list of updates
+                // args(1) = size of serialized list of updates
+                let (updates_ptr, updates_size) = Args::parse(args)?;
+                let ret = self.update_associated_keys(updates_ptr, updates_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::CanManageKeysIndex => {
+                Ok(Some(RuntimeValue::I32(i32::from(self.can_manage_keys()))))
+            }
+
+            FunctionIndex::RotateAssociatedKeyFuncIndex => {
+                // args(0) = pointer to serialized account public key
+                // args(1) = size of serialized account public key
+                // args(2) = pointer to serialized public key to be replaced
+                // args(3) = size of serialized public key to be replaced
+                // args(4) = pointer to serialized replacement public key
+                // args(5) = size of serialized replacement public key
+                let (
+                    account_ptr,
+                    account_size,
+                    old_key_ptr,
+                    old_key_size,
+                    new_key_ptr,
+                    new_key_size,
+                ) = Args::parse(args)?;
+                let ret = self.rotate_associated_key(
+                    account_ptr,
+                    account_size,
+                    old_key_ptr,
+                    old_key_size,
+                    new_key_ptr,
+                    new_key_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetBlockHeightIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.get_block_height(dest_ptr)?;
+                Ok(None)
+            }
+
             #[cfg(feature = "test-support")]
             FunctionIndex::PrintIndex => {
                 let (text_ptr, text_size): (_, u32) = Args::parse(args)?;
@@ -5,7 +5,7 @@ use types::{
     account::PublicKey,
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::mint::Error,
-    CLTyped, CLValue, Key, URef,
+    BlockTime, CLTyped, CLValue, Key, URef, U512,
 };
 
 use crate::{execution, runtime_context::RuntimeContext};
@@ -19,10 +19,22 @@ where
         self.get_caller()
     }
 
+    fn get_system_account(&self) -> PublicKey {
+        self.protocol_data().system_account()
+    }
+
     fn put_key(&mut self, name: &str, key: Key) {
         // TODO: update RuntimeProvider to better handle errors
         self.put_key(name.to_string(), key).expect("should put key")
     }
+
+    fn get_block_time(&self) -> BlockTime {
+        self.get_blocktime()
+    }
+
+    fn get_min_main_purse_balance(&self) -> Option<(URef, U512)> {
+        self.min_main_purse_balance()
+    }
 }
 
 // TODO: update Mint + StorageProvider to better handle errors
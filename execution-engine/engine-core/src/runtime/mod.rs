@@ -1,4 +1,6 @@
 mod args;
+mod bignum;
+mod crypto;
 mod externals;
 mod mint_internal;
 mod proof_of_stake_internal;
@@ -6,47 +8,91 @@ mod scoped_timer;
 mod standard_payment_internal;
 
 use std::{
+    cell::{Cell, RefCell},
     cmp,
     collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
     iter::IntoIterator,
+    rc::Rc,
+    time::Instant,
 };
 
 use itertools::Itertools;
 use parity_wasm::elements::Module;
-use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
+use wasmi::{Trap, TrapKind};
 
 use ::mint::Mint;
 use contract::args_parser::ArgsParser;
-use engine_shared::{account::Account, contract::Contract, gas::Gas, stored_value::StoredValue};
+use engine_shared::{
+    account::Account, contract::Contract, gas::Gas, newtypes::Blake2bHash,
+    stored_value::StoredValue, TypeMismatch,
+};
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use proof_of_stake::ProofOfStake;
 use standard_payment::StandardPayment;
 use types::{
-    account::{ActionType, PublicKey, Weight},
+    account::{AccountInfo, ActionType, AssociatedKeysUpdate, PublicKey, Weight},
     bytesrepr::{self, FromBytes, ToBytes},
     system_contract_errors,
     system_contract_errors::mint,
-    AccessRights, ApiError, CLType, CLTyped, CLValue, Key, ProtocolVersion, SystemContractType,
-    TransferResult, TransferredTo, URef, U128, U256, U512,
+    AccessRights, ApiError, BigNumOp, BlockTime, CLType, CLTyped, CLValue, ChainConfigKey,
+    ContractAbi, EntryPoint, Key, Phase, ProtocolVersion, SystemContractType, TransferReceipt,
+    TransferResult, TransferredTo, URef, VestingSchedule, BLAKE2B_DIGEST_LENGTH,
+    MAX_LIST_LOCAL_ENTRIES, U128, U256, U512,
 };
 
 use crate::{
-    engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
-    execution::{Error, MINT_NAME, POS_NAME},
-    resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
-    runtime_context::RuntimeContext,
+    engine_state::{
+        deploy_whitelist, genesis::POS_PAYMENT_PURSE, system_contract_cache::SystemContractCache,
+        EngineConfig, WhitelistMode, CONV_RATE, MAX_PAYMENT,
+    },
+    execution::{
+        CancellationToken, DebugAction, Debugger, DeployTrace, Error, Step, TraceEntry, MINT_NAME,
+        POS_NAME,
+    },
+    runtime_context::{check_cl_type, RuntimeContext},
+    wasm_backend::{self, Memory, WasmInstance, WasmMemory},
     Address,
 };
+use crypto::SignatureScheme;
 use scoped_timer::ScopedTimer;
 
+/// The number of host functions, including gas charges, which may be called while finalizing the
+/// payment for a deploy.
+pub const FINALIZATION_HOST_CALL_BUDGET: u32 = 1_000_000;
+
+/// The Proof of Stake entry points which may be executed while finalizing the payment for a deploy.
+pub const PROOF_OF_STAKE_FINALIZATION_METHODS: [&str; 1] = ["finalize_payment"];
+
+/// The mint entry points which may be called while finalizing the payment for a deploy.
+const MINT_FINALIZATION_METHODS: [&str; 3] = ["create", "balance", "transfer"];
+
 pub struct Runtime<'a, R> {
     system_contract_cache: SystemContractCache,
     config: EngineConfig,
-    memory: MemoryRef,
+    memory: Memory,
     module: Module,
     host_buffer: Option<CLValue>,
     context: RuntimeContext<'a, R>,
+    /// Base keys of the contexts that led to the current one, ending with the current base key.
+    call_stack: Vec<Key>,
+    /// When execution must stop by, if the engine is configured with a time limit.  Shared by
+    /// every contract called in the same phase.
+    deadline: Option<Instant>,
+    /// How many more host functions may be called during payment finalization, which isn't
+    /// metered.  Shared by every contract called in the same phase.
+    host_call_budget: Option<Rc<Cell<u32>>>,
+    /// The record of the execution if tracing is enabled.  Shared by every contract called in the
+    /// same phase.
+    trace: Option<Rc<RefCell<DeployTrace>>>,
+    /// Called before each step of execution if set.  Shared by every contract called in the same
+    /// phase.
+    debugger: Option<Rc<RefCell<dyn Debugger>>>,
+    /// Stops execution once cancelled, if set.  Shared by every contract called in the same phase.
+    cancellation_token: Option<CancellationToken>,
+    /// The most pages of memory any contract has held.  Shared by every contract called in the
+    /// same phase.
+    peak_memory_pages: Rc<Cell<u32>>,
 }
 
 /// Rename function called `name` in the `module` to `call`.
@@ -65,21 +111,37 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
     main_export.push_str("call");
 }
 
-pub fn instance_and_memory(
-    parity_module: Module,
-    protocol_version: ProtocolVersion,
-) -> Result<(ModuleRef, MemoryRef), Error> {
-    let module = wasmi::Module::from_parity_wasm_module(parity_module)?;
-    let resolver = create_module_resolver(protocol_version)?;
-    let mut imports = ImportsBuilder::new();
-    imports.push_resolver("env", &resolver);
-    let not_started_module = ModuleInstance::new(&module, &imports)?;
-    if not_started_module.has_start() {
-        return Err(Error::UnsupportedWasmStart);
-    }
-    let instance = not_started_module.not_started_instance().clone();
-    let memory = resolver.memory_ref()?;
-    Ok((instance, memory))
+/// Returns the name of the function to invoke when calling `contract`, checking that `caller` may
+/// call `entry_point`.
+///
+/// A contract stored with a single function is invoked through its `call` export and can't be
+/// called by entry point, while a contract stored with named entry points can only be called by
+/// entry point.
+fn dispatch_entry_point(
+    contract: &Contract,
+    entry_point: Option<String>,
+    caller: PublicKey,
+) -> Result<String, Error> {
+    let name = match entry_point {
+        None if contract.has_entry_points() => {
+            return Err(Error::FunctionNotFound(String::from(
+                "contract must be called through one of its entry points",
+            )))
+        }
+        None => return Ok(String::from("call")),
+        Some(name) => name,
+    };
+    let no_entry_point =
+        || Error::FunctionNotFound(format!("contract has no entry point {}", name));
+    let abi = match contract.abi() {
+        Some(abi) if contract.has_entry_points() => abi,
+        _ => return Err(no_entry_point()),
+    };
+    let entry_point = abi.entry_point(&name).ok_or_else(no_entry_point)?;
+    if !abi.can_call(entry_point, caller) {
+        return Err(Error::EntryPointAccessDenied(name));
+    }
+    Ok(name)
 }
 
 /// Turns `key` into a `([u8; 32], AccessRights)` tuple.
@@ -92,6 +154,8 @@ pub fn key_to_tuple(key: Key) -> Option<([u8; 32], AccessRights)> {
         Key::Account(_) => None,
         Key::Hash(_) => None,
         Key::Local { .. } => None,
+        Key::ContractPackage(_) => None,
+        Key::DeployInfo(_) => None,
     }
 }
 
@@ -1349,10 +1413,25 @@ where
     pub fn new(
         config: EngineConfig,
         system_contract_cache: SystemContractCache,
-        memory: MemoryRef,
+        memory: Memory,
         module: Module,
         context: RuntimeContext<'a, R>,
     ) -> Self {
+        let call_stack = vec![context.base_key()];
+        let peak_memory_pages = Rc::new(Cell::new(memory.size_in_pages()));
+        let deadline = config
+            .execution_time_limit()
+            .map(|limit| Instant::now() + limit);
+        let host_call_budget = if context.phase() == Phase::FinalizePayment {
+            Some(Rc::new(Cell::new(FINALIZATION_HOST_CALL_BUDGET)))
+        } else {
+            None
+        };
+        let trace = if config.enable_tracing() {
+            Some(Default::default())
+        } else {
+            None
+        };
         Runtime {
             config,
             system_contract_cache,
@@ -1360,10 +1439,37 @@ where
             module,
             host_buffer: None,
             context,
+            call_stack,
+            deadline,
+            host_call_budget,
+            trace,
+            debugger: None,
+            cancellation_token: None,
+            peak_memory_pages,
         }
     }
 
-    pub fn memory(&self) -> &MemoryRef {
+    /// Pauses before each step of execution to consult `debugger`.
+    pub fn with_debugger(mut self, debugger: Option<Rc<RefCell<dyn Debugger>>>) -> Self {
+        self.debugger = debugger;
+        self
+    }
+
+    /// Stops execution with [`Error::Cancelled`] once `cancellation_token` is cancelled.
+    pub fn with_cancellation_token(
+        mut self,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Self {
+        self.cancellation_token = cancellation_token;
+        self
+    }
+
+    /// The most 64KiB pages of memory any contract executed by this runtime has held.
+    pub fn peak_memory_pages(&self) -> u32 {
+        self.peak_memory_pages.get()
+    }
+
+    pub fn memory(&self) -> &Memory {
         &self.memory
     }
 
@@ -1385,6 +1491,10 @@ where
     /// Intuition about the return value sense is to answer the question 'are we
     /// allowed to continue?'
     fn charge_gas(&mut self, amount: Gas) -> bool {
+        // Payment finalization is bounded by the host call budget instead.
+        if self.context.phase() == Phase::FinalizePayment {
+            return true;
+        }
         let prev = self.context.gas_counter();
         match prev.checked_add(amount) {
             // gas charge overflow protection
@@ -1397,6 +1507,90 @@ where
         }
     }
 
+    /// Fails once the configured time limit has passed.  Called on every host function call,
+    /// which includes the gas charges injected into each block of code, so no long-running loop
+    /// can avoid it.
+    fn check_deadline(&self) -> Result<(), Error> {
+        match (self.deadline, self.config.execution_time_limit()) {
+            (Some(deadline), Some(limit)) if Instant::now() > deadline => {
+                Err(Error::ExecutionTimeout(limit.as_millis()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails once the cancellation token has been cancelled.  Checked alongside the time limit.
+    fn check_cancelled(&self) -> Result<(), Error> {
+        match self.cancellation_token {
+            Some(ref token) if token.is_cancelled() => Err(Error::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the size of the contract's memory towards the peak.  Called on every host function
+    /// call, and once more when the contract returns, as its memory may have grown since.  The
+    /// growth itself is charged for by the `grow_mem` wasm cost.
+    pub(crate) fn record_memory_pages(&self) {
+        let memory_pages = self.memory.size_in_pages();
+        if memory_pages > self.peak_memory_pages.get() {
+            self.peak_memory_pages.set(memory_pages);
+        }
+    }
+
+    /// Uses up one host function call of the budget during payment finalization, failing once
+    /// it's exhausted.  Like the time limit, this also covers the gas charges injected into each
+    /// block of code.
+    fn spend_host_call(&self) -> Result<(), Error> {
+        match self.host_call_budget {
+            Some(ref budget) if budget.get() == 0 => {
+                Err(Error::HostCallBudgetExceeded(FINALIZATION_HOST_CALL_BUDGET))
+            }
+            Some(ref budget) => {
+                budget.set(budget.get() - 1);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Records `entry` if tracing is enabled, returning its position in the trace.
+    fn record_trace(&self, entry: TraceEntry) -> Option<usize> {
+        self.trace
+            .as_ref()
+            .map(|trace| trace.borrow_mut().push(entry))
+    }
+
+    /// Reports `step` to the debugger, if any, failing if it asks for execution to stop.
+    fn debug_step(&self, step: Step) -> Result<(), Error> {
+        match self.debugger.as_ref() {
+            Some(debugger) if debugger.borrow_mut().step(&step) == DebugAction::Abort => {
+                Err(Error::DebuggerAbort)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reports a global state access to the debugger and records it in the trace.
+    fn access_key(&self, entry: TraceEntry) -> Result<(), Error> {
+        let step = match &entry {
+            TraceEntry::Read(key) => Step::Read(*key),
+            TraceEntry::Write(key) => Step::Write(*key),
+            TraceEntry::Add(key) => Step::Add(*key),
+            TraceEntry::Delete(key) => Step::Delete(*key),
+            TraceEntry::HostCall { .. } => unreachable!("host calls are not key accesses"),
+        };
+        self.debug_step(step)?;
+        self.record_trace(entry);
+        Ok(())
+    }
+
+    /// Takes the steps recorded so far, if tracing is enabled.
+    pub fn take_trace(&self) -> Option<DeployTrace> {
+        self.trace
+            .as_ref()
+            .map(|trace| trace.replace(Default::default()))
+    }
+
     fn gas(&mut self, amount: Gas) -> Result<(), Trap> {
         if self.charge_gas(amount) {
             Ok(())
@@ -1405,8 +1599,27 @@ where
         }
     }
 
+    /// Charges for the values read from and written to global state since the last charge,
+    /// according to the number of storage buckets spanned by each value's serialized size.
+    fn charge_storage(&mut self) -> Result<(), Trap> {
+        let usage = self.context.state().borrow_mut().take_storage_usage();
+        if usage.reads.is_empty() && usage.writes.is_empty() {
+            return Ok(());
+        }
+        let wasm_costs = self.protocol_data().wasm_costs();
+        let buckets = |sizes: &[usize]| -> U512 {
+            sizes
+                .iter()
+                .map(|size| U512::from(wasm_costs.storage_buckets(*size)))
+                .fold(U512::zero(), |total, buckets| total + buckets)
+        };
+        let cost = buckets(&usage.reads) * U512::from(wasm_costs.storage_read_per_bucket)
+            + buckets(&usage.writes) * U512::from(wasm_costs.storage_write_per_bucket);
+        self.gas(Gas::new(cost))
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
-        self.memory.get(ptr, size).map_err(Into::into)
+        self.memory.get(ptr, size)
     }
 
     /// Reads key (defined as `key_ptr` and `key_size` tuple) from Wasm memory.
@@ -1457,12 +1670,64 @@ where
         }
     }
 
+    /// Returns the current module reduced to the functions exported under the names of `abi`'s
+    /// entry points, failing if any of them isn't exported.
+    fn get_entry_points_module(&self, abi: &ContractAbi) -> Result<Vec<u8>, Error> {
+        let names: Vec<&str> = abi.entry_points().iter().map(EntryPoint::name).collect();
+        for name in &names {
+            let has_name = self
+                .module
+                .export_section()
+                .and_then(|export_section| {
+                    export_section
+                        .entries()
+                        .iter()
+                        .find(|export_entry| export_entry.field() == *name)
+                })
+                .is_some();
+            if !has_name {
+                return Err(Error::FunctionNotFound(name.to_string()));
+            }
+        }
+
+        let mut module = self.module.clone();
+        pwasm_utils::optimize(&mut module, names).unwrap();
+        parity_wasm::serialize(module).map_err(Error::ParityWasm)
+    }
+
     fn is_valid_uref(&mut self, uref_ptr: u32, uref_size: u32) -> Result<bool, Trap> {
         let bytes = self.bytes_from_mem(uref_ptr, uref_size as usize)?;
         let uref: URef = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
         Ok(self.context.validate_uref(&uref).is_ok())
     }
 
+    /// Writes `uref`, keeping only those of its access rights which are in `access_rights`, to
+    /// `dest_ptr`.
+    ///
+    /// The current context must hold `uref`, so the attenuated `URef` is valid wherever `uref` is.
+    fn attenuate_uref(
+        &mut self,
+        uref_ptr: u32,
+        uref_size: u32,
+        access_rights: u32,
+        dest_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let bytes = self.bytes_from_mem(uref_ptr, uref_size as usize)?;
+        let uref: URef = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
+        self.context.validate_uref(&uref)?;
+        let access_rights = match u8::try_from(access_rights)
+            .ok()
+            .and_then(AccessRights::from_bits)
+        {
+            Some(access_rights) => access_rights,
+            None => return Ok(Err(ApiError::InvalidArgument)),
+        };
+        let attenuated = uref.attenuate(access_rights);
+        let attenuated_bytes = attenuated.into_bytes().map_err(Error::BytesRepr)?;
+        self.memory.set(dest_ptr, &attenuated_bytes)?;
+        Ok(Ok(()))
+    }
+
     fn get_arg_size(&mut self, index: usize, size_ptr: u32) -> Result<Result<(), ApiError>, Trap> {
         let arg_size = match self.context.args().get(index) {
             Some(arg) if arg.inner_bytes().len() > u32::max_value() as usize => {
@@ -1475,7 +1740,7 @@ where
         let arg_size_bytes = arg_size.to_le_bytes(); // Wasm is little-endian
 
         if let Err(e) = self.memory.set(size_ptr, &arg_size_bytes) {
-            return Err(Error::Interpreter(e.into()).into());
+            return Err(e.into());
         }
 
         Ok(Ok(()))
@@ -1500,7 +1765,66 @@ where
             .memory
             .set(output_ptr, &arg.inner_bytes()[..output_size])
         {
-            return Err(Error::Interpreter(e.into()).into());
+            return Err(e.into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Returns the serialized `CLValue` of the named arg called `name`, or `None` if there is no
+    /// such arg.
+    fn named_arg_bytes(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        match self.context.args().get_named(name) {
+            Some(arg) => Ok(Some(arg.to_bytes()?)),
+            None => Ok(None),
+        }
+    }
+
+    fn get_named_arg_size(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let arg_size = match self.named_arg_bytes(&name)? {
+            Some(bytes) if bytes.len() > u32::max_value() as usize => {
+                return Ok(Err(ApiError::OutOfMemory))
+            }
+            None => return Ok(Err(ApiError::MissingArgument)),
+            Some(bytes) => bytes.len() as u32,
+        };
+
+        let arg_size_bytes = arg_size.to_le_bytes(); // Wasm is little-endian
+
+        if let Err(e) = self.memory.set(size_ptr, &arg_size_bytes) {
+            return Err(e.into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Writes the named arg called `name` to Wasm memory as a serialized `CLValue`, so that the
+    /// contract can check its type.
+    fn get_named_arg(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        output_ptr: u32,
+        output_size: usize,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let arg_bytes = match self.named_arg_bytes(&name)? {
+            Some(bytes) => bytes,
+            None => return Ok(Err(ApiError::MissingArgument)),
+        };
+
+        if arg_bytes.len() > output_size {
+            return Ok(Err(ApiError::OutOfMemory));
+        }
+
+        if let Err(e) = self.memory.set(output_ptr, &arg_bytes) {
+            return Err(e.into());
         }
 
         Ok(Ok(()))
@@ -1535,14 +1859,14 @@ where
 
         // Set serialized Key bytes into the output buffer
         if let Err(error) = self.memory.set(output_ptr, &key_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(error.into());
         }
 
         // For all practical purposes following cast is assumed to be safe
         let bytes_size = key_bytes.len() as u32;
         let size_bytes = bytes_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.memory.set(bytes_written_ptr, &size_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(error.into());
         }
 
         Ok(Ok(()))
@@ -1566,6 +1890,9 @@ where
     ) -> Result<(), Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
+        if !self.context.named_keys_contains_key(&name) {
+            self.check_named_keys_count(self.context.named_keys().len() + 1)?;
+        }
         self.context.put_key(name, key).map_err(Into::into)
     }
 
@@ -1579,9 +1906,7 @@ where
     fn get_main_purse(&mut self, dest_ptr: u32) -> Result<(), Trap> {
         let purse = self.context.get_main_purse()?;
         let purse_bytes = purse.into_bytes().map_err(Error::BytesRepr)?;
-        self.memory
-            .set(dest_ptr, &purse_bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+        self.memory.set(dest_ptr, &purse_bytes).map_err(Into::into)
     }
 
     /// Writes caller (deploy) account public key to [dest_ptr] in the Wasm
@@ -1602,7 +1927,139 @@ where
         // Write output
         let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.memory.set(output_size, &output_size_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(error.into());
+        }
+        Ok(Ok(()))
+    }
+
+    /// Returns the keys of the accounts and contracts on the call stack, starting with the account
+    /// which sent the deploy and ending with the currently executing code.  Contract `URef`s have
+    /// their access rights removed.
+    fn call_stack_keys(&self) -> Vec<Key> {
+        self.call_stack.iter().map(|key| key.normalize()).collect()
+    }
+
+    /// Writes the key of the account or contract which invoked the currently executing code to the
+    /// host buffer.  Session code is invoked by the account which sent the deploy.
+    fn get_immediate_caller(&mut self, output_size_ptr: u32) -> Result<Result<(), ApiError>, Trap> {
+        let call_stack = self.call_stack_keys();
+        // Session code has only its account on the call stack, which is also its caller.
+        let caller = call_stack[call_stack.len().saturating_sub(2)];
+        self.write_host_buffer_with_size(caller, output_size_ptr)
+    }
+
+    /// Writes the keys of the call stack, as returned by [`Runtime::call_stack_keys`], to the host
+    /// buffer.
+    fn get_call_stack(&mut self, output_size_ptr: u32) -> Result<Result<(), ApiError>, Trap> {
+        let call_stack = self.call_stack_keys();
+        self.write_host_buffer_with_size(call_stack, output_size_ptr)
+    }
+
+    /// Writes the public configuration of the account with the public key given in the Wasm memory
+    /// to the host buffer.  The account is read from global state like any other value, and
+    /// charged for accordingly.
+    fn get_account_info(
+        &mut self,
+        public_key_ptr: u32,
+        public_key_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let public_key_bytes = self.bytes_from_mem(public_key_ptr, public_key_size as usize)?;
+        let public_key: PublicKey =
+            bytesrepr::deserialize(public_key_bytes).map_err(Error::from)?;
+        let key = Key::Account(public_key);
+        self.access_key(TraceEntry::Read(key))?;
+        let account = match self.context.read_account(&key)? {
+            Some(StoredValue::Account(account)) => account,
+            Some(other) => {
+                return Err(Error::TypeMismatch(TypeMismatch::new(
+                    "Account".to_string(),
+                    other.type_name(),
+                ))
+                .into())
+            }
+            None => return Ok(Err(ApiError::ValueNotFound)),
+        };
+        let associated_keys = account
+            .get_associated_keys()
+            .map(|(public_key, weight)| (*public_key, *weight))
+            .collect();
+        let action_thresholds = account.action_thresholds();
+        let account_info = AccountInfo::new(
+            account.public_key(),
+            associated_keys,
+            *action_thresholds.deployment(),
+            *action_thresholds.key_management(),
+            account.main_purse(),
+        );
+        self.write_host_buffer_with_size(account_info, output_size_ptr)
+    }
+
+    /// Writes the chain configuration value identified by `key` to the host buffer.
+    fn get_chain_config(
+        &mut self,
+        key: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let key = match ChainConfigKey::try_from(key) {
+            Ok(key) => key,
+            Err(error) => return Ok(Err(error)),
+        };
+        match key {
+            ChainConfigKey::ChainName => {
+                let chain_name: String = match self.protocol_data().chain_name() {
+                    Some(uref) => {
+                        let key = Key::URef(uref);
+                        match self.context.read_gs_direct(&key)? {
+                            Some(StoredValue::CLValue(cl_value)) => {
+                                cl_value.into_t().map_err(Error::CLValue)?
+                            }
+                            _ => return Err(Error::KeyNotFound(key).into()),
+                        }
+                    }
+                    None => String::new(),
+                };
+                self.write_host_buffer_with_size(chain_name, output_size_ptr)
+            }
+            ChainConfigKey::ConvRate => {
+                self.write_host_buffer_with_size(CONV_RATE, output_size_ptr)
+            }
+            ChainConfigKey::MaxPayment => {
+                self.write_host_buffer_with_size(U512::from(MAX_PAYMENT), output_size_ptr)
+            }
+            ChainConfigKey::ProtocolVersion => {
+                let version = self.context.protocol_version().value();
+                let version = (version.major, version.minor, version.patch);
+                self.write_host_buffer_with_size(version, output_size_ptr)
+            }
+            ChainConfigKey::SystemAccount => {
+                let system_account = self.protocol_data().system_account();
+                self.write_host_buffer_with_size(system_account, output_size_ptr)
+            }
+        }
+    }
+
+    /// Writes `value` to the host buffer and its serialized length to `output_size_ptr` in the
+    /// Wasm memory.
+    fn write_host_buffer_with_size<T: CLTyped + ToBytes>(
+        &mut self,
+        value: T,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+        let value = CLValue::from_t(value).map_err(Error::CLValue)?;
+        let value_size = value.inner_bytes().len() as u32;
+
+        if let Err(error) = self.write_host_buffer(value) {
+            return Ok(Err(error));
+        }
+
+        let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &output_size_bytes) {
+            return Err(error.into());
         }
         Ok(Ok(()))
     }
@@ -1611,9 +2068,21 @@ where
     fn get_phase(&mut self, dest_ptr: u32) -> Result<(), Trap> {
         let phase = self.context.phase();
         let bytes = phase.into_bytes().map_err(Error::BytesRepr)?;
-        self.memory
-            .set(dest_ptr, &bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+        self.memory.set(dest_ptr, &bytes).map_err(Into::into)
+    }
+
+    /// Fails with [`Error::Reentrancy`] if the currently executing contract is already present
+    /// further down the call stack.
+    fn guard_reentrancy(&self) -> Result<(), Trap> {
+        let (current, callers) = self
+            .call_stack
+            .split_last()
+            .expect("call stack should never be empty");
+        let current = current.normalize();
+        if callers.iter().any(|caller| caller.normalize() == current) {
+            return Err(Error::Reentrancy(current).into());
+        }
+        Ok(())
     }
 
     /// Writes current blocktime to [dest_ptr] in Wasm memory.
@@ -1623,19 +2092,112 @@ where
             .get_blocktime()
             .into_bytes()
             .map_err(Error::BytesRepr)?;
-        self.memory
-            .set(dest_ptr, &blocktime)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+        self.memory.set(dest_ptr, &blocktime).map_err(Into::into)
+    }
+
+    /// Charges for hashing `length` bytes on the host.
+    fn charge_hashing(&mut self, length: u32) -> Result<(), Trap> {
+        let hash_per_byte = self.protocol_data().wasm_costs().hash_per_byte;
+        self.gas(Gas::new(U512::from(hash_per_byte) * length))
+    }
+
+    /// Writes the Blake2b-256 digest of the input to the output buffer in Wasm memory.
+    fn blake2b(
+        &mut self,
+        in_ptr: u32,
+        in_size: u32,
+        out_ptr: u32,
+        out_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if out_size as usize != BLAKE2B_DIGEST_LENGTH {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        self.charge_hashing(in_size)?;
+        let input = self.bytes_from_mem(in_ptr, in_size as usize)?;
+        let digest = Blake2bHash::new(&input);
+        self.memory.set(out_ptr, &digest.value())?;
+        Ok(Ok(()))
+    }
+
+    /// Returns whether the signature is a valid signature of the message by the public key under
+    /// `scheme`.  Charged as the scheme's fixed cost plus hashing the message.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_signature(
+        &mut self,
+        scheme: SignatureScheme,
+        signature_ptr: u32,
+        signature_size: u32,
+        message_ptr: u32,
+        message_size: u32,
+        key_ptr: u32,
+        key_size: u32,
+    ) -> Result<bool, Trap> {
+        let wasm_costs = self.protocol_data().wasm_costs();
+        let verify_cost = match scheme {
+            SignatureScheme::Ed25519 => wasm_costs.verify_ed25519,
+            SignatureScheme::Secp256k1 => wasm_costs.verify_secp256k1,
+        };
+        self.gas(Gas::new(U512::from(verify_cost)))?;
+        self.charge_hashing(message_size)?;
+
+        let signature = self.bytes_from_mem(signature_ptr, signature_size as usize)?;
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let public_key = self.bytes_from_mem(key_ptr, key_size as usize)?;
+        Ok(scheme.verify(&signature, &message, &public_key))
+    }
+
+    /// Applies the arithmetic operation `op` to the two operands of `width` bytes and writes the
+    /// result to the output buffer in Wasm memory.  Charged at a fixed cost per operation.
+    fn bignum_op(
+        &mut self,
+        op: u32,
+        width: u32,
+        lhs_ptr: u32,
+        rhs_ptr: u32,
+        out_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let bignum_op_cost = self.protocol_data().wasm_costs().bignum_op;
+        self.gas(Gas::new(U512::from(bignum_op_cost)))?;
+
+        let op = match BigNumOp::try_from(op) {
+            Ok(op) => op,
+            Err(error) => return Ok(Err(error)),
+        };
+        let width = width as usize;
+        if width != bignum::U256_WIDTH && width != bignum::U512_WIDTH {
+            return Ok(Err(ApiError::InvalidArgument));
+        }
+        let lhs = self.bytes_from_mem(lhs_ptr, width)?;
+        let rhs = self.bytes_from_mem(rhs_ptr, width)?;
+        let output = match bignum::apply(op, &lhs, &rhs) {
+            Ok(output) => output,
+            Err(error) => return Ok(Err(error)),
+        };
+        self.memory.set(out_ptr, &output)?;
+        Ok(Ok(()))
+    }
+
+    /// Writes 32 bytes from the deploy's deterministic random stream to [dest_ptr] in Wasm memory.
+    fn random_bytes(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let bytes = self.context.random_bytes();
+        self.memory.set(dest_ptr, &bytes).map_err(Into::into)
+    }
+
+    /// Writes current block height to [dest_ptr] in Wasm memory.
+    fn get_block_height(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let block_height = self
+            .context
+            .get_block_height()
+            .into_bytes()
+            .map_err(Error::BytesRepr)?;
+        self.memory.set(dest_ptr, &block_height).map_err(Into::into)
     }
 
     /// Return some bytes from the memory and terminate the current `sub_call`. Note that the return
     /// type is `Trap`, indicating that this function will always kill the current Wasm instance.
     fn ret(&mut self, value_ptr: u32, value_size: usize) -> Trap {
         self.host_buffer = None;
-        let mem_get = self
-            .memory
-            .get(value_ptr, value_size)
-            .map_err(|e| Error::Interpreter(e.into()));
+        let mem_get = self.memory.get(value_ptr, value_size);
         match mem_get {
             Ok(buf) => {
                 // Set the result field in the runtime and return the proper element of the `Error`
@@ -1669,6 +2231,127 @@ where
         }
     }
 
+    /// Rejects a call to one of the Proof of Stake entry points reserved for the system account
+    /// unless it's made by the system account.
+    fn check_proof_of_stake_privileges(&self, args: &[CLValue]) -> Result<(), Error> {
+        const PRIVILEGED_METHODS: [&str; 4] =
+            ["finalize_payment", "slash", "distribute_rewards", "step"];
+
+        let method_name: String = match args.first().map(|arg| arg.to_owned().into_t()) {
+            Some(Ok(method_name)) => method_name,
+            _ => return Ok(()),
+        };
+        if PRIVILEGED_METHODS.contains(&method_name.as_str())
+            && self.context.account().public_key() != self.protocol_data().system_account()
+        {
+            return Err(Error::Revert(ApiError::PermissionDenied));
+        }
+        Ok(())
+    }
+
+    /// Rejects a call made by payment code unless it reads a purse balance, transfers into the
+    /// payment purse or calls one of the Proof of Stake entry points used to pay for a deploy.
+    fn check_payment_call(&mut self, key: Key, args: &[CLValue]) -> Result<(), Error> {
+        const PROOF_OF_STAKE_METHODS: [&str; 3] =
+            ["get_payment_purse", "set_refund_purse", "get_refund_purse"];
+
+        let method_name: String = match args.first().map(|arg| arg.to_owned().into_t()) {
+            Some(Ok(method_name)) => method_name,
+            _ => return Err(Error::PaymentSandboxViolation),
+        };
+        let permitted = if self.is_mint(key) {
+            match method_name.as_str() {
+                "balance" => true,
+                "transfer" => {
+                    let target: URef = Self::get_argument(args, 2)?;
+                    self.payment_purse_addr()? == Some(target.addr())
+                }
+                _ => false,
+            }
+        } else if self.is_proof_of_stake(key) {
+            PROOF_OF_STAKE_METHODS.contains(&method_name.as_str())
+        } else {
+            false
+        };
+        if !permitted {
+            return Err(Error::PaymentSandboxViolation);
+        }
+        Ok(())
+    }
+
+    /// Rejects a call made during payment finalization unless it's to one of the mint entry points
+    /// used to settle the payment or to the Proof of Stake contract's `finalize_payment`.
+    fn check_finalization_call(&self, key: Key, args: &[CLValue]) -> Result<(), Error> {
+        let method_name: String = match args.first().map(|arg| arg.to_owned().into_t()) {
+            Some(Ok(method_name)) => method_name,
+            _ => return Err(Error::FinalizationCallDenied(format!("{}", key))),
+        };
+        let permitted = if self.is_mint(key) {
+            MINT_FINALIZATION_METHODS.contains(&method_name.as_str())
+        } else if self.is_proof_of_stake(key) {
+            PROOF_OF_STAKE_FINALIZATION_METHODS.contains(&method_name.as_str())
+        } else {
+            false
+        };
+        if !permitted {
+            return Err(Error::FinalizationCallDenied(format!(
+                "{} of {}",
+                method_name, key
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the address of the Proof of Stake contract's payment purse.
+    fn payment_purse_addr(&mut self) -> Result<Option<Address>, Error> {
+        let pos_key = Key::from(self.protocol_data().proof_of_stake());
+        match self.context.read_gs_direct(&pos_key)? {
+            Some(StoredValue::Contract(contract)) => Ok(contract
+                .named_keys()
+                .get(POS_PAYMENT_PURSE)
+                .and_then(Key::as_uref)
+                .map(URef::addr)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Fails unless the deploying account may store new contracts: the capability must not have
+    /// been revoked from it and, on a permissioned network which restricts contract installs, it
+    /// must be on the deploy whitelist.
+    fn check_contract_install_permitted(&mut self) -> Result<(), Error> {
+        if !self.context.account().can_install_contracts() {
+            return Err(Error::Revert(ApiError::PermissionDenied));
+        }
+        if self.config.whitelist_mode() != WhitelistMode::ContractInstalls {
+            return Ok(());
+        }
+        let whitelist = match deploy_whitelist::whitelist_key(&self.protocol_data()) {
+            Some(whitelist_key) => self.context.read_gs_direct(&whitelist_key)?,
+            None => None,
+        };
+        if deploy_whitelist::is_whitelisted(whitelist, self.context.account().public_key()) {
+            Ok(())
+        } else {
+            Err(Error::Revert(ApiError::PermissionDenied))
+        }
+    }
+
+    /// Fails if writing a contract or account with `count` named keys would exceed the limit set
+    /// by the wasm costs.  The system account and the system contracts, which hold a named key per
+    /// bonded validator and delegation, aren't limited.
+    fn check_named_keys_count(&self, count: usize) -> Result<(), Error> {
+        let max = self.protocol_data().wasm_costs().max_named_keys_per_write as usize;
+        let base_key = self.context.base_key();
+        if count <= max
+            || self.context.account().public_key() == self.protocol_data().system_account()
+            || self.is_mint(base_key)
+            || self.is_proof_of_stake(base_key)
+        {
+            return Ok(());
+        }
+        Err(Error::TooManyNamedKeys { count, max })
+    }
+
     fn get_argument<T: FromBytes + CLTyped>(args: &[CLValue], index: usize) -> Result<T, Error> {
         let arg: CLValue = args
             .get(index)
@@ -1694,6 +2377,10 @@ where
         const METHOD_CREATE: &str = "create";
         const METHOD_BALANCE: &str = "balance";
         const METHOD_TRANSFER: &str = "transfer";
+        const METHOD_BURN: &str = "burn";
+        const METHOD_BALANCE_OF: &str = "balance_of";
+        const METHOD_BATCH_TRANSFER: &str = "batch_transfer";
+        const METHOD_SET_VESTING_SCHEDULE: &str = "set_vesting_schedule";
 
         let state = self.context.state();
         let access_rights = {
@@ -1707,6 +2394,7 @@ where
         let account = self.context.account();
         let base_key = self.protocol_data().mint().into();
         let blocktime = self.context.get_blocktime();
+        let block_height = self.context.get_block_height();
         let deploy_hash = self.context.get_deployhash();
         let gas_limit = self.context.gas_limit();
         let gas_counter = self.context.gas_counter();
@@ -1720,11 +2408,12 @@ where
             state,
             &mut named_keys,
             access_rights,
-            args.to_owned(),
+            args.to_vec().into(),
             authorization_keys,
             account,
             base_key,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -1735,6 +2424,7 @@ where
             phase,
             protocol_data,
         );
+        mint_context.set_min_main_purse_balance(self.min_main_purse_balance());
 
         let method_name: String = Self::get_argument(&args, 0)?;
 
@@ -1765,6 +2455,34 @@ where
                 let result: Result<(), mint::Error> = mint_context.transfer(source, target, amount);
                 CLValue::from_t(result).map_err(Self::reverter)?
             }
+            // Type: `fn burn(purse: URef, amount: U512) -> Result<(), Error>`
+            METHOD_BURN => {
+                let purse: URef = Self::get_argument(&args, 1)?;
+                let amount: U512 = Self::get_argument(&args, 2)?;
+                let result: Result<(), mint::Error> = mint_context.burn(purse, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn balance_of(purse: URef) -> Result<Option<U512>, Error>`
+            METHOD_BALANCE_OF => {
+                let purse: URef = Self::get_argument(&args, 1)?;
+                let result: Result<Option<U512>, mint::Error> = mint_context.balance_of(purse);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn batch_transfer(transfers: Vec<(URef, URef, U512)>) -> Result<(), Error>`
+            METHOD_BATCH_TRANSFER => {
+                let transfers: Vec<(URef, URef, U512)> = Self::get_argument(&args, 1)?;
+                let result: Result<(), mint::Error> = mint_context.batch_transfer(transfers);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn set_vesting_schedule(purse: URef, schedule: VestingSchedule) -> Result<(),
+            // Error>`
+            METHOD_SET_VESTING_SCHEDULE => {
+                let purse: URef = Self::get_argument(&args, 1)?;
+                let schedule: VestingSchedule = Self::get_argument(&args, 2)?;
+                let result: Result<(), mint::Error> =
+                    mint_context.set_vesting_schedule(purse, schedule);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -1782,6 +2500,12 @@ where
     ) -> Result<CLValue, Error> {
         const METHOD_BOND: &str = "bond";
         const METHOD_UNBOND: &str = "unbond";
+        const METHOD_WITHDRAW: &str = "withdraw";
+        const METHOD_STEP: &str = "step";
+        const METHOD_DELEGATE: &str = "delegate";
+        const METHOD_UNDELEGATE: &str = "undelegate";
+        const METHOD_SLASH: &str = "slash";
+        const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
         const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
         const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
         const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
@@ -1799,6 +2523,7 @@ where
         let account = self.context.account();
         let base_key = self.protocol_data().proof_of_stake().into();
         let blocktime = self.context.get_blocktime();
+        let block_height = self.context.get_block_height();
         let deploy_hash = self.context.get_deployhash();
         let gas_limit = self.context.gas_limit();
         let gas_counter = self.context.gas_counter();
@@ -1812,11 +2537,12 @@ where
             state,
             &mut named_keys,
             access_rights,
-            args.to_owned(),
+            args.to_vec().into(),
             authorization_keys,
             account,
             base_key,
             blocktime,
+            block_height,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -1835,6 +2561,12 @@ where
             self.module.clone(),
             runtime_context,
         );
+        runtime.deadline = self.deadline;
+        runtime.host_call_budget = self.host_call_budget.clone();
+        runtime.trace = self.trace.clone();
+        runtime.debugger = self.debugger.clone();
+        runtime.cancellation_token = self.cancellation_token.clone();
+        runtime.peak_memory_pages = Rc::clone(&self.peak_memory_pages);
 
         let method_name: String = Self::get_argument(&args, 0)?;
 
@@ -1866,11 +2598,53 @@ where
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
-            METHOD_GET_PAYMENT_PURSE => {
-                let rights_controlled_purse =
-                    runtime.get_payment_purse().map_err(Self::reverter)?;
-                CLValue::from_t(rights_controlled_purse).map_err(Self::reverter)?
-            }
+            METHOD_WITHDRAW => {
+                if !self.config.enable_bonding() {
+                    let err = Error::Revert(ApiError::Unhandled);
+                    return Err(err);
+                }
+
+                runtime.withdraw().map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_STEP => {
+                runtime.step().map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_DELEGATE => {
+                if !self.config.enable_bonding() {
+                    let err = Error::Revert(ApiError::Unhandled);
+                    return Err(err);
+                }
+
+                let delegator: PublicKey = runtime.context.get_caller();
+                let validator: PublicKey = Self::get_argument(&args, 1)?;
+                let amount: U512 = Self::get_argument(&args, 2)?;
+                let source_uref: URef = Self::get_argument(&args, 3)?;
+                runtime
+                    .delegate(delegator, validator, amount, source_uref)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_UNDELEGATE => {
+                if !self.config.enable_bonding() {
+                    let err = Error::Revert(ApiError::Unhandled);
+                    return Err(err);
+                }
+
+                let delegator: PublicKey = runtime.context.get_caller();
+                let validator: PublicKey = Self::get_argument(&args, 1)?;
+                let maybe_amount: Option<U512> = Self::get_argument(&args, 2)?;
+                runtime
+                    .undelegate(delegator, validator, maybe_amount)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_GET_PAYMENT_PURSE => {
+                let rights_controlled_purse =
+                    runtime.get_payment_purse().map_err(Self::reverter)?;
+                CLValue::from_t(rights_controlled_purse).map_err(Self::reverter)?
+            }
             METHOD_SET_REFUND_PURSE => {
                 let purse: URef = Self::get_argument(&args, 1)?;
                 runtime.set_refund_purse(purse).map_err(Self::reverter)?;
@@ -1883,11 +2657,24 @@ where
             METHOD_FINALIZE_PAYMENT => {
                 let amount_spent: U512 = Self::get_argument(&args, 1)?;
                 let account: PublicKey = Self::get_argument(&args, 2)?;
+                let refund_ratio: u8 = Self::get_argument(&args, 3)?;
                 runtime
-                    .finalize_payment(amount_spent, account)
+                    .finalize_payment(amount_spent, account, refund_ratio)
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_SLASH => {
+                let validator: PublicKey = Self::get_argument(&args, 1)?;
+                let slashed = runtime.slash(validator).map_err(Self::reverter)?;
+                CLValue::from_t(slashed).map_err(Self::reverter)?
+            }
+            METHOD_DISTRIBUTE_REWARDS => {
+                let excluded: Vec<PublicKey> = Self::get_argument(&args, 1)?;
+                let distributed = runtime
+                    .distribute_rewards(excluded)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(distributed).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -1897,16 +2684,52 @@ where
     }
 
     pub fn call_host_standard_payment(&mut self) -> Result<(), Error> {
-        let first_arg = match self.context.args().first() {
+        let first_arg = match self.context.args().get(0) {
             Some(cl_value) => cl_value.clone(),
             None => return Err(Error::InvalidContext),
         };
         let amount = first_arg.into_t()?;
-        self.pay(amount).map_err(Self::reverter)
+        match self.context.args().get(1) {
+            Some(cl_value) => {
+                let source: URef = cl_value.clone().into_t()?;
+                self.context.validate_uref(&source)?;
+                self.pay_from(source, amount).map_err(Self::reverter)
+            }
+            None => self.pay(amount).map_err(Self::reverter),
+        }
     }
 
     /// Calls contract living under a `key`, with supplied `args`.
     pub fn call_contract(&mut self, key: Key, args_bytes: Vec<u8>) -> Result<CLValue, Error> {
+        self.call_contract_entry_point(key, None, args_bytes)
+    }
+
+    /// Calls the entry point named `entry_point` of the contract living under `key`, with supplied
+    /// `args`.  The contract must have been stored with named entry points.
+    pub fn call_versioned_contract(
+        &mut self,
+        key: Key,
+        entry_point: String,
+        args_bytes: Vec<u8>,
+    ) -> Result<CLValue, Error> {
+        self.call_contract_entry_point(key, Some(entry_point), args_bytes)
+    }
+
+    /// Calls the function exported as `entry_point` by the contract living under `key`, or as
+    /// `call` if `entry_point` is `None`.
+    fn call_contract_entry_point(
+        &mut self,
+        key: Key,
+        entry_point: Option<String>,
+        args_bytes: Vec<u8>,
+    ) -> Result<CLValue, Error> {
+        // The first entry of the call stack is the context which started the execution, so it
+        // doesn't count towards the depth.
+        let max_call_depth = self.context.protocol_data().max_call_depth();
+        if self.call_stack.len() > max_call_depth as usize {
+            return Err(Error::CallDepthExceeded(max_call_depth));
+        }
+
         let contract = match self.context.read_gs(&key)? {
             Some(StoredValue::Contract(contract)) => contract,
             Some(_) => {
@@ -1928,6 +2751,8 @@ where
             });
         }
 
+        let export_name = dispatch_entry_point(&contract, entry_point, self.context.get_caller())?;
+
         let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes)?;
 
         let mut extra_urefs = vec![];
@@ -1943,6 +2768,16 @@ where
             self.context.validate_key(key)?;
         }
 
+        if self.is_proof_of_stake(key) {
+            self.check_proof_of_stake_privileges(&args)?;
+        }
+        if self.context.is_payment_code() {
+            self.check_payment_call(key, &args)?;
+        }
+        if self.context.phase() == Phase::FinalizePayment {
+            self.check_finalization_call(key, &args)?;
+        }
+
         if !self.config.use_system_contracts() {
             if self.is_mint(key) {
                 return self.call_host_mint(
@@ -1973,7 +2808,11 @@ where
 
         let mut named_keys = contract.take_named_keys();
 
-        let (instance, memory) = instance_and_memory(module.clone(), contract_version)?;
+        let (instance, memory) = wasm_backend::instantiate(
+            module.clone(),
+            contract_version,
+            self.context.protocol_data().wasm_costs().max_memory_pages,
+        )?;
 
         let access_rights = {
             let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -1989,15 +2828,19 @@ where
 
         let host_buffer = None;
 
+        let mut call_stack = self.call_stack.clone();
+        call_stack.push(key);
+
         let context = RuntimeContext::new(
             self.context.state(),
             &mut named_keys,
             access_rights,
-            args,
+            args.into(),
             self.context.authorization_keys().clone(),
             &self.context.account(),
             key,
             self.context.get_blocktime(),
+            self.context.get_block_height(),
             self.context.get_deployhash(),
             self.context.gas_limit(),
             self.context.gas_counter(),
@@ -2016,48 +2859,49 @@ where
             module,
             host_buffer,
             context,
+            call_stack,
+            deadline: self.deadline,
+            host_call_budget: self.host_call_budget.clone(),
+            trace: self.trace.clone(),
+            debugger: self.debugger.clone(),
+            cancellation_token: self.cancellation_token.clone(),
+            peak_memory_pages: Rc::clone(&self.peak_memory_pages),
         };
 
-        let result = instance.invoke_export("call", &[], &mut runtime);
+        let result = instance.invoke_export(&export_name, &mut runtime);
+        // Memory may have grown since the last host function call, and memory never shrinks
+        runtime.record_memory_pages();
 
         // The `runtime`'s context was initialized with our counter from before the call and any gas
         // charged by the sub-call was added to its counter - so let's copy the correct value of the
         // counter from there to our counter
         self.context.set_gas_counter(runtime.context.gas_counter());
 
-        let error = match result {
-            Err(error) => error,
+        match result {
             // If `Ok` and the `host_buffer` is `None`, the contract's execution succeeded but did
             // not explicitly call `runtime::ret()`.  Treat as though the execution
             // returned the unit type `()` as per Rust functions which don't specify a
             // return value.
-            Ok(_) => return Ok(runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?)),
-        };
-
-        if let Some(host_error) = error.as_host_error() {
+            Ok(()) => Ok(runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?)),
             // If the "error" was in fact a trap caused by calling `ret` then
             // this is normal operation and we should return the value captured
             // in the Runtime result field.
-            let downcasted_error = host_error.downcast_ref::<Error>().unwrap();
-            match downcasted_error {
-                Error::Ret(ref ret_urefs) => {
-                    // insert extra urefs returned from call
-                    let ret_urefs_map: HashMap<Address, HashSet<AccessRights>> =
-                        extract_access_rights_from_urefs(ret_urefs.clone());
-                    self.context.access_rights_extend(ret_urefs_map);
-                    // if ret has not set host_buffer consider it programmer error
-                    return runtime.take_host_buffer().ok_or(Error::ExpectedReturnValue);
-                }
-                error => return Err(error.clone()),
-            }
+            Err(Error::Ret(ret_urefs)) => {
+                // insert extra urefs returned from call
+                let ret_urefs_map: HashMap<Address, HashSet<AccessRights>> =
+                    extract_access_rights_from_urefs(ret_urefs);
+                self.context.access_rights_extend(ret_urefs_map);
+                // if ret has not set host_buffer consider it programmer error
+                runtime.take_host_buffer().ok_or(Error::ExpectedReturnValue)
+            }
+            Err(error) => Err(error),
         }
-
-        Err(Error::Interpreter(error.into()))
     }
 
     fn call_contract_host_buffer(
         &mut self,
         key: Key,
+        entry_point: Option<String>,
         args_bytes: Vec<u8>,
         result_size_ptr: u32,
         scoped_timer: &mut ScopedTimer,
@@ -2067,10 +2911,26 @@ where
             return Ok(Err(ApiError::HostBufferFull));
         }
 
+        // The callee's changes to global state are kept in their own scope, so that they can be
+        // discarded if it reverts while leaving the caller free to carry on.
         scoped_timer.pause();
-        let result = self.call_contract(key, args_bytes);
+        self.context.state().borrow_mut().begin_scope();
+        let result = self.call_contract_entry_point(key, entry_point, args_bytes);
         scoped_timer.unpause();
-        let result = result?;
+        let result = match result {
+            Ok(result) => {
+                self.context.state().borrow_mut().commit_scope();
+                result
+            }
+            Err(Error::Revert(api_error)) => {
+                self.context.state().borrow_mut().rollback_scope();
+                return Ok(Err(api_error));
+            }
+            Err(error) => {
+                self.context.state().borrow_mut().commit_scope();
+                return Err(error);
+            }
+        };
         let result_size = result.inner_bytes().len() as u32; // considered to be safe
 
         // leave the host buffer set to `None` if there's nothing to write there
@@ -2082,7 +2942,7 @@ where
 
         let result_size_bytes = result_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.memory.set(result_size_ptr, &result_size_bytes) {
-            return Err(Error::Interpreter(error.into()));
+            return Err(error);
         }
 
         Ok(Ok(()))
@@ -2112,7 +2972,7 @@ where
         let total_keys = self.context.named_keys().len() as u32;
         let total_keys_bytes = total_keys.to_le_bytes();
         if let Err(error) = self.memory.set(total_keys_ptr, &total_keys_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(error.into());
         }
 
         if total_keys == 0 {
@@ -2130,7 +2990,96 @@ where
 
         let length_bytes = length.to_le_bytes();
         if let Err(error) = self.memory.set(result_size_ptr, &length_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(error.into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Writes the purses registered to the account whose context this is to the host buffer.
+    fn list_purses(
+        &mut self,
+        total_purses_ptr: u32,
+        result_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let purses = self.context.registered_purses()?;
+
+        let total_purses = purses.len() as u32;
+        if let Err(error) = self
+            .memory
+            .set(total_purses_ptr, &total_purses.to_le_bytes())
+        {
+            return Err(error.into());
+        }
+
+        if total_purses == 0 {
+            // No need to do anything else, we leave host buffer empty.
+            return Ok(Ok(()));
+        }
+
+        let purses = CLValue::from_t(purses).map_err(Error::CLValue)?;
+
+        let length = purses.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buffer(purses) {
+            return Ok(Err(error));
+        }
+
+        if let Err(error) = self.memory.set(result_size_ptr, &length.to_le_bytes()) {
+            return Err(error.into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Writes up to `limit` entries of the context-local partition of global state, starting after
+    /// the key serialized at `cursor_ptr`, to the host buffer.
+    fn list_local(
+        &mut self,
+        cursor_ptr: u32,
+        cursor_size: u32,
+        limit: u32,
+        total_entries_ptr: u32,
+        result_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let cursor_bytes = self.bytes_from_mem(cursor_ptr, cursor_size as usize)?;
+        let cursor: Option<Key> = bytesrepr::deserialize(cursor_bytes).map_err(Error::BytesRepr)?;
+        let limit = cmp::min(limit, MAX_LIST_LOCAL_ENTRIES) as usize;
+        let entries = self.context.list_ls(cursor, limit)?;
+
+        let total_entries = entries.len() as u32;
+        if let Err(error) = self
+            .memory
+            .set(total_entries_ptr, &total_entries.to_le_bytes())
+        {
+            return Err(error.into());
+        }
+
+        if total_entries == 0 {
+            // No need to do anything else, we leave host buffer empty.
+            return Ok(Ok(()));
+        }
+
+        // `CLValue` is not `CLTyped`, so the entries are passed as untyped bytes.
+        let entries_bytes = entries.into_bytes().map_err(Error::BytesRepr)?;
+        let length = entries_bytes.len() as u32;
+        if let Err(error) =
+            self.write_host_buffer(CLValue::from_components(CLType::Any, entries_bytes))
+        {
+            return Ok(Err(error));
+        }
+
+        if let Err(error) = self.memory.set(result_size_ptr, &length.to_le_bytes()) {
+            return Err(error.into());
         }
 
         Ok(Ok(()))
@@ -2140,8 +3089,12 @@ where
         &mut self,
         fn_bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
+        abi: Option<ContractAbi>,
     ) -> Result<[u8; 32], Error> {
-        let contract = Contract::new(fn_bytes, named_keys, self.context.protocol_version());
+        self.check_contract_install_permitted()?;
+        self.check_named_keys_count(named_keys.len())?;
+        let contract =
+            Contract::new(fn_bytes, named_keys, self.context.protocol_version()).with_abi(abi);
         let contract_addr = self
             .context
             .store_function(StoredValue::Contract(contract))?;
@@ -2155,8 +3108,30 @@ where
         &mut self,
         fn_bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
+        abi: Option<ContractAbi>,
+    ) -> Result<[u8; 32], Error> {
+        self.check_contract_install_permitted()?;
+        self.check_named_keys_count(named_keys.len())?;
+        let contract =
+            Contract::new(fn_bytes, named_keys, self.context.protocol_version()).with_abi(abi);
+        let new_hash = self
+            .context
+            .store_function_at_hash(StoredValue::Contract(contract))?;
+        Ok(new_hash)
+    }
+
+    /// Stores the current module as a contract at a new hash, callable only through the entry
+    /// points of `abi`.
+    fn store_versioned_contract(
+        &mut self,
+        named_keys: BTreeMap<String, Key>,
+        abi: ContractAbi,
     ) -> Result<[u8; 32], Error> {
-        let contract = Contract::new(fn_bytes, named_keys, self.context.protocol_version());
+        self.check_contract_install_permitted()?;
+        self.check_named_keys_count(named_keys.len())?;
+        let module_bytes = self.get_entry_points_module(&abi)?;
+        let contract = Contract::new(module_bytes, named_keys, self.context.protocol_version())
+            .with_entry_points(abi);
         let new_hash = self
             .context
             .store_function_at_hash(StoredValue::Contract(contract))?;
@@ -2166,9 +3141,7 @@ where
     /// Writes function address (`hash_bytes`) into the Wasm memory (at
     /// `dest_ptr` pointer).
     fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
-        self.memory
-            .set(dest_ptr, &hash_bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+        self.memory.set(dest_ptr, &hash_bytes).map_err(Into::into)
     }
 
     /// Generates new unforgable reference and adds it to the context's
@@ -2178,10 +3151,13 @@ where
         let uref = self.context.new_uref(StoredValue::CLValue(cl_value))?;
         self.memory
             .set(uref_ptr, &uref.into_bytes().map_err(Error::BytesRepr)?)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+            .map_err(Into::into)
     }
 
     /// Writes `value` under `key` in GlobalState.
+    ///
+    /// If `key` is a `URef`, `value` must be of the same `CLType` as the value already stored
+    /// there.
     fn write(
         &mut self,
         key_ptr: u32,
@@ -2191,11 +3167,20 @@ where
     ) -> Result<(), Trap> {
         let key = self.key_from_mem(key_ptr, key_size)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.access_key(TraceEntry::Write(key))?;
+        self.context.validate_cl_type(&key, &cl_value)?;
         self.context
             .write_gs(key, StoredValue::CLValue(cl_value))
             .map_err(Into::into)
     }
 
+    /// Removes the value under `key` from global state.
+    fn delete(&mut self, key_ptr: u32, key_size: u32) -> Result<(), Trap> {
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        self.access_key(TraceEntry::Delete(key))?;
+        self.context.delete_gs(key).map_err(Into::into)
+    }
+
     /// Writes `value` under a key derived from `key` in the "local cluster" of
     /// GlobalState
     fn write_local(
@@ -2207,6 +3192,10 @@ where
     ) -> Result<(), Trap> {
         let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.access_key(TraceEntry::Write(Key::local(
+            self.context.seed(),
+            &key_bytes,
+        )))?;
         self.context
             .write_ls(&key_bytes, cl_value)
             .map_err(Into::into)
@@ -2222,6 +3211,7 @@ where
     ) -> Result<(), Trap> {
         let key = self.key_from_mem(key_ptr, key_size)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.access_key(TraceEntry::Add(key))?;
         self.context
             .add_gs(key, StoredValue::CLValue(cl_value))
             .map_err(Into::into)
@@ -2238,6 +3228,7 @@ where
     ) -> Result<(), Trap> {
         let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.access_key(TraceEntry::Add(Key::local(self.context.seed(), &key_bytes)))?;
         self.context
             .add_ls(&key_bytes, cl_value)
             .map_err(Into::into)
@@ -2253,6 +3244,31 @@ where
         key_ptr: u32,
         key_size: u32,
         output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        self.read_checked(key_ptr, key_size, None, output_size_ptr)
+    }
+
+    /// Similar to `read`, but returns `ApiError::CLTypeMismatch` instead of writing the value to
+    /// the host buffer if it isn't of the `CLType` serialized at `cl_type_ptr`.
+    fn read_typed(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        cl_type_ptr: u32,
+        cl_type_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let cl_type_bytes = self.bytes_from_mem(cl_type_ptr, cl_type_size as usize)?;
+        let expected: CLType = bytesrepr::deserialize(cl_type_bytes).map_err(Error::from)?;
+        self.read_checked(key_ptr, key_size, Some(&expected), output_size_ptr)
+    }
+
+    fn read_checked(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        expected: Option<&CLType>,
+        output_size_ptr: u32,
     ) -> Result<Result<(), ApiError>, Trap> {
         if !self.can_write_to_host_buffer() {
             // Exit early if the host buffer is already occupied
@@ -2260,10 +3276,16 @@ where
         }
 
         let key = self.key_from_mem(key_ptr, key_size)?;
+        self.access_key(TraceEntry::Read(key))?;
         let cl_value = match self.context.read_gs(&key)? {
             Some(stored_value) => CLValue::try_from(stored_value).map_err(Error::TypeMismatch)?,
             None => return Ok(Err(ApiError::ValueNotFound)),
         };
+        if let Some(expected) = expected {
+            if check_cl_type(expected, cl_value.cl_type()).is_err() {
+                return Ok(Err(ApiError::CLTypeMismatch));
+            }
+        }
 
         let value_size = cl_value.inner_bytes().len() as u32;
         if let Err(error) = self.write_host_buffer(cl_value) {
@@ -2272,7 +3294,7 @@ where
 
         let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(error.into());
         }
 
         Ok(Ok(()))
@@ -2292,6 +3314,10 @@ where
         }
 
         let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+        self.access_key(TraceEntry::Read(Key::local(
+            self.context.seed(),
+            &key_bytes,
+        )))?;
 
         let cl_value = match self.context.read_ls(&key_bytes)? {
             Some(cl_value) => cl_value,
@@ -2305,7 +3331,7 @@ where
 
         let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(error.into());
         }
 
         Ok(Ok(()))
@@ -2410,6 +3436,129 @@ where
         }
     }
 
+    fn update_associated_keys(
+        &mut self,
+        updates_ptr: u32,
+        updates_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let updates: Vec<AssociatedKeysUpdate> = {
+            let bytes = self.bytes_from_mem(updates_ptr, updates_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        Ok(self.context.update_associated_keys(&updates)?)
+    }
+
+    fn can_manage_keys(&self) -> bool {
+        self.context.can_manage_keys()
+    }
+
+    fn rotate_associated_key(
+        &mut self,
+        account_ptr: u32,
+        account_size: u32,
+        old_key_ptr: u32,
+        old_key_size: u32,
+        new_key_ptr: u32,
+        new_key_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let account: PublicKey = {
+            let bytes = self.bytes_from_mem(account_ptr, account_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let old_key: PublicKey = {
+            let bytes = self.bytes_from_mem(old_key_ptr, old_key_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let new_key: PublicKey = {
+            let bytes = self.bytes_from_mem(new_key_ptr, new_key_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        match self
+            .context
+            .rotate_associated_key(account, old_key, new_key)
+        {
+            Ok(()) => Ok(Ok(())),
+            Err(Error::AddKeyFailure(error)) => Ok(Err(error.into())),
+            Err(Error::RemoveKeyFailure(error)) => Ok(Err(error.into())),
+            Err(Error::UpdateKeyFailure(error)) => Ok(Err(error.into())),
+            Err(Error::KeyNotFound(_)) => Ok(Err(ApiError::ValueNotFound)),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Grants or revokes the ability of the account whose public key is at `public_key_ptr` to
+    /// store new contracts.
+    fn set_contract_install_permission(
+        &mut self,
+        public_key_ptr: u32,
+        public_key_size: u32,
+        allowed: bool,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let account: PublicKey = {
+            let bytes = self.bytes_from_mem(public_key_ptr, public_key_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        match self
+            .context
+            .set_contract_install_permission(account, allowed)
+        {
+            Ok(()) => Ok(Ok(())),
+            Err(Error::Revert(api_error)) => Ok(Err(api_error)),
+            Err(Error::KeyNotFound(_)) => Ok(Err(ApiError::ValueNotFound)),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Registers a call to the contract under the key serialized at `key_ptr` with the arguments
+    /// serialized at `args_ptr`, to be made by the system once the block time reaches the one
+    /// serialized at `blocktime_ptr`.  The call's gas budget is charged to the current deploy, on
+    /// top of the storage charge for writing the call's own entry, as for any other write.
+    #[allow(clippy::too_many_arguments)]
+    fn schedule_call(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        args_ptr: u32,
+        args_size: u32,
+        blocktime_ptr: u32,
+        blocktime_size: u32,
+        gas_budget_ptr: u32,
+        gas_budget_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let key: Key = {
+            let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let args_bytes = self.bytes_from_mem(args_ptr, args_size as usize)?;
+        let earliest_blocktime: BlockTime = {
+            let bytes = self.bytes_from_mem(blocktime_ptr, blocktime_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let gas_budget: u64 = {
+            let bytes = self.bytes_from_mem(gas_budget_ptr, gas_budget_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        // The call is made with the registrant's access rights later on, so any keys it is given
+        // must be valid now.
+        self.context.validate_key(&key)?;
+        let args: Vec<CLValue> =
+            bytesrepr::deserialize(args_bytes.clone()).map_err(Error::BytesRepr)?;
+        for arg in &args {
+            for uref in extract_urefs(arg)? {
+                self.context.validate_key(&Key::from(uref))?;
+            }
+        }
+
+        self.gas(Gas::new(U512::from(gas_budget)))?;
+
+        self.context
+            .schedule_call(key, args_bytes, earliest_blocktime, gas_budget)?;
+        Ok(Ok(()))
+    }
+
     /// Looks up the public mint contract key in the context's protocol data.
     ///
     /// Returned URef is already attenuated depending on the calling account.
@@ -2434,6 +3583,14 @@ where
         self.context.attenuate_uref(standard_payment)
     }
 
+    /// Looks up the public key recovery contract key in the context's protocol data.
+    ///
+    /// Returned URef is already attenuated depending on the calling account.
+    fn get_key_recovery_contract_uref(&self) -> URef {
+        let key_recovery = self.context.protocol_data().key_recovery();
+        self.context.attenuate_uref(key_recovery)
+    }
+
     /// Calls the "create" method on the mint contract at the given mint
     /// contract key
     fn mint_create(&mut self, mint_contract_key: Key) -> Result<URef, Error> {
@@ -2450,7 +3607,9 @@ where
 
     fn create_purse(&mut self) -> Result<URef, Error> {
         let mint_contract_key = self.get_mint_contract_uref().into();
-        self.mint_create(mint_contract_key)
+        let purse = self.mint_create(mint_contract_key)?;
+        self.context.register_purse(purse)?;
+        Ok(purse)
     }
 
     /// Calls the "transfer" method on the mint contract at the given mint
@@ -2472,6 +3631,81 @@ where
         Ok(result.map_err(system_contract_errors::Error::from)?)
     }
 
+    /// Calls the "batch_transfer" method on the mint contract at the given mint
+    /// contract key
+    fn mint_batch_transfer(
+        &mut self,
+        mint_contract_key: Key,
+        transfers: Vec<(URef, URef, U512)>,
+    ) -> Result<(), Error> {
+        let args_bytes = {
+            let args = ("batch_transfer", transfers);
+            ArgsParser::parse(args)?.into_bytes()?
+        };
+
+        let result = self.call_contract(mint_contract_key, args_bytes)?;
+        let result: Result<(), mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
+    /// Calls the "burn" method on the mint contract at the given mint
+    /// contract key
+    fn mint_burn(
+        &mut self,
+        mint_contract_key: Key,
+        purse: URef,
+        amount: U512,
+    ) -> Result<(), Error> {
+        let args_bytes = {
+            let args = ("burn", purse, amount);
+            ArgsParser::parse(args)?.into_bytes()?
+        };
+
+        let result = self.call_contract(mint_contract_key, args_bytes)?;
+        let result: Result<(), mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
+    /// Calls the "balance_of" method on the mint contract at the given mint
+    /// contract key
+    fn mint_balance_of(
+        &mut self,
+        mint_contract_key: Key,
+        purse: URef,
+    ) -> Result<Option<U512>, Error> {
+        let args_bytes = {
+            let args = ("balance_of", purse);
+            ArgsParser::parse(args)?.into_bytes()?
+        };
+
+        let result = self.call_contract(mint_contract_key, args_bytes)?;
+        let result: Result<Option<U512>, mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
+    /// Returns the main purse of the account the deploy runs as and the minimum balance the mint
+    /// holds it to, if any.  Only session code is held to the minimum, so that the payment code can
+    /// still pay for the deploy.
+    fn min_main_purse_balance(&self) -> Option<(URef, U512)> {
+        if self.context.phase() != Phase::Session {
+            return None;
+        }
+        let min_main_purse_balance = self.config.min_main_purse_balance()?;
+        let main_purse = self.context.account().main_purse().remove_access_rights();
+        Some((main_purse, min_main_purse_balance))
+    }
+
+    /// Returns the error reported to the caller of a failed transfer.  Only a transfer which the
+    /// mint refused for leaving the main purse below its minimum balance is told apart.
+    fn transfer_error(error: Error) -> ApiError {
+        match error {
+            Error::SystemContract(system_contract_errors::Error::Mint(
+                error @ mint::Error::BelowMinimumBalance,
+            )) => error.into(),
+            _ => ApiError::Transfer,
+        }
+    }
+
     /// Creates a new account at a given public key, transferring a given amount
     /// of motes from the given source purse to the new account's purse.
     fn transfer_to_new_account(
@@ -2490,6 +3724,13 @@ where
             return Ok(Err(ApiError::Transfer));
         }
 
+        // The new account's purse must start out holding at least the configured minimum.
+        if let Some(min_account_creation_balance) = self.config.min_account_creation_balance() {
+            if amount < min_account_creation_balance {
+                return Ok(Err(ApiError::Transfer));
+            }
+        }
+
         let target_purse = self.mint_create(mint_contract_key)?;
 
         if source == target_purse {
@@ -2523,7 +3764,7 @@ where
                 self.context.write_account(target_key, account)?;
                 Ok(Ok(TransferredTo::NewAccount))
             }
-            Err(_) => Ok(Err(ApiError::Transfer)),
+            Err(error) => Ok(Err(Self::transfer_error(error))),
         }
     }
 
@@ -2543,13 +3784,14 @@ where
 
         match self.mint_transfer(mint_contract_key, source, target, amount) {
             Ok(_) => Ok(Ok(TransferredTo::ExistingAccount)),
-            Err(_) => Ok(Err(ApiError::Transfer)),
+            Err(Error::PaymentSandboxViolation) => Err(Error::PaymentSandboxViolation),
+            Err(error) => Ok(Err(Self::transfer_error(error))),
         }
     }
 
     /// Transfers `amount` of motes from default purse of the account to
     /// `target` account. If that account does not exist, creates one.
-    fn transfer_to_account(
+    pub(crate) fn transfer_to_account(
         &mut self,
         target: PublicKey,
         amount: U512,
@@ -2616,13 +3858,119 @@ where
 
         let mint_contract_key = self.get_mint_contract_uref().into();
 
-        if self
-            .mint_transfer(mint_contract_key, source, target, amount)
-            .is_ok()
-        {
-            Ok(Ok(()))
-        } else {
-            Ok(Err(ApiError::Transfer))
+        match self.mint_transfer(mint_contract_key, source, target, amount) {
+            Ok(()) => Ok(Ok(())),
+            Err(Error::PaymentSandboxViolation) => Err(Error::PaymentSandboxViolation),
+            Err(error) => Ok(Err(Self::transfer_error(error))),
+        }
+    }
+
+    /// Applies the serialized list of `(source, target, amount)` transfers in a single call to the
+    /// mint, so that either all of them take effect or none do.
+    fn batch_transfer(
+        &mut self,
+        transfers_ptr: u32,
+        transfers_size: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let transfers: Vec<(URef, URef, U512)> = {
+            let bytes = self.bytes_from_mem(transfers_ptr, transfers_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        // The purses are nested inside tuples, where the generic argument check done when calling
+        // a contract doesn't look for them.
+        for (source, target, _) in &transfers {
+            self.context.validate_uref(source)?;
+            self.context.validate_uref(target)?;
+        }
+
+        let mint_contract_key = self.get_mint_contract_uref().into();
+
+        match self.mint_batch_transfer(mint_contract_key, transfers) {
+            Ok(()) => Ok(Ok(())),
+            Err(Error::PaymentSandboxViolation) => Err(Error::PaymentSandboxViolation),
+            Err(error) => Ok(Err(Self::transfer_error(error))),
+        }
+    }
+
+    /// Transfers `amount` of motes from `source` purse to `target` purse and, on success, records
+    /// a [`TransferReceipt`] under a new read-only URef which is written to `dest_ptr`.
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_from_purse_to_purse_with_proof(
+        &mut self,
+        source_ptr: u32,
+        source_size: u32,
+        target_ptr: u32,
+        target_size: u32,
+        amount_ptr: u32,
+        amount_size: u32,
+        dest_ptr: u32,
+        dest_size: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let source: URef = {
+            let bytes = self.bytes_from_mem(source_ptr, source_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        let target: URef = {
+            let bytes = self.bytes_from_mem(target_ptr, target_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        let amount: U512 = {
+            let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        let mint_contract_key = self.get_mint_contract_uref().into();
+
+        match self.mint_transfer(mint_contract_key, source, target, amount) {
+            Ok(()) => {}
+            Err(Error::PaymentSandboxViolation) => return Err(Error::PaymentSandboxViolation),
+            Err(error) => return Ok(Err(Self::transfer_error(error))),
+        }
+
+        let receipt = TransferReceipt::new(self.context.get_deployhash(), source, target, amount);
+        let receipt_value = StoredValue::CLValue(CLValue::from_t(receipt)?);
+        let receipt_uref = self.context.new_read_only_uref(receipt_value)?;
+
+        let receipt_uref_bytes = receipt_uref.into_bytes().map_err(Error::BytesRepr)?;
+        assert_eq!(dest_size, receipt_uref_bytes.len() as u32);
+        self.memory.set(dest_ptr, &receipt_uref_bytes)?;
+
+        Ok(Ok(()))
+    }
+
+    /// Burns `amount` of motes from `purse`, which the caller must hold with write access.
+    fn burn(
+        &mut self,
+        purse_ptr: u32,
+        purse_size: u32,
+        amount_ptr: u32,
+        amount_size: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let purse: URef = {
+            let bytes = self.bytes_from_mem(purse_ptr, purse_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        let amount: U512 = {
+            let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        if !purse.is_writeable() || self.context.validate_uref(&purse).is_err() {
+            return Ok(Err(ApiError::InvalidPurse));
+        }
+
+        let mint_contract_key = self.get_mint_contract_uref().into();
+
+        match self.mint_burn(mint_contract_key, purse, amount) {
+            Ok(()) => Ok(Ok(())),
+            Err(Error::SystemContract(system_contract_errors::Error::Mint(error))) => {
+                Ok(Err(error.into()))
+            }
+            Err(error) => Err(error),
         }
     }
 
@@ -2659,6 +4007,94 @@ where
         Ok(ret)
     }
 
+    /// Writes the main purse of the account the deploy runs as and the minimum balance the mint
+    /// holds it to, if any, to the host buffer.  Only the mint may call this.
+    fn get_min_main_purse_balance(
+        &mut self,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.is_mint(self.context.base_key()) {
+            return Ok(Err(ApiError::PermissionDenied));
+        }
+        let min_main_purse_balance = self.min_main_purse_balance();
+        self.write_host_buffer_with_size(min_main_purse_balance, output_size_ptr)
+    }
+
+    /// Writes the balance of the purse serialized at `purse_ptr` to the host buffer.  Unlike
+    /// `get_balance`, the lookup goes through the mint, which only requires the purse to be held
+    /// with READ rights.
+    fn get_balance_of(
+        &mut self,
+        purse_ptr: u32,
+        purse_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let purse: URef = {
+            let bytes = self.bytes_from_mem(purse_ptr, purse_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        self.context.validate_uref(&purse)?;
+
+        let mint_contract_key = self.get_mint_contract_uref().into();
+
+        let balance = match self.mint_balance_of(mint_contract_key, purse) {
+            Ok(Some(balance)) => balance,
+            Ok(None) => return Ok(Err(ApiError::InvalidPurse)),
+            Err(Error::SystemContract(system_contract_errors::Error::Mint(error))) => {
+                return Ok(Err(error.into()))
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        self.write_host_buffer_with_size(balance, output_size_ptr)
+    }
+
+    /// Writes the ABI descriptor of the contract stored under the key serialized at `key_ptr` to
+    /// the host buffer.  Returns `ApiError::ValueNotFound` if there is no such contract or it was
+    /// stored without a descriptor.
+    fn get_contract_abi(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        result_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let key = match self.key_from_mem(key_ptr, key_size) {
+            Ok(key) => key,
+            Err(Error::BytesRepr(error)) => return Ok(Err(error.into())),
+            Err(error) => return Err(error.into()),
+        };
+
+        let abi = match self.context.read_gs(&key)? {
+            Some(StoredValue::Contract(contract)) => match contract.abi() {
+                Some(abi) => abi.clone(),
+                None => return Ok(Err(ApiError::ValueNotFound)),
+            },
+            _ => return Ok(Err(ApiError::ValueNotFound)),
+        };
+
+        let abi_cl_value = match CLValue::from_t(abi) {
+            Ok(cl_value) => cl_value,
+            Err(error) => return Ok(Err(error.into())),
+        };
+
+        let abi_size = abi_cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buffer(abi_cl_value) {
+            return Ok(Err(error));
+        }
+
+        if let Err(error) = self.memory.set(result_size_ptr, &abi_size.to_le_bytes()) {
+            return Err(error.into());
+        }
+
+        Ok(Ok(()))
+    }
+
     fn get_balance_host_buffer(
         &mut self,
         purse_ptr: u32,
@@ -2695,7 +4131,7 @@ where
 
         let balance_size_bytes = balance_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.memory.set(output_size_ptr, &balance_size_bytes) {
-            return Err(Error::Interpreter(error.into()));
+            return Err(error);
         }
 
         Ok(Ok(()))
@@ -2747,6 +4183,7 @@ where
             Ok(SystemContractType::Mint) => self.get_mint_contract_uref(),
             Ok(SystemContractType::ProofOfStake) => self.get_pos_contract_uref(),
             Ok(SystemContractType::StandardPayment) => self.get_standard_payment_contract_uref(),
+            Ok(SystemContractType::KeyRecovery) => self.get_key_recovery_contract_uref(),
             Err(error) => return Ok(Err(error)),
         };
 
@@ -2754,7 +4191,7 @@ where
         let attenuated_uref_bytes = attenuated_uref.into_bytes().map_err(Error::BytesRepr)?;
         match self.memory.set(dest_ptr, &attenuated_uref_bytes) {
             Ok(_) => Ok(Ok(())),
-            Err(error) => Err(Error::Interpreter(error.into()).into()),
+            Err(error) => Err(error.into()),
         }
     }
 
@@ -2801,14 +4238,14 @@ where
         // as whole.
         let sliced_buf = &serialized_value[..cmp::min(dest_size, serialized_value.len())];
         if let Err(error) = self.memory.set(dest_ptr, sliced_buf) {
-            return Err(Error::Interpreter(error.into()));
+            return Err(error);
         }
 
         let bytes_written = sliced_buf.len() as u32;
         let bytes_written_data = bytes_written.to_le_bytes();
 
         if let Err(error) = self.memory.set(bytes_written_ptr, &bytes_written_data) {
-            return Err(Error::Interpreter(error.into()));
+            return Err(error);
         }
 
         Ok(Ok(()))
@@ -1,12 +1,10 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    fmt::Write,
-};
+use std::collections::BTreeSet;
 
 use engine_shared::stored_value::StoredValue;
 use engine_storage::global_state::StateReader;
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    Delegations, DelegationsProvider, MintProvider, ProofOfStake, Queue, QueueProvider,
+    RuntimeProvider, Stakes, StakesProvider, BONDING_KEY, UNBONDING_DELAY_KEY, UNBONDING_KEY,
 };
 use types::{
     account::PublicKey, bytesrepr::ToBytes, system_contract_errors::pos::Error, ApiError,
@@ -15,9 +13,6 @@ use types::{
 
 use crate::{execution, runtime::Runtime};
 
-const BONDING_KEY: u8 = 1;
-const UNBONDING_KEY: u8 = 2;
-
 // TODO: Update MintProvider to better handle errors
 impl<'a, R> MintProvider for Runtime<'a, R>
 where
@@ -93,6 +88,17 @@ where
             .write_ls(&key, value)
             .expect("should write local state")
     }
+
+    fn read_unbonding_delay(&mut self) -> u64 {
+        let key = match self.context.named_keys_get(UNBONDING_DELAY_KEY).cloned() {
+            Some(key) => key,
+            None => return 0,
+        };
+        match self.context.read_gs(&key) {
+            Ok(Some(StoredValue::CLValue(cl_value))) => cl_value.into_t().expect("should convert"),
+            _ => panic!("should read unbonding delay"),
+        }
+    }
 }
 
 // TODO: Update RuntimeProvider to better handle errors
@@ -126,6 +132,10 @@ where
     fn get_caller(&self) -> PublicKey {
         self.context.get_caller()
     }
+
+    fn get_system_account(&self) -> PublicKey {
+        self.protocol_data().system_account()
+    }
 }
 
 impl<'a, R> StakesProvider for Runtime<'a, R>
@@ -134,52 +144,12 @@ where
     R::Error: Into<execution::Error>,
 {
     fn read(&self) -> Result<Stakes, Error> {
-        let mut stakes = BTreeMap::new();
-        for (name, _) in self.context.named_keys().iter() {
-            let mut split_name = name.split('_');
-            if Some("v") != split_name.next() {
-                continue;
-            }
-            let hex_key = split_name
-                .next()
-                .ok_or(Error::StakesKeyDeserializationFailed)?;
-            if hex_key.len() != 64 {
-                return Err(Error::StakesKeyDeserializationFailed);
-            }
-            let mut key_bytes = [0u8; 32];
-            let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
-                .map_err(|_| Error::StakesKeyDeserializationFailed)?;
-            debug_assert!(_bytes_written == key_bytes.len());
-            let pub_key = PublicKey::ed25519_from(key_bytes);
-            let balance = split_name
-                .next()
-                .and_then(|b| U512::from_dec_str(b).ok())
-                .ok_or(Error::StakesDeserializationFailed)?;
-            stakes.insert(pub_key, balance);
-        }
-        if stakes.is_empty() {
-            return Err(Error::StakesNotFound);
-        }
-        Ok(Stakes(stakes))
+        Stakes::from_names(self.context.named_keys().keys().map(String::as_str))
     }
 
     fn write(&mut self, stakes: &Stakes) {
         // Encode the stakes as a set of uref names.
-        let mut new_urefs: BTreeSet<String> = stakes
-            .0
-            .iter()
-            .map(|(pub_key, balance)| {
-                let key_bytes = pub_key.value();
-                let mut hex_key = String::with_capacity(64);
-                for byte in &key_bytes[..32] {
-                    write!(hex_key, "{:02x}", byte).expect("Writing to a string cannot fail");
-                }
-                let mut uref = String::new();
-                uref.write_fmt(format_args!("v_{}_{}", hex_key, balance))
-                    .expect("Writing to a string cannot fail");
-                uref
-            })
-            .collect();
+        let mut new_urefs: BTreeSet<String> = stakes.strings().collect();
         // Remove and add urefs to update the contract's known urefs accordingly.
         let mut removes = Vec::new();
         for (name, _) in self.context.named_keys().iter() {
@@ -198,6 +168,34 @@ where
     }
 }
 
+impl<'a, R> DelegationsProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn read_delegations(&self) -> Result<Delegations, Error> {
+        Delegations::from_names(self.context.named_keys().keys().map(String::as_str))
+    }
+
+    fn write_delegations(&mut self, delegations: &Delegations) {
+        let mut new_urefs: BTreeSet<String> = delegations.strings().collect();
+        let mut removes = Vec::new();
+        for (name, _) in self.context.named_keys().iter() {
+            if name.starts_with("d_") && !new_urefs.remove(name) {
+                removes.push(name.to_owned())
+            }
+        }
+        for name in removes.iter() {
+            self.context.remove_key(name).expect("should remove key")
+        }
+        for name in new_urefs {
+            self.context
+                .put_key(name, Key::Hash([0; 32]))
+                .expect("should put key")
+        }
+    }
+}
+
 impl<'a, R> ProofOfStake for Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
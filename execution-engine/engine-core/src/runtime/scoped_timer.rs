@@ -89,6 +89,7 @@ impl Drop for ScopedTimer {
             FunctionIndex::WriteFuncIndex => "host_function_write",
             FunctionIndex::WriteLocalFuncIndex => "host_function_write_local",
             FunctionIndex::ReadFuncIndex => "host_function_read_value",
+            FunctionIndex::ReadTypedFuncIndex => "host_function_read_value_typed",
             FunctionIndex::ReadLocalFuncIndex => "host_function_read_value_local",
             FunctionIndex::AddFuncIndex => "host_function_add",
             FunctionIndex::AddLocalFuncIndex => "host_function_add_local",
@@ -101,7 +102,24 @@ impl Drop for ScopedTimer {
             FunctionIndex::PutKeyFuncIndex => "host_function_put_key",
             FunctionIndex::StoreFnIndex => "host_function_store_function",
             FunctionIndex::StoreFnAtHashIndex => "host_function_store_function_at_hash",
+            FunctionIndex::StoreFnWithAbiIndex => "host_function_store_function_with_abi",
+            FunctionIndex::StoreFnAtHashWithAbiIndex => {
+                "host_function_store_function_at_hash_with_abi"
+            }
+            FunctionIndex::GetContractAbiIndex => "host_function_get_contract_abi",
+            FunctionIndex::StoreVersionedContractIndex => "host_function_store_versioned_contract",
+            FunctionIndex::CallVersionedContractIndex => "host_function_call_versioned_contract",
+            FunctionIndex::GetImmediateCallerIndex => "host_function_get_immediate_caller",
+            FunctionIndex::GetCallStackIndex => "host_function_get_call_stack",
+            FunctionIndex::GetAccountInfoIndex => "host_function_get_account_info",
             FunctionIndex::IsValidURefFnIndex => "host_function_is_valid_uref",
+            FunctionIndex::AttenuateURefIndex => "host_function_attenuate_uref",
+            FunctionIndex::GetChainConfigIndex => "host_function_get_chain_config",
+            FunctionIndex::GetBalanceOfIndex => "host_function_get_balance_of",
+            FunctionIndex::ScheduleCallIndex => "host_function_schedule_call",
+            FunctionIndex::GetMinMainPurseBalanceIndex => {
+                "host_function_get_min_main_purse_balance"
+            }
             FunctionIndex::RevertFuncIndex => "host_function_revert",
             FunctionIndex::AddAssociatedKeyFuncIndex => "host_function_add_associated_key",
             FunctionIndex::RemoveAssociatedKeyFuncIndex => "host_function_remove_associated_key",
@@ -119,13 +137,36 @@ impl Drop for ScopedTimer {
             FunctionIndex::TransferFromPurseToPurseIndex => {
                 "host_function_transfer_from_purse_to_purse"
             }
+            FunctionIndex::BatchTransferIndex => "host_function_batch_transfer",
+            FunctionIndex::TransferFromPurseToPurseWithProofIndex => {
+                "host_function_transfer_from_purse_to_purse_with_proof"
+            }
+            FunctionIndex::BurnIndex => "host_function_burn",
+            FunctionIndex::ListPursesIndex => "host_function_list_purses",
+            FunctionIndex::SetContractInstallPermissionIndex => {
+                "host_function_set_contract_install_permission"
+            }
             FunctionIndex::GetBalanceIndex => "host_function_get_balance",
             FunctionIndex::GetPhaseIndex => "host_function_get_phase",
             FunctionIndex::UpgradeContractAtURefIndex => "host_function_upgrade_contract_at_uref",
             FunctionIndex::GetSystemContractIndex => "host_function_get_system_contract",
             FunctionIndex::GetMainPurseIndex => "host_function_get_main_purse",
             FunctionIndex::GetArgSizeFuncIndex => "host_function_get_arg_size",
+            FunctionIndex::GetNamedArgSizeFuncIndex => "host_function_get_named_arg_size",
+            FunctionIndex::GetNamedArgFuncIndex => "host_function_get_named_arg",
             FunctionIndex::ReadHostBufferIndex => "host_function_read_host_buffer",
+            FunctionIndex::GuardReentrancyIndex => "host_function_guard_reentrancy",
+            FunctionIndex::DeleteFuncIndex => "host_function_delete",
+            FunctionIndex::ListLocalFuncIndex => "host_function_list_local",
+            FunctionIndex::CanManageKeysIndex => "host_function_can_manage_keys",
+            FunctionIndex::RotateAssociatedKeyFuncIndex => "host_function_rotate_associated_key",
+            FunctionIndex::UpdateAssociatedKeysFuncIndex => "host_function_update_associated_keys",
+            FunctionIndex::GetBlockHeightIndex => "host_function_get_block_height",
+            FunctionIndex::RandomBytesIndex => "host_function_random_bytes",
+            FunctionIndex::Blake2bFuncIndex => "host_function_blake2b",
+            FunctionIndex::VerifyEd25519FuncIndex => "host_function_verify_ed25519",
+            FunctionIndex::VerifySecp256k1FuncIndex => "host_function_verify_secp256k1",
+            FunctionIndex::BigNumOpIndex => "host_function_bignum_op",
             #[cfg(feature = "test-support")]
             FunctionIndex::PrintIndex => "host_function_print",
         };
@@ -68,6 +68,18 @@ where
         let payment_purse_ref: URef = cl_value.into_t()?;
         Ok(payment_purse_ref)
     }
+
+    fn set_refund_purse(&mut self, purse: URef) -> Result<(), ApiError> {
+        let pos_contract_key = Key::from(self.get_pos_contract_uref());
+        let args_bytes = ArgsParser::parse(("set_refund_purse", purse))
+            .map_err(|_| ApiError::Unhandled)?
+            .into_bytes()
+            .map_err(|_| ApiError::Unhandled)?;
+
+        self.call_contract(pos_contract_key, args_bytes)
+            .map_err(|_| ApiError::Unhandled)?;
+        Ok(())
+    }
 }
 
 impl<'a, R> StandardPayment for Runtime<'a, R>
@@ -18,30 +18,50 @@ use engine_shared::{
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::{
-        ActionType, AddKeyFailure, PublicKey, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        ActionType, AddKeyFailure, AssociatedKeysUpdate, PublicKey, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr::{self, ToBytes},
-    AccessRights, BlockTime, CLType, CLValue, Key, Phase, ProtocolVersion, URef,
-    KEY_LOCAL_SEED_LENGTH,
+    AccessRights, ApiError, BlockTime, CLType, CLTypeMismatch, CLValue, CLValueError, Key, Phase,
+    ProtocolVersion, RuntimeArgs, URef, KEY_LOCAL_SEED_LENGTH, U512,
 };
 
 use crate::{
-    engine_state::{execution_effect::ExecutionEffect, SYSTEM_ACCOUNT_ADDR},
+    engine_state::{
+        execution_effect::ExecutionEffect,
+        scheduled_call::{self, ScheduledCall},
+        utils::{local_key_prefix, purse_registry_key},
+    },
     execution::{AddressGenerator, Error},
-    tracking_copy::{AddResult, TrackingCopy},
+    tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
     Address,
 };
 
 #[cfg(test)]
 mod tests;
 
+/// Returns an error if a value of type `found` can't be used where one of type `expected` is
+/// required.  [`CLType::Any`] matches every type, since values of that type can't be checked.
+pub(crate) fn check_cl_type(expected: &CLType, found: &CLType) -> Result<(), Error> {
+    if expected == found || *expected == CLType::Any || *found == CLType::Any {
+        return Ok(());
+    }
+    Err(Error::CLValue(CLValueError::Type(CLTypeMismatch {
+        expected: expected.clone(),
+        found: found.clone(),
+    })))
+}
+
 /// Attenuates given URef for a given account context.
 ///
 /// System account transfers given URefs into READ_ADD_WRITE access rights,
 /// and any other URef is transformed into READ only URef.
-pub(crate) fn attenuate_uref_for_account(account: &Account, uref: URef) -> URef {
-    if account.public_key() == SYSTEM_ACCOUNT_ADDR {
+pub(crate) fn attenuate_uref_for_account(
+    account: &Account,
+    system_account: PublicKey,
+    uref: URef,
+) -> URef {
+    if account.public_key() == system_account {
         // If the system account calls this function, it is given READ_ADD_WRITE access.
         uref.into_read_add_write()
     } else {
@@ -59,12 +79,13 @@ pub struct RuntimeContext<'a, R> {
     access_rights: HashMap<Address, HashSet<AccessRights>>,
     // Original account for read only tasks taken before execution
     account: &'a Account,
-    args: Vec<CLValue>,
+    args: RuntimeArgs,
     authorization_keys: BTreeSet<PublicKey>,
     // Key pointing to the entity we are currently running
     //(could point at an account or contract in the global state)
     base_key: Key,
     blocktime: BlockTime,
+    block_height: u64,
     deploy_hash: [u8; 32],
     gas_limit: Gas,
     gas_counter: Gas,
@@ -74,6 +95,8 @@ pub struct RuntimeContext<'a, R> {
     correlation_id: CorrelationId,
     phase: Phase,
     protocol_data: ProtocolData,
+    // The main purse of the deploy's account and the minimum balance the mint holds it to
+    min_main_purse_balance: Option<(URef, U512)>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -86,11 +109,12 @@ where
         state: Rc<RefCell<TrackingCopy<R>>>,
         named_keys: &'a mut BTreeMap<String, Key>,
         access_rights: HashMap<Address, HashSet<AccessRights>>,
-        args: Vec<CLValue>,
+        args: RuntimeArgs,
         authorization_keys: BTreeSet<PublicKey>,
         account: &'a Account,
         base_key: Key,
         blocktime: BlockTime,
+        block_height: u64,
         deploy_hash: [u8; 32],
         gas_limit: Gas,
         gas_counter: Gas,
@@ -109,6 +133,7 @@ where
             account,
             authorization_keys,
             blocktime,
+            block_height,
             deploy_hash,
             base_key,
             gas_limit,
@@ -119,6 +144,7 @@ where
             correlation_id,
             phase,
             protocol_data,
+            min_main_purse_balance: None,
         }
     }
 
@@ -163,6 +189,7 @@ where
     /// also persistable map (one that is found in the
     /// TrackingCopy/GlobalState).
     pub fn remove_key(&mut self, name: &str) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         match self.base_key() {
             public_key @ Key::Account(_) => {
                 let account: Account = {
@@ -200,6 +227,7 @@ where
                 self.named_keys.remove(name);
                 self.remove_key_from_contract(contract_local, contract, name)
             }
+            Key::ContractPackage(_) | Key::DeployInfo(_) => Err(Error::InvalidContext),
         }
     }
 
@@ -211,6 +239,10 @@ where
         self.blocktime
     }
 
+    pub fn get_block_height(&self) -> u64 {
+        self.block_height
+    }
+
     pub fn get_deployhash(&self) -> [u8; 32] {
         self.deploy_hash
     }
@@ -223,7 +255,7 @@ where
         &self.account
     }
 
-    pub fn args(&self) -> &Vec<CLValue> {
+    pub fn args(&self) -> &RuntimeArgs {
         &self.args
     }
 
@@ -257,10 +289,12 @@ where
 
     pub fn seed(&self) -> [u8; KEY_LOCAL_SEED_LENGTH] {
         match self.base_key {
-            Key::Account(PublicKey::Ed25519(bytes)) => bytes.value(),
+            Key::Account(public_key) => public_key.value(),
             Key::Hash(bytes) => bytes,
             Key::URef(uref) => uref.addr(),
             Key::Local { seed, .. } => seed,
+            Key::ContractPackage(addr) => addr,
+            Key::DeployInfo(deploy_hash) => deploy_hash,
         }
     }
 
@@ -276,6 +310,31 @@ where
         self.phase
     }
 
+    pub fn min_main_purse_balance(&self) -> Option<(URef, U512)> {
+        self.min_main_purse_balance
+    }
+
+    pub fn set_min_main_purse_balance(&mut self, min_main_purse_balance: Option<(URef, U512)>) {
+        self.min_main_purse_balance = min_main_purse_balance;
+    }
+
+    /// Returns whether this is the context of payment code, i.e. code run during the payment phase
+    /// with the deploy's account as its base key.  System contracts called by payment code run in
+    /// their own contexts.
+    pub fn is_payment_code(&self) -> bool {
+        self.phase == Phase::Payment && self.is_valid_context()
+    }
+
+    /// Fails with [`Error::PaymentSandboxViolation`] in the context of payment code, which may
+    /// only add named keys to its account.  Any other state change it needs, such as funding the
+    /// payment purse, is made by a system contract in its own context.
+    fn check_payment_sandbox(&self) -> Result<(), Error> {
+        if self.is_payment_code() {
+            return Err(Error::PaymentSandboxViolation);
+        }
+        Ok(())
+    }
+
     /// Generates new function address.
     /// Function address is deterministic. It is a hash of public key, nonce and
     /// `fn_store_id`, which is a counter that is being incremented after
@@ -296,6 +355,14 @@ where
         Ok(hash_bytes)
     }
 
+    /// Returns the next bytes of the random stream seeded from the deploy hash and phase.
+    ///
+    /// The stream is shared with URef address generation, so the output is unpredictable before the
+    /// deploy is made but identical on every node which executes it.
+    pub fn random_bytes(&self) -> Address {
+        self.address_generator.borrow_mut().create_address()
+    }
+
     pub fn new_uref(&mut self, value: StoredValue) -> Result<URef, Error> {
         let uref = {
             let addr = self.address_generator.borrow_mut().create_address();
@@ -307,6 +374,50 @@ where
         Ok(uref)
     }
 
+    /// Creates a new URef holding `value` to which the current context is only granted read
+    /// access, so that the value can't be modified afterwards.
+    pub fn new_read_only_uref(&mut self, value: StoredValue) -> Result<URef, Error> {
+        self.check_payment_sandbox()?;
+        let uref = {
+            let addr = self.address_generator.borrow_mut().create_address();
+            URef::new(addr, AccessRights::READ)
+        };
+        self.validate_value(&value)?;
+        self.insert_uref(uref);
+        self.state.borrow_mut().write(Key::URef(uref), value);
+        Ok(uref)
+    }
+
+    /// Records `purse` in the purse registry of the account whose context this is.  Purses created
+    /// in the context of a stored contract are not recorded.
+    pub fn register_purse(&mut self, purse: URef) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
+        let public_key = match self.base_key {
+            Key::Account(public_key) => public_key,
+            _ => return Ok(()),
+        };
+        let mut purses = self.registered_purses()?;
+        purses.push(purse.remove_access_rights());
+        let cl_value = CLValue::from_t(purses)?;
+        self.state.borrow_mut().write(
+            purse_registry_key(public_key),
+            StoredValue::CLValue(cl_value),
+        );
+        Ok(())
+    }
+
+    /// Returns the purses registered to the account whose context this is, with their access
+    /// rights removed, or an empty list in the context of a stored contract.
+    pub fn registered_purses(&mut self) -> Result<Vec<URef>, Error> {
+        match self.base_key {
+            Key::Account(public_key) => self
+                .state
+                .borrow_mut()
+                .get_registered_purses(self.correlation_id, public_key),
+            _ => Ok(Vec::new()),
+        }
+    }
+
     /// Puts `key` to the map of named keys of current context.
     pub fn put_key(&mut self, name: String, key: Key) -> Result<(), Error> {
         // No need to perform actual validation on the base key because an account or contract (i.e.
@@ -345,6 +456,7 @@ where
     }
 
     pub fn write_ls(&mut self, key_bytes: &[u8], cl_value: CLValue) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         let seed = self.seed();
         let key = Key::local(seed, key_bytes);
         self.state
@@ -353,6 +465,40 @@ where
         Ok(())
     }
 
+    /// Returns up to `limit` entries of the context-local partition of global state, ordered by
+    /// key and starting after `cursor` if given.
+    pub fn list_ls(
+        &mut self,
+        cursor: Option<Key>,
+        limit: usize,
+    ) -> Result<Vec<(Key, CLValue)>, Error> {
+        let prefix = local_key_prefix(self.seed());
+        let mut keys = self
+            .state
+            .borrow()
+            .keys_with_prefix(self.correlation_id, &prefix)
+            .map_err(Into::into)?;
+        keys.sort();
+
+        let mut entries = Vec::new();
+        for key in keys
+            .into_iter()
+            .filter(|key| cursor.map_or(true, |cursor| *key > cursor))
+            .take(limit)
+        {
+            let maybe_stored_value = self
+                .state
+                .borrow_mut()
+                .read(self.correlation_id, &key)
+                .map_err(Into::into)?;
+            if let Some(stored_value) = maybe_stored_value {
+                let cl_value = stored_value.try_into().map_err(Error::TypeMismatch)?;
+                entries.push((key, cl_value));
+            }
+        }
+        Ok(entries)
+    }
+
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         self.validate_readable(key)?;
         self.validate_key(key)?;
@@ -394,6 +540,7 @@ where
     }
 
     pub fn write_gs(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         self.validate_writeable(&key)?;
         self.validate_key(&key)?;
         self.validate_value(&value)?;
@@ -401,6 +548,30 @@ where
         Ok(())
     }
 
+    /// Checks that writing `cl_value` under `key` wouldn't change the [`CLType`] of the value
+    /// currently stored there, if `key` is a [`URef`].
+    pub fn validate_cl_type(&mut self, key: &Key, cl_value: &CLValue) -> Result<(), Error> {
+        if let Key::URef(_) = key {
+            self.validate_writeable(key)?;
+            self.validate_key(key)?;
+            if let Some(StoredValue::CLValue(existing)) = self.read_gs_direct(key)? {
+                check_cl_type(existing.cl_type(), cl_value.cl_type())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn delete_gs(&mut self, key: Key) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
+        self.validate_writeable(&key)?;
+        self.validate_key(&key)?;
+        if self.read_gs(&key)?.is_none() {
+            return Err(Error::KeyNotFound(key));
+        }
+        self.state.borrow_mut().delete(key);
+        Ok(())
+    }
+
     pub fn read_account(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         if let Key::Account(_) = key {
             self.validate_key(key)?;
@@ -414,6 +585,7 @@ where
     }
 
     pub fn write_account(&mut self, key: Key, account: Account) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         if let Key::Account(_) = key {
             self.validate_key(&key)?;
             let account_value = self.account_to_validated_value(account)?;
@@ -430,6 +602,7 @@ where
     }
 
     pub fn store_function_at_hash(&mut self, contract: StoredValue) -> Result<[u8; 32], Error> {
+        self.check_payment_sandbox()?;
         let new_hash = self.new_function_address()?;
         self.validate_value(&contract)?;
         let hash_key = Key::Hash(new_hash);
@@ -597,7 +770,7 @@ where
     pub fn is_readable(&self, key: &Key) -> bool {
         match key {
             Key::Account(_) => &self.base_key() == key,
-            Key::Hash(_) => true,
+            Key::Hash(_) | Key::ContractPackage(_) | Key::DeployInfo(_) => true,
             Key::URef(uref) => uref.is_readable(),
             Key::Local { .. } => false,
         }
@@ -608,7 +781,7 @@ where
         match key {
             Key::Account(_) | Key::Hash(_) => &self.base_key() == key,
             Key::URef(uref) => uref.is_addable(),
-            Key::Local { .. } => false,
+            Key::Local { .. } | Key::ContractPackage(_) | Key::DeployInfo(_) => false,
         }
     }
 
@@ -617,7 +790,7 @@ where
         match key {
             Key::Account(_) | Key::Hash(_) => false,
             Key::URef(uref) => uref.is_writeable(),
-            Key::Local { .. } => false,
+            Key::Local { .. } | Key::ContractPackage(_) | Key::DeployInfo(_) => false,
         }
     }
 
@@ -627,6 +800,7 @@ where
     /// value stored under `key` has different type, then `TypeMismatch`
     /// errors is returned.
     pub fn add_gs(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         self.validate_addable(&key)?;
         self.validate_key(&key)?;
         self.validate_value(&value)?;
@@ -634,6 +808,7 @@ where
     }
 
     pub fn add_ls(&mut self, key_bytes: &[u8], cl_value: CLValue) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         let seed = self.seed();
         let key = Key::local(seed, key_bytes);
         self.add_unsafe(key, StoredValue::CLValue(cl_value))
@@ -654,6 +829,7 @@ where
         public_key: PublicKey,
         weight: Weight,
     ) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         // Check permission to modify associated keys
         if !self.is_valid_context() {
             // Exit early with error to avoid mutations
@@ -690,6 +866,7 @@ where
     }
 
     pub fn remove_associated_key(&mut self, public_key: PublicKey) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         // Check permission to modify associated keys
         if !self.is_valid_context() {
             // Exit early with error to avoid mutations
@@ -728,6 +905,7 @@ where
         public_key: PublicKey,
         weight: Weight,
     ) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         // Check permission to modify associated keys
         if !self.is_valid_context() {
             // Exit early with error to avoid mutations
@@ -766,6 +944,7 @@ where
         action_type: ActionType,
         threshold: Weight,
     ) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         // Check permission to modify associated keys
         if !self.is_valid_context() {
             // Exit early with error to avoid mutations
@@ -799,12 +978,162 @@ where
         Ok(())
     }
 
+    /// Applies a batch of changes to the associated keys and action thresholds of the account,
+    /// checking the thresholds only against the final result.
+    ///
+    /// The outer error is reserved for failures which should trap; an invalid batch is reported
+    /// as an [`ApiError`] and leaves the account unchanged.
+    pub fn update_associated_keys(
+        &mut self,
+        updates: &[AssociatedKeysUpdate],
+    ) -> Result<Result<(), ApiError>, Error> {
+        self.check_payment_sandbox()?;
+        // Check permission to modify associated keys
+        if !self.is_valid_context()
+            || !self
+                .account()
+                .can_manage_keys_with(&self.authorization_keys)
+        {
+            return Ok(Err(ApiError::PermissionDenied));
+        }
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().public_key());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        // Exit early in case of error without updating global state
+        if let Err(error) = account.update_associated_keys(updates) {
+            return Ok(Err(error));
+        }
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.state.borrow_mut().write(key, account_value);
+
+        Ok(Ok(()))
+    }
+
+    /// Checks whether the authorization keys of the current deploy meet the key management
+    /// threshold of the deploy's account.
+    pub fn can_manage_keys(&self) -> bool {
+        self.account()
+            .can_manage_keys_with(&self.authorization_keys)
+    }
+
+    /// Replaces `old_key` in the associated keys of `account` with `new_key`, which inherits its
+    /// weight.
+    ///
+    /// Unlike the other key management functions this acts on an arbitrary account and bypasses
+    /// the authorization checks, so it is only permitted within the Key Recovery contract.
+    pub fn rotate_associated_key(
+        &mut self,
+        account: PublicKey,
+        old_key: PublicKey,
+        new_key: PublicKey,
+    ) -> Result<(), Error> {
+        let key_recovery = self.protocol_data.key_recovery();
+        match self.base_key() {
+            Key::URef(uref) if uref.addr() == key_recovery.addr() => {}
+            _ => return Err(UpdateKeyFailure::PermissionDenied.into()),
+        }
+
+        let key = Key::Account(account);
+
+        // The account is not readable from this context, so read it directly
+        let mut account: Account = match self.read_gs_direct(&key)? {
+            Some(StoredValue::Account(account)) => account,
+            _ => return Err(Error::KeyNotFound(key)),
+        };
+
+        let weight = *account
+            .get_associated_key_weight(old_key)
+            .ok_or(UpdateKeyFailure::MissingKey)?;
+
+        // Add the new key first so that the total weight never drops below the thresholds
+        account
+            .add_associated_key(new_key, weight)
+            .map_err(Error::from)?;
+        account
+            .remove_associated_key(old_key)
+            .map_err(Error::from)?;
+
+        // Only the associated keys changed, so the account's named keys needn't be revalidated
+        self.state
+            .borrow_mut()
+            .write(key, StoredValue::Account(account));
+
+        Ok(())
+    }
+
+    /// Grants or revokes the ability of `account` to store new contracts.
+    ///
+    /// Like `rotate_associated_key` this acts on an arbitrary account, so it is only permitted to
+    /// the system account, e.g. while running an upgrade installer.
+    pub fn set_contract_install_permission(
+        &mut self,
+        account: PublicKey,
+        allowed: bool,
+    ) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
+        if self.account().public_key() != self.protocol_data.system_account() {
+            return Err(Error::Revert(ApiError::PermissionDenied));
+        }
+
+        let key = Key::Account(account);
+
+        let mut account: Account = match self.read_gs_direct(&key)? {
+            Some(StoredValue::Account(account)) => account,
+            _ => return Err(Error::KeyNotFound(key)),
+        };
+        account.set_can_install_contracts(allowed);
+
+        // Only the capability changed, so the account's named keys needn't be revalidated
+        self.state
+            .borrow_mut()
+            .write(key, StoredValue::Account(account));
+
+        Ok(())
+    }
+
+    /// Stores a call to the contract under `contract_key` with the serialized `args`, made as the
+    /// current account once the block time reaches `earliest_blocktime`, among the scheduled calls.
+    pub fn schedule_call(
+        &mut self,
+        contract_key: Key,
+        args: Vec<u8>,
+        earliest_blocktime: BlockTime,
+        gas_budget: u64,
+    ) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
+
+        let call_address = self.address_generator.borrow_mut().create_address();
+        let key =
+            scheduled_call::scheduled_call_key(earliest_blocktime, self.deploy_hash, call_address);
+        let call = CLValue::from_t(ScheduledCall::new(
+            self.account().public_key(),
+            contract_key,
+            args,
+            earliest_blocktime,
+            gas_budget,
+        ))?;
+
+        // The call is kept by the system rather than held by any account or contract
+        self.state
+            .borrow_mut()
+            .write(key, StoredValue::CLValue(call));
+
+        Ok(())
+    }
+
     pub fn upgrade_contract_at_uref(
         &mut self,
         key: Key,
         bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
     ) -> Result<(), Error> {
+        self.check_payment_sandbox()?;
         let protocol_version = self.protocol_version();
         let contract = Contract::new(bytes, named_keys, protocol_version);
         let contract = StoredValue::Contract(contract);
@@ -825,7 +1154,7 @@ where
     /// If the account is system account, then given URef receives
     /// full rights (READ_ADD_WRITE). Otherwise READ access is returned.
     pub(crate) fn attenuate_uref(&self, uref: URef) -> URef {
-        attenuate_uref_for_account(&self.account(), uref)
+        attenuate_uref_for_account(&self.account(), self.protocol_data.system_account(), uref)
     }
 
     /// Creates validated instance of `StoredValue` from `account`.
@@ -24,7 +24,8 @@ use types::{
     account::{
         ActionType, AddKeyFailure, PublicKey, RemoveKeyFailure, SetThresholdFailure, Weight,
     },
-    AccessRights, BlockTime, CLValue, Key, Phase, ProtocolVersion, URef, KEY_LOCAL_SEED_LENGTH,
+    AccessRights, BlockTime, CLType, CLTypeMismatch, CLValue, CLValueError, Key, Phase,
+    ProtocolVersion, RuntimeArgs, URef, KEY_LOCAL_SEED_LENGTH,
 };
 
 use super::{attenuate_uref_for_account, Address, Error, RuntimeContext};
@@ -117,11 +118,12 @@ fn mock_runtime_context<'a>(
         Rc::new(RefCell::new(tc)),
         named_keys,
         access_rights,
-        Vec::new(),
+        RuntimeArgs::default(),
         BTreeSet::from_iter(vec![PublicKey::ed25519_from([0; 32])]),
         &account,
         base_key,
         BlockTime::new(0),
+        0,
         [1u8; 32],
         Gas::default(),
         Gas::default(),
@@ -448,11 +450,12 @@ fn contract_key_addable_valid() {
         Rc::clone(&tc),
         &mut uref_map,
         access_rights,
-        Vec::new(),
+        RuntimeArgs::default(),
         BTreeSet::from_iter(vec![PublicKey::ed25519_from(base_acc_addr)]),
         &account,
         contract_key,
         BlockTime::new(0),
+        0,
         DEPLOY_HASH,
         Gas::default(),
         Gas::default(),
@@ -510,11 +513,12 @@ fn contract_key_addable_invalid() {
         Rc::clone(&tc),
         &mut uref_map,
         access_rights,
-        Vec::new(),
+        RuntimeArgs::default(),
         BTreeSet::from_iter(vec![PublicKey::ed25519_from(base_acc_addr)]),
         &account,
         other_contract_key,
         BlockTime::new(0),
+        0,
         DEPLOY_HASH,
         Gas::default(),
         Gas::default(),
@@ -580,6 +584,37 @@ fn uref_key_writeable_invalid() {
     assert_invalid_access(query_result, AccessRights::WRITE);
 }
 
+#[test]
+fn uref_value_type_cannot_be_changed() {
+    let query_result = test(HashMap::new(), |mut rc| {
+        let uref = rc.new_uref(StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()))?;
+        let key = Key::URef(uref);
+        rc.validate_cl_type(&key, &CLValue::from_t(2_i32).unwrap())?;
+        rc.validate_cl_type(&key, &CLValue::from_t(String::from("two")).unwrap())
+    });
+    match query_result {
+        Err(Error::CLValue(CLValueError::Type(CLTypeMismatch { expected, found }))) => {
+            assert_eq!(expected, CLType::I32);
+            assert_eq!(found, CLType::String);
+        }
+        other => panic!("expected type mismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn read_only_uref_cannot_be_written() {
+    let query_result = test(HashMap::new(), |mut rc| {
+        let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+        let uref = rc.new_read_only_uref(value.clone())?;
+        assert_eq!(uref.access_rights(), AccessRights::READ);
+        assert_eq!(rc.read_gs(&Key::URef(uref))?, Some(value.clone()));
+
+        let escalated = Key::URef(uref.with_access_rights(AccessRights::READ_WRITE));
+        rc.write_gs(escalated, value)
+    });
+    assert_forged_reference(query_result);
+}
+
 #[test]
 fn uref_key_addable_valid() {
     let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
@@ -959,7 +994,8 @@ fn validate_valid_purse_of_an_account() {
 fn attenuate_uref_for_system_account() {
     let (_key, account) = mock_account(SYSTEM_ACCOUNT_ADDR);
     let system_contract_uref = URef::new([42; 32], AccessRights::READ_ADD);
-    let attenuated_uref = attenuate_uref_for_account(&account, system_contract_uref);
+    let attenuated_uref =
+        attenuate_uref_for_account(&account, SYSTEM_ACCOUNT_ADDR, system_contract_uref);
 
     let access_rights = attenuated_uref.access_rights();
     assert_eq!(access_rights, AccessRights::READ_ADD_WRITE);
@@ -969,7 +1005,8 @@ fn attenuate_uref_for_system_account() {
 fn attenuate_uref_for_user_account() {
     let (_key, account) = mock_account(PublicKey::ed25519_from([42; 32]));
     let system_contract_uref = URef::new([42; 32], AccessRights::READ_ADD_WRITE);
-    let attenuated_uref = attenuate_uref_for_account(&account, system_contract_uref);
+    let attenuated_uref =
+        attenuate_uref_for_account(&account, SYSTEM_ACCOUNT_ADDR, system_contract_uref);
 
     let access_rights = attenuated_uref.access_rights();
     assert_eq!(access_rights, AccessRights::READ);
@@ -0,0 +1,163 @@
+use std::sync::{Arc, Mutex};
+
+use linked_hash_map::LinkedHashMap;
+
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use types::Key;
+
+use super::meter::{heap_meter::HeapSize, Meter};
+
+/// The default limit on the total size of the values held by a [`BlockCache`].
+pub const DEFAULT_MAX_BLOCK_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+struct Entries<M> {
+    state_root: Option<Blake2bHash>,
+    max_size: usize,
+    current_size: usize,
+    values: LinkedHashMap<Key, StoredValue>,
+    meter: M,
+    hits: u64,
+    misses: u64,
+}
+
+impl<M: Meter<Key, StoredValue>> Entries<M> {
+    fn new(max_size: usize, meter: M) -> Self {
+        Entries {
+            state_root: None,
+            max_size,
+            current_size: 0,
+            values: LinkedHashMap::new(),
+            meter,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn clear(&mut self, state_root: Blake2bHash) {
+        self.state_root = Some(state_root);
+        self.current_size = 0;
+        self.values.clear();
+    }
+
+    fn get(&mut self, state_root: Blake2bHash, key: &Key) -> Option<StoredValue> {
+        if self.state_root != Some(state_root) {
+            self.misses += 1;
+            return None;
+        }
+        let maybe_value = self.values.get_refresh(key).cloned();
+        if maybe_value.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        maybe_value
+    }
+
+    fn insert(&mut self, state_root: Blake2bHash, key: Key, value: StoredValue) {
+        if self.state_root != Some(state_root) {
+            return;
+        }
+        self.current_size += Meter::measure(&self.meter, &key, &value);
+        if let Some(old_value) = self.values.insert(key, value) {
+            self.current_size -= Meter::measure(&self.meter, &key, &old_value);
+        }
+        while self.current_size > self.max_size {
+            match self.values.pop_front() {
+                Some((key, value)) => {
+                    self.current_size -= Meter::measure(&self.meter, &key, &value);
+                }
+                None => break,
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &Key) {
+        if let Some(value) = self.values.remove(key) {
+            self.current_size -= Meter::measure(&self.meter, key, &value);
+        }
+    }
+}
+
+/// A read cache of the values found in global state under a single state root, shared by all the
+/// deploys of a block which execute against that root.
+///
+/// Values are only served and stored for the current root.  Committing effects on top of it moves
+/// the cache to the resulting root, keeping every value the effects didn't touch, so consecutive
+/// blocks keep hot keys such as the mint contract cached.
+#[derive(Clone)]
+pub struct BlockCache(Arc<Mutex<Entries<HeapSize>>>);
+
+impl BlockCache {
+    /// Creates an empty cache holding values of at most `max_size` bytes in total, above which
+    /// the least-recently-used values are evicted.
+    pub fn new(max_size: usize) -> Self {
+        BlockCache(Arc::new(Mutex::new(Entries::new(max_size, HeapSize))))
+    }
+
+    /// Makes `state_root` the current root, dropping all cached values if it differs from the
+    /// previous one.
+    pub fn checkout(&self, state_root: Blake2bHash) {
+        let mut entries = self.0.lock().unwrap();
+        if entries.state_root != Some(state_root) {
+            entries.clear(state_root);
+        }
+    }
+
+    /// Returns the value cached under `key` if `state_root` is the current root.
+    pub fn get(&self, state_root: Blake2bHash, key: &Key) -> Option<StoredValue> {
+        self.0.lock().unwrap().get(state_root, key)
+    }
+
+    /// Returns the numbers of lookups which hit and missed the cache since the previous call, and
+    /// resets both to zero.
+    pub fn take_hit_counts(&self) -> (u64, u64) {
+        let mut entries = self.0.lock().unwrap();
+        let counts = (entries.hits, entries.misses);
+        entries.hits = 0;
+        entries.misses = 0;
+        counts
+    }
+
+    /// Caches `value` under `key` if `state_root` is the current root.
+    pub fn insert(&self, state_root: Blake2bHash, key: Key, value: StoredValue) {
+        self.0.lock().unwrap().insert(state_root, key, value)
+    }
+
+    /// Records that committing writes to `touched_keys` on top of `pre_state_hash` resulted in
+    /// `post_state_hash`.
+    ///
+    /// If `pre_state_hash` is the current root, the values under `touched_keys` are dropped and
+    /// `post_state_hash` becomes the current root.  Otherwise the cache is left unchanged.
+    pub fn advance<'a>(
+        &self,
+        pre_state_hash: Blake2bHash,
+        post_state_hash: Blake2bHash,
+        touched_keys: impl IntoIterator<Item = &'a Key>,
+    ) {
+        let mut entries = self.0.lock().unwrap();
+        if entries.state_root != Some(pre_state_hash) {
+            return;
+        }
+        for key in touched_keys {
+            entries.remove(&key.normalize());
+        }
+        entries.state_root = Some(post_state_hash);
+    }
+}
+
+impl Default for BlockCache {
+    fn default() -> Self {
+        BlockCache::new(DEFAULT_MAX_BLOCK_CACHE_SIZE)
+    }
+}
+
+impl std::fmt::Debug for BlockCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let entries = self.0.lock().unwrap();
+        f.debug_struct("BlockCache")
+            .field("state_root", &entries.state_root)
+            .field("len", &entries.values.len())
+            .field("size", &entries.current_size)
+            .finish()
+    }
+}
@@ -7,7 +7,7 @@ use engine_shared::{
 use engine_storage::global_state::StateReader;
 use types::{account::PublicKey, bytesrepr::ToBytes, CLValue, Key, URef, U512};
 
-use crate::{execution, tracking_copy::TrackingCopy};
+use crate::{engine_state::utils, execution, tracking_copy::TrackingCopy};
 
 pub trait TrackingCopyExt<R> {
     type Error;
@@ -34,6 +34,20 @@ pub trait TrackingCopyExt<R> {
         balance_key: Key,
     ) -> Result<Motes, Self::Error>;
 
+    /// Gets the total supply recorded by the mint contract at a given uref
+    fn get_total_supply(
+        &mut self,
+        correlation_id: CorrelationId,
+        mint_contract_uref: URef,
+    ) -> Result<Motes, Self::Error>;
+
+    /// Gets the purses registered to the account at a given account address
+    fn get_registered_purses(
+        &mut self,
+        correlation_id: CorrelationId,
+        public_key: PublicKey,
+    ) -> Result<Vec<URef>, Self::Error>;
+
     /// Gets a contract by Key
     fn get_contract(
         &mut self,
@@ -113,6 +127,50 @@ where
         }
     }
 
+    fn get_total_supply(
+        &mut self,
+        correlation_id: CorrelationId,
+        mint_contract_uref: URef,
+    ) -> Result<Motes, Self::Error> {
+        let total_supply_key = Key::local(
+            mint_contract_uref.addr(),
+            &::mint::TOTAL_SUPPLY_KEY.to_bytes()?,
+        );
+        match self
+            .read(correlation_id, &total_supply_key)
+            .map_err(Into::into)?
+        {
+            Some(stored_value) => {
+                let cl_value: CLValue = stored_value
+                    .try_into()
+                    .map_err(execution::Error::TypeMismatch)?;
+                let total_supply: U512 = cl_value.into_t()?;
+                Ok(Motes::new(total_supply))
+            }
+            None => Err(execution::Error::KeyNotFound(total_supply_key)),
+        }
+    }
+
+    fn get_registered_purses(
+        &mut self,
+        correlation_id: CorrelationId,
+        public_key: PublicKey,
+    ) -> Result<Vec<URef>, Self::Error> {
+        let registry_key = utils::purse_registry_key(public_key);
+        match self
+            .read(correlation_id, &registry_key)
+            .map_err(Into::into)?
+        {
+            Some(stored_value) => {
+                let cl_value: CLValue = stored_value
+                    .try_into()
+                    .map_err(execution::Error::TypeMismatch)?;
+                Ok(cl_value.into_t()?)
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
     fn get_contract(
         &mut self,
         correlation_id: CorrelationId,
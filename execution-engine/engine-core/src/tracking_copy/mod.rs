@@ -1,3 +1,4 @@
+pub mod block_cache;
 mod byte_size;
 mod ext;
 pub(self) mod meter;
@@ -7,25 +8,28 @@ mod tests;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     convert::From,
-    iter,
+    iter, mem,
 };
 
 use linked_hash_map::LinkedHashMap;
 
 use engine_shared::{
     additive_map::AdditiveMap,
-    newtypes::CorrelationId,
+    newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
     transform::{self, Transform},
     TypeMismatch,
 };
 use engine_storage::global_state::StateReader;
-use types::{bytesrepr, CLType, CLValueError, Key};
+use types::{
+    bytesrepr::{self, ToBytes},
+    CLType, CLValueError, Key,
+};
 
 use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
 
-pub use self::ext::TrackingCopyExt;
 use self::meter::{heap_meter::HeapSize, Meter};
+pub use self::{block_cache::BlockCache, ext::TrackingCopyExt};
 
 #[derive(Debug)]
 pub enum TrackingCopyQueryResult {
@@ -100,6 +104,7 @@ pub struct TrackingCopyCache<M> {
     current_cache_size: usize,
     reads_cached: LinkedHashMap<Key, StoredValue>,
     muts_cached: HashMap<Key, StoredValue>,
+    deletes_cached: HashSet<Key>,
     meter: M,
 }
 
@@ -114,6 +119,7 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
             current_cache_size: 0,
             reads_cached: LinkedHashMap::new(),
             muts_cached: HashMap::new(),
+            deletes_cached: HashSet::new(),
             meter,
         }
     }
@@ -136,9 +142,45 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
 
     /// Inserts `key` and `value` pair to Write/Add cache.
     pub fn insert_write(&mut self, key: Key, value: StoredValue) {
+        self.deletes_cached.remove(&key);
         self.muts_cached.insert(key, value);
     }
 
+    /// Marks `key` as deleted, dropping any value cached under it.
+    pub fn insert_delete(&mut self, key: Key) {
+        self.muts_cached.remove(&key);
+        if let Some(value) = self.reads_cached.remove(&key) {
+            self.current_cache_size -= Meter::measure(&self.meter, &key, &value);
+        }
+        self.deletes_cached.insert(key);
+    }
+
+    /// Returns `true` if `key` has been deleted.
+    pub fn is_deleted(&self, key: &Key) -> bool {
+        self.deletes_cached.contains(key)
+    }
+
+    /// Returns the mutation currently cached under `key`.
+    fn cached_mutation(&self, key: &Key) -> CachedMutation {
+        CachedMutation {
+            value: self.muts_cached.get(key).cloned(),
+            deleted: self.deletes_cached.contains(key),
+        }
+    }
+
+    /// Puts back `mutation` as the one cached under `key`.
+    fn restore_mutation(&mut self, key: Key, mutation: CachedMutation) {
+        match mutation.value {
+            Some(value) => self.muts_cached.insert(key, value),
+            None => self.muts_cached.remove(&key),
+        };
+        if mutation.deleted {
+            self.deletes_cached.insert(key);
+        } else {
+            self.deletes_cached.remove(&key);
+        }
+    }
+
     /// Gets value from `key` in the cache.
     pub fn get(&mut self, key: &Key) -> Option<&StoredValue> {
         if let Some(value) = self.muts_cached.get(&key) {
@@ -149,11 +191,50 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
     }
 }
 
+/// The limit on the total size of the values held by a [`TrackingCopy`]'s read cache, above which
+/// the least-recently-used values are evicted.
+pub const MAX_READ_CACHE_SIZE: usize = 1024 * 16;
+
+/// The numbers of reads served by a [`TrackingCopy`]'s own caches and of those which had to go to
+/// its reader.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// The serialized sizes of the values read from and written to global state through a
+/// `TrackingCopy`, so that they can be charged for.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StorageUsage {
+    pub reads: Vec<usize>,
+    pub writes: Vec<usize>,
+}
+
+/// The value written to or deletion of a key cached by a [`TrackingCopy`], if any.
+struct CachedMutation {
+    value: Option<StoredValue>,
+    deleted: bool,
+}
+
+/// The state of a [`TrackingCopy`] set aside by [`TrackingCopy::begin_scope`]: the effects
+/// recorded in the enclosing scope, and an undo log holding the cached mutation of each key changed
+/// within the new scope as it was before the first change, to restore if the scope is rolled back.
+struct Scope {
+    ops: AdditiveMap<Key, Op>,
+    fns: AdditiveMap<Key, Transform>,
+    undo_log: HashMap<Key, CachedMutation>,
+}
+
 pub struct TrackingCopy<R> {
     reader: R,
     cache: TrackingCopyCache<HeapSize>,
+    block_cache: Option<(BlockCache, Blake2bHash)>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    scopes: Vec<Scope>,
+    storage_usage: StorageUsage,
+    cache_stats: CacheStats,
 }
 
 #[derive(Debug)]
@@ -181,11 +262,26 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     pub fn new(reader: R) -> TrackingCopy<R> {
         TrackingCopy {
             reader,
-            cache: TrackingCopyCache::new(1024 * 16, HeapSize), /* TODO: Should `max_cache_size`
-                                                                 * be fraction of wasm memory
-                                                                 * limit? */
+            cache: TrackingCopyCache::new(MAX_READ_CACHE_SIZE, HeapSize),
+            block_cache: None,
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            scopes: Vec::new(),
+            storage_usage: StorageUsage::default(),
+            cache_stats: CacheStats::default(),
+        }
+    }
+
+    /// Creates a new `TrackingCopy` reading state under `state_root`, which consults and fills
+    /// `block_cache` whenever a value has to be read from `reader`.
+    pub fn with_block_cache(
+        reader: R,
+        block_cache: BlockCache,
+        state_root: Blake2bHash,
+    ) -> TrackingCopy<R> {
+        TrackingCopy {
+            block_cache: Some((block_cache, state_root)),
+            ..TrackingCopy::new(reader)
         }
     }
 
@@ -193,31 +289,111 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         &self.reader
     }
 
+    /// Returns the numbers of reads which hit and missed this `TrackingCopy`'s caches so far.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache_stats
+    }
+
+    /// Returns the sizes of the values read and written since the last call, and starts
+    /// recording afresh.
+    pub fn take_storage_usage(&mut self) -> StorageUsage {
+        mem::replace(&mut self.storage_usage, StorageUsage::default())
+    }
+
     /// Creates a new TrackingCopy, using this one (including its mutations) as
-    /// the base state to read against. The intended use case for this
-    /// function is to "snapshot" the current `TrackingCopy` and produce a
-    /// new `TrackingCopy` where further changes can be made. This
-    /// allows isolating a specific set of changes (those in the new
-    /// `TrackingCopy`) from existing changes. Note that mutations to state
-    /// caused by new changes (i.e. writes and adds) only impact the new
-    /// `TrackingCopy`, not this one. Note that currently there is no `join` /
-    /// `merge` function to bring changes from a fork back to the main
-    /// `TrackingCopy`. this means the current usage requires repeated
-    /// forking, however we recognize this is sub-optimal and will revisit
-    /// in the future.
+    /// the base state to read against. Mutations made to the new `TrackingCopy`
+    /// (i.e. writes and adds) only impact it, not this one, and there is no way
+    /// to bring them back. To isolate a set of changes which may or may not be
+    /// kept, use [`begin_scope`](TrackingCopy::begin_scope) instead.
     pub fn fork(&self) -> TrackingCopy<&TrackingCopy<R>> {
         TrackingCopy::new(self)
     }
 
+    /// Opens a new scope nested in the current one.
+    ///
+    /// Changes made from now on are visible to subsequent reads as usual, but
+    /// [`effect`](TrackingCopy::effect) only reports those made within the new scope until it is
+    /// closed by either [`commit_scope`](TrackingCopy::commit_scope) or
+    /// [`rollback_scope`](TrackingCopy::rollback_scope).
+    pub fn begin_scope(&mut self) {
+        let scope = Scope {
+            ops: mem::replace(&mut self.ops, AdditiveMap::new()),
+            fns: mem::replace(&mut self.fns, AdditiveMap::new()),
+            undo_log: HashMap::new(),
+        };
+        self.scopes.push(scope);
+    }
+
+    /// Closes the innermost scope, keeping its changes and merging its effects into those of the
+    /// enclosing scope.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no open scope.
+    pub fn commit_scope(&mut self) {
+        let scope = self.scopes.pop().expect("no scope to commit");
+        let ops = mem::replace(&mut self.ops, scope.ops);
+        let fns = mem::replace(&mut self.fns, scope.fns);
+        for (key, op) in ops {
+            self.ops.insert_add(key, op);
+        }
+        for (key, transform) in fns {
+            self.fns.insert_add(key, transform);
+        }
+        // Rolling back the enclosing scope must now also undo the changes made within this one.
+        if let Some(enclosing_scope) = self.scopes.last_mut() {
+            for (key, mutation) in scope.undo_log {
+                enclosing_scope.undo_log.entry(key).or_insert(mutation);
+            }
+        }
+    }
+
+    /// Closes the innermost scope, discarding its changes and effects.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no open scope.
+    pub fn rollback_scope(&mut self) {
+        let scope = self.scopes.pop().expect("no scope to roll back");
+        self.ops = scope.ops;
+        self.fns = scope.fns;
+        for (key, mutation) in scope.undo_log {
+            self.cache.restore_mutation(key, mutation);
+        }
+    }
+
+    /// Records the mutation cached under `key` in the innermost scope's undo log, unless the key
+    /// was already changed within that scope.
+    fn log_undo(&mut self, key: Key) {
+        let cache = &self.cache;
+        if let Some(scope) = self.scopes.last_mut() {
+            scope
+                .undo_log
+                .entry(key)
+                .or_insert_with(|| cache.cached_mutation(&key));
+        }
+    }
+
+    /// Returns the number of scopes currently open.
+    pub fn scope_depth(&self) -> usize {
+        self.scopes.len()
+    }
+
     pub fn get(
         &mut self,
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
+        if self.cache.is_deleted(key) {
+            self.cache_stats.hits += 1;
+            return Ok(None);
+        }
         if let Some(value) = self.cache.get(key) {
+            self.cache_stats.hits += 1;
             return Ok(Some(value.to_owned()));
         }
-        if let Some(value) = self.reader.read(correlation_id, key)? {
+        self.cache_stats.misses += 1;
+        if let Some(value) = self.read_from_reader(correlation_id, key)? {
             self.cache.insert_read(*key, value.to_owned());
             Ok(Some(value))
         } else {
@@ -225,6 +401,37 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Reads the value under `key` from the underlying reader, going through the block cache if
+    /// there is one.
+    fn read_from_reader(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, R::Error> {
+        let (block_cache, state_root) = match &self.block_cache {
+            Some((block_cache, state_root)) => (block_cache, *state_root),
+            None => return self.reader.read(correlation_id, key),
+        };
+        if let Some(value) = block_cache.get(state_root, key) {
+            return Ok(Some(value));
+        }
+        let maybe_value = self.reader.read(correlation_id, key)?;
+        if let Some(value) = &maybe_value {
+            block_cache.insert(state_root, *key, value.to_owned());
+        }
+        Ok(maybe_value)
+    }
+
+    /// Returns the keys whose serialized form starts with `prefix`, taking into account the
+    /// writes and deletes cached by this `TrackingCopy`.
+    pub fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, R::Error> {
+        StateReader::keys_with_prefix(&self, correlation_id, prefix)
+    }
+
     pub fn read(
         &mut self,
         correlation_id: CorrelationId,
@@ -234,6 +441,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         if let Some(value) = self.get(correlation_id, &normalized_key)? {
             self.ops.insert_add(normalized_key, Op::Read);
             self.fns.insert_add(normalized_key, Transform::Identity);
+            self.storage_usage.reads.push(value.serialized_length());
             Ok(Some(value))
         } else {
             Ok(None)
@@ -242,11 +450,23 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
     pub fn write(&mut self, key: Key, value: StoredValue) {
         let normalized_key = key.normalize();
+        self.storage_usage.writes.push(value.serialized_length());
+        self.log_undo(normalized_key);
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
         self.fns.insert_add(normalized_key, Transform::Write(value));
     }
 
+    /// Removes the value under `key`, so that it reads as missing from then on.
+    pub fn delete(&mut self, key: Key) {
+        let normalized_key = key.normalize();
+        self.log_undo(normalized_key);
+        self.cache.insert_delete(normalized_key);
+        self.storage_usage.writes.push(0);
+        self.ops.insert_add(normalized_key, Op::Write);
+        self.fns.insert_add(normalized_key, Transform::Delete);
+    }
+
     /// Ok(None) represents missing key to which we want to "add" some value.
     /// Ok(Some(unit)) represents successful operation.
     /// Err(error) is reserved for unexpected errors when accessing global
@@ -312,6 +532,10 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
         match transform.clone().apply(current_value) {
             Ok(new_value) => {
+                self.storage_usage
+                    .writes
+                    .push(new_value.serialized_length());
+                self.log_undo(normalized_key);
                 self.cache.insert_write(normalized_key, new_value);
                 self.ops.insert_add(normalized_key, Op::Add);
                 self.fns.insert_add(normalized_key, transform);
@@ -324,6 +548,46 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Applies `transforms` to the state read by this `TrackingCopy`, as though they had been
+    /// committed before it was created.  Unlike changes made through it, they are not part of its
+    /// [`effect`](TrackingCopy::effect).
+    pub fn apply_transforms(
+        &mut self,
+        correlation_id: CorrelationId,
+        transforms: &AdditiveMap<Key, Transform>,
+    ) -> Result<Result<(), transform::Error>, R::Error> {
+        for (key, transform) in transforms.iter() {
+            match transform {
+                Transform::Identity => (),
+                Transform::Write(value) => self.cache.insert_write(*key, value.to_owned()),
+                Transform::Delete => self.cache.insert_delete(*key),
+                transform => {
+                    let current_value = if self.cache.is_deleted(key) {
+                        None
+                    } else if let Some(value) = self.cache.get(key) {
+                        Some(value.to_owned())
+                    } else {
+                        self.read_from_reader(correlation_id, key)?
+                    };
+                    let current_value = match current_value {
+                        Some(current_value) => current_value,
+                        None => {
+                            let found = "nothing".to_string();
+                            return Ok(Err(TypeMismatch::new(transform.to_string(), found).into()));
+                        }
+                    };
+                    match transform.to_owned().apply(current_value) {
+                        Ok(new_value) => self.cache.insert_write(*key, new_value),
+                        Err(error) => return Ok(Err(error)),
+                    }
+                }
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    /// Returns the effects of the changes made within the innermost open scope, or of all changes
+    /// if there is none.
     pub fn effect(&self) -> ExecutionEffect {
         ExecutionEffect::new(self.ops.clone(), self.fns.clone())
     }
@@ -346,7 +610,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
             if !query.visited_keys.insert(query.current_key) {
                 return Ok(query.into_circular_ref_result());
             }
-            let stored_value = match self.reader.read(correlation_id, &query.current_key)? {
+            let stored_value = match self.read_from_reader(correlation_id, &query.current_key)? {
                 None => {
                     return Ok(query.into_not_found_result("Failed to find base key"));
                 }
@@ -412,13 +676,32 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
+        if self.cache.is_deleted(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.muts_cached.get(key) {
             return Ok(Some(value.to_owned()));
         }
-        if let Some(value) = self.reader.read(correlation_id, key)? {
-            Ok(Some(value))
-        } else {
-            Ok(None)
+        self.read_from_reader(correlation_id, key)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let mut ret = self.reader.keys_with_prefix(correlation_id, prefix)?;
+        ret.retain(|key| !self.cache.is_deleted(key));
+        let cached_keys = self.cache.muts_cached.keys().filter(|key| {
+            key.to_bytes()
+                .map(|key_bytes| key_bytes.starts_with(prefix))
+                .unwrap_or(false)
+        });
+        for key in cached_keys {
+            if !ret.contains(key) {
+                ret.push(*key);
+            }
         }
+        Ok(ret)
     }
 }
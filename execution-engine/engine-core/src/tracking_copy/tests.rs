@@ -5,20 +5,23 @@ use proptest::{collection::vec, prelude::*};
 
 use engine_shared::{
     account::{Account, AssociatedKeys},
+    additive_map::AdditiveMap,
     contract::Contract,
-    newtypes::CorrelationId,
+    newtypes::{Blake2bHash, CorrelationId},
     stored_value::{gens::stored_value_arb, StoredValue},
     transform::Transform,
 };
 use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader};
 use types::{
     account::{PublicKey, Weight, ED25519_LENGTH},
+    bytesrepr::ToBytes,
     gens::*,
     AccessRights, CLValue, Key, ProtocolVersion, URef,
 };
 
 use super::{
-    meter::count_meter::Count, AddResult, TrackingCopy, TrackingCopyCache, TrackingCopyQueryResult,
+    meter::count_meter::Count, AddResult, BlockCache, CacheStats, StorageUsage, TrackingCopy,
+    TrackingCopyCache, TrackingCopyQueryResult,
 };
 use crate::engine_state::op::Op;
 
@@ -58,6 +61,14 @@ impl StateReader<Key, StoredValue> for CountingDb {
         self.count.set(count + 1);
         Ok(Some(value))
     }
+
+    fn keys_with_prefix(
+        &self,
+        _correlation_id: CorrelationId,
+        _prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        Ok(Vec::new())
+    }
 }
 
 #[test]
@@ -91,6 +102,108 @@ fn tracking_copy_caching() {
     assert_eq!(db_value, 1);
 }
 
+#[test]
+fn tracking_copy_counts_cache_hits_and_misses() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let read_key = Key::Hash([0u8; 32]);
+    let written_key = Key::Hash([1u8; 32]);
+
+    tc.read(correlation_id, &read_key).unwrap();
+    tc.read(correlation_id, &read_key).unwrap();
+    tc.write(
+        written_key,
+        StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+    );
+    tc.read(correlation_id, &written_key).unwrap();
+
+    assert_eq!(tc.cache_stats(), CacheStats { hits: 2, misses: 1 });
+    assert_eq!(counter.get(), 1);
+}
+
+#[test]
+fn block_cache_shared_between_tracking_copies() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let block_cache = BlockCache::default();
+    let state_root = Blake2bHash::new(&[0]);
+    block_cache.checkout(state_root);
+    let k = Key::Hash([0u8; 32]);
+    let zero = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+
+    let mut tc = TrackingCopy::with_block_cache(
+        CountingDb::new(Rc::clone(&counter)),
+        BlockCache::clone(&block_cache),
+        state_root,
+    );
+    assert_eq!(tc.read(correlation_id, &k).unwrap().unwrap(), zero);
+    assert_eq!(counter.get(), 1);
+
+    // a second deploy against the same root is served from the block cache
+    let mut tc = TrackingCopy::with_block_cache(
+        CountingDb::new(Rc::clone(&counter)),
+        BlockCache::clone(&block_cache),
+        state_root,
+    );
+    assert_eq!(tc.read(correlation_id, &k).unwrap().unwrap(), zero);
+    assert_eq!(counter.get(), 1);
+
+    // a deploy against a different root isn't
+    let mut tc = TrackingCopy::with_block_cache(
+        CountingDb::new(Rc::clone(&counter)),
+        BlockCache::clone(&block_cache),
+        Blake2bHash::new(&[1]),
+    );
+    tc.read(correlation_id, &k).unwrap().unwrap();
+    assert_eq!(counter.get(), 2);
+
+    assert_eq!(block_cache.take_hit_counts(), (1, 2));
+    assert_eq!(block_cache.take_hit_counts(), (0, 0));
+}
+
+#[test]
+fn block_cache_advance_drops_touched_keys() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let block_cache = BlockCache::default();
+    let pre_state_hash = Blake2bHash::new(&[0]);
+    let post_state_hash = Blake2bHash::new(&[1]);
+    block_cache.checkout(pre_state_hash);
+    let touched = Key::Hash([0u8; 32]);
+    let untouched = Key::Hash([1u8; 32]);
+
+    let mut tc = TrackingCopy::with_block_cache(
+        CountingDb::new(Rc::clone(&counter)),
+        BlockCache::clone(&block_cache),
+        pre_state_hash,
+    );
+    tc.read(correlation_id, &touched).unwrap();
+    tc.read(correlation_id, &untouched).unwrap();
+    assert_eq!(counter.get(), 2);
+
+    // advancing from a root other than the current one is ignored
+    block_cache.advance(post_state_hash, Blake2bHash::new(&[2]), &[]);
+    assert!(block_cache.get(pre_state_hash, &untouched).is_some());
+
+    block_cache.advance(pre_state_hash, post_state_hash, &[touched]);
+    assert!(block_cache.get(pre_state_hash, &untouched).is_none());
+    assert!(block_cache.get(post_state_hash, &touched).is_none());
+
+    let mut tc = TrackingCopy::with_block_cache(
+        CountingDb::new(Rc::clone(&counter)),
+        BlockCache::clone(&block_cache),
+        post_state_hash,
+    );
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    assert_eq!(tc.read(correlation_id, &untouched).unwrap().unwrap(), one);
+    assert_eq!(counter.get(), 2);
+    let two = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+    assert_eq!(tc.read(correlation_id, &touched).unwrap().unwrap(), two);
+    assert_eq!(counter.get(), 3);
+}
+
 #[test]
 fn tracking_copy_read() {
     let correlation_id = CorrelationId::new();
@@ -143,6 +256,218 @@ fn tracking_copy_write() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_records_storage_usage() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    let small = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let large = StoredValue::CLValue(CLValue::from_t(vec!["x".repeat(100); 10]).unwrap());
+
+    tc.read(correlation_id, &k).unwrap();
+    tc.write(k, small.clone());
+    tc.write(k, large.clone());
+    tc.delete(k);
+    assert_eq!(
+        tc.take_storage_usage(),
+        StorageUsage {
+            reads: vec![small.serialized_length()],
+            writes: vec![small.serialized_length(), large.serialized_length(), 0],
+        }
+    );
+
+    // taking the usage starts recording afresh
+    assert_eq!(tc.take_storage_usage(), StorageUsage::default());
+}
+
+#[test]
+fn tracking_copy_delete() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+
+    // deleting a written value should hide it from reads
+    tc.write(k, one.clone());
+    tc.delete(k);
+    assert_eq!(tc.get(correlation_id, &k).unwrap(), None);
+    // delete creates a Transform
+    assert_eq!(tc.fns.len(), 1);
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Delete));
+    // delete is recorded as a write
+    assert_eq!(tc.ops.len(), 1);
+    assert_eq!(tc.ops.get(&k), Some(&Op::Write));
+    // the deleted value is not fetched from the DB
+    assert_eq!(counter.get(), 0);
+
+    // writing after a delete should restore the value
+    tc.write(k, one.clone());
+    assert_eq!(tc.get(correlation_id, &k).unwrap(), Some(one.clone()));
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Write(one)));
+}
+
+#[test]
+fn tracking_copy_apply_transforms() {
+    let correlation_id = CorrelationId::new();
+    let zero = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+    let db = CountingDb::new_init(zero);
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([0u8; 32]);
+    let k2 = Key::Hash([1u8; 32]);
+    let k3 = Key::Hash([2u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let three = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+
+    let mut transforms = AdditiveMap::new();
+    transforms.insert(k1, Transform::Write(one.clone()));
+    transforms.insert(k2, Transform::AddInt32(3));
+    transforms.insert(k3, Transform::Delete);
+    tc.apply_transforms(correlation_id, &transforms)
+        .unwrap()
+        .unwrap();
+
+    // the transforms should be visible to reads
+    assert_eq!(tc.get(correlation_id, &k1).unwrap(), Some(one));
+    assert_eq!(tc.get(correlation_id, &k2).unwrap(), Some(three));
+    assert_eq!(tc.get(correlation_id, &k3).unwrap(), None);
+    // but not be part of the effect
+    assert!(tc.fns.is_empty());
+    assert!(tc.ops.is_empty());
+
+    // a transform which can't be applied should be reported
+    let mut transforms = AdditiveMap::new();
+    transforms.insert(k1, Transform::AddUInt512(1.into()));
+    assert!(tc
+        .apply_transforms(correlation_id, &transforms)
+        .unwrap()
+        .is_err());
+}
+
+#[test]
+fn tracking_copy_commit_scope() {
+    let correlation_id = CorrelationId::new();
+    let zero = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+    let db = CountingDb::new_init(zero.clone());
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([0u8; 32]);
+    let k2 = Key::Hash([1u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let two = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+
+    tc.write(k1, one.clone());
+    tc.begin_scope();
+    tc.begin_scope();
+    tc.write(k2, two.clone());
+    assert_eq!(tc.scope_depth(), 2);
+    // the effect only covers the innermost scope
+    assert_eq!(tc.effect().transforms.len(), 1);
+    assert_eq!(
+        tc.effect().transforms.get(&k2),
+        Some(&Transform::Write(two.clone()))
+    );
+
+    tc.commit_scope();
+    assert_eq!(tc.effect().transforms.len(), 1);
+    let result = tc.add(correlation_id, k1, one.clone()).unwrap();
+    assert_matches!(result, AddResult::Success);
+    tc.commit_scope();
+    assert_eq!(tc.scope_depth(), 0);
+
+    // committed effects are merged into the enclosing scope
+    let effect = tc.effect();
+    assert_eq!(
+        effect.transforms.get(&k1),
+        Some(&Transform::Write(two.clone()))
+    );
+    assert_eq!(
+        effect.transforms.get(&k2),
+        Some(&Transform::Write(two.clone()))
+    );
+    assert_eq!(effect.ops.get(&k1), Some(&Op::Write));
+    assert_eq!(tc.get(correlation_id, &k1).unwrap(), Some(two.clone()));
+    assert_eq!(tc.get(correlation_id, &k2).unwrap(), Some(two));
+}
+
+#[test]
+fn tracking_copy_rollback_scope() {
+    let correlation_id = CorrelationId::new();
+    let zero = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+    let db = CountingDb::new_init(zero.clone());
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([0u8; 32]);
+    let k2 = Key::Hash([1u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let two = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+
+    tc.write(k1, one.clone());
+    tc.begin_scope();
+    tc.write(k2, one.clone());
+    tc.begin_scope();
+    tc.write(k1, two.clone());
+    tc.delete(k2);
+    assert_eq!(tc.get(correlation_id, &k1).unwrap(), Some(two));
+    assert_eq!(tc.get(correlation_id, &k2).unwrap(), None);
+
+    // rolling back the inner scope restores the state of the outer one
+    tc.rollback_scope();
+    assert_eq!(tc.get(correlation_id, &k1).unwrap(), Some(one.clone()));
+    assert_eq!(tc.get(correlation_id, &k2).unwrap(), Some(one.clone()));
+    assert_eq!(tc.effect().transforms.len(), 1);
+    assert_eq!(
+        tc.effect().transforms.get(&k2),
+        Some(&Transform::Write(one.clone()))
+    );
+
+    // rolling back the outer scope leaves only the changes made before it
+    tc.rollback_scope();
+    assert_eq!(tc.get(correlation_id, &k2).unwrap(), Some(zero));
+    let effect = tc.effect();
+    assert_eq!(effect.transforms.len(), 1);
+    assert_eq!(effect.transforms.get(&k1), Some(&Transform::Write(one)));
+}
+
+#[test]
+fn tracking_copy_rollback_scope_after_committing_nested_scope() {
+    let correlation_id = CorrelationId::new();
+    let zero = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+    let db = CountingDb::new_init(zero.clone());
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([0u8; 32]);
+    let k2 = Key::Hash([1u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let two = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+
+    tc.write(k1, one.clone());
+    tc.begin_scope();
+    tc.write(k1, two.clone());
+    tc.begin_scope();
+    // k2 is only changed within the nested scope
+    tc.write(k1, one.clone());
+    tc.write(k2, two.clone());
+    tc.delete(k1);
+    tc.commit_scope();
+    assert_eq!(tc.get(correlation_id, &k1).unwrap(), None);
+    assert_eq!(tc.get(correlation_id, &k2).unwrap(), Some(two));
+
+    // the committed changes are undone along with those of the enclosing scope
+    tc.rollback_scope();
+    assert_eq!(tc.get(correlation_id, &k1).unwrap(), Some(one.clone()));
+    assert_eq!(tc.get(correlation_id, &k2).unwrap(), Some(zero));
+    let effect = tc.effect();
+    assert_eq!(effect.transforms.len(), 1);
+    assert_eq!(effect.transforms.get(&k1), Some(&Transform::Write(one)));
+}
+
 #[test]
 fn tracking_copy_add_i32() {
     let correlation_id = CorrelationId::new();
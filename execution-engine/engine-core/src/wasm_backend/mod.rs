@@ -0,0 +1,75 @@
+//! The interface between the engine and the Wasm implementation executing contracts.
+//!
+//! Gas metering, stack limiting and memory limits are all injected into a module by the
+//! preprocessor, so a backend only has to instantiate the preprocessed module, expose its memory
+//! and run its exports, serving host function calls with a [`Runtime`].  wasmi is the default
+//! backend; another, such as a JIT compiler, can be dropped in by implementing [`WasmBackend`] and
+//! selecting it as [`DefaultBackend`] behind a cargo feature.
+
+mod wasmi_backend;
+
+use parity_wasm::elements::Module;
+
+use engine_shared::stored_value::StoredValue;
+use engine_storage::global_state::StateReader;
+use types::{Key, ProtocolVersion};
+
+use crate::{execution::Error, runtime::Runtime};
+
+pub use wasmi_backend::WasmiBackend;
+
+/// The backend every module is executed with.
+pub type DefaultBackend = WasmiBackend;
+
+/// An instance of a module created by the default backend.
+pub type Instance = <DefaultBackend as WasmBackend>::Instance;
+
+/// The memory of an instance created by the default backend.
+pub type Memory = <DefaultBackend as WasmBackend>::Memory;
+
+/// A Wasm implementation able to execute preprocessed modules.
+pub trait WasmBackend {
+    type Instance: WasmInstance;
+    type Memory: WasmMemory;
+
+    /// Instantiates `module`, resolving its imports against the host functions of
+    /// `protocol_version`.  Its memory may grow to at most `max_memory_pages` 64KiB pages.
+    fn instantiate(
+        module: Module,
+        protocol_version: ProtocolVersion,
+        max_memory_pages: u32,
+    ) -> Result<(Self::Instance, Self::Memory), Error>;
+}
+
+/// An instantiated module.
+pub trait WasmInstance {
+    /// Runs the export named `name`, serving the host functions it calls with `runtime`.
+    ///
+    /// A trap raised by a host function, including the ones `ret` and `revert` end execution
+    /// with, is returned as the [`Error`] it was raised with.
+    fn invoke_export<R>(&self, name: &str, runtime: &mut Runtime<R>) -> Result<(), Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>;
+}
+
+/// The linear memory of an instantiated module.
+pub trait WasmMemory: Clone {
+    /// Reads `size` bytes starting at `offset`.
+    fn get(&self, offset: u32, size: usize) -> Result<Vec<u8>, Error>;
+
+    /// Writes `value` starting at `offset`.
+    fn set(&self, offset: u32, value: &[u8]) -> Result<(), Error>;
+
+    /// The current size of the memory in 64KiB pages.
+    fn size_in_pages(&self) -> u32;
+}
+
+/// Instantiates a preprocessed module with the default backend.
+pub fn instantiate(
+    module: Module,
+    protocol_version: ProtocolVersion,
+    max_memory_pages: u32,
+) -> Result<(Instance, Memory), Error> {
+    DefaultBackend::instantiate(module, protocol_version, max_memory_pages)
+}
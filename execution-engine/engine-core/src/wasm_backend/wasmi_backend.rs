@@ -0,0 +1,66 @@
+use parity_wasm::elements::Module;
+use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef};
+
+use engine_shared::stored_value::StoredValue;
+use engine_storage::global_state::StateReader;
+use types::{Key, ProtocolVersion};
+
+use super::{WasmBackend, WasmInstance, WasmMemory};
+use crate::{
+    execution::Error,
+    resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
+    runtime::Runtime,
+};
+
+/// Interprets modules with wasmi.  Host functions are served through [`Runtime`]'s implementation
+/// of wasmi's `Externals`.
+pub struct WasmiBackend;
+
+impl WasmBackend for WasmiBackend {
+    type Instance = ModuleRef;
+    type Memory = MemoryRef;
+
+    fn instantiate(
+        parity_module: Module,
+        protocol_version: ProtocolVersion,
+        max_memory_pages: u32,
+    ) -> Result<(ModuleRef, MemoryRef), Error> {
+        let module = wasmi::Module::from_parity_wasm_module(parity_module)?;
+        let resolver = create_module_resolver(protocol_version, max_memory_pages)?;
+        let mut imports = ImportsBuilder::new();
+        imports.push_resolver("env", &resolver);
+        let not_started_module = ModuleInstance::new(&module, &imports)?;
+        if not_started_module.has_start() {
+            return Err(Error::UnsupportedWasmStart);
+        }
+        let instance = not_started_module.not_started_instance().clone();
+        let memory = resolver.memory_ref()?;
+        Ok((instance, memory))
+    }
+}
+
+impl WasmInstance for ModuleRef {
+    fn invoke_export<R>(&self, name: &str, runtime: &mut Runtime<R>) -> Result<(), Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        // A host error is always the `Error` the trap was raised with, which `From` recovers.
+        ModuleInstance::invoke_export(self, name, &[], runtime)?;
+        Ok(())
+    }
+}
+
+impl WasmMemory for MemoryRef {
+    fn get(&self, offset: u32, size: usize) -> Result<Vec<u8>, Error> {
+        Ok(wasmi::MemoryInstance::get(self, offset, size)?)
+    }
+
+    fn set(&self, offset: u32, value: &[u8]) -> Result<(), Error> {
+        Ok(wasmi::MemoryInstance::set(self, offset, value)?)
+    }
+
+    fn size_in_pages(&self) -> u32 {
+        self.current_size().0 as u32
+    }
+}
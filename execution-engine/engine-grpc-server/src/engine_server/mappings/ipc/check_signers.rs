@@ -0,0 +1,65 @@
+use std::{
+    collections::BTreeSet,
+    convert::{TryFrom, TryInto},
+};
+
+use engine_core::engine_state::check_signers::{CheckSignersRequest, SignersCheck};
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use types::account::PublicKey;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl TryFrom<ipc::CheckSignersRequest> for CheckSignersRequest {
+    type Error = MappingError;
+
+    fn try_from(check_signers_request: ipc::CheckSignersRequest) -> Result<Self, Self::Error> {
+        let state_hash = {
+            let state_hash = check_signers_request.get_state_hash();
+            let length = state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let account_bytes = check_signers_request.get_account_public_key();
+        let account = PublicKey::try_from_raw(account_bytes)
+            .map_err(|_| MappingError::invalid_public_key_length(account_bytes.len()))?;
+
+        let signers = check_signers_request
+            .get_signing_keys()
+            .iter()
+            .map(|raw: &Vec<u8>| {
+                PublicKey::try_from_raw(raw.as_slice())
+                    .map_err(|_| MappingError::invalid_public_key_length(raw.len()))
+            })
+            .collect::<Result<BTreeSet<PublicKey>, Self::Error>>()?;
+
+        Ok(CheckSignersRequest::new(state_hash, account, signers))
+    }
+}
+
+impl From<SignersCheck> for ipc::SignersCheck {
+    fn from(signers_check: SignersCheck) -> Self {
+        let mut result = ipc::SignersCheck::new();
+        result.set_total_weight(signers_check.total_weight().value().into());
+        result.set_deployment_threshold(signers_check.deployment_threshold().value().into());
+        result
+            .set_key_management_threshold(signers_check.key_management_threshold().value().into());
+        result.set_unknown_keys(
+            signers_check
+                .unknown_keys()
+                .iter()
+                .map(|key| key.as_bytes().to_vec())
+                .collect(),
+        );
+        result.set_can_deploy(signers_check.can_deploy());
+        result.set_can_manage_keys(signers_check.can_manage_keys());
+        result
+    }
+}
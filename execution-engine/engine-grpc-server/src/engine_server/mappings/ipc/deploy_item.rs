@@ -3,7 +3,13 @@ use std::{
     convert::{TryFrom, TryInto},
 };
 
-use engine_core::engine_state::deploy_item::DeployItem;
+use engine_core::{
+    engine_state::{
+        deploy_header::DeployHeader, deploy_item::DeployItem,
+        executable_deploy_item::ExecutableDeployItem,
+    },
+    DeployHash,
+};
 use types::account::PublicKey;
 
 use crate::engine_server::{ipc, mappings::MappingError};
@@ -12,20 +18,20 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
     type Error = MappingError;
 
     fn try_from(mut pb_deploy_item: ipc::DeployItem) -> Result<Self, Self::Error> {
-        let address = PublicKey::ed25519_try_from(pb_deploy_item.get_address())
+        let address = PublicKey::try_from_raw(pb_deploy_item.get_address())
             .map_err(|_| MappingError::invalid_public_key_length(pb_deploy_item.address.len()))?;
 
-        let session = pb_deploy_item
+        let session: ExecutableDeployItem = pb_deploy_item
             .take_session()
             .payload
-            .map(Into::into)
-            .ok_or_else(|| MappingError::MissingPayload)?;
+            .ok_or_else(|| MappingError::MissingPayload)?
+            .try_into()?;
 
-        let payment = pb_deploy_item
+        let payment: ExecutableDeployItem = pb_deploy_item
             .take_payment()
             .payload
-            .map(Into::into)
-            .ok_or_else(|| MappingError::MissingPayload)?;
+            .ok_or_else(|| MappingError::MissingPayload)?
+            .try_into()?;
 
         let gas_price = pb_deploy_item.get_gas_price();
 
@@ -33,7 +39,7 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             .get_authorization_keys()
             .iter()
             .map(|raw: &Vec<u8>| {
-                PublicKey::ed25519_try_from(raw.as_slice())
+                PublicKey::try_from_raw(raw.as_slice())
                     .map_err(|_| MappingError::invalid_public_key_length(raw.len()))
             })
             .collect::<Result<BTreeSet<PublicKey>, Self::Error>>()?;
@@ -42,6 +48,29 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             MappingError::invalid_deploy_hash_length(pb_deploy_item.deploy_hash.len())
         })?;
 
+        let header = if pb_deploy_item.has_header() {
+            Some(pb_deploy_item.take_header().try_into()?)
+        } else {
+            None
+        };
+
+        let sequence_number = match pb_deploy_item.get_sequence_number() {
+            0 => None,
+            sequence_number => Some(sequence_number),
+        };
+
+        let payment_source = if pb_deploy_item.has_payment_source() {
+            Some(pb_deploy_item.take_payment_source().try_into()?)
+        } else {
+            None
+        };
+
+        let protocol_version = if pb_deploy_item.has_protocol_version() {
+            Some(pb_deploy_item.take_protocol_version().into())
+        } else {
+            None
+        };
+
         Ok(DeployItem::new(
             address,
             session,
@@ -49,6 +78,10 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            header,
+            sequence_number,
+            payment_source,
+            protocol_version,
         ))
     }
 }
@@ -68,6 +101,54 @@ impl From<DeployItem> for ipc::DeployItem {
                 .collect(),
         );
         result.set_deploy_hash(deploy_item.deploy_hash.to_vec());
+        if let Some(header) = deploy_item.header {
+            result.set_header(header.into());
+        }
+        result.set_sequence_number(deploy_item.sequence_number.unwrap_or_default());
+        if let Some(payment_source) = deploy_item.payment_source {
+            result.set_payment_source(payment_source.into());
+        }
+        if let Some(protocol_version) = deploy_item.protocol_version {
+            result.set_protocol_version(protocol_version.into());
+        }
+        result
+    }
+}
+
+impl TryFrom<ipc::DeployHeader> for DeployHeader {
+    type Error = MappingError;
+
+    fn try_from(pb_deploy_header: ipc::DeployHeader) -> Result<Self, Self::Error> {
+        let dependencies = pb_deploy_header
+            .get_dependencies()
+            .iter()
+            .map(|raw: &Vec<u8>| {
+                raw.as_slice()
+                    .try_into()
+                    .map_err(|_| MappingError::invalid_deploy_hash_length(raw.len()))
+            })
+            .collect::<Result<Vec<DeployHash>, Self::Error>>()?;
+
+        Ok(DeployHeader::new(
+            pb_deploy_header.get_timestamp(),
+            pb_deploy_header.get_ttl_millis(),
+            dependencies,
+        ))
+    }
+}
+
+impl From<DeployHeader> for ipc::DeployHeader {
+    fn from(deploy_header: DeployHeader) -> Self {
+        let mut result = ipc::DeployHeader::new();
+        result.set_timestamp(deploy_header.timestamp);
+        result.set_ttl_millis(deploy_header.ttl_millis);
+        result.set_dependencies(
+            deploy_header
+                .dependencies
+                .iter()
+                .map(|deploy_hash| deploy_hash.to_vec())
+                .collect(),
+        );
         result
     }
 }
@@ -11,14 +11,24 @@ use crate::engine_server::ipc::{DeployError_OutOfGasError, DeployResult};
 
 impl From<ExecutionResult> for DeployResult {
     fn from(execution_result: ExecutionResult) -> DeployResult {
-        match execution_result {
-            ExecutionResult::Success { effect, cost } => detail::execution_success(effect, cost),
+        let peak_memory_pages = execution_result.peak_memory_pages();
+        let mut pb_deploy_result: DeployResult = match execution_result {
+            ExecutionResult::Success { effect, cost, .. } => {
+                detail::execution_success(effect, cost)
+            }
             ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                ..
             } => (error, effect, cost).into(),
+        };
+        if pb_deploy_result.has_execution_result() {
+            pb_deploy_result
+                .mut_execution_result()
+                .set_peak_memory_pages(peak_memory_pages);
         }
+        pb_deploy_result
     }
 }
 
@@ -31,11 +41,21 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             error @ EngineStateError::InvalidHashLength { .. }
             | error @ EngineStateError::InvalidPublicKeyLength { .. }
             | error @ EngineStateError::InvalidProtocolVersion { .. }
+            | error @ EngineStateError::ProtocolVersionMismatch { .. }
             | error @ EngineStateError::InvalidUpgradeConfig
             | error @ EngineStateError::WasmPreprocessing(_)
             | error @ EngineStateError::WasmSerialization(_)
             | error @ EngineStateError::Exec(ExecutionError::DeploymentAuthorizationFailure)
-            | error @ EngineStateError::Authorization => {
+            | error @ EngineStateError::Authorization
+            | error @ EngineStateError::AuthorizationFailure(_)
+            | error @ EngineStateError::NativePayment
+            | error @ EngineStateError::DeployHeader(_)
+            | error @ EngineStateError::ReplayedDeploy
+            | error @ EngineStateError::InvalidSequenceNumber { .. }
+            | error @ EngineStateError::InvalidPaymentSource(_)
+            | error @ EngineStateError::NotWhitelisted(_)
+            | error @ EngineStateError::ArgsTooLarge { .. }
+            | error @ EngineStateError::ModuleTooLarge { .. } => {
                 detail::precondition_error(error.to_string())
             }
             EngineStateError::Storage(storage_error) => {
@@ -45,10 +65,19 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
                 detail::execution_error(msg, effect, cost)
             }
             error @ EngineStateError::InsufficientPayment
+            | error @ EngineStateError::PaymentFailure { .. }
             | error @ EngineStateError::Deploy
             | error @ EngineStateError::Finalization
             | error @ EngineStateError::Serialization(_)
-            | error @ EngineStateError::Mint(_) => detail::execution_error(error, effect, cost),
+            | error @ EngineStateError::Mint(_)
+            | error @ EngineStateError::MissingRoot(_)
+            | error @ EngineStateError::MissingTotalSupply
+            | error @ EngineStateError::MissingEraValidators(_)
+            | error @ EngineStateError::TotalSupplyMismatch { .. }
+            | error @ EngineStateError::InvalidAccountBalanceProof
+            | error @ EngineStateError::BondingPurseDeficit { .. } => {
+                detail::execution_error(error, effect, cost)
+            }
             EngineStateError::Exec(exec_error) => (exec_error, effect, cost).into(),
         }
     }
@@ -159,15 +188,20 @@ mod tests {
         };
         let execution_effect = ExecutionEffect::new(AdditiveMap::new(), input_transforms.clone());
         let cost = Gas::new(U512::from(123));
+        let peak_memory_pages = 17;
         let execution_result = ExecutionResult::Success {
             effect: execution_effect,
             cost,
+            trace: None,
+            cache_stats: Default::default(),
+            peak_memory_pages,
         };
         let mut ipc_deploy_result: DeployResult = execution_result.into();
         assert!(ipc_deploy_result.has_execution_result());
         let mut success = ipc_deploy_result.take_execution_result();
         let execution_cost: U512 = success.take_cost().try_into().expect("should map to U512");
         assert_eq!(execution_cost, cost.value());
+        assert_eq!(success.get_peak_memory_pages(), peak_memory_pages);
 
         // Extract transform map from the IPC message and parse it back to the domain
         let ipc_transforms: AdditiveMap<Key, Transform> = {
@@ -187,6 +221,9 @@ mod tests {
             error: error.into(),
             effect: Default::default(),
             cost: expected_cost,
+            trace: None,
+            cache_stats: Default::default(),
+            peak_memory_pages: 0,
         };
         let mut ipc_deploy_result: DeployResult = execution_failure.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -230,6 +267,9 @@ mod tests {
             error: EngineStateError::Exec(revert_error),
             effect: Default::default(),
             cost: Gas::new(amount),
+            trace: None,
+            cache_stats: Default::default(),
+            peak_memory_pages: 0,
         };
         let mut ipc_result: DeployResult = exec_result.into();
         assert!(
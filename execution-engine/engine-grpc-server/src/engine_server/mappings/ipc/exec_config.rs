@@ -1,6 +1,7 @@
 use std::convert::{TryFrom, TryInto};
 
-use engine_core::engine_state::genesis::{ExecConfig, GenesisAccount};
+use engine_core::engine_state::genesis::{ExecConfig, GenesisAccount, GenesisInstaller};
+use types::account::PublicKey;
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
@@ -19,13 +20,34 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig> for ExecConfig {
         let mint_initializer_bytes = pb_exec_config.take_mint_installer();
         let proof_of_stake_initializer_bytes = pb_exec_config.take_pos_installer();
         let standard_payment_installer_bytes = pb_exec_config.take_standard_payment_installer();
-        Ok(ExecConfig::new(
+        let key_recovery_installer_bytes = pb_exec_config.take_key_recovery_installer();
+        let unbonding_delay = pb_exec_config.get_unbonding_delay();
+        let mut exec_config = ExecConfig::new(
             mint_initializer_bytes,
             proof_of_stake_initializer_bytes,
             standard_payment_installer_bytes,
+            key_recovery_installer_bytes,
             accounts,
             wasm_costs,
-        ))
+            unbonding_delay,
+        );
+        for pb_installer in pb_exec_config.take_extra_installers().into_iter() {
+            exec_config.push_installer(pb_installer.into());
+        }
+        let pb_system_account = pb_exec_config.get_system_account();
+        if !pb_system_account.is_empty() {
+            let system_account = PublicKey::try_from_raw(pb_system_account)
+                .map_err(|_| MappingError::invalid_public_key_length(pb_system_account.len()))?;
+            exec_config.set_system_account(system_account);
+        }
+        let pb_whitelist_admin = pb_exec_config.get_whitelist_admin();
+        if !pb_whitelist_admin.is_empty() {
+            let whitelist_admin = PublicKey::try_from_raw(pb_whitelist_admin)
+                .map_err(|_| MappingError::invalid_public_key_length(pb_whitelist_admin.len()))?;
+            exec_config.set_whitelist_admin(Some(whitelist_admin));
+        }
+        exec_config.set_chain_name(pb_exec_config.take_chain_name());
+        Ok(exec_config)
     }
 }
 
@@ -37,6 +59,14 @@ impl From<ExecConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig {
         pb_exec_config.set_standard_payment_installer(
             exec_config.standard_payment_installer_bytes().to_vec(),
         );
+        pb_exec_config
+            .set_key_recovery_installer(exec_config.key_recovery_installer_bytes().to_vec());
+        pb_exec_config.set_unbonding_delay(exec_config.unbonding_delay());
+        pb_exec_config.set_system_account(exec_config.system_account().as_bytes().to_vec());
+        if let Some(whitelist_admin) = exec_config.whitelist_admin() {
+            pb_exec_config.set_whitelist_admin(whitelist_admin.as_bytes().to_vec());
+        }
+        pb_exec_config.set_chain_name(exec_config.chain_name().to_string());
         {
             let accounts = exec_config
                 .accounts()
@@ -46,6 +76,15 @@ impl From<ExecConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig {
                 .collect::<Vec<ipc::ChainSpec_GenesisConfig_ExecConfig_GenesisAccount>>();
             pb_exec_config.set_accounts(accounts.into());
         }
+        {
+            let extra_installers = exec_config
+                .extra_installers()
+                .iter()
+                .cloned()
+                .map(Into::into)
+                .collect::<Vec<ipc::ChainSpec_GenesisConfig_ExecConfig_GenesisInstaller>>();
+            pb_exec_config.set_extra_installers(extra_installers.into());
+        }
         pb_exec_config
             .mut_costs()
             .set_wasm(exec_config.wasm_costs().into());
@@ -53,6 +92,26 @@ impl From<ExecConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig {
     }
 }
 
+impl From<ipc::ChainSpec_GenesisConfig_ExecConfig_GenesisInstaller> for GenesisInstaller {
+    fn from(mut pb_installer: ipc::ChainSpec_GenesisConfig_ExecConfig_GenesisInstaller) -> Self {
+        GenesisInstaller::new(
+            pb_installer.take_name(),
+            pb_installer.take_installer(),
+            pb_installer.take_args(),
+        )
+    }
+}
+
+impl From<GenesisInstaller> for ipc::ChainSpec_GenesisConfig_ExecConfig_GenesisInstaller {
+    fn from(installer: GenesisInstaller) -> Self {
+        let mut pb_installer = ipc::ChainSpec_GenesisConfig_ExecConfig_GenesisInstaller::new();
+        pb_installer.set_name(installer.name().to_string());
+        pb_installer.set_installer(installer.installer_bytes().to_vec());
+        pb_installer.set_args(installer.args().to_vec());
+        pb_installer
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,35 +1,66 @@
+use std::convert::{TryFrom, TryInto};
+
 use engine_core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use types::account::{ActionType, AssociatedKeysUpdate, PublicKey, Weight};
+
+use crate::engine_server::{
+    ipc::{
+        DeployPayload, DeployPayload_oneof_payload, NativeKeyManagement,
+        NativeKeyManagement_AssociatedKey, NativeKeyManagement_Threshold,
+        NativeKeyManagement_Threshold_ActionType, NativeKeyManagement_Update,
+        NativeKeyManagement_Update_oneof_update, NativeTransfer,
+    },
+    mappings::{MappingError, ParsingError},
+};
 
-use crate::engine_server::ipc::{DeployPayload, DeployPayload_oneof_payload};
+impl TryFrom<DeployPayload_oneof_payload> for ExecutableDeployItem {
+    type Error = MappingError;
 
-impl From<DeployPayload_oneof_payload> for ExecutableDeployItem {
-    fn from(pb_deploy_payload: DeployPayload_oneof_payload) -> Self {
-        match pb_deploy_payload {
+    fn try_from(pb_deploy_payload: DeployPayload_oneof_payload) -> Result<Self, Self::Error> {
+        let deploy_item = match pb_deploy_payload {
             DeployPayload_oneof_payload::deploy_code(pb_deploy_code) => {
                 ExecutableDeployItem::ModuleBytes {
                     module_bytes: pb_deploy_code.code,
                     args: pb_deploy_code.args,
+                    named_args: pb_deploy_code.named_args,
                 }
             }
             DeployPayload_oneof_payload::stored_contract_hash(pb_stored_contract_hash) => {
                 ExecutableDeployItem::StoredContractByHash {
                     hash: pb_stored_contract_hash.hash,
                     args: pb_stored_contract_hash.args,
+                    named_args: pb_stored_contract_hash.named_args,
                 }
             }
             DeployPayload_oneof_payload::stored_contract_name(pb_stored_contract_name) => {
                 ExecutableDeployItem::StoredContractByName {
                     name: pb_stored_contract_name.stored_contract_name,
                     args: pb_stored_contract_name.args,
+                    named_args: pb_stored_contract_name.named_args,
                 }
             }
             DeployPayload_oneof_payload::stored_contract_uref(pb_stored_contract_uref) => {
                 ExecutableDeployItem::StoredContractByURef {
                     uref: pb_stored_contract_uref.uref,
                     args: pb_stored_contract_uref.args,
+                    named_args: pb_stored_contract_uref.named_args,
                 }
             }
-        }
+            DeployPayload_oneof_payload::native_key_management(pb_native_key_management) => {
+                let updates = pb_native_key_management
+                    .updates
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<Vec<AssociatedKeysUpdate>, MappingError>>()?;
+                ExecutableDeployItem::NativeKeyManagement { updates }
+            }
+            DeployPayload_oneof_payload::native_transfer(mut pb_native_transfer) => {
+                let target = public_key_from(pb_native_transfer.get_target())?;
+                let amount = pb_native_transfer.take_amount().try_into()?;
+                ExecutableDeployItem::NativeTransfer { target, amount }
+            }
+        };
+        Ok(deploy_item)
     }
 }
 
@@ -37,27 +68,173 @@ impl From<ExecutableDeployItem> for DeployPayload {
     fn from(edi: ExecutableDeployItem) -> Self {
         let mut result = DeployPayload::new();
         match edi {
-            ExecutableDeployItem::ModuleBytes { module_bytes, args } => {
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes,
+                args,
+                named_args,
+            } => {
                 let code = result.mut_deploy_code();
                 code.set_code(module_bytes);
                 code.set_args(args);
+                code.set_named_args(named_args);
             }
-            ExecutableDeployItem::StoredContractByHash { hash, args } => {
+            ExecutableDeployItem::StoredContractByHash {
+                hash,
+                args,
+                named_args,
+            } => {
                 let inner = result.mut_stored_contract_hash();
                 inner.set_hash(hash);
                 inner.set_args(args);
+                inner.set_named_args(named_args);
             }
-            ExecutableDeployItem::StoredContractByName { name, args } => {
+            ExecutableDeployItem::StoredContractByName {
+                name,
+                args,
+                named_args,
+            } => {
                 let inner = result.mut_stored_contract_name();
                 inner.set_stored_contract_name(name);
                 inner.set_args(args);
+                inner.set_named_args(named_args);
             }
-            ExecutableDeployItem::StoredContractByURef { uref, args } => {
+            ExecutableDeployItem::StoredContractByURef {
+                uref,
+                args,
+                named_args,
+            } => {
                 let inner = result.mut_stored_contract_uref();
                 inner.set_uref(uref);
                 inner.set_args(args);
+                inner.set_named_args(named_args);
+            }
+            ExecutableDeployItem::NativeKeyManagement { updates } => {
+                let mut inner = NativeKeyManagement::new();
+                inner.set_updates(updates.into_iter().map(Into::into).collect());
+                result.set_native_key_management(inner);
+            }
+            ExecutableDeployItem::NativeTransfer { target, amount } => {
+                let mut inner = NativeTransfer::new();
+                inner.set_target(target.as_bytes().to_vec());
+                inner.set_amount(amount.into());
+                result.set_native_transfer(inner);
             }
         }
         result
     }
 }
+
+impl TryFrom<NativeKeyManagement_Update> for AssociatedKeysUpdate {
+    type Error = MappingError;
+
+    fn try_from(pb_update: NativeKeyManagement_Update) -> Result<Self, Self::Error> {
+        let pb_update = pb_update
+            .update
+            .ok_or_else(|| ParsingError::from("Unable to parse Protobuf key management update"))?;
+
+        let update = match pb_update {
+            NativeKeyManagement_Update_oneof_update::add_key(pb_associated_key) => {
+                let (public_key, weight) = associated_key_from(&pb_associated_key)?;
+                AssociatedKeysUpdate::Add(public_key, weight)
+            }
+            NativeKeyManagement_Update_oneof_update::remove_key(pb_public_key) => {
+                AssociatedKeysUpdate::Remove(public_key_from(&pb_public_key)?)
+            }
+            NativeKeyManagement_Update_oneof_update::update_key(pb_associated_key) => {
+                let (public_key, weight) = associated_key_from(&pb_associated_key)?;
+                AssociatedKeysUpdate::UpdateWeight(public_key, weight)
+            }
+            NativeKeyManagement_Update_oneof_update::set_threshold(pb_threshold) => {
+                let action_type = match pb_threshold.get_action_type() {
+                    NativeKeyManagement_Threshold_ActionType::DEPLOYMENT => ActionType::Deployment,
+                    NativeKeyManagement_Threshold_ActionType::KEY_MANAGEMENT => {
+                        ActionType::KeyManagement
+                    }
+                };
+                AssociatedKeysUpdate::SetThreshold(
+                    action_type,
+                    weight_from(pb_threshold.get_weight())?,
+                )
+            }
+        };
+        Ok(update)
+    }
+}
+
+impl From<AssociatedKeysUpdate> for NativeKeyManagement_Update {
+    fn from(update: AssociatedKeysUpdate) -> Self {
+        let mut pb_update = NativeKeyManagement_Update::new();
+        match update {
+            AssociatedKeysUpdate::Add(public_key, weight) => {
+                pb_update.set_add_key(associated_key_into(public_key, weight))
+            }
+            AssociatedKeysUpdate::Remove(public_key) => {
+                pb_update.set_remove_key(public_key.as_bytes().to_vec())
+            }
+            AssociatedKeysUpdate::UpdateWeight(public_key, weight) => {
+                pb_update.set_update_key(associated_key_into(public_key, weight))
+            }
+            AssociatedKeysUpdate::SetThreshold(action_type, weight) => {
+                let mut pb_threshold = NativeKeyManagement_Threshold::new();
+                pb_threshold.set_action_type(match action_type {
+                    ActionType::Deployment => NativeKeyManagement_Threshold_ActionType::DEPLOYMENT,
+                    ActionType::KeyManagement => {
+                        NativeKeyManagement_Threshold_ActionType::KEY_MANAGEMENT
+                    }
+                });
+                pb_threshold.set_weight(weight.value().into());
+                pb_update.set_set_threshold(pb_threshold)
+            }
+        }
+        pb_update
+    }
+}
+
+fn public_key_from(bytes: &[u8]) -> Result<PublicKey, MappingError> {
+    PublicKey::try_from_raw(bytes).map_err(|_| MappingError::invalid_public_key_length(bytes.len()))
+}
+
+fn weight_from(value: u32) -> Result<Weight, MappingError> {
+    u8::try_from(value)
+        .map(Weight::new)
+        .map_err(|_| ParsingError(format!("Weight {} does not fit in a byte", value)).into())
+}
+
+fn associated_key_from(
+    pb_associated_key: &NativeKeyManagement_AssociatedKey,
+) -> Result<(PublicKey, Weight), MappingError> {
+    Ok((
+        public_key_from(pb_associated_key.get_public_key())?,
+        weight_from(pb_associated_key.get_weight())?,
+    ))
+}
+
+fn associated_key_into(public_key: PublicKey, weight: Weight) -> NativeKeyManagement_AssociatedKey {
+    let mut pb_associated_key = NativeKeyManagement_AssociatedKey::new();
+    pb_associated_key.set_public_key(public_key.as_bytes().to_vec());
+    pb_associated_key.set_weight(weight.value().into());
+    pb_associated_key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    #[test]
+    fn round_trip() {
+        let public_key = PublicKey::ed25519_from([1; 32]);
+        let updates = vec![
+            AssociatedKeysUpdate::Add(public_key, Weight::new(2)),
+            AssociatedKeysUpdate::UpdateWeight(public_key, Weight::new(3)),
+            AssociatedKeysUpdate::SetThreshold(ActionType::KeyManagement, Weight::new(3)),
+            AssociatedKeysUpdate::SetThreshold(ActionType::Deployment, Weight::new(1)),
+            AssociatedKeysUpdate::Remove(public_key),
+        ];
+        for update in updates {
+            test_utils::protobuf_round_trip::<AssociatedKeysUpdate, NativeKeyManagement_Update>(
+                update,
+            );
+        }
+    }
+}
@@ -2,12 +2,47 @@ use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::genesis::GenesisAccount;
 use engine_shared::motes::Motes;
-use types::account::PublicKey;
+use types::{account::PublicKey, BlockTime, VestingSchedule};
 
 use crate::engine_server::{
-    ipc::ChainSpec_GenesisConfig_ExecConfig_GenesisAccount, mappings::MappingError,
+    ipc::{
+        ChainSpec_GenesisConfig_ExecConfig_GenesisAccount,
+        ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule,
+    },
+    mappings::MappingError,
 };
 
+impl From<VestingSchedule> for ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule {
+    fn from(vesting_schedule: VestingSchedule) -> Self {
+        let mut pb_vesting_schedule =
+            ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule::new();
+
+        pb_vesting_schedule.set_locked_amount(vesting_schedule.locked_amount().into());
+        pb_vesting_schedule.set_cliff(vesting_schedule.cliff().into());
+        pb_vesting_schedule.set_release_duration(vesting_schedule.release_duration());
+
+        pb_vesting_schedule
+    }
+}
+
+impl TryFrom<ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule>
+    for VestingSchedule
+{
+    type Error = MappingError;
+
+    fn try_from(
+        mut pb_vesting_schedule: ChainSpec_GenesisConfig_ExecConfig_GenesisAccount_VestingSchedule,
+    ) -> Result<Self, Self::Error> {
+        let locked_amount = pb_vesting_schedule.take_locked_amount().try_into()?;
+        let cliff = BlockTime::new(pb_vesting_schedule.get_cliff());
+        Ok(VestingSchedule::new(
+            locked_amount,
+            cliff,
+            pb_vesting_schedule.get_release_duration(),
+        ))
+    }
+}
+
 impl From<GenesisAccount> for ChainSpec_GenesisConfig_ExecConfig_GenesisAccount {
     fn from(genesis_account: GenesisAccount) -> Self {
         let mut pb_genesis_account = ChainSpec_GenesisConfig_ExecConfig_GenesisAccount::new();
@@ -15,6 +50,9 @@ impl From<GenesisAccount> for ChainSpec_GenesisConfig_ExecConfig_GenesisAccount
         pb_genesis_account.set_public_key(genesis_account.public_key().as_bytes().to_vec());
         pb_genesis_account.set_balance(genesis_account.balance().value().into());
         pb_genesis_account.set_bonded_amount(genesis_account.bonded_amount().value().into());
+        if let Some(vesting_schedule) = genesis_account.vesting_schedule() {
+            pb_genesis_account.set_vesting_schedule(vesting_schedule.into());
+        }
 
         pb_genesis_account
     }
@@ -28,7 +66,7 @@ impl TryFrom<ChainSpec_GenesisConfig_ExecConfig_GenesisAccount> for GenesisAccou
     ) -> Result<Self, Self::Error> {
         // TODO: our TryFromSliceForPublicKeyError should convey length info
         let public_key =
-            PublicKey::ed25519_try_from(pb_genesis_account.get_public_key()).map_err(|_| {
+            PublicKey::try_from_raw(pb_genesis_account.get_public_key()).map_err(|_| {
                 MappingError::invalid_public_key_length(pb_genesis_account.public_key.len())
             })?;
         let balance = pb_genesis_account
@@ -39,7 +77,12 @@ impl TryFrom<ChainSpec_GenesisConfig_ExecConfig_GenesisAccount> for GenesisAccou
             .take_bonded_amount()
             .try_into()
             .map(Motes::new)?;
-        Ok(GenesisAccount::new(public_key, balance, bonded_amount))
+        let genesis_account = GenesisAccount::new(public_key, balance, bonded_amount);
+        if pb_genesis_account.has_vesting_schedule() {
+            let vesting_schedule = pb_genesis_account.take_vesting_schedule().try_into()?;
+            return Ok(genesis_account.with_vesting_schedule(vesting_schedule));
+        }
+        Ok(genesis_account)
     }
 }
 
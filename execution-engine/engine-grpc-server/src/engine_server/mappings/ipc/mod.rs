@@ -2,6 +2,7 @@
 //! defined in protobuf/io/casperlabs/ipc/ipc.proto
 
 mod bond;
+mod check_signers;
 mod deploy_item;
 mod deploy_result;
 mod exec_config;
@@ -10,7 +11,9 @@ mod execute_request;
 mod execution_effect;
 mod genesis_account;
 mod genesis_config;
+mod pos_view;
 mod query_request;
 mod run_genesis_request;
 mod upgrade_request;
 mod wasm_costs;
+mod wasm_features;
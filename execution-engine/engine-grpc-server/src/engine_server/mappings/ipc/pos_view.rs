@@ -0,0 +1,109 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::{
+    era_validators::{EraId, EraValidators},
+    pos_view::{PendingUnbond, PosView, PosViewRequest},
+};
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::engine_server::{ipc, mappings::MappingError, state};
+
+fn parse_pos_view_request(
+    parent_state_hash: &[u8],
+    protocol_version: state::ProtocolVersion,
+) -> Result<PosViewRequest, MappingError> {
+    let state_hash: Blake2bHash = parent_state_hash
+        .try_into()
+        .map_err(|_| MappingError::InvalidStateHash("parent_state_hash".to_string()))?;
+    Ok(PosViewRequest::new(state_hash, protocol_version.into()))
+}
+
+impl TryFrom<ipc::BidStateRequest> for PosViewRequest {
+    type Error = MappingError;
+
+    fn try_from(mut pb_request: ipc::BidStateRequest) -> Result<Self, Self::Error> {
+        parse_pos_view_request(
+            pb_request.get_parent_state_hash(),
+            pb_request.take_protocol_version(),
+        )
+    }
+}
+
+impl TryFrom<ipc::EraValidatorsRequest> for PosViewRequest {
+    type Error = MappingError;
+
+    fn try_from(mut pb_request: ipc::EraValidatorsRequest) -> Result<Self, Self::Error> {
+        parse_pos_view_request(
+            pb_request.get_parent_state_hash(),
+            pb_request.take_protocol_version(),
+        )
+    }
+}
+
+impl TryFrom<ipc::UnbondingQueueRequest> for PosViewRequest {
+    type Error = MappingError;
+
+    fn try_from(mut pb_request: ipc::UnbondingQueueRequest) -> Result<Self, Self::Error> {
+        parse_pos_view_request(
+            pb_request.get_parent_state_hash(),
+            pb_request.take_protocol_version(),
+        )
+    }
+}
+
+impl From<PosView> for ipc::BidState {
+    fn from(pos_view: PosView) -> Self {
+        let mut pb_bid_state = ipc::BidState::new();
+        pb_bid_state.set_bids(
+            pos_view
+                .bids()
+                .iter()
+                .map(|(public_key, amount)| {
+                    let mut pb_bid = ipc::BidState_Bid::new();
+                    pb_bid.set_id(public_key.as_bytes().to_vec());
+                    pb_bid.set_value((*amount).into());
+                    pb_bid
+                })
+                .collect(),
+        );
+        pb_bid_state
+    }
+}
+
+impl From<(EraId, EraValidators)> for ipc::EraValidators {
+    fn from((era_id, era_validators): (EraId, EraValidators)) -> Self {
+        let mut pb_era_validators = ipc::EraValidators::new();
+        pb_era_validators.set_validators(
+            era_validators
+                .iter()
+                .map(|(public_key, stake)| (*public_key, stake.weight()).into())
+                .collect(),
+        );
+        pb_era_validators.set_era_id(era_id);
+        pb_era_validators
+    }
+}
+
+impl From<PendingUnbond> for ipc::UnbondingQueue_Entry {
+    fn from(pending_unbond: PendingUnbond) -> Self {
+        let mut pb_entry = ipc::UnbondingQueue_Entry::new();
+        pb_entry.set_public_key(pending_unbond.public_key.as_bytes().to_vec());
+        pb_entry.set_amount(pending_unbond.amount.into());
+        pb_entry.set_timestamp(pending_unbond.timestamp.into());
+        pb_entry
+    }
+}
+
+impl From<PosView> for ipc::UnbondingQueue {
+    fn from(pos_view: PosView) -> Self {
+        let mut pb_unbonding_queue = ipc::UnbondingQueue::new();
+        pb_unbonding_queue.set_entries(
+            pos_view
+                .unbonding_queue()
+                .iter()
+                .map(|pending_unbond| (*pending_unbond).into())
+                .collect(),
+        );
+        pb_unbonding_queue
+    }
+}
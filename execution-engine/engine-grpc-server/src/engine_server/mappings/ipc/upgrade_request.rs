@@ -3,7 +3,10 @@ use std::convert::{TryFrom, TryInto};
 use engine_core::engine_state::upgrade::UpgradeConfig;
 use types::ProtocolVersion;
 
-use crate::engine_server::{ipc::UpgradeRequest, mappings::MappingError};
+use crate::engine_server::{
+    ipc::UpgradeRequest,
+    mappings::{MappingError, ParsingError},
+};
 
 impl TryFrom<UpgradeRequest> for UpgradeConfig {
     type Error = MappingError;
@@ -40,6 +43,23 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
         } else {
             Some(upgrade_point.get_activation_point().rank)
         };
+        let max_call_depth = match upgrade_point.get_new_max_call_depth() {
+            0 => None,
+            max_call_depth => Some(max_call_depth),
+        };
+        let wasm_features = if !upgrade_point.has_new_wasm_features() {
+            None
+        } else {
+            Some(upgrade_point.take_new_wasm_features().into())
+        };
+        let refund_ratio = if !upgrade_point.has_new_refund_ratio() {
+            None
+        } else {
+            let percentage = upgrade_point.get_new_refund_ratio().percentage;
+            let refund_ratio = u8::try_from(percentage)
+                .map_err(|_| ParsingError::from(format!("Invalid refund ratio: {}", percentage)))?;
+            Some(refund_ratio)
+        };
 
         Ok(UpgradeConfig::new(
             pre_state_hash,
@@ -49,6 +69,9 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_bytes,
             wasm_costs,
             activation_point,
+            max_call_depth,
+            wasm_features,
+            refund_ratio,
         ))
     }
 }
@@ -15,6 +15,18 @@ impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
             max_stack_height: wasm_costs.max_stack_height,
             opcodes_mul: wasm_costs.opcodes_mul,
             opcodes_div: wasm_costs.opcodes_div,
+            max_memory_pages: wasm_costs.max_memory_pages,
+            max_table_size: wasm_costs.max_table_size,
+            hash_per_byte: wasm_costs.hash_per_byte,
+            verify_ed25519: wasm_costs.verify_ed25519,
+            verify_secp256k1: wasm_costs.verify_secp256k1,
+            bignum_op: wasm_costs.bignum_op,
+            storage_bucket_size: wasm_costs.storage_bucket_size,
+            storage_read_per_bucket: wasm_costs.storage_read_per_bucket,
+            storage_write_per_bucket: wasm_costs.storage_write_per_bucket,
+            max_module_size: wasm_costs.max_module_size,
+            max_function_count: wasm_costs.max_function_count,
+            max_named_keys_per_write: wasm_costs.max_named_keys_per_write,
             ..Default::default()
         }
     }
@@ -33,6 +45,18 @@ impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height: pb_wasm_costs.max_stack_height,
             opcodes_mul: pb_wasm_costs.opcodes_mul,
             opcodes_div: pb_wasm_costs.opcodes_div,
+            max_memory_pages: pb_wasm_costs.max_memory_pages,
+            max_table_size: pb_wasm_costs.max_table_size,
+            hash_per_byte: pb_wasm_costs.hash_per_byte,
+            verify_ed25519: pb_wasm_costs.verify_ed25519,
+            verify_secp256k1: pb_wasm_costs.verify_secp256k1,
+            bignum_op: pb_wasm_costs.bignum_op,
+            storage_bucket_size: pb_wasm_costs.storage_bucket_size,
+            storage_read_per_bucket: pb_wasm_costs.storage_read_per_bucket,
+            storage_write_per_bucket: pb_wasm_costs.storage_write_per_bucket,
+            max_module_size: pb_wasm_costs.max_module_size,
+            max_function_count: pb_wasm_costs.max_function_count,
+            max_named_keys_per_write: pb_wasm_costs.max_named_keys_per_write,
         }
     }
 }
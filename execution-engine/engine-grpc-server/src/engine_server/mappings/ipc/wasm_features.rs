@@ -0,0 +1,43 @@
+use engine_wasm_prep::wasm_features::WasmFeatures;
+
+use crate::engine_server::ipc::ChainSpec_WasmFeatures;
+
+impl From<WasmFeatures> for ChainSpec_WasmFeatures {
+    fn from(wasm_features: WasmFeatures) -> Self {
+        ChainSpec_WasmFeatures {
+            sign_ext: wasm_features.sign_ext,
+            sat_float_to_int: wasm_features.sat_float_to_int,
+            bulk_memory: wasm_features.bulk_memory,
+            simd: wasm_features.simd,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ChainSpec_WasmFeatures> for WasmFeatures {
+    fn from(pb_wasm_features: ChainSpec_WasmFeatures) -> Self {
+        WasmFeatures {
+            sign_ext: pb_wasm_features.sign_ext,
+            sat_float_to_int: pb_wasm_features.sat_float_to_int,
+            bulk_memory: pb_wasm_features.bulk_memory,
+            simd: pb_wasm_features.simd,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_wasm_prep::wasm_features::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(wasm_features in gens::wasm_features_arb()) {
+            test_utils::protobuf_round_trip::<WasmFeatures, ChainSpec_WasmFeatures>(wasm_features);
+        }
+    }
+}
@@ -11,7 +11,7 @@ use std::{
 };
 
 use engine_core::{engine_state, DEPLOY_HASH_LENGTH};
-use types::account::ED25519_LENGTH;
+use types::account::{PublicKey, ED25519_LENGTH};
 
 pub use transforms::TransformMap;
 
@@ -33,6 +33,21 @@ pub(crate) fn vec_to_array64(input: Vec<u8>, input_name: &str) -> Result<[u8; 64
     Ok(result)
 }
 
+/// Try to convert a `Vec<u8>` holding the raw bytes of a public key to a `PublicKey`, inferring
+/// the key's algorithm from its length.
+pub(crate) fn vec_to_public_key(
+    input: Vec<u8>,
+    input_name: &str,
+) -> Result<PublicKey, ParsingError> {
+    PublicKey::try_from_raw(&input).map_err(|_| {
+        format!(
+            "{} must be a 32-byte Ed25519 key or a 33- or 65-byte secp256k1 key.",
+            input_name
+        )
+        .into()
+    })
+}
+
 #[derive(Debug)]
 pub enum MappingError {
     InvalidStateHashLength { expected: usize, actual: usize },
@@ -37,6 +37,9 @@ impl From<Account> for state::Account {
             pb_action_thresholds.set_key_management_threshold(key_management)
         }
 
+        pb_account.set_sequence_number(account.sequence_number());
+        pb_account.set_contract_installs_revoked(!account.can_install_contracts());
+
         pb_account
     }
 }
@@ -46,7 +49,7 @@ impl TryFrom<state::Account> for Account {
 
     fn try_from(pb_account: state::Account) -> Result<Self, Self::Error> {
         let public_key =
-            mappings::vec_to_array(pb_account.public_key, "Protobuf Account::PublicKey")?;
+            mappings::vec_to_public_key(pb_account.public_key, "Protobuf Account::PublicKey")?;
 
         let named_keys: NamedKeyMap = pb_account.named_keys.into_vec().try_into()?;
 
@@ -91,13 +94,15 @@ impl TryFrom<state::Account> for Account {
             .map_err(ParsingError::from)?
         };
 
-        let account = Account::new(
-            PublicKey::ed25519_from(public_key),
+        let mut account = Account::new(
+            public_key,
             named_keys.into_inner(),
             main_purse,
             associated_keys,
             action_thresholds,
         );
+        account.set_sequence_number(pb_account.sequence_number);
+        account.set_can_install_contracts(!pb_account.contract_installs_revoked);
         Ok(account)
     }
 }
@@ -115,10 +120,10 @@ impl TryFrom<Account_AssociatedKey> for (PublicKey, Weight) {
     type Error = ParsingError;
 
     fn try_from(pb_associated_key: Account_AssociatedKey) -> Result<Self, Self::Error> {
-        let public_key = PublicKey::ed25519_from(mappings::vec_to_array(
+        let public_key = mappings::vec_to_public_key(
             pb_associated_key.public_key,
             "Protobuf Account::AssociatedKey",
-        )?);
+        )?;
 
         let weight = weight_from(pb_associated_key.weight, "Protobuf AssociatedKey::Weight")?;
 
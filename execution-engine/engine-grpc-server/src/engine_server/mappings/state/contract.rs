@@ -10,12 +10,18 @@ use crate::engine_server::{
 
 impl From<Contract> for state::Contract {
     fn from(contract: Contract) -> Self {
+        let abi = contract.abi().cloned();
+        let has_entry_points = contract.has_entry_points();
         let (bytes, named_keys, protocol_version) = contract.destructure();
         let mut pb_contract = state::Contract::new();
         let named_keys: Vec<NamedKey> = NamedKeyMap::new(named_keys).into();
         pb_contract.set_body(bytes);
         pb_contract.set_named_keys(named_keys.into());
         pb_contract.set_protocol_version(protocol_version.into());
+        if let Some(abi) = abi {
+            pb_contract.set_abi(abi.into());
+        }
+        pb_contract.set_has_entry_points(has_entry_points);
         pb_contract
     }
 }
@@ -26,8 +32,19 @@ impl TryFrom<state::Contract> for Contract {
     fn try_from(mut pb_contract: state::Contract) -> Result<Self, Self::Error> {
         let named_keys: NamedKeyMap = pb_contract.take_named_keys().into_vec().try_into()?;
         let protocol_version = pb_contract.take_protocol_version().into();
+        let abi = if pb_contract.has_abi() {
+            Some(pb_contract.take_abi().try_into()?)
+        } else {
+            None
+        };
         let contract = Contract::new(pb_contract.body, named_keys.into_inner(), protocol_version);
-        Ok(contract)
+        match abi {
+            Some(abi) if pb_contract.has_entry_points => Ok(contract.with_entry_points(abi)),
+            None if pb_contract.has_entry_points => Err(ParsingError(
+                "Protobuf Contract with entry points must have an ABI".to_string(),
+            )),
+            abi => Ok(contract.with_abi(abi)),
+        }
     }
 }
 
@@ -0,0 +1,181 @@
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+};
+
+use types::{account::PublicKey, ContractAbi, EntryPoint, EntryPointAccess, Parameter};
+
+use crate::engine_server::{
+    mappings::{self, ParsingError},
+    state::{
+        self, ContractAbi_Access, ContractAbi_Access_Groups, ContractAbi_Access_oneof_variants,
+        ContractAbi_EntryPoint, ContractAbi_Group, ContractAbi_Parameter,
+    },
+};
+
+impl From<Parameter> for ContractAbi_Parameter {
+    fn from(parameter: Parameter) -> Self {
+        let mut pb_parameter = ContractAbi_Parameter::new();
+        pb_parameter.set_name(parameter.name().to_string());
+        pb_parameter.set_cl_type(parameter.cl_type().clone().into());
+        pb_parameter
+    }
+}
+
+impl TryFrom<ContractAbi_Parameter> for Parameter {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_parameter: ContractAbi_Parameter) -> Result<Self, Self::Error> {
+        let cl_type = pb_parameter.take_cl_type().try_into()?;
+        Ok(Parameter::new(pb_parameter.name, cl_type))
+    }
+}
+
+impl From<EntryPointAccess> for ContractAbi_Access {
+    fn from(access: EntryPointAccess) -> Self {
+        let mut pb_access = ContractAbi_Access::new();
+        match access {
+            EntryPointAccess::Public => pb_access.set_public(state::Unit::new()),
+            EntryPointAccess::Groups(names) => {
+                let mut pb_groups = ContractAbi_Access_Groups::new();
+                pb_groups.set_names(names.into());
+                pb_access.set_groups(pb_groups);
+            }
+            EntryPointAccess::Caller(caller) => {
+                pb_access.set_caller(caller.as_bytes().to_vec());
+            }
+        }
+        pb_access
+    }
+}
+
+impl TryFrom<ContractAbi_Access> for EntryPointAccess {
+    type Error = ParsingError;
+
+    fn try_from(pb_access: ContractAbi_Access) -> Result<Self, Self::Error> {
+        let pb_access = pb_access.variants.ok_or_else(|| {
+            ParsingError("Unable to parse Protobuf ContractAbi::Access".to_string())
+        })?;
+
+        let access = match pb_access {
+            ContractAbi_Access_oneof_variants::public(_) => EntryPointAccess::Public,
+            ContractAbi_Access_oneof_variants::groups(mut pb_groups) => {
+                EntryPointAccess::Groups(pb_groups.take_names().into_vec())
+            }
+            ContractAbi_Access_oneof_variants::caller(pb_caller) => {
+                let caller =
+                    mappings::vec_to_public_key(pb_caller, "Protobuf ContractAbi::Access::Caller")?;
+                EntryPointAccess::Caller(caller)
+            }
+        };
+        Ok(access)
+    }
+}
+
+impl From<(String, Vec<PublicKey>)> for ContractAbi_Group {
+    fn from((name, members): (String, Vec<PublicKey>)) -> Self {
+        let mut pb_group = ContractAbi_Group::new();
+        pb_group.set_name(name);
+        let members: Vec<Vec<u8>> = members
+            .iter()
+            .map(|member| member.as_bytes().to_vec())
+            .collect();
+        pb_group.set_members(members.into());
+        pb_group
+    }
+}
+
+impl TryFrom<ContractAbi_Group> for (String, Vec<PublicKey>) {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_group: ContractAbi_Group) -> Result<Self, Self::Error> {
+        let members = pb_group
+            .take_members()
+            .into_iter()
+            .map(|member| {
+                mappings::vec_to_public_key(member, "Protobuf ContractAbi::Group::Member")
+            })
+            .collect::<Result<Vec<PublicKey>, ParsingError>>()?;
+        Ok((pb_group.name, members))
+    }
+}
+
+impl From<EntryPoint> for ContractAbi_EntryPoint {
+    fn from(entry_point: EntryPoint) -> Self {
+        let mut pb_entry_point = ContractAbi_EntryPoint::new();
+        pb_entry_point.set_name(entry_point.name().to_string());
+        let parameters: Vec<ContractAbi_Parameter> = entry_point
+            .parameters()
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
+        pb_entry_point.set_parameters(parameters.into());
+        pb_entry_point.set_ret(entry_point.ret().clone().into());
+        pb_entry_point.set_access(entry_point.access().clone().into());
+        pb_entry_point
+    }
+}
+
+impl TryFrom<ContractAbi_EntryPoint> for EntryPoint {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_entry_point: ContractAbi_EntryPoint) -> Result<Self, Self::Error> {
+        let parameters = pb_entry_point
+            .take_parameters()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<Parameter>, ParsingError>>()?;
+        let ret = pb_entry_point.take_ret().try_into()?;
+        let access = pb_entry_point.take_access().try_into()?;
+        Ok(EntryPoint::new(pb_entry_point.name, parameters, ret).with_access(access))
+    }
+}
+
+impl From<ContractAbi> for state::ContractAbi {
+    fn from(abi: ContractAbi) -> Self {
+        let mut pb_abi = state::ContractAbi::new();
+        let entry_points: Vec<ContractAbi_EntryPoint> =
+            abi.entry_points().iter().cloned().map(Into::into).collect();
+        pb_abi.set_entry_points(entry_points.into());
+        let groups: Vec<ContractAbi_Group> =
+            abi.groups().clone().into_iter().map(Into::into).collect();
+        pb_abi.set_groups(groups.into());
+        pb_abi
+    }
+}
+
+impl TryFrom<state::ContractAbi> for ContractAbi {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_abi: state::ContractAbi) -> Result<Self, Self::Error> {
+        let entry_points = pb_abi
+            .take_entry_points()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<EntryPoint>, ParsingError>>()?;
+        let groups = pb_abi
+            .take_groups()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<BTreeMap<String, Vec<PublicKey>>, ParsingError>>()?;
+        Ok(ContractAbi::new(entry_points).with_groups(groups))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(abi in gens::contract_abi_arb()) {
+            test_utils::protobuf_round_trip::<ContractAbi, state::ContractAbi>(abi);
+        }
+    }
+}
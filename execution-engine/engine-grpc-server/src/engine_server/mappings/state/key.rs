@@ -1,12 +1,13 @@
 use std::convert::{TryFrom, TryInto};
 
-use types::{
-    account::PublicKey, Key, BLAKE2B_DIGEST_LENGTH, KEY_LOCAL_LENGTH, KEY_LOCAL_SEED_LENGTH,
-};
+use types::{Key, BLAKE2B_DIGEST_LENGTH, KEY_LOCAL_LENGTH, KEY_LOCAL_SEED_LENGTH};
 
 use crate::engine_server::{
     mappings::{self, ParsingError},
-    state::{self, Key_Address, Key_Hash, Key_Local, Key_oneof_value},
+    state::{
+        self, Key_Address, Key_ContractPackage, Key_DeployInfo, Key_Hash, Key_Local,
+        Key_oneof_value,
+    },
 };
 
 impl From<Key> for state::Key {
@@ -34,6 +35,16 @@ impl From<Key> for state::Key {
                 pb_local.set_hash(local.to_vec());
                 pb_key.set_local(pb_local);
             }
+            Key::ContractPackage(hash) => {
+                let mut pb_contract_package = Key_ContractPackage::new();
+                pb_contract_package.set_hash(hash.to_vec());
+                pb_key.set_contract_package(pb_contract_package);
+            }
+            Key::DeployInfo(deploy_hash) => {
+                let mut pb_deploy_info = Key_DeployInfo::new();
+                pb_deploy_info.set_deploy_hash(deploy_hash.to_vec());
+                pb_key.set_deploy_info(pb_deploy_info);
+            }
         }
         pb_key
     }
@@ -49,8 +60,9 @@ impl TryFrom<state::Key> for Key {
 
         let key = match pb_key {
             Key_oneof_value::address(pb_account) => {
-                let account = mappings::vec_to_array(pb_account.account, "Protobuf Key::Account")?;
-                Key::Account(PublicKey::ed25519_from(account))
+                let account =
+                    mappings::vec_to_public_key(pb_account.account, "Protobuf Key::Account")?;
+                Key::Account(account)
             }
             Key_oneof_value::hash(pb_hash) => {
                 let hash = mappings::vec_to_array(pb_hash.hash, "Protobuf Key::Hash")?;
@@ -68,6 +80,18 @@ impl TryFrom<state::Key> for Key {
                 hash.copy_from_slice(&local[KEY_LOCAL_SEED_LENGTH..]);
                 Key::Local { seed, hash }
             }
+            Key_oneof_value::contract_package(pb_contract_package) => {
+                let hash = mappings::vec_to_array(
+                    pb_contract_package.hash,
+                    "Protobuf Key::ContractPackage",
+                )?;
+                Key::ContractPackage(hash)
+            }
+            Key_oneof_value::deploy_info(pb_deploy_info) => {
+                let deploy_hash =
+                    mappings::vec_to_array(pb_deploy_info.deploy_hash, "Protobuf Key::DeployInfo")?;
+                Key::DeployInfo(deploy_hash)
+            }
         };
         Ok(key)
     }
@@ -77,7 +101,10 @@ impl TryFrom<state::Key> for Key {
 mod tests {
     use proptest::proptest;
 
-    use types::gens;
+    use types::{
+        account::{PublicKey, SECP256K1_LENGTH, SECP256K1_UNCOMPRESSED_LENGTH},
+        gens,
+    };
 
     use super::*;
     use crate::engine_server::mappings::test_utils;
@@ -88,4 +115,19 @@ mod tests {
             test_utils::protobuf_round_trip::<Key, state::Key>(key);
         }
     }
+
+    #[test]
+    fn should_compress_uncompressed_secp256k1_account() {
+        let mut uncompressed = vec![7u8; SECP256K1_UNCOMPRESSED_LENGTH];
+        uncompressed[0] = 4;
+        let mut pb_account = Key_Address::new();
+        pb_account.set_account(uncompressed);
+        let mut pb_key = state::Key::new();
+        pb_key.set_address(pb_account);
+
+        let mut compressed = [7u8; SECP256K1_LENGTH];
+        compressed[0] = 3;
+        let expected = Key::Account(PublicKey::secp256k1_from(compressed));
+        assert_eq!(Key::try_from(pb_key), Ok(expected));
+    }
 }
@@ -6,6 +6,7 @@ mod big_int;
 mod cl_type;
 mod cl_value;
 mod contract;
+mod contract_abi;
 mod key;
 mod named_key;
 mod protocol_version;
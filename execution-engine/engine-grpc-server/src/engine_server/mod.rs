@@ -23,15 +23,19 @@ use std::{
     io::ErrorKind,
     iter::FromIterator,
     marker::{Send, Sync},
+    thread,
     time::Instant,
 };
 
-use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse};
+use futures::{sync::mpsc, Stream};
+use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse, StreamingResponse};
 use log::{info, warn, Level};
 
 use engine_core::engine_state::{
+    check_signers::{CheckSignersRequest, CheckSignersResult},
     execute_request::ExecuteRequest,
     genesis::GenesisResult,
+    pos_view::{PosViewRequest, PosViewResult},
     query::{QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
     upgrade::{UpgradeConfig, UpgradeResult},
@@ -46,25 +50,37 @@ use types::{bytesrepr::ToBytes, ProtocolVersion};
 
 use self::{
     ipc::{
-        BidStateRequest, BidStateResponse, CommitRequest, CommitResponse, DistributeRewardsRequest,
-        DistributeRewardsResponse, ExecuteResponse, GenesisResponse, QueryResponse, SlashRequest,
-        SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+        BidStateRequest, BidStateResponse, CheckSignersResponse, CommitRequest, CommitResponse,
+        DistributeRewardsRequest, DistributeRewardsResponse, EraValidatorsRequest,
+        EraValidatorsResponse, ExecuteResponse, ExecuteStreamResponse, GenesisResponse,
+        QueryResponse, SlashRequest, SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse,
+        UnbondingQueueRequest, UnbondingQueueResponse, UpgradeRequest, UpgradeResponse,
     },
     ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
-    mappings::{ParsingError, TransformMap},
+    mappings::{MappingError, ParsingError, TransformMap},
 };
 
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
+const METRIC_DURATION_EXEC_STREAM: &str = "exec_stream_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_CHECK_SIGNERS: &str = "check_signers_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_BID_STATE: &str = "bid_state_duration";
+const METRIC_DURATION_ERA_VALIDATORS: &str = "era_validators_duration";
+const METRIC_DURATION_UNBONDING_QUEUE: &str = "unbonding_queue_duration";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
+const TAG_RESPONSE_EXEC_STREAM: &str = "exec_stream_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_CHECK_SIGNERS: &str = "check_signers_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_BID_STATE: &str = "bid_state_response";
+const TAG_RESPONSE_ERA_VALIDATORS: &str = "era_validators_response";
+const TAG_RESPONSE_UNBONDING_QUEUE: &str = "unbonding_queue_response";
 
 const UNIMPLEMENTED: &str = "unimplemented";
 
@@ -77,7 +93,7 @@ const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 // (outer layer) leading to cleaner design.
 impl<S> ExecutionEngineService for EngineState<S>
 where
-    S: StateProvider,
+    S: StateProvider + Send + Sync + 'static,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
@@ -162,42 +178,79 @@ where
         SingleResponse::completed(response)
     }
 
-    fn execute(
+    fn check_signers(
         &self,
         _request_options: RequestOptions,
-        exec_request: ipc::ExecuteRequest,
-    ) -> SingleResponse<ExecuteResponse> {
+        check_signers_request: ipc::CheckSignersRequest,
+    ) -> SingleResponse<CheckSignersResponse> {
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
-        let exec_request: ExecuteRequest = match exec_request.try_into() {
+        let request: CheckSignersRequest = match check_signers_request.try_into() {
             Ok(ret) => ret,
             Err(err) => {
-                return SingleResponse::completed(err);
-            }
-        };
-
-        let mut exec_response = ExecuteResponse::new();
-
-        let results = match self.run_execute(correlation_id, exec_request) {
-            Ok(results) => results,
-            Err(error) => {
-                info!("deploy results error: RootNotFound");
-                exec_response.mut_missing_parent().set_hash(error.to_vec());
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ipc::CheckSignersResponse::new();
+                result.set_failure(log_message);
                 log_duration(
                     correlation_id,
-                    METRIC_DURATION_EXEC,
-                    TAG_RESPONSE_EXEC,
+                    METRIC_DURATION_CHECK_SIGNERS,
+                    TAG_RESPONSE_CHECK_SIGNERS,
                     start.elapsed(),
                 );
-                return SingleResponse::completed(exec_response);
+                return SingleResponse::completed(result);
             }
         };
 
-        let protobuf_results_iter = results.into_iter().map(Into::into);
-        exec_response
-            .mut_success()
-            .set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+        let result = self.run_check_signers(correlation_id, request);
+
+        let mut response = ipc::CheckSignersResponse::new();
+        match result {
+            Ok(CheckSignersResult::Success(signers_check)) => {
+                info!(
+                    "check signers successful; correlation_id: {}",
+                    correlation_id
+                );
+                response.set_success(signers_check.into());
+            }
+            Ok(CheckSignersResult::AccountNotFound(account)) => {
+                let log_message = format!("Account not found: {}", account);
+                info!("{}", log_message);
+                response.set_failure(log_message);
+            }
+            Ok(CheckSignersResult::RootNotFound) => {
+                let log_message = "Root not found";
+                info!("{}", log_message);
+                response.set_failure(log_message.to_string());
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_CHECK_SIGNERS,
+            TAG_RESPONSE_CHECK_SIGNERS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn execute(
+        &self,
+        _request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> SingleResponse<ExecuteResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let exec_response = run_execute(self, correlation_id, exec_request);
+
         log_duration(
             correlation_id,
             METRIC_DURATION_EXEC,
@@ -207,6 +260,43 @@ where
         SingleResponse::completed(exec_response)
     }
 
+    fn execute_stream(
+        &self,
+        _request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> StreamingResponse<ExecuteStreamResponse> {
+        let engine_state = self.clone();
+        let (sender, receiver) = mpsc::unbounded();
+
+        // The deploys are executed on their own thread, so that each result reaches the client as
+        // soon as it's known rather than once the whole block is executed.
+        thread::spawn(move || {
+            let start = Instant::now();
+            let correlation_id = CorrelationId::new();
+
+            run_execute_stream(
+                &engine_state,
+                correlation_id,
+                exec_request,
+                |stream_response| {
+                    // Fails only if the client dropped the stream, which leaves no one to tell
+                    let _ = sender.unbounded_send(stream_response);
+                },
+            );
+
+            log_duration(
+                correlation_id,
+                METRIC_DURATION_EXEC_STREAM,
+                TAG_RESPONSE_EXEC_STREAM,
+                start.elapsed(),
+            );
+        });
+
+        StreamingResponse::no_metadata(
+            receiver.map_err(|()| GrpcError::Other("execute stream receiver failed")),
+        )
+    }
+
     fn commit(
         &self,
         _request_options: RequestOptions,
@@ -252,18 +342,46 @@ where
             Ok(transforms) => transforms.into_inner(),
         };
 
+        #[cfg(feature = "account-activity-index")]
+        let deploy_hashes: Vec<[u8; 32]> = commit_request
+            .get_deploy_hashes()
+            .iter()
+            .filter_map(|deploy_hash| match deploy_hash.as_slice().try_into() {
+                Ok(deploy_hash) => Some(deploy_hash),
+                Err(_) => {
+                    warn!("Ignoring invalid deploy hash {:?}", deploy_hash);
+                    None
+                }
+            })
+            .collect();
+
         // "Apply" effects to global state
         let commit_response = {
             let mut ret = CommitResponse::new();
 
-            match self.apply_effect(correlation_id, protocol_version, pre_state_hash, transforms) {
+            // With deferred fsync, the commit is flushed in the background and the response
+            // doesn't wait for it.
+            match self
+                .apply_effect_with_durability(
+                    correlation_id,
+                    protocol_version,
+                    pre_state_hash,
+                    transforms,
+                )
+                .map(|(commit_result, _durability_handle)| commit_result)
+            {
                 Ok(CommitResult::Success {
                     state_root,
                     bonded_validators,
+                    stats,
                 }) => {
                     let properties = {
                         let mut tmp = BTreeMap::new();
                         tmp.insert("post-state-hash", format!("{:?}", state_root));
+                        tmp.insert("writes", stats.writes.to_string());
+                        tmp.insert("adds", stats.adds.to_string());
+                        tmp.insert("deletes", stats.deletes.to_string());
+                        tmp.insert("bytes-written", stats.bytes_written.to_string());
                         tmp.insert("success", true.to_string());
                         tmp
                     };
@@ -277,6 +395,17 @@ where
                     let commit_result = ret.mut_success();
                     commit_result.set_poststate_hash(state_root.to_vec());
                     commit_result.set_bonded_validators(bonds);
+                    commit_result.set_writes(stats.writes);
+                    commit_result.set_adds(stats.adds);
+                    commit_result.set_deletes(stats.deletes);
+                    commit_result.set_bytes_written(stats.bytes_written);
+
+                    #[cfg(feature = "account-activity-index")]
+                    {
+                        if let Err(error) = self.index_account_activity(&deploy_hashes) {
+                            warn!("Unable to index account activity: {:?}", error);
+                        }
+                    }
                 }
                 Ok(CommitResult::RootNotFound) => {
                     warn!("RootNotFound");
@@ -456,9 +585,83 @@ where
     fn bid_state(
         &self,
         _request_options: RequestOptions,
-        _bid_state_request: BidStateRequest,
+        bid_state_request: BidStateRequest,
     ) -> SingleResponse<BidStateResponse> {
-        SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let parent_state_hash = bid_state_request.get_parent_state_hash().to_vec();
+        let mut response = BidStateResponse::new();
+        match run_pos_view(self, correlation_id, bid_state_request.try_into()) {
+            Ok(PosViewResult::Success(pos_view)) => response.set_success(pos_view.into()),
+            Ok(PosViewResult::RootNotFound) => {
+                response.mut_missing_parent().set_hash(parent_state_hash)
+            }
+            Err(log_message) => response.set_failure(log_message),
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_BID_STATE,
+            TAG_RESPONSE_BID_STATE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn era_validators(
+        &self,
+        _request_options: RequestOptions,
+        era_validators_request: EraValidatorsRequest,
+    ) -> SingleResponse<EraValidatorsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let parent_state_hash = era_validators_request.get_parent_state_hash().to_vec();
+        let mut response = EraValidatorsResponse::new();
+        match run_era_validators(self, correlation_id, era_validators_request.try_into()) {
+            Ok(Some(era_validators)) => response.set_success(era_validators),
+            Ok(None) => response.mut_missing_parent().set_hash(parent_state_hash),
+            Err(log_message) => response.set_failure(log_message),
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_ERA_VALIDATORS,
+            TAG_RESPONSE_ERA_VALIDATORS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn unbonding_queue(
+        &self,
+        _request_options: RequestOptions,
+        unbonding_queue_request: UnbondingQueueRequest,
+    ) -> SingleResponse<UnbondingQueueResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let parent_state_hash = unbonding_queue_request.get_parent_state_hash().to_vec();
+        let mut response = UnbondingQueueResponse::new();
+        match run_pos_view(self, correlation_id, unbonding_queue_request.try_into()) {
+            Ok(PosViewResult::Success(pos_view)) => response.set_success(pos_view.into()),
+            Ok(PosViewResult::RootNotFound) => {
+                response.mut_missing_parent().set_hash(parent_state_hash)
+            }
+            Err(log_message) => response.set_failure(log_message),
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_UNBONDING_QUEUE,
+            TAG_RESPONSE_UNBONDING_QUEUE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
     }
 
     fn distribute_rewards(
@@ -486,6 +689,137 @@ where
     }
 }
 
+/// Executes the deploys of an execute request, shared by the `execute` and `execute_stream` RPCs.
+fn run_execute<S>(
+    engine_state: &EngineState<S>,
+    correlation_id: CorrelationId,
+    exec_request: ipc::ExecuteRequest,
+) -> ExecuteResponse
+where
+    S: StateProvider,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+{
+    let exec_request: ExecuteRequest = match exec_request.try_into() {
+        Ok(ret) => ret,
+        Err(err) => return err,
+    };
+
+    let mut exec_response = ExecuteResponse::new();
+
+    let results = match engine_state.run_execute(correlation_id, exec_request) {
+        Ok(results) => results,
+        Err(error) => {
+            info!("deploy results error: RootNotFound");
+            exec_response.mut_missing_parent().set_hash(error.to_vec());
+            return exec_response;
+        }
+    };
+
+    let protobuf_results_iter = results.into_iter().map(Into::into);
+    exec_response
+        .mut_success()
+        .set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+    exec_response
+}
+
+/// Executes the deploys of `exec_request` for the `execute_stream` RPC, passing each message of the
+/// stream to `send` as soon as it's known.
+fn run_execute_stream<S, F>(
+    engine_state: &EngineState<S>,
+    correlation_id: CorrelationId,
+    exec_request: ipc::ExecuteRequest,
+    mut send: F,
+) where
+    S: StateProvider,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+    F: FnMut(ExecuteStreamResponse),
+{
+    let exec_request: ExecuteRequest = match exec_request.try_into() {
+        Ok(ret) => ret,
+        Err(mut exec_response) => {
+            let mut stream_response = ExecuteStreamResponse::new();
+            stream_response.set_missing_parent(exec_response.take_missing_parent());
+            return send(stream_response);
+        }
+    };
+
+    let result = engine_state.run_execute_streaming(correlation_id, exec_request, |result| {
+        let mut stream_response = ExecuteStreamResponse::new();
+        stream_response.set_deploy_result(result.into());
+        send(stream_response)
+    });
+
+    if let Err(error) = result {
+        info!("deploy results error: RootNotFound");
+        let mut stream_response = ExecuteStreamResponse::new();
+        stream_response
+            .mut_missing_parent()
+            .set_hash(error.to_vec());
+        send(stream_response)
+    }
+}
+
+/// Runs a read-only view of the Proof of Stake contract for one of the PoS RPCs, logging and
+/// returning any failure as a message.
+fn run_pos_view<S>(
+    engine_state: &EngineState<S>,
+    correlation_id: CorrelationId,
+    maybe_request: Result<PosViewRequest, MappingError>,
+) -> Result<PosViewResult, String>
+where
+    S: StateProvider,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+{
+    let log_failure = |log_message: String| {
+        warn!("{}", log_message);
+        log_message
+    };
+    let request = maybe_request.map_err(|error| log_failure(format!("{:?}", error)))?;
+    let result = engine_state
+        .run_pos_view(correlation_id, request)
+        .map_err(|error| log_failure(format!("{:?}", error)))?;
+    info!("pos view successful; correlation_id: {}", correlation_id);
+    Ok(result)
+}
+
+/// Reads the validators of the era in progress for the `era_validators` RPC, returning `None` if
+/// the parent state is missing and logging and returning any failure as a message.
+fn run_era_validators<S>(
+    engine_state: &EngineState<S>,
+    correlation_id: CorrelationId,
+    maybe_request: Result<PosViewRequest, MappingError>,
+) -> Result<Option<ipc::EraValidators>, String>
+where
+    S: StateProvider,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+{
+    let log_failure = |log_message: String| {
+        warn!("{}", log_message);
+        log_message
+    };
+    let state_hash = maybe_request
+        .map_err(|error| log_failure(format!("{:?}", error)))?
+        .state_hash();
+    let era_id = match engine_state.get_current_era_id(correlation_id, state_hash) {
+        Ok(era_id) => era_id,
+        Err(EngineError::MissingRoot(_)) => return Ok(None),
+        Err(error) => return Err(log_failure(format!("{:?}", error))),
+    };
+    let era_validators = engine_state
+        .get_era_validators(correlation_id, state_hash, era_id)
+        .map_err(|error| log_failure(format!("{:?}", error)))?
+        .unwrap_or_default();
+    info!(
+        "era validators successful; correlation_id: {}",
+        correlation_id
+    );
+    Ok(Some((era_id, era_validators).into()))
+}
+
 // Helper method which returns single DeployResult that is set to be a
 // WasmError.
 pub fn new<E: ExecutionEngineService + Sync + Send + 'static>(
@@ -12,7 +12,7 @@ use std::{
 
 use clap::{App, Arg, ArgMatches};
 use dirs::home_dir;
-use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_core::engine_state::{metrics::LogMetrics, EngineConfig, EngineState, WhitelistMode};
 use lmdb::DatabaseFlags;
 use log::{error, info, Level, LevelFilter};
 
@@ -22,11 +22,16 @@ use engine_shared::{
     socket,
 };
 use engine_storage::{
-    global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
+    deploy_effects_store::lmdb::LmdbDeployEffectsStore,
+    global_state::lmdb::LmdbGlobalState,
+    transaction_source::lmdb::{LmdbConfig, LmdbEnvironment, LmdbSyncMode},
     trie_store::lmdb::LmdbTrieStore,
 };
+use types::U512;
 
 use casperlabs_engine_grpc_server::engine_server;
+#[cfg(feature = "rocksdb")]
+use engine_storage::global_state::rocksdb::RocksDbGlobalState;
 use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
 
 // exe / proc
@@ -47,7 +52,13 @@ const CREATE_DATA_DIR_EXPECT: &str = "Could not create directory";
 const LMDB_ENVIRONMENT_EXPECT: &str = "Could not create LmdbEnvironment";
 const LMDB_TRIE_STORE_EXPECT: &str = "Could not create LmdbTrieStore";
 const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create LmdbProtocolDataStore";
+const LMDB_DEPLOY_EFFECTS_STORE_EXPECT: &str = "Could not create LmdbDeployEffectsStore";
 const LMDB_GLOBAL_STATE_EXPECT: &str = "Could not create LmdbGlobalState";
+const ENGINE_STATE_EXPECT: &str = "Could not create EngineState";
+#[cfg(feature = "rocksdb")]
+const ROCKSDB_DIR: &str = "rocksdb";
+#[cfg(feature = "rocksdb")]
+const ROCKSDB_GLOBAL_STATE_EXPECT: &str = "Could not create RocksDbGlobalState";
 
 // pages / lmdb
 const ARG_PAGES: &str = "pages";
@@ -60,6 +71,30 @@ const GET_PAGES_EXPECT: &str = "Could not parse pages argument";
 // 805306368000 / 4096 = 196608000
 const DEFAULT_PAGES: usize = 196_608_000;
 
+// max pages / lmdb
+const ARG_MAX_PAGES: &str = "max-pages";
+const ARG_MAX_PAGES_VALUE: &str = "NUM";
+const ARG_MAX_PAGES_HELP: &str =
+    "Lets lmdb's mmap grow as it fills up, up to this many pages.  Defaults to the pages argument";
+const GET_MAX_PAGES_EXPECT: &str = "Could not parse max-pages argument";
+
+// sync mode / lmdb
+const ARG_LMDB_SYNC: &str = "lmdb-sync";
+const ARG_LMDB_SYNC_VALUE: &str = "MODE";
+const ARG_LMDB_SYNC_HELP: &str =
+    "Sets how lmdb flushes commits to disk, trading durability after a crash for speed";
+const LMDB_SYNC_FULL: &str = "full";
+const LMDB_SYNC_NO_META_SYNC: &str = "no-meta-sync";
+const LMDB_SYNC_NO_SYNC: &str = "no-sync";
+
+// storage
+const ARG_STORAGE: &str = "storage";
+const ARG_STORAGE_VALUE: &str = "BACKEND";
+const ARG_STORAGE_HELP: &str =
+    "Sets the database backing global state.  rocksdb requires building with the rocksdb feature";
+const STORAGE_LMDB: &str = "lmdb";
+const STORAGE_ROCKSDB: &str = "rocksdb";
+
 // socket
 const ARG_SOCKET: &str = "socket";
 const ARG_SOCKET_HELP: &str =
@@ -107,6 +142,72 @@ const ARG_ENABLE_BONDING: &str = "enable-bonding";
 const ARG_ENABLE_BONDING_SHORT: &str = "b";
 const ARG_ENABLE_BONDING_HELP: &str = "Enable bonding";
 
+// Replay protection
+const ARG_ENABLE_REPLAY_PROTECTION: &str = "enable-replay-protection";
+const ARG_ENABLE_REPLAY_PROTECTION_SHORT: &str = "r";
+const ARG_ENABLE_REPLAY_PROTECTION_HELP: &str =
+    "Record executed deploy hashes in global state and reject deploys which replay them";
+
+// Determinism checks
+const ARG_ENABLE_DETERMINISM_CHECKS: &str = "enable-determinism-checks";
+const ARG_ENABLE_DETERMINISM_CHECKS_SHORT: &str = "n";
+const ARG_ENABLE_DETERMINISM_CHECKS_HELP: &str =
+    "Reject deploys whose Wasm uses floating point or other non-deterministic features";
+
+// Deferred fsync
+const ARG_DEFERRED_FSYNC: &str = "deferred-fsync";
+const ARG_DEFERRED_FSYNC_HELP: &str =
+    "Respond to commits before flushing them to disk.  A crash may lose the most recent commits";
+
+// Execution result cache
+const ARG_ENABLE_EXECUTION_RESULT_CACHE: &str = "enable-execution-result-cache";
+const ARG_ENABLE_EXECUTION_RESULT_CACHE_HELP: &str =
+    "Reuse the results of deploys already executed against the same prestate in the same block";
+
+// Effects journal
+const ARG_ENABLE_EFFECTS_JOURNAL: &str = "enable-effects-journal";
+const ARG_ENABLE_EFFECTS_JOURNAL_HELP: &str =
+    "Record the execution effects of every deploy so they can be looked up by deploy hash";
+
+// Execution time limit
+const ARG_MAX_EXECUTION_MILLIS: &str = "max-execution-millis";
+const ARG_MAX_EXECUTION_MILLIS_SHORT: &str = "m";
+const ARG_MAX_EXECUTION_MILLIS_VALUE: &str = "MILLIS";
+const ARG_MAX_EXECUTION_MILLIS_HELP: &str =
+    "Aborts any phase of a deploy which runs for longer than this, even if it has gas left";
+const ARG_MAX_EXECUTION_MILLIS_EXPECT: &str = "expected valid max execution millis";
+
+// Deploy size limits
+const ARG_MAX_ARGS_BYTES: &str = "max-args-bytes";
+const ARG_MAX_ARGS_BYTES_VALUE: &str = "BYTES";
+const ARG_MAX_ARGS_BYTES_HELP: &str =
+    "Rejects deploys whose session or payment args are larger than this";
+const ARG_MAX_ARGS_BYTES_EXPECT: &str = "expected valid max args bytes";
+const ARG_MAX_MODULE_BYTES: &str = "max-module-bytes";
+const ARG_MAX_MODULE_BYTES_VALUE: &str = "BYTES";
+const ARG_MAX_MODULE_BYTES_HELP: &str =
+    "Rejects deploys whose session or payment wasm is larger than this";
+const ARG_MAX_MODULE_BYTES_EXPECT: &str = "expected valid max module bytes";
+const ARG_MIN_ACCOUNT_CREATION_BALANCE: &str = "min-account-creation-balance";
+const ARG_MIN_ACCOUNT_CREATION_BALANCE_VALUE: &str = "MOTES";
+const ARG_MIN_ACCOUNT_CREATION_BALANCE_HELP: &str =
+    "Fails transfers which would create an account with fewer motes than this";
+const ARG_MIN_ACCOUNT_CREATION_BALANCE_EXPECT: &str = "expected valid min account creation balance";
+const ARG_MIN_MAIN_PURSE_BALANCE: &str = "min-main-purse-balance";
+const ARG_MIN_MAIN_PURSE_BALANCE_VALUE: &str = "MOTES";
+const ARG_MIN_MAIN_PURSE_BALANCE_HELP: &str =
+    "Fails session transfers which would leave the account's main purse with fewer motes than this";
+const ARG_MIN_MAIN_PURSE_BALANCE_EXPECT: &str = "expected valid min main purse balance";
+
+// deploy whitelist
+const ARG_DEPLOY_WHITELIST: &str = "deploy-whitelist";
+const ARG_DEPLOY_WHITELIST_VALUE: &str = "MODE";
+const ARG_DEPLOY_WHITELIST_HELP: &str =
+    "Restricts sending deploys or installing contracts to accounts on the chain's deploy whitelist";
+const DEPLOY_WHITELIST_DISABLED: &str = "disabled";
+const DEPLOY_WHITELIST_DEPLOYS: &str = "deploys";
+const DEPLOY_WHITELIST_CONTRACT_INSTALLS: &str = "contract-installs";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -129,13 +230,22 @@ fn main() {
 
     let data_dir = get_data_dir(&arg_matches);
 
-    let map_size = get_map_size(&arg_matches);
+    let lmdb_config = get_lmdb_config(&arg_matches);
 
     let thread_count = get_thread_count(&arg_matches);
 
     let engine_config: EngineConfig = get_engine_config(&arg_matches);
 
-    let _server = get_grpc_server(&socket, data_dir, map_size, thread_count, engine_config);
+    let storage = get_storage(&arg_matches);
+
+    let _server = get_grpc_server(
+        &socket,
+        data_dir,
+        storage,
+        lmdb_config,
+        thread_count,
+        engine_config,
+    );
 
     log_listening_message(&socket);
 
@@ -218,6 +328,36 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_PAGES_HELP)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(ARG_MAX_PAGES)
+                .long(ARG_MAX_PAGES)
+                .value_name(ARG_MAX_PAGES_VALUE)
+                .help(ARG_MAX_PAGES_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_LMDB_SYNC)
+                .required(false)
+                .long(ARG_LMDB_SYNC)
+                .takes_value(true)
+                .possible_value(LMDB_SYNC_FULL)
+                .possible_value(LMDB_SYNC_NO_META_SYNC)
+                .possible_value(LMDB_SYNC_NO_SYNC)
+                .default_value(LMDB_SYNC_FULL)
+                .value_name(ARG_LMDB_SYNC_VALUE)
+                .help(ARG_LMDB_SYNC_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_STORAGE)
+                .required(false)
+                .long(ARG_STORAGE)
+                .takes_value(true)
+                .possible_value(STORAGE_LMDB)
+                .possible_value(STORAGE_ROCKSDB)
+                .default_value(STORAGE_LMDB)
+                .value_name(ARG_STORAGE_VALUE)
+                .help(ARG_STORAGE_HELP),
+        )
         .arg(
             Arg::with_name(ARG_THREAD_COUNT)
                 .short(ARG_THREAD_COUNT_SHORT)
@@ -239,6 +379,81 @@ fn get_args() -> ArgMatches<'static> {
                 .long(ARG_ENABLE_BONDING)
                 .help(ARG_ENABLE_BONDING_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_ENABLE_REPLAY_PROTECTION)
+                .short(ARG_ENABLE_REPLAY_PROTECTION_SHORT)
+                .long(ARG_ENABLE_REPLAY_PROTECTION)
+                .help(ARG_ENABLE_REPLAY_PROTECTION_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_ENABLE_DETERMINISM_CHECKS)
+                .short(ARG_ENABLE_DETERMINISM_CHECKS_SHORT)
+                .long(ARG_ENABLE_DETERMINISM_CHECKS)
+                .help(ARG_ENABLE_DETERMINISM_CHECKS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_DEFERRED_FSYNC)
+                .long(ARG_DEFERRED_FSYNC)
+                .help(ARG_DEFERRED_FSYNC_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_ENABLE_EXECUTION_RESULT_CACHE)
+                .long(ARG_ENABLE_EXECUTION_RESULT_CACHE)
+                .help(ARG_ENABLE_EXECUTION_RESULT_CACHE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_ENABLE_EFFECTS_JOURNAL)
+                .long(ARG_ENABLE_EFFECTS_JOURNAL)
+                .help(ARG_ENABLE_EFFECTS_JOURNAL_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_EXECUTION_MILLIS)
+                .short(ARG_MAX_EXECUTION_MILLIS_SHORT)
+                .long(ARG_MAX_EXECUTION_MILLIS)
+                .value_name(ARG_MAX_EXECUTION_MILLIS_VALUE)
+                .help(ARG_MAX_EXECUTION_MILLIS_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_ARGS_BYTES)
+                .long(ARG_MAX_ARGS_BYTES)
+                .value_name(ARG_MAX_ARGS_BYTES_VALUE)
+                .help(ARG_MAX_ARGS_BYTES_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_MODULE_BYTES)
+                .long(ARG_MAX_MODULE_BYTES)
+                .value_name(ARG_MAX_MODULE_BYTES_VALUE)
+                .help(ARG_MAX_MODULE_BYTES_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MIN_ACCOUNT_CREATION_BALANCE)
+                .long(ARG_MIN_ACCOUNT_CREATION_BALANCE)
+                .value_name(ARG_MIN_ACCOUNT_CREATION_BALANCE_VALUE)
+                .help(ARG_MIN_ACCOUNT_CREATION_BALANCE_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MIN_MAIN_PURSE_BALANCE)
+                .long(ARG_MIN_MAIN_PURSE_BALANCE)
+                .value_name(ARG_MIN_MAIN_PURSE_BALANCE_VALUE)
+                .help(ARG_MIN_MAIN_PURSE_BALANCE_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_DEPLOY_WHITELIST)
+                .required(false)
+                .long(ARG_DEPLOY_WHITELIST)
+                .takes_value(true)
+                .possible_value(DEPLOY_WHITELIST_DISABLED)
+                .possible_value(DEPLOY_WHITELIST_DEPLOYS)
+                .possible_value(DEPLOY_WHITELIST_CONTRACT_INSTALLS)
+                .default_value(DEPLOY_WHITELIST_DISABLED)
+                .value_name(ARG_DEPLOY_WHITELIST_VALUE)
+                .help(ARG_DEPLOY_WHITELIST_HELP),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
                 .required(true)
@@ -281,14 +496,32 @@ fn get_data_dir(arg_matches: &ArgMatches) -> PathBuf {
     buf
 }
 
-///  Parses pages argument and returns map size
-fn get_map_size(arg_matches: &ArgMatches) -> usize {
+/// Parses pages, max-pages and lmdb-sync arguments and returns an [`LmdbConfig`]
+fn get_lmdb_config(arg_matches: &ArgMatches) -> LmdbConfig {
     let page_size = get_page_size().unwrap();
     let pages = arg_matches
         .value_of(ARG_PAGES)
         .map_or(Ok(DEFAULT_PAGES), usize::from_str)
         .expect(GET_PAGES_EXPECT);
-    page_size * pages
+    let max_pages = arg_matches
+        .value_of(ARG_MAX_PAGES)
+        .map_or(Ok(pages), usize::from_str)
+        .expect(GET_MAX_PAGES_EXPECT);
+    let sync_mode = match arg_matches.value_of(ARG_LMDB_SYNC) {
+        Some(LMDB_SYNC_NO_META_SYNC) => LmdbSyncMode::NoMetaSync,
+        Some(LMDB_SYNC_NO_SYNC) => LmdbSyncMode::NoSync,
+        _ => LmdbSyncMode::Full,
+    };
+    LmdbConfig::new(page_size * pages)
+        .with_max_map_size(page_size * max_pages)
+        .with_sync_mode(sync_mode)
+}
+
+/// Returns the name of the database backing global state.
+fn get_storage<'a>(arg_matches: &'a ArgMatches) -> &'a str {
+    arg_matches
+        .value_of(ARG_STORAGE)
+        .expect("should have default value if not explicitly set")
 }
 
 fn get_thread_count(arg_matches: &ArgMatches) -> usize {
@@ -304,34 +537,80 @@ fn get_engine_config(arg_matches: &ArgMatches) -> EngineConfig {
     // feature flags go here
     let use_system_contracts = arg_matches.is_present(ARG_USE_SYSTEM_CONTRACTS);
     let enable_bonding = arg_matches.is_present(ARG_ENABLE_BONDING);
+    let enable_replay_protection = arg_matches.is_present(ARG_ENABLE_REPLAY_PROTECTION);
+    let enable_determinism_checks = arg_matches.is_present(ARG_ENABLE_DETERMINISM_CHECKS);
+    let deferred_fsync = arg_matches.is_present(ARG_DEFERRED_FSYNC);
+    let enable_execution_result_cache = arg_matches.is_present(ARG_ENABLE_EXECUTION_RESULT_CACHE);
+    let enable_effects_journal = arg_matches.is_present(ARG_ENABLE_EFFECTS_JOURNAL);
+    let execution_time_limit = arg_matches
+        .value_of(ARG_MAX_EXECUTION_MILLIS)
+        .map(|millis| u64::from_str(millis).expect(ARG_MAX_EXECUTION_MILLIS_EXPECT))
+        .map(Duration::from_millis);
+    let max_args_bytes = arg_matches
+        .value_of(ARG_MAX_ARGS_BYTES)
+        .map(|bytes| usize::from_str(bytes).expect(ARG_MAX_ARGS_BYTES_EXPECT));
+    let max_module_bytes = arg_matches
+        .value_of(ARG_MAX_MODULE_BYTES)
+        .map(|bytes| usize::from_str(bytes).expect(ARG_MAX_MODULE_BYTES_EXPECT));
+    let min_account_creation_balance = arg_matches
+        .value_of(ARG_MIN_ACCOUNT_CREATION_BALANCE)
+        .map(|motes| U512::from_dec_str(motes).expect(ARG_MIN_ACCOUNT_CREATION_BALANCE_EXPECT));
+    let min_main_purse_balance = arg_matches
+        .value_of(ARG_MIN_MAIN_PURSE_BALANCE)
+        .map(|motes| U512::from_dec_str(motes).expect(ARG_MIN_MAIN_PURSE_BALANCE_EXPECT));
+    let whitelist_mode = match arg_matches.value_of(ARG_DEPLOY_WHITELIST) {
+        Some(DEPLOY_WHITELIST_DEPLOYS) => WhitelistMode::Deploys,
+        Some(DEPLOY_WHITELIST_CONTRACT_INSTALLS) => WhitelistMode::ContractInstalls,
+        _ => WhitelistMode::Disabled,
+    };
     EngineConfig::new()
         .with_use_system_contracts(use_system_contracts)
         .with_enable_bonding(enable_bonding)
+        .with_enable_replay_protection(enable_replay_protection)
+        .with_enable_determinism_checks(enable_determinism_checks)
+        .with_execution_time_limit(execution_time_limit)
+        .with_whitelist_mode(whitelist_mode)
+        .with_deferred_fsync(deferred_fsync)
+        .with_enable_execution_result_cache(enable_execution_result_cache)
+        .with_enable_effects_journal(enable_effects_journal)
+        .with_max_args_bytes(max_args_bytes)
+        .with_max_module_bytes(max_module_bytes)
+        .with_min_account_creation_balance(min_account_creation_balance)
+        .with_min_main_purse_balance(min_main_purse_balance)
 }
 
 /// Builds and returns a gRPC server.
 fn get_grpc_server(
     socket: &socket::Socket,
     data_dir: PathBuf,
-    map_size: usize,
+    storage: &str,
+    lmdb_config: LmdbConfig,
     thread_count: usize,
     engine_config: EngineConfig,
 ) -> grpc::Server {
-    let engine_state = get_engine_state(data_dir, map_size, engine_config);
+    let server_builder = match storage {
+        STORAGE_ROCKSDB => {
+            let engine_state = get_rocksdb_engine_state(data_dir, engine_config);
+            engine_server::new(socket.as_str(), thread_count, engine_state)
+        }
+        _ => {
+            let engine_state = get_engine_state(data_dir, lmdb_config, engine_config);
+            engine_server::new(socket.as_str(), thread_count, engine_state)
+        }
+    };
 
-    engine_server::new(socket.as_str(), thread_count, engine_state)
-        .build()
-        .expect(SERVER_START_EXPECT)
+    server_builder.build().expect(SERVER_START_EXPECT)
 }
 
 /// Builds and returns engine global state
 fn get_engine_state(
     data_dir: PathBuf,
-    map_size: usize,
+    lmdb_config: LmdbConfig,
     engine_config: EngineConfig,
 ) -> EngineState<LmdbGlobalState> {
     let environment = {
-        let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
+        let ret =
+            LmdbEnvironment::with_config(&data_dir, lmdb_config).expect(LMDB_ENVIRONMENT_EXPECT);
         Arc::new(ret)
     };
 
@@ -347,10 +626,41 @@ fn get_engine_state(
         Arc::new(ret)
     };
 
-    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-        .expect(LMDB_GLOBAL_STATE_EXPECT);
+    let deploy_effects_store = {
+        let ret = LmdbDeployEffectsStore::new(&environment, None, DatabaseFlags::empty())
+            .expect(LMDB_DEPLOY_EFFECTS_STORE_EXPECT);
+        Arc::new(ret)
+    };
+
+    let global_state = LmdbGlobalState::empty(
+        environment,
+        trie_store,
+        protocol_data_store,
+        deploy_effects_store,
+    )
+    .expect(LMDB_GLOBAL_STATE_EXPECT);
 
-    EngineState::new(global_state, engine_config)
+    EngineState::new(global_state, engine_config, Arc::new(LogMetrics)).expect(ENGINE_STATE_EXPECT)
+}
+
+/// Builds and returns engine global state backed by RocksDB
+#[cfg(feature = "rocksdb")]
+fn get_rocksdb_engine_state(
+    mut data_dir: PathBuf,
+    engine_config: EngineConfig,
+) -> EngineState<RocksDbGlobalState> {
+    data_dir.push(ROCKSDB_DIR);
+    let global_state = RocksDbGlobalState::open(&data_dir).expect(ROCKSDB_GLOBAL_STATE_EXPECT);
+
+    EngineState::new(global_state, engine_config, Arc::new(LogMetrics)).expect(ENGINE_STATE_EXPECT)
+}
+
+#[cfg(not(feature = "rocksdb"))]
+fn get_rocksdb_engine_state(
+    _data_dir: PathBuf,
+    _engine_config: EngineConfig,
+) -> EngineState<LmdbGlobalState> {
+    panic!("{} was built without the rocksdb feature", PROC_NAME)
 }
 
 /// Builds and returns log settings
@@ -5,11 +5,11 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use types::{
     account::{
-        ActionType, AddKeyFailure, PublicKey, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        ActionType, AddKeyFailure, AssociatedKeysUpdate, PublicKey, RemoveKeyFailure,
+        SetThresholdFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr::{self, Error, FromBytes, ToBytes},
-    AccessRights, Key, URef,
+    AccessRights, ApiError, Key, URef,
 };
 
 pub use action_thresholds::ActionThresholds;
@@ -22,6 +22,8 @@ pub struct Account {
     main_purse: URef,
     associated_keys: AssociatedKeys,
     action_thresholds: ActionThresholds,
+    sequence_number: u64,
+    can_install_contracts: bool,
 }
 
 impl Account {
@@ -38,6 +40,8 @@ impl Account {
             main_purse,
             associated_keys,
             action_thresholds,
+            sequence_number: 0,
+            can_install_contracts: true,
         }
     }
 
@@ -86,6 +90,31 @@ impl Account {
         &self.action_thresholds
     }
 
+    /// Returns the sequence number of the last sequenced deploy executed by the account, or `0`
+    /// if there hasn't been one.
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn set_sequence_number(&mut self, sequence_number: u64) {
+        self.sequence_number = sequence_number;
+    }
+
+    /// Returns the sequence number the account's next sequenced deploy must carry.
+    pub fn next_sequence_number(&self) -> u64 {
+        self.sequence_number.saturating_add(1)
+    }
+
+    /// Returns whether the account may store new contracts.  This is independent of its ability to
+    /// run session code and call existing contracts, which it always has.
+    pub fn can_install_contracts(&self) -> bool {
+        self.can_install_contracts
+    }
+
+    pub fn set_can_install_contracts(&mut self, can_install_contracts: bool) {
+        self.can_install_contracts = can_install_contracts;
+    }
+
     pub fn add_associated_key(
         &mut self,
         public_key: PublicKey,
@@ -170,6 +199,50 @@ impl Account {
         Ok(())
     }
 
+    /// Applies `updates` in order, leaving the account untouched if any of them fails.
+    ///
+    /// Unlike the individual key management functions, the thresholds are only checked against
+    /// the result of the whole batch, so e.g. a key may be removed before its replacement is added.
+    /// The limit on the number of associated keys still applies at every step.
+    pub fn update_associated_keys(
+        &mut self,
+        updates: &[AssociatedKeysUpdate],
+    ) -> Result<(), ApiError> {
+        let mut associated_keys = self.associated_keys.clone();
+        let mut deployment = *self.action_thresholds.deployment();
+        let mut key_management = *self.action_thresholds.key_management();
+
+        for update in updates {
+            match *update {
+                AssociatedKeysUpdate::Add(public_key, weight) => {
+                    associated_keys.add_key(public_key, weight)?
+                }
+                AssociatedKeysUpdate::Remove(public_key) => {
+                    associated_keys.remove_key(&public_key)?
+                }
+                AssociatedKeysUpdate::UpdateWeight(public_key, weight) => {
+                    associated_keys.update_key(public_key, weight)?
+                }
+                AssociatedKeysUpdate::SetThreshold(ActionType::Deployment, weight) => {
+                    deployment = weight
+                }
+                AssociatedKeysUpdate::SetThreshold(ActionType::KeyManagement, weight) => {
+                    key_management = weight
+                }
+            }
+        }
+
+        let action_thresholds = ActionThresholds::new(deployment, key_management)?;
+        // Deployment threshold is not greater than key management threshold at this point
+        if associated_keys.total_keys_weight() < key_management {
+            return Err(SetThresholdFailure::InsufficientTotalWeight.into());
+        }
+
+        self.associated_keys = associated_keys;
+        self.action_thresholds = action_thresholds;
+        Ok(())
+    }
+
     /// Checks whether all authorization keys are associated with this account
     pub fn can_authorize(&self, authorization_keys: &BTreeSet<PublicKey>) -> bool {
         !authorization_keys.is_empty()
@@ -178,12 +251,17 @@ impl Account {
                 .all(|e| self.associated_keys.contains_key(e))
     }
 
+    /// Calculates the sum of the weights of all authorization keys which are associated with this
+    /// account.
+    pub fn calculate_keys_weight(&self, authorization_keys: &BTreeSet<PublicKey>) -> Weight {
+        self.associated_keys
+            .calculate_keys_weight(authorization_keys)
+    }
+
     /// Checks whether the sum of the weights of all authorization keys is
     /// greater or equal to deploy threshold.
     pub fn can_deploy_with(&self, authorization_keys: &BTreeSet<PublicKey>) -> bool {
-        let total_weight = self
-            .associated_keys
-            .calculate_keys_weight(authorization_keys);
+        let total_weight = self.calculate_keys_weight(authorization_keys);
 
         total_weight >= *self.action_thresholds().deployment()
     }
@@ -191,9 +269,7 @@ impl Account {
     /// Checks whether the sum of the weights of all authorization keys is
     /// greater or equal to key management threshold.
     pub fn can_manage_keys_with(&self, authorization_keys: &BTreeSet<PublicKey>) -> bool {
-        let total_weight = self
-            .associated_keys
-            .calculate_keys_weight(authorization_keys);
+        let total_weight = self.calculate_keys_weight(authorization_keys);
 
         total_weight >= *self.action_thresholds().key_management()
     }
@@ -207,6 +283,8 @@ impl ToBytes for Account {
         result.append(&mut self.main_purse.to_bytes()?);
         result.append(&mut self.associated_keys.to_bytes()?);
         result.append(&mut self.action_thresholds.to_bytes()?);
+        result.append(&mut self.sequence_number.to_bytes()?);
+        result.append(&mut self.can_install_contracts.to_bytes()?);
         Ok(result)
     }
 
@@ -216,6 +294,8 @@ impl ToBytes for Account {
             + self.main_purse.serialized_length()
             + self.associated_keys.serialized_length()
             + self.action_thresholds.serialized_length()
+            + self.sequence_number.serialized_length()
+            + self.can_install_contracts.serialized_length()
     }
 }
 
@@ -226,6 +306,8 @@ impl FromBytes for Account {
         let (main_purse, rem) = URef::from_bytes(rem)?;
         let (associated_keys, rem) = AssociatedKeys::from_bytes(rem)?;
         let (action_thresholds, rem) = ActionThresholds::from_bytes(rem)?;
+        let (sequence_number, rem) = u64::from_bytes(rem)?;
+        let (can_install_contracts, rem) = bool::from_bytes(rem)?;
         Ok((
             Account {
                 public_key,
@@ -233,6 +315,8 @@ impl FromBytes for Account {
                 main_purse,
                 associated_keys,
                 action_thresholds,
+                sequence_number,
+                can_install_contracts,
             },
             rem,
         ))
@@ -259,15 +343,20 @@ pub mod gens {
             purse in uref_arb(),
             thresholds in action_thresholds_arb(),
             mut associated_keys in associated_keys_arb(MAX_ASSOCIATED_KEYS - 1),
+            sequence_number in any::<u64>(),
+            can_install_contracts in any::<bool>(),
         ) -> Account {
                 associated_keys.add_key(pub_key, Weight::new(1)).unwrap();
-                Account::new(
+                let mut account = Account::new(
                     pub_key,
                     urefs,
                     purse,
                     associated_keys,
                     thresholds,
-                )
+                );
+                account.set_sequence_number(sequence_number);
+                account.set_can_install_contracts(can_install_contracts);
+                account
         }
     }
 }
@@ -645,4 +734,89 @@ mod tests {
             .update_associated_key(key_1, Weight::new(1))
             .expect("should work");
     }
+
+    fn single_key_account(identity_key: PublicKey) -> Account {
+        Account::new(
+            identity_key,
+            BTreeMap::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            AssociatedKeys::new(identity_key, Weight::new(1)),
+            ActionThresholds::new(Weight::new(1), Weight::new(1))
+                .expect("should create thresholds"),
+        )
+    }
+
+    #[test]
+    fn should_apply_batch_which_passes_through_invalid_states() {
+        let identity_key = PublicKey::ed25519_from([1; 32]);
+        let new_key = PublicKey::ed25519_from([2; 32]);
+        let mut account = single_key_account(identity_key);
+
+        // Removing the only key first, and raising the deployment threshold above the key
+        // management threshold, would each be rejected on their own
+        account
+            .update_associated_keys(&[
+                AssociatedKeysUpdate::Remove(identity_key),
+                AssociatedKeysUpdate::SetThreshold(ActionType::Deployment, Weight::new(2)),
+                AssociatedKeysUpdate::SetThreshold(ActionType::KeyManagement, Weight::new(3)),
+                AssociatedKeysUpdate::Add(new_key, Weight::new(2)),
+                AssociatedKeysUpdate::UpdateWeight(new_key, Weight::new(3)),
+            ])
+            .expect("should apply batch");
+
+        assert_eq!(account.get_associated_key_weight(identity_key), None);
+        assert_eq!(
+            account.get_associated_key_weight(new_key),
+            Some(&Weight::new(3))
+        );
+        assert_eq!(
+            account.action_thresholds(),
+            &ActionThresholds::new(Weight::new(2), Weight::new(3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_reject_batch_leaving_insufficient_weight() {
+        let identity_key = PublicKey::ed25519_from([1; 32]);
+        let mut account = single_key_account(identity_key);
+        let expected = account.clone();
+
+        assert_eq!(
+            account.update_associated_keys(&[AssociatedKeysUpdate::SetThreshold(
+                ActionType::KeyManagement,
+                Weight::new(2)
+            )]),
+            Err(ApiError::InsufficientTotalWeight)
+        );
+        assert_eq!(
+            account.update_associated_keys(&[AssociatedKeysUpdate::Remove(identity_key)]),
+            Err(ApiError::InsufficientTotalWeight)
+        );
+        assert_eq!(
+            account.update_associated_keys(&[AssociatedKeysUpdate::SetThreshold(
+                ActionType::Deployment,
+                Weight::new(2)
+            )]),
+            Err(ApiError::DeploymentThreshold)
+        );
+        assert_eq!(account, expected);
+    }
+
+    #[test]
+    fn should_leave_account_untouched_on_failure_mid_batch() {
+        let identity_key = PublicKey::ed25519_from([1; 32]);
+        let key_1 = PublicKey::ed25519_from([2; 32]);
+        let missing_key = PublicKey::ed25519_from([3; 32]);
+        let mut account = single_key_account(identity_key);
+        let expected = account.clone();
+
+        assert_eq!(
+            account.update_associated_keys(&[
+                AssociatedKeysUpdate::Add(key_1, Weight::new(1)),
+                AssociatedKeysUpdate::Remove(missing_key),
+            ]),
+            Err(ApiError::MissingKey)
+        );
+        assert_eq!(account, expected);
+    }
 }
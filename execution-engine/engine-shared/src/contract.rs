@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use types::{
     bytesrepr::{self, Error, FromBytes, ToBytes},
-    Key, ProtocolVersion,
+    ContractAbi, Key, ProtocolVersion,
 };
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -10,6 +10,8 @@ pub struct Contract {
     bytes: Vec<u8>,
     named_keys: BTreeMap<String, Key>,
     protocol_version: ProtocolVersion,
+    abi: Option<ContractAbi>,
+    has_entry_points: bool,
 }
 
 impl Contract {
@@ -22,9 +24,25 @@ impl Contract {
             bytes,
             named_keys,
             protocol_version,
+            abi: None,
+            has_entry_points: false,
         }
     }
 
+    /// Attaches a description of the contract's entry points, as supplied by its author.
+    pub fn with_abi(mut self, abi: Option<ContractAbi>) -> Self {
+        self.abi = abi;
+        self
+    }
+
+    /// Makes the contract callable only through the entry points of `abi`, each of which is a
+    /// function exported by the contract's module.
+    pub fn with_entry_points(mut self, abi: ContractAbi) -> Self {
+        self.abi = Some(abi);
+        self.has_entry_points = true;
+        self
+    }
+
     pub fn named_keys_append(&mut self, keys: &mut BTreeMap<String, Key>) {
         self.named_keys.append(keys);
     }
@@ -49,6 +67,16 @@ impl Contract {
         self.protocol_version
     }
 
+    pub fn abi(&self) -> Option<&ContractAbi> {
+        self.abi.as_ref()
+    }
+
+    /// Returns whether the contract is called through the entry points of its ABI rather than a
+    /// single exported `call` function.
+    pub fn has_entry_points(&self) -> bool {
+        self.has_entry_points
+    }
+
     pub fn take_named_keys(self) -> BTreeMap<String, Key> {
         self.named_keys
     }
@@ -60,6 +88,8 @@ impl ToBytes for Contract {
         result.append(&mut self.bytes.to_bytes()?);
         result.append(&mut self.named_keys.to_bytes()?);
         result.append(&mut self.protocol_version.to_bytes()?);
+        result.append(&mut self.abi.to_bytes()?);
+        result.append(&mut self.has_entry_points.to_bytes()?);
         Ok(result)
     }
 
@@ -67,6 +97,8 @@ impl ToBytes for Contract {
         self.bytes.serialized_length()
             + self.named_keys.serialized_length()
             + self.protocol_version.serialized_length()
+            + self.abi.serialized_length()
+            + self.has_entry_points.serialized_length()
     }
 }
 
@@ -75,29 +107,43 @@ impl FromBytes for Contract {
         let (bytes, rem1) = Vec::<u8>::from_bytes(bytes)?;
         let (named_keys, rem2) = BTreeMap::<String, Key>::from_bytes(rem1)?;
         let (protocol_version, rem3) = ProtocolVersion::from_bytes(rem2)?;
+        let (abi, rem4) = Option::<ContractAbi>::from_bytes(rem3)?;
+        let (has_entry_points, rem5) = bool::from_bytes(rem4)?;
         Ok((
             Contract {
                 bytes,
                 named_keys,
                 protocol_version,
+                abi,
+                has_entry_points,
             },
-            rem3,
+            rem5,
         ))
     }
 }
 
 pub mod gens {
-    use proptest::{collection::vec, prelude::*};
+    use proptest::{collection::vec, option, prelude::*};
 
-    use types::gens::{named_keys_arb, protocol_version_arb};
+    use types::gens::{contract_abi_arb, named_keys_arb, protocol_version_arb};
 
     use super::Contract;
 
     pub fn contract_arb() -> impl Strategy<Value = Contract> {
         protocol_version_arb().prop_flat_map(move |protocol_version_arb| {
             named_keys_arb(20).prop_flat_map(move |urefs| {
-                vec(any::<u8>(), 1..1000)
-                    .prop_map(move |body| Contract::new(body, urefs.clone(), protocol_version_arb))
+                (
+                    vec(any::<u8>(), 1..1000),
+                    option::of(contract_abi_arb()),
+                    any::<bool>(),
+                )
+                    .prop_map(move |(body, abi, has_entry_points)| {
+                        let contract = Contract::new(body, urefs.clone(), protocol_version_arb);
+                        match abi {
+                            Some(abi) if has_entry_points => contract.with_entry_points(abi),
+                            abi => contract.with_abi(abi),
+                        }
+                    })
             })
         })
     }
@@ -1,6 +1,7 @@
 //! A logger implementation which outputs log messages from CasperLabs crates to the terminal.
 
 mod settings;
+mod stage_logger;
 mod structured_message;
 mod terminal_logger;
 
@@ -14,6 +15,7 @@ use log::{self, Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
 pub use self::terminal_logger::TerminalLogger;
 use crate::newtypes::CorrelationId;
 pub use settings::{Settings, Style};
+pub use stage_logger::{DefaultStageLogger, Stage, StageLogger, StageOutcome};
 
 #[doc(hidden)]
 pub const PAYLOAD_KEY: &str = "payload=";
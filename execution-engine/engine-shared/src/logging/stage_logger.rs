@@ -0,0 +1,94 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug, Display, Formatter},
+    time::Duration,
+};
+
+use log::Level;
+
+use crate::newtypes::CorrelationId;
+
+/// A stage of executing and committing a deploy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Preconditions,
+    Payment,
+    Session,
+    Finalize,
+    Commit,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Stage::Preconditions => "preconditions",
+            Stage::Payment => "payment",
+            Stage::Session => "session",
+            Stage::Finalize => "finalize",
+            Stage::Commit => "commit",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How a [`Stage`] ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageOutcome {
+    Success,
+    Failure(String),
+}
+
+impl Display for StageOutcome {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            StageOutcome::Success => write!(f, "success"),
+            StageOutcome::Failure(error) => write!(f, "failure: {}", error),
+        }
+    }
+}
+
+/// Receives an event each time a [`Stage`] ends.
+pub trait StageLogger: Debug + Send + Sync {
+    fn log_stage(
+        &self,
+        correlation_id: CorrelationId,
+        stage: Stage,
+        duration: Duration,
+        outcome: &StageOutcome,
+    );
+}
+
+/// Logs stage events as structured messages via [`log_details`](super::log_details), successes at
+/// debug level and failures at info level.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultStageLogger;
+
+impl StageLogger for DefaultStageLogger {
+    fn log_stage(
+        &self,
+        correlation_id: CorrelationId,
+        stage: Stage,
+        duration: Duration,
+        outcome: &StageOutcome,
+    ) {
+        let log_level = match outcome {
+            StageOutcome::Success => Level::Debug,
+            StageOutcome::Failure(_) => Level::Info,
+        };
+        let mut properties = BTreeMap::new();
+        properties.insert("correlation_id", correlation_id.to_string());
+        properties.insert("stage", stage.to_string());
+        properties.insert(
+            "duration_in_seconds",
+            format!("{:?}", duration.as_secs_f64()),
+        );
+        properties.insert("outcome", outcome.to_string());
+        super::log_details(
+            log_level,
+            "{stage} finished in {duration_in_seconds}s with {outcome}; correlation_id: \
+             {correlation_id}"
+                .to_owned(),
+            properties,
+        );
+    }
+}
@@ -25,6 +25,18 @@ pub fn wasm_costs_mock() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        max_memory_pages: 64,
+        max_table_size: 4096,
+        hash_per_byte: 2,
+        verify_ed25519: 25_000,
+        verify_secp256k1: 50_000,
+        bignum_op: 10,
+        storage_bucket_size: 128,
+        storage_read_per_bucket: 50,
+        storage_write_per_bucket: 500,
+        max_module_size: 4 * 1024 * 1024,
+        max_function_count: 10_000,
+        max_named_keys_per_write: 10_000,
     }
 }
 
@@ -40,5 +52,17 @@ pub fn wasm_costs_free() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 1,
         opcodes_div: 1,
+        max_memory_pages: 64,
+        max_table_size: 4096,
+        hash_per_byte: 0,
+        verify_ed25519: 0,
+        verify_secp256k1: 0,
+        bignum_op: 0,
+        storage_bucket_size: 128,
+        storage_read_per_bucket: 0,
+        storage_write_per_bucket: 0,
+        max_module_size: 4 * 1024 * 1024,
+        max_function_count: 10_000,
+        max_named_keys_per_write: 10_000,
     }
 }
@@ -10,7 +10,7 @@ use std::{
 use num::traits::{AsPrimitive, WrappingAdd};
 
 use types::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     CLType, CLTyped, CLValue, CLValueError, Key, U128, U256, U512,
 };
 
@@ -59,6 +59,8 @@ pub enum Transform {
     AddUInt256(U256),
     AddUInt512(U512),
     AddKeys(BTreeMap<String, Key>),
+    /// Removes the value under the key from global state.
+    Delete,
     Failure(Error),
 }
 
@@ -160,6 +162,12 @@ impl Transform {
                     Err(TypeMismatch::new(expected, found).into())
                 }
             },
+            // A deleted key holds no value, so callers must handle `Delete` before applying.
+            Transform::Delete => Err(TypeMismatch::new(
+                "Identity, Write or Add transform".to_string(),
+                "Delete".to_string(),
+            )
+            .into()),
             Transform::Failure(error) => Err(error),
         }
     }
@@ -207,6 +215,11 @@ impl Add for Transform {
             (a @ Transform::Failure(_), _) => a,
             (_, b @ Transform::Failure(_)) => b,
             (_, b @ Transform::Write(_)) => b,
+            (_, b @ Transform::Delete) => b,
+            // nothing can be added to a deleted value
+            (Transform::Delete, b) => Transform::Failure(
+                TypeMismatch::new("Write or Delete".to_owned(), format!("{:?}", b)).into(),
+            ),
             (Transform::Write(v), b) => {
                 // second transform changes value being written
                 match b.apply(v) {
@@ -268,6 +281,146 @@ impl Default for Transform {
     }
 }
 
+#[repr(u8)]
+enum Tag {
+    Identity = 0,
+    Write = 1,
+    AddInt32 = 2,
+    AddUInt64 = 3,
+    AddUInt128 = 4,
+    AddUInt256 = 5,
+    AddUInt512 = 6,
+    AddKeys = 7,
+    Delete = 8,
+    Failure = 9,
+}
+
+#[repr(u8)]
+enum ErrorTag {
+    Serialization = 0,
+    TypeMismatch = 1,
+}
+
+impl ToBytes for Error {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            Error::Serialization(error) => {
+                result.push(ErrorTag::Serialization as u8);
+                result.push(error.clone() as u8);
+            }
+            Error::TypeMismatch(TypeMismatch { expected, found }) => {
+                result.push(ErrorTag::TypeMismatch as u8);
+                result.append(&mut expected.to_bytes()?);
+                result.append(&mut found.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                Error::Serialization(_) => U8_SERIALIZED_LENGTH,
+                Error::TypeMismatch(TypeMismatch { expected, found }) => {
+                    expected.serialized_length() + found.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for Error {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            tag if tag == ErrorTag::Serialization as u8 => {
+                let (error, remainder): (u8, &[u8]) = FromBytes::from_bytes(remainder)?;
+                let error = match error {
+                    0 => bytesrepr::Error::EarlyEndOfStream,
+                    1 => bytesrepr::Error::Formatting,
+                    2 => bytesrepr::Error::LeftOverBytes,
+                    3 => bytesrepr::Error::OutOfMemory,
+                    _ => return Err(bytesrepr::Error::Formatting),
+                };
+                Ok((Error::Serialization(error), remainder))
+            }
+            tag if tag == ErrorTag::TypeMismatch as u8 => {
+                let (expected, remainder) = String::from_bytes(remainder)?;
+                let (found, remainder) = String::from_bytes(remainder)?;
+                Ok((
+                    Error::TypeMismatch(TypeMismatch { expected, found }),
+                    remainder,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+impl ToBytes for Transform {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        let (tag, mut serialized_data) = match self {
+            Transform::Identity => (Tag::Identity, Vec::new()),
+            Transform::Write(stored_value) => (Tag::Write, stored_value.to_bytes()?),
+            Transform::AddInt32(i) => (Tag::AddInt32, i.to_bytes()?),
+            Transform::AddUInt64(u) => (Tag::AddUInt64, u.to_bytes()?),
+            Transform::AddUInt128(u) => (Tag::AddUInt128, u.to_bytes()?),
+            Transform::AddUInt256(u) => (Tag::AddUInt256, u.to_bytes()?),
+            Transform::AddUInt512(u) => (Tag::AddUInt512, u.to_bytes()?),
+            Transform::AddKeys(keys) => (Tag::AddKeys, keys.to_bytes()?),
+            Transform::Delete => (Tag::Delete, Vec::new()),
+            Transform::Failure(error) => (Tag::Failure, error.to_bytes()?),
+        };
+        result.push(tag as u8);
+        result.append(&mut serialized_data);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                Transform::Identity | Transform::Delete => 0,
+                Transform::Write(stored_value) => stored_value.serialized_length(),
+                Transform::AddInt32(i) => i.serialized_length(),
+                Transform::AddUInt64(u) => u.serialized_length(),
+                Transform::AddUInt128(u) => u.serialized_length(),
+                Transform::AddUInt256(u) => u.serialized_length(),
+                Transform::AddUInt512(u) => u.serialized_length(),
+                Transform::AddKeys(keys) => keys.serialized_length(),
+                Transform::Failure(error) => error.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for Transform {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            tag if tag == Tag::Identity as u8 => Ok((Transform::Identity, remainder)),
+            tag if tag == Tag::Write as u8 => StoredValue::from_bytes(remainder)
+                .map(|(stored_value, remainder)| (Transform::Write(stored_value), remainder)),
+            tag if tag == Tag::AddInt32 as u8 => {
+                i32::from_bytes(remainder).map(|(i, remainder)| (Transform::AddInt32(i), remainder))
+            }
+            tag if tag == Tag::AddUInt64 as u8 => u64::from_bytes(remainder)
+                .map(|(u, remainder)| (Transform::AddUInt64(u), remainder)),
+            tag if tag == Tag::AddUInt128 as u8 => U128::from_bytes(remainder)
+                .map(|(u, remainder)| (Transform::AddUInt128(u), remainder)),
+            tag if tag == Tag::AddUInt256 as u8 => U256::from_bytes(remainder)
+                .map(|(u, remainder)| (Transform::AddUInt256(u), remainder)),
+            tag if tag == Tag::AddUInt512 as u8 => U512::from_bytes(remainder)
+                .map(|(u, remainder)| (Transform::AddUInt512(u), remainder)),
+            tag if tag == Tag::AddKeys as u8 => BTreeMap::from_bytes(remainder)
+                .map(|(keys, remainder)| (Transform::AddKeys(keys), remainder)),
+            tag if tag == Tag::Delete as u8 => Ok((Transform::Delete, remainder)),
+            tag if tag == Tag::Failure as u8 => Error::from_bytes(remainder)
+                .map(|(error, remainder)| (Transform::Failure(error), remainder)),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
 pub mod gens {
     use proptest::{collection::vec, prelude::*};
 
@@ -278,6 +431,7 @@ pub mod gens {
         prop_oneof![
             Just(Transform::Identity),
             stored_value_arb().prop_map(Transform::Write),
+            Just(Transform::Delete),
             any::<i32>().prop_map(Transform::AddInt32),
             any::<u64>().prop_map(Transform::AddUInt64),
             any::<u128>().prop_map(|u| Transform::AddUInt128(u.into())),
@@ -298,6 +452,7 @@ pub mod gens {
 #[cfg(test)]
 mod tests {
     use num::{Bounded, Num};
+    use proptest::proptest;
 
     use types::{account::PublicKey, AccessRights, ProtocolVersion, URef, U128, U256, U512};
 
@@ -742,4 +897,41 @@ mod tests {
         assert_eq!(ZERO_U512, add(MAX_U512, ONE_U512));
         assert_eq!(MAX_U512 - 1, add(MAX_U512, MAX_U512));
     }
+
+    proptest! {
+        #[test]
+        fn serialization_roundtrip(transform in gens::transform_arb()) {
+            bytesrepr::test_serialization_roundtrip(&transform);
+        }
+    }
+
+    #[test]
+    fn failure_serialization_roundtrip() {
+        let type_mismatch = TypeMismatch::new("U64".to_string(), "Unit".to_string());
+        bytesrepr::test_serialization_roundtrip(&Transform::Failure(type_mismatch.into()));
+        bytesrepr::test_serialization_roundtrip(&Transform::Failure(Error::Serialization(
+            bytesrepr::Error::LeftOverBytes,
+        )));
+    }
+
+    #[test]
+    fn delete_should_combine_like_write() {
+        let value = StoredValue::CLValue(CLValue::from_t(ONE_U64).unwrap());
+        let write = Transform::Write(value.clone());
+
+        assert_eq!(write.clone() + Transform::Delete, Transform::Delete);
+        assert_eq!(
+            Transform::AddUInt64(ONE_U64) + Transform::Delete,
+            Transform::Delete
+        );
+        assert_eq!(Transform::Delete + write.clone(), write);
+        assert_eq!(Transform::Delete + Transform::Identity, Transform::Delete);
+        assert_eq!(Transform::Identity + Transform::Delete, Transform::Delete);
+
+        match Transform::Delete + Transform::AddUInt64(ONE_U64) {
+            Transform::Failure(Error::TypeMismatch(_)) => (),
+            other => panic!("expected type mismatch, got {:?}", other),
+        }
+        assert!(Transform::Delete.apply(value).is_err());
+    }
 }
@@ -0,0 +1,36 @@
+use types::account::PublicKey;
+
+use crate::{
+    account_activity_store::{self, AccountActivityStore},
+    deploy_effects_store::DeployHash,
+    error::in_memory::Error,
+    store::Store,
+    transaction_source::in_memory::InMemoryEnvironment,
+};
+
+/// An in-memory account activity store
+pub struct InMemoryAccountActivityStore {
+    maybe_name: Option<String>,
+}
+
+impl InMemoryAccountActivityStore {
+    pub fn new(_env: &InMemoryEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", account_activity_store::NAME, name))
+            .unwrap_or_else(|| String::from(account_activity_store::NAME));
+        InMemoryAccountActivityStore {
+            maybe_name: Some(name),
+        }
+    }
+}
+
+impl Store<PublicKey, Vec<DeployHash>> for InMemoryAccountActivityStore {
+    type Error = Error;
+    type Handle = Option<String>;
+
+    fn handle(&self) -> Self::Handle {
+        self.maybe_name.to_owned()
+    }
+}
+
+impl AccountActivityStore for InMemoryAccountActivityStore {}
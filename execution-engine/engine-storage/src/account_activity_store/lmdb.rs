@@ -0,0 +1,54 @@
+use lmdb::{Database, DatabaseFlags};
+use types::account::PublicKey;
+
+use crate::{
+    account_activity_store::{self, AccountActivityStore},
+    deploy_effects_store::DeployHash,
+    error,
+    store::Store,
+    transaction_source::lmdb::LmdbEnvironment,
+};
+
+/// An LMDB-backed account activity store.
+///
+/// Wraps [`lmdb::Database`].
+#[derive(Debug, Clone)]
+pub struct LmdbAccountActivityStore {
+    db: Database,
+}
+
+impl LmdbAccountActivityStore {
+    pub fn new(
+        env: &LmdbEnvironment,
+        maybe_name: Option<&str>,
+        flags: DatabaseFlags,
+    ) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().create_db(Some(&name), flags)?;
+        Ok(LmdbAccountActivityStore { db })
+    }
+
+    pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().open_db(Some(&name))?;
+        Ok(LmdbAccountActivityStore { db })
+    }
+
+    fn name(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", account_activity_store::NAME, name))
+            .unwrap_or_else(|| String::from(account_activity_store::NAME))
+    }
+}
+
+impl Store<PublicKey, Vec<DeployHash>> for LmdbAccountActivityStore {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn handle(&self) -> Self::Handle {
+        self.db
+    }
+}
+
+impl AccountActivityStore for LmdbAccountActivityStore {}
@@ -0,0 +1,40 @@
+//! A secondary index of the deploys which touched each account, for explorers.
+//!
+//! Only built with the `account-activity-index` feature.
+use types::account::PublicKey;
+
+pub mod in_memory;
+pub mod lmdb;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
+
+use crate::{
+    deploy_effects_store::DeployHash,
+    store::Store,
+    transaction_source::{Readable, Writable},
+};
+
+const NAME: &str = "ACCOUNT_ACTIVITY_STORE";
+
+/// An entity which persists, for each account, the hashes of the deploys which touched it in the
+/// order they were committed.
+pub trait AccountActivityStore: Store<PublicKey, Vec<DeployHash>> {
+    /// Appends `deploy_hash` to the activity of `account`, unless it is already recorded there.
+    fn append<T>(
+        &self,
+        txn: &mut T,
+        account: &PublicKey,
+        deploy_hash: DeployHash,
+    ) -> Result<(), Self::Error>
+    where
+        T: Readable<Handle = Self::Handle> + Writable<Handle = Self::Handle>,
+        Self::Error: From<T::Error>,
+    {
+        let mut deploy_hashes = self.get(txn, account)?.unwrap_or_default();
+        if deploy_hashes.contains(&deploy_hash) {
+            return Ok(());
+        }
+        deploy_hashes.push(deploy_hash);
+        self.put(txn, account, &deploy_hashes)
+    }
+}
@@ -0,0 +1,48 @@
+use types::account::PublicKey;
+
+use crate::{
+    account_activity_store::{self, AccountActivityStore},
+    deploy_effects_store::DeployHash,
+    error,
+    store::Store,
+    transaction_source::rocksdb::RocksDbEnvironment,
+};
+
+/// A RocksDB-backed account activity store.
+///
+/// Wraps the name of a column family of a [`rocksdb::DB`].
+#[derive(Debug, Clone)]
+pub struct RocksDbAccountActivityStore {
+    cf: String,
+}
+
+impl RocksDbAccountActivityStore {
+    /// Opens the store in the column family named after `maybe_name`, which `env` must have been
+    /// opened with.  See [`RocksDbAccountActivityStore::column_family`].
+    pub fn new(env: &RocksDbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let cf = Self::column_family(maybe_name);
+        if !env.has_column_family(&cf) {
+            return Err(error::rocksdb::Error::MissingColumnFamily(cf).into());
+        }
+        Ok(RocksDbAccountActivityStore { cf })
+    }
+
+    /// Returns the name of the column family holding the store called `maybe_name`.
+    pub fn column_family(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", account_activity_store::NAME, name))
+            .unwrap_or_else(|| String::from(account_activity_store::NAME))
+    }
+}
+
+impl Store<PublicKey, Vec<DeployHash>> for RocksDbAccountActivityStore {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.cf.clone()
+    }
+}
+
+impl AccountActivityStore for RocksDbAccountActivityStore {}
@@ -0,0 +1,33 @@
+use crate::{
+    deploy_effects_store::{self, DeployEffectsStore, DeployHash},
+    error::in_memory::Error,
+    store::Store,
+    transaction_source::in_memory::InMemoryEnvironment,
+};
+
+/// An in-memory deploy effects store
+pub struct InMemoryDeployEffectsStore {
+    maybe_name: Option<String>,
+}
+
+impl InMemoryDeployEffectsStore {
+    pub fn new(_env: &InMemoryEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", deploy_effects_store::NAME, name))
+            .unwrap_or_else(|| String::from(deploy_effects_store::NAME));
+        InMemoryDeployEffectsStore {
+            maybe_name: Some(name),
+        }
+    }
+}
+
+impl Store<DeployHash, Vec<u8>> for InMemoryDeployEffectsStore {
+    type Error = Error;
+    type Handle = Option<String>;
+
+    fn handle(&self) -> Self::Handle {
+        self.maybe_name.to_owned()
+    }
+}
+
+impl DeployEffectsStore for InMemoryDeployEffectsStore {}
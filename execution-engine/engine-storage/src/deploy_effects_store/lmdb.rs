@@ -0,0 +1,52 @@
+use lmdb::{Database, DatabaseFlags};
+
+use crate::{
+    deploy_effects_store::{self, DeployEffectsStore, DeployHash},
+    error,
+    store::Store,
+    transaction_source::lmdb::LmdbEnvironment,
+};
+
+/// An LMDB-backed deploy effects store.
+///
+/// Wraps [`lmdb::Database`].
+#[derive(Debug, Clone)]
+pub struct LmdbDeployEffectsStore {
+    db: Database,
+}
+
+impl LmdbDeployEffectsStore {
+    pub fn new(
+        env: &LmdbEnvironment,
+        maybe_name: Option<&str>,
+        flags: DatabaseFlags,
+    ) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().create_db(Some(&name), flags)?;
+        Ok(LmdbDeployEffectsStore { db })
+    }
+
+    pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().open_db(Some(&name))?;
+        Ok(LmdbDeployEffectsStore { db })
+    }
+
+    fn name(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", deploy_effects_store::NAME, name))
+            .unwrap_or_else(|| String::from(deploy_effects_store::NAME))
+    }
+}
+
+impl Store<DeployHash, Vec<u8>> for LmdbDeployEffectsStore {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn handle(&self) -> Self::Handle {
+        self.db
+    }
+}
+
+impl DeployEffectsStore for LmdbDeployEffectsStore {}
@@ -0,0 +1,18 @@
+//! A store for persisting the serialized execution effects of deploys at their deploy hashes.
+pub mod in_memory;
+pub mod lmdb;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
+
+use crate::store::Store;
+
+const NAME: &str = "DEPLOY_EFFECTS_STORE";
+
+/// The hash of a deploy, as given in an execute request.
+pub type DeployHash = [u8; 32];
+
+/// An entity which persists the serialized execution effects of deploys at their deploy hashes.
+///
+/// The effects are opaque to the store, which only holds the bytes recorded by the most recent
+/// execution of each deploy.
+pub trait DeployEffectsStore: Store<DeployHash, Vec<u8>> {}
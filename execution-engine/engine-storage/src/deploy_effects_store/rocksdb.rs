@@ -0,0 +1,45 @@
+use crate::{
+    deploy_effects_store::{self, DeployEffectsStore, DeployHash},
+    error,
+    store::Store,
+    transaction_source::rocksdb::RocksDbEnvironment,
+};
+
+/// A RocksDB-backed deploy effects store.
+///
+/// Wraps the name of a column family of a [`rocksdb::DB`].
+#[derive(Debug, Clone)]
+pub struct RocksDbDeployEffectsStore {
+    cf: String,
+}
+
+impl RocksDbDeployEffectsStore {
+    /// Opens the store in the column family named after `maybe_name`, which `env` must have been
+    /// opened with.  See [`RocksDbDeployEffectsStore::column_family`].
+    pub fn new(env: &RocksDbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let cf = Self::column_family(maybe_name);
+        if !env.has_column_family(&cf) {
+            return Err(error::rocksdb::Error::MissingColumnFamily(cf).into());
+        }
+        Ok(RocksDbDeployEffectsStore { cf })
+    }
+
+    /// Returns the name of the column family holding the store called `maybe_name`.
+    pub fn column_family(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", deploy_effects_store::NAME, name))
+            .unwrap_or_else(|| String::from(deploy_effects_store::NAME))
+    }
+}
+
+impl Store<DeployHash, Vec<u8>> for RocksDbDeployEffectsStore {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.cf.clone()
+    }
+}
+
+impl DeployEffectsStore for RocksDbDeployEffectsStore {}
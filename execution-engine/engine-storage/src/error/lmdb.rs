@@ -2,6 +2,8 @@ use std::sync;
 
 use failure::Fail;
 use lmdb as lmdb_external;
+#[cfg(feature = "rocksdb")]
+use rocksdb as rocksdb_external;
 
 use types::bytesrepr;
 
@@ -17,6 +19,10 @@ pub enum Error {
 
     #[fail(display = "Another thread panicked while holding a lock")]
     Poison,
+
+    #[cfg(feature = "rocksdb")]
+    #[fail(display = "{}", _0)]
+    RocksDb(String),
 }
 
 impl wasmi::HostError for Error {}
@@ -39,6 +45,23 @@ impl<T> From<sync::PoisonError<T>> for Error {
     }
 }
 
+#[cfg(feature = "rocksdb")]
+impl From<rocksdb_external::Error> for Error {
+    fn from(error: rocksdb_external::Error) -> Self {
+        Error::RocksDb(error.to_string())
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+impl From<super::rocksdb::Error> for Error {
+    fn from(error: super::rocksdb::Error) -> Self {
+        match error {
+            super::rocksdb::Error::BytesRepr(error) => Error::BytesRepr(error),
+            error => Error::RocksDb(error.to_string()),
+        }
+    }
+}
+
 impl From<in_memory::Error> for Error {
     fn from(error: in_memory::Error) -> Self {
         match error {
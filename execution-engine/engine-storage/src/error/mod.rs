@@ -1,4 +1,6 @@
 pub mod in_memory;
 pub mod lmdb;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
 
 pub use self::lmdb::Error;
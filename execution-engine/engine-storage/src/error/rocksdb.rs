@@ -0,0 +1,28 @@
+use failure::Fail;
+use rocksdb as rocksdb_external;
+
+use types::bytesrepr;
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum Error {
+    #[fail(display = "{}", _0)]
+    RocksDb(String),
+
+    #[fail(display = "{}", _0)]
+    BytesRepr(#[fail(cause)] bytesrepr::Error),
+
+    #[fail(display = "Missing column family {}", _0)]
+    MissingColumnFamily(String),
+}
+
+impl From<rocksdb_external::Error> for Error {
+    fn from(error: rocksdb_external::Error) -> Self {
+        Error::RocksDb(error.to_string())
+    }
+}
+
+impl From<bytesrepr::Error> for Error {
+    fn from(error: bytesrepr::Error) -> Self {
+        Error::BytesRepr(error)
+    }
+}
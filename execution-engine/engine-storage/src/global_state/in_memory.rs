@@ -6,9 +6,16 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::Transform,
 };
+#[cfg(feature = "account-activity-index")]
+use types::account::PublicKey;
 use types::{Key, ProtocolVersion};
 
+#[cfg(feature = "account-activity-index")]
+use crate::account_activity_store::{
+    in_memory::InMemoryAccountActivityStore, AccountActivityStore,
+};
 use crate::{
+    deploy_effects_store::{in_memory::InMemoryDeployEffectsStore, DeployHash},
     error::{self, in_memory},
     global_state::{commit, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
@@ -18,10 +25,12 @@ use crate::{
         in_memory::{InMemoryEnvironment, InMemoryReadTransaction},
         Transaction, TransactionSource,
     },
-    trie::{operations::create_hashed_empty_trie, Trie},
+    trie::{merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie},
     trie_store::{
         in_memory::InMemoryTrieStore,
-        operations::{self, read, ReadResult, WriteResult},
+        operations::{
+            self, read, ReadResult, TrieRepairReport, TrieVerificationReport, WriteResult,
+        },
     },
 };
 
@@ -29,6 +38,9 @@ pub struct InMemoryGlobalState {
     pub environment: Arc<InMemoryEnvironment>,
     pub trie_store: Arc<InMemoryTrieStore>,
     pub protocol_data_store: Arc<InMemoryProtocolDataStore>,
+    pub deploy_effects_store: Arc<InMemoryDeployEffectsStore>,
+    #[cfg(feature = "account-activity-index")]
+    pub account_activity_store: Arc<InMemoryAccountActivityStore>,
     pub empty_root_hash: Blake2bHash,
 }
 
@@ -45,6 +57,7 @@ impl InMemoryGlobalState {
         let environment = Arc::new(InMemoryEnvironment::new());
         let trie_store = Arc::new(InMemoryTrieStore::new(&environment, None));
         let protocol_data_store = Arc::new(InMemoryProtocolDataStore::new(&environment, None));
+        let deploy_effects_store = Arc::new(InMemoryDeployEffectsStore::new(&environment, None));
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
             let mut txn = environment.create_read_write_txn()?;
@@ -56,6 +69,7 @@ impl InMemoryGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            deploy_effects_store,
             root_hash,
         ))
     }
@@ -66,16 +80,40 @@ impl InMemoryGlobalState {
         environment: Arc<InMemoryEnvironment>,
         trie_store: Arc<InMemoryTrieStore>,
         protocol_data_store: Arc<InMemoryProtocolDataStore>,
+        deploy_effects_store: Arc<InMemoryDeployEffectsStore>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
+        #[cfg(feature = "account-activity-index")]
+        let account_activity_store =
+            Arc::new(InMemoryAccountActivityStore::new(&environment, None));
         InMemoryGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            deploy_effects_store,
+            #[cfg(feature = "account-activity-index")]
+            account_activity_store,
             empty_root_hash,
         }
     }
 
+    /// Returns a copy-on-write fork of this state.
+    ///
+    /// Forking is cheap: nothing is copied.  The fork can check out any root hash known to this
+    /// state at the time of forking, while roots and protocol data committed afterwards to
+    /// either state are invisible to the other, which makes forks suitable for speculative
+    /// execution.
+    pub fn fork(&self) -> Result<Self, error::Error> {
+        let environment = Arc::new(self.environment.fork()?);
+        Ok(InMemoryGlobalState::new(
+            environment,
+            Arc::clone(&self.trie_store),
+            Arc::clone(&self.protocol_data_store),
+            Arc::clone(&self.deploy_effects_store),
+            self.empty_root_hash,
+        ))
+    }
+
     /// Creates a state from a given set of `Key, StoredValue` pairs.
     pub fn from_pairs(
         correlation_id: CorrelationId,
@@ -137,6 +175,26 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter = operations::keys_with_prefix::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let mut ret = Vec::new();
+        for result in keys_iter {
+            ret.push(result?);
+        }
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for InMemoryGlobalState {
@@ -194,16 +252,143 @@ impl StateProvider for InMemoryGlobalState {
         Ok(result)
     }
 
+    fn put_deploy_effects(
+        &self,
+        deploy_hash: DeployHash,
+        effects: &[u8],
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.deploy_effects_store
+            .put(&mut txn, &deploy_hash, &effects.to_vec())?;
+        txn.commit().map_err(Into::into)
+    }
+
+    fn get_deploy_effects(&self, deploy_hash: DeployHash) -> Result<Option<Vec<u8>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.deploy_effects_store.get(&txn, &deploy_hash)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    #[cfg(feature = "account-activity-index")]
+    fn record_account_activity(
+        &self,
+        deploy_hash: DeployHash,
+        accounts: &[PublicKey],
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        for account in accounts {
+            self.account_activity_store
+                .append(&mut txn, account, deploy_hash)?;
+        }
+        txn.commit().map_err(Into::into)
+    }
+
+    #[cfg(feature = "account-activity-index")]
+    fn get_account_activity(&self, account: PublicKey) -> Result<Vec<DeployHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.account_activity_store.get(&txn, &account)?;
+        txn.commit()?;
+        Ok(result.unwrap_or_default())
+    }
+
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match operations::read_with_proof::<
+            Key,
+            StoredValue,
+            _,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound | ReadResult::RootNotFound => None,
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let missing_descendants =
+            operations::missing_trie_keys::<Key, StoredValue, _, InMemoryTrieStore, Self::Error>(
+                correlation_id,
+                &txn,
+                self.trie_store.deref(),
+                trie_keys,
+            )?;
+        txn.commit()?;
+        Ok(missing_descendants)
+    }
+
+    fn set_deferred_sync(&self, _deferred: bool) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn sync(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn verify(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<TrieVerificationReport, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let report = operations::verify::<Key, StoredValue, _, InMemoryTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_hash,
+        )?;
+        txn.commit()?;
+        Ok(report)
+    }
+
+    fn repair(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<TrieRepairReport, Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let report = operations::repair::<Key, StoredValue, _, InMemoryTrieStore, Self::Error>(
+            correlation_id,
+            &mut txn,
+            self.trie_store.deref(),
+            &state_hash,
+        )?;
+        txn.commit()?;
+        Ok(report)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use types::{account::PublicKey, CLValue};
+    use std::iter;
+
+    use types::{account::PublicKey, bytesrepr::ToBytes, CLValue};
 
     use super::*;
+    use crate::trie_store;
 
     #[derive(Debug, Clone)]
     struct TestPair {
@@ -263,6 +448,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keys_with_prefix_from_a_checkout_return_expected_keys() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+
+        let mut expected_keys = create_test_pairs()
+            .iter()
+            .map(|test_pair| test_pair.key)
+            .collect::<Vec<Key>>();
+        expected_keys.sort();
+        let account_prefix = expected_keys[0].to_bytes().unwrap()[..1].to_vec();
+        let mut keys = checkout
+            .keys_with_prefix(correlation_id, &account_prefix)
+            .unwrap();
+        keys.sort();
+        assert_eq!(keys, expected_keys);
+
+        let hash_prefix = Key::Hash([0; 32]).to_bytes().unwrap()[..1].to_vec();
+        assert!(checkout
+            .keys_with_prefix(correlation_id, &hash_prefix)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn state_has_no_missing_trie_keys() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        assert!(state
+            .missing_trie_keys(correlation_id, vec![root_hash])
+            .unwrap()
+            .is_empty());
+
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        assert_eq!(
+            state
+                .missing_trie_keys(correlation_id, vec![fake_hash])
+                .unwrap(),
+            vec![fake_hash]
+        );
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _) = create_test_state();
@@ -300,6 +528,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn commit_deletes_state() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> =
+            iter::once((test_pairs[0].key, Transform::Delete)).collect();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            updated_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(test_pairs[1].value.clone()),
+            updated_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+
+        // Deleting a key which isn't present leaves the state untouched.
+        let effects: AdditiveMap<Key, Transform> =
+            iter::once((test_pairs[0].key, Transform::Delete)).collect();
+        match state.commit(correlation_id, updated_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => assert_eq!(state_root, updated_hash),
+            other => panic!("expected Success, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn commit_reports_stats() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+        let (state, root_hash) = create_test_state();
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(
+            test_pairs_updated[0].key,
+            Transform::Write(test_pairs_updated[0].value.clone()),
+        );
+        effects.insert(
+            test_pairs_updated[2].key,
+            Transform::Write(test_pairs_updated[2].value.clone()),
+        );
+        effects.insert(test_pairs_updated[1].key, Transform::Delete);
+
+        let stats = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { stats, .. } => stats,
+            other => panic!("expected Success, got {:?}", other),
+        };
+        assert_eq!(stats.writes, 2);
+        assert_eq!(stats.adds, 0);
+        assert_eq!(stats.deletes, 1);
+        assert!(stats.bytes_written > 0);
+    }
+
     #[test]
     fn commit_updates_state_and_original_state_stays_intact() {
         let correlation_id = CorrelationId::new();
@@ -343,6 +635,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_only_stores_tries_reachable_from_the_new_root() {
+        let correlation_id = CorrelationId::new();
+        let state = InMemoryGlobalState::empty().unwrap();
+
+        let effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+        let root_hash = match state
+            .commit(correlation_id, state.empty_root(), effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let stored = state
+            .environment
+            .data(Some(trie_store::NAME))
+            .unwrap()
+            .unwrap()
+            .len();
+        let reachable = state
+            .verify(correlation_id, root_hash)
+            .unwrap()
+            .tries_checked;
+        // Besides the new state, the store only holds the empty root.
+        assert_eq!(stored, reachable + 1);
+    }
+
+    #[test]
+    fn commits_to_a_fork_are_isolated_from_the_original() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+        let (state, root_hash) = create_test_state();
+        let fork = state.fork().unwrap();
+
+        let effects: AdditiveMap<Key, Transform> = test_pairs_updated
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+
+        let forked_hash = match fork.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        // The fork can still check out the root it was forked with, as well as its own commit.
+        let original_checkout = fork.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(
+                Some(value),
+                original_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        let forked_checkout = fork.checkout(forked_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                forked_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+
+        // The original state never sees the fork's commit.
+        assert!(state.checkout(forked_hash).unwrap().is_none());
+
+        // And the fork never sees commits made to the original after forking.
+        let effects: AdditiveMap<Key, Transform> = iter::once((
+            test_pairs_updated[2].key,
+            Transform::Write(test_pairs_updated[2].value.clone()),
+        ))
+        .collect();
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        assert!(state.checkout(updated_hash).unwrap().is_some());
+        assert!(fork.checkout(updated_hash).unwrap().is_none());
+    }
+
     #[test]
     fn initial_state_has_the_expected_hash() {
         let correlation_id = CorrelationId::new();
@@ -1,4 +1,4 @@
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, path::Path, sync::Arc};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -6,19 +6,27 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::Transform,
 };
+#[cfg(feature = "account-activity-index")]
+use types::account::PublicKey;
 use types::{Key, ProtocolVersion};
 
+#[cfg(feature = "account-activity-index")]
+use crate::account_activity_store::{lmdb::LmdbAccountActivityStore, AccountActivityStore};
 use crate::{
+    deploy_effects_store::{lmdb::LmdbDeployEffectsStore, DeployHash},
     error,
     global_state::{commit, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     store::Store,
-    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
-    trie::{operations::create_hashed_empty_trie, Trie},
+    transaction_source::{
+        lmdb::{LmdbEnvironment, LmdbReadTransaction},
+        Transaction, TransactionSource,
+    },
+    trie::{merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie},
     trie_store::{
         lmdb::LmdbTrieStore,
-        operations::{read, ReadResult},
+        operations::{self, read, ReadResult, TrieRepairReport, TrieVerificationReport},
     },
 };
 
@@ -26,6 +34,9 @@ pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
+    pub deploy_effects_store: Arc<LmdbDeployEffectsStore>,
+    #[cfg(feature = "account-activity-index")]
+    pub account_activity_store: Arc<LmdbAccountActivityStore>,
     pub empty_root_hash: Blake2bHash,
 }
 
@@ -37,11 +48,12 @@ pub struct LmdbGlobalStateView {
 }
 
 impl LmdbGlobalState {
-    /// Creates an empty state from an existing environment and trie_store.
+    /// Creates an empty state from an existing environment and stores.
     pub fn empty(
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        deploy_effects_store: Arc<LmdbDeployEffectsStore>,
     ) -> Result<Self, error::Error> {
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
@@ -50,12 +62,13 @@ impl LmdbGlobalState {
             txn.commit()?;
             root_hash
         };
-        Ok(LmdbGlobalState::new(
+        LmdbGlobalState::new(
             environment,
             trie_store,
             protocol_data_store,
+            deploy_effects_store,
             root_hash,
-        ))
+        )
     }
 
     /// Creates a state from an existing environment, store, and root_hash.
@@ -64,14 +77,30 @@ impl LmdbGlobalState {
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        deploy_effects_store: Arc<LmdbDeployEffectsStore>,
         empty_root_hash: Blake2bHash,
-    ) -> Self {
-        LmdbGlobalState {
+    ) -> Result<Self, error::Error> {
+        #[cfg(feature = "account-activity-index")]
+        let account_activity_store = Arc::new(LmdbAccountActivityStore::new(
+            &environment,
+            None,
+            lmdb::DatabaseFlags::empty(),
+        )?);
+        Ok(LmdbGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            deploy_effects_store,
+            #[cfg(feature = "account-activity-index")]
+            account_activity_store,
             empty_root_hash,
-        }
+        })
+    }
+
+    /// Writes a compacted copy of the global state to the existing, empty directory
+    /// `destination`.  See [`LmdbEnvironment::compact`].
+    pub fn compact(&self, destination: &Path) -> Result<(), error::Error> {
+        self.environment.compact(destination)
     }
 }
 
@@ -84,7 +113,7 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
         let txn = self.environment.create_read_txn()?;
-        let ret = match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+        let ret = match read::<Key, StoredValue, LmdbReadTransaction, LmdbTrieStore, Self::Error>(
             correlation_id,
             &txn,
             self.store.deref(),
@@ -98,6 +127,26 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter = operations::keys_with_prefix::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let mut ret = Vec::new();
+        for result in keys_iter {
+            ret.push(result?);
+        }
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for LmdbGlobalState {
@@ -154,13 +203,134 @@ impl StateProvider for LmdbGlobalState {
         Ok(result)
     }
 
+    fn put_deploy_effects(
+        &self,
+        deploy_hash: DeployHash,
+        effects: &[u8],
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.deploy_effects_store
+            .put(&mut txn, &deploy_hash, &effects.to_vec())?;
+        txn.commit().map_err(Into::into)
+    }
+
+    fn get_deploy_effects(&self, deploy_hash: DeployHash) -> Result<Option<Vec<u8>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.deploy_effects_store.get(&txn, &deploy_hash)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    #[cfg(feature = "account-activity-index")]
+    fn record_account_activity(
+        &self,
+        deploy_hash: DeployHash,
+        accounts: &[PublicKey],
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        for account in accounts {
+            self.account_activity_store
+                .append(&mut txn, account, deploy_hash)?;
+        }
+        txn.commit().map_err(Into::into)
+    }
+
+    #[cfg(feature = "account-activity-index")]
+    fn get_account_activity(&self, account: PublicKey) -> Result<Vec<DeployHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.account_activity_store.get(&txn, &account)?;
+        txn.commit()?;
+        Ok(result.unwrap_or_default())
+    }
+
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret =
+            match operations::read_with_proof::<Key, StoredValue, _, LmdbTrieStore, Self::Error>(
+                correlation_id,
+                &txn,
+                self.trie_store.deref(),
+                &state_hash,
+                key,
+            )? {
+                ReadResult::Found(proof) => Some(proof),
+                ReadResult::NotFound | ReadResult::RootNotFound => None,
+            };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let missing_descendants =
+            operations::missing_trie_keys::<Key, StoredValue, _, LmdbTrieStore, Self::Error>(
+                correlation_id,
+                &txn,
+                self.trie_store.deref(),
+                trie_keys,
+            )?;
+        txn.commit()?;
+        Ok(missing_descendants)
+    }
+
+    fn set_deferred_sync(&self, deferred: bool) -> Result<(), Self::Error> {
+        self.environment.set_deferred_sync(deferred)
+    }
+
+    fn sync(&self) -> Result<(), Self::Error> {
+        self.environment.sync()
+    }
+
+    fn verify(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<TrieVerificationReport, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let report = operations::verify::<Key, StoredValue, _, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_hash,
+        )?;
+        txn.commit()?;
+        Ok(report)
+    }
+
+    fn repair(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<TrieRepairReport, Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let report = operations::repair::<Key, StoredValue, _, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            &mut txn,
+            self.trie_store.deref(),
+            &state_hash,
+        )?;
+        txn.commit()?;
+        Ok(report)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::iter;
+
     use lmdb::DatabaseFlags;
     use tempfile::tempdir;
 
@@ -220,7 +390,16 @@ mod tests {
         let protocol_data_store = Arc::new(
             LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
         );
-        let ret = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        let deploy_effects_store = Arc::new(
+            LmdbDeployEffectsStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let ret = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            deploy_effects_store,
+        )
+        .unwrap();
         let mut current_root = ret.empty_root_hash;
         {
             let mut txn = ret.environment.create_read_write_txn().unwrap();
@@ -297,6 +476,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn commit_deletes_state() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> =
+            iter::once((test_pairs[0].key, Transform::Delete)).collect();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            updated_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(test_pairs[1].value.clone()),
+            updated_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+
+        // Deleting a key which isn't present leaves the state untouched.
+        let effects: AdditiveMap<Key, Transform> =
+            iter::once((test_pairs[0].key, Transform::Delete)).collect();
+        match state.commit(correlation_id, updated_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => assert_eq!(state_root, updated_hash),
+            other => panic!("expected Success, got {:?}", other),
+        }
+    }
+
     #[test]
     fn commit_updates_state_and_original_state_stays_intact() {
         let correlation_id = CorrelationId::new();
@@ -339,4 +555,55 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn deploy_effects_are_replaced_by_later_puts() {
+        let (state, _) = create_test_state();
+        let deploy_hash = [1u8; 32];
+        assert_eq!(None, state.get_deploy_effects(deploy_hash).unwrap());
+
+        state.put_deploy_effects(deploy_hash, &[1, 2, 3]).unwrap();
+        assert_eq!(
+            Some(vec![1, 2, 3]),
+            state.get_deploy_effects(deploy_hash).unwrap()
+        );
+
+        state.put_deploy_effects(deploy_hash, &[4]).unwrap();
+        assert_eq!(
+            Some(vec![4]),
+            state.get_deploy_effects(deploy_hash).unwrap()
+        );
+        assert_eq!(None, state.get_deploy_effects([2u8; 32]).unwrap());
+    }
+
+    #[cfg(feature = "account-activity-index")]
+    #[test]
+    fn account_activity_records_each_deploy_once() {
+        let (state, _) = create_test_state();
+        let account_1 = PublicKey::ed25519_from([1u8; 32]);
+        let account_2 = PublicKey::ed25519_from([2u8; 32]);
+
+        state
+            .record_account_activity([1u8; 32], &[account_1, account_2])
+            .unwrap();
+        state
+            .record_account_activity([2u8; 32], &[account_1])
+            .unwrap();
+        state
+            .record_account_activity([1u8; 32], &[account_1])
+            .unwrap();
+
+        assert_eq!(
+            vec![[1u8; 32], [2u8; 32]],
+            state.get_account_activity(account_1).unwrap()
+        );
+        assert_eq!(
+            vec![[1u8; 32]],
+            state.get_account_activity(account_2).unwrap()
+        );
+        assert!(state
+            .get_account_activity(PublicKey::ed25519_from([3u8; 32]))
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -1,5 +1,7 @@
 pub mod in_memory;
 pub mod lmdb;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
 
 use std::{collections::HashMap, fmt, hash::BuildHasher, time::Instant};
 
@@ -11,14 +13,22 @@ use engine_shared::{
     transform::{self, Transform},
     TypeMismatch,
 };
-use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion, U512,
+};
 
 use crate::{
+    deploy_effects_store::DeployHash,
     protocol_data::ProtocolData,
-    transaction_source::{Transaction, TransactionSource},
-    trie::Trie,
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    trie::{merkle_proof::TrieMerkleProof, Trie, RADIX},
     trie_store::{
-        operations::{read, write, ReadResult, WriteResult},
+        operations::{
+            delete, read, write_with_stats, DeleteResult, ReadResult, TrieRepairReport,
+            TrieVerificationReport, WriteResult, WriteStats,
+        },
         TrieStore,
     },
     GAUGE_METRIC_KEY,
@@ -26,6 +36,9 @@ use crate::{
 
 const GLOBAL_STATE_COMMIT_READS: &str = "global_state_commit_reads";
 const GLOBAL_STATE_COMMIT_WRITES: &str = "global_state_commit_writes";
+const GLOBAL_STATE_COMMIT_TRIE_PUTS: &str = "global_state_commit_trie_puts";
+const GLOBAL_STATE_COMMIT_TRIE_REUSED: &str = "global_state_commit_trie_reused";
+const GLOBAL_STATE_COMMIT_TRIE_SUPERSEDED: &str = "global_state_commit_trie_superseded";
 const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
 const GLOBAL_STATE_COMMIT_READ_DURATION: &str = "global_state_commit_read_duration";
 const GLOBAL_STATE_COMMIT_WRITE_DURATION: &str = "global_state_commit_write_duration";
@@ -38,6 +51,36 @@ pub trait StateReader<K, V> {
 
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
+
+    /// Returns the keys whose serialized form starts with `prefix`
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<K>, Self::Error>;
+}
+
+/// Counts of the changes a commit applied to global state.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CommitStats {
+    /// Keys whose value was replaced by a `Transform::Write`.
+    pub writes: u32,
+    /// Keys whose value was added to.
+    pub adds: u32,
+    /// Keys which were deleted.
+    pub deletes: u32,
+    /// Serialized size in bytes of the trie nodes newly put into the store.
+    pub bytes_written: u64,
+}
+
+impl CommitStats {
+    /// Adds the counts of `other` to `self`.
+    pub fn accumulate(&mut self, other: CommitStats) {
+        self.writes += other.writes;
+        self.adds += other.adds;
+        self.deletes += other.deletes;
+        self.bytes_written += other.bytes_written;
+    }
 }
 
 #[derive(Debug)]
@@ -46,6 +89,7 @@ pub enum CommitResult {
     Success {
         state_root: Blake2bHash,
         bonded_validators: HashMap<PublicKey, U512>,
+        stats: CommitStats,
     },
     KeyNotFound(Key),
     TypeMismatch(TypeMismatch),
@@ -59,10 +103,11 @@ impl fmt::Display for CommitResult {
             CommitResult::Success {
                 state_root,
                 bonded_validators,
+                stats,
             } => write!(
                 f,
-                "Success: state_root: {}, bonded_validators: {:?}",
-                state_root, bonded_validators
+                "Success: state_root: {}, bonded_validators: {:?}, stats: {:?}",
+                state_root, bonded_validators, stats
             ),
             CommitResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
             CommitResult::TypeMismatch(type_mismatch) => {
@@ -111,9 +156,153 @@ pub trait StateProvider {
         protocol_version: ProtocolVersion,
     ) -> Result<Option<ProtocolData>, Self::Error>;
 
+    /// Records the serialized execution effects of the deploy with hash `deploy_hash`, replacing
+    /// any recorded before.
+    fn put_deploy_effects(
+        &self,
+        deploy_hash: DeployHash,
+        effects: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Returns the serialized execution effects last recorded for the deploy with hash
+    /// `deploy_hash`.
+    fn get_deploy_effects(&self, deploy_hash: DeployHash) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Appends `deploy_hash` to the recorded activity of each of `accounts`.
+    #[cfg(feature = "account-activity-index")]
+    fn record_account_activity(
+        &self,
+        deploy_hash: DeployHash,
+        accounts: &[PublicKey],
+    ) -> Result<(), Self::Error>;
+
+    /// Returns the hashes of the deploys recorded as having touched `account`, oldest first.
+    #[cfg(feature = "account-activity-index")]
+    fn get_account_activity(&self, account: PublicKey) -> Result<Vec<DeployHash>, Self::Error>;
+
     fn empty_root(&self) -> Blake2bHash;
+
+    /// Reads the value under `key` at `state_hash` together with a merkle proof of its presence.
+    ///
+    /// Returns `None` if `state_hash` is unknown or `key` is not present under it.
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error>;
+
+    /// Returns the hashes of any tries reachable from `trie_keys` which are missing or corrupted.
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error>;
+
+    /// Sets whether commits return before their data is flushed to durable storage, leaving that
+    /// to [`sync`](StateProvider::sync).
+    fn set_deferred_sync(&self, deferred: bool) -> Result<(), Self::Error>;
+
+    /// Flushes everything committed so far to durable storage.
+    fn sync(&self) -> Result<(), Self::Error>;
+
+    /// Walks the whole trie under `state_hash`, reporting every missing, corrupted or misplaced
+    /// trie found.
+    fn verify(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<TrieVerificationReport, Self::Error>;
+
+    /// Verifies the trie under `state_hash` and commits a new trie holding all the leaves which
+    /// could be salvaged from it.
+    fn repair(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<TrieRepairReport, Self::Error>;
+}
+
+/// Buffers the tries written during a commit, so that only those reachable from the final state
+/// root are written to the underlying transaction.
+///
+/// Each transform rehashes the whole path from its key up to the root, so most of the nodes
+/// written for one transform are superseded by those written for the next.
+struct TrieWriteBatch<'t, T: Transaction> {
+    txn: &'t mut T,
+    pending: HashMap<Vec<u8>, (T::Handle, Vec<u8>)>,
 }
 
+impl<'t, T: Readable + Writable> TrieWriteBatch<'t, T> {
+    fn new(txn: &'t mut T) -> Self {
+        TrieWriteBatch {
+            txn,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Writes the buffered tries reachable from `root` to the underlying transaction, dropping
+    /// the rest, and returns how many were dropped.
+    fn flush<E>(mut self, root: &Blake2bHash) -> Result<u32, E>
+    where
+        E: From<T::Error> + From<types::bytesrepr::Error>,
+    {
+        let mut to_flush = vec![root.to_bytes()?];
+        while let Some(trie_key) = to_flush.pop() {
+            let (handle, trie_bytes) = match self.pending.remove(&trie_key) {
+                Some(pending) => pending,
+                // Already in the store before this commit, along with its descendants.
+                None => continue,
+            };
+            self.txn.write(handle, &trie_key, &trie_bytes)?;
+            match bytesrepr::deserialize::<Trie<Key, StoredValue>>(trie_bytes)? {
+                Trie::Leaf { .. } => (),
+                Trie::Node { pointer_block } => {
+                    for pointer in (0..RADIX).filter_map(|index| pointer_block[index]) {
+                        to_flush.push(pointer.hash().to_bytes()?);
+                    }
+                }
+                Trie::Extension { pointer, .. } => to_flush.push(pointer.hash().to_bytes()?),
+            }
+        }
+        Ok(self.pending.len() as u32)
+    }
+}
+
+impl<'t, T: Writable> Transaction for TrieWriteBatch<'t, T> {
+    type Error = T::Error;
+
+    type Handle = T::Handle;
+
+    /// Writes every buffered trie to the underlying transaction, superseded or not.
+    fn commit(self) -> Result<(), Self::Error> {
+        for (key, (handle, value)) in self.pending {
+            self.txn.write(handle, &key, &value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'t, T: Readable + Writable> Readable for TrieWriteBatch<'t, T> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        // Only trie nodes are written during a commit, so a buffered key is always a trie key.
+        match self.pending.get(key) {
+            Some((_, value)) => Ok(Some(value.to_owned())),
+            None => self.txn.read(handle, key),
+        }
+    }
+}
+
+impl<'t, T: Writable> Writable for TrieWriteBatch<'t, T> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        self.pending.insert(key.to_vec(), (handle, value.to_vec()));
+        Ok(())
+    }
+}
+
+/// Applies `effects` on top of the state at `prestate_hash` in a single transaction.
+///
+/// Trie nodes are buffered in memory and only those making up the final state are written.
 pub fn commit<'a, R, S, H, E>(
     environment: &'a R,
     store: &S,
@@ -129,9 +318,10 @@ where
     H: BuildHasher,
 {
     let mut txn = environment.create_read_write_txn()?;
+    let mut batch = TrieWriteBatch::new(&mut txn);
     let mut state_root = prestate_hash;
 
-    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
+    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&batch, &state_root)?;
 
     if maybe_root.is_none() {
         return Ok(CommitResult::RootNotFound);
@@ -140,9 +330,11 @@ where
     let start = Instant::now();
     let mut reads: i32 = 0;
     let mut writes: i32 = 0;
+    let mut write_stats = WriteStats::default();
+    let mut commit_stats = CommitStats::default();
 
     for (key, transform) in effects.into_iter() {
-        let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
+        let read_result = read::<_, _, _, _, E>(correlation_id, &batch, store, &state_root, &key)?;
 
         log_duration(
             correlation_id,
@@ -153,8 +345,42 @@ where
 
         reads += 1;
 
+        match transform {
+            Transform::Write(_) => commit_stats.writes += 1,
+            Transform::AddInt32(_)
+            | Transform::AddUInt64(_)
+            | Transform::AddUInt128(_)
+            | Transform::AddUInt256(_)
+            | Transform::AddUInt512(_)
+            | Transform::AddKeys(_) => commit_stats.adds += 1,
+            Transform::Identity | Transform::Delete | Transform::Failure(_) => (),
+        }
+
         let value = match (read_result, transform) {
             (ReadResult::NotFound, Transform::Write(new_value)) => new_value,
+            (ReadResult::Found(_), Transform::Delete) => {
+                let delete_result =
+                    delete::<_, _, _, _, E>(correlation_id, &mut batch, store, &state_root, &key)?;
+
+                log_duration(
+                    correlation_id,
+                    GLOBAL_STATE_COMMIT_WRITE_DURATION,
+                    COMMIT,
+                    start.elapsed(),
+                );
+
+                match delete_result {
+                    DeleteResult::Deleted(root_hash) => {
+                        state_root = root_hash;
+                        writes += 1;
+                        commit_stats.deletes += 1;
+                    }
+                    other => panic!("failed to delete {:?} after reading it: {:?}", key, other),
+                }
+                continue;
+            }
+            // Nothing to reclaim, e.g. a key created and deleted by the same deploy.
+            (ReadResult::NotFound, Transform::Delete) => continue,
             (ReadResult::NotFound, _) => {
                 return Ok(CommitResult::KeyNotFound(key));
             }
@@ -165,8 +391,15 @@ where
             _x @ (ReadResult::RootNotFound, _) => panic!(stringify!(_x._1)),
         };
 
-        let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key, &value)?;
+        let (write_result, stats) = write_with_stats::<_, _, _, _, E>(
+            correlation_id,
+            &mut batch,
+            store,
+            &state_root,
+            &key,
+            &value,
+        )?;
+        write_stats.accumulate(stats);
 
         log_duration(
             correlation_id,
@@ -185,6 +418,7 @@ where
         }
     }
 
+    let superseded = batch.flush::<E>(&state_root)?;
     txn.commit()?;
 
     log_duration(
@@ -210,10 +444,36 @@ where
         f64::from(writes),
     );
 
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_TRIE_PUTS,
+        COMMIT,
+        GAUGE_METRIC_KEY,
+        f64::from(write_stats.puts),
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_TRIE_REUSED,
+        COMMIT,
+        GAUGE_METRIC_KEY,
+        f64::from(write_stats.reused),
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_TRIE_SUPERSEDED,
+        COMMIT,
+        GAUGE_METRIC_KEY,
+        f64::from(superseded),
+    );
+
     let bonded_validators = Default::default();
+    commit_stats.bytes_written = write_stats.bytes_put;
 
     Ok(CommitResult::Success {
         state_root,
         bonded_validators,
+        stats: commit_stats,
     })
 }
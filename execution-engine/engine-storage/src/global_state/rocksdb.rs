@@ -0,0 +1,480 @@
+use std::{ops::Deref, path::PathBuf, sync::Arc};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+#[cfg(feature = "account-activity-index")]
+use types::account::PublicKey;
+use types::{Key, ProtocolVersion};
+
+#[cfg(feature = "account-activity-index")]
+use crate::account_activity_store::{rocksdb::RocksDbAccountActivityStore, AccountActivityStore};
+use crate::{
+    deploy_effects_store::{rocksdb::RocksDbDeployEffectsStore, DeployHash},
+    error,
+    global_state::{commit, CommitResult, StateProvider, StateReader},
+    protocol_data::ProtocolData,
+    protocol_data_store::rocksdb::RocksDbProtocolDataStore,
+    store::Store,
+    transaction_source::{
+        rocksdb::{RocksDbEnvironment, RocksDbReadTransaction},
+        Transaction, TransactionSource,
+    },
+    trie::{merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie},
+    trie_store::{
+        operations::{self, read, ReadResult, TrieRepairReport, TrieVerificationReport},
+        rocksdb::RocksDbTrieStore,
+    },
+};
+
+/// The column family recording the state roots known to be complete in the trie store.
+pub const ROOTS_COLUMN_FAMILY: &str = "ROOTS";
+
+/// A record of the state roots created by committing to a [`RocksDbGlobalState`], kept so that
+/// checking them out doesn't need to load their root trie nodes.
+#[derive(Debug, Clone)]
+pub struct RocksDbRootStore {
+    cf: String,
+}
+
+impl RocksDbRootStore {
+    pub fn new(env: &RocksDbEnvironment) -> Result<Self, error::Error> {
+        let cf = String::from(ROOTS_COLUMN_FAMILY);
+        if !env.has_column_family(&cf) {
+            return Err(error::rocksdb::Error::MissingColumnFamily(cf).into());
+        }
+        Ok(RocksDbRootStore { cf })
+    }
+}
+
+impl Store<Blake2bHash, ()> for RocksDbRootStore {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.cf.clone()
+    }
+}
+
+pub struct RocksDbGlobalState {
+    pub environment: Arc<RocksDbEnvironment>,
+    pub trie_store: Arc<RocksDbTrieStore>,
+    pub protocol_data_store: Arc<RocksDbProtocolDataStore>,
+    pub deploy_effects_store: Arc<RocksDbDeployEffectsStore>,
+    #[cfg(feature = "account-activity-index")]
+    pub account_activity_store: Arc<RocksDbAccountActivityStore>,
+    pub root_store: Arc<RocksDbRootStore>,
+    pub empty_root_hash: Blake2bHash,
+}
+
+/// Represents a "view" of global state at a particular root hash.
+pub struct RocksDbGlobalStateView {
+    pub environment: Arc<RocksDbEnvironment>,
+    pub store: Arc<RocksDbTrieStore>,
+    pub root_hash: Blake2bHash,
+}
+
+impl RocksDbGlobalState {
+    /// Opens the database at `path` with column families for trie nodes, protocol data, deploy
+    /// effects and state roots, creating whichever don't exist yet, and returns a state over it.
+    pub fn open(path: &PathBuf) -> Result<Self, error::Error> {
+        #[allow(unused_mut)]
+        let mut column_families = vec![
+            RocksDbTrieStore::column_family(None),
+            RocksDbProtocolDataStore::column_family(None),
+            RocksDbDeployEffectsStore::column_family(None),
+            String::from(ROOTS_COLUMN_FAMILY),
+        ];
+        #[cfg(feature = "account-activity-index")]
+        column_families.push(RocksDbAccountActivityStore::column_family(None));
+        let environment = Arc::new(RocksDbEnvironment::new(path, &column_families)?);
+        let trie_store = Arc::new(RocksDbTrieStore::new(&environment, None)?);
+        let protocol_data_store = Arc::new(RocksDbProtocolDataStore::new(&environment, None)?);
+        let deploy_effects_store = Arc::new(RocksDbDeployEffectsStore::new(&environment, None)?);
+        let root_store = Arc::new(RocksDbRootStore::new(&environment)?);
+        RocksDbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            deploy_effects_store,
+            root_store,
+        )
+    }
+
+    /// Creates an empty state from an existing environment and stores.
+    pub fn empty(
+        environment: Arc<RocksDbEnvironment>,
+        trie_store: Arc<RocksDbTrieStore>,
+        protocol_data_store: Arc<RocksDbProtocolDataStore>,
+        deploy_effects_store: Arc<RocksDbDeployEffectsStore>,
+        root_store: Arc<RocksDbRootStore>,
+    ) -> Result<Self, error::Error> {
+        #[cfg(feature = "account-activity-index")]
+        let account_activity_store =
+            Arc::new(RocksDbAccountActivityStore::new(&environment, None)?);
+        let root_hash: Blake2bHash = {
+            let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
+            let mut txn = environment.create_read_write_txn()?;
+            trie_store.put(&mut txn, &root_hash, &root)?;
+            root_store.put(&mut txn, &root_hash, &())?;
+            txn.commit()?;
+            root_hash
+        };
+        Ok(RocksDbGlobalState {
+            environment,
+            trie_store,
+            protocol_data_store,
+            deploy_effects_store,
+            #[cfg(feature = "account-activity-index")]
+            account_activity_store,
+            root_store,
+            empty_root_hash: root_hash,
+        })
+    }
+
+    fn view(&self, root_hash: Blake2bHash) -> RocksDbGlobalStateView {
+        RocksDbGlobalStateView {
+            environment: Arc::clone(&self.environment),
+            store: Arc::clone(&self.trie_store),
+            root_hash,
+        }
+    }
+}
+
+impl StateReader<Key, StoredValue> for RocksDbGlobalStateView {
+    type Error = error::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret =
+            match read::<Key, StoredValue, RocksDbReadTransaction, RocksDbTrieStore, Self::Error>(
+                correlation_id,
+                &txn,
+                self.store.deref(),
+                &self.root_hash,
+                key,
+            )? {
+                ReadResult::Found(value) => Some(value),
+                ReadResult::NotFound => None,
+                ReadResult::RootNotFound => panic!("RocksDbGlobalState has invalid root"),
+            };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter = operations::keys_with_prefix::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let mut ret = Vec::new();
+        for result in keys_iter {
+            ret.push(result?);
+        }
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
+impl StateProvider for RocksDbGlobalState {
+    type Error = error::Error;
+
+    type Reader = RocksDbGlobalStateView;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let is_known_root = self.root_store.get(&txn, &state_hash)?.is_some() || {
+            // Roots received from elsewhere rather than committed here aren't recorded.
+            let maybe_root: Option<Trie<Key, StoredValue>> =
+                self.trie_store.get(&txn, &state_hash)?;
+            maybe_root.is_some()
+        };
+        txn.commit()?;
+        Ok(if is_known_root {
+            Some(self.view(state_hash))
+        } else {
+            None
+        })
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        let commit_result = commit::<RocksDbEnvironment, RocksDbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+        )?;
+        if let CommitResult::Success { state_root, .. } = &commit_result {
+            let mut txn = self.environment.create_read_write_txn()?;
+            self.root_store.put(&mut txn, state_root, &())?;
+            txn.commit()?;
+        }
+        Ok(commit_result)
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.protocol_data_store
+            .put(&mut txn, &protocol_version, protocol_data)?;
+        txn.commit().map_err(Into::into)
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.protocol_data_store.get(&txn, &protocol_version)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    fn put_deploy_effects(
+        &self,
+        deploy_hash: DeployHash,
+        effects: &[u8],
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.deploy_effects_store
+            .put(&mut txn, &deploy_hash, &effects.to_vec())?;
+        txn.commit().map_err(Into::into)
+    }
+
+    fn get_deploy_effects(&self, deploy_hash: DeployHash) -> Result<Option<Vec<u8>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.deploy_effects_store.get(&txn, &deploy_hash)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    #[cfg(feature = "account-activity-index")]
+    fn record_account_activity(
+        &self,
+        deploy_hash: DeployHash,
+        accounts: &[PublicKey],
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        for account in accounts {
+            self.account_activity_store
+                .append(&mut txn, account, deploy_hash)?;
+        }
+        txn.commit().map_err(Into::into)
+    }
+
+    #[cfg(feature = "account-activity-index")]
+    fn get_account_activity(&self, account: PublicKey) -> Result<Vec<DeployHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.account_activity_store.get(&txn, &account)?;
+        txn.commit()?;
+        Ok(result.unwrap_or_default())
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.empty_root_hash
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match operations::read_with_proof::<
+            Key,
+            StoredValue,
+            _,
+            RocksDbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound | ReadResult::RootNotFound => None,
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let missing_descendants =
+            operations::missing_trie_keys::<Key, StoredValue, _, RocksDbTrieStore, Self::Error>(
+                correlation_id,
+                &txn,
+                self.trie_store.deref(),
+                trie_keys,
+            )?;
+        txn.commit()?;
+        Ok(missing_descendants)
+    }
+
+    fn set_deferred_sync(&self, deferred: bool) -> Result<(), Self::Error> {
+        self.environment.set_deferred_sync(deferred);
+        Ok(())
+    }
+
+    fn sync(&self) -> Result<(), Self::Error> {
+        self.environment.sync()
+    }
+
+    fn verify(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<TrieVerificationReport, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let report = operations::verify::<Key, StoredValue, _, RocksDbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_hash,
+        )?;
+        txn.commit()?;
+        Ok(report)
+    }
+
+    fn repair(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<TrieRepairReport, Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let report = operations::repair::<Key, StoredValue, _, RocksDbTrieStore, Self::Error>(
+            correlation_id,
+            &mut txn,
+            self.trie_store.deref(),
+            &state_hash,
+        )?;
+        self.root_store.put(&mut txn, &report.repaired_root, &())?;
+        txn.commit()?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use types::{account::PublicKey, CLValue};
+
+    use super::*;
+
+    fn test_pairs() -> Vec<(Key, StoredValue)> {
+        vec![
+            (
+                Key::Account(PublicKey::ed25519_from([1u8; 32])),
+                StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+            ),
+            (
+                Key::Account(PublicKey::ed25519_from([2u8; 32])),
+                StoredValue::CLValue(CLValue::from_t("two".to_string()).unwrap()),
+            ),
+        ]
+    }
+
+    fn write_effects(pairs: &[(Key, StoredValue)]) -> AdditiveMap<Key, Transform> {
+        pairs
+            .iter()
+            .map(|(key, value)| (*key, Transform::Write(value.to_owned())))
+            .collect()
+    }
+
+    fn commit_pairs(state: &RocksDbGlobalState, pairs: &[(Key, StoredValue)]) -> Blake2bHash {
+        let correlation_id = CorrelationId::new();
+        match state
+            .commit(correlation_id, state.empty_root(), write_effects(pairs))
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            other => panic!("commit failed: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn commit_updates_state() {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let state = RocksDbGlobalState::open(&temp_dir.path().to_path_buf()).unwrap();
+
+        let root_hash = commit_pairs(&state, &test_pairs());
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for (key, value) in test_pairs() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        let empty_checkout = state.checkout(state.empty_root()).unwrap().unwrap();
+        for (key, _) in test_pairs() {
+            assert_eq!(None, empty_checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+
+    #[test]
+    fn checkout_fails_if_unknown_hash_is_given() {
+        let temp_dir = tempdir().unwrap();
+        let state = RocksDbGlobalState::open(&temp_dir.path().to_path_buf()).unwrap();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        assert!(state.checkout(fake_hash).unwrap().is_none());
+    }
+
+    #[test]
+    fn state_persists_across_reopening() {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().to_path_buf();
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let protocol_data = ProtocolData::default();
+
+        let root_hash = {
+            let state = RocksDbGlobalState::open(&path).unwrap();
+            state
+                .put_protocol_data(protocol_version, &protocol_data)
+                .unwrap();
+            commit_pairs(&state, &test_pairs())
+        };
+
+        let state = RocksDbGlobalState::open(&path).unwrap();
+        assert_eq!(
+            Some(protocol_data),
+            state.get_protocol_data(protocol_version).unwrap()
+        );
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for (key, value) in test_pairs() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
+}
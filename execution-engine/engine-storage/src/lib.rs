@@ -1,6 +1,9 @@
 #![feature(never_type)]
 
 // modules
+#[cfg(feature = "account-activity-index")]
+pub mod account_activity_store;
+pub mod deploy_effects_store;
 pub mod error;
 pub mod global_state;
 pub mod protocol_data;
@@ -14,7 +17,7 @@ pub mod trie_store;
 use lazy_static::lazy_static;
 
 pub(crate) const GAUGE_METRIC_KEY: &str = "gauge";
-const MAX_DBS: u32 = 2;
+const MAX_DBS: u32 = 4;
 
 #[cfg(test)]
 lazy_static! {
@@ -1,13 +1,32 @@
-use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
+use engine_wasm_prep::{
+    wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH},
+    wasm_features::{WasmFeatures, WASM_FEATURES_SERIALIZED_LENGTH},
+};
 use types::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    account::PublicKey,
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
     AccessRights, URef, UREF_SERIALIZED_LENGTH,
 };
 
-const PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
-    WASM_COSTS_SERIALIZED_LENGTH + 3 * UREF_SERIALIZED_LENGTH;
+const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = WASM_COSTS_SERIALIZED_LENGTH
+    + 4 * UREF_SERIALIZED_LENGTH
+    + U32_SERIALIZED_LENGTH
+    + WASM_FEATURES_SERIALIZED_LENGTH
+    + U8_SERIALIZED_LENGTH;
 const DEFAULT_UREF_ADDRESS: [u8; 32] = [0; 32];
 
+/// The default maximum depth of nested `call_contract` invocations.
+pub const DEFAULT_MAX_CALL_DEPTH: u32 = 32;
+
+/// The largest valid refund ratio, i.e. a full refund of unspent payment.
+pub const MAX_REFUND_RATIO: u8 = 100;
+
+/// The default percentage of unspent payment refunded to the user at finalization.
+pub const DEFAULT_REFUND_RATIO: u8 = MAX_REFUND_RATIO;
+
+/// The public key of the system account unless genesis configures another one.
+pub const DEFAULT_SYSTEM_ACCOUNT: PublicKey = PublicKey::ed25519_from([0u8; 32]);
+
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
@@ -15,6 +34,13 @@ pub struct ProtocolData {
     mint: URef,
     proof_of_stake: URef,
     standard_payment: URef,
+    key_recovery: URef,
+    max_call_depth: u32,
+    wasm_features: WasmFeatures,
+    refund_ratio: u8,
+    system_account: PublicKey,
+    deploy_whitelist: Option<URef>,
+    chain_name: Option<URef>,
 }
 
 /// Provides a default instance with non existing urefs and empty costs table.
@@ -28,23 +54,43 @@ impl Default for ProtocolData {
             mint: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
             proof_of_stake: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
             standard_payment: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
+            key_recovery: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            wasm_features: WasmFeatures::default(),
+            refund_ratio: DEFAULT_REFUND_RATIO,
+            system_account: DEFAULT_SYSTEM_ACCOUNT,
+            deploy_whitelist: None,
+            chain_name: None,
         }
     }
 }
 
 impl ProtocolData {
     /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         wasm_costs: WasmCosts,
         mint: URef,
         proof_of_stake: URef,
         standard_payment: URef,
+        key_recovery: URef,
+        max_call_depth: u32,
+        wasm_features: WasmFeatures,
+        refund_ratio: u8,
+        system_account: PublicKey,
     ) -> Self {
         ProtocolData {
             wasm_costs,
             mint,
             proof_of_stake,
             standard_payment,
+            key_recovery,
+            max_call_depth,
+            wasm_features,
+            refund_ratio,
+            system_account,
+            deploy_whitelist: None,
+            chain_name: None,
         }
     }
 
@@ -75,6 +121,52 @@ impl ProtocolData {
         }
     }
 
+    /// Creates a new, partially-valid [`ProtocolData`] value where all but the key recovery uref
+    /// is known.
+    ///
+    /// Used during `commit_genesis` before all system contracts' URefs are known.
+    pub fn partial_without_key_recovery(
+        wasm_costs: WasmCosts,
+        mint: URef,
+        proof_of_stake: URef,
+        standard_payment: URef,
+    ) -> Self {
+        ProtocolData {
+            wasm_costs,
+            mint,
+            proof_of_stake,
+            standard_payment,
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the public key of the system account.
+    ///
+    /// Used during `commit_genesis` when the chain configures a system account other than
+    /// [`DEFAULT_SYSTEM_ACCOUNT`].
+    pub fn with_system_account(mut self, system_account: PublicKey) -> Self {
+        self.system_account = system_account;
+        self
+    }
+
+    /// Replaces the URef of the deploy whitelist.
+    ///
+    /// Used during `commit_genesis` when the chain has a whitelist admin, and when upgrading to
+    /// carry the whitelist over to the new protocol version.
+    pub fn with_deploy_whitelist(mut self, deploy_whitelist: Option<URef>) -> Self {
+        self.deploy_whitelist = deploy_whitelist;
+        self
+    }
+
+    /// Replaces the URef of the chain name.
+    ///
+    /// Used during `commit_genesis` when the chainspec names the chain, and when upgrading to
+    /// carry the name over to the new protocol version.
+    pub fn with_chain_name(mut self, chain_name: Option<URef>) -> Self {
+        self.chain_name = chain_name;
+        self
+    }
+
     /// Gets the [`WasmCosts`] value from a given [`ProtocolData`] value.
     pub fn wasm_costs(&self) -> &WasmCosts {
         &self.wasm_costs
@@ -92,9 +184,47 @@ impl ProtocolData {
         self.standard_payment
     }
 
+    pub fn key_recovery(&self) -> URef {
+        self.key_recovery
+    }
+
+    /// Gets the maximum number of nested `call_contract` invocations allowed within a single
+    /// execution.
+    pub fn max_call_depth(&self) -> u32 {
+        self.max_call_depth
+    }
+
+    /// Gets the post-MVP Wasm features which contracts may use.
+    pub fn wasm_features(&self) -> WasmFeatures {
+        self.wasm_features
+    }
+
+    /// Gets the percentage (between 0 and [`MAX_REFUND_RATIO`]) of unspent payment which is
+    /// refunded to the user when a deploy is finalized.
+    pub fn refund_ratio(&self) -> u8 {
+        self.refund_ratio
+    }
+
+    /// Gets the public key of the system account, the only account allowed to call privileged
+    /// system contract entry points.
+    pub fn system_account(&self) -> PublicKey {
+        self.system_account
+    }
+
+    /// Gets the URef holding the list of accounts allowed to take the actions a permissioned
+    /// network restricts, if the chain was set up with one.
+    pub fn deploy_whitelist(&self) -> Option<URef> {
+        self.deploy_whitelist
+    }
+
+    /// Gets the URef holding the name of the chain, if its chainspec gave one.
+    pub fn chain_name(&self) -> Option<URef> {
+        self.chain_name
+    }
+
     /// Retrieves all valid system contracts stored in protocol version
     pub fn system_contracts(&self) -> Vec<URef> {
-        let mut vec = Vec::with_capacity(3);
+        let mut vec = Vec::with_capacity(4);
         if self.mint.addr() != DEFAULT_UREF_ADDRESS {
             vec.push(self.mint)
         }
@@ -104,6 +234,9 @@ impl ProtocolData {
         if self.standard_payment.addr() != DEFAULT_UREF_ADDRESS {
             vec.push(self.standard_payment)
         }
+        if self.key_recovery.addr() != DEFAULT_UREF_ADDRESS {
+            vec.push(self.key_recovery)
+        }
         vec
     }
 }
@@ -115,11 +248,21 @@ impl ToBytes for ProtocolData {
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
         ret.append(&mut self.standard_payment.to_bytes()?);
+        ret.append(&mut self.key_recovery.to_bytes()?);
+        ret.append(&mut self.max_call_depth.to_bytes()?);
+        ret.append(&mut self.wasm_features.to_bytes()?);
+        ret.append(&mut self.refund_ratio.to_bytes()?);
+        ret.append(&mut self.system_account.to_bytes()?);
+        ret.append(&mut self.deploy_whitelist.to_bytes()?);
+        ret.append(&mut self.chain_name.to_bytes()?);
         Ok(ret)
     }
 
     fn serialized_length(&self) -> usize {
         PROTOCOL_DATA_SERIALIZED_LENGTH
+            + self.system_account.serialized_length()
+            + self.deploy_whitelist.serialized_length()
+            + self.chain_name.serialized_length()
     }
 }
 
@@ -129,12 +272,26 @@ impl FromBytes for ProtocolData {
         let (mint, rem) = URef::from_bytes(rem)?;
         let (proof_of_stake, rem) = URef::from_bytes(rem)?;
         let (standard_payment, rem) = URef::from_bytes(rem)?;
+        let (key_recovery, rem) = URef::from_bytes(rem)?;
+        let (max_call_depth, rem) = u32::from_bytes(rem)?;
+        let (wasm_features, rem) = WasmFeatures::from_bytes(rem)?;
+        let (refund_ratio, rem) = u8::from_bytes(rem)?;
+        let (system_account, rem) = PublicKey::from_bytes(rem)?;
+        let (deploy_whitelist, rem) = Option::<URef>::from_bytes(rem)?;
+        let (chain_name, rem) = Option::<URef>::from_bytes(rem)?;
         Ok((
             ProtocolData {
                 wasm_costs,
                 mint,
                 proof_of_stake,
                 standard_payment,
+                key_recovery,
+                max_call_depth,
+                wasm_features,
+                refund_ratio,
+                system_account,
+                deploy_whitelist,
+                chain_name,
             },
             rem,
         ))
@@ -143,12 +300,14 @@ impl FromBytes for ProtocolData {
 
 #[cfg(test)]
 pub(crate) mod gens {
-    use proptest::prop_compose;
+    use proptest::{option, prelude::any, prop_compose};
 
-    use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
+    use engine_wasm_prep::{
+        wasm_costs::gens as wasm_costs_gens, wasm_features::gens as wasm_features_gens,
+    };
     use types::gens;
 
-    use super::ProtocolData;
+    use super::{ProtocolData, MAX_REFUND_RATIO};
 
     prop_compose! {
         pub fn protocol_data_arb()(
@@ -156,12 +315,26 @@ pub(crate) mod gens {
             mint in gens::uref_arb(),
             proof_of_stake in gens::uref_arb(),
             standard_payment in gens::uref_arb(),
+            key_recovery in gens::uref_arb(),
+            max_call_depth in any::<u32>(),
+            wasm_features in wasm_features_gens::wasm_features_arb(),
+            refund_ratio in 0..=MAX_REFUND_RATIO,
+            system_account in gens::public_key_arb(),
+            deploy_whitelist in option::of(gens::uref_arb()),
+            chain_name in option::of(gens::uref_arb()),
         ) -> ProtocolData {
             ProtocolData {
                 wasm_costs,
                 mint,
                 proof_of_stake,
                 standard_payment,
+                key_recovery,
+                max_call_depth,
+                wasm_features,
+                refund_ratio,
+                system_account,
+                deploy_whitelist,
+                chain_name,
             }
         }
     }
@@ -171,10 +344,12 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use engine_wasm_prep::wasm_costs::WasmCosts;
+    use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_features::WasmFeatures};
     use types::{bytesrepr, AccessRights, URef};
 
-    use super::{gens, ProtocolData};
+    use super::{
+        gens, ProtocolData, DEFAULT_MAX_CALL_DEPTH, DEFAULT_REFUND_RATIO, DEFAULT_SYSTEM_ACCOUNT,
+    };
 
     fn wasm_costs_mock() -> WasmCosts {
         WasmCosts {
@@ -188,6 +363,18 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            max_memory_pages: 64,
+            max_table_size: 4096,
+            hash_per_byte: 2,
+            verify_ed25519: 25_000,
+            verify_secp256k1: 50_000,
+            bignum_op: 10,
+            storage_bucket_size: 128,
+            storage_read_per_bucket: 50,
+            storage_write_per_bucket: 500,
+            max_module_size: 4 * 1024 * 1024,
+            max_function_count: 10_000,
+            max_named_keys_per_write: 10_000,
         }
     }
 
@@ -203,6 +390,18 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            max_memory_pages: 64,
+            max_table_size: 4096,
+            hash_per_byte: 0,
+            verify_ed25519: 0,
+            verify_secp256k1: 0,
+            bignum_op: 0,
+            storage_bucket_size: 128,
+            storage_read_per_bucket: 0,
+            storage_write_per_bucket: 0,
+            max_module_size: 4 * 1024 * 1024,
+            max_function_count: 10_000,
+            max_named_keys_per_write: 10_000,
         }
     }
 
@@ -213,11 +412,17 @@ mod tests {
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
             let standard_payment_reference = URef::new([2u8; 32], AccessRights::READ_ADD_WRITE);
+            let key_recovery_reference = URef::new([3u8; 32], AccessRights::READ_ADD_WRITE);
             ProtocolData::new(
                 costs,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
+                key_recovery_reference,
+                DEFAULT_MAX_CALL_DEPTH,
+                WasmFeatures::default(),
+                DEFAULT_REFUND_RATIO,
+                DEFAULT_SYSTEM_ACCOUNT,
             )
         };
         let free = {
@@ -225,11 +430,17 @@ mod tests {
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
             let standard_payment_reference = URef::new([2u8; 32], AccessRights::READ_ADD_WRITE);
+            let key_recovery_reference = URef::new([3u8; 32], AccessRights::READ_ADD_WRITE);
             ProtocolData::new(
                 costs,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
+                key_recovery_reference,
+                DEFAULT_MAX_CALL_DEPTH,
+                WasmFeatures::default(),
+                DEFAULT_REFUND_RATIO,
+                DEFAULT_SYSTEM_ACCOUNT,
             )
         };
         bytesrepr::test_serialization_roundtrip(&mock);
@@ -241,6 +452,7 @@ mod tests {
         let mint_reference = URef::new([197u8; 32], AccessRights::READ_ADD_WRITE);
         let proof_of_stake_reference = URef::new([198u8; 32], AccessRights::READ_ADD_WRITE);
         let standard_payment_reference = URef::new([199u8; 32], AccessRights::READ_ADD_WRITE);
+        let key_recovery_reference = URef::new([200u8; 32], AccessRights::READ_ADD_WRITE);
         let protocol_data = {
             let costs = wasm_costs_mock();
             ProtocolData::new(
@@ -248,6 +460,11 @@ mod tests {
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
+                key_recovery_reference,
+                DEFAULT_MAX_CALL_DEPTH,
+                WasmFeatures::default(),
+                DEFAULT_REFUND_RATIO,
+                DEFAULT_SYSTEM_ACCOUNT,
             )
         };
 
@@ -257,10 +474,11 @@ mod tests {
             items
         };
 
-        assert_eq!(actual.len(), 3);
+        assert_eq!(actual.len(), 4);
         assert_eq!(actual[0], mint_reference);
         assert_eq!(actual[1], proof_of_stake_reference);
         assert_eq!(actual[2], standard_payment_reference);
+        assert_eq!(actual[3], key_recovery_reference);
     }
 
     #[test]
@@ -270,6 +488,7 @@ mod tests {
         let mint_reference = URef::new([197u8; 32], AccessRights::READ_ADD_WRITE);
         let proof_of_stake_reference = URef::new([0u8; 32], AccessRights::READ);
         let standard_payment_reference = URef::new([199u8; 32], AccessRights::READ_ADD_WRITE);
+        let key_recovery_reference = URef::new([200u8; 32], AccessRights::READ_ADD_WRITE);
         let protocol_data = {
             let costs = wasm_costs_mock();
             ProtocolData::new(
@@ -277,6 +496,11 @@ mod tests {
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
+                key_recovery_reference,
+                DEFAULT_MAX_CALL_DEPTH,
+                WasmFeatures::default(),
+                DEFAULT_REFUND_RATIO,
+                DEFAULT_SYSTEM_ACCOUNT,
             )
         };
 
@@ -286,9 +510,10 @@ mod tests {
             items
         };
 
-        assert_eq!(actual.len(), 2);
+        assert_eq!(actual.len(), 3);
         assert_eq!(actual[0], mint_reference);
         assert_eq!(actual[1], standard_payment_reference);
+        assert_eq!(actual[2], key_recovery_reference);
     }
 
     proptest! {
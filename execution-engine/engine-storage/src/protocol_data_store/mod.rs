@@ -4,6 +4,8 @@ use types::ProtocolVersion;
 
 pub mod in_memory;
 pub mod lmdb;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,48 @@
+use types::ProtocolVersion;
+
+use crate::{
+    error,
+    protocol_data::ProtocolData,
+    protocol_data_store::{self, ProtocolDataStore},
+    store::Store,
+    transaction_source::rocksdb::RocksDbEnvironment,
+};
+
+/// A RocksDB-backed protocol data store.
+///
+/// Wraps the name of a column family of a [`rocksdb::DB`].
+#[derive(Debug, Clone)]
+pub struct RocksDbProtocolDataStore {
+    cf: String,
+}
+
+impl RocksDbProtocolDataStore {
+    /// Opens the store in the column family named after `maybe_name`, which `env` must have been
+    /// opened with.  See [`RocksDbProtocolDataStore::column_family`].
+    pub fn new(env: &RocksDbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let cf = Self::column_family(maybe_name);
+        if !env.has_column_family(&cf) {
+            return Err(error::rocksdb::Error::MissingColumnFamily(cf).into());
+        }
+        Ok(RocksDbProtocolDataStore { cf })
+    }
+
+    /// Returns the name of the column family holding the store called `maybe_name`.
+    pub fn column_family(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", protocol_data_store::NAME, name))
+            .unwrap_or_else(|| String::from(protocol_data_store::NAME))
+    }
+}
+
+impl Store<ProtocolVersion, ProtocolData> for RocksDbProtocolDataStore {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.cf.clone()
+    }
+}
+
+impl ProtocolDataStore for RocksDbProtocolDataStore {}
@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap,
-    sync::{self, Arc, Mutex, MutexGuard},
+    sync::{Arc, Mutex, MutexGuard},
 };
 
 use crate::{
@@ -16,20 +16,77 @@ type WriteLock<'a> = MutexGuard<'a, WriteCapability>;
 
 type BytesMap = HashMap<Vec<u8>, Vec<u8>>;
 
-type PoisonError<'a> = sync::PoisonError<MutexGuard<'a, HashMap<Option<String>, BytesMap>>>;
+type Layer = HashMap<Option<String>, BytesMap>;
+
+/// The contents of an [`InMemoryEnvironment`] at some point in time.
+///
+/// The data is split into layers, the most recent last.  Commits only ever modify the last layer,
+/// while the earlier ones are shared with forks of the environment and never modified again, so
+/// taking a snapshot or forking only clones a handful of [`Arc`]s.
+#[derive(Clone)]
+struct Snapshot {
+    layers: Vec<Arc<Layer>>,
+}
+
+impl Default for Snapshot {
+    fn default() -> Self {
+        let mut initial_layer = Layer::new();
+        initial_layer.insert(None, Default::default());
+        Snapshot {
+            layers: vec![Arc::new(initial_layer)],
+        }
+    }
+}
+
+impl Snapshot {
+    fn read(&self, handle: &Option<String>, key: &[u8]) -> Option<Vec<u8>> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.get(handle).and_then(|values| values.get(key)))
+            .cloned()
+    }
+
+    fn merged(&self, handle: &Option<String>) -> Option<BytesMap> {
+        let mut ret: Option<BytesMap> = None;
+        for values in self.layers.iter().filter_map(|layer| layer.get(handle)) {
+            ret.get_or_insert_with(Default::default)
+                .extend(values.iter().map(|(k, v)| (k.to_owned(), v.to_owned())));
+        }
+        ret
+    }
+
+    fn apply(&mut self, pending: Layer) {
+        let top = self
+            .layers
+            .last_mut()
+            .expect("snapshot should have at least one layer");
+        let top = Arc::make_mut(top);
+        for (handle, values) in pending {
+            top.entry(handle).or_default().extend(values);
+        }
+    }
+
+    /// Starts a new layer for subsequent commits, so that everything written so far can be shared.
+    fn freeze(&mut self) {
+        let top_is_empty = self
+            .layers
+            .last()
+            .map_or(false, |layer| layer.values().all(HashMap::is_empty));
+        if !top_is_empty {
+            self.layers.push(Default::default());
+        }
+    }
+}
 
 /// A read transaction for the in-memory trie store.
 pub struct InMemoryReadTransaction {
-    view: HashMap<Option<String>, BytesMap>,
+    view: Snapshot,
 }
 
 impl InMemoryReadTransaction {
     pub fn new(store: &InMemoryEnvironment) -> Result<InMemoryReadTransaction, Error> {
-        let view = {
-            let db_ref = Arc::clone(&store.data);
-            let view_lock = db_ref.lock()?;
-            view_lock.to_owned()
-        };
+        let view = store.data.lock()?.to_owned();
         Ok(InMemoryReadTransaction { view })
     }
 }
@@ -46,31 +103,26 @@ impl Transaction for InMemoryReadTransaction {
 
 impl Readable for InMemoryReadTransaction {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-        let sub_view = match self.view.get(&handle) {
-            Some(view) => view,
-            None => return Ok(None),
-        };
-        Ok(sub_view.get(&key.to_vec()).cloned())
+        Ok(self.view.read(&handle, key))
     }
 }
 
 /// A read-write transaction for the in-memory trie store.
 pub struct InMemoryReadWriteTransaction<'a> {
-    view: HashMap<Option<String>, BytesMap>,
-    store_ref: Arc<Mutex<HashMap<Option<String>, BytesMap>>>,
+    view: Snapshot,
+    pending: Layer,
+    store_ref: Arc<Mutex<Snapshot>>,
     _write_lock: WriteLock<'a>,
 }
 
 impl<'a> InMemoryReadWriteTransaction<'a> {
     pub fn new(store: &'a InMemoryEnvironment) -> Result<InMemoryReadWriteTransaction<'a>, Error> {
-        let store_ref = Arc::clone(&store.data);
-        let view = {
-            let view_lock = store_ref.lock()?;
-            view_lock.to_owned()
-        };
         let _write_lock = store.write_mutex.lock()?;
+        let store_ref = Arc::clone(&store.data);
+        let view = store_ref.lock()?.to_owned();
         Ok(InMemoryReadWriteTransaction {
             view,
+            pending: Layer::new(),
             store_ref,
             _write_lock,
         })
@@ -83,25 +135,26 @@ impl<'a> Transaction for InMemoryReadWriteTransaction<'a> {
     type Handle = Option<String>;
 
     fn commit(self) -> Result<(), Self::Error> {
+        // Release our references to the current layer first, so that it isn't copied needlessly.
+        drop(self.view);
         let mut store_ref_lock = self.store_ref.lock()?;
-        store_ref_lock.extend(self.view);
+        store_ref_lock.apply(self.pending);
         Ok(())
     }
 }
 
 impl<'a> Readable for InMemoryReadWriteTransaction<'a> {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-        let sub_view = match self.view.get(&handle) {
-            Some(view) => view,
-            None => return Ok(None),
-        };
-        Ok(sub_view.get(&key.to_vec()).cloned())
+        if let Some(value) = self.pending.get(&handle).and_then(|values| values.get(key)) {
+            return Ok(Some(value.to_owned()));
+        }
+        Ok(self.view.read(&handle, key))
     }
 }
 
 impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
-        let sub_view = self.view.entry(handle).or_default();
+        let sub_view = self.pending.entry(handle).or_default();
         sub_view.insert(key.to_vec(), value.to_vec());
         Ok(())
     }
@@ -109,17 +162,13 @@ impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
 
 /// An environment for the in-memory trie store.
 pub struct InMemoryEnvironment {
-    data: Arc<Mutex<HashMap<Option<String>, BytesMap>>>,
+    data: Arc<Mutex<Snapshot>>,
     write_mutex: Arc<Mutex<WriteCapability>>,
 }
 
 impl Default for InMemoryEnvironment {
     fn default() -> Self {
-        let data = {
-            let mut initial_map = HashMap::new();
-            initial_map.insert(None, Default::default());
-            Arc::new(Mutex::new(initial_map))
-        };
+        let data = Arc::new(Mutex::new(Snapshot::default()));
         let write_mutex = Arc::new(Mutex::new(WriteCapability));
         InMemoryEnvironment { data, write_mutex }
     }
@@ -130,12 +179,27 @@ impl InMemoryEnvironment {
         Default::default()
     }
 
-    pub fn data(&self, name: Option<&str>) -> Result<Option<BytesMap>, PoisonError> {
+    pub fn data(&self, name: Option<&str>) -> Result<Option<BytesMap>, Error> {
         let data = self.data.lock()?;
         let name = name.map(ToString::to_string);
-        let ret = data.get(&name).cloned();
+        let ret = data.merged(&name);
         Ok(ret)
     }
+
+    /// Returns a copy-on-write fork of this environment.
+    ///
+    /// The fork starts out with everything committed to this environment so far, without copying
+    /// it.  Afterwards, commits to either environment are invisible to the other.
+    pub fn fork(&self) -> Result<InMemoryEnvironment, Error> {
+        let snapshot = {
+            let mut data = self.data.lock()?;
+            data.freeze();
+            data.to_owned()
+        };
+        let data = Arc::new(Mutex::new(snapshot));
+        let write_mutex = Arc::new(Mutex::new(WriteCapability));
+        Ok(InMemoryEnvironment { data, write_mutex })
+    }
 }
 
 impl<'a> TransactionSource<'a> for InMemoryEnvironment {
@@ -1,6 +1,14 @@
-use std::path::PathBuf;
+use std::{
+    ffi::CString,
+    mem::MaybeUninit,
+    os::{raw::c_int, unix::ffi::OsStrExt},
+    path::{Path, PathBuf},
+};
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb::{
+    self, Database, Environment, EnvironmentFlags, RoTransaction, RwTransaction, WriteFlags,
+};
+use parking_lot::{RwLock, RwLockReadGuard};
 
 use crate::{
     error,
@@ -8,19 +16,134 @@ use crate::{
     MAX_DBS,
 };
 
-impl<'a> Transaction for RoTransaction<'a> {
+/// The map is grown once more than `MAP_GROWTH_THRESHOLD_NUMERATOR /
+/// MAP_GROWTH_THRESHOLD_DENOMINATOR` of it is in use.
+const MAP_GROWTH_THRESHOLD_NUMERATOR: usize = 3;
+const MAP_GROWTH_THRESHOLD_DENOMINATOR: usize = 4;
+
+/// How LMDB flushes committed transactions to disk.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LmdbSyncMode {
+    /// Flush data and metadata on every commit.  A crash can't lose or corrupt committed
+    /// transactions.
+    Full,
+    /// Flush data but not metadata on every commit (`MDB_NOMETASYNC`).  A crash may undo the
+    /// last committed transaction, but can't corrupt the database.
+    NoMetaSync,
+    /// Leave flushing to the operating system (`MDB_NOSYNC`).  A crash may undo any number of
+    /// recent transactions, or corrupt the database if the filesystem doesn't preserve write
+    /// order.
+    NoSync,
+}
+
+impl Default for LmdbSyncMode {
+    fn default() -> Self {
+        LmdbSyncMode::Full
+    }
+}
+
+/// The tuning of an [`LmdbEnvironment`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LmdbConfig {
+    map_size: usize,
+    max_map_size: usize,
+    sync_mode: LmdbSyncMode,
+    read_only: bool,
+}
+
+impl LmdbConfig {
+    /// Creates a configuration for a map of `map_size` bytes which never grows, using
+    /// [`LmdbSyncMode::Full`].
+    ///
+    /// `map_size` should be a multiple of the OS page size.
+    pub fn new(map_size: usize) -> LmdbConfig {
+        LmdbConfig {
+            map_size,
+            max_map_size: map_size,
+            sync_mode: LmdbSyncMode::default(),
+            read_only: false,
+        }
+    }
+
+    pub fn map_size(self) -> usize {
+        self.map_size
+    }
+
+    /// The size in bytes up to which the map is doubled as it fills up, rather than failing
+    /// writes with `MDB_MAP_FULL`.
+    pub fn max_map_size(self) -> usize {
+        self.max_map_size
+    }
+
+    /// Sets the size up to which the map may grow.  It should be a multiple of the OS page size,
+    /// and is ignored if smaller than the initial map size.
+    pub fn with_max_map_size(mut self, max_map_size: usize) -> LmdbConfig {
+        self.max_map_size = max_map_size;
+        self
+    }
+
+    pub fn sync_mode(self) -> LmdbSyncMode {
+        self.sync_mode
+    }
+
+    pub fn with_sync_mode(mut self, sync_mode: LmdbSyncMode) -> LmdbConfig {
+        self.sync_mode = sync_mode;
+        self
+    }
+
+    /// Whether the environment is opened read-only, in which case read-write transactions can't
+    /// be created.
+    pub fn read_only(self) -> bool {
+        self.read_only
+    }
+
+    pub fn with_read_only(mut self, read_only: bool) -> LmdbConfig {
+        self.read_only = read_only;
+        self
+    }
+
+    fn flags(self) -> EnvironmentFlags {
+        let mut flags = match self.sync_mode {
+            LmdbSyncMode::Full => EnvironmentFlags::empty(),
+            LmdbSyncMode::NoMetaSync => EnvironmentFlags::NO_META_SYNC,
+            LmdbSyncMode::NoSync => EnvironmentFlags::NO_SYNC,
+        };
+        if self.read_only {
+            flags |= EnvironmentFlags::READ_ONLY;
+        }
+        flags
+    }
+}
+
+/// A read transaction on an [`LmdbEnvironment`].
+///
+/// Wraps [`lmdb::RoTransaction`], keeping the map from being resized while it is open.
+pub struct LmdbReadTransaction<'a> {
+    txn: RoTransaction<'a>,
+    _map_guard: RwLockReadGuard<'a, ()>,
+}
+
+/// A read-write transaction on an [`LmdbEnvironment`].
+///
+/// Wraps [`lmdb::RwTransaction`], keeping the map from being resized while it is open.
+pub struct LmdbReadWriteTransaction<'a> {
+    txn: RwTransaction<'a>,
+    _map_guard: RwLockReadGuard<'a, ()>,
+}
+
+impl<'a> Transaction for LmdbReadTransaction<'a> {
     type Error = lmdb::Error;
 
     type Handle = Database;
 
     fn commit(self) -> Result<(), Self::Error> {
-        lmdb::Transaction::commit(self)
+        lmdb::Transaction::commit(self.txn)
     }
 }
 
-impl<'a> Readable for RoTransaction<'a> {
+impl<'a> Readable for LmdbReadTransaction<'a> {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-        match lmdb::Transaction::get(self, handle, &key) {
+        match lmdb::Transaction::get(&self.txn, handle, &key) {
             Ok(bytes) => Ok(Some(bytes.to_vec())),
             Err(lmdb::Error::NotFound) => Ok(None),
             Err(e) => Err(e),
@@ -28,19 +151,19 @@ impl<'a> Readable for RoTransaction<'a> {
     }
 }
 
-impl<'a> Transaction for RwTransaction<'a> {
+impl<'a> Transaction for LmdbReadWriteTransaction<'a> {
     type Error = lmdb::Error;
 
     type Handle = Database;
 
     fn commit(self) -> Result<(), Self::Error> {
-        <RwTransaction<'a> as lmdb::Transaction>::commit(self)
+        lmdb::Transaction::commit(self.txn)
     }
 }
 
-impl<'a> Readable for RwTransaction<'a> {
+impl<'a> Readable for LmdbReadWriteTransaction<'a> {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-        match lmdb::Transaction::get(self, handle, &key) {
+        match lmdb::Transaction::get(&self.txn, handle, &key) {
             Ok(bytes) => Ok(Some(bytes.to_vec())),
             Err(lmdb::Error::NotFound) => Ok(None),
             Err(e) => Err(e),
@@ -48,9 +171,10 @@ impl<'a> Readable for RwTransaction<'a> {
     }
 }
 
-impl<'a> Writable for RwTransaction<'a> {
+impl<'a> Writable for LmdbReadWriteTransaction<'a> {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
-        self.put(handle, &key, &value, WriteFlags::empty())
+        self.txn
+            .put(handle, &key, &value, WriteFlags::empty())
             .map_err(Into::into)
     }
 }
@@ -62,16 +186,30 @@ impl<'a> Writable for RwTransaction<'a> {
 pub struct LmdbEnvironment {
     path: PathBuf,
     env: Environment,
+    config: LmdbConfig,
+    /// Held for reading by every open transaction, and for writing while the map is resized.
+    map_lock: RwLock<()>,
 }
 
 impl LmdbEnvironment {
     pub fn new(path: &PathBuf, map_size: usize) -> Result<Self, error::Error> {
+        LmdbEnvironment::with_config(path, LmdbConfig::new(map_size))
+    }
+
+    pub fn with_config(path: &PathBuf, config: LmdbConfig) -> Result<Self, error::Error> {
         let env = Environment::new()
             .set_max_dbs(MAX_DBS)
-            .set_map_size(map_size)
+            .set_map_size(config.map_size)
+            .set_flags(config.flags())
             .open(path)?;
         let path = path.to_owned();
-        Ok(LmdbEnvironment { path, env })
+        let map_lock = RwLock::new(());
+        Ok(LmdbEnvironment {
+            path,
+            env,
+            config,
+            map_lock,
+        })
     }
 
     pub fn path(&self) -> &PathBuf {
@@ -81,6 +219,92 @@ impl LmdbEnvironment {
     pub fn env(&self) -> &Environment {
         &self.env
     }
+
+    pub fn config(&self) -> LmdbConfig {
+        self.config
+    }
+
+    /// Returns the current size of the map in bytes.
+    pub fn map_size(&self) -> Result<usize, error::Error> {
+        Ok(self.info()?.me_mapsize)
+    }
+
+    /// Flushes every committed transaction to disk, whatever the sync mode.
+    pub fn sync(&self) -> Result<(), error::Error> {
+        self.env.sync(true).map_err(Into::into)
+    }
+
+    /// Sets whether committing a read-write transaction returns before it is flushed to disk, as
+    /// with [`LmdbSyncMode::NoSync`], leaving that to [`sync`](LmdbEnvironment::sync).
+    pub fn set_deferred_sync(&self, deferred: bool) -> Result<(), error::Error> {
+        let onoff = c_int::from(deferred);
+        let code =
+            unsafe { lmdb_sys::mdb_env_set_flags(self.env.env(), lmdb_sys::MDB_NOSYNC, onoff) };
+        lmdb_result(code).map_err(Into::into)
+    }
+
+    /// Writes a compacted copy of the environment to the existing, empty directory
+    /// `destination`, omitting free pages.
+    ///
+    /// Space freed in the map is only reused, never returned to the filesystem, so the copy can
+    /// be much smaller than the original.  Replacing the original's data file with the copy's
+    /// while no process has the environment open reclaims that space.
+    pub fn compact(&self, destination: &Path) -> Result<(), error::Error> {
+        let destination =
+            CString::new(destination.as_os_str().as_bytes()).map_err(|_| lmdb::Error::Invalid)?;
+        let _map_guard = self.map_lock.read_recursive();
+        let code = unsafe {
+            lmdb_sys::mdb_env_copy2(
+                self.env.env(),
+                destination.as_ptr(),
+                lmdb_sys::MDB_CP_COMPACT,
+            )
+        };
+        lmdb_result(code).map_err(Into::into)
+    }
+
+    fn info(&self) -> Result<lmdb_sys::MDB_envinfo, lmdb::Error> {
+        let mut info = MaybeUninit::uninit();
+        lmdb_result(unsafe { lmdb_sys::mdb_env_info(self.env.env(), info.as_mut_ptr()) })?;
+        Ok(unsafe { info.assume_init() })
+    }
+
+    fn page_size(&self) -> Result<usize, lmdb::Error> {
+        let mut stat = MaybeUninit::uninit();
+        lmdb_result(unsafe { lmdb_sys::mdb_env_stat(self.env.env(), stat.as_mut_ptr()) })?;
+        Ok(unsafe { stat.assume_init() }.ms_psize as usize)
+    }
+
+    /// Doubles the map, up to the configured maximum, if more than the growth threshold of it is
+    /// in use.
+    ///
+    /// LMDB requires that no transactions are open in this process while the map is resized, so
+    /// growth is skipped if any are, and retried when the next read-write transaction is created.
+    fn grow_map_if_needed(&self) -> Result<(), lmdb::Error> {
+        let info = self.info()?;
+        let page_size = self.page_size()?;
+        let used = (info.me_last_pgno + 1) * page_size;
+        let map_size = info.me_mapsize;
+        if map_size >= self.config.max_map_size
+            || used * MAP_GROWTH_THRESHOLD_DENOMINATOR < map_size * MAP_GROWTH_THRESHOLD_NUMERATOR
+        {
+            return Ok(());
+        }
+        let _map_guard = match self.map_lock.try_write() {
+            Some(guard) => guard,
+            None => return Ok(()),
+        };
+        let new_map_size = map_size.saturating_mul(2).min(self.config.max_map_size);
+        lmdb_result(unsafe { lmdb_sys::mdb_env_set_mapsize(self.env.env(), new_map_size) })
+    }
+}
+
+fn lmdb_result(code: c_int) -> Result<(), lmdb::Error> {
+    if code == lmdb_sys::MDB_SUCCESS {
+        Ok(())
+    } else {
+        Err(lmdb::Error::from_err_code(code))
+    }
 }
 
 impl<'a> TransactionSource<'a> for LmdbEnvironment {
@@ -88,15 +312,155 @@ impl<'a> TransactionSource<'a> for LmdbEnvironment {
 
     type Handle = Database;
 
-    type ReadTransaction = RoTransaction<'a>;
+    type ReadTransaction = LmdbReadTransaction<'a>;
+
+    type ReadWriteTransaction = LmdbReadWriteTransaction<'a>;
+
+    fn create_read_txn(&'a self) -> Result<LmdbReadTransaction<'a>, Self::Error> {
+        let map_guard = self.map_lock.read_recursive();
+        let txn = self.env.begin_ro_txn()?;
+        Ok(LmdbReadTransaction {
+            txn,
+            _map_guard: map_guard,
+        })
+    }
+
+    fn create_read_write_txn(&'a self) -> Result<LmdbReadWriteTransaction<'a>, Self::Error> {
+        if !self.config.read_only {
+            self.grow_map_if_needed()?;
+        }
+        let map_guard = self.map_lock.read_recursive();
+        let txn = self.env.begin_rw_txn()?;
+        Ok(LmdbReadWriteTransaction {
+            txn,
+            _map_guard: map_guard,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lmdb::DatabaseFlags;
+    use tempfile::tempdir;
 
-    type ReadWriteTransaction = RwTransaction<'a>;
+    use super::*;
 
-    fn create_read_txn(&'a self) -> Result<RoTransaction<'a>, Self::Error> {
-        self.env.begin_ro_txn()
+    const PAGES: usize = 64;
+
+    fn page_size() -> usize {
+        engine_shared::os::get_page_size().unwrap()
+    }
+
+    fn fill(env: &LmdbEnvironment, db: Database, from: u32, count: u32) {
+        for i in from..from + count {
+            let mut txn = env.create_read_write_txn().unwrap();
+            txn.write(db, &i.to_le_bytes(), &[0xaa; 1024]).unwrap();
+            txn.commit().unwrap();
+        }
+    }
+
+    #[test]
+    fn should_grow_map_up_to_max_map_size() {
+        let dir = tempdir().unwrap();
+        let map_size = PAGES * page_size();
+        let config = LmdbConfig::new(map_size).with_max_map_size(4 * map_size);
+        let env = LmdbEnvironment::with_config(&dir.path().to_path_buf(), config).unwrap();
+        let db = env.env().create_db(None, DatabaseFlags::empty()).unwrap();
+
+        fill(&env, db, 0, 512);
+        assert_eq!(env.map_size().unwrap(), 4 * map_size);
+
+        let txn = env.create_read_txn().unwrap();
+        assert_eq!(
+            txn.read(db, &0u32.to_le_bytes()).unwrap(),
+            Some(vec![0xaa; 1024])
+        );
+        txn.commit().unwrap();
+    }
+
+    #[test]
+    fn should_not_grow_map_by_default() {
+        let dir = tempdir().unwrap();
+        let map_size = PAGES * page_size();
+        let env = LmdbEnvironment::new(&dir.path().to_path_buf(), map_size).unwrap();
+        let db = env.env().create_db(None, DatabaseFlags::empty()).unwrap();
+
+        fill(&env, db, 0, 32);
+        assert_eq!(env.map_size().unwrap(), map_size);
     }
 
-    fn create_read_write_txn(&'a self) -> Result<RwTransaction<'a>, Self::Error> {
-        self.env.begin_rw_txn()
+    #[test]
+    fn should_not_write_when_read_only() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().to_path_buf();
+        let map_size = PAGES * page_size();
+        {
+            let env = LmdbEnvironment::new(&path, map_size).unwrap();
+            let db = env.env().create_db(None, DatabaseFlags::empty()).unwrap();
+            fill(&env, db, 0, 1);
+        }
+
+        let config = LmdbConfig::new(map_size).with_read_only(true);
+        let env = LmdbEnvironment::with_config(&path, config).unwrap();
+        let db = env.env().open_db(None).unwrap();
+        let txn = env.create_read_txn().unwrap();
+        assert_eq!(
+            txn.read(db, &0u32.to_le_bytes()).unwrap(),
+            Some(vec![0xaa; 1024])
+        );
+        txn.commit().unwrap();
+        assert!(env.create_read_write_txn().is_err());
+    }
+
+    #[test]
+    fn should_toggle_deferred_sync() {
+        let dir = tempdir().unwrap();
+        let env = LmdbEnvironment::new(&dir.path().to_path_buf(), PAGES * page_size()).unwrap();
+        let db = env.env().create_db(None, DatabaseFlags::empty()).unwrap();
+        let is_deferred = |env: &LmdbEnvironment| {
+            let mut flags = 0;
+            let code = unsafe { lmdb_sys::mdb_env_get_flags(env.env.env(), &mut flags) };
+            lmdb_result(code).unwrap();
+            flags & lmdb_sys::MDB_NOSYNC != 0
+        };
+
+        assert!(!is_deferred(&env));
+        env.set_deferred_sync(true).unwrap();
+        assert!(is_deferred(&env));
+        fill(&env, db, 0, 8);
+        env.sync().unwrap();
+        env.set_deferred_sync(false).unwrap();
+        assert!(!is_deferred(&env));
+    }
+
+    #[test]
+    fn should_compact_into_smaller_copy() {
+        let dir = tempdir().unwrap();
+        let map_size = 4 * PAGES * page_size();
+        let config = LmdbConfig::new(map_size).with_sync_mode(LmdbSyncMode::NoSync);
+        let env = LmdbEnvironment::with_config(&dir.path().to_path_buf(), config).unwrap();
+        let db = env.env().create_db(None, DatabaseFlags::empty()).unwrap();
+
+        fill(&env, db, 0, 128);
+        let mut txn = env.create_read_write_txn().unwrap();
+        txn.txn.clear_db(db).unwrap();
+        txn.commit().unwrap();
+        fill(&env, db, 0, 1);
+
+        let copy_dir = tempdir().unwrap();
+        env.compact(copy_dir.path()).unwrap();
+
+        let data_size = |dir: &Path| dir.join("data.mdb").metadata().unwrap().len();
+        assert!(data_size(copy_dir.path()) < data_size(dir.path()));
+
+        let copy = LmdbEnvironment::new(&copy_dir.path().to_path_buf(), map_size).unwrap();
+        let copy_db = copy.env().open_db(None).unwrap();
+        let txn = copy.create_read_txn().unwrap();
+        assert_eq!(
+            txn.read(copy_db, &0u32.to_le_bytes()).unwrap(),
+            Some(vec![0xaa; 1024])
+        );
+        assert_eq!(txn.read(copy_db, &1u32.to_le_bytes()).unwrap(), None);
+        txn.commit().unwrap();
     }
 }
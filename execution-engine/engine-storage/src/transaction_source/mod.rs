@@ -1,5 +1,7 @@
 pub mod in_memory;
 pub mod lmdb;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
 
 /// A transaction which can be committed or aborted.
 pub trait Transaction: Sized {
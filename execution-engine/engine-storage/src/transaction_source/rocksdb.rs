@@ -0,0 +1,172 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use rocksdb::{ColumnFamily, Options, Snapshot, WriteBatch, WriteOptions, DB};
+
+use crate::{
+    error::{self, rocksdb::Error},
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+};
+
+type BytesMap = HashMap<Vec<u8>, Vec<u8>>;
+
+fn column_family<'a>(db: &'a DB, name: &str) -> Result<&'a ColumnFamily, Error> {
+    db.cf_handle(name)
+        .ok_or_else(|| Error::MissingColumnFamily(name.to_string()))
+}
+
+/// A read transaction on a [`RocksDbEnvironment`], reading from a consistent snapshot of the
+/// database.
+pub struct RocksDbReadTransaction<'a> {
+    db: &'a DB,
+    snapshot: Snapshot<'a>,
+}
+
+impl<'a> Transaction for RocksDbReadTransaction<'a> {
+    type Error = Error;
+
+    type Handle = String;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a> Readable for RocksDbReadTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let cf = column_family(self.db, &handle)?;
+        let maybe_value = self.snapshot.get_cf(cf, key)?;
+        Ok(maybe_value.map(|value| value.to_vec()))
+    }
+}
+
+/// A read-write transaction on a [`RocksDbEnvironment`].
+///
+/// Writes are buffered and applied atomically on commit.  Unlike LMDB, RocksDB doesn't serialize
+/// read-write transactions; since trie nodes are stored under their hashes, concurrent commits
+/// never write conflicting values.
+pub struct RocksDbReadWriteTransaction<'a> {
+    db: &'a DB,
+    pending: HashMap<String, BytesMap>,
+    sync: bool,
+}
+
+impl<'a> Transaction for RocksDbReadWriteTransaction<'a> {
+    type Error = Error;
+
+    type Handle = String;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        let mut batch = WriteBatch::default();
+        for (handle, values) in self.pending {
+            let cf = column_family(self.db, &handle)?;
+            for (key, value) in values {
+                batch.put_cf(cf, key, value)?;
+            }
+        }
+        let mut write_options = WriteOptions::default();
+        write_options.set_sync(self.sync);
+        self.db.write_opt(batch, &write_options).map_err(Into::into)
+    }
+}
+
+impl<'a> Readable for RocksDbReadWriteTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        if let Some(value) = self.pending.get(&handle).and_then(|values| values.get(key)) {
+            return Ok(Some(value.to_owned()));
+        }
+        let cf = column_family(self.db, &handle)?;
+        let maybe_value = self.db.get_cf(cf, key)?;
+        Ok(maybe_value.map(|value| value.to_vec()))
+    }
+}
+
+impl<'a> Writable for RocksDbReadWriteTransaction<'a> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        column_family(self.db, &handle)?;
+        self.pending
+            .entry(handle)
+            .or_default()
+            .insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+}
+
+/// The environment for a RocksDB-backed trie store.
+///
+/// Wraps [`rocksdb::DB`].
+pub struct RocksDbEnvironment {
+    path: PathBuf,
+    db: DB,
+    deferred_sync: AtomicBool,
+}
+
+impl RocksDbEnvironment {
+    /// Opens the database at `path`, creating it and any of `column_families` which don't exist
+    /// yet.
+    pub fn new<S: AsRef<str>>(path: &PathBuf, column_families: &[S]) -> Result<Self, error::Error> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        let db = DB::open_cf(&options, path, column_families.iter().map(AsRef::as_ref))?;
+        let path = path.to_owned();
+        let deferred_sync = AtomicBool::new(false);
+        Ok(RocksDbEnvironment {
+            path,
+            db,
+            deferred_sync,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn db(&self) -> &DB {
+        &self.db
+    }
+
+    /// Flushes every committed transaction to disk.
+    pub fn sync(&self) -> Result<(), error::Error> {
+        self.db.flush().map_err(Into::into)
+    }
+
+    /// Sets whether committing a read-write transaction returns before its write-ahead log entry
+    /// is flushed to disk, leaving that to [`sync`](RocksDbEnvironment::sync).
+    pub fn set_deferred_sync(&self, deferred: bool) {
+        self.deferred_sync.store(deferred, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the database has a column family called `name`.
+    pub fn has_column_family(&self, name: &str) -> bool {
+        self.db.cf_handle(name).is_some()
+    }
+}
+
+impl<'a> TransactionSource<'a> for RocksDbEnvironment {
+    type Error = Error;
+
+    type Handle = String;
+
+    type ReadTransaction = RocksDbReadTransaction<'a>;
+
+    type ReadWriteTransaction = RocksDbReadWriteTransaction<'a>;
+
+    fn create_read_txn(&'a self) -> Result<RocksDbReadTransaction<'a>, Self::Error> {
+        Ok(RocksDbReadTransaction {
+            db: &self.db,
+            snapshot: self.db.snapshot(),
+        })
+    }
+
+    fn create_read_write_txn(&'a self) -> Result<RocksDbReadWriteTransaction<'a>, Self::Error> {
+        Ok(RocksDbReadWriteTransaction {
+            db: &self.db,
+            pending: HashMap::new(),
+            sync: !self.deferred_sync.load(Ordering::SeqCst),
+        })
+    }
+}
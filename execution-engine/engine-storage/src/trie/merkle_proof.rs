@@ -0,0 +1,219 @@
+//! Merkle proofs of the presence of a single leaf in a [`Trie`].
+
+use std::collections::VecDeque;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+use crate::trie::{Pointer, Trie};
+
+/// One level of the path from a root to a proven leaf, holding everything in that level's trie
+/// other than the pointer to the level below.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieMerkleProofStep {
+    Node {
+        hole_index: u8,
+        indexed_pointers_with_hole: Vec<(u8, Pointer)>,
+    },
+    Extension {
+        affix: Vec<u8>,
+    },
+}
+
+impl TrieMerkleProofStep {
+    fn tag(&self) -> u8 {
+        match self {
+            TrieMerkleProofStep::Node { .. } => 0,
+            TrieMerkleProofStep::Extension { .. } => 1,
+        }
+    }
+}
+
+impl ToBytes for TrieMerkleProofStep {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.push(self.tag());
+        match self {
+            TrieMerkleProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                ret.push(*hole_index);
+                ret.append(&mut (indexed_pointers_with_hole.len() as u32).to_bytes()?);
+                for (index, pointer) in indexed_pointers_with_hole {
+                    ret.push(*index);
+                    ret.append(&mut pointer.to_bytes()?);
+                }
+            }
+            TrieMerkleProofStep::Extension { affix } => {
+                ret.append(&mut affix.to_bytes()?);
+            }
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        bytesrepr::U8_SERIALIZED_LENGTH
+            + match self {
+                TrieMerkleProofStep::Node {
+                    indexed_pointers_with_hole,
+                    ..
+                } => {
+                    bytesrepr::U8_SERIALIZED_LENGTH
+                        + bytesrepr::U32_SERIALIZED_LENGTH
+                        + indexed_pointers_with_hole
+                            .iter()
+                            .map(|(_, pointer)| {
+                                bytesrepr::U8_SERIALIZED_LENGTH + pointer.serialized_length()
+                            })
+                            .sum::<usize>()
+                }
+                TrieMerkleProofStep::Extension { affix } => affix.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for TrieMerkleProofStep {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            0 => {
+                let (hole_index, rem) = u8::from_bytes(rem)?;
+                let (count, mut rem) = u32::from_bytes(rem)?;
+                let mut indexed_pointers_with_hole = Vec::new();
+                for _ in 0..count {
+                    let (index, next) = u8::from_bytes(rem)?;
+                    let (pointer, next) = Pointer::from_bytes(next)?;
+                    indexed_pointers_with_hole.push((index, pointer));
+                    rem = next;
+                }
+                Ok((
+                    TrieMerkleProofStep::Node {
+                        hole_index,
+                        indexed_pointers_with_hole,
+                    },
+                    rem,
+                ))
+            }
+            1 => {
+                let (affix, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((TrieMerkleProofStep::Extension { affix }, rem))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A proof that `key` maps to `value` in the trie with a given root hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProof<K, V> {
+    key: K,
+    value: V,
+    /// Ordered from the level just above the leaf up to the root.
+    proof_steps: VecDeque<TrieMerkleProofStep>,
+}
+
+impl<K, V> TrieMerkleProof<K, V> {
+    pub fn new(key: K, value: V, proof_steps: VecDeque<TrieMerkleProofStep>) -> Self {
+        TrieMerkleProof {
+            key,
+            value,
+            proof_steps,
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    pub fn proof_steps(&self) -> &VecDeque<TrieMerkleProofStep> {
+        &self.proof_steps
+    }
+
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<K, V> TrieMerkleProof<K, V>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    /// Recomputes the root hash of the trie the proof was taken from.  The proof is valid for a
+    /// given root if and only if this returns that root.
+    pub fn compute_state_hash(&self) -> Result<Blake2bHash, bytesrepr::Error> {
+        let leaf: Trie<K, V> = Trie::leaf(self.key.clone(), self.value.clone());
+        let mut hash = Blake2bHash::new(&leaf.to_bytes()?);
+        let mut child_is_leaf = true;
+
+        for step in &self.proof_steps {
+            let pointer = if child_is_leaf {
+                Pointer::LeafPointer(hash)
+            } else {
+                Pointer::NodePointer(hash)
+            };
+            let parent: Trie<K, V> = match step {
+                TrieMerkleProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                } => {
+                    let mut indexed_pointers: Vec<(usize, Pointer)> = indexed_pointers_with_hole
+                        .iter()
+                        .map(|(index, pointer)| (usize::from(*index), *pointer))
+                        .collect();
+                    indexed_pointers.push((usize::from(*hole_index), pointer));
+                    Trie::node(&indexed_pointers)
+                }
+                TrieMerkleProofStep::Extension { affix } => Trie::extension(affix.clone(), pointer),
+            };
+            hash = Blake2bHash::new(&parent.to_bytes()?);
+            child_is_leaf = false;
+        }
+
+        Ok(hash)
+    }
+}
+
+impl<K: ToBytes, V: ToBytes> ToBytes for TrieMerkleProof<K, V> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.append(&mut self.key.to_bytes()?);
+        ret.append(&mut self.value.to_bytes()?);
+        ret.append(&mut (self.proof_steps.len() as u32).to_bytes()?);
+        for step in &self.proof_steps {
+            ret.append(&mut step.to_bytes()?);
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.value.serialized_length()
+            + bytesrepr::U32_SERIALIZED_LENGTH
+            + self
+                .proof_steps
+                .iter()
+                .map(ToBytes::serialized_length)
+                .sum::<usize>()
+    }
+}
+
+impl<K: FromBytes, V: FromBytes> FromBytes for TrieMerkleProof<K, V> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem) = K::from_bytes(bytes)?;
+        let (value, rem) = V::from_bytes(rem)?;
+        let (count, mut rem) = u32::from_bytes(rem)?;
+        let mut proof_steps = VecDeque::new();
+        for _ in 0..count {
+            let (step, next) = TrieMerkleProofStep::from_bytes(rem)?;
+            proof_steps.push_back(step);
+            rem = next;
+        }
+        Ok((TrieMerkleProof::new(key, value, proof_steps), rem))
+    }
+}
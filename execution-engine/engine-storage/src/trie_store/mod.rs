@@ -5,6 +5,8 @@
 pub mod in_memory;
 pub mod lmdb;
 pub(crate) mod operations;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
 #[cfg(test)]
 mod tests;
 
@@ -12,7 +14,7 @@ use engine_shared::newtypes::Blake2bHash;
 
 use crate::{store::Store, trie::Trie};
 
-const NAME: &str = "TRIE_STORE";
+pub(crate) const NAME: &str = "TRIE_STORE";
 
 /// An entity which persists [`Trie`] values at their hashes.
 pub trait TrieStore<K, V>: Store<Blake2bHash, Trie<K, V>> {}
@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod tests;
 
-use std::{cmp, collections::VecDeque, mem, time::Instant};
+use std::{
+    cmp,
+    collections::{HashSet, VecDeque},
+    mem,
+    time::Instant,
+};
 
 use engine_shared::{
     logging::{log_duration, log_metric},
@@ -11,7 +16,11 @@ use types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
     transaction_source::{Readable, Writable},
-    trie::{self, Parents, Pointer, Trie, RADIX},
+    trie::{
+        self,
+        merkle_proof::{TrieMerkleProof, TrieMerkleProofStep},
+        Parents, Pointer, Trie, RADIX,
+    },
     trie_store::TrieStore,
     GAUGE_METRIC_KEY,
 };
@@ -22,11 +31,15 @@ const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+const TRIE_STORE_WRITE_REUSED: &str = "trie_store_write_reused";
+const TRIE_STORE_DELETE_DURATION: &str = "trie_store_delete_duration";
+const TRIE_STORE_DELETE_PUTS: &str = "trie_store_delete_puts";
 const READ: &str = "read";
 const GET: &str = "get";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
 const PUT: &str = "put";
+const DELETE: &str = "delete";
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ReadResult<V> {
@@ -199,6 +212,90 @@ where
     }
 }
 
+/// Returns a value from the corresponding key at a given root in a given store, along with a
+/// proof that the value is present under that root.
+pub fn read_with_proof<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ReadResult<TrieMerkleProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let path: Vec<u8> = key.to_bytes()?;
+
+    let mut depth: usize = 0;
+    let mut proof_steps = VecDeque::new();
+    let mut current: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ReadResult::RootNotFound),
+    };
+
+    loop {
+        let next_pointer = match current {
+            Trie::Leaf {
+                key: leaf_key,
+                value: leaf_value,
+            } => {
+                if *key != leaf_key {
+                    return Ok(ReadResult::NotFound);
+                }
+                return Ok(ReadResult::Found(TrieMerkleProof::new(
+                    leaf_key,
+                    leaf_value,
+                    proof_steps,
+                )));
+            }
+            Trie::Node { pointer_block } => {
+                assert!(depth < path.len(), "depth must be < {}", path.len());
+                let hole_index = path[depth];
+                let pointer = match pointer_block[usize::from(hole_index)] {
+                    Some(pointer) => pointer,
+                    None => return Ok(ReadResult::NotFound),
+                };
+                let indexed_pointers_with_hole = pointer_block[..]
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| *index != usize::from(hole_index))
+                    .filter_map(|(index, maybe_pointer)| {
+                        maybe_pointer.map(|pointer| (index as u8, pointer))
+                    })
+                    .collect();
+                proof_steps.push_front(TrieMerkleProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                });
+                depth += 1;
+                pointer
+            }
+            Trie::Extension { affix, pointer } => {
+                if path.len() < depth + affix.len() || path[depth..depth + affix.len()] != affix[..]
+                {
+                    return Ok(ReadResult::NotFound);
+                }
+                depth += affix.len();
+                proof_steps.push_front(TrieMerkleProofStep::Extension { affix });
+                pointer
+            }
+        };
+        current = match store.get(txn, next_pointer.hash())? {
+            Some(next) => next,
+            None => panic!(
+                "No trie value at key: {:?} (reading from key: {:?})",
+                next_pointer.hash(),
+                key
+            ),
+        };
+    }
+}
+
 struct TrieScan<K, V> {
     tip: Trie<K, V>,
     parents: Parents<K, V>,
@@ -618,6 +715,26 @@ pub enum WriteResult {
     RootNotFound,
 }
 
+/// Counts of the trie nodes produced by one or more writes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WriteStats {
+    /// Nodes which were not yet in the store and had to be put.
+    pub puts: u32,
+    /// Nodes which were already in the store, i.e. subtrees shared with some existing root.
+    pub reused: u32,
+    /// Serialized size in bytes of the nodes which were put.
+    pub bytes_put: u64,
+}
+
+impl WriteStats {
+    /// Adds the counts of `other` to `self`.
+    pub fn accumulate(&mut self, other: WriteStats) {
+        self.puts += other.puts;
+        self.reused += other.reused;
+        self.bytes_put += other.bytes_put;
+    }
+}
+
 pub fn write<K, V, T, S, E>(
     correlation_id: CorrelationId,
     txn: &mut T,
@@ -626,6 +743,32 @@ pub fn write<K, V, T, S, E>(
     key: &K,
     value: &V,
 ) -> Result<WriteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    write_with_stats::<K, V, T, S, E>(correlation_id, txn, store, root, key, value)
+        .map(|(write_result, _stats)| write_result)
+}
+
+/// Like [`write`], but also returns how many of the rehashed nodes had to be put and how many were
+/// already present in the store.
+///
+/// Since nodes are content-addressed, a node whose hash is already in the store is not put again.
+/// Its stored form must then be identical to the new node; a mismatch would mean the encoding of
+/// tries is not deterministic.
+pub fn write_with_stats<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+    value: &V,
+) -> Result<(WriteResult, WriteStats), E>
 where
     K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
     V: ToBytes + FromBytes + Clone + Eq,
@@ -635,10 +778,10 @@ where
     E: From<S::Error> + From<types::bytesrepr::Error>,
 {
     let start = Instant::now();
-    let mut put_counter: i32 = 0;
+    let mut stats = WriteStats::default();
 
     match store.get(txn, root)? {
-        None => Ok(WriteResult::RootNotFound),
+        None => Ok((WriteResult::RootNotFound, stats)),
         Some(current_root) => {
             let new_leaf = Trie::Leaf {
                 key: key.to_owned(),
@@ -705,12 +848,25 @@ where
                     WRITE,
                     start.elapsed(),
                 );
-                return Ok(WriteResult::AlreadyExists);
+                return Ok((WriteResult::AlreadyExists, stats));
             }
             let mut root_hash = root.to_owned();
             for (hash, element) in new_elements.iter() {
-                put_counter += 1;
-                store.put(txn, hash, element)?;
+                match store.get(txn, hash)? {
+                    Some(existing) => {
+                        assert!(
+                            existing == *element,
+                            "trie node {} is already stored with a different encoding",
+                            hash
+                        );
+                        stats.reused += 1;
+                    }
+                    None => {
+                        store.put(txn, hash, element)?;
+                        stats.puts += 1;
+                        stats.bytes_put += element.serialized_length() as u64;
+                    }
+                }
                 root_hash = *hash;
             }
             log_metric(
@@ -718,7 +874,14 @@ where
                 TRIE_STORE_WRITE_PUTS,
                 PUT,
                 GAUGE_METRIC_KEY,
-                f64::from(put_counter),
+                f64::from(stats.puts),
+            );
+            log_metric(
+                correlation_id,
+                TRIE_STORE_WRITE_REUSED,
+                PUT,
+                GAUGE_METRIC_KEY,
+                f64::from(stats.reused),
             );
             log_duration(
                 correlation_id,
@@ -726,9 +889,153 @@ where
                 WRITE,
                 start.elapsed(),
             );
-            Ok(WriteResult::Written(root_hash))
+            Ok((WriteResult::Written(root_hash), stats))
+        }
+    }
+}
+
+/// Takes the node which held a deleted leaf (with the leaf's pointer already removed) and the
+/// parents of that node, and returns the trie which should replace the node along with the parents
+/// of that replacement.
+///
+/// The root node is kept however many children it has left.  Any other node is left with at least
+/// one child, since nodes only exist where paths diverge.  If only one child remains, the node is
+/// collapsed: a remaining leaf moves up to the nearest ancestor node, while a remaining node or
+/// extension is joined to any extension directly above the collapsed node to form a single
+/// extension.
+#[allow(clippy::type_complexity)]
+fn collapse_node<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    pointer_block: Box<trie::PointerBlock>,
+    mut parents: Parents<K, V>,
+) -> Result<(Trie<K, V>, Parents<K, V>), E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error>,
+{
+    let remaining: Vec<(usize, Pointer)> = (0..RADIX)
+        .filter_map(|index| pointer_block[index].map(|pointer| (index, pointer)))
+        .collect();
+    if parents.is_empty() || remaining.len() > 1 {
+        return Ok((Trie::Node { pointer_block }, parents));
+    }
+    let (sibling_index, sibling_pointer) = match remaining.as_slice() {
+        [indexed_pointer] => *indexed_pointer,
+        _ => panic!("a non-root node should have at least two children"),
+    };
+    // An extension directly above the collapsed node is absorbed into its replacement.
+    let mut affix = match parents.pop() {
+        Some((_, Trie::Extension { affix, .. })) => affix,
+        Some(parent) => {
+            parents.push(parent);
+            Vec::new()
+        }
+        None => unreachable!(),
+    };
+    affix.push(sibling_index as u8);
+    let sibling = match store.get(txn, sibling_pointer.hash())? {
+        Some(sibling) => sibling,
+        None => panic!("No trie value at key: {:?}", sibling_pointer.hash()),
+    };
+    let replacement = match sibling {
+        leaf @ Trie::Leaf { .. } => leaf,
+        Trie::Node { .. } => Trie::extension(affix, sibling_pointer),
+        Trie::Extension {
+            affix: sibling_affix,
+            pointer,
+        } => {
+            affix.extend(sibling_affix);
+            Trie::extension(affix, pointer)
+        }
+    };
+    Ok((replacement, parents))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteResult {
+    Deleted(Blake2bHash),
+    DoesNotExist,
+    RootNotFound,
+}
+
+/// Removes the leaf under `key` from the trie at `root`, returning the root hash of the resulting
+/// trie.
+///
+/// The resulting trie has the same shape (and hence root hash) as a trie in which `key` was never
+/// written.
+pub fn delete<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut put_counter: i32 = 0;
+
+    let current_root = match store.get(txn, root)? {
+        Some(current_root) => current_root,
+        None => return Ok(DeleteResult::RootNotFound),
+    };
+    let path: Vec<u8> = key.to_bytes()?;
+    let TrieScan { tip, mut parents } =
+        scan::<K, V, T, S, E>(correlation_id, txn, store, &path, &current_root)?;
+    match tip {
+        Trie::Leaf {
+            key: ref leaf_key, ..
+        } if key == leaf_key => (),
+        _ => {
+            log_duration(
+                correlation_id,
+                TRIE_STORE_DELETE_DURATION,
+                DELETE,
+                start.elapsed(),
+            );
+            return Ok(DeleteResult::DoesNotExist);
         }
     }
+    let (leaf_index, parent) = parents.pop().expect("parents should not be empty");
+    let mut pointer_block = match parent {
+        Trie::Node { pointer_block } => pointer_block,
+        _ => panic!("A leaf should have a node for its parent"),
+    };
+    pointer_block[leaf_index.into()] = None;
+    let (new_tip, parents) = collapse_node::<K, V, T, S, E>(txn, store, pointer_block, parents)?;
+    let new_elements = rehash(new_tip, parents)?;
+
+    let mut root_hash = root.to_owned();
+    for (hash, element) in new_elements.iter() {
+        put_counter += 1;
+        store.put(txn, hash, element)?;
+        root_hash = *hash;
+    }
+    log_metric(
+        correlation_id,
+        TRIE_STORE_DELETE_PUTS,
+        PUT,
+        GAUGE_METRIC_KEY,
+        f64::from(put_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DELETE_DURATION,
+        DELETE,
+        start.elapsed(),
+    );
+    Ok(DeleteResult::Deleted(root_hash))
 }
 
 enum KeysIteratorState<K, V, S: TrieStore<K, V>> {
@@ -902,7 +1209,6 @@ where
 /// Returns the iterator over the keys in the subtrie matching `prefix`.
 ///
 /// The root should be the apex of the trie.
-#[allow(dead_code)]
 pub fn keys_with_prefix<'a, 'b, K, V, T, S>(
     _correlation_id: CorrelationId,
     txn: &'b T,
@@ -938,3 +1244,286 @@ where
         state: init_state,
     }
 }
+
+/// Returns the hashes of all tries reachable from `trie_keys` which are either absent from `store`
+/// or which don't hash to the key they are stored under.
+///
+/// An empty result means that every trie reachable from the given keys is present and intact.
+pub fn missing_trie_keys<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    mut trie_keys: Vec<Blake2bHash>,
+) -> Result<Vec<Blake2bHash>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut missing_descendants = Vec::new();
+    let mut visited = HashSet::new();
+    while let Some(trie_key) = trie_keys.pop() {
+        if !visited.insert(trie_key) {
+            continue;
+        }
+        let trie: Trie<K, V> = match store.get(txn, &trie_key)? {
+            Some(trie) => trie,
+            None => {
+                missing_descendants.push(trie_key);
+                continue;
+            }
+        };
+        if Blake2bHash::new(&trie.to_bytes()?) != trie_key {
+            missing_descendants.push(trie_key);
+            continue;
+        }
+        match trie {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => trie_keys.extend(
+                (0..RADIX)
+                    .filter_map(|index| pointer_block[index])
+                    .map(|pointer| *pointer.hash()),
+            ),
+            Trie::Extension { pointer, .. } => trie_keys.push(*pointer.hash()),
+        }
+    }
+    Ok(missing_descendants)
+}
+
+/// A problem found by [`verify`] in a trie, along with the path of key bytes leading to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieCorruption {
+    /// Nothing is stored under a trie key which is pointed to.
+    Missing {
+        trie_key: Blake2bHash,
+        path: Vec<u8>,
+    },
+    /// The bytes stored under a trie key don't hash to that key.
+    HashMismatch {
+        trie_key: Blake2bHash,
+        actual_hash: Blake2bHash,
+        path: Vec<u8>,
+    },
+    /// The bytes stored under a trie key, including a leaf's key and value, can't be deserialized.
+    Undeserializable {
+        trie_key: Blake2bHash,
+        path: Vec<u8>,
+        error: bytesrepr::Error,
+    },
+    /// A leaf pointer points to a node or extension, or a node pointer points to a leaf, or an
+    /// extension points to anything other than a node.
+    PointerKindMismatch {
+        trie_key: Blake2bHash,
+        path: Vec<u8>,
+    },
+    /// The key of a leaf doesn't start with the path leading to it, so reads can't find it.
+    KeyPathMismatch {
+        trie_key: Blake2bHash,
+        path: Vec<u8>,
+    },
+}
+
+/// The outcome of verifying the trie under a given root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieVerificationReport {
+    pub root: Blake2bHash,
+    /// The number of tries, including leaves, which were found and checked.
+    pub tries_checked: usize,
+    /// The number of intact leaves found.
+    pub leaves_checked: usize,
+    pub corruptions: Vec<TrieCorruption>,
+}
+
+impl TrieVerificationReport {
+    /// Returns `true` if no corruption was found.
+    pub fn is_intact(&self) -> bool {
+        self.corruptions.is_empty()
+    }
+}
+
+/// The outcome of repairing the trie under a given root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieRepairReport {
+    /// The corruption found while salvaging leaves.
+    pub verification: TrieVerificationReport,
+    /// The root of a trie holding all the salvaged leaves.  It equals the original root if that
+    /// trie was intact.
+    pub repaired_root: Blake2bHash,
+}
+
+/// What a pointer requires the trie it points to to be.
+#[derive(Clone, Copy)]
+enum ExpectedTrie {
+    Any,
+    Leaf,
+    Branch,
+    Node,
+}
+
+/// Walks the trie under `root`, checking every trie it reaches and passing each intact leaf to
+/// `on_leaf`.
+///
+/// Subtries under a corrupted trie are unreachable and so aren't checked.
+fn walk<K, V, T, S, E, F>(
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    mut on_leaf: F,
+) -> Result<TrieVerificationReport, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+    F: FnMut(K, V),
+{
+    let mut report = TrieVerificationReport {
+        root: *root,
+        tries_checked: 0,
+        leaves_checked: 0,
+        corruptions: Vec::new(),
+    };
+    let mut to_check = vec![(*root, Vec::new(), ExpectedTrie::Any)];
+    while let Some((trie_key, path, expected)) = to_check.pop() {
+        let trie_bytes = match txn
+            .read(store.handle(), &trie_key.to_bytes()?)
+            .map_err(S::Error::from)?
+        {
+            Some(trie_bytes) => trie_bytes,
+            None => {
+                report
+                    .corruptions
+                    .push(TrieCorruption::Missing { trie_key, path });
+                continue;
+            }
+        };
+        report.tries_checked += 1;
+        let actual_hash = Blake2bHash::new(&trie_bytes);
+        if actual_hash != trie_key {
+            report.corruptions.push(TrieCorruption::HashMismatch {
+                trie_key,
+                actual_hash,
+                path,
+            });
+            continue;
+        }
+        let trie: Trie<K, V> = match bytesrepr::deserialize(trie_bytes) {
+            Ok(trie) => trie,
+            Err(error) => {
+                report.corruptions.push(TrieCorruption::Undeserializable {
+                    trie_key,
+                    path,
+                    error,
+                });
+                continue;
+            }
+        };
+        let kind_matches = match (expected, &trie) {
+            (ExpectedTrie::Any, _) => true,
+            (ExpectedTrie::Leaf, Trie::Leaf { .. }) => true,
+            (ExpectedTrie::Branch, Trie::Node { .. })
+            | (ExpectedTrie::Branch, Trie::Extension { .. }) => true,
+            (ExpectedTrie::Node, Trie::Node { .. }) => true,
+            _ => false,
+        };
+        if !kind_matches {
+            report
+                .corruptions
+                .push(TrieCorruption::PointerKindMismatch { trie_key, path });
+            continue;
+        }
+        match trie {
+            Trie::Leaf { key, value } => {
+                if !key.to_bytes()?.starts_with(&path) {
+                    report
+                        .corruptions
+                        .push(TrieCorruption::KeyPathMismatch { trie_key, path });
+                    continue;
+                }
+                report.leaves_checked += 1;
+                on_leaf(key, value);
+            }
+            Trie::Node { pointer_block } => {
+                for index in 0..RADIX {
+                    let pointer = match pointer_block[index] {
+                        Some(pointer) => pointer,
+                        None => continue,
+                    };
+                    let mut child_path = path.clone();
+                    child_path.push(index as u8);
+                    let child_expected = match pointer {
+                        Pointer::LeafPointer(_) => ExpectedTrie::Leaf,
+                        Pointer::NodePointer(_) => ExpectedTrie::Branch,
+                    };
+                    to_check.push((*pointer.hash(), child_path, child_expected));
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                let mut child_path = path;
+                child_path.extend_from_slice(&affix);
+                to_check.push((*pointer.hash(), child_path, ExpectedTrie::Node));
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Walks the trie under `root` checking that every trie is present, hashes to its key,
+/// deserializes, and is of the kind its pointer claims, and that every leaf sits on the path of
+/// its key.
+pub fn verify<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+) -> Result<TrieVerificationReport, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    walk::<K, V, T, S, E, _>(txn, store, root, |_, _| ())
+}
+
+/// Verifies the trie under `root` like [`verify`], and writes every intact leaf found into a new
+/// trie, rederiving all the branches which can be salvaged.
+pub fn repair<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+) -> Result<TrieRepairReport, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut leaves = Vec::new();
+    let verification =
+        walk::<K, V, T, S, E, _>(txn, store, root, |key, value| leaves.push((key, value)))?;
+
+    let (mut repaired_root, empty_root) = trie::operations::create_hashed_empty_trie::<K, V>()?;
+    store.put(txn, &repaired_root, &empty_root)?;
+    for (key, value) in leaves {
+        match write::<K, V, T, S, E>(correlation_id, txn, store, &repaired_root, &key, &value)? {
+            WriteResult::Written(root_hash) => repaired_root = root_hash,
+            WriteResult::AlreadyExists => (),
+            WriteResult::RootNotFound => panic!("repaired trie has invalid root"),
+        }
+    }
+    Ok(TrieRepairReport {
+        verification,
+        repaired_root,
+    })
+}
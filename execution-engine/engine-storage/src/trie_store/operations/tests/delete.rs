@@ -0,0 +1,178 @@
+use super::*;
+use crate::trie_store::operations::{delete, DeleteResult};
+
+fn delete_leaf<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let delete_result = delete::<K, V, _, S, E>(correlation_id, &mut txn, store, root, key)?;
+    txn.commit()?;
+    Ok(delete_result)
+}
+
+/// Deletes the leaf at `index` from the trie at `root` holding `leaves`, and checks that the result
+/// is the same trie as one built by writing the remaining leaves to the empty trie.
+fn delete_from_n_leaf_trie_had_expected_results<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    empty_root: &Blake2bHash,
+    leaves: &[Trie<K, V>],
+    index: usize,
+) -> Result<(), E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Ord,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + Copy,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let deleted = &leaves[index..=index];
+    let remaining: Vec<Trie<K, V>> = leaves[..index]
+        .iter()
+        .chain(leaves[index + 1..].iter())
+        .cloned()
+        .collect();
+
+    let expected_root =
+        write_leaves::<_, _, _, _, E>(correlation_id, environment, store, empty_root, &remaining)?
+            .into_iter()
+            .fold(*empty_root, |root, result| match result {
+                WriteResult::Written(root_hash) => root_hash,
+                _ => root,
+            });
+
+    let key = deleted[0].key().expect("should be a leaf");
+    let delete_result =
+        delete_leaf::<_, _, _, _, E>(correlation_id, environment, store, root, key)?;
+    assert_eq!(delete_result, DeleteResult::Deleted(expected_root));
+
+    check_leaves::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        &expected_root,
+        &remaining,
+        deleted,
+    )
+}
+
+#[test]
+fn lmdb_delete_from_n_leaf_trie_had_expected_results() {
+    for num_leaves in 1..=TEST_LEAVES_LENGTH {
+        for index in 0..num_leaves {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = TEST_TRIE_GENERATORS[num_leaves]().unwrap();
+            let (empty_root_hash, empty_tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+            context.update(&empty_tries).unwrap();
+
+            delete_from_n_leaf_trie_had_expected_results::<_, _, _, _, error::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+                &empty_root_hash,
+                &TEST_LEAVES[..num_leaves],
+                index,
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[test]
+fn in_memory_delete_from_n_leaf_trie_had_expected_results() {
+    for num_leaves in 1..=TEST_LEAVES_LENGTH {
+        for index in 0..num_leaves {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = TEST_TRIE_GENERATORS[num_leaves]().unwrap();
+            let (empty_root_hash, empty_tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+            let context = InMemoryTestContext::new(&tries).unwrap();
+            context.update(&empty_tries).unwrap();
+
+            delete_from_n_leaf_trie_had_expected_results::<_, _, _, _, in_memory::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+                &empty_root_hash,
+                &TEST_LEAVES[..num_leaves],
+                index,
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[test]
+fn lmdb_delete_of_absent_key_does_not_exist() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[3]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    for leaf in &TEST_LEAVES[3..] {
+        let key = leaf.key().unwrap();
+        let delete_result = delete_leaf::<TestKey, TestValue, _, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            key,
+        )
+        .unwrap();
+        assert_eq!(delete_result, DeleteResult::DoesNotExist);
+    }
+}
+
+#[test]
+fn in_memory_delete_of_absent_key_does_not_exist() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[3]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    for leaf in &TEST_LEAVES[3..] {
+        let key = leaf.key().unwrap();
+        let delete_result = delete_leaf::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            key,
+        )
+        .unwrap();
+        assert_eq!(delete_result, DeleteResult::DoesNotExist);
+    }
+}
+
+#[test]
+fn in_memory_delete_from_missing_root_is_root_not_found() {
+    let correlation_id = CorrelationId::new();
+    let (_, tries) = TEST_TRIE_GENERATORS[1]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let missing_root_hash = Blake2bHash::new(&[1u8; 32]);
+
+    let delete_result = delete_leaf::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &missing_root_hash,
+        TEST_LEAVES[0].key().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(delete_result, DeleteResult::RootNotFound);
+}
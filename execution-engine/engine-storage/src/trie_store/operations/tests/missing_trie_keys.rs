@@ -0,0 +1,79 @@
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+
+use crate::{
+    error::in_memory,
+    transaction_source::{Transaction, TransactionSource},
+    trie_store::operations::{
+        self,
+        tests::{
+            create_6_leaf_trie, HashedTestTrie, HashedTrie, InMemoryTestContext, TestKey,
+            TestValue, TEST_LEAVES, TEST_TRIE_GENERATORS,
+        },
+    },
+};
+
+fn missing_trie_keys(context: &InMemoryTestContext, root_hash: Blake2bHash) -> Vec<Blake2bHash> {
+    let correlation_id = CorrelationId::new();
+    let txn = context.environment.create_read_txn().unwrap();
+    let missing = operations::missing_trie_keys::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        vec![root_hash],
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    missing
+}
+
+#[test]
+fn should_find_no_missing_keys_in_complete_tries() {
+    for generator in TEST_TRIE_GENERATORS.iter() {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        assert!(missing_trie_keys(&context, root_hash).is_empty());
+    }
+}
+
+#[test]
+fn should_find_missing_leaf() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let leaf = HashedTrie::new(TEST_LEAVES[4].clone()).unwrap();
+    let incomplete_tries: Vec<HashedTestTrie> = tries
+        .into_iter()
+        .filter(|hashed_trie| hashed_trie.hash != leaf.hash)
+        .collect();
+    let context = InMemoryTestContext::new(&incomplete_tries).unwrap();
+
+    assert_eq!(missing_trie_keys(&context, root_hash), vec![leaf.hash]);
+}
+
+#[test]
+fn should_find_missing_root() {
+    let (root_hash, _tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new::<TestKey, TestValue>(&[]).unwrap();
+
+    assert_eq!(missing_trie_keys(&context, root_hash), vec![root_hash]);
+}
+
+#[test]
+fn should_find_corrupted_leaf() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let leaf = HashedTrie::new(TEST_LEAVES[4].clone()).unwrap();
+    let corrupted_tries: Vec<HashedTestTrie> = tries
+        .into_iter()
+        .map(|hashed_trie| {
+            if hashed_trie.hash == leaf.hash {
+                HashedTrie {
+                    hash: leaf.hash,
+                    trie: TEST_LEAVES[5].clone(),
+                }
+            } else {
+                hashed_trie
+            }
+        })
+        .collect();
+    let context = InMemoryTestContext::new(&corrupted_tries).unwrap();
+
+    assert_eq!(missing_trie_keys(&context, root_hash), vec![leaf.hash]);
+}
@@ -1,8 +1,12 @@
+mod delete;
 mod ee_699;
 mod keys;
+mod missing_trie_keys;
 mod proptests;
 mod read;
+mod read_with_proof;
 mod scan;
+mod verify;
 mod write;
 
 use std::{collections::HashMap, convert};
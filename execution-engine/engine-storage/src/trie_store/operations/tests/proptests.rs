@@ -1,4 +1,4 @@
-use std::ops::RangeInclusive;
+use std::{collections::BTreeMap, ops::RangeInclusive};
 
 use proptest::{
     array,
@@ -7,6 +7,7 @@ use proptest::{
 };
 
 use super::*;
+use crate::trie_store::operations::{delete, DeleteResult};
 
 const DEFAULT_MIN_LENGTH: usize = 0;
 
@@ -76,10 +77,81 @@ fn in_memory_roundtrip_succeeds(pairs: &[(TestKey, TestValue)]) -> bool {
     .unwrap()
 }
 
+/// Writes all `inputs`, then deletes those flagged for deletion, and checks that the result is the
+/// same trie as one built by writing only the remaining inputs.
+fn delete_matches_write_of_remaining<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+    inputs: &[(TestKey, TestValue, bool)],
+) -> Result<bool, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let correlation_id = CorrelationId::new();
+    let entries: BTreeMap<TestKey, (TestValue, bool)> = inputs
+        .iter()
+        .map(|(key, value, to_delete)| (*key, (*value, *to_delete)))
+        .collect();
+    let all_pairs: Vec<(TestKey, TestValue)> = entries
+        .iter()
+        .map(|(key, (value, _))| (*key, *value))
+        .collect();
+    let remaining_pairs: Vec<(TestKey, TestValue)> = entries
+        .iter()
+        .filter(|(_, (_, to_delete))| !to_delete)
+        .map(|(key, (value, _))| (*key, *value))
+        .collect();
+
+    let last_root = |root_hashes: Vec<Blake2bHash>| {
+        root_hashes
+            .last()
+            .cloned()
+            .unwrap_or_else(|| *empty_root_hash)
+    };
+    let full_root_hash = last_root(write_pairs::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        &all_pairs,
+    )?);
+    let expected_root_hash = last_root(write_pairs::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        &remaining_pairs,
+    )?);
+
+    let mut root_hash = full_root_hash;
+    let mut txn = environment.create_read_write_txn()?;
+    for (key, (_, to_delete)) in entries.iter() {
+        if !to_delete {
+            continue;
+        }
+        match delete::<_, _, _, _, E>(correlation_id, &mut txn, store, &root_hash, key)? {
+            DeleteResult::Deleted(new_root_hash) => root_hash = new_root_hash,
+            other => panic!("unexpected delete result {:?}", other),
+        }
+    }
+    txn.commit()?;
+
+    Ok(root_hash == expected_root_hash)
+}
+
 fn test_key_arb() -> impl Strategy<Value = TestKey> {
     array::uniform7(any::<u8>()).prop_map(TestKey)
 }
 
+/// Keys drawn from a small alphabet, so that they often share prefixes.
+fn colliding_test_key_arb() -> impl Strategy<Value = TestKey> {
+    array::uniform7(0u8..3).prop_map(TestKey)
+}
+
 fn test_value_arb() -> impl Strategy<Value = TestValue> {
     array::uniform6(any::<u8>()).prop_map(TestValue)
 }
@@ -94,4 +166,34 @@ proptest! {
     fn prop_lmdb_roundtrip_succeeds(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
         assert!(lmdb_roundtrip_succeeds(&inputs));
     }
+
+    #[test]
+    fn prop_in_memory_delete_matches_write_of_remaining(
+        inputs in vec((colliding_test_key_arb(), test_value_arb(), any::<bool>()), get_range())
+    ) {
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        assert!(delete_matches_write_of_remaining::<_, _, in_memory::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &inputs,
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn prop_lmdb_delete_matches_write_of_remaining(
+        inputs in vec((colliding_test_key_arb(), test_value_arb(), any::<bool>()), get_range())
+    ) {
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        assert!(delete_matches_write_of_remaining::<_, _, error::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &inputs,
+        )
+        .unwrap());
+    }
 }
@@ -0,0 +1,106 @@
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use types::bytesrepr::{FromBytes, ToBytes};
+
+use crate::{
+    error::in_memory,
+    transaction_source::{Transaction, TransactionSource},
+    trie::{merkle_proof::TrieMerkleProof, Trie},
+    trie_store::operations::{
+        self,
+        tests::{
+            create_6_leaf_trie, InMemoryTestContext, TestKey, TestValue, TEST_LEAVES,
+            TEST_TRIE_GENERATORS,
+        },
+        ReadResult,
+    },
+};
+
+fn read_with_proof(
+    context: &InMemoryTestContext,
+    root_hash: Blake2bHash,
+    key: TestKey,
+) -> ReadResult<TrieMerkleProof<TestKey, TestValue>> {
+    let correlation_id = CorrelationId::new();
+    let txn = context.environment.create_read_txn().unwrap();
+    let result = operations::read_with_proof::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+        &key,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    result
+}
+
+fn leaf_key_and_value(leaf: &Trie<TestKey, TestValue>) -> (TestKey, TestValue) {
+    match leaf {
+        Trie::Leaf { key, value } => (*key, *value),
+        _ => panic!("should be a leaf"),
+    }
+}
+
+#[test]
+fn should_prove_every_present_leaf() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let (present, absent) = TEST_LEAVES.split_at(num_leaves);
+
+        for leaf in present {
+            let (key, value) = leaf_key_and_value(leaf);
+            let proof = match read_with_proof(&context, root_hash, key) {
+                ReadResult::Found(proof) => proof,
+                other => panic!("should find {:?}, got {:?}", key, other),
+            };
+            assert_eq!(*proof.key(), key);
+            assert_eq!(*proof.value(), value);
+            assert_eq!(proof.compute_state_hash().unwrap(), root_hash);
+        }
+
+        for leaf in absent {
+            let (key, _) = leaf_key_and_value(leaf);
+            assert_eq!(
+                read_with_proof(&context, root_hash, key),
+                ReadResult::NotFound
+            );
+        }
+    }
+}
+
+#[test]
+fn should_not_verify_tampered_proof() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let (key, _) = leaf_key_and_value(&TEST_LEAVES[2]);
+    let (_, other_value) = leaf_key_and_value(&TEST_LEAVES[3]);
+
+    let proof = match read_with_proof(&context, root_hash, key) {
+        ReadResult::Found(proof) => proof,
+        other => panic!("should find {:?}, got {:?}", key, other),
+    };
+    let tampered = TrieMerkleProof::new(key, other_value, proof.proof_steps().clone());
+
+    assert_ne!(tampered.compute_state_hash().unwrap(), root_hash);
+}
+
+#[test]
+fn should_roundtrip_proof_serialization() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    for leaf in TEST_LEAVES.iter() {
+        let (key, _) = leaf_key_and_value(leaf);
+        let proof = match read_with_proof(&context, root_hash, key) {
+            ReadResult::Found(proof) => proof,
+            other => panic!("should find {:?}, got {:?}", key, other),
+        };
+        let bytes = proof.to_bytes().unwrap();
+        assert_eq!(bytes.len(), proof.serialized_length());
+        let (deserialized, rem) =
+            TrieMerkleProof::<TestKey, TestValue>::from_bytes(&bytes).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(deserialized, proof);
+    }
+}
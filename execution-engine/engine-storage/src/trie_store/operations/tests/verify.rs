@@ -0,0 +1,202 @@
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+
+use crate::{
+    error::in_memory,
+    transaction_source::{Transaction, TransactionSource},
+    trie::Trie,
+    trie_store::operations::{
+        self,
+        tests::{
+            create_6_leaf_trie, HashedTestTrie, HashedTrie, InMemoryTestContext, TestKey,
+            TestValue, TEST_LEAVES, TEST_TRIE_GENERATORS,
+        },
+        ReadResult, TrieCorruption, TrieRepairReport, TrieVerificationReport,
+    },
+};
+
+fn verify(context: &InMemoryTestContext, root_hash: Blake2bHash) -> TrieVerificationReport {
+    let correlation_id = CorrelationId::new();
+    let txn = context.environment.create_read_txn().unwrap();
+    let report = operations::verify::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    report
+}
+
+fn repair(context: &InMemoryTestContext, root_hash: Blake2bHash) -> TrieRepairReport {
+    let correlation_id = CorrelationId::new();
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    let report = operations::repair::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &mut txn,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    report
+}
+
+fn read(
+    context: &InMemoryTestContext,
+    root_hash: Blake2bHash,
+    key: &TestKey,
+) -> ReadResult<TestValue> {
+    let correlation_id = CorrelationId::new();
+    let txn = context.environment.create_read_txn().unwrap();
+    let result = operations::read::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+        key,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    result
+}
+
+fn leaf_pair(trie: &Trie<TestKey, TestValue>) -> (TestKey, TestValue) {
+    match trie {
+        Trie::Leaf { key, value } => (*key, *value),
+        _ => panic!("expected a leaf"),
+    }
+}
+
+#[test]
+fn should_find_no_corruption_in_complete_tries() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let report = verify(&context, root_hash);
+        assert!(report.is_intact(), "{:?}", report);
+        assert_eq!(report.root, root_hash);
+        assert_eq!(report.tries_checked, tries.len());
+        assert_eq!(report.leaves_checked, num_leaves);
+    }
+}
+
+#[test]
+fn should_report_missing_root() {
+    let (root_hash, _tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new::<TestKey, TestValue>(&[]).unwrap();
+
+    let report = verify(&context, root_hash);
+    assert_eq!(
+        report.corruptions,
+        vec![TrieCorruption::Missing {
+            trie_key: root_hash,
+            path: vec![]
+        }]
+    );
+    assert_eq!(report.tries_checked, 0);
+}
+
+#[test]
+fn should_report_missing_leaf_with_its_path() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let leaf = HashedTrie::new(TEST_LEAVES[4].clone()).unwrap();
+    let incomplete_tries: Vec<HashedTestTrie> = tries
+        .into_iter()
+        .filter(|hashed_trie| hashed_trie.hash != leaf.hash)
+        .collect();
+    let context = InMemoryTestContext::new(&incomplete_tries).unwrap();
+
+    let report = verify(&context, root_hash);
+    assert_eq!(report.leaves_checked, TEST_LEAVES.len() - 1);
+    match report.corruptions.as_slice() {
+        [TrieCorruption::Missing { trie_key, path }] => {
+            assert_eq!(*trie_key, leaf.hash);
+            let (key, _) = leaf_pair(&leaf.trie);
+            assert!(key.0.starts_with(path));
+        }
+        other => panic!("expected a single missing leaf, got {:?}", other),
+    }
+}
+
+#[test]
+fn should_report_corrupted_leaf() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let leaf = HashedTrie::new(TEST_LEAVES[4].clone()).unwrap();
+    let other_leaf = HashedTrie::new(TEST_LEAVES[5].clone()).unwrap();
+    let corrupted_tries: Vec<HashedTestTrie> = tries
+        .into_iter()
+        .map(|hashed_trie| {
+            if hashed_trie.hash == leaf.hash {
+                HashedTrie {
+                    hash: leaf.hash,
+                    trie: TEST_LEAVES[5].clone(),
+                }
+            } else {
+                hashed_trie
+            }
+        })
+        .collect();
+    let context = InMemoryTestContext::new(&corrupted_tries).unwrap();
+
+    let report = verify(&context, root_hash);
+    match report.corruptions.as_slice() {
+        [TrieCorruption::HashMismatch {
+            trie_key,
+            actual_hash,
+            ..
+        }] => {
+            assert_eq!(*trie_key, leaf.hash);
+            assert_eq!(*actual_hash, other_leaf.hash);
+        }
+        other => panic!("expected a single corrupted leaf, got {:?}", other),
+    }
+}
+
+#[test]
+fn should_repair_complete_trie_into_equivalent_trie() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let repair_report = repair(&context, root_hash);
+    assert!(repair_report.verification.is_intact());
+
+    let repaired_root = repair_report.repaired_root;
+    let report = verify(&context, repaired_root);
+    assert!(report.is_intact(), "{:?}", report);
+    assert_eq!(report.leaves_checked, TEST_LEAVES.len());
+    for leaf in TEST_LEAVES.iter() {
+        let (key, value) = leaf_pair(leaf);
+        assert_eq!(
+            read(&context, repaired_root, &key),
+            ReadResult::Found(value)
+        );
+    }
+}
+
+#[test]
+fn should_salvage_intact_leaves_when_repairing() {
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let leaf = HashedTrie::new(TEST_LEAVES[4].clone()).unwrap();
+    let incomplete_tries: Vec<HashedTestTrie> = tries
+        .into_iter()
+        .filter(|hashed_trie| hashed_trie.hash != leaf.hash)
+        .collect();
+    let context = InMemoryTestContext::new(&incomplete_tries).unwrap();
+
+    let repair_report = repair(&context, root_hash);
+    assert_eq!(repair_report.verification.corruptions.len(), 1);
+
+    let repaired_root = repair_report.repaired_root;
+    assert_ne!(repaired_root, root_hash);
+    assert!(verify(&context, repaired_root).is_intact());
+    for (index, leaf) in TEST_LEAVES.iter().enumerate() {
+        let (key, value) = leaf_pair(leaf);
+        let expected = if index == 4 {
+            ReadResult::NotFound
+        } else {
+            ReadResult::Found(value)
+        };
+        assert_eq!(read(&context, repaired_root, &key), expected);
+    }
+}
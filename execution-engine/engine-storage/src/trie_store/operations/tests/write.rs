@@ -655,3 +655,112 @@ mod full_tries {
         .unwrap()
     }
 }
+
+mod shared_subtrees {
+    use super::*;
+    use crate::trie_store::operations::{write_with_stats, WriteStats};
+
+    fn write_with_stats_in_txn<'a, R, S, E>(
+        correlation_id: CorrelationId,
+        environment: &'a R,
+        store: &S,
+        root: &Blake2bHash,
+        leaf: &TestTrie,
+    ) -> Result<(WriteResult, WriteStats), E>
+    where
+        R: TransactionSource<'a, Handle = S::Handle>,
+        S: TrieStore<TestKey, TestValue>,
+        S::Error: From<R::Error>,
+        E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+    {
+        let (key, value) = match leaf {
+            Trie::Leaf { key, value } => (key, value),
+            _ => panic!("should be a leaf"),
+        };
+        let mut txn = environment.create_read_write_txn()?;
+        let result =
+            write_with_stats::<_, _, _, _, E>(correlation_id, &mut txn, store, root, key, value)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    fn reverting_update_reuses_all_nodes<'a, R, S, E>(
+        correlation_id: CorrelationId,
+        environment: &'a R,
+        store: &S,
+        root: &Blake2bHash,
+    ) -> Result<(), E>
+    where
+        R: TransactionSource<'a, Handle = S::Handle>,
+        S: TrieStore<TestKey, TestValue>,
+        S::Error: From<R::Error>,
+        E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+    {
+        let (updated_root, update_stats) = match write_with_stats_in_txn::<_, _, E>(
+            correlation_id,
+            environment,
+            store,
+            root,
+            &TEST_LEAVES_UPDATED[0],
+        )? {
+            (WriteResult::Written(updated_root), stats) => (updated_root, stats),
+            _ => panic!("value not written"),
+        };
+        assert!(update_stats.puts > 0);
+        assert!(update_stats.bytes_put > 0);
+        assert_eq!(update_stats.reused, 0);
+
+        // Writing the original value back recreates exactly the nodes of the original root, all
+        // of which are still in the store.
+        let (reverted_root, revert_stats) = match write_with_stats_in_txn::<_, _, E>(
+            correlation_id,
+            environment,
+            store,
+            &updated_root,
+            &TEST_LEAVES[0],
+        )? {
+            (WriteResult::Written(reverted_root), stats) => (reverted_root, stats),
+            _ => panic!("value not written"),
+        };
+        assert_eq!(reverted_root, *root);
+        assert_eq!(revert_stats.puts, 0);
+        assert_eq!(revert_stats.bytes_put, 0);
+        assert_eq!(revert_stats.reused, update_stats.puts);
+
+        Ok(())
+    }
+
+    #[test]
+    fn lmdb_reverting_update_reuses_all_nodes() {
+        for generator in &TEST_TRIE_GENERATORS[1..] {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = generator().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+
+            reverting_update_reuses_all_nodes::<_, _, error::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+            )
+            .unwrap()
+        }
+    }
+
+    #[test]
+    fn in_memory_reverting_update_reuses_all_nodes() {
+        for generator in &TEST_TRIE_GENERATORS[1..] {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = generator().unwrap();
+            let context = InMemoryTestContext::new(&tries).unwrap();
+
+            reverting_update_reuses_all_nodes::<_, _, in_memory::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+            )
+            .unwrap()
+        }
+    }
+}
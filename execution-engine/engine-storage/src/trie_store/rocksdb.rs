@@ -0,0 +1,50 @@
+//! A RocksDB-backed trie store, keeping trie nodes in their own column family.
+
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error,
+    store::Store,
+    transaction_source::rocksdb::RocksDbEnvironment,
+    trie::Trie,
+    trie_store::{self, TrieStore},
+};
+
+/// A RocksDB-backed trie store.
+///
+/// Wraps the name of a column family of a [`rocksdb::DB`].
+#[derive(Debug, Clone)]
+pub struct RocksDbTrieStore {
+    cf: String,
+}
+
+impl RocksDbTrieStore {
+    /// Opens the store in the column family named after `maybe_name`, which `env` must have been
+    /// opened with.  See [`RocksDbTrieStore::column_family`].
+    pub fn new(env: &RocksDbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let cf = Self::column_family(maybe_name);
+        if !env.has_column_family(&cf) {
+            return Err(error::rocksdb::Error::MissingColumnFamily(cf).into());
+        }
+        Ok(RocksDbTrieStore { cf })
+    }
+
+    /// Returns the name of the column family holding the store called `maybe_name`.
+    pub fn column_family(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", trie_store::NAME, name))
+            .unwrap_or_else(|| String::from(trie_store::NAME))
+    }
+}
+
+impl<K, V> Store<Blake2bHash, Trie<K, V>> for RocksDbTrieStore {
+    type Error = error::Error;
+
+    type Handle = String;
+
+    fn handle(&self) -> Self::Handle {
+        self.cf.clone()
+    }
+}
+
+impl<K, V> TrieStore<K, V> for RocksDbTrieStore {}
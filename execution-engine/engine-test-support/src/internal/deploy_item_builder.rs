@@ -2,10 +2,17 @@ use std::{collections::BTreeSet, path::Path};
 
 use contract::args_parser::ArgsParser;
 use engine_core::{
-    engine_state::{deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem},
+    engine_state::{
+        deploy_header::DeployHeader, deploy_item::DeployItem,
+        executable_deploy_item::ExecutableDeployItem,
+    },
     DeployHash,
 };
-use types::{account::PublicKey, bytesrepr::ToBytes, URef};
+use types::{
+    account::{AssociatedKeysUpdate, PublicKey},
+    bytesrepr::ToBytes,
+    NamedArgs, ProtocolVersion, URef, U512,
+};
 
 use crate::internal::utils;
 
@@ -17,6 +24,10 @@ struct DeployItemData {
     pub gas_price: u64,
     pub authorization_keys: BTreeSet<PublicKey>,
     pub deploy_hash: DeployHash,
+    pub header: Option<DeployHeader>,
+    pub sequence_number: Option<u64>,
+    pub payment_source: Option<URef>,
+    pub protocol_version: Option<ProtocolVersion>,
 }
 
 pub struct DeployItemBuilder {
@@ -35,8 +46,11 @@ impl DeployItemBuilder {
 
     pub fn with_payment_bytes(mut self, module_bytes: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
-        self.deploy_item.payment_code =
-            Some(ExecutableDeployItem::ModuleBytes { module_bytes, args });
+        self.deploy_item.payment_code = Some(ExecutableDeployItem::ModuleBytes {
+            module_bytes,
+            args,
+            named_args: Vec::new(),
+        });
         self
     }
 
@@ -51,8 +65,11 @@ impl DeployItemBuilder {
 
     pub fn with_stored_payment_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
-        self.deploy_item.payment_code =
-            Some(ExecutableDeployItem::StoredContractByHash { hash, args });
+        self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByHash {
+            hash,
+            args,
+            named_args: Vec::new(),
+        });
         self
     }
 
@@ -65,6 +82,7 @@ impl DeployItemBuilder {
         self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref_addr,
             args,
+            named_args: Vec::new(),
         });
         self
     }
@@ -74,6 +92,7 @@ impl DeployItemBuilder {
         self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref.addr().to_vec(),
             args,
+            named_args: Vec::new(),
         });
         self
     }
@@ -83,14 +102,18 @@ impl DeployItemBuilder {
         self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByName {
             name: uref_name.to_owned(),
             args,
+            named_args: Vec::new(),
         });
         self
     }
 
     pub fn with_session_bytes(mut self, module_bytes: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
-        self.deploy_item.session_code =
-            Some(ExecutableDeployItem::ModuleBytes { module_bytes, args });
+        self.deploy_item.session_code = Some(ExecutableDeployItem::ModuleBytes {
+            module_bytes,
+            args,
+            named_args: Vec::new(),
+        });
         self
     }
 
@@ -99,10 +122,31 @@ impl DeployItemBuilder {
         self.with_session_bytes(module_bytes, args)
     }
 
+    /// Sets the session code to the given Wasm file, taking named args instead of positional ones.
+    pub fn with_session_code_named_args<T: AsRef<Path>>(
+        mut self,
+        file_name: T,
+        named_args: NamedArgs,
+    ) -> Self {
+        let module_bytes = utils::read_wasm_file_bytes(file_name);
+        let named_args = named_args
+            .into_bytes()
+            .expect("should serialize named args");
+        self.deploy_item.session_code = Some(ExecutableDeployItem::ModuleBytes {
+            module_bytes,
+            args: Vec::new(),
+            named_args,
+        });
+        self
+    }
+
     pub fn with_stored_session_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
-        self.deploy_item.session_code =
-            Some(ExecutableDeployItem::StoredContractByHash { hash, args });
+        self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByHash {
+            hash,
+            args,
+            named_args: Vec::new(),
+        });
         self
     }
 
@@ -115,6 +159,7 @@ impl DeployItemBuilder {
         self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref_addr,
             args,
+            named_args: Vec::new(),
         });
         self
     }
@@ -124,6 +169,7 @@ impl DeployItemBuilder {
         self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref.addr().to_vec(),
             args,
+            named_args: Vec::new(),
         });
         self
     }
@@ -133,10 +179,22 @@ impl DeployItemBuilder {
         self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByName {
             name: uref_name.to_owned(),
             args,
+            named_args: Vec::new(),
         });
         self
     }
 
+    pub fn with_native_key_management(mut self, updates: Vec<AssociatedKeysUpdate>) -> Self {
+        self.deploy_item.session_code = Some(ExecutableDeployItem::NativeKeyManagement { updates });
+        self
+    }
+
+    pub fn with_native_transfer(mut self, target: PublicKey, amount: U512) -> Self {
+        self.deploy_item.session_code =
+            Some(ExecutableDeployItem::NativeTransfer { target, amount });
+        self
+    }
+
     pub fn with_authorization_keys<T: Clone + Into<PublicKey>>(
         mut self,
         authorization_keys: &[T],
@@ -159,6 +217,26 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_header(mut self, header: DeployHeader) -> Self {
+        self.deploy_item.header = Some(header);
+        self
+    }
+
+    pub fn with_sequence_number(mut self, sequence_number: u64) -> Self {
+        self.deploy_item.sequence_number = Some(sequence_number);
+        self
+    }
+
+    pub fn with_payment_source(mut self, purse: URef) -> Self {
+        self.deploy_item.payment_source = Some(purse);
+        self
+    }
+
+    pub fn with_protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
+        self.deploy_item.protocol_version = Some(protocol_version);
+        self
+    }
+
     pub fn build(self) -> DeployItem {
         DeployItem {
             address: self
@@ -170,6 +248,10 @@ impl DeployItemBuilder {
             gas_price: self.deploy_item.gas_price,
             authorization_keys: self.deploy_item.authorization_keys,
             deploy_hash: self.deploy_item.deploy_hash,
+            header: self.deploy_item.header,
+            sequence_number: self.deploy_item.sequence_number,
+            payment_source: self.deploy_item.payment_source,
+            protocol_version: self.deploy_item.protocol_version,
         }
     }
 
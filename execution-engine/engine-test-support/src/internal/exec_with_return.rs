@@ -9,11 +9,17 @@ use engine_core::{
     execution::{self, AddressGenerator},
     runtime::{self, Runtime},
     runtime_context::RuntimeContext,
+    wasm_backend::{self, WasmInstance},
 };
 use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use engine_shared::{gas::Gas, newtypes::CorrelationId};
-use engine_storage::{global_state::StateProvider, protocol_data::ProtocolData};
-use engine_wasm_prep::Preprocessor;
+use engine_storage::{
+    global_state::StateProvider,
+    protocol_data::{
+        ProtocolData, DEFAULT_MAX_CALL_DEPTH, DEFAULT_REFUND_RATIO, DEFAULT_SYSTEM_ACCOUNT,
+    },
+};
+use engine_wasm_prep::{wasm_features::WasmFeatures, Preprocessor};
 use types::{
     account::PublicKey, bytesrepr::FromBytes, BlockTime, CLTyped, CLValue, Key, Phase,
     ProtocolVersion, URef, U512,
@@ -22,6 +28,7 @@ use types::{
 use crate::internal::{utils, WasmTestBuilder, DEFAULT_WASM_COSTS};
 
 const INIT_FN_STORE_ID: u32 = 0;
+const INIT_BLOCK_HEIGHT: u64 = 0;
 
 /// This function allows executing the contract stored in the given `wasm_file`, while capturing the
 /// output. It is essentially the same functionality as `Executor::exec`, but the return value of
@@ -85,18 +92,30 @@ where
         let mint = builder.get_mint_contract_uref();
         let pos = builder.get_mint_contract_uref();
         let standard_payment = builder.get_standard_payment_contract_uref();
-        ProtocolData::new(*DEFAULT_WASM_COSTS, mint, pos, standard_payment)
+        let key_recovery = builder.get_key_recovery_contract_uref();
+        ProtocolData::new(
+            *DEFAULT_WASM_COSTS,
+            mint,
+            pos,
+            standard_payment,
+            key_recovery,
+            DEFAULT_MAX_CALL_DEPTH,
+            WasmFeatures::default(),
+            DEFAULT_REFUND_RATIO,
+            DEFAULT_SYSTEM_ACCOUNT,
+        )
     };
 
     let context = RuntimeContext::new(
         Rc::clone(&tracking_copy),
         &mut named_keys,
         access_rights,
-        arguments,
+        arguments.into(),
         BTreeSet::new(),
         &account,
         base_key,
         BlockTime::new(block_time),
+        INIT_BLOCK_HEIGHT,
         deploy_hash,
         gas_limit,
         gas_counter,
@@ -112,11 +131,12 @@ where
     let deploy_item = ExecutableDeployItem::ModuleBytes {
         module_bytes: wasm_bytes,
         args: Vec::new(),
+        named_args: Vec::new(),
     };
 
     let wasm_costs = *DEFAULT_WASM_COSTS;
 
-    let preprocessor = Preprocessor::new(wasm_costs);
+    let preprocessor = Preprocessor::new(wasm_costs, WasmFeatures::default());
     let parity_module = builder
         .get_engine_state()
         .get_module(
@@ -129,36 +149,28 @@ where
         )
         .expect("should get wasm module");
 
-    let (instance, memory) = runtime::instance_and_memory(parity_module.clone(), protocol_version)
-        .expect("should be able to make wasm instance from module");
+    let (instance, memory) = wasm_backend::instantiate(
+        parity_module.clone(),
+        protocol_version,
+        wasm_costs.max_memory_pages,
+    )
+    .expect("should be able to make wasm instance from module");
 
     let mut runtime = Runtime::new(config, Default::default(), memory, parity_module, context);
 
-    match instance.invoke_export("call", &[], &mut runtime) {
-        Ok(_) => None,
-        Err(e) => {
-            if let Some(host_error) = e.as_host_error() {
-                // `ret` Trap is a success; downcast and attempt to extract result
-                let downcasted_error = host_error.downcast_ref::<execution::Error>().unwrap();
-                match downcasted_error {
-                    execution::Error::Ret(ref ret_urefs) => {
-                        let effect = runtime.context().effect();
-                        let urefs = ret_urefs.clone();
-
-                        let value: T = runtime
-                            .take_host_buffer()
-                            .expect("should have return value in the host_buffer")
-                            .into_t()
-                            .expect("should deserialize return value");
-
-                        Some((value, urefs, effect))
-                    }
-
-                    _ => None,
-                }
-            } else {
-                None
-            }
+    match instance.invoke_export("call", &mut runtime) {
+        // `ret` Trap is a success; attempt to extract result
+        Err(execution::Error::Ret(urefs)) => {
+            let effect = runtime.context().effect();
+
+            let value: T = runtime
+                .take_host_buffer()
+                .expect("should have return value in the host_buffer")
+                .into_t()
+                .expect("should deserialize return value");
+
+            Some((value, urefs, effect))
         }
+        _ => None,
     }
 }
@@ -6,7 +6,7 @@ use contract::args_parser::ArgsParser;
 use engine_core::engine_state::{deploy_item::DeployItem, execute_request::ExecuteRequest};
 use types::{account::PublicKey, ProtocolVersion};
 
-use crate::internal::{DeployItemBuilder, DEFAULT_BLOCK_TIME, DEFAULT_PAYMENT};
+use crate::internal::{DeployItemBuilder, TestClock, DEFAULT_BLOCK_TIME, DEFAULT_PAYMENT};
 
 pub struct ExecuteRequestBuilder {
     execute_request: ExecuteRequest,
@@ -36,6 +36,16 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    pub fn with_block_height(mut self, block_height: u64) -> Self {
+        self.execute_request.block_height = block_height;
+        self
+    }
+
+    /// Sets the block time to the current time of `clock`.
+    pub fn with_clock(self, clock: &TestClock) -> Self {
+        self.with_block_time(clock.block_time())
+    }
+
     pub fn with_protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
         self.execute_request.protocol_version = protocol_version;
         self
@@ -2,6 +2,7 @@ mod additive_map_diff;
 mod deploy_item_builder;
 pub mod exec_with_return;
 mod execute_request_builder;
+mod test_clock;
 mod upgrade_request_builder;
 pub mod utils;
 mod wasm_test_builder;
@@ -21,6 +22,7 @@ use super::{DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE};
 pub use additive_map_diff::AdditiveMapDiff;
 pub use deploy_item_builder::DeployItemBuilder;
 pub use execute_request_builder::ExecuteRequestBuilder;
+pub use test_clock::TestClock;
 pub use upgrade_request_builder::UpgradeRequestBuilder;
 pub use wasm_test_builder::{
     InMemoryWasmTestBuilder, LmdbWasmTestBuilder, WasmTestBuilder, WasmTestResult,
@@ -30,10 +32,13 @@ pub const MINT_INSTALL_CONTRACT: &str = "mint_install.wasm";
 pub const POS_INSTALL_CONTRACT: &str = "pos_install.wasm";
 pub const STANDARD_PAYMENT_INSTALL_CONTRACT: &str = "standard_payment_install.wasm";
 pub const STANDARD_PAYMENT_CONTRACT: &str = "standard_payment.wasm";
+pub const KEY_RECOVERY_INSTALL_CONTRACT: &str = "key_recovery_install.wasm";
 
 pub const DEFAULT_CHAIN_NAME: &str = "gerald";
 pub const DEFAULT_GENESIS_TIMESTAMP: u64 = 0;
 pub const DEFAULT_BLOCK_TIME: u64 = 0;
+pub const DEFAULT_ERA_DURATION: u64 = 1_000;
+pub const DEFAULT_UNBONDING_DELAY: u64 = 0;
 pub const MOCKED_ACCOUNT_ADDRESS: PublicKey = PublicKey::ed25519_from([48u8; 32]);
 
 pub const DEFAULT_ACCOUNT_KEY: PublicKey = DEFAULT_ACCOUNT_ADDR;
@@ -57,17 +62,21 @@ lazy_static! {
         let mint_installer_bytes;
         let pos_installer_bytes;
         let standard_payment_installer_bytes;
+        let key_recovery_installer_bytes;
         mint_installer_bytes = utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT);
         pos_installer_bytes = utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT);
         standard_payment_installer_bytes =
             utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
+        key_recovery_installer_bytes = utils::read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT);
 
         ExecConfig::new(
             mint_installer_bytes,
             pos_installer_bytes,
             standard_payment_installer_bytes,
+            key_recovery_installer_bytes,
             DEFAULT_ACCOUNTS.clone(),
             *DEFAULT_WASM_COSTS,
+            DEFAULT_UNBONDING_DELAY,
         )
     };
     pub static ref DEFAULT_GENESIS_CONFIG: GenesisConfig = {
@@ -0,0 +1,98 @@
+use crate::internal::{DEFAULT_BLOCK_TIME, DEFAULT_ERA_DURATION};
+
+/// A deterministic source of block times for tests spanning several exec/commit rounds.
+///
+/// Eras are fixed-length windows of block time starting at zero, so the era of a given block time
+/// is simply `block_time / era_duration`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TestClock {
+    block_time: u64,
+    era_duration: u64,
+}
+
+impl TestClock {
+    /// Creates a clock at `DEFAULT_BLOCK_TIME` with eras lasting `era_duration`.
+    pub fn new(era_duration: u64) -> Self {
+        TestClock::starting_at(DEFAULT_BLOCK_TIME, era_duration)
+    }
+
+    /// Creates a clock at `block_time` with eras lasting `era_duration`.
+    pub fn starting_at(block_time: u64, era_duration: u64) -> Self {
+        assert!(era_duration > 0, "era duration must be non-zero");
+        TestClock {
+            block_time,
+            era_duration,
+        }
+    }
+
+    pub fn block_time(&self) -> u64 {
+        self.block_time
+    }
+
+    pub fn era_duration(&self) -> u64 {
+        self.era_duration
+    }
+
+    /// Returns the era which the current block time falls in.
+    pub fn era(&self) -> u64 {
+        self.block_time / self.era_duration
+    }
+
+    /// Moves the clock forward by `duration` and returns the new block time.
+    pub fn advance(&mut self, duration: u64) -> u64 {
+        self.block_time = self
+            .block_time
+            .checked_add(duration)
+            .expect("block time should not overflow");
+        self.block_time
+    }
+
+    /// Moves the clock to the start of the era `count` eras after the current one and returns the
+    /// new block time.  Advancing by zero eras leaves the clock unchanged.
+    pub fn advance_eras(&mut self, count: u64) -> u64 {
+        if count == 0 {
+            return self.block_time;
+        }
+        let era = self
+            .era()
+            .checked_add(count)
+            .expect("era should not overflow");
+        self.block_time = era
+            .checked_mul(self.era_duration)
+            .expect("block time should not overflow");
+        self.block_time
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        TestClock::new(DEFAULT_ERA_DURATION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_advance_block_time() {
+        let mut clock = TestClock::new(100);
+        assert_eq!(clock.advance(42), DEFAULT_BLOCK_TIME + 42);
+        assert_eq!(clock.advance(0), DEFAULT_BLOCK_TIME + 42);
+    }
+
+    #[test]
+    fn should_advance_to_start_of_later_era() {
+        let mut clock = TestClock::starting_at(150, 100);
+        assert_eq!(clock.era(), 1);
+
+        assert_eq!(clock.advance_eras(1), 200);
+        assert_eq!(clock.era(), 2);
+
+        assert_eq!(clock.advance_eras(3), 500);
+        assert_eq!(clock.era(), 5);
+
+        clock.advance(10);
+        assert_eq!(clock.advance_eras(0), 510);
+    }
+}
@@ -1,11 +1,11 @@
 use engine_grpc_server::engine_server::{
     ipc::{
-        ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_UpgradePoint,
-        DeployCode, UpgradeRequest,
+        ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_RefundRatio,
+        ChainSpec_UpgradePoint, ChainSpec_WasmFeatures, DeployCode, UpgradeRequest,
     },
     state,
 };
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_features::WasmFeatures};
 use types::ProtocolVersion;
 
 pub struct UpgradeRequestBuilder {
@@ -15,6 +15,9 @@ pub struct UpgradeRequestBuilder {
     upgrade_installer: DeployCode,
     new_costs: Option<ChainSpec_CostTable_WasmCosts>,
     activation_point: ChainSpec_ActivationPoint,
+    new_max_call_depth: u32,
+    new_wasm_features: Option<ChainSpec_WasmFeatures>,
+    new_refund_ratio: Option<ChainSpec_RefundRatio>,
 }
 
 impl UpgradeRequestBuilder {
@@ -54,6 +57,18 @@ impl UpgradeRequestBuilder {
         new_costs.set_max_stack_height(wasm_costs.max_stack_height);
         new_costs.set_mem(wasm_costs.mem);
         new_costs.set_memcpy(wasm_costs.memcpy);
+        new_costs.set_max_memory_pages(wasm_costs.max_memory_pages);
+        new_costs.set_max_table_size(wasm_costs.max_table_size);
+        new_costs.set_hash_per_byte(wasm_costs.hash_per_byte);
+        new_costs.set_verify_ed25519(wasm_costs.verify_ed25519);
+        new_costs.set_verify_secp256k1(wasm_costs.verify_secp256k1);
+        new_costs.set_bignum_op(wasm_costs.bignum_op);
+        new_costs.set_storage_bucket_size(wasm_costs.storage_bucket_size);
+        new_costs.set_storage_read_per_bucket(wasm_costs.storage_read_per_bucket);
+        new_costs.set_storage_write_per_bucket(wasm_costs.storage_write_per_bucket);
+        new_costs.set_max_module_size(wasm_costs.max_module_size);
+        new_costs.set_max_function_count(wasm_costs.max_function_count);
+        new_costs.set_max_named_keys_per_write(wasm_costs.max_named_keys_per_write);
         self.new_costs = Some(new_costs);
         self
     }
@@ -67,6 +82,23 @@ impl UpgradeRequestBuilder {
         self
     }
 
+    pub fn with_new_max_call_depth(mut self, max_call_depth: u32) -> Self {
+        self.new_max_call_depth = max_call_depth;
+        self
+    }
+
+    pub fn with_new_wasm_features(mut self, wasm_features: WasmFeatures) -> Self {
+        self.new_wasm_features = Some(wasm_features.into());
+        self
+    }
+
+    pub fn with_new_refund_ratio(mut self, refund_ratio: u8) -> Self {
+        let mut new_refund_ratio = ChainSpec_RefundRatio::new();
+        new_refund_ratio.set_percentage(refund_ratio.into());
+        self.new_refund_ratio = Some(new_refund_ratio);
+        self
+    }
+
     pub fn build(self) -> UpgradeRequest {
         let mut upgrade_point = ChainSpec_UpgradePoint::new();
         upgrade_point.set_activation_point(self.activation_point);
@@ -81,6 +113,13 @@ impl UpgradeRequestBuilder {
         }
         upgrade_point.set_protocol_version(self.new_protocol_version);
         upgrade_point.set_upgrade_installer(self.upgrade_installer);
+        upgrade_point.set_new_max_call_depth(self.new_max_call_depth);
+        if let Some(new_wasm_features) = self.new_wasm_features {
+            upgrade_point.set_new_wasm_features(new_wasm_features);
+        }
+        if let Some(new_refund_ratio) = self.new_refund_ratio {
+            upgrade_point.set_new_refund_ratio(new_refund_ratio);
+        }
 
         let mut upgrade_request = UpgradeRequest::new();
         upgrade_request.set_protocol_version(self.current_protocol_version);
@@ -98,6 +137,9 @@ impl Default for UpgradeRequestBuilder {
             upgrade_installer: Default::default(),
             new_costs: None,
             activation_point: Default::default(),
+            new_max_call_depth: 0,
+            new_wasm_features: None,
+            new_refund_ratio: None,
         }
     }
 }
@@ -20,7 +20,8 @@ use types::Key;
 
 use crate::internal::{
     DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP,
-    DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    DEFAULT_PROTOCOL_VERSION, DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS,
+    KEY_RECOVERY_INSTALL_CONTRACT, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
     STANDARD_PAYMENT_INSTALL_CONTRACT,
 };
 
@@ -108,13 +109,16 @@ pub fn create_exec_config(accounts: Vec<GenesisAccount>) -> ExecConfig {
     let mint_installer_bytes = read_wasm_file_bytes(MINT_INSTALL_CONTRACT);
     let proof_of_stake_installer_bytes = read_wasm_file_bytes(POS_INSTALL_CONTRACT);
     let standard_payment_installer_bytes = read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
+    let key_recovery_installer_bytes = read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT);
     let wasm_costs = *DEFAULT_WASM_COSTS;
     ExecConfig::new(
         mint_installer_bytes,
         proof_of_stake_installer_bytes,
         standard_payment_installer_bytes,
+        key_recovery_installer_bytes,
         accounts,
         wasm_costs,
+        DEFAULT_UNBONDING_DELAY,
     )
 }
 
@@ -14,15 +14,25 @@ use log::LevelFilter;
 
 use engine_core::{
     engine_state::{
-        execute_request::ExecuteRequest, execution_result::ExecutionResult,
-        run_genesis_request::RunGenesisRequest, EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
+        accounts_index::AccountBalanceProof,
+        era_rewards::{EraReport, RewardsResult},
+        era_validators::{self, EraId},
+        execute_request::ExecuteRequest,
+        execution_result::ExecutionResult,
+        metrics::NoopMetrics,
+        run_genesis_request::RunGenesisRequest,
+        scheduled_call::ScheduledCall,
+        slashing::{EquivocationEvidence, SlashingResult},
+        step::{StepRequest, StepResult},
+        EngineConfig, EngineState, Error,
     },
     execution,
 };
 use engine_grpc_server::engine_server::{
     ipc::{
-        CommitRequest, CommitResponse, GenesisResponse, QueryRequest, UpgradeRequest,
-        UpgradeResponse,
+        CheckSignersRequest, CommitRequest, CommitResponse, EraValidators, EraValidatorsRequest,
+        GenesisResponse, QueryRequest, SignersCheck, UnbondingQueue, UnbondingQueueRequest,
+        UpgradeRequest, UpgradeResponse,
     },
     ipc_grpc::ExecutionEngineService,
     mappings::{MappingError, TransformMap},
@@ -40,6 +50,7 @@ use engine_shared::{
     transform::Transform,
 };
 use engine_storage::{
+    deploy_effects_store::lmdb::LmdbDeployEffectsStore,
     global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider},
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     transaction_source::lmdb::LmdbEnvironment,
@@ -48,10 +59,10 @@ use engine_storage::{
 use types::{
     account::PublicKey,
     bytesrepr::{self, ToBytes},
-    CLValue, Key, URef, U512,
+    ApiError, CLValue, Key, URef, U512,
 };
 
-use crate::internal::utils;
+use crate::internal::{utils, DEFAULT_PROTOCOL_VERSION};
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
 ///
@@ -67,8 +78,7 @@ pub type LmdbWasmTestBuilder = WasmTestBuilder<LmdbGlobalState>;
 
 /// Builder for simple WASM test
 pub struct WasmTestBuilder<S> {
-    /// [`EngineState`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
-    engine_state: Rc<EngineState<S>>,
+    engine_state: EngineState<S>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_responses: Vec<Vec<Rc<ExecutionResult>>>,
     upgrade_responses: Vec<UpgradeResponse>,
@@ -88,6 +98,8 @@ pub struct WasmTestBuilder<S> {
     pos_contract_uref: Option<URef>,
     /// Standard payment contract uref
     standard_payment_uref: Option<URef>,
+    /// Key recovery contract uref
+    key_recovery_uref: Option<URef>,
 }
 
 impl<S> WasmTestBuilder<S> {
@@ -105,10 +117,11 @@ impl Default for InMemoryWasmTestBuilder {
             .with_enable_bonding(cfg!(feature = "enable-bonding"));
 
         let global_state = InMemoryGlobalState::empty().expect("should create global state");
-        let engine_state = EngineState::new(global_state, engine_config);
+        let engine_state = EngineState::new(global_state, engine_config, Arc::new(NoopMetrics))
+            .expect("should create engine state");
 
         WasmTestBuilder {
-            engine_state: Rc::new(engine_state),
+            engine_state,
             exec_responses: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
@@ -120,6 +133,7 @@ impl Default for InMemoryWasmTestBuilder {
             mint_contract_uref: None,
             pos_contract_uref: None,
             standard_payment_uref: None,
+            key_recovery_uref: None,
         }
     }
 }
@@ -129,7 +143,7 @@ impl Default for InMemoryWasmTestBuilder {
 impl<S> Clone for WasmTestBuilder<S> {
     fn clone(&self) -> Self {
         WasmTestBuilder {
-            engine_state: Rc::clone(&self.engine_state),
+            engine_state: self.engine_state.clone(),
             exec_responses: self.exec_responses.clone(),
             upgrade_responses: self.upgrade_responses.clone(),
             genesis_hash: self.genesis_hash.clone(),
@@ -141,6 +155,7 @@ impl<S> Clone for WasmTestBuilder<S> {
             mint_contract_uref: self.mint_contract_uref,
             pos_contract_uref: self.pos_contract_uref,
             standard_payment_uref: self.standard_payment_uref,
+            key_recovery_uref: self.key_recovery_uref,
         }
     }
 }
@@ -163,14 +178,37 @@ impl InMemoryWasmTestBuilder {
         post_state_hash: Vec<u8>,
     ) -> Self {
         Self::initialize_logging();
-        let engine_state = EngineState::new(global_state, engine_config);
+        let engine_state = EngineState::new(global_state, engine_config, Arc::new(NoopMetrics))
+            .expect("should create engine state");
         WasmTestBuilder {
-            engine_state: Rc::new(engine_state),
+            engine_state,
             genesis_hash: Some(post_state_hash.clone()),
             post_state_hash: Some(post_state_hash),
             ..Default::default()
         }
     }
+
+    /// Returns a copy of this builder over a copy-on-write fork of its global state.
+    ///
+    /// Anything executed or committed on either builder afterwards is invisible to the other, so a
+    /// fork can be used to try out deploys without disturbing the state they were forked from.
+    pub fn fork(&self) -> Self {
+        let global_state = self
+            .engine_state
+            .global_state()
+            .fork()
+            .expect("should fork global state");
+        let engine_state = EngineState::new(
+            global_state,
+            *self.engine_state.config(),
+            Arc::new(NoopMetrics),
+        )
+        .expect("should create engine state");
+        WasmTestBuilder {
+            engine_state,
+            ..self.clone()
+        }
+    }
 }
 
 impl LmdbWasmTestBuilder {
@@ -193,11 +231,21 @@ impl LmdbWasmTestBuilder {
             LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
                 .expect("should create LmdbProtocolDataStore"),
         );
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-            .expect("should create LmdbGlobalState");
-        let engine_state = EngineState::new(global_state, engine_config);
+        let deploy_effects_store = Arc::new(
+            LmdbDeployEffectsStore::new(&environment, None, DatabaseFlags::empty())
+                .expect("should create LmdbDeployEffectsStore"),
+        );
+        let global_state = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            deploy_effects_store,
+        )
+        .expect("should create LmdbGlobalState");
+        let engine_state = EngineState::new(global_state, engine_config, Arc::new(NoopMetrics))
+            .expect("should create engine state");
         WasmTestBuilder {
-            engine_state: Rc::new(engine_state),
+            engine_state,
             exec_responses: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
@@ -209,6 +257,7 @@ impl LmdbWasmTestBuilder {
             mint_contract_uref: None,
             pos_contract_uref: None,
             standard_payment_uref: None,
+            key_recovery_uref: None,
         }
     }
 
@@ -254,11 +303,21 @@ impl LmdbWasmTestBuilder {
             LmdbProtocolDataStore::open(&environment, None)
                 .expect("should open LmdbProtocolDataStore"),
         );
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-            .expect("should create LmdbGlobalState");
-        let engine_state = EngineState::new(global_state, engine_config);
+        let deploy_effects_store = Arc::new(
+            LmdbDeployEffectsStore::open(&environment, None)
+                .expect("should open LmdbDeployEffectsStore"),
+        );
+        let global_state = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            deploy_effects_store,
+        )
+        .expect("should create LmdbGlobalState");
+        let engine_state = EngineState::new(global_state, engine_config, Arc::new(NoopMetrics))
+            .expect("should create engine state");
         WasmTestBuilder {
-            engine_state: Rc::new(engine_state),
+            engine_state,
             exec_responses: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
@@ -270,6 +329,7 @@ impl LmdbWasmTestBuilder {
             mint_contract_uref: None,
             pos_contract_uref: None,
             standard_payment_uref: None,
+            key_recovery_uref: None,
         }
     }
 
@@ -305,12 +365,13 @@ where
             mint_contract_uref: result.0.mint_contract_uref,
             pos_contract_uref: result.0.pos_contract_uref,
             standard_payment_uref: result.0.standard_payment_uref,
+            key_recovery_uref: result.0.key_recovery_uref,
             genesis_transforms: result.0.genesis_transforms,
         }
     }
 
     pub fn run_genesis(&mut self, run_genesis_request: &RunGenesisRequest) -> &mut Self {
-        let system_account = Key::Account(SYSTEM_ACCOUNT_ADDR);
+        let system_account = Key::Account(run_genesis_request.ee_config().system_account());
         let run_genesis_request_proto = run_genesis_request
             .to_owned()
             .try_into()
@@ -350,6 +411,7 @@ where
         self.mint_contract_uref = Some(protocol_data.mint());
         self.pos_contract_uref = Some(protocol_data.proof_of_stake());
         self.standard_payment_uref = Some(protocol_data.standard_payment());
+        self.key_recovery_uref = Some(protocol_data.key_recovery());
         self.genesis_account = Some(genesis_account);
         self.genesis_transforms = Some(transforms);
         self
@@ -382,6 +444,91 @@ where
         bytesrepr::deserialize(query_response.take_success()).map_err(|err| format!("{}", err))
     }
 
+    /// Reports how `signers` measure up against the action thresholds of `account`, without
+    /// executing a deploy.
+    pub fn check_signers(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+        account: PublicKey,
+        signers: &[PublicKey],
+    ) -> Result<SignersCheck, String> {
+        let post_state = maybe_post_state
+            .or_else(|| self.post_state_hash.clone())
+            .expect("builder must have a post-state hash");
+
+        let check_signers_request = create_check_signers_request(post_state, account, signers);
+
+        let mut check_signers_response = self
+            .engine_state
+            .check_signers(RequestOptions::new(), check_signers_request)
+            .wait_drop_metadata()
+            .expect("should get check signers response");
+
+        if check_signers_response.has_failure() {
+            return Err(check_signers_response.take_failure());
+        }
+
+        Ok(check_signers_response.take_success())
+    }
+
+    /// Returns the vote weights of the validators of the era in progress at `maybe_post_state`, as
+    /// reported by the `era_validators` RPC.
+    pub fn era_validators(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+    ) -> Result<EraValidators, String> {
+        let post_state = maybe_post_state
+            .or_else(|| self.post_state_hash.clone())
+            .expect("builder must have a post-state hash");
+
+        let mut era_validators_request = EraValidatorsRequest::new();
+        era_validators_request.set_parent_state_hash(post_state);
+        era_validators_request.set_protocol_version((*DEFAULT_PROTOCOL_VERSION).into());
+
+        let mut era_validators_response = self
+            .engine_state
+            .era_validators(RequestOptions::new(), era_validators_request)
+            .wait_drop_metadata()
+            .expect("should get era validators response");
+
+        if era_validators_response.has_success() {
+            Ok(era_validators_response.take_success())
+        } else if era_validators_response.has_failure() {
+            Err(era_validators_response.take_failure())
+        } else {
+            Err("root not found".to_string())
+        }
+    }
+
+    /// Returns the PoS unbonding queue at `maybe_post_state`, as reported by the
+    /// `unbonding_queue` RPC.
+    pub fn unbonding_queue(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+    ) -> Result<UnbondingQueue, String> {
+        let post_state = maybe_post_state
+            .or_else(|| self.post_state_hash.clone())
+            .expect("builder must have a post-state hash");
+
+        let mut unbonding_queue_request = UnbondingQueueRequest::new();
+        unbonding_queue_request.set_parent_state_hash(post_state);
+        unbonding_queue_request.set_protocol_version((*DEFAULT_PROTOCOL_VERSION).into());
+
+        let mut unbonding_queue_response = self
+            .engine_state
+            .unbonding_queue(RequestOptions::new(), unbonding_queue_request)
+            .wait_drop_metadata()
+            .expect("should get unbonding queue response");
+
+        if unbonding_queue_response.has_success() {
+            Ok(unbonding_queue_response.take_success())
+        } else if unbonding_queue_response.has_failure() {
+            Err(unbonding_queue_response.take_failure())
+        } else {
+            Err("root not found".to_string())
+        }
+    }
+
     pub fn exec(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
         let exec_request = {
             let hash = self
@@ -488,6 +635,81 @@ where
         self
     }
 
+    /// Slashes the validator who equivocated according to `evidence` on top of the current
+    /// post-state hash.
+    pub fn commit_slashing(&mut self, evidence: EquivocationEvidence) -> &mut Self {
+        let pre_state_hash = Blake2bHash::try_from(self.get_post_state_hash().as_slice())
+            .expect("should convert post-state hash");
+        let slashing_result = self
+            .engine_state
+            .commit_slashing(
+                CorrelationId::new(),
+                *DEFAULT_PROTOCOL_VERSION,
+                pre_state_hash,
+                evidence,
+            )
+            .expect("should slash");
+
+        match slashing_result {
+            SlashingResult::Success {
+                post_state_hash, ..
+            } => self.post_state_hash = Some(post_state_hash.to_vec()),
+            other => panic!("Expected successful slashing, but instead got: {}", other),
+        }
+        self
+    }
+
+    /// Pays out the PoS rewards purse to the bonded validators on top of the current post-state
+    /// hash.
+    pub fn distribute_rewards(&mut self, era_report: EraReport) -> &mut Self {
+        let pre_state_hash = Blake2bHash::try_from(self.get_post_state_hash().as_slice())
+            .expect("should convert post-state hash");
+        let rewards_result = self
+            .engine_state
+            .distribute_rewards(
+                CorrelationId::new(),
+                *DEFAULT_PROTOCOL_VERSION,
+                pre_state_hash,
+                era_report,
+            )
+            .expect("should distribute rewards");
+
+        match rewards_result {
+            RewardsResult::Success {
+                post_state_hash, ..
+            } => self.post_state_hash = Some(post_state_hash.to_vec()),
+            other => panic!(
+                "Expected successful rewards distribution, but instead got: {}",
+                other
+            ),
+        }
+        self
+    }
+
+    /// Runs the system bookkeeping due at the end of a block on top of the current post-state
+    /// hash.
+    pub fn step(&mut self, step_request: StepRequest) -> &mut Self {
+        let pre_state_hash = Blake2bHash::try_from(self.get_post_state_hash().as_slice())
+            .expect("should convert post-state hash");
+        let step_result = self
+            .engine_state
+            .step(
+                CorrelationId::new(),
+                *DEFAULT_PROTOCOL_VERSION,
+                pre_state_hash,
+                step_request,
+            )
+            .expect("should step");
+
+        match step_result {
+            StepResult::Success {
+                post_state_hash, ..
+            } => self.post_state_hash = Some(post_state_hash.to_vec()),
+            other => panic!("Expected successful step, but instead got: {}", other),
+        }
+        self
+    }
+
     /// Expects a successful run and caches transformations
     pub fn expect_success(&mut self) -> &mut Self {
         // Check first result, as only first result is interesting for a simple test
@@ -519,6 +741,19 @@ where
         exec_result.is_failure()
     }
 
+    /// Returns the error the last deploy reverted with.  Panics if it didn't revert.
+    pub fn get_revert_error(&self) -> ApiError {
+        let exec_response = self
+            .exec_responses
+            .last()
+            .expect("Expected to be called after run()");
+        let exec_result = exec_response.last().expect("should have response");
+        match exec_result.as_error() {
+            Some(Error::Exec(execution::Error::Revert(api_error))) => *api_error,
+            error => panic!("should have reverted, got {:?}", error),
+        }
+    }
+
     /// Gets the transform map that's cached between runs
     pub fn get_transforms(&self) -> Vec<AdditiveMap<Key, Transform>> {
         self.transforms.clone()
@@ -550,6 +785,11 @@ where
             .expect("Unable to obtain standard payment contract uref. Please run genesis first.")
     }
 
+    pub fn get_key_recovery_contract_uref(&self) -> URef {
+        self.key_recovery_uref
+            .expect("Unable to obtain key recovery contract uref. Please run genesis first.")
+    }
+
     pub fn get_genesis_transforms(&self) -> &AdditiveMap<Key, engine_shared::transform::Transform> {
         &self
             .genesis_transforms
@@ -621,6 +861,42 @@ where
             .expect("should parse balance into a U512")
     }
 
+    /// Returns a proof of the balance of `public_key`'s main purse at the current post-state hash.
+    pub fn get_account_balance_proof(&self, public_key: PublicKey) -> Option<AccountBalanceProof> {
+        let post_state_hash = Blake2bHash::try_from(self.get_post_state_hash().as_slice())
+            .expect("should convert post-state hash");
+        self.engine_state
+            .get_account_balance_proof(CorrelationId::new(), post_state_hash, public_key)
+            .expect("should get account balance proof")
+    }
+
+    /// Returns the id of the era in progress at the current post-state hash.
+    pub fn get_current_era_id(&self) -> EraId {
+        let post_state_hash = Blake2bHash::try_from(self.get_post_state_hash().as_slice())
+            .expect("should convert post-state hash");
+        self.engine_state
+            .get_current_era_id(CorrelationId::new(), post_state_hash)
+            .expect("should get current era id")
+    }
+
+    /// Returns the validators recorded for era `era_id` at the current post-state hash.
+    pub fn get_era_validators(&self, era_id: EraId) -> Option<era_validators::EraValidators> {
+        let post_state_hash = Blake2bHash::try_from(self.get_post_state_hash().as_slice())
+            .expect("should convert post-state hash");
+        self.engine_state
+            .get_era_validators(CorrelationId::new(), post_state_hash, era_id)
+            .expect("should get era validators")
+    }
+
+    /// Returns the calls scheduled at the current post-state hash which haven't been made yet.
+    pub fn get_scheduled_calls(&self) -> Vec<ScheduledCall> {
+        let post_state_hash = Blake2bHash::try_from(self.get_post_state_hash().as_slice())
+            .expect("should convert post-state hash");
+        self.engine_state
+            .get_scheduled_calls(CorrelationId::new(), post_state_hash)
+            .expect("should get scheduled calls")
+    }
+
     pub fn get_account(&self, public_key: PublicKey) -> Option<Account> {
         let account_value = self
             .query(None, Key::Account(public_key), &[])
@@ -675,6 +951,25 @@ fn create_query_request(post_state: Vec<u8>, base_key: Key, path: Vec<String>) -
     query_request
 }
 
+fn create_check_signers_request(
+    post_state: Vec<u8>,
+    account: PublicKey,
+    signers: &[PublicKey],
+) -> CheckSignersRequest {
+    let mut check_signers_request = CheckSignersRequest::new();
+
+    check_signers_request.set_state_hash(post_state);
+    check_signers_request.set_account_public_key(account.as_bytes().to_vec());
+    check_signers_request.set_signing_keys(
+        signers
+            .iter()
+            .map(|signer| signer.as_bytes().to_vec())
+            .collect(),
+    );
+
+    check_signers_request
+}
+
 #[allow(clippy::implicit_hasher)]
 fn create_commit_request(
     prestate_hash: &[u8],
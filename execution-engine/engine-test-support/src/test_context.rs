@@ -4,7 +4,7 @@ use engine_core::engine_state::{
     genesis::{GenesisAccount, GenesisConfig},
     run_genesis_request::RunGenesisRequest,
 };
-use engine_shared::motes::Motes;
+use engine_shared::{motes::Motes, stored_value::StoredValue, transform::Transform};
 use types::{AccessRights, Key, URef, U512};
 
 use crate::{
@@ -29,6 +29,21 @@ impl TestContext {
         self
     }
 
+    /// Runs the supplied [`Session`], committing the resulting transforms only if execution of the
+    /// contained deploy succeeds.
+    ///
+    /// Returns an [`Error`] describing the failure otherwise, in which case nothing is committed.
+    pub fn try_run(&mut self, session: Session) -> Result<&mut Self> {
+        self.inner.exec(session.inner);
+        if self.inner.is_error() {
+            let index = self.inner.get_exec_responses_count() - 1;
+            let message = self.inner.exec_error_message(index).unwrap_or_default();
+            return Err(Error::from(message));
+        }
+        self.inner.commit().expect_success();
+        Ok(self)
+    }
+
     /// Queries for a [`Value`] stored under the given `key` and `path`.
     ///
     /// Returns an [`Error`] if not found.
@@ -47,6 +62,35 @@ impl TestContext {
         let purse = URef::new(purse_addr, AccessRights::READ);
         self.inner.get_purse_balance(purse)
     }
+
+    /// Gets the address of the main purse of the account under `key`.
+    ///
+    /// Returns `None` if there is no such account.
+    pub fn main_purse_address(&self, key: PublicKey) -> Option<URefAddr> {
+        match self.inner.query(None, Key::Account(key), &[]) {
+            Ok(StoredValue::Account(account)) => Some(account.main_purse().addr()),
+            _ => None,
+        }
+    }
+
+    /// Gets the balance of the main purse of the account under `key`.
+    ///
+    /// Returns `None` if there is no such account.
+    pub fn get_account_balance(&self, key: PublicKey) -> Option<U512> {
+        self.main_purse_address(key)
+            .map(|purse_addr| self.get_balance(purse_addr))
+    }
+
+    /// Gets the [`Value`] written under `key` by the most recently executed session.
+    ///
+    /// Returns `None` if that session didn't overwrite `key`, including if it only added to the
+    /// existing value.
+    pub fn written_value(&self, key: Key) -> Option<Value> {
+        match self.inner.get_transforms().last()?.get(&key)? {
+            Transform::Write(stored_value) => Some(Value::new(stored_value.clone())),
+            _ => None,
+        }
+    }
 }
 
 /// Builder for a [`TestContext`].
@@ -0,0 +1,548 @@
+//! This executable is designed to run a long-lived stream of randomized, valid deploys against an
+//! LMDB-backed global state, periodically checking that a set of invariants still holds.
+//!
+//! The deploys are a mix of transfers (including to new accounts), storing and calling contracts,
+//! and associated key management.  After every `--check-interval` deploys the following invariants
+//! are checked at the latest post-state hash:
+//!
+//! * total supply conservation: the sum of all purse balances held in the mint's balance keyspace
+//!   is unchanged since genesis
+//! * no orphan purses: every purse with a balance is reachable from the named keys or main purse of
+//!   an account, or from the named keys of a stored contract
+//! * trie integrity: every trie node reachable from the post-state hash is present in the store and
+//!   hashes to the key it's stored under
+//!
+//! Any violation causes a panic which includes the RNG seed and the offending post-state hash so
+//! the run can be reproduced.
+
+use std::{
+    collections::HashSet,
+    convert::TryInto,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use clap::{crate_version, App, Arg};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use engine_core::engine_state::{genesis::GenesisAccount, utils::local_key_prefix, EngineConfig};
+use engine_shared::{motes::Motes, newtypes::CorrelationId, stored_value::StoredValue};
+use engine_storage::global_state::StateReader;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, LmdbWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::PublicKey, bytesrepr::ToBytes, AccessRights, CLType, CLValue, Key, URef, U512,
+};
+
+use casperlabs_engine_tests::profiling;
+
+const ABOUT: &str = "Executes randomized deploys against LMDB global state for a given duration, \
+                     periodically checking total supply conservation, the absence of orphan \
+                     purses and trie integrity.";
+
+const DURATION_ARG_NAME: &str = "duration";
+const DURATION_ARG_SHORT: &str = "t";
+const DURATION_ARG_DEFAULT: &str = "3600";
+const DURATION_ARG_VALUE_NAME: &str = "SECONDS";
+const DURATION_ARG_HELP: &str = "Number of seconds to keep executing deploys for";
+
+const CHECK_INTERVAL_ARG_NAME: &str = "check-interval";
+const CHECK_INTERVAL_ARG_SHORT: &str = "i";
+const CHECK_INTERVAL_ARG_DEFAULT: &str = "100";
+const CHECK_INTERVAL_ARG_VALUE_NAME: &str = "NUM";
+const CHECK_INTERVAL_ARG_HELP: &str = "Number of deploys to execute between invariant checks";
+
+const SEED_ARG_NAME: &str = "seed";
+const SEED_ARG_SHORT: &str = "s";
+const SEED_ARG_VALUE_NAME: &str = "NUM";
+const SEED_ARG_HELP: &str =
+    "Seed for the random number generator, allowing a failing run to be reproduced [default: \
+     random]";
+
+const TRANSFER_CONTRACT: &str = "transfer_to_account_u512.wasm";
+const STORE_CONTRACT: &str = "do_nothing_stored.wasm";
+const ADD_ASSOCIATED_KEY_CONTRACT: &str = "add_update_associated_key.wasm";
+const REMOVE_ASSOCIATED_KEY_CONTRACT: &str = "remove_associated_key.wasm";
+const STORED_CONTRACT_NAME: &str = "do_nothing_stored";
+const STORE_DESTINATIONS: [&str; 2] = ["hash", "uref"];
+
+/// Large enough that the faucet account can fund new accounts for the whole run.
+const FAUCET_INITIAL_BALANCE: u64 = u64::max_value();
+const VALIDATOR_BONDED_AMOUNT: u64 = 1_000_000_000;
+/// Amount given to each newly-created account, enough to pay for a few hundred deploys.
+const NEW_ACCOUNT_FUNDING: u64 = 1_000_000_000_000;
+const MAX_ACCOUNTS: usize = 1_000;
+const GLOBAL_STATE_DATA_FILE: &str = "global_state/data.mdb";
+
+fn duration_arg() -> Arg<'static, 'static> {
+    Arg::with_name(DURATION_ARG_NAME)
+        .long(DURATION_ARG_NAME)
+        .short(DURATION_ARG_SHORT)
+        .default_value(DURATION_ARG_DEFAULT)
+        .value_name(DURATION_ARG_VALUE_NAME)
+        .help(DURATION_ARG_HELP)
+}
+
+fn check_interval_arg() -> Arg<'static, 'static> {
+    Arg::with_name(CHECK_INTERVAL_ARG_NAME)
+        .long(CHECK_INTERVAL_ARG_NAME)
+        .short(CHECK_INTERVAL_ARG_SHORT)
+        .default_value(CHECK_INTERVAL_ARG_DEFAULT)
+        .value_name(CHECK_INTERVAL_ARG_VALUE_NAME)
+        .help(CHECK_INTERVAL_ARG_HELP)
+}
+
+fn seed_arg() -> Arg<'static, 'static> {
+    Arg::with_name(SEED_ARG_NAME)
+        .long(SEED_ARG_NAME)
+        .short(SEED_ARG_SHORT)
+        .value_name(SEED_ARG_VALUE_NAME)
+        .help(SEED_ARG_HELP)
+}
+
+#[derive(Debug)]
+struct Args {
+    duration: Duration,
+    check_interval: usize,
+    seed: u64,
+    data_dir: PathBuf,
+}
+
+impl Args {
+    fn new() -> Self {
+        let exe_name = profiling::exe_name();
+        let data_dir_arg = profiling::data_dir_arg();
+        let arg_matches = App::new(&exe_name)
+            .version(crate_version!())
+            .about(ABOUT)
+            .arg(duration_arg())
+            .arg(check_interval_arg())
+            .arg(seed_arg())
+            .arg(data_dir_arg)
+            .get_matches();
+        let duration = arg_matches
+            .value_of(DURATION_ARG_NAME)
+            .map(profiling::parse_count)
+            .map(|seconds| Duration::from_secs(seconds as u64))
+            .expect("should have duration");
+        let check_interval = arg_matches
+            .value_of(CHECK_INTERVAL_ARG_NAME)
+            .map(profiling::parse_count)
+            .expect("should have check interval");
+        let seed = arg_matches
+            .value_of(SEED_ARG_NAME)
+            .map(|seed| seed.parse().expect("Expected an integral seed"))
+            .unwrap_or_else(rand::random);
+        let data_dir = profiling::data_dir(&arg_matches);
+        Args {
+            duration,
+            check_interval,
+            seed,
+            data_dir,
+        }
+    }
+}
+
+/// The harness' own view of an account, used to pick deploys which are expected to be valid.
+struct SoakAccount {
+    public_key: PublicKey,
+    associated_keys: Vec<PublicKey>,
+    has_stored_contract: bool,
+}
+
+impl SoakAccount {
+    fn new(public_key: PublicKey) -> Self {
+        SoakAccount {
+            public_key,
+            associated_keys: Vec::new(),
+            has_stored_contract: false,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Action {
+    TransferToNewAccount,
+    TransferToExistingAccount,
+    StoreContract,
+    CallStoredContract,
+    AddAssociatedKey,
+    RemoveAssociatedKey,
+}
+
+const ACTIONS: [Action; 6] = [
+    Action::TransferToNewAccount,
+    Action::TransferToExistingAccount,
+    Action::StoreContract,
+    Action::CallStoredContract,
+    Action::AddAssociatedKey,
+    Action::RemoveAssociatedKey,
+];
+
+#[derive(Debug, Default)]
+struct Stats {
+    deploys: usize,
+    failures: usize,
+    checks: usize,
+}
+
+struct Soak {
+    rng: StdRng,
+    accounts: Vec<SoakAccount>,
+    builder: LmdbWasmTestBuilder,
+    total_supply: U512,
+    stats: Stats,
+}
+
+impl Soak {
+    fn new(data_dir: &Path, seed: u64) -> Self {
+        let engine_config = EngineConfig::new()
+            .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+            .with_enable_bonding(cfg!(feature = "enable-bonding"));
+        let mut builder = LmdbWasmTestBuilder::new_with_config(data_dir, engine_config);
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let genesis_accounts = vec![
+            GenesisAccount::new(
+                DEFAULT_ACCOUNT_ADDR,
+                Motes::new(FAUCET_INITIAL_BALANCE.into()),
+                Motes::new(U512::zero()),
+            ),
+            GenesisAccount::new(
+                PublicKey::ed25519_from(rng.gen()),
+                Motes::new(NEW_ACCOUNT_FUNDING.into()),
+                Motes::new(VALIDATOR_BONDED_AMOUNT.into()),
+            ),
+        ];
+        let accounts = genesis_accounts
+            .iter()
+            .map(|genesis_account| SoakAccount::new(genesis_account.public_key()))
+            .collect();
+        let run_genesis_request = utils::create_run_genesis_request(genesis_accounts);
+        builder.run_genesis(&run_genesis_request);
+
+        let mut soak = Soak {
+            rng,
+            accounts,
+            builder,
+            total_supply: U512::zero(),
+            stats: Stats::default(),
+        };
+        soak.total_supply = soak
+            .check_total_supply()
+            .expect("genesis should record the total supply");
+        soak
+    }
+
+    fn run(&mut self, duration: Duration, check_interval: usize, data_dir: &Path) {
+        let start = Instant::now();
+        while start.elapsed() < duration {
+            for _ in 0..check_interval {
+                self.execute_random_deploy();
+            }
+            self.check_invariants();
+            self.report(start.elapsed(), data_dir);
+            // Drop the cached responses and transforms which the builder accumulates with every
+            // exec, so that the harness itself doesn't grow without bound.
+            self.builder = LmdbWasmTestBuilder::from_result(self.builder.finish());
+        }
+    }
+
+    fn execute_random_deploy(&mut self) {
+        let action = *ACTIONS
+            .choose(&mut self.rng)
+            .expect("should choose an action");
+        // Accounts other than the faucet may run out of funds, so most deploys come from the
+        // faucet.
+        let sender_index = if self.rng.gen_bool(0.5) {
+            0
+        } else {
+            self.rng.gen_range(0, self.accounts.len())
+        };
+        let sender = self.accounts[sender_index].public_key;
+
+        let deploy_builder = match action {
+            Action::TransferToNewAccount if self.accounts.len() < MAX_ACCOUNTS => {
+                let target = PublicKey::ed25519_from(self.rng.gen());
+                let amount = U512::from(NEW_ACCOUNT_FUNDING);
+                DeployItemBuilder::new().with_session_code(TRANSFER_CONTRACT, (target, amount))
+            }
+            Action::TransferToNewAccount | Action::TransferToExistingAccount => {
+                let target = self
+                    .accounts
+                    .choose(&mut self.rng)
+                    .expect("should choose an account")
+                    .public_key;
+                let amount = U512::from(self.rng.gen_range(1, NEW_ACCOUNT_FUNDING / 100));
+                DeployItemBuilder::new().with_session_code(TRANSFER_CONTRACT, (target, amount))
+            }
+            Action::StoreContract => {
+                let destination = *STORE_DESTINATIONS
+                    .choose(&mut self.rng)
+                    .expect("should choose a destination");
+                DeployItemBuilder::new()
+                    .with_session_code(STORE_CONTRACT, (destination.to_string(),))
+            }
+            Action::CallStoredContract if self.accounts[sender_index].has_stored_contract => {
+                DeployItemBuilder::new().with_stored_session_named_key(STORED_CONTRACT_NAME, ())
+            }
+            Action::CallStoredContract | Action::AddAssociatedKey => {
+                let associated_key = PublicKey::ed25519_from(self.rng.gen());
+                DeployItemBuilder::new()
+                    .with_session_code(ADD_ASSOCIATED_KEY_CONTRACT, (associated_key,))
+            }
+            Action::RemoveAssociatedKey => {
+                match self.accounts[sender_index]
+                    .associated_keys
+                    .choose(&mut self.rng)
+                {
+                    Some(associated_key) => DeployItemBuilder::new()
+                        .with_session_code(REMOVE_ASSOCIATED_KEY_CONTRACT, (*associated_key,)),
+                    None => {
+                        let destination = *STORE_DESTINATIONS
+                            .choose(&mut self.rng)
+                            .expect("should choose a destination");
+                        DeployItemBuilder::new()
+                            .with_session_code(STORE_CONTRACT, (destination.to_string(),))
+                    }
+                }
+            }
+        };
+
+        let deploy = deploy_builder
+            .with_address(sender)
+            .with_deploy_hash(self.rng.gen())
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[sender])
+            .build();
+        let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+        self.builder.exec(exec_request).commit();
+        self.stats.deploys += 1;
+        if self.builder.is_error() {
+            self.stats.failures += 1;
+            return;
+        }
+
+        // Keep the harness' model of the accounts in line with the successful deploy.
+        if let Action::TransferToNewAccount = action {
+            if let Some(public_key) = self.new_account_from_transforms(sender) {
+                self.accounts.push(SoakAccount::new(public_key));
+            }
+        }
+        let account = self
+            .builder
+            .get_account(sender)
+            .expect("sender account should exist");
+        let soak_account = &mut self.accounts[sender_index];
+        soak_account.associated_keys = account
+            .get_associated_keys()
+            .map(|(public_key, _weight)| *public_key)
+            .filter(|public_key| *public_key != sender)
+            .collect();
+        soak_account.has_stored_contract = account.named_keys().contains_key(STORED_CONTRACT_NAME);
+    }
+
+    /// Returns the public key of the account created by the last transfer, if any.
+    fn new_account_from_transforms(&self, sender: PublicKey) -> Option<PublicKey> {
+        let transforms = self.builder.get_transforms();
+        let last_transforms = transforms.last()?;
+        last_transforms.keys().find_map(|key| match key {
+            Key::Account(public_key)
+                if *public_key != sender
+                    && self
+                        .accounts
+                        .iter()
+                        .all(|account| account.public_key != *public_key) =>
+            {
+                Some(*public_key)
+            }
+            _ => None,
+        })
+    }
+
+    fn check_invariants(&mut self) {
+        self.stats.checks += 1;
+        let post_state_hash = self.builder.get_post_state_hash();
+        let fail = |message: String| -> ! {
+            panic!(
+                "Invariant violated after {} deploys at post-state hash {}: {}",
+                self.stats.deploys,
+                base16::encode_lower(&post_state_hash),
+                message
+            )
+        };
+
+        match self.check_total_supply() {
+            Ok(total_supply) if total_supply != self.total_supply => fail(format!(
+                "total supply changed from {} to {}",
+                self.total_supply, total_supply
+            )),
+            Ok(_) => {}
+            Err(error) => fail(error),
+        }
+
+        let orphan_purses = self.orphan_purses();
+        if !orphan_purses.is_empty() {
+            fail(format!("found orphan purses: {:?}", orphan_purses));
+        }
+
+        let missing_trie_keys = self
+            .builder
+            .get_engine_state()
+            .missing_trie_keys(
+                CorrelationId::new(),
+                vec![post_state_hash
+                    .as_slice()
+                    .try_into()
+                    .expect("should be a valid hash")],
+            )
+            .expect("should check trie integrity");
+        if !missing_trie_keys.is_empty() {
+            fail(format!(
+                "found missing or corrupted trie nodes: {:?}",
+                missing_trie_keys
+            ));
+        }
+    }
+
+    /// Returns all keys at the latest post-state hash whose serialized form starts with `prefix`.
+    fn keys_with_prefix(&self, prefix: &[u8]) -> Vec<Key> {
+        let post_state_hash = self.builder.get_post_state_hash();
+        let tracking_copy = self
+            .builder
+            .get_engine_state()
+            .tracking_copy(
+                post_state_hash
+                    .as_slice()
+                    .try_into()
+                    .expect("should be a valid hash"),
+            )
+            .expect("should check out post-state")
+            .expect("post-state should exist");
+        tracking_copy
+            .reader()
+            .keys_with_prefix(CorrelationId::new(), prefix)
+            .expect("should list keys")
+    }
+
+    /// Returns the keys in the mint's balance keyspace, i.e. one per purse.
+    fn balance_keys(&self) -> Vec<Key> {
+        let mint_seed = self.builder.get_mint_contract_uref().addr();
+        let prefix = local_key_prefix(mint_seed);
+        // The mint's local state also holds its total supply record, which isn't a purse.
+        self.keys_with_prefix(&prefix)
+            .into_iter()
+            .filter(|key| *self.query_cl_value(*key).cl_type() == CLType::Key)
+            .collect()
+    }
+
+    fn check_total_supply(&self) -> Result<U512, String> {
+        let post_state_hash = self.builder.get_post_state_hash();
+        self.builder
+            .get_engine_state()
+            .check_total_supply(
+                CorrelationId::new(),
+                *DEFAULT_PROTOCOL_VERSION,
+                post_state_hash
+                    .as_slice()
+                    .try_into()
+                    .expect("should be a valid hash"),
+            )
+            .map_err(|error| error.to_string())
+    }
+
+    /// Returns the balance keys which can't be reached from any account or stored contract.
+    fn orphan_purses(&self) -> Vec<Key> {
+        let mint_seed = self.builder.get_mint_contract_uref().addr();
+        let mut reachable_urefs: Vec<URef> = Vec::new();
+        for key in self.keys_with_prefix(&key_tag_prefix(Key::Account(DEFAULT_ACCOUNT_ADDR))) {
+            if let Ok(StoredValue::Account(account)) = self.builder.query(None, key, &[]) {
+                reachable_urefs.push(account.main_purse());
+                reachable_urefs.extend(
+                    account
+                        .named_keys()
+                        .values()
+                        .filter_map(Key::as_uref)
+                        .copied(),
+                );
+            }
+        }
+        let contract_prefixes = [
+            key_tag_prefix(Key::Hash([0; 32])),
+            key_tag_prefix(Key::URef(URef::new([0; 32], AccessRights::READ))),
+        ];
+        for prefix in contract_prefixes.iter() {
+            for key in self.keys_with_prefix(prefix) {
+                if let Ok(StoredValue::Contract(contract)) = self.builder.query(None, key, &[]) {
+                    reachable_urefs.extend(
+                        contract
+                            .named_keys()
+                            .values()
+                            .filter_map(Key::as_uref)
+                            .copied(),
+                    );
+                }
+            }
+        }
+        let reachable_balance_keys: HashSet<Key> = reachable_urefs
+            .into_iter()
+            .map(|uref| {
+                let purse_bytes = uref.addr().to_bytes().expect("should serialize purse addr");
+                Key::local(mint_seed, &purse_bytes)
+            })
+            .collect();
+
+        self.balance_keys()
+            .into_iter()
+            .filter(|balance_key| !reachable_balance_keys.contains(balance_key))
+            .collect()
+    }
+
+    fn query_cl_value(&self, key: Key) -> CLValue {
+        let stored_value = self
+            .builder
+            .query(None, key, &[])
+            .unwrap_or_else(|error| panic!("should query {}: {}", key, error));
+        stored_value
+            .try_into()
+            .unwrap_or_else(|error| panic!("value under {} should be a CLValue: {:?}", key, error))
+    }
+
+    fn report(&self, elapsed: Duration, data_dir: &Path) {
+        let data_size = fs::metadata(data_dir.join(GLOBAL_STATE_DATA_FILE))
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        println!(
+            "elapsed: {}s, deploys: {}, failed deploys: {}, invariant checks: {}, accounts: {}, \
+             global state size: {} bytes",
+            elapsed.as_secs(),
+            self.stats.deploys,
+            self.stats.failures,
+            self.stats.checks,
+            self.accounts.len(),
+            data_size
+        );
+    }
+}
+
+/// Returns the serialized tag common to all keys of the same variant as `key`.
+fn key_tag_prefix(key: Key) -> Vec<u8> {
+    let mut key_bytes = key.to_bytes().expect("should serialize key");
+    key_bytes.truncate(1);
+    key_bytes
+}
+
+fn main() {
+    let args = Args::new();
+    println!("seed: {}", args.seed);
+
+    let mut soak = Soak::new(&args.data_dir, args.seed);
+    soak.run(args.duration, args.check_interval, &args.data_dir);
+}
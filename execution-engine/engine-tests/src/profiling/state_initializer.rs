@@ -12,7 +12,8 @@ use engine_core::engine_state::{
 use engine_test_support::{
     internal::{
         utils, DeployItemBuilder, ExecuteRequestBuilder, LmdbWasmTestBuilder, DEFAULT_ACCOUNTS,
-        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS, KEY_RECOVERY_INSTALL_CONTRACT,
         MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT, STANDARD_PAYMENT_CONTRACT,
         STANDARD_PAYMENT_INSTALL_CONTRACT,
     },
@@ -70,12 +71,15 @@ fn main() {
     let pos_installer_bytes = utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT);
     let standard_payment_installer_bytes =
         utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
+    let key_recovery_installer_bytes = utils::read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT);
     let exec_config = ExecConfig::new(
         mint_installer_bytes,
         pos_installer_bytes,
         standard_payment_installer_bytes,
+        key_recovery_installer_bytes,
         DEFAULT_ACCOUNTS.clone(),
         *DEFAULT_WASM_COSTS,
+        DEFAULT_UNBONDING_DELAY,
     );
     let run_genesis_request = RunGenesisRequest::new(
         *DEFAULT_GENESIS_CONFIG_HASH,
@@ -32,7 +32,7 @@ fn should_deploy_with_authorized_identity_key() {
 #[test]
 fn should_raise_auth_failure_with_invalid_key() {
     // tests that authorized keys that does not belong to account raises
-    // Error::Authorization
+    // Error::AuthorizationFailure
     let key_1 = PublicKey::ed25519_from([254; 32]);
     assert_ne!(DEFAULT_ACCOUNT_ADDR, key_1);
 
@@ -66,16 +66,21 @@ fn should_raise_auth_failure_with_invalid_key() {
         "{:?}",
         deploy_result
     );
-    let message = format!("{}", deploy_result.as_error().unwrap());
-
-    assert_eq!(message, format!("{}", engine_state::Error::Authorization))
+    match deploy_result.as_error() {
+        Some(engine_state::Error::AuthorizationFailure(failure)) => {
+            assert!(failure.recognized_keys().is_empty());
+            assert_eq!(failure.unknown_keys().len(), 1);
+            assert!(failure.unknown_keys().contains(&key_1));
+        }
+        other => panic!("expected authorization failure, got {:?}", other),
+    }
 }
 
 #[ignore]
 #[test]
 fn should_raise_auth_failure_with_invalid_keys() {
     // tests that authorized keys that does not belong to account raises
-    // Error::Authorization
+    // Error::AuthorizationFailure
     let key_1 = PublicKey::ed25519_from([254; 32]);
     let key_2 = PublicKey::ed25519_from([253; 32]);
     let key_3 = PublicKey::ed25519_from([252; 32]);
@@ -109,9 +114,15 @@ fn should_raise_auth_failure_with_invalid_keys() {
         .expect("should have at least one deploy result");
 
     assert!(deploy_result.has_precondition_failure());
-    let message = format!("{}", deploy_result.as_error().unwrap());
-
-    assert_eq!(message, format!("{}", engine_state::Error::Authorization))
+    match deploy_result.as_error() {
+        Some(engine_state::Error::AuthorizationFailure(failure)) => {
+            assert!(failure.recognized_keys().is_empty());
+            assert_eq!(failure.unknown_keys().len(), 3);
+            assert_eq!(failure.total_weight(), Weight::new(0));
+            assert_eq!(failure.shortfall(), Weight::new(1));
+        }
+        other => panic!("expected authorization failure, got {:?}", other),
+    }
 }
 
 #[ignore]
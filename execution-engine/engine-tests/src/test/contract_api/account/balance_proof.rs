@@ -0,0 +1,116 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::Error;
+use engine_shared::newtypes::Blake2bHash;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const UNKNOWN_ADDR: PublicKey = PublicKey::ed25519_from([254u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+fn post_state_hash(builder: &InMemoryWasmTestBuilder) -> Blake2bHash {
+    Blake2bHash::try_from(builder.get_post_state_hash().as_slice())
+        .expect("should convert post-state hash")
+}
+
+fn main_purse_balance(builder: &InMemoryWasmTestBuilder, public_key: PublicKey) -> U512 {
+    let account = builder
+        .get_account(public_key)
+        .expect("should have account");
+    builder.get_purse_balance(account.main_purse())
+}
+
+fn verified_balance(builder: &InMemoryWasmTestBuilder, public_key: PublicKey) -> U512 {
+    builder
+        .get_account_balance_proof(public_key)
+        .expect("should have proof")
+        .verify(post_state_hash(builder), public_key)
+        .expect("proof should verify")
+}
+
+fn transfer_to_account_1(builder: &mut InMemoryWasmTestBuilder) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_prove_genesis_account_balance() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    assert_eq!(
+        verified_balance(&builder, DEFAULT_ACCOUNT_ADDR),
+        main_purse_balance(&builder, DEFAULT_ACCOUNT_ADDR)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_prove_balances_after_transfer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    transfer_to_account_1(&mut builder);
+
+    assert_eq!(
+        verified_balance(&builder, ACCOUNT_1_ADDR),
+        U512::from(TRANSFER_AMOUNT)
+    );
+    assert_eq!(
+        verified_balance(&builder, DEFAULT_ACCOUNT_ADDR),
+        main_purse_balance(&builder, DEFAULT_ACCOUNT_ADDR)
+    );
+
+    // A second transfer only touches the purses, which the index traces back to both accounts
+    transfer_to_account_1(&mut builder);
+
+    assert_eq!(
+        verified_balance(&builder, ACCOUNT_1_ADDR),
+        U512::from(2 * TRANSFER_AMOUNT)
+    );
+    assert_eq!(
+        verified_balance(&builder, DEFAULT_ACCOUNT_ADDR),
+        main_purse_balance(&builder, DEFAULT_ACCOUNT_ADDR)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_prove_unknown_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    assert!(builder.get_account_balance_proof(UNKNOWN_ADDR).is_none());
+}
+
+#[ignore]
+#[test]
+fn should_not_verify_proof_against_other_state_or_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let genesis_hash = post_state_hash(&builder);
+    transfer_to_account_1(&mut builder);
+
+    let proof = builder
+        .get_account_balance_proof(ACCOUNT_1_ADDR)
+        .expect("should have proof");
+
+    match proof.verify(genesis_hash, ACCOUNT_1_ADDR) {
+        Err(Error::InvalidAccountBalanceProof) => {}
+        other => panic!("should not verify against genesis, got {:?}", other),
+    }
+    match proof.verify(post_state_hash(&builder), DEFAULT_ACCOUNT_ADDR) {
+        Err(Error::InvalidAccountBalanceProof) => {}
+        other => panic!("should not verify for another account, got {:?}", other),
+    }
+}
@@ -0,0 +1,123 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::{PublicKey, Weight};
+
+const CONTRACT_AUTHORIZED_KEYS: &str = "authorized_keys.wasm";
+// Associated with a weight of 100 by the `authorized_keys` contract
+const KEY_1: PublicKey = PublicKey::ed25519_from([123; 32]);
+const UNKNOWN_KEY: PublicKey = PublicKey::ed25519_from([254; 32]);
+const KEY_MANAGEMENT_THRESHOLD: u8 = 3;
+const DEPLOYMENT_THRESHOLD: u8 = 2;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_AUTHORIZED_KEYS,
+        (
+            Weight::new(KEY_MANAGEMENT_THRESHOLD),
+            Weight::new(DEPLOYMENT_THRESHOLD),
+        ),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn deploy_succeeds(builder: &mut InMemoryWasmTestBuilder, signers: &[PublicKey]) -> bool {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_session_code(CONTRACT_AUTHORIZED_KEYS, (Weight::new(0), Weight::new(0)))
+        .with_deploy_hash([1u8; 32])
+        .with_authorization_keys(signers)
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+
+    builder.exec(exec_request);
+    !builder.is_error()
+}
+
+#[ignore]
+#[test]
+fn should_report_insufficient_weight_of_single_signer() {
+    let mut builder = setup();
+
+    let signers_check = builder
+        .check_signers(None, DEFAULT_ACCOUNT_ADDR, &[DEFAULT_ACCOUNT_ADDR])
+        .expect("should check signers");
+
+    assert_eq!(signers_check.get_total_weight(), 1);
+    assert_eq!(
+        signers_check.get_deployment_threshold(),
+        u32::from(DEPLOYMENT_THRESHOLD)
+    );
+    assert_eq!(
+        signers_check.get_key_management_threshold(),
+        u32::from(KEY_MANAGEMENT_THRESHOLD)
+    );
+    assert!(signers_check.get_unknown_keys().is_empty());
+    assert!(!signers_check.get_can_deploy());
+    assert!(!signers_check.get_can_manage_keys());
+
+    assert!(!deploy_succeeds(&mut builder, &[DEFAULT_ACCOUNT_ADDR]));
+}
+
+#[ignore]
+#[test]
+fn should_report_sufficient_combined_weight() {
+    let mut builder = setup();
+
+    let signers = [DEFAULT_ACCOUNT_ADDR, KEY_1];
+    let signers_check = builder
+        .check_signers(None, DEFAULT_ACCOUNT_ADDR, &signers)
+        .expect("should check signers");
+
+    assert_eq!(signers_check.get_total_weight(), 101);
+    assert!(signers_check.get_unknown_keys().is_empty());
+    assert!(signers_check.get_can_deploy());
+    assert!(signers_check.get_can_manage_keys());
+
+    assert!(deploy_succeeds(&mut builder, &signers));
+}
+
+#[ignore]
+#[test]
+fn should_report_unknown_signer() {
+    let mut builder = setup();
+
+    let signers = [KEY_1, UNKNOWN_KEY];
+    let signers_check = builder
+        .check_signers(None, DEFAULT_ACCOUNT_ADDR, &signers)
+        .expect("should check signers");
+
+    assert_eq!(signers_check.get_total_weight(), 100);
+    assert_eq!(
+        signers_check.get_unknown_keys(),
+        &[UNKNOWN_KEY.as_bytes().to_vec()]
+    );
+    assert!(!signers_check.get_can_deploy());
+    assert!(!signers_check.get_can_manage_keys());
+
+    assert!(!deploy_succeeds(&mut builder, &signers));
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_check_signers_of_missing_account() {
+    let builder = setup();
+
+    let result = builder.check_signers(None, UNKNOWN_KEY, &[UNKNOWN_KEY]);
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,131 @@
+use contract::args_parser::ArgsParser;
+use engine_core::engine_state::upgrade::ActivationPoint;
+use engine_grpc_server::engine_server::ipc::DeployCode;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, bytesrepr::ToBytes, ApiError, ProtocolVersion};
+
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const CONTRACT_INSTALL_PERMISSION: &str = "contract_install_permission.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_DO_NOTHING_STORED: &str = "do_nothing_stored.wasm";
+
+/// Runs an upgrade whose installer sets whether `account` may install contracts, returning the
+/// new protocol version.
+fn upgrade_with_permission(
+    builder: &mut InMemoryWasmTestBuilder,
+    account: PublicKey,
+    allowed: bool,
+) -> ProtocolVersion {
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = {
+        let bytes = utils::read_wasm_file_bytes(CONTRACT_INSTALL_PERMISSION);
+        let args = (account, allowed)
+            .parse()
+            .expect("should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("should serialize args");
+        let mut installer_code = DeployCode::new();
+        installer_code.set_code(bytes);
+        installer_code.set_args(args);
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_installer_code(installer_code)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(
+        upgrade_response.has_success(),
+        "upgrade_response expected success"
+    );
+
+    new_protocol_version
+}
+
+#[ignore]
+#[test]
+fn should_allow_contract_installs_by_default() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.can_install_contracts());
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING_STORED,
+        ("hash",),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_non_system_account_to_set_permission() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_INSTALL_PERMISSION,
+        (DEFAULT_ACCOUNT_ADDR, false),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+    assert_eq!(builder.get_revert_error(), ApiError::PermissionDenied);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.can_install_contracts());
+}
+
+#[ignore]
+#[test]
+fn should_only_allow_calls_once_contract_installs_are_revoked() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let new_protocol_version = upgrade_with_permission(&mut builder, DEFAULT_ACCOUNT_ADDR, false);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account.can_install_contracts());
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ())
+            .with_protocol_version(new_protocol_version)
+            .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING_STORED,
+        ("hash",),
+    )
+    .with_protocol_version(new_protocol_version)
+    .build();
+    builder.exec(exec_request).commit();
+    assert_eq!(builder.get_revert_error(), ApiError::PermissionDenied);
+}
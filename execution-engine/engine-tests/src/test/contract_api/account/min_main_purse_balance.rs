@@ -0,0 +1,107 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{EngineConfig, Error},
+    execution,
+};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::PublicKey, system_contract_errors::mint, ApiError, U512};
+
+const CONTRACT_MINT_TRANSFER: &str = "mint_transfer.wasm";
+const NEW_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const MIN_MAIN_PURSE_BALANCE: u64 = 1_000_000;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_min_main_purse_balance(Some(MIN_MAIN_PURSE_BALANCE.into()));
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn exec_native_transfer(builder: &mut InMemoryWasmTestBuilder, amount: U512) {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_native_transfer(NEW_ACCOUNT_ADDR, amount)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    builder.exec(exec_request).commit();
+}
+
+fn assert_below_minimum_balance(builder: &InMemoryWasmTestBuilder) {
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have response")
+        .last()
+        .expect("should have result");
+    let expected_error = ApiError::from(mint::Error::BelowMinimumBalance);
+    assert_matches!(
+        response.as_error(),
+        Some(Error::Exec(execution::Error::Revert(error))) if *error == expected_error
+    );
+}
+
+/// The most the default account can transfer in its session while keeping the minimum balance,
+/// given that its payment has already been taken from its main purse.
+fn max_transfer_amount() -> U512 {
+    U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - *DEFAULT_PAYMENT - MIN_MAIN_PURSE_BALANCE
+}
+
+#[ignore]
+#[test]
+fn should_allow_transfer_down_to_min_main_purse_balance() {
+    let mut builder = setup();
+
+    exec_native_transfer(&mut builder, max_transfer_amount());
+    assert!(!builder.is_error(), "transfer should succeed");
+
+    let account = builder
+        .get_account(NEW_ACCOUNT_ADDR)
+        .expect("should create account");
+    assert_eq!(
+        builder.get_purse_balance(account.main_purse()),
+        max_transfer_amount()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_refuse_transfer_below_min_main_purse_balance() {
+    let mut builder = setup();
+
+    exec_native_transfer(&mut builder, max_transfer_amount() + 1);
+
+    assert_below_minimum_balance(&builder);
+    assert!(builder.get_account(NEW_ACCOUNT_ADDR).is_none());
+}
+
+#[ignore]
+#[test]
+fn should_refuse_direct_mint_transfer_below_min_main_purse_balance() {
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MINT_TRANSFER,
+        (max_transfer_amount() + 1,),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    assert_below_minimum_balance(&builder);
+}
@@ -1,4 +1,11 @@
 mod associated_keys;
 mod authorized_keys;
+mod balance_proof;
+mod check_signers;
+mod contract_install_permission;
 mod key_management_thresholds;
+mod min_main_purse_balance;
 mod named_keys;
+mod native_key_management;
+mod native_transfer;
+mod update_associated_keys;
@@ -0,0 +1,132 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::{executable_deploy_item::ExecutableDeployItem, Error};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::{ActionType, AssociatedKeysUpdate, PublicKey, Weight};
+
+const CONTRACT_AUTHORIZED_KEYS: &str = "authorized_keys.wasm";
+const NEW_KEY: PublicKey = PublicKey::ed25519_from([42; 32]);
+
+fn exec_native_key_management(
+    builder: &mut InMemoryWasmTestBuilder,
+    updates: Vec<AssociatedKeysUpdate>,
+) {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_native_key_management(updates)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_rotate_key_without_wasm() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec_native_key_management(
+        &mut builder,
+        vec![
+            AssociatedKeysUpdate::Add(NEW_KEY, Weight::new(3)),
+            AssociatedKeysUpdate::Remove(DEFAULT_ACCOUNT_ADDR),
+            AssociatedKeysUpdate::SetThreshold(ActionType::KeyManagement, Weight::new(3)),
+            AssociatedKeysUpdate::SetThreshold(ActionType::Deployment, Weight::new(2)),
+        ],
+    );
+    assert!(!builder.is_error(), "updates should be applied");
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.get_associated_key_weight(DEFAULT_ACCOUNT_ADDR),
+        None
+    );
+    assert_eq!(
+        account.get_associated_key_weight(NEW_KEY),
+        Some(&Weight::new(3))
+    );
+    assert_eq!(*account.action_thresholds().deployment(), Weight::new(2));
+    assert_eq!(
+        *account.action_thresholds().key_management(),
+        Weight::new(3)
+    );
+
+    // The new key alone can now deploy for the account
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_session_code(CONTRACT_AUTHORIZED_KEYS, (Weight::new(3), Weight::new(2)))
+        .with_authorization_keys(&[NEW_KEY])
+        .with_deploy_hash([2u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_leave_account_unchanged_if_native_updates_fail() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // Removing the only key would leave the account unable to meet its thresholds
+    exec_native_key_management(
+        &mut builder,
+        vec![
+            AssociatedKeysUpdate::Add(NEW_KEY, Weight::new(1)),
+            AssociatedKeysUpdate::Remove(DEFAULT_ACCOUNT_ADDR),
+            AssociatedKeysUpdate::Remove(NEW_KEY),
+        ],
+    );
+    assert!(builder.is_error(), "updates should be rejected");
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.get_associated_key_weight(DEFAULT_ACCOUNT_ADDR),
+        Some(&Weight::new(1))
+    );
+    assert_eq!(account.get_associated_key_weight(NEW_KEY), None);
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_native_key_management_as_payment() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let updates = vec![AssociatedKeysUpdate::Add(NEW_KEY, Weight::new(1))];
+    let mut deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_native_key_management(updates.clone())
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1u8; 32])
+        .build();
+    deploy.payment = ExecutableDeployItem::NativeKeyManagement { updates };
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    builder.exec(exec_request);
+
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let error = response
+        .last()
+        .expect("should have response")
+        .as_error()
+        .expect("should have error");
+    assert_matches!(error, Error::NativePayment);
+}
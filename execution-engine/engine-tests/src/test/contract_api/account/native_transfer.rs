@@ -0,0 +1,82 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{EngineConfig, Error},
+    execution,
+};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, ApiError, U512};
+
+const NEW_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const MIN_ACCOUNT_CREATION_BALANCE: u64 = 1_000;
+
+fn setup(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let engine_config = engine_config
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"));
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn exec_native_transfer(builder: &mut InMemoryWasmTestBuilder, amount: U512) {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_native_transfer(NEW_ACCOUNT_ADDR, amount)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_create_account_on_native_transfer() {
+    let mut builder = setup(
+        EngineConfig::new()
+            .with_min_account_creation_balance(Some(MIN_ACCOUNT_CREATION_BALANCE.into())),
+    );
+
+    let amount = U512::from(MIN_ACCOUNT_CREATION_BALANCE);
+    exec_native_transfer(&mut builder, amount);
+    assert!(!builder.is_error(), "transfer should succeed");
+
+    let account = builder
+        .get_account(NEW_ACCOUNT_ADDR)
+        .expect("should create account");
+    assert_eq!(builder.get_purse_balance(account.main_purse()), amount);
+}
+
+#[ignore]
+#[test]
+fn should_not_create_account_below_min_creation_balance() {
+    let mut builder = setup(
+        EngineConfig::new()
+            .with_min_account_creation_balance(Some(MIN_ACCOUNT_CREATION_BALANCE.into())),
+    );
+
+    exec_native_transfer(&mut builder, U512::from(MIN_ACCOUNT_CREATION_BALANCE - 1));
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have response")
+        .last()
+        .expect("should have result");
+    assert_matches!(
+        response.as_error(),
+        Some(Error::Exec(execution::Error::Revert(ApiError::Transfer)))
+    );
+    assert!(builder.get_account(NEW_ACCOUNT_ADDR).is_none());
+}
@@ -0,0 +1,113 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, Weight},
+    ApiError,
+};
+
+const CONTRACT_AUTHORIZED_KEYS: &str = "authorized_keys.wasm";
+const CONTRACT_UPDATE_ASSOCIATED_KEYS: &str = "update_associated_keys.wasm";
+const COMMAND_ROTATE: &str = "rotate";
+const COMMAND_REMOVE_ALL: &str = "remove_all";
+const COMMAND_PARTIAL: &str = "partial";
+const NEW_KEY: PublicKey = PublicKey::ed25519_from([42; 32]);
+
+fn update_associated_keys(builder: &mut InMemoryWasmTestBuilder, command: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_UPDATE_ASSOCIATED_KEYS,
+        (command, NEW_KEY),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_rotate_key_and_raise_thresholds_in_one_batch() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    update_associated_keys(&mut builder, COMMAND_ROTATE);
+    assert!(!builder.is_error(), "batch should be applied");
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.get_associated_key_weight(DEFAULT_ACCOUNT_ADDR),
+        None
+    );
+    assert_eq!(
+        account.get_associated_key_weight(NEW_KEY),
+        Some(&Weight::new(3))
+    );
+    assert_eq!(*account.action_thresholds().deployment(), Weight::new(2));
+    assert_eq!(
+        *account.action_thresholds().key_management(),
+        Weight::new(3)
+    );
+
+    // The new key alone can now deploy for the account
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_session_code(CONTRACT_AUTHORIZED_KEYS, (Weight::new(3), Weight::new(2)))
+        .with_authorization_keys(&[NEW_KEY])
+        .with_deploy_hash([1u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_remove_last_key() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    update_associated_keys(&mut builder, COMMAND_REMOVE_ALL);
+    assert_eq!(
+        builder.get_revert_error(),
+        ApiError::InsufficientTotalWeight
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_apply_any_update_of_failed_batch() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    update_associated_keys(&mut builder, COMMAND_PARTIAL);
+    assert!(!builder.is_error(), "contract should observe the failure");
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(account.get_associated_key_weight(NEW_KEY), None);
+}
+
+#[ignore]
+#[test]
+fn should_not_update_keys_without_key_management_weight() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // Raise the key management threshold above the weight of the default account's own key
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_AUTHORIZED_KEYS,
+        (Weight::new(3), Weight::new(1)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    update_associated_keys(&mut builder, COMMAND_ROTATE);
+    assert_eq!(builder.get_revert_error(), ApiError::PermissionDenied);
+}
@@ -0,0 +1,62 @@
+use std::{collections::BTreeSet, convert::TryInto, iter::FromIterator};
+
+use engine_shared::newtypes::CorrelationId;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{AccessRights, Key};
+
+const CONTRACT_ATTENUATE_UREF: &str = "attenuate_uref.wasm";
+const VALUE: &str = "value";
+const READ_ONLY_VALUE: &str = "read_only_value";
+const CONTRACT: &str = "contract";
+
+#[ignore]
+#[test]
+fn should_attenuate_uref_and_list_its_holders() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ATTENUATE_UREF, ()).build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let value = account.named_keys()[VALUE]
+        .into_uref()
+        .expect("should be uref");
+    let read_only_value = account.named_keys()[READ_ONLY_VALUE]
+        .into_uref()
+        .expect("should be uref");
+    assert_eq!(read_only_value.addr(), value.addr());
+    assert_eq!(read_only_value.access_rights(), AccessRights::READ);
+    let contract_key = account.named_keys()[CONTRACT];
+
+    let holders = builder
+        .get_engine_state()
+        .get_uref_holders(
+            CorrelationId::new(),
+            builder
+                .get_post_state_hash()
+                .as_slice()
+                .try_into()
+                .expect("should be a valid hash"),
+            value.addr(),
+        )
+        .expect("should list holders");
+
+    assert_eq!(holders.len(), 2);
+    assert_eq!(
+        holders[&Key::Account(DEFAULT_ACCOUNT_ADDR)],
+        BTreeSet::from_iter(vec![AccessRights::READ, AccessRights::READ_ADD_WRITE])
+    );
+    assert_eq!(
+        holders[&contract_key],
+        BTreeSet::from_iter(vec![AccessRights::READ])
+    );
+}
@@ -0,0 +1,65 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, U512};
+
+const CONTRACT_BATCH_TRANSFER: &str = "batch_transfer.wasm";
+const PURSE_1: &str = "purse_1";
+const PURSE_2: &str = "purse_2";
+const SUCCEEDED: &str = "succeeded";
+
+fn run_batch_transfer(amount_1: u64, amount_2: u64) -> (bool, U512, U512) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_BATCH_TRANSFER,
+        (U512::from(amount_1), U512::from(amount_2)),
+    )
+    .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let purse_balance = |name: &str| {
+        let purse = account.named_keys()[name]
+            .into_uref()
+            .expect("should be uref");
+        builder.get_purse_balance(purse)
+    };
+    let succeeded: bool = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[SUCCEEDED])
+        .expect("should have result")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should be bool");
+
+    (succeeded, purse_balance(PURSE_1), purse_balance(PURSE_2))
+}
+
+#[ignore]
+#[test]
+fn should_apply_all_transfers_in_batch() {
+    let (succeeded, balance_1, balance_2) = run_batch_transfer(100, 40);
+
+    assert!(succeeded);
+    assert_eq!(balance_1, U512::from(60));
+    assert_eq!(balance_2, U512::from(40));
+}
+
+#[ignore]
+#[test]
+fn should_apply_no_transfers_if_any_in_batch_fails() {
+    let (succeeded, balance_1, balance_2) = run_batch_transfer(100, 101);
+
+    assert!(!succeeded);
+    assert_eq!(balance_1, U512::zero());
+    assert_eq!(balance_2, U512::zero());
+}
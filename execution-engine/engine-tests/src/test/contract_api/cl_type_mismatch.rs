@@ -0,0 +1,52 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{ApiError, Key};
+
+const CONTRACT_CL_TYPE_MISMATCH: &str = "cl_type_mismatch.wasm";
+const READ_ERROR: &str = "read_error";
+const EXPECTED_ERROR_MESSAGE: &str = "Type mismatch: Expected List(String) but found U64.";
+
+#[ignore]
+#[test]
+fn should_return_type_mismatch_when_reading_uref_as_other_type() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CL_TYPE_MISMATCH, (false,))
+            .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let read_error: u32 = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[READ_ERROR])
+        .expect("should have value")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should be u32");
+    assert_eq!(read_error, u32::from(ApiError::CLTypeMismatch));
+}
+
+#[ignore]
+#[test]
+fn should_fail_when_writing_other_type_to_uref() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CL_TYPE_MISMATCH, (true,))
+            .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    assert!(builder.is_error());
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have exec error");
+    assert!(error_message.contains(EXPECTED_ERROR_MESSAGE));
+}
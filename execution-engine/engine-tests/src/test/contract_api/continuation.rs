@@ -0,0 +1,91 @@
+use std::convert::TryFrom;
+
+use contract::args_parser::ArgsParser;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{CLValue, Key};
+
+const CONTRACT_CONTINUATION: &str = "continuation.wasm";
+const CONTRACT_NAME: &str = "continuation";
+const RESULT_KEY: &str = "result";
+const METHOD_INSTALL: &str = "install";
+const METHOD_START: &str = "start";
+const METHOD_RESUME: &str = "resume";
+const VALUE: u64 = 42;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CONTINUATION,
+        (METHOD_INSTALL,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn exec(builder: &mut InMemoryWasmTestBuilder, args: impl ArgsParser) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CONTINUATION, args).build();
+    builder.exec(exec_request).commit();
+}
+
+fn get_result(builder: &InMemoryWasmTestBuilder) -> u64 {
+    let stored_value = builder
+        .query(
+            None,
+            Key::Account(DEFAULT_ACCOUNT_ADDR),
+            &[CONTRACT_NAME, RESULT_KEY],
+        )
+        .expect("should have result");
+    CLValue::try_from(stored_value)
+        .expect("should be a CLValue")
+        .into_t()
+        .expect("should be u64")
+}
+
+#[ignore]
+#[test]
+fn should_run_scheduled_continuation_in_later_deploy() {
+    let mut builder = setup();
+
+    exec(&mut builder, (METHOD_START, VALUE));
+    builder.expect_success();
+    assert_eq!(get_result(&builder), 0);
+
+    exec(&mut builder, (METHOD_RESUME,));
+    builder.expect_success();
+    assert_eq!(get_result(&builder), VALUE);
+}
+
+#[ignore]
+#[test]
+fn should_run_continuation_at_most_once() {
+    let mut builder = setup();
+
+    exec(&mut builder, (METHOD_START, VALUE));
+    exec(&mut builder, (METHOD_RESUME,));
+    builder.expect_success();
+
+    exec(&mut builder, (METHOD_RESUME,));
+    let message = builder.exec_error_message(3).expect("should fail");
+    assert!(message.contains("ValueNotFound"), "{}", message);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_resume_without_scheduled_continuation() {
+    let mut builder = setup();
+
+    exec(&mut builder, (METHOD_RESUME,));
+    let message = builder.exec_error_message(1).expect("should fail");
+    assert!(message.contains("ValueNotFound"), "{}", message);
+}
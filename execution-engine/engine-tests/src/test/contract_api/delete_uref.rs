@@ -0,0 +1,65 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::Key;
+
+const CONTRACT_DELETE_UREF: &str = "delete_uref.wasm";
+const DATA_KEY: &str = "data";
+const METHOD_WRITE: &str = "write";
+const METHOD_DELETE: &str = "delete";
+const METHOD_WRITE_AND_DELETE: &str = "write_and_delete";
+
+fn exec(builder: &mut InMemoryWasmTestBuilder, method: &str) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DELETE_UREF, (method,))
+            .build();
+    builder.exec(exec_request).commit();
+}
+
+fn query_data(builder: &InMemoryWasmTestBuilder) -> Result<(), String> {
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[DATA_KEY])
+        .map(|_| ())
+}
+
+#[ignore]
+#[test]
+fn should_delete_written_uref() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec(&mut builder, METHOD_WRITE);
+    builder.expect_success();
+    assert!(query_data(&builder).is_ok());
+
+    exec(&mut builder, METHOD_DELETE);
+    builder.expect_success();
+    assert!(query_data(&builder).is_err());
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_delete_missing_value() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec(&mut builder, METHOD_WRITE);
+    exec(&mut builder, METHOD_DELETE);
+    builder.expect_success();
+
+    exec(&mut builder, METHOD_DELETE);
+    let error_message = builder.exec_error_message(2).expect("should have error");
+    assert!(error_message.contains("KeyNotFound"), error_message);
+}
+
+#[ignore]
+#[test]
+fn should_delete_uref_created_in_same_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec(&mut builder, METHOD_WRITE_AND_DELETE);
+    builder.expect_success();
+    assert!(query_data(&builder).is_err());
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, Weight},
+    bytesrepr::FromBytes,
+    CLTyped, Key,
+};
+
+const CONTRACT_GET_ACCOUNT_INFO: &str = "get_account_info.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const MISSING_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+
+fn query<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should have value");
+    stored_value
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should have expected type")
+}
+
+#[ignore]
+#[test]
+fn should_read_public_fields_of_another_account() {
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT),
+    )
+    .build();
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_ACCOUNT_INFO,
+        (ACCOUNT_1_ADDR, MISSING_ACCOUNT_ADDR),
+    )
+    .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec(exec_request_2)
+        .expect_success()
+        .commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    let expected_associated_keys: BTreeMap<PublicKey, Weight> = account_1
+        .get_associated_keys()
+        .map(|(public_key, weight)| (*public_key, *weight))
+        .collect();
+
+    assert_eq!(
+        query::<BTreeMap<PublicKey, Weight>>(&builder, "associated_keys"),
+        expected_associated_keys
+    );
+    assert_eq!(
+        query::<Weight>(&builder, "deployment_threshold"),
+        *account_1.action_thresholds().deployment()
+    );
+    assert_eq!(
+        query::<Weight>(&builder, "key_management_threshold"),
+        *account_1.action_thresholds().key_management()
+    );
+    assert_eq!(
+        query::<[u8; 32]>(&builder, "main_purse_addr"),
+        account_1.main_purse().addr()
+    );
+    assert!(!query::<bool>(&builder, "missing_account_found"));
+}
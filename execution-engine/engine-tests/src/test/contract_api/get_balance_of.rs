@@ -0,0 +1,52 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{system_contract_errors::mint, ApiError, Key, U512};
+
+const CONTRACT_GET_BALANCE_OF: &str = "get_balance_of.wasm";
+const BALANCE: &str = "balance";
+
+#[ignore]
+#[test]
+fn should_get_balance_of_purse_with_read_rights() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GET_BALANCE_OF, (true,))
+            .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let balance: U512 = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[BALANCE])
+        .expect("should have balance")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should be U512");
+    assert!(!balance.is_zero());
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_get_balance_of_purse_without_read_rights() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GET_BALANCE_OF, (false,))
+            .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let error_message = builder.exec_error_message(0).expect("should have error");
+    let expected_error = ApiError::from(mint::Error::InvalidAccessRights);
+    assert!(
+        error_message.contains(&format!("{:?}", expected_error)),
+        error_message
+    );
+}
@@ -0,0 +1,24 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_GET_BLOCK_HEIGHT: &str = "get_block_height.wasm";
+
+#[ignore]
+#[test]
+fn should_run_get_block_height_contract() {
+    let block_height: u64 = 7;
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_BLOCK_HEIGHT,
+        (block_height,),
+    )
+    .with_block_height(block_height)
+    .build();
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
@@ -0,0 +1,57 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::FromBytes, CLTyped, Key};
+
+const CONTRACT_GET_CALL_STACK: &str = "get_call_stack.wasm";
+const CONTRACT_NAME: &str = "call_stack_contract";
+const SESSION_CALLER: &str = "session_caller";
+const SESSION_CALL_STACK: &str = "session_call_stack";
+const CONTRACT_CALLER: &str = "contract_caller";
+const CONTRACT_CALL_STACK: &str = "contract_call_stack";
+
+fn query<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should have value");
+    stored_value
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should have expected type")
+}
+
+#[ignore]
+#[test]
+fn should_identify_callers_of_session_and_stored_contract() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GET_CALL_STACK, ()).build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    let contract_key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(CONTRACT_NAME)
+        .expect("should have contract");
+
+    assert_eq!(query::<Key>(&builder, SESSION_CALLER), account_key);
+    assert_eq!(
+        query::<Vec<Key>>(&builder, SESSION_CALL_STACK),
+        vec![account_key]
+    );
+
+    assert_eq!(query::<Key>(&builder, CONTRACT_CALLER), account_key);
+    assert_eq!(
+        query::<Vec<Key>>(&builder, CONTRACT_CALL_STACK),
+        vec![account_key, contract_key]
+    );
+}
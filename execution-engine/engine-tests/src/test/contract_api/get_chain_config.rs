@@ -0,0 +1,76 @@
+use engine_core::engine_state::{
+    run_genesis_request::RunGenesisRequest, CONV_RATE, MAX_PAYMENT, SYSTEM_ACCOUNT_ADDR,
+};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, bytesrepr::FromBytes, CLTyped, Key, U512};
+
+const CONTRACT_GET_CHAIN_CONFIG: &str = "get_chain_config.wasm";
+const CHAIN_NAME: &str = "casperlabs-testnet";
+
+fn query<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should have value");
+    stored_value
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should have expected type")
+}
+
+fn run_get_chain_config(builder: &mut InMemoryWasmTestBuilder) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GET_CHAIN_CONFIG, ())
+            .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_read_chain_config() {
+    let mut exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone());
+    exec_config.set_chain_name(CHAIN_NAME.to_string());
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    run_get_chain_config(&mut builder);
+
+    assert_eq!(query::<String>(&builder, "chain_name"), CHAIN_NAME);
+    assert_eq!(query::<u64>(&builder, "conv_rate"), CONV_RATE);
+    assert_eq!(
+        query::<U512>(&builder, "max_payment"),
+        U512::from(MAX_PAYMENT)
+    );
+    let version = DEFAULT_PROTOCOL_VERSION.value();
+    assert_eq!(
+        query::<(u32, u32, u32)>(&builder, "protocol_version"),
+        (version.major, version.minor, version.patch)
+    );
+    assert_eq!(
+        query::<PublicKey>(&builder, "system_account"),
+        SYSTEM_ACCOUNT_ADDR
+    );
+}
+
+#[ignore]
+#[test]
+fn should_read_empty_chain_name_when_chain_is_unnamed() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    run_get_chain_config(&mut builder);
+
+    assert_eq!(query::<String>(&builder, "chain_name"), "");
+}
@@ -0,0 +1,111 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{ApiError, CLValue, Key, NamedArgs, NamedArgsError};
+
+const CONTRACT_GET_NAMED_ARG: &str = "get_named_arg.wasm";
+const ARG_VALUE: &str = "value";
+const VALUE: u64 = 42;
+
+fn exec_with_named_args(named_args: NamedArgs) -> InMemoryWasmTestBuilder {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code_named_args(CONTRACT_GET_NAMED_ARG, named_args)
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+fn exec_error(builder: &InMemoryWasmTestBuilder) -> execution::Error {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let exec_response = response.last().expect("should have response");
+    let error = exec_response.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(exec_error) => exec_error.clone())
+}
+
+#[ignore]
+#[test]
+fn should_read_named_arg() {
+    let mut named_args = NamedArgs::new();
+    named_args.insert(ARG_VALUE, VALUE).unwrap();
+    let mut builder = exec_with_named_args(named_args);
+    builder.expect_success();
+
+    let value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[ARG_VALUE])
+        .expect("should have value");
+    assert_eq!(value, StoredValue::CLValue(CLValue::from_t(VALUE).unwrap()));
+}
+
+#[ignore]
+#[test]
+fn should_revert_if_named_arg_has_wrong_type() {
+    let mut named_args = NamedArgs::new();
+    named_args.insert(ARG_VALUE, VALUE as u32).unwrap();
+    let builder = exec_with_named_args(named_args);
+
+    assert_matches!(
+        exec_error(&builder),
+        execution::Error::Revert(ApiError::InvalidArgument)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_revert_if_named_arg_is_missing() {
+    let mut named_args = NamedArgs::new();
+    named_args.insert("other", VALUE).unwrap();
+    let builder = exec_with_named_args(named_args);
+    assert_matches!(
+        exec_error(&builder),
+        execution::Error::Revert(ApiError::MissingArgument)
+    );
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GET_NAMED_ARG, (VALUE,))
+            .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+    assert_matches!(
+        exec_error(&builder),
+        execution::Error::Revert(ApiError::MissingArgument)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_duplicate_named_args() {
+    let mut named_args = NamedArgs::new();
+    named_args.insert(ARG_VALUE, VALUE).unwrap();
+    named_args.insert(ARG_VALUE, VALUE + 1).unwrap();
+    let builder = exec_with_named_args(named_args);
+
+    let expected_error = NamedArgsError::DuplicateName(ARG_VALUE.to_string());
+    assert_matches!(
+        exec_error(&builder),
+        execution::Error::NamedArgs(error) if error == expected_error
+    );
+}
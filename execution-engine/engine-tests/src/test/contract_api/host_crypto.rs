@@ -0,0 +1,53 @@
+use engine_shared::newtypes::Blake2bHash;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_HOST_CRYPTO: &str = "host_crypto.wasm";
+const MESSAGE: &[u8] = b"pay 10 motes to channel 1";
+const SECRET_KEY: [u8; 32] = [7u8; 32];
+
+fn ed25519_signature(message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let secret_key = ed25519_dalek::SecretKey::from_bytes(&SECRET_KEY).unwrap();
+    let public_key = ed25519_dalek::PublicKey::from(&secret_key);
+    let signature = ed25519_dalek::ExpandedSecretKey::from(&secret_key).sign(message, &public_key);
+    (
+        signature.to_bytes().to_vec(),
+        public_key.to_bytes().to_vec(),
+    )
+}
+
+fn secp256k1_signature(message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let secret_key = secp256k1::SecretKey::parse(&SECRET_KEY).unwrap();
+    let public_key = secp256k1::PublicKey::from_secret_key(&secret_key);
+    let digest = secp256k1::Message::parse(&Blake2bHash::new(message).value());
+    let (signature, _) = secp256k1::sign(&digest, &secret_key).unwrap();
+    (
+        signature.serialize().to_vec(),
+        public_key.serialize_compressed().to_vec(),
+    )
+}
+
+#[ignore]
+#[test]
+fn should_hash_and_verify_signatures_on_host() {
+    let (ed25519_signature, ed25519_public_key) = ed25519_signature(MESSAGE);
+    let (secp256k1_signature, secp256k1_public_key) = secp256k1_signature(MESSAGE);
+    let args = (
+        MESSAGE.to_vec(),
+        Blake2bHash::new(MESSAGE).value(),
+        ed25519_signature,
+        ed25519_public_key,
+        secp256k1_signature,
+        secp256k1_public_key,
+    );
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_HOST_CRYPTO, args).build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
@@ -0,0 +1,19 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_HOST_MATH: &str = "host_math.wasm";
+
+#[ignore]
+#[test]
+fn should_compute_bignum_arithmetic_on_host() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_HOST_MATH, ()).build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
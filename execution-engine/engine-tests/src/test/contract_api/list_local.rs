@@ -0,0 +1,50 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::Key;
+
+const CONTRACT_LIST_LOCAL: &str = "list_local.wasm";
+const LISTED_KEY: &str = "listed";
+const METHOD_WRITE: &str = "write";
+const METHOD_LIST: &str = "list";
+const METHOD_WRITE_AND_LIST: &str = "write_and_list";
+
+fn exec(builder: &mut InMemoryWasmTestBuilder, method: &str) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_LIST_LOCAL, (method,))
+            .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn get_listed(builder: &InMemoryWasmTestBuilder) -> Vec<u64> {
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[LISTED_KEY])
+        .expect("should have listed values")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should be Vec<u64>")
+}
+
+#[ignore]
+#[test]
+fn should_list_local_entries_across_pages() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    exec(&mut builder, METHOD_WRITE);
+    exec(&mut builder, METHOD_LIST);
+
+    assert_eq!(get_listed(&builder), vec![0, 10, 20, 30, 40]);
+}
+
+#[ignore]
+#[test]
+fn should_list_local_entries_written_in_same_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    exec(&mut builder, METHOD_WRITE_AND_LIST);
+
+    assert_eq!(get_listed(&builder), vec![0, 10, 20, 30, 40]);
+}
@@ -0,0 +1,71 @@
+use std::convert::TryInto;
+
+use engine_shared::newtypes::CorrelationId;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{URef, U512};
+
+const CONTRACT_LIST_PURSES: &str = "list_purses.wasm";
+
+fn get_account_purses(builder: &InMemoryWasmTestBuilder) -> Vec<(URef, U512)> {
+    builder
+        .get_engine_state()
+        .get_account_purses(
+            CorrelationId::new(),
+            *DEFAULT_PROTOCOL_VERSION,
+            builder
+                .get_post_state_hash()
+                .as_slice()
+                .try_into()
+                .expect("should be a valid hash"),
+            DEFAULT_ACCOUNT_ADDR,
+        )
+        .expect("should get account purses")
+}
+
+#[ignore]
+#[test]
+fn should_list_purses_created_by_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let main_purse = account.main_purse().remove_access_rights();
+    assert_eq!(
+        get_account_purses(&builder)
+            .into_iter()
+            .map(|(purse, _balance)| purse)
+            .collect::<Vec<_>>(),
+        vec![main_purse]
+    );
+
+    for _ in 0..2 {
+        let exec_request =
+            ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_LIST_PURSES, ()).build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let purses = get_account_purses(&builder);
+    assert_eq!(purses.len(), 5);
+    assert_eq!(purses[0].0, main_purse);
+    assert_eq!(
+        purses[4].0,
+        account.named_keys()["purse_2"]
+            .into_uref()
+            .expect("should be a uref")
+            .remove_access_rights()
+    );
+    for (_purse, balance) in &purses[1..] {
+        assert_eq!(*balance, U512::zero());
+    }
+}
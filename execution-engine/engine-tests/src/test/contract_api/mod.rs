@@ -1,17 +1,41 @@
 mod account;
+mod attenuate_uref;
+mod batch_transfer;
+mod cl_type_mismatch;
+mod continuation;
 mod create_purse;
+mod delete_uref;
+mod get_account_info;
 mod get_arg;
+mod get_balance_of;
+mod get_block_height;
 mod get_blocktime;
+mod get_call_stack;
 mod get_caller;
+mod get_chain_config;
+mod get_named_arg;
 mod get_phase;
+mod host_crypto;
+mod host_math;
+mod list_local;
 mod list_named_keys;
+mod list_purses;
 mod local_state;
 mod main_purse;
 mod mint_purse;
+mod random_bytes;
+mod recursive_subcall;
 mod revert;
+mod schedule_call;
+mod store_contract_abi;
+mod stored_collections;
 mod subcall;
 mod transfer;
 mod transfer_purse_to_account;
 mod transfer_purse_to_purse;
 mod transfer_stored;
 mod transfer_u512_stored;
+mod transfer_with_proof;
+mod try_call_contract;
+mod try_get_arg;
+mod versioned_contract;
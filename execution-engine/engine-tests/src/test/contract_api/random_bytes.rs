@@ -0,0 +1,33 @@
+use engine_core::execution::AddressGenerator;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::Phase;
+
+const CONTRACT_RANDOM_BYTES: &str = "random_bytes.wasm";
+const DEPLOY_HASH: [u8; 32] = [42u8; 32];
+
+#[ignore]
+#[test]
+fn should_run_random_bytes_contract() {
+    let expected_bytes = AddressGenerator::new(&DEPLOY_HASH, Phase::Session).create_address();
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_RANDOM_BYTES, (expected_bytes,))
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(DEPLOY_HASH)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
@@ -0,0 +1,149 @@
+use engine_core::engine_state::upgrade::ActivationPoint;
+use engine_storage::protocol_data::DEFAULT_MAX_CALL_DEPTH;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::ProtocolVersion;
+
+const CONTRACT_RECURSIVE_SUBCALL: &str = "recursive_subcall.wasm";
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+
+fn expect_error_containing(builder: &InMemoryWasmTestBuilder, index: usize, pattern: &str) {
+    let message = builder.exec_error_message(index).expect("should fail");
+    assert!(
+        message.contains(pattern),
+        "Error message {:?} does not contain expected pattern {:?}",
+        message,
+        pattern
+    );
+}
+
+#[ignore]
+#[test]
+fn should_allow_nested_calls_up_to_max_call_depth() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RECURSIVE_SUBCALL,
+        (DEFAULT_MAX_CALL_DEPTH, false),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_fail_when_exceeding_max_call_depth() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RECURSIVE_SUBCALL,
+        (DEFAULT_MAX_CALL_DEPTH + 1, false),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    expect_error_containing(&builder, 0, "CallDepthExceeded");
+}
+
+#[ignore]
+#[test]
+fn should_allow_guarded_contract_which_is_not_reentered() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RECURSIVE_SUBCALL,
+        (1u32, true),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_fail_when_guarded_contract_is_reentered() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RECURSIVE_SUBCALL,
+        (2u32, true),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    expect_error_containing(&builder, 0, "Reentrancy");
+}
+
+#[ignore]
+#[test]
+fn should_apply_max_call_depth_from_upgrade() {
+    const NEW_MAX_CALL_DEPTH: u32 = 4;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = ProtocolVersion::V1_0_0.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(ProtocolVersion::V1_0_0)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_max_call_depth(NEW_MAX_CALL_DEPTH)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(new_protocol_version)
+        .expect("should have result")
+        .expect("should have protocol data");
+    assert_eq!(protocol_data.max_call_depth(), NEW_MAX_CALL_DEPTH);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RECURSIVE_SUBCALL,
+        (NEW_MAX_CALL_DEPTH, false),
+    )
+    .with_protocol_version(new_protocol_version)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_RECURSIVE_SUBCALL,
+        (NEW_MAX_CALL_DEPTH + 1, false),
+    )
+    .with_protocol_version(new_protocol_version)
+    .build();
+    builder.exec(exec_request).commit();
+
+    expect_error_containing(&builder, 1, "CallDepthExceeded");
+}
@@ -0,0 +1,116 @@
+use engine_core::engine_state::{
+    era_rewards::EraReport, execute_request::ExecuteRequest, step::StepRequest,
+};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{BlockTime, Key};
+
+const CONTRACT_SCHEDULE_CALL: &str = "schedule_call.wasm";
+const VALUE: &str = "value";
+const SCHEDULED_VALUE: u64 = 42;
+const EARLIEST_BLOCKTIME: u64 = 1_000;
+const GAS_BUDGET: u64 = 1_000_000;
+
+fn schedule_call_request(earliest_blocktime: u64, gas_budget: u64) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SCHEDULE_CALL,
+        (earliest_blocktime, gas_budget),
+    )
+    .build()
+}
+
+fn schedule_call(gas_budget: u64) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(schedule_call_request(EARLIEST_BLOCKTIME, gas_budget))
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn query_value(builder: &InMemoryWasmTestBuilder) -> u64 {
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[VALUE])
+        .expect("should have value")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should be u64")
+}
+
+fn step_request(blocktime: u64) -> StepRequest {
+    StepRequest::new(BlockTime::new(blocktime))
+}
+
+fn era_end_step_request(blocktime: u64) -> StepRequest {
+    step_request(blocktime).with_era_end(EraReport::default())
+}
+
+#[ignore]
+#[test]
+fn should_make_scheduled_call_at_first_era_end_after_its_blocktime() {
+    let mut builder = schedule_call(GAS_BUDGET);
+    let scheduled_calls = builder.get_scheduled_calls();
+    assert_eq!(scheduled_calls.len(), 1);
+    assert_eq!(scheduled_calls[0].registrant(), DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(scheduled_calls[0].gas_budget(), GAS_BUDGET);
+
+    // Too early, even at an era end.
+    builder.step(era_end_step_request(EARLIEST_BLOCKTIME - 1));
+    assert_eq!(query_value(&builder), 0);
+
+    // Due, but not at an era end.
+    builder.step(step_request(EARLIEST_BLOCKTIME));
+    assert_eq!(query_value(&builder), 0);
+
+    builder.step(era_end_step_request(EARLIEST_BLOCKTIME + 1));
+    assert_eq!(query_value(&builder), SCHEDULED_VALUE);
+    assert!(builder.get_scheduled_calls().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_discard_scheduled_call_exceeding_its_gas_budget() {
+    let mut builder = schedule_call(1);
+    builder.step(era_end_step_request(EARLIEST_BLOCKTIME));
+    assert_eq!(query_value(&builder), 0);
+    assert!(builder.get_scheduled_calls().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_only_make_scheduled_calls_which_are_due() {
+    let mut builder = schedule_call(GAS_BUDGET);
+    let later_blocktime = EARLIEST_BLOCKTIME * 2;
+    builder
+        .exec(schedule_call_request(later_blocktime, GAS_BUDGET))
+        .expect_success()
+        .commit();
+
+    let scheduled_calls = builder.get_scheduled_calls();
+    assert_eq!(scheduled_calls.len(), 2);
+    assert_eq!(
+        scheduled_calls[0].earliest_blocktime(),
+        BlockTime::new(EARLIEST_BLOCKTIME)
+    );
+    assert_eq!(
+        scheduled_calls[1].earliest_blocktime(),
+        BlockTime::new(later_blocktime)
+    );
+
+    builder.step(era_end_step_request(EARLIEST_BLOCKTIME));
+    let scheduled_calls = builder.get_scheduled_calls();
+    assert_eq!(scheduled_calls.len(), 1);
+    assert_eq!(
+        scheduled_calls[0].earliest_blocktime(),
+        BlockTime::new(later_blocktime)
+    );
+
+    builder.step(era_end_step_request(later_blocktime));
+    assert!(builder.get_scheduled_calls().is_empty());
+}
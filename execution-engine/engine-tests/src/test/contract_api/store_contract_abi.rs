@@ -0,0 +1,65 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{CLType, CLTyped, ContractAbi, Key, U512};
+
+const CONTRACT_STORE_CONTRACT_ABI: &str = "store_contract_abi.wasm";
+const CONTRACT_WITH_ABI_NAME: &str = "contract_with_abi";
+const CONTRACT_WITHOUT_ABI_NAME: &str = "contract_without_abi";
+
+fn query_abi(builder: &InMemoryWasmTestBuilder, name: &str) -> Option<ContractAbi> {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should query contract");
+    match stored_value {
+        StoredValue::Contract(contract) => contract.abi().cloned(),
+        other => panic!("expected contract, got {:?}", other),
+    }
+}
+
+fn store_contracts(destination: &str) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_STORE_CONTRACT_ABI,
+        (destination,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn assert_abis_queryable(builder: &InMemoryWasmTestBuilder) {
+    let abi = query_abi(builder, CONTRACT_WITH_ABI_NAME).expect("should have abi");
+    let deposit = abi.entry_point("deposit").expect("should have deposit");
+    assert_eq!(deposit.parameters().len(), 1);
+    assert_eq!(deposit.parameters()[0].name(), "amount");
+    assert_eq!(deposit.parameters()[0].cl_type(), &U512::cl_type());
+    assert_eq!(deposit.ret(), &CLType::Unit);
+    let balance = abi.entry_point("balance").expect("should have balance");
+    assert!(balance.parameters().is_empty());
+    assert_eq!(balance.ret(), &U512::cl_type());
+
+    assert_eq!(query_abi(builder, CONTRACT_WITHOUT_ABI_NAME), None);
+}
+
+#[ignore]
+#[test]
+fn should_store_and_query_abi_of_contract_at_hash() {
+    let builder = store_contracts("hash");
+    assert_abis_queryable(&builder);
+}
+
+#[ignore]
+#[test]
+fn should_store_and_query_abi_of_contract_at_uref() {
+    let builder = store_contracts("uref");
+    assert_abis_queryable(&builder);
+}
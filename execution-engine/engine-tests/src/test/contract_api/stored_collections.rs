@@ -0,0 +1,134 @@
+use std::convert::TryInto;
+
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, Key,
+};
+
+const CONTRACT_STORED_COLLECTIONS: &str = "stored_collections.wasm";
+const VEC_NAME: &str = "vec:log";
+const MAP_NAME: &str = "map:totals";
+const SINGLETON_KEY: &str = "singleton";
+const METHOD_WRITE: &str = "write";
+const METHOD_UPDATE: &str = "update";
+const METHOD_PAGE: &str = "page";
+const LOG_SUM_KEY: &str = "log_sum";
+const TOTALS_SUM_KEY: &str = "totals_sum";
+const LOG_DONE_KEY: &str = "log_done";
+const TOTALS_DONE_KEY: &str = "totals_done";
+// Enough deploys to page through everything stored by `METHOD_WRITE` two entries at a time.
+const MAX_PAGE_DEPLOYS: usize = 20;
+
+type MapEntry = (String, String, Option<u64>);
+
+fn exec(builder: &mut InMemoryWasmTestBuilder, method: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_STORED_COLLECTIONS,
+        (method,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn local_key<K: ToBytes>(key: K) -> Key {
+    let seed = DEFAULT_ACCOUNT_ADDR
+        .as_bytes()
+        .try_into()
+        .expect("should be 32 bytes");
+    Key::local(seed, &key.to_bytes().expect("should serialize"))
+}
+
+fn query<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, key: Key, path: &[&str]) -> T {
+    builder
+        .query(None, key, path)
+        .expect("should have value")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should have expected type")
+}
+
+fn is_done(builder: &InMemoryWasmTestBuilder, done_key: &str) -> bool {
+    builder
+        .query(None, local_key(done_key), &[])
+        .ok()
+        .and_then(|stored_value| stored_value.as_cl_value().cloned())
+        .map(|cl_value| cl_value.into_t().expect("should be bool"))
+        .unwrap_or(false)
+}
+
+#[ignore]
+#[test]
+fn should_write_stored_collections() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    exec(&mut builder, METHOD_WRITE);
+
+    assert_eq!(query::<u64>(&builder, local_key(VEC_NAME), &[]), 3);
+    for index in 0..3u64 {
+        let value: u64 = query(&builder, local_key((VEC_NAME, index)), &[]);
+        assert_eq!(value, index + 1);
+    }
+
+    let a: MapEntry = query(&builder, local_key((MAP_NAME, "a")), &[]);
+    assert_eq!(a, (MAP_NAME.to_string(), "a".to_string(), Some(1)));
+    let b: MapEntry = query(&builder, local_key((MAP_NAME, "b")), &[]);
+    assert_eq!(b, (MAP_NAME.to_string(), "b".to_string(), Some(2)));
+
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(query::<u64>(&builder, account_key, &[SINGLETON_KEY]), 7);
+}
+
+#[ignore]
+#[test]
+fn should_update_only_modified_elements() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    exec(&mut builder, METHOD_WRITE);
+    exec(&mut builder, METHOD_UPDATE);
+
+    assert_eq!(query::<u64>(&builder, local_key(VEC_NAME), &[]), 2);
+    assert_eq!(query::<u64>(&builder, local_key((VEC_NAME, 0u64)), &[]), 10);
+    assert_eq!(query::<u64>(&builder, local_key((VEC_NAME, 1u64)), &[]), 2);
+
+    let a: MapEntry = query(&builder, local_key((MAP_NAME, "a")), &[]);
+    assert_eq!(a.2, None);
+    let b: MapEntry = query(&builder, local_key((MAP_NAME, "b")), &[]);
+    assert_eq!(b.2, Some(3));
+
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(query::<u64>(&builder, account_key, &[SINGLETON_KEY]), 8);
+
+    // Elements which were neither read nor modified are left alone.
+    let transforms = builder.get_transforms();
+    let update_transforms = transforms.last().expect("should have transforms");
+    assert!(update_transforms
+        .get(&local_key((VEC_NAME, 1u64)))
+        .is_none());
+}
+
+#[ignore]
+#[test]
+fn should_page_through_collections_across_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    exec(&mut builder, METHOD_WRITE);
+
+    let mut deploys = 0;
+    while !(is_done(&builder, LOG_DONE_KEY) && is_done(&builder, TOTALS_DONE_KEY)) {
+        assert!(deploys < MAX_PAGE_DEPLOYS, "should finish paging");
+        exec(&mut builder, METHOD_PAGE);
+        deploys += 1;
+    }
+    // The vector alone takes two pages.
+    assert!(deploys >= 2);
+
+    assert_eq!(query::<u64>(&builder, local_key(LOG_SUM_KEY), &[]), 6);
+    assert_eq!(query::<u64>(&builder, local_key(TOTALS_SUM_KEY), &[]), 3);
+}
@@ -0,0 +1,90 @@
+use std::convert::TryFrom;
+
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{AccessRights, CLValue, TransferReceipt, U512};
+
+const CONTRACT_TRANSFER_WITH_PROOF: &str = "transfer_with_proof.wasm";
+const DEPLOY_HASH: [u8; 32] = [42u8; 32];
+const TRANSFER_AMOUNT: u64 = 42;
+
+#[ignore]
+#[test]
+fn should_store_read_only_transfer_receipt() {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_TRANSFER_WITH_PROOF, (U512::from(TRANSFER_AMOUNT),))
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(DEPLOY_HASH)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get genesis account");
+
+    let receipt_uref = default_account.named_keys()["transfer_receipt"]
+        .into_uref()
+        .expect("should be a uref");
+    assert_eq!(receipt_uref.access_rights(), AccessRights::READ);
+
+    let receipt = CLValue::try_from(
+        builder
+            .query(None, receipt_uref.into(), &[])
+            .expect("should have transfer receipt"),
+    )
+    .expect("should be a CLValue")
+    .into_t::<TransferReceipt>()
+    .expect("should be TransferReceipt");
+
+    let secondary_purse = default_account.named_keys()["purse:secondary"]
+        .into_uref()
+        .expect("should be a uref");
+    let expected_receipt = TransferReceipt::new(
+        DEPLOY_HASH,
+        default_account.main_purse(),
+        secondary_purse,
+        U512::from(TRANSFER_AMOUNT),
+    );
+    assert_eq!(receipt, expected_receipt);
+    assert_eq!(receipt.source().access_rights(), AccessRights::NONE);
+}
+
+#[ignore]
+#[test]
+fn should_not_store_receipt_for_failed_transfer() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_WITH_PROOF,
+        (U512::from(999_999_999_999i64),),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    assert!(builder.is_error());
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get genesis account");
+    assert!(!default_account
+        .named_keys()
+        .contains_key("transfer_receipt"));
+}
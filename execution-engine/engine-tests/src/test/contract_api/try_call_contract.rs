@@ -0,0 +1,43 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::FromBytes, ApiError, CLTyped, Key};
+
+const CONTRACT_TRY_CALL_CONTRACT: &str = "try_call_contract.wasm";
+const VALUE: &str = "value";
+const REVERT_ERROR: &str = "revert_error";
+
+fn query<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should have value");
+    stored_value
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should have expected type")
+}
+
+#[ignore]
+#[test]
+fn should_discard_only_effects_of_reverted_subcall() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_TRY_CALL_CONTRACT, ())
+            .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    // the caller sees the error the callee reverted with...
+    assert_eq!(
+        query::<u32>(&builder, REVERT_ERROR),
+        u32::from(ApiError::User(42))
+    );
+    // ...and the callee's write is discarded, while the later call's addition is kept
+    assert_eq!(query::<u64>(&builder, VALUE), 10);
+}
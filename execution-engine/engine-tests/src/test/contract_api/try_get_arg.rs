@@ -0,0 +1,49 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::FromBytes, ApiError, CLTyped, Key};
+
+const CONTRACT_TRY_GET_ARG: &str = "try_get_arg.wasm";
+const INVALID_ARG_ERROR: &str = "invalid_arg_error";
+const MISSING_ARG_ERROR: &str = "missing_arg_error";
+const FALLBACK: &str = "fallback";
+
+fn query<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should have value");
+    stored_value
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should have expected type")
+}
+
+#[ignore]
+#[test]
+fn should_return_arg_errors_without_reverting() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRY_GET_ARG,
+        (String::from("not a u64"),),
+    )
+    .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        query::<u32>(&builder, INVALID_ARG_ERROR),
+        u32::from(ApiError::InvalidArgument)
+    );
+    assert_eq!(
+        query::<u32>(&builder, MISSING_ARG_ERROR),
+        u32::from(ApiError::MissingArgument)
+    );
+    assert_eq!(query::<u64>(&builder, FALLBACK), 7);
+}
@@ -0,0 +1,205 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key, U512};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+const ACCOUNT_INITIAL_BALANCE: u64 = 100_000_000_000;
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const CONTRACT_VERSIONED_CONTRACT_CALLER: &str = "versioned_contract_caller.wasm";
+const CONTRACT_VERSIONED_CONTRACT_STORED: &str = "versioned_contract_stored.wasm";
+const CONTRACT_NAME: &str = "versioned_contract";
+const PUBLIC_ENTRY_POINT: &str = "public_value";
+const ADMINS_ENTRY_POINT: &str = "admins_value";
+const INSTALLER_ENTRY_POINT: &str = "installer_value";
+
+/// Returns a builder in which the default account stored the versioned contract, with account 1
+/// in its admins group and account 2 an outsider, along with the contract's key.
+fn setup() -> (InMemoryWasmTestBuilder, Key) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for account in &[ACCOUNT_1_ADDR, ACCOUNT_2_ADDR] {
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            (*account, U512::from(ACCOUNT_INITIAL_BALANCE)),
+        )
+        .build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_VERSIONED_CONTRACT_STORED,
+        (ACCOUNT_1_ADDR,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let contract_key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(CONTRACT_NAME)
+        .expect("should have contract");
+
+    (builder, contract_key)
+}
+
+fn call_entry_point(
+    builder: &mut InMemoryWasmTestBuilder,
+    account: PublicKey,
+    contract_key: Key,
+    entry_point: &str,
+    expected_value: u64,
+) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        account,
+        CONTRACT_VERSIONED_CONTRACT_CALLER,
+        (contract_key, entry_point.to_string(), expected_value),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn exec_error(builder: &InMemoryWasmTestBuilder) -> execution::Error {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let exec_response = response.last().expect("should have response");
+    let error = exec_response.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(exec_error) => exec_error.clone())
+}
+
+#[ignore]
+#[test]
+fn should_call_entry_points_allowed_to_caller() {
+    let (mut builder, contract_key) = setup();
+
+    call_entry_point(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        contract_key,
+        PUBLIC_ENTRY_POINT,
+        1,
+    );
+    builder.expect_success();
+    call_entry_point(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        contract_key,
+        ADMINS_ENTRY_POINT,
+        2,
+    );
+    builder.expect_success();
+    call_entry_point(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        contract_key,
+        INSTALLER_ENTRY_POINT,
+        3,
+    );
+    builder.expect_success();
+
+    call_entry_point(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        contract_key,
+        PUBLIC_ENTRY_POINT,
+        1,
+    );
+    builder.expect_success();
+    call_entry_point(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        contract_key,
+        ADMINS_ENTRY_POINT,
+        2,
+    );
+    builder.expect_success();
+
+    call_entry_point(
+        &mut builder,
+        ACCOUNT_2_ADDR,
+        contract_key,
+        PUBLIC_ENTRY_POINT,
+        1,
+    );
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_deny_entry_points_not_allowed_to_caller() {
+    let (mut builder, contract_key) = setup();
+
+    call_entry_point(
+        &mut builder,
+        ACCOUNT_1_ADDR,
+        contract_key,
+        INSTALLER_ENTRY_POINT,
+        3,
+    );
+    assert_matches!(
+        exec_error(&builder),
+        execution::Error::EntryPointAccessDenied(name) if name == INSTALLER_ENTRY_POINT
+    );
+
+    call_entry_point(
+        &mut builder,
+        ACCOUNT_2_ADDR,
+        contract_key,
+        ADMINS_ENTRY_POINT,
+        2,
+    );
+    assert_matches!(
+        exec_error(&builder),
+        execution::Error::EntryPointAccessDenied(name) if name == ADMINS_ENTRY_POINT
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_call_unknown_entry_point() {
+    let (mut builder, contract_key) = setup();
+
+    call_entry_point(&mut builder, DEFAULT_ACCOUNT_ADDR, contract_key, "call", 0);
+    assert_matches!(exec_error(&builder), execution::Error::FunctionNotFound(_));
+}
+
+#[ignore]
+#[test]
+fn should_not_run_versioned_contract_as_session_code() {
+    let (mut builder, contract_key) = setup();
+    let contract_hash = contract_key.into_hash().expect("should be stored at hash");
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_session_hash(contract_hash.to_vec(), ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request);
+
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::Exec(execution::Error::FunctionNotFound(_))
+    );
+}
@@ -0,0 +1,74 @@
+use std::{thread, time::Duration};
+
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::Error,
+    execution::{self, CancellationToken},
+};
+use engine_shared::newtypes::CorrelationId;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::U512;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_ENDLESS_LOOP: &str = "endless_loop.wasm";
+const CANCEL_AFTER_MILLIS: u64 = 200;
+// Enough gas for the loop to keep running well past the cancellation.
+const PAYMENT_AMOUNT: u64 = 10_000_000_000;
+
+#[ignore]
+#[test]
+fn should_cancel_in_flight_and_pending_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let endless_loop = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_ENDLESS_LOOP, ())
+        .with_empty_payment_bytes((U512::from(PAYMENT_AMOUNT),))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let do_nothing = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([2; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new()
+        .with_pre_state_hash(&builder.get_post_state_hash())
+        .push_deploy(endless_loop)
+        .push_deploy(do_nothing)
+        .build();
+
+    let cancellation_token = CancellationToken::new();
+    let canceller = {
+        let cancellation_token = cancellation_token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(CANCEL_AFTER_MILLIS));
+            cancellation_token.cancel();
+        })
+    };
+
+    let results = builder
+        .get_engine_state()
+        .run_execute_with_cancellation(CorrelationId::new(), exec_request, cancellation_token)
+        .expect("should find prestate");
+    canceller.join().expect("should cancel");
+
+    assert_eq!(results.len(), 2);
+    for result in results {
+        assert_matches!(
+            result.as_error(),
+            Some(Error::Exec(execution::Error::Cancelled))
+        );
+        assert_eq!(result.cost().value(), U512::zero());
+    }
+}
@@ -0,0 +1,149 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::{
+    deploy_whitelist::DEPLOY_WHITELIST_NAME, run_genesis_request::RunGenesisRequest, EngineConfig,
+    Error, WhitelistMode,
+};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, ApiError, U512};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_1_INITIAL_BALANCE: u64 = 100_000_000_000;
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_DO_NOTHING_STORED: &str = "do_nothing_stored.wasm";
+const CONTRACT_DEPLOY_WHITELIST_ADD: &str = "deploy_whitelist_add.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+
+/// Returns a builder whose genesis made the default account the whitelist admin, with account 1
+/// created afterwards and so not on the whitelist.
+fn setup(whitelist_mode: WhitelistMode) -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_whitelist_mode(whitelist_mode);
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+
+    let mut exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone());
+    exec_config.set_whitelist_admin(Some(DEFAULT_ACCOUNT_ADDR));
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+    builder.run_genesis(&run_genesis_request);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_INITIAL_BALANCE)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_give_whitelist_to_admin() {
+    let builder = setup(WhitelistMode::Disabled);
+
+    let admin = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have admin account");
+    assert!(admin.named_keys().contains_key(DEPLOY_WHITELIST_NAME));
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    assert!(!account_1.named_keys().contains_key(DEPLOY_WHITELIST_NAME));
+}
+
+#[ignore]
+#[test]
+fn should_only_accept_deploys_from_whitelisted_accounts() {
+    let mut builder = setup(WhitelistMode::Deploys);
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request);
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::NotWhitelisted(account) if *account == ACCOUNT_1_ADDR
+    );
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DEPLOY_WHITELIST_ADD,
+        (ACCOUNT_1_ADDR,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_only_accept_contract_installs_from_whitelisted_accounts() {
+    let mut builder = setup(WhitelistMode::ContractInstalls);
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING_STORED, ("hash",))
+            .build();
+    builder.exec(exec_request).commit();
+    assert_eq!(builder.get_revert_error(), ApiError::PermissionDenied);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING_STORED,
+        ("hash",),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_reject_all_deploys_without_a_whitelist() {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_whitelist_mode(WhitelistMode::Deploys);
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request);
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(precondition_failure, Error::NotWhitelisted(_));
+}
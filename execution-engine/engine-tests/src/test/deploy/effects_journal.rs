@@ -0,0 +1,70 @@
+use engine_core::engine_state::EngineConfig;
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const DEPLOY_HASH: [u8; 32] = [42; 32];
+
+fn execute_do_nothing(enable_effects_journal: bool) -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_enable_effects_journal(enable_effects_journal);
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(DEPLOY_HASH)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_record_deploy_effects() {
+    let builder = execute_do_nothing(true);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let expected_effect = response[0].effect();
+
+    let recorded_effect = builder
+        .get_engine_state()
+        .get_deploy_effects(DEPLOY_HASH)
+        .expect("should read deploy effects")
+        .expect("should have recorded deploy effects");
+    assert_eq!(&recorded_effect, expected_effect);
+    assert!(builder
+        .get_engine_state()
+        .get_deploy_effects([0; 32])
+        .expect("should read deploy effects")
+        .is_none());
+}
+
+#[ignore]
+#[test]
+fn should_not_record_deploy_effects_with_journal_disabled() {
+    let builder = execute_do_nothing(false);
+
+    let recorded_effect = builder
+        .get_engine_state()
+        .get_deploy_effects(DEPLOY_HASH)
+        .expect("should read deploy effects");
+    assert!(recorded_effect.is_none());
+}
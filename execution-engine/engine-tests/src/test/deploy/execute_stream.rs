@@ -0,0 +1,117 @@
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{
+    ipc::{self, ExecuteStreamResponse},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::PublicKey;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const DEPLOY_COUNT: u8 = 3;
+const UNKNOWN_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+
+fn execute_stream(
+    builder: &InMemoryWasmTestBuilder,
+    exec_request_builder: ExecuteRequestBuilder,
+) -> Vec<ExecuteStreamResponse> {
+    let exec_request: ipc::ExecuteRequest = exec_request_builder.build().into();
+    builder
+        .get_engine_state()
+        .execute_stream(RequestOptions::new(), exec_request)
+        .collect()
+        .wait_drop_metadata()
+        .expect("should get execute stream")
+}
+
+#[ignore]
+#[test]
+fn should_stream_one_result_per_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request_builder = (0..DEPLOY_COUNT).fold(
+        ExecuteRequestBuilder::new().with_pre_state_hash(&builder.get_post_state_hash()),
+        |exec_request_builder, index| {
+            let deploy = DeployItemBuilder::new()
+                .with_address(DEFAULT_ACCOUNT_ADDR)
+                .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+                .with_session_code(CONTRACT_DO_NOTHING, ())
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+                .with_deploy_hash([index; 32])
+                .build();
+            exec_request_builder.push_deploy(deploy)
+        },
+    );
+
+    let stream_responses = execute_stream(&builder, exec_request_builder);
+    assert_eq!(stream_responses.len(), usize::from(DEPLOY_COUNT));
+    for stream_response in stream_responses {
+        assert!(stream_response.has_deploy_result());
+        assert!(stream_response.get_deploy_result().has_execution_result());
+        assert!(!stream_response
+            .get_deploy_result()
+            .get_execution_result()
+            .has_error());
+    }
+}
+
+#[ignore]
+#[test]
+fn should_stream_missing_parent_for_unknown_prestate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let missing_hash = [255u8; 32];
+    let exec_request_builder =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ())
+            .with_pre_state_hash(&missing_hash);
+
+    let stream_responses = execute_stream(&builder, exec_request_builder);
+    assert_eq!(stream_responses.len(), 1);
+    assert!(stream_responses[0].has_missing_parent());
+    assert_eq!(
+        stream_responses[0].get_missing_parent().get_hash(),
+        &missing_hash[..]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_stream_results_in_order_of_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request_builder = (0..DEPLOY_COUNT).fold(
+        ExecuteRequestBuilder::new().with_pre_state_hash(&builder.get_post_state_hash()),
+        |exec_request_builder, index| {
+            // Only the deploy in the middle is sent from an account which doesn't exist
+            let address = if index == 1 {
+                UNKNOWN_ACCOUNT_ADDR
+            } else {
+                DEFAULT_ACCOUNT_ADDR
+            };
+            let deploy = DeployItemBuilder::new()
+                .with_address(address)
+                .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+                .with_session_code(CONTRACT_DO_NOTHING, ())
+                .with_authorization_keys(&[address])
+                .with_deploy_hash([index; 32])
+                .build();
+            exec_request_builder.push_deploy(deploy)
+        },
+    );
+
+    let stream_responses = execute_stream(&builder, exec_request_builder);
+    assert_eq!(stream_responses.len(), usize::from(DEPLOY_COUNT));
+    for (index, stream_response) in stream_responses.iter().enumerate() {
+        let deploy_result = stream_response.get_deploy_result();
+        assert_eq!(deploy_result.has_precondition_failure(), index == 1);
+    }
+}
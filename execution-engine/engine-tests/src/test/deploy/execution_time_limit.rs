@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use engine_core::engine_state::EngineConfig;
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::U512;
+
+const CONTRACT_ENDLESS_LOOP: &str = "endless_loop.wasm";
+const TIME_LIMIT_MILLIS: u64 = 200;
+// Enough gas for the loop to keep running well past the time limit.
+const PAYMENT_AMOUNT: u64 = 10_000_000_000;
+
+fn builder_with_time_limit() -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_execution_time_limit(Some(Duration::from_millis(TIME_LIMIT_MILLIS)));
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_abort_deploy_exceeding_time_limit() {
+    let mut builder = builder_with_time_limit();
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_ENDLESS_LOOP, ())
+        .with_empty_payment_bytes((U512::from(PAYMENT_AMOUNT),))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let error_message = utils::get_error_message(response);
+    assert!(
+        error_message.contains("ExecutionTimeout"),
+        "expected execution timeout, got: {}",
+        error_message
+    );
+}
@@ -1,3 +1,16 @@
+mod cancellation;
+mod deploy_whitelist;
+mod effects_journal;
+mod execute_stream;
+mod execution_time_limit;
 mod non_standard_payment;
+mod payment_sandbox;
+mod payment_source;
+mod peak_memory_pages;
 mod preconditions;
+mod protocol_version;
+mod replay_protection;
+mod sequence_numbers;
+mod size_limits;
 mod stored_contracts;
+mod tracing;
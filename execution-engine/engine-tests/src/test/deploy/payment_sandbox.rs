@@ -0,0 +1,81 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_ACCOUNT_KEY, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const PAYMENT_SANDBOX_WASM: &str = "payment_sandbox.wasm";
+const PAYMENT_NAMED_KEY: &str = "payment_named_key";
+
+fn pay_with_action(action: &str) -> InMemoryWasmTestBuilder {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(DO_NOTHING_WASM, ())
+            .with_payment_code(PAYMENT_SANDBOX_WASM, (*DEFAULT_PAYMENT, action.to_string()))
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_deploy_hash([1; 32])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+fn assert_sandbox_violation(builder: &InMemoryWasmTestBuilder) {
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    let cause = assert_matches!(error, Error::PaymentFailure { error, .. } => error);
+    assert_matches!(
+        **cause,
+        Error::Exec(execution::Error::PaymentSandboxViolation)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_allow_payment_code_to_add_named_key() {
+    let mut builder = pay_with_action("put_key");
+    builder.expect_success();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.named_keys().get(PAYMENT_NAMED_KEY),
+        Some(&Key::Account(ACCOUNT_1_ADDR))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_forbid_payment_code_to_write_state() {
+    let builder = pay_with_action("new_uref");
+    assert_sandbox_violation(&builder);
+}
+
+#[ignore]
+#[test]
+fn should_forbid_payment_code_to_transfer_outside_payment_purse() {
+    let builder = pay_with_action("transfer_to_account");
+    assert_sandbox_violation(&builder);
+
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+}
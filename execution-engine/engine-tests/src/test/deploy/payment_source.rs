@@ -0,0 +1,109 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::{Error, CONV_RATE};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{AccessRights, Key, URef, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_TRANSFER_MAIN_PURSE_TO_NEW_PURSE: &str = "transfer_main_purse_to_new_purse.wasm";
+const OPERATIONS_PURSE: &str = "operations_purse";
+
+fn setup() -> (InMemoryWasmTestBuilder, URef) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_MAIN_PURSE_TO_NEW_PURSE,
+        (OPERATIONS_PURSE, *DEFAULT_PAYMENT * U512::from(2)),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let operations_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(OPERATIONS_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have operations purse");
+
+    (builder, operations_purse)
+}
+
+fn do_nothing_request(payment_source: URef) -> ExecuteRequestBuilder {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([2; 32])
+        .with_payment_source(payment_source)
+        .build();
+    ExecuteRequestBuilder::new().push_deploy(deploy)
+}
+
+#[ignore]
+#[test]
+fn should_pay_from_payment_source_purse() {
+    let (mut builder, operations_purse) = setup();
+
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let main_purse_pre_balance = builder.get_purse_balance(main_purse);
+    let operations_purse_pre_balance = builder.get_purse_balance(operations_purse);
+
+    builder
+        .exec(do_nothing_request(operations_purse).build())
+        .expect_success()
+        .commit();
+
+    let spent_amount: U512 = {
+        let response = builder
+            .get_exec_response(1)
+            .expect("there should be a response");
+        let success_result = utils::get_success_result(response);
+        Motes::from_gas(success_result.cost(), CONV_RATE)
+            .expect("should have motes")
+            .value()
+    };
+
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        main_purse_pre_balance,
+        "main purse should be untouched"
+    );
+    assert_eq!(
+        builder.get_purse_balance(operations_purse),
+        operations_purse_pre_balance - spent_amount,
+        "operations purse should pay for the deploy"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_unknown_payment_source() {
+    let (mut builder, _) = setup();
+    let unknown_purse = URef::new([42; 32], AccessRights::READ_ADD_WRITE);
+
+    builder.exec(do_nothing_request(unknown_purse).build());
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let error = response[0].as_error().expect("should have error");
+    assert_matches!(error, Error::InvalidPaymentSource(purse) if *purse == unknown_purse);
+}
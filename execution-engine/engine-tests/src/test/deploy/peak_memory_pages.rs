@@ -0,0 +1,54 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const GROWN_PAGES: u32 = 40;
+
+// Starts with a single page of memory and grows it by `GROWN_PAGES`.
+const CONTRACT_WAT_GROW_MEMORY: &str = r#"
+(module
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (func (export "call")
+      (drop (memory.grow (i32.const 40)))))
+"#;
+
+fn exec_grow_memory() -> u32 {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let wasm_binary = wabt::wat2wasm(CONTRACT_WAT_GROW_MEMORY).expect("should parse");
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(wasm_binary, ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let result = builder
+        .get_exec_response(0)
+        .expect("should have response")
+        .last()
+        .expect("should have result")
+        .clone();
+    result.peak_memory_pages()
+}
+
+#[ignore]
+#[test]
+fn should_record_peak_memory_pages() {
+    let peak_memory_pages = exec_grow_memory();
+    assert!(
+        peak_memory_pages > GROWN_PAGES,
+        "peak of {} pages should include the {} grown pages",
+        peak_memory_pages,
+        GROWN_PAGES
+    );
+}
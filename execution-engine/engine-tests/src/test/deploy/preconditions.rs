@@ -78,7 +78,7 @@ fn should_raise_precondition_authorization_failure_empty_authorized_keys() {
         .expect("there should be a response");
 
     let precondition_failure = utils::get_precondition_failure(response);
-    assert_matches!(precondition_failure, Error::Authorization);
+    assert_matches!(precondition_failure, Error::AuthorizationFailure(_));
 }
 
 #[ignore]
@@ -116,5 +116,5 @@ fn should_raise_precondition_authorization_failure_invalid_authorized_keys() {
         .expect("there should be a response");
 
     let precondition_failure = utils::get_precondition_failure(response);
-    assert_matches!(precondition_failure, Error::Authorization);
+    assert_matches!(precondition_failure, Error::AuthorizationFailure(_));
 }
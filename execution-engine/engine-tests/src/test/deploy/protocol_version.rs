@@ -0,0 +1,86 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::{deploy_item::DeployItem, Error};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::ProtocolVersion;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+/// A protocol version the chain was never upgraded to.
+fn unknown_protocol_version() -> ProtocolVersion {
+    ProtocolVersion::from_parts(1, 1, 0)
+}
+
+fn do_nothing_deploy(protocol_version: ProtocolVersion) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([42; 32])
+        .with_protocol_version(protocol_version)
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_declaring_request_protocol_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(*DEFAULT_PROTOCOL_VERSION))
+        .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_declaring_other_protocol_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(do_nothing_deploy(unknown_protocol_version()))
+        .build();
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::ProtocolVersionMismatch { declared, expected }
+            if *declared == unknown_protocol_version() && *expected == *DEFAULT_PROTOCOL_VERSION
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploys_of_request_with_unknown_protocol_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .with_protocol_version(unknown_protocol_version())
+        .push_deploy(do_nothing_deploy(unknown_protocol_version()))
+        .build();
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::InvalidProtocolVersion(protocol_version)
+            if *protocol_version == unknown_protocol_version()
+    );
+}
@@ -0,0 +1,306 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::{
+    deploy_header::{DeployHeader, DeployHeaderError},
+    deploy_item::DeployItem,
+    EngineConfig, Error,
+};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_NAMED_KEYS: &str = "named_keys.wasm";
+const COMMAND_CREATE_UREF: &str = "create-uref1";
+const COMMAND_TEST_READ_UREF: &str = "test-read-uref1";
+const BLOCK_TIME: u64 = 1_000_000;
+const TTL_MILLIS: u64 = 1_000;
+
+fn builder_with_replay_protection() -> InMemoryWasmTestBuilder {
+    builder_with_config(
+        EngineConfig::new()
+            .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+            .with_enable_bonding(cfg!(feature = "enable-bonding"))
+            .with_enable_replay_protection(true),
+    )
+}
+
+fn builder_with_result_cache() -> InMemoryWasmTestBuilder {
+    builder_with_config(
+        EngineConfig::new()
+            .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+            .with_enable_bonding(cfg!(feature = "enable-bonding"))
+            .with_enable_replay_protection(true)
+            .with_enable_execution_result_cache(true),
+    )
+}
+
+fn builder_with_config(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn do_nothing_deploy(deploy_hash: [u8; 32], header: Option<DeployHeader>) -> DeployItem {
+    let deploy_builder = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash);
+    match header {
+        Some(header) => deploy_builder.with_header(header),
+        None => deploy_builder,
+    }
+    .build()
+}
+
+fn named_keys_deploy(
+    deploy_hash: [u8; 32],
+    command: &str,
+    header: Option<DeployHeader>,
+) -> DeployItem {
+    let deploy_builder = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_NAMED_KEYS, (command,))
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash);
+    match header {
+        Some(header) => deploy_builder.with_header(header),
+        None => deploy_builder,
+    }
+    .build()
+}
+
+fn do_nothing_request(
+    deploy_hash: [u8; 32],
+    header: Option<DeployHeader>,
+) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::new()
+        .with_block_time(BLOCK_TIME)
+        .push_deploy(do_nothing_deploy(deploy_hash, header))
+}
+
+#[ignore]
+#[test]
+fn should_reject_replayed_deploy() {
+    let mut builder = builder_with_replay_protection();
+
+    builder
+        .exec(do_nothing_request([1; 32], None).build())
+        .expect_success()
+        .commit();
+
+    builder.exec(do_nothing_request([1; 32], None).build());
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(precondition_failure, Error::ReplayedDeploy);
+}
+
+#[ignore]
+#[test]
+fn should_allow_same_deploy_hash_without_replay_protection() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for _ in 0..2 {
+        builder
+            .exec(do_nothing_request([1; 32], None).build())
+            .expect_success()
+            .commit();
+    }
+}
+
+#[ignore]
+#[test]
+fn should_reject_expired_deploy() {
+    let header = DeployHeader::new(BLOCK_TIME - TTL_MILLIS - 1, TTL_MILLIS, vec![]);
+    let exec_request = do_nothing_request([1; 32], Some(header)).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::DeployHeader(DeployHeaderError::Expired { .. })
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_from_the_future() {
+    let header = DeployHeader::new(BLOCK_TIME + 1, TTL_MILLIS, vec![]);
+    let exec_request = do_nothing_request([1; 32], Some(header)).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::DeployHeader(DeployHeaderError::TimestampInFuture { .. })
+    );
+}
+
+#[ignore]
+#[test]
+fn should_require_dependencies_to_have_been_executed() {
+    let mut builder = builder_with_replay_protection();
+    let header = DeployHeader::new(BLOCK_TIME, TTL_MILLIS, vec![[1; 32]]);
+
+    builder.exec(do_nothing_request([2; 32], Some(header.clone())).build());
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::DeployHeader(DeployHeaderError::MissingDependency(_))
+    );
+
+    builder
+        .exec(do_nothing_request([1; 32], None).build())
+        .expect_success()
+        .commit()
+        .exec(do_nothing_request([2; 32], Some(header)).build())
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_execute_dependencies_in_the_same_batch_first() {
+    let mut builder = builder_with_replay_protection();
+    let header = DeployHeader::new(BLOCK_TIME, TTL_MILLIS, vec![[1; 32]]);
+    // The deploy reading the named key comes before the one creating it, and only the first of the
+    // two readers declares the dependency.
+    let exec_request = ExecuteRequestBuilder::new()
+        .with_block_time(BLOCK_TIME)
+        .push_deploy(named_keys_deploy(
+            [2; 32],
+            COMMAND_TEST_READ_UREF,
+            Some(header),
+        ))
+        .push_deploy(named_keys_deploy([3; 32], COMMAND_TEST_READ_UREF, None))
+        .push_deploy(named_keys_deploy([1; 32], COMMAND_CREATE_UREF, None))
+        .build();
+
+    builder.exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    assert_eq!(response.len(), 3);
+    assert!(
+        !response[0].is_failure(),
+        "the dependent should see the named key written by its dependency: {:?}",
+        response[0]
+    );
+    assert!(
+        response[1].is_failure(),
+        "a deploy without the dependency should not see the named key"
+    );
+    assert!(!response[2].is_failure(), "{:?}", response[2]);
+}
+
+#[ignore]
+#[test]
+fn should_reject_cyclic_dependencies() {
+    let mut builder = builder_with_replay_protection();
+    let first_header = DeployHeader::new(BLOCK_TIME, TTL_MILLIS, vec![[2; 32]]);
+    let second_header = DeployHeader::new(BLOCK_TIME, TTL_MILLIS, vec![[1; 32]]);
+    let exec_request = ExecuteRequestBuilder::new()
+        .with_block_time(BLOCK_TIME)
+        .push_deploy(do_nothing_deploy([1; 32], Some(first_header)))
+        .push_deploy(do_nothing_deploy([2; 32], Some(second_header)))
+        .push_deploy(do_nothing_deploy([3; 32], None))
+        .build();
+
+    builder.exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    for result in &response[..2] {
+        assert!(result.has_precondition_failure());
+        assert_matches!(
+            result.as_error(),
+            Some(Error::DeployHeader(DeployHeaderError::CyclicDependency(_)))
+        );
+    }
+    assert!(!response[2].is_failure());
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_repeated_in_the_same_batch() {
+    let mut builder = builder_with_result_cache();
+    let exec_request = ExecuteRequestBuilder::new()
+        .with_block_time(BLOCK_TIME)
+        .push_deploy(do_nothing_deploy([1; 32], None))
+        .push_deploy(do_nothing_deploy([1; 32], None))
+        .build();
+
+    builder.exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    assert!(!response[0].is_failure(), "{:?}", response[0]);
+    assert_matches!(response[1].as_error(), Some(Error::ReplayedDeploy));
+}
+
+#[ignore]
+#[test]
+fn should_not_reuse_cached_result_of_deploy_with_dependencies() {
+    let mut builder = builder_with_result_cache();
+    let header = DeployHeader::new(BLOCK_TIME, TTL_MILLIS, vec![[1; 32]]);
+
+    // Alone, the deploy's dependency is missing...
+    builder.exec(do_nothing_request([2; 32], Some(header.clone())).build());
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    assert_matches!(
+        utils::get_precondition_failure(response),
+        Error::DeployHeader(DeployHeaderError::MissingDependency(_))
+    );
+
+    // ...but from the same prestate, in a batch with its dependency, it succeeds.
+    let exec_request = ExecuteRequestBuilder::new()
+        .with_block_time(BLOCK_TIME)
+        .push_deploy(do_nothing_deploy([1; 32], None))
+        .push_deploy(do_nothing_deploy([2; 32], Some(header)))
+        .build();
+    builder.exec(exec_request);
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    for result in response {
+        assert!(!result.is_failure(), "{:?}", result);
+    }
+}
@@ -0,0 +1,97 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::Error;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::account::PublicKey;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+fn do_nothing_request(
+    deploy_hash: [u8; 32],
+    sequence_number: Option<u64>,
+) -> ExecuteRequestBuilder {
+    let deploy_builder = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash);
+    let deploy = match sequence_number {
+        Some(sequence_number) => deploy_builder.with_sequence_number(sequence_number),
+        None => deploy_builder,
+    }
+    .build();
+    ExecuteRequestBuilder::new().push_deploy(deploy)
+}
+
+fn sequence_number(builder: &InMemoryWasmTestBuilder, public_key: PublicKey) -> u64 {
+    builder
+        .get_account(public_key)
+        .expect("should have account")
+        .sequence_number()
+}
+
+#[ignore]
+#[test]
+fn should_advance_sequence_number_of_sequenced_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    assert_eq!(sequence_number(&builder, DEFAULT_ACCOUNT_ADDR), 0);
+
+    builder
+        .exec(do_nothing_request([1; 32], Some(1)).build())
+        .expect_success()
+        .commit();
+    assert_eq!(sequence_number(&builder, DEFAULT_ACCOUNT_ADDR), 1);
+
+    builder
+        .exec(do_nothing_request([2; 32], Some(2)).build())
+        .expect_success()
+        .commit();
+    assert_eq!(sequence_number(&builder, DEFAULT_ACCOUNT_ADDR), 2);
+}
+
+#[ignore]
+#[test]
+fn should_not_advance_sequence_number_of_unsequenced_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(do_nothing_request([1; 32], None).build())
+        .expect_success()
+        .commit();
+
+    assert_eq!(sequence_number(&builder, DEFAULT_ACCOUNT_ADDR), 0);
+}
+
+#[ignore]
+#[test]
+fn should_reject_out_of_order_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(do_nothing_request([1; 32], Some(1)).build())
+        .expect_success()
+        .commit();
+
+    for (index, stale_or_skipped) in [1, 3].iter().enumerate() {
+        builder.exec(do_nothing_request([2; 32], Some(*stale_or_skipped)).build());
+
+        let response = builder
+            .get_exec_response(index + 1)
+            .expect("there should be a response");
+        let precondition_failure = utils::get_precondition_failure(response);
+        assert_matches!(
+            precondition_failure,
+            Error::InvalidSequenceNumber { expected: 2, actual } if actual == stale_or_skipped
+        );
+    }
+
+    assert_eq!(sequence_number(&builder, DEFAULT_ACCOUNT_ADDR), 1);
+}
@@ -0,0 +1,200 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{
+        genesis::ExecConfig, run_genesis_request::RunGenesisRequest, EngineConfig, Error,
+    },
+    execution,
+};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS, KEY_RECOVERY_INSTALL_CONTRACT,
+        MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_NAMED_KEYS: &str = "named_keys.wasm";
+const CONTRACT_STORE_NAMED_KEYS: &str = "store_named_keys.wasm";
+const MAX_ARGS_BYTES: usize = 64;
+const MAX_MODULE_BYTES: usize = 16;
+
+fn setup(engine_config: EngineConfig) -> InMemoryWasmTestBuilder {
+    let engine_config = engine_config
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"));
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+/// Returns the number of named keys the default account holds after genesis.
+fn default_account_named_keys_count() -> usize {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .named_keys()
+        .len()
+}
+
+fn setup_with_max_named_keys_per_write(max_named_keys_per_write: usize) -> InMemoryWasmTestBuilder {
+    let wasm_costs = WasmCosts {
+        max_named_keys_per_write: max_named_keys_per_write as u32,
+        ..*DEFAULT_WASM_COSTS
+    };
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT),
+        DEFAULT_ACCOUNTS.clone(),
+        wasm_costs,
+        DEFAULT_UNBONDING_DELAY,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn assert_too_many_named_keys(
+    builder: &InMemoryWasmTestBuilder,
+    expected_count: usize,
+    expected_max: usize,
+) {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let exec_response = response.last().expect("should have response");
+    let error = exec_response.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::TooManyNamedKeys { count, max })
+            if *count == expected_count && *max == expected_max
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_with_oversized_args() {
+    let mut builder = setup(EngineConfig::new().with_max_args_bytes(Some(MAX_ARGS_BYTES)));
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING,
+        (vec![0u8; MAX_ARGS_BYTES],),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::ArgsTooLarge { size, max } if *size > MAX_ARGS_BYTES && *max == MAX_ARGS_BYTES
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_with_oversized_module() {
+    let mut builder = setup(EngineConfig::new().with_max_module_bytes(Some(MAX_MODULE_BYTES)));
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::ModuleTooLarge { max, .. } if *max == MAX_MODULE_BYTES
+    );
+}
+
+#[ignore]
+#[test]
+fn should_limit_named_keys_stored_with_contract() {
+    // Leave room for the account to hold the key under which the contract gets stored.
+    let max_named_keys_per_write = default_account_named_keys_count() + 2;
+    let mut builder = setup_with_max_named_keys_per_write(max_named_keys_per_write);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_STORE_NAMED_KEYS,
+        (max_named_keys_per_write as u32,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_STORE_NAMED_KEYS,
+        (max_named_keys_per_write as u32 + 1,),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    assert_too_many_named_keys(
+        &builder,
+        max_named_keys_per_write + 1,
+        max_named_keys_per_write,
+    );
+}
+
+#[ignore]
+#[test]
+fn should_limit_named_keys_put_to_account() {
+    let max_named_keys_per_write = default_account_named_keys_count() + 1;
+    let mut builder = setup_with_max_named_keys_per_write(max_named_keys_per_write);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS,
+        ("create-uref1",),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    // Overwriting an existing named key doesn't add to the count.
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS,
+        ("create-uref1",),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS,
+        ("create-uref2",),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    assert_too_many_named_keys(
+        &builder,
+        max_named_keys_per_write + 1,
+        max_named_keys_per_write,
+    );
+}
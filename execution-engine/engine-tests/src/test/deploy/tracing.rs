@@ -0,0 +1,90 @@
+use engine_core::{
+    engine_state::EngineConfig, execution::TraceEntry, resolvers::v1_function_index::FunctionIndex,
+};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::ToBytes, Key};
+
+const CONTRACT_LOCAL_STATE: &str = "local_state.wasm";
+const LOCAL_KEY: [u8; 32] = [66u8; 32];
+
+fn builder_with_tracing() -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_enable_tracing(true);
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_trace_host_calls_and_global_state_access() {
+    let mut builder = builder_with_tracing();
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_LOCAL_STATE, ()).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let trace = response[0].trace().expect("should have a trace");
+
+    let local_key = Key::local(
+        DEFAULT_ACCOUNT_ADDR.value(),
+        &LOCAL_KEY.to_bytes().expect("should serialize"),
+    );
+    let local_key_accesses: Vec<&TraceEntry> = trace
+        .entries()
+        .iter()
+        .filter(|entry| match entry {
+            TraceEntry::Read(key) | TraceEntry::Write(key) => *key == local_key,
+            _ => false,
+        })
+        .collect();
+    assert_eq!(
+        local_key_accesses,
+        vec![
+            &TraceEntry::Read(local_key),
+            &TraceEntry::Write(local_key),
+            &TraceEntry::Read(local_key),
+            &TraceEntry::Write(local_key),
+        ]
+    );
+
+    let write_local_calls = trace
+        .entries()
+        .iter()
+        .filter(|entry| match entry {
+            TraceEntry::HostCall { function, .. } => {
+                *function == FunctionIndex::WriteLocalFuncIndex
+            }
+            _ => false,
+        })
+        .count();
+    assert_eq!(write_local_calls, 2);
+}
+
+#[ignore]
+#[test]
+fn should_not_trace_by_default() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_LOCAL_STATE, ()).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    assert!(response[0].trace().is_none());
+}
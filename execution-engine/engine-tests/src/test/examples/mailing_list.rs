@@ -2,13 +2,15 @@ use engine_test_support::{
     internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::Key;
+use types::{bytesrepr::ToBytes, Key};
 
 const MAILING_CALL: &str = "mailing_list_call.wasm";
 const MAILING_DEFINE: &str = "mailing_list_define.wasm";
-const LIST_KEY: &str = "list";
 const MAILING_KEY: &str = "mailing";
 const MAIL_FEED_KEY: &str = "mail_feed";
+const PUBLISHER_KEY: &str = "publisher";
+const SUBSCRIBERS_KEY: &str = "subscribers";
+const SUBSCRIBER: &str = "CasperLabs";
 
 #[ignore]
 #[test]
@@ -41,53 +43,60 @@ fn should_run_mailing_example_contract() {
         .expect("should query account");
     let account = account_value.as_account().expect("should be account");
 
+    let expected_messages = vec![
+        "Welcome!".to_string(),
+        "Hello, World!".to_string(),
+        "Hello, World!".to_string(),
+    ];
+
     // Mail feed
 
     let mail_feed_key = account
         .named_keys()
         .get(MAIL_FEED_KEY)
-        .expect("should have counter key");
+        .expect("should have mail feed key");
     let stored_value = builder
         .query(None, *mail_feed_key, &[])
-        .expect("should have counter value");
+        .expect("should have mail feed value");
     let cl_value = stored_value.as_cl_value().expect("should be CLValue");
     let value: Vec<String> = cl_value
         .clone()
         .into_t()
-        .expect("should cast CLValue to integer");
-    assert_eq!(value, vec!["Hello again!".to_string()]);
+        .expect("should cast CLValue to Vec<String>");
+    assert_eq!(value, expected_messages);
 
     // Mailing key
+
     let mailing_key = account
         .named_keys()
         .get(MAILING_KEY)
-        .expect("should have counter key");
-    let stored_value = builder
-        .query(None, *mailing_key, &[LIST_KEY])
-        .expect("should have counter value");
-    let cl_value = stored_value.as_cl_value().expect("should be CLValue");
-    let value: Vec<String> = cl_value
-        .clone()
-        .into_t()
-        .expect("should cast CLValue to integer");
-    assert_eq!(value, vec!["CasperLabs".to_string()]);
-
-    // List key
-
+        .expect("should have mailing key");
     let stored_value = builder
-        .query(None, *mailing_key, &["CasperLabs"])
-        .expect("should have list value");
-    let cl_value = stored_value.as_cl_value().expect("should be CLValue");
-    let value: Vec<String> = cl_value
-        .clone()
-        .into_t()
-        .expect("should cast CLValue to integer");
+        .query(None, *mailing_key, &[])
+        .expect("should have mailing list contract");
+    let contract = stored_value.as_contract().expect("should be contract");
     assert_eq!(
-        value,
-        vec![
-            "Welcome!".to_string(),
-            "Hello, World!".to_string(),
-            "Hello, World!".to_string()
-        ]
+        contract.named_keys().get(PUBLISHER_KEY),
+        Some(&Key::Account(DEFAULT_ACCOUNT_ADDR))
     );
+    let seed = mailing_key.into_hash().expect("should be hash");
+
+    // Subscribers and their messages live in the contract's local state
+
+    let query_local = |key_bytes: Vec<u8>| {
+        let stored_value = builder
+            .query(None, Key::local(seed, &key_bytes), &[])
+            .expect("should have local value");
+        let cl_value = stored_value.as_cl_value().expect("should be CLValue");
+        cl_value
+            .clone()
+            .into_t::<Vec<String>>()
+            .expect("should cast CLValue to Vec<String>")
+    };
+
+    let subscribers = query_local(SUBSCRIBERS_KEY.to_bytes().unwrap());
+    assert_eq!(subscribers, vec![SUBSCRIBER.to_string()]);
+
+    let messages = query_local(SUBSCRIBER.to_bytes().unwrap());
+    assert_eq!(messages, expected_messages);
 }
@@ -0,0 +1,59 @@
+use engine_core::engine_state::EngineConfig;
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, U512};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000_000;
+const DEPLOY_HASH: [u8; 32] = [42; 32];
+
+#[ignore]
+#[test]
+fn should_index_accounts_touched_by_committed_deploys() {
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_enable_effects_journal(true);
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root = global_state.empty_root();
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root.to_vec());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(DEPLOY_HASH)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let engine_state = builder.get_engine_state();
+    engine_state
+        .index_account_activity(&[DEPLOY_HASH])
+        .expect("should index account activity");
+
+    for account in &[DEFAULT_ACCOUNT_ADDR, ACCOUNT_1_ADDR] {
+        let activity = engine_state
+            .get_account_activity(*account)
+            .expect("should get account activity");
+        assert_eq!(activity, vec![DEPLOY_HASH]);
+    }
+    let untouched_activity = engine_state
+        .get_account_activity(ACCOUNT_2_ADDR)
+        .expect("should get account activity");
+    assert!(untouched_activity.is_empty());
+}
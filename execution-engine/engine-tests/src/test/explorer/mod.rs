@@ -1,2 +1,4 @@
+#[cfg(feature = "account-activity-index")]
+mod account_activity;
 mod faucet;
 mod faucet_stored;
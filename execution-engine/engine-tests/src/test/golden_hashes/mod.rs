@@ -0,0 +1,236 @@
+//! Pins the global state root hash and the serialized protocol data produced by genesis and
+//! upgrades for a set of canonical chainspecs.
+//!
+//! Any change to these values alters consensus, so this suite fails if a change in engine-core,
+//! the system contracts or `bytesrepr` moves them, even if every other test still passes.
+//!
+//! The expected values live in `golden_hashes.json` next to this file.  If a change is meant to
+//! alter them (e.g. a deliberate protocol change, or rebuilding the contracts with a new
+//! toolchain), re-run the suite with the `UPDATE_GOLDEN_HASHES` environment variable set:
+//!
+//! ```text
+//! UPDATE_GOLDEN_HASHES=1 cargo test -p casperlabs-engine-tests golden_hashes -- --ignored
+//! ```
+//!
+//! This rewrites `golden_hashes.json` with the values computed by the current tree.  Commit the
+//! updated file together with the change and call out the new hashes in its description so that
+//! reviewers can confirm the break is intended.
+//!
+//! Building with `use-system-contracts` or `use-as-wasm` changes which Wasm gets executed and
+//! stored, so the suite only runs against the default build.
+
+use std::{collections::BTreeMap, env, fs};
+
+use engine_core::engine_state::{
+    genesis::{ExecConfig, GenesisAccount, GenesisInstaller},
+    run_genesis_request::RunGenesisRequest,
+};
+use engine_shared::{motes::Motes, newtypes::Blake2bHash};
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNTS,
+    DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, STANDARD_PAYMENT_INSTALL_CONTRACT,
+};
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_features::WasmFeatures};
+use types::{account::PublicKey, bytesrepr::ToBytes, ProtocolVersion};
+
+const GOLDEN_HASHES_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/src/test/golden_hashes/golden_hashes.json"
+);
+const UPDATE_GOLDEN_HASHES: &str = "UPDATE_GOLDEN_HASHES";
+
+const STATE_ROOT_HASH: &str = "state_root_hash";
+const PROTOCOL_DATA_HASH: &str = "protocol_data_hash";
+
+const VALIDATOR_1_ADDR: PublicKey = PublicKey::ed25519_from([1; 32]);
+const VALIDATOR_2_ADDR: PublicKey = PublicKey::ed25519_from([2; 32]);
+const VALIDATOR_BALANCE: u64 = 1_000_000_000;
+const VALIDATOR_1_BOND: u64 = 1_000_000;
+const VALIDATOR_2_BOND: u64 = 2_000_000;
+const EXTRA_CONTRACT_NAME: &str = "extra_contract";
+const ACTIVATION_POINT: u64 = 1;
+
+type GoldenHashes = BTreeMap<String, BTreeMap<String, String>>;
+
+fn validator_accounts() -> Vec<GenesisAccount> {
+    let mut accounts = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        VALIDATOR_1_ADDR,
+        Motes::new(VALIDATOR_BALANCE.into()),
+        Motes::new(VALIDATOR_1_BOND.into()),
+    ));
+    accounts.push(GenesisAccount::new(
+        VALIDATOR_2_ADDR,
+        Motes::new(VALIDATOR_BALANCE.into()),
+        Motes::new(VALIDATOR_2_BOND.into()),
+    ));
+    accounts
+}
+
+fn run_genesis(exec_config: ExecConfig) -> InMemoryWasmTestBuilder {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn upgrade(
+    builder: &mut InMemoryWasmTestBuilder,
+    upgrade_request_builder: UpgradeRequestBuilder,
+    new_protocol_version: ProtocolVersion,
+) {
+    let mut upgrade_request = upgrade_request_builder
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(ACTIVATION_POINT)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have upgrade response");
+    assert!(upgrade_response.has_success(), "upgrade should succeed");
+}
+
+fn next_version(minor: u32, patch: u32) -> ProtocolVersion {
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + minor, sem_ver.patch + patch)
+}
+
+/// Runs each canonical chainspec and returns the resulting hashes, keyed by case name.
+fn compute_hashes() -> GoldenHashes {
+    let mut cases: Vec<(&str, InMemoryWasmTestBuilder, ProtocolVersion)> = Vec::new();
+
+    let builder = run_genesis(utils::create_exec_config(DEFAULT_ACCOUNTS.clone()));
+    cases.push(("genesis_default", builder, *DEFAULT_PROTOCOL_VERSION));
+
+    let builder = run_genesis(utils::create_exec_config(validator_accounts()));
+    cases.push(("genesis_validators", builder, *DEFAULT_PROTOCOL_VERSION));
+
+    let mut exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone());
+    exec_config.push_installer(GenesisInstaller::new(
+        EXTRA_CONTRACT_NAME.to_string(),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        Vec::new(),
+    ));
+    let builder = run_genesis(exec_config);
+    cases.push((
+        "genesis_extra_installer",
+        builder,
+        *DEFAULT_PROTOCOL_VERSION,
+    ));
+
+    let mut builder = run_genesis(utils::create_exec_config(validator_accounts()));
+    let new_protocol_version = next_version(0, 1);
+    upgrade(
+        &mut builder,
+        UpgradeRequestBuilder::new(),
+        new_protocol_version,
+    );
+    cases.push(("upgrade_patch", builder, new_protocol_version));
+
+    let mut builder = run_genesis(utils::create_exec_config(validator_accounts()));
+    let new_protocol_version = next_version(1, 0);
+    let upgrade_request_builder = UpgradeRequestBuilder::new()
+        .with_new_costs(WasmCosts {
+            regular: 1,
+            div: 1,
+            mul: 1,
+            mem: 1,
+            initial_mem: 4096,
+            grow_mem: 8192,
+            memcpy: 1,
+            max_stack_height: 64 * 1024,
+            opcodes_mul: 3,
+            opcodes_div: 8,
+            max_memory_pages: 64,
+            max_table_size: 4096,
+            hash_per_byte: 2,
+            verify_ed25519: 25_000,
+            verify_secp256k1: 50_000,
+            bignum_op: 10,
+            storage_bucket_size: 128,
+            storage_read_per_bucket: 50,
+            storage_write_per_bucket: 500,
+            max_module_size: 4 * 1024 * 1024,
+            max_function_count: 10_000,
+            max_named_keys_per_write: 10_000,
+        })
+        .with_new_max_call_depth(16)
+        .with_new_wasm_features(WasmFeatures {
+            sign_ext: true,
+            ..Default::default()
+        });
+    upgrade(&mut builder, upgrade_request_builder, new_protocol_version);
+    cases.push(("upgrade_minor_config", builder, new_protocol_version));
+
+    cases
+        .into_iter()
+        .map(|(name, builder, protocol_version)| {
+            let state_root_hash = base16::encode_lower(&builder.get_post_state_hash());
+            let protocol_data_hash = {
+                let protocol_data = builder
+                    .get_engine_state()
+                    .get_protocol_data(protocol_version)
+                    .expect("should read protocol data")
+                    .expect("should have protocol data");
+                let bytes = protocol_data
+                    .to_bytes()
+                    .expect("should serialize protocol data");
+                base16::encode_lower(&Blake2bHash::new(&bytes).to_vec())
+            };
+            let mut hashes = BTreeMap::new();
+            hashes.insert(STATE_ROOT_HASH.to_string(), state_root_hash);
+            hashes.insert(PROTOCOL_DATA_HASH.to_string(), protocol_data_hash);
+            (name.to_string(), hashes)
+        })
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_match_golden_hashes() {
+    let actual = compute_hashes();
+
+    if env::var_os(UPDATE_GOLDEN_HASHES).is_some() {
+        let json = serde_json::to_string_pretty(&actual).expect("should serialize hashes");
+        fs::write(GOLDEN_HASHES_PATH, format!("{}\n", json)).expect("should write golden hashes");
+        return;
+    }
+
+    let expected: GoldenHashes = {
+        let json = fs::read_to_string(GOLDEN_HASHES_PATH).expect("should read golden hashes");
+        serde_json::from_str(&json).expect("should parse golden hashes")
+    };
+
+    let mismatches: Vec<String> = actual
+        .iter()
+        .flat_map(|(case, hashes)| {
+            hashes.iter().filter_map(move |(kind, actual_hash)| {
+                let expected_hash = expected.get(case).and_then(|hashes| hashes.get(kind));
+                if expected_hash == Some(actual_hash) {
+                    None
+                } else {
+                    Some(format!(
+                        "{} {}: expected {}, actual {}",
+                        case,
+                        kind,
+                        expected_hash.map(String::as_str).unwrap_or("<missing>"),
+                        actual_hash
+                    ))
+                }
+            })
+        })
+        .collect();
+
+    assert!(
+        mismatches.is_empty(),
+        "golden hashes changed; if this is intended, re-run with {} set (see {}):\n{}",
+        UPDATE_GOLDEN_HASHES,
+        file!(),
+        mismatches.join("\n")
+    );
+}
@@ -2,6 +2,10 @@ mod contract_api;
 mod deploy;
 mod examples;
 mod explorer;
+// Hashes are pinned for the contracts built by default only
+#[cfg(not(any(feature = "use-system-contracts", feature = "use-as-wasm")))]
+mod golden_hashes;
 mod regression;
 mod system_contracts;
+mod test_context;
 mod upgrade;
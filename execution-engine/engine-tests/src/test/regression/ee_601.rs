@@ -45,31 +45,18 @@ fn should_run_ee_601_pay_session_new_uref_collision() {
         );
     };
 
-    let pay_uref = add_keys
-        .get("new_uref_result-payment")
-        .expect("payment uref should exist");
+    // Payment code may not create URefs, so only the session code's URef is expected
+    assert!(
+        !add_keys.contains_key("new_uref_result-payment"),
+        "payment uref should not exist"
+    );
 
     let session_uref = add_keys
         .get("new_uref_result-session")
         .expect("session uref should exist");
 
-    assert_ne!(
-        pay_uref, session_uref,
-        "payment and session code should not create same uref"
-    );
-
     builder.commit();
 
-    let payment_value: StoredValue = builder
-        .query(None, *pay_uref, &[])
-        .expect("should find payment value");
-
-    assert_eq!(
-        payment_value,
-        StoredValue::CLValue(CLValue::from_t("payment".to_string()).unwrap()),
-        "expected payment"
-    );
-
     let session_value: StoredValue = builder
         .query(None, *session_uref, &[])
         .expect("should find session value");
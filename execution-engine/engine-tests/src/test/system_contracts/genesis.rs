@@ -1,14 +1,18 @@
 use engine_core::engine_state::{
-    genesis::{ExecConfig, GenesisAccount},
+    genesis::{ExecConfig, GenesisAccount, GenesisInstaller},
     run_genesis_request::RunGenesisRequest,
-    SYSTEM_ACCOUNT_ADDR,
+    CONV_RATE, SYSTEM_ACCOUNT_ADDR,
 };
 use engine_shared::{motes::Motes, stored_value::StoredValue};
-use engine_test_support::internal::{
-    utils, InMemoryWasmTestBuilder, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
-    POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_COSTS, KEY_RECOVERY_INSTALL_CONTRACT,
+        MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
-use types::{account::PublicKey, Key, ProtocolVersion, U512};
+use types::{account::PublicKey, AccessRights, Key, ProtocolVersion, U512};
 
 #[cfg(feature = "use-system-contracts")]
 const BAD_INSTALL: &str = "standard_payment.wasm";
@@ -20,6 +24,10 @@ const ACCOUNT_1_BONDED_AMOUNT: u64 = 1_000_000;
 const ACCOUNT_2_BONDED_AMOUNT: u64 = 2_000_000;
 const ACCOUNT_1_BALANCE: u64 = 1_000_000_000;
 const ACCOUNT_2_BALANCE: u64 = 2_000_000_000;
+const EXTRA_CONTRACT_NAME: &str = "extra_contract";
+const CUSTOM_SYSTEM_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([9u8; 32]);
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
 
 #[ignore]
 #[test]
@@ -50,6 +58,7 @@ fn should_run_genesis() {
     let pos_installer_bytes = utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT);
     let standard_payment_installer_bytes =
         utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
+    let key_recovery_installer_bytes = utils::read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT);
     let accounts = vec![account_1, account_2];
     let protocol_version = ProtocolVersion::V1_0_0;
     let wasm_costs = *DEFAULT_WASM_COSTS;
@@ -58,8 +67,10 @@ fn should_run_genesis() {
         mint_installer_bytes,
         pos_installer_bytes,
         standard_payment_installer_bytes,
+        key_recovery_installer_bytes,
         accounts,
         wasm_costs,
+        DEFAULT_UNBONDING_DELAY,
     );
     let run_genesis_request =
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config);
@@ -134,6 +145,8 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
         let pos_installer_bytes = utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT);
         let standard_payment_installer_bytes =
             utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
+        let key_recovery_installer_bytes =
+            utils::read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT);
         let accounts = vec![account_1, account_2];
         let protocol_version = ProtocolVersion::V1_0_0;
         let wasm_costs = *DEFAULT_WASM_COSTS;
@@ -142,8 +155,10 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
             mint_installer_bytes,
             pos_installer_bytes,
             standard_payment_installer_bytes,
+            key_recovery_installer_bytes,
             accounts,
             wasm_costs,
+            DEFAULT_UNBONDING_DELAY,
         );
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config)
     };
@@ -183,6 +198,8 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
         let pos_installer_bytes = utils::read_wasm_file_bytes(BAD_INSTALL);
         let standard_payment_installer_bytes =
             utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
+        let key_recovery_installer_bytes =
+            utils::read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT);
         let accounts = vec![account_1, account_2];
         let protocol_version = ProtocolVersion::V1_0_0;
         let wasm_costs = *DEFAULT_WASM_COSTS;
@@ -190,8 +207,10 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
             mint_installer_bytes,
             pos_installer_bytes,
             standard_payment_installer_bytes,
+            key_recovery_installer_bytes,
             accounts,
             wasm_costs,
+            DEFAULT_UNBONDING_DELAY,
         );
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config)
     };
@@ -200,3 +219,126 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
 
     builder.run_genesis(&run_genesis_request);
 }
+
+#[ignore]
+#[test]
+fn should_run_extra_genesis_installers() {
+    let mut exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone());
+    // Any installer returning a URef will do, so reuse the standard payment one
+    exec_config.push_installer(GenesisInstaller::new(
+        EXTRA_CONTRACT_NAME.to_string(),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        Vec::new(),
+    ));
+    let run_genesis_request = RunGenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        ProtocolVersion::V1_0_0,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("account should exist");
+    let extra_contract_uref = account
+        .named_keys()
+        .get(EXTRA_CONTRACT_NAME)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("account should know the extra contract");
+    assert_eq!(extra_contract_uref.access_rights(), AccessRights::READ);
+
+    let system_account = builder
+        .get_account(SYSTEM_ACCOUNT_ADDR)
+        .expect("system account should exist");
+    let system_extra_contract_uref = system_account
+        .named_keys()
+        .get(EXTRA_CONTRACT_NAME)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("system account should know the extra contract");
+    assert_eq!(
+        system_extra_contract_uref.addr(),
+        extra_contract_uref.addr()
+    );
+
+    if let Ok(StoredValue::Contract(_)) = builder.query(None, Key::URef(extra_contract_uref), &[]) {
+        // Contract exists at the extra contract URef
+    } else {
+        panic!("contract not found at extra contract uref");
+    }
+}
+
+#[ignore]
+#[test]
+fn should_run_genesis_with_configured_system_account() {
+    let mut exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone());
+    exec_config.set_system_account(CUSTOM_SYSTEM_ACCOUNT_ADDR);
+    let run_genesis_request = RunGenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        ProtocolVersion::V1_0_0,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+
+    assert!(
+        builder.get_account(CUSTOM_SYSTEM_ACCOUNT_ADDR).is_some(),
+        "configured system account should exist"
+    );
+    assert!(
+        builder.get_account(SYSTEM_ACCOUNT_ADDR).is_none(),
+        "default system account should not exist"
+    );
+
+    // Genesis accounts are funded by the mint, which only mints non-empty purses for the
+    // configured system account
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)
+    );
+
+    let rewards_purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_REWARDS_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have rewards purse");
+    let rewards_pre_balance = builder.get_purse_balance(rewards_purse);
+
+    // Finalizing the payment of a deploy runs as the configured system account
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let spent_amount = {
+        let response = builder
+            .get_exec_response(0)
+            .expect("there should be a response");
+        let success_result = utils::get_success_result(response);
+        Motes::from_gas(success_result.cost(), CONV_RATE)
+            .expect("should have motes")
+            .value()
+    };
+    assert!(!spent_amount.is_zero());
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - spent_amount,
+        "finalize_payment should refund the unspent payment"
+    );
+    assert_eq!(
+        builder.get_purse_balance(rewards_purse),
+        rewards_pre_balance + spent_amount,
+        "finalize_payment should pay the spent amount into the rewards purse"
+    );
+}
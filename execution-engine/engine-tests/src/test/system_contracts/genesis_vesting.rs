@@ -0,0 +1,103 @@
+use engine_core::engine_state::genesis::GenesisAccount;
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+};
+use types::{account::PublicKey, BlockTime, VestingSchedule, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const VESTING_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1; 32]);
+const VESTING_ACCOUNT_BALANCE: u64 = 100_000_000_000;
+const LOCKED_AMOUNT: u64 = 90_000_000_000;
+const CLIFF: u64 = 10_000;
+const RELEASE_DURATION: u64 = 10_000;
+const TRANSFER_AMOUNT: u64 = 20_000_000_000;
+
+fn run_genesis(builder: &mut InMemoryWasmTestBuilder) {
+    let vesting_schedule = VestingSchedule::new(
+        LOCKED_AMOUNT.into(),
+        BlockTime::new(CLIFF),
+        RELEASE_DURATION,
+    );
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(
+        GenesisAccount::new(
+            VESTING_ACCOUNT_ADDR,
+            Motes::new(VESTING_ACCOUNT_BALANCE.into()),
+            Motes::zero(),
+        )
+        .with_vesting_schedule(vesting_schedule),
+    );
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+}
+
+fn transfer_at(builder: &mut InMemoryWasmTestBuilder, block_time: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        VESTING_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+    )
+    .with_block_time(block_time)
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn account_1_balance(builder: &InMemoryWasmTestBuilder) -> Option<U512> {
+    builder
+        .get_account(ACCOUNT_1_ADDR)
+        .map(|account| builder.get_purse_balance(account.main_purse()))
+}
+
+#[ignore]
+#[test]
+fn should_refuse_transfers_of_locked_funds_before_cliff() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    run_genesis(&mut builder);
+
+    transfer_at(&mut builder, CLIFF - 1);
+
+    assert!(builder.is_error(), "transfer should dip into locked funds");
+    assert_eq!(account_1_balance(&builder), None);
+}
+
+#[ignore]
+#[test]
+fn should_release_locked_funds_linearly_after_cliff() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    run_genesis(&mut builder);
+
+    // A tenth of the locked amount is released, which still doesn't cover the transfer
+    transfer_at(&mut builder, CLIFF + RELEASE_DURATION / 10);
+    assert!(builder.is_error(), "transfer should dip into locked funds");
+
+    // Half of the locked amount is released, which does
+    transfer_at(&mut builder, CLIFF + RELEASE_DURATION / 2);
+    assert!(
+        !builder.is_error(),
+        "transfer should only use released funds"
+    );
+    assert_eq!(
+        account_1_balance(&builder),
+        Some(U512::from(TRANSFER_AMOUNT))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_allow_transfers_of_unlocked_funds_before_cliff() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    run_genesis(&mut builder);
+
+    let unlocked_amount = U512::from(VESTING_ACCOUNT_BALANCE - LOCKED_AMOUNT) / 2;
+    let exec_request = ExecuteRequestBuilder::standard(
+        VESTING_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, unlocked_amount),
+    )
+    .with_block_time(CLIFF - 1)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    assert_eq!(account_1_balance(&builder), Some(unlocked_amount));
+}
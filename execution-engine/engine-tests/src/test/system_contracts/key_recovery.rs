@@ -0,0 +1,259 @@
+use lazy_static::lazy_static;
+
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, Weight},
+    system_contract_errors::key_recovery,
+    ApiError, U512,
+};
+
+const CONTRACT_AUTHORIZED_KEYS: &str = "authorized_keys.wasm";
+const CONTRACT_KEY_RECOVERY_CALL: &str = "key_recovery_call.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+
+const COMMAND_CONFIGURE: &str = "configure";
+const COMMAND_REQUEST: &str = "request";
+const COMMAND_CANCEL: &str = "cancel";
+const COMMAND_FINALIZE: &str = "finalize";
+const COMMAND_ROTATE_DIRECTLY: &str = "rotate_directly";
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+const ACCOUNT_3_ADDR: PublicKey = PublicKey::ed25519_from([3u8; 32]);
+const NEW_KEY: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+
+const THRESHOLD: u32 = 2;
+const DELAY: u64 = 1_000;
+const REQUEST_TIME: u64 = 100;
+
+lazy_static! {
+    static ref ACCOUNT_INITIAL_BALANCE: U512 = *DEFAULT_PAYMENT * 10;
+}
+
+/// Funds accounts 1 to 3 and configures accounts 1 and 2 as the recovery keys of the default
+/// account.
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for account in &[ACCOUNT_1_ADDR, ACCOUNT_2_ADDR, ACCOUNT_3_ADDR] {
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            (*account, *ACCOUNT_INITIAL_BALANCE),
+        )
+        .build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_KEY_RECOVERY_CALL,
+        (
+            COMMAND_CONFIGURE,
+            vec![ACCOUNT_1_ADDR, ACCOUNT_2_ADDR],
+            THRESHOLD,
+            DELAY,
+        ),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    builder
+}
+
+fn request_rotation(builder: &mut InMemoryWasmTestBuilder, caller: PublicKey) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        caller,
+        CONTRACT_KEY_RECOVERY_CALL,
+        (
+            COMMAND_REQUEST,
+            DEFAULT_ACCOUNT_ADDR,
+            DEFAULT_ACCOUNT_ADDR,
+            NEW_KEY,
+        ),
+    )
+    .with_block_time(REQUEST_TIME)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn finalize_at(builder: &mut InMemoryWasmTestBuilder, block_time: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_3_ADDR,
+        CONTRACT_KEY_RECOVERY_CALL,
+        (COMMAND_FINALIZE, DEFAULT_ACCOUNT_ADDR),
+    )
+    .with_block_time(block_time)
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_rotate_lost_key_after_delay() {
+    let mut builder = setup();
+
+    request_rotation(&mut builder, ACCOUNT_1_ADDR);
+    request_rotation(&mut builder, ACCOUNT_2_ADDR);
+
+    finalize_at(&mut builder, REQUEST_TIME + DELAY);
+    assert!(!builder.is_error(), "finalize should succeed");
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.get_associated_key_weight(DEFAULT_ACCOUNT_ADDR),
+        None
+    );
+    assert_eq!(
+        account.get_associated_key_weight(NEW_KEY),
+        Some(&Weight::new(1))
+    );
+
+    // The new key can now sign deploys for the account
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            (ACCOUNT_1_ADDR, U512::from(1)),
+        )
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[NEW_KEY])
+        .with_deploy_hash([7u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_finalize_before_delay_elapsed() {
+    let mut builder = setup();
+
+    request_rotation(&mut builder, ACCOUNT_1_ADDR);
+    request_rotation(&mut builder, ACCOUNT_2_ADDR);
+
+    finalize_at(&mut builder, REQUEST_TIME + DELAY - 1);
+    assert_eq!(
+        builder.get_revert_error(),
+        key_recovery::Error::DelayNotElapsed.into()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_finalize_without_enough_approvals() {
+    let mut builder = setup();
+
+    request_rotation(&mut builder, ACCOUNT_1_ADDR);
+
+    finalize_at(&mut builder, REQUEST_TIME + DELAY);
+    assert_eq!(
+        builder.get_revert_error(),
+        key_recovery::Error::InsufficientApprovals.into()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_finalize_cancelled_request() {
+    let mut builder = setup();
+
+    request_rotation(&mut builder, ACCOUNT_1_ADDR);
+    request_rotation(&mut builder, ACCOUNT_2_ADDR);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_KEY_RECOVERY_CALL,
+        (COMMAND_CANCEL,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    finalize_at(&mut builder, REQUEST_TIME + DELAY);
+    assert_eq!(
+        builder.get_revert_error(),
+        key_recovery::Error::RequestNotFound.into()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_accept_request_from_non_recovery_key() {
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_3_ADDR,
+        CONTRACT_KEY_RECOVERY_CALL,
+        (
+            COMMAND_REQUEST,
+            DEFAULT_ACCOUNT_ADDR,
+            DEFAULT_ACCOUNT_ADDR,
+            NEW_KEY,
+        ),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    assert_eq!(
+        builder.get_revert_error(),
+        key_recovery::Error::NotRecoveryKey.into()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_configure_without_key_management_weight() {
+    let mut builder = setup();
+
+    // Raise the key management threshold above the weight of the default account's own key
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_AUTHORIZED_KEYS,
+        (Weight::new(3), Weight::new(1)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_KEY_RECOVERY_CALL,
+        (COMMAND_CONFIGURE, vec![ACCOUNT_3_ADDR], 1u32, DELAY),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    assert_eq!(
+        builder.get_revert_error(),
+        key_recovery::Error::KeyManagementThreshold.into()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_rotate_key_outside_key_recovery_contract() {
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_KEY_RECOVERY_CALL,
+        (
+            COMMAND_ROTATE_DIRECTLY,
+            DEFAULT_ACCOUNT_ADDR,
+            DEFAULT_ACCOUNT_ADDR,
+            NEW_KEY,
+        ),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    assert_eq!(builder.get_revert_error(), ApiError::PermissionDenied);
+}
@@ -1,5 +1,8 @@
 mod genesis;
+mod genesis_vesting;
+mod key_recovery;
 mod mint_install;
+mod mint_total_supply;
 mod pos_install;
 mod proof_of_stake;
 mod standard_payment;
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use engine_core::engine_state::EngineConfig;
+use engine_shared::stored_value::StoredValue;
 use engine_test_support::{
     internal::{
         exec_with_return, ExecuteRequestBuilder, WasmTestBuilder, DEFAULT_BLOCK_TIME,
@@ -15,13 +16,15 @@ const TRANSFER_AMOUNT: u64 = 250_000_000 + 1000;
 const SYSTEM_ADDR: PublicKey = PublicKey::ed25519_from([0u8; 32]);
 const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
 const N_VALIDATORS: u8 = 5;
+const UNBONDING_DELAY: u64 = 3_600_000;
 
-// one named_key for each validator and three for the purses
-const EXPECTED_KNOWN_KEYS_LEN: usize = (N_VALIDATORS as usize) + 3;
+// one named_key for each validator, three for the purses and one for the unbonding delay
+const EXPECTED_KNOWN_KEYS_LEN: usize = (N_VALIDATORS as usize) + 4;
 
 const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+const POS_UNBONDING_DELAY: &str = "pos_unbonding_delay";
 
 #[ignore]
 #[test]
@@ -57,7 +60,7 @@ fn should_run_pos_install_contract() {
         "pos_install.wasm",
         DEFAULT_BLOCK_TIME,
         DEPLOY_HASH_2,
-        (mint_uref, genesis_validators),
+        (mint_uref, genesis_validators, UNBONDING_DELAY),
         vec![mint_uref],
     )
     .expect("should run successfully");
@@ -96,6 +99,15 @@ fn should_run_pos_install_contract() {
 
     let rewards_purse_balance = builder.get_purse_balance(rewards_purse);
     assert_eq!(rewards_purse_balance, U512::zero());
+
+    // unbonding delay is recorded
+    let unbonding_delay_uref = get_purse(named_keys, POS_UNBONDING_DELAY)
+        .expect("should find unbonding delay in named_keys");
+    let unbonding_delay: u64 = match builder.query(None, Key::URef(unbonding_delay_uref), &[]) {
+        Ok(StoredValue::CLValue(cl_value)) => cl_value.into_t().expect("should be u64"),
+        _ => panic!("should read unbonding delay"),
+    };
+    assert_eq!(unbonding_delay, UNBONDING_DELAY);
 }
 
 fn get_purse(named_keys: &BTreeMap<String, Key>, name: &str) -> Option<URef> {
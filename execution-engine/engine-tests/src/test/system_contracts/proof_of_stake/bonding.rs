@@ -17,6 +17,7 @@ const ACCOUNT_1_SEED_AMOUNT: u64 = 100_000_000 * 2;
 const ACCOUNT_1_STAKE: u64 = 42_000;
 const ACCOUNT_1_UNBOND_1: u64 = 22_000;
 const ACCOUNT_1_UNBOND_2: u64 = 20_000;
+const SECP256K1_ACCOUNT_ADDR: PublicKey = PublicKey::secp256k1_from([3u8; 33]);
 
 const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
 const GENESIS_ACCOUNT_STAKE: u64 = 100_000;
@@ -96,7 +97,7 @@ fn should_run_successful_bond_and_unbond() {
 
     let lookup_key = format!(
         "v_{}_{}",
-        base16::encode_lower(&DEFAULT_ACCOUNT_ADDR.as_bytes()),
+        DEFAULT_ACCOUNT_ADDR.to_tagged_hex(),
         GENESIS_ACCOUNT_STAKE
     );
     assert!(contract.named_keys().contains_key(&lookup_key));
@@ -156,11 +157,7 @@ fn should_run_successful_bond_and_unbond() {
         .get_contract(pos.remove_access_rights())
         .expect("should have contract");
 
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_STAKE
-    );
+    let lookup_key = format!("v_{}_{}", ACCOUNT_1_ADDR.to_tagged_hex(), ACCOUNT_1_STAKE);
     assert!(contract.named_keys().contains_key(&lookup_key));
 
     // Gensis validator [42; 32] bonded 50k, and genesis account bonded 100k inside
@@ -212,16 +209,12 @@ fn should_run_successful_bond_and_unbond() {
 
     let pos_contract = builder.get_pos_contract();
 
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_STAKE
-    );
+    let lookup_key = format!("v_{}_{}", ACCOUNT_1_ADDR.to_tagged_hex(), ACCOUNT_1_STAKE);
     assert!(!pos_contract.named_keys().contains_key(&lookup_key));
 
     let lookup_key = format!(
         "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
+        ACCOUNT_1_ADDR.to_tagged_hex(),
         ACCOUNT_1_UNBOND_2
     );
     // Account 1 is still tracked anymore in the bonding queue with different uref
@@ -318,7 +311,7 @@ fn should_run_successful_bond_and_unbond() {
 
     let lookup_key = format!(
         "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
+        ACCOUNT_1_ADDR.to_tagged_hex(),
         ACCOUNT_1_UNBOND_2
     );
     // Account 1 isn't tracked anymore in the bonding queue
@@ -372,7 +365,7 @@ fn should_run_successful_bond_and_unbond() {
     let pos_contract = builder.get_pos_contract();
     let lookup_key = format!(
         "v_{}_{}",
-        base16::encode_lower(&DEFAULT_ACCOUNT_ADDR.as_bytes()),
+        DEFAULT_ACCOUNT_ADDR.to_tagged_hex(),
         GENESIS_ACCOUNT_UNBOND_2
     );
     // Genesis is still tracked anymore in the bonding queue with different uref
@@ -389,10 +382,9 @@ fn should_run_successful_bond_and_unbond() {
         pos_contract
             .named_keys()
             .iter()
-            .filter(|(key, _)| key.starts_with(&format!(
-                "v_{}",
-                base16::encode_lower(&DEFAULT_ACCOUNT_ADDR.as_bytes())
-            )))
+            .filter(
+                |(key, _)| key.starts_with(&format!("v_{}", DEFAULT_ACCOUNT_ADDR.to_tagged_hex()))
+            )
             .count(),
         0
     );
@@ -400,10 +392,7 @@ fn should_run_successful_bond_and_unbond() {
         pos_contract
             .named_keys()
             .iter()
-            .filter(|(key, _)| key.starts_with(&format!(
-                "v_{}",
-                base16::encode_lower(ACCOUNT_1_ADDR.as_bytes())
-            )))
+            .filter(|(key, _)| key.starts_with(&format!("v_{}", ACCOUNT_1_ADDR.to_tagged_hex())))
             .count(),
         0
     );
@@ -418,6 +407,94 @@ fn should_run_successful_bond_and_unbond() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_bond_and_unbond_secp256k1_validator() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            PublicKey::ed25519_from([42; 32]),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (
+            String::from(TEST_SEED_NEW_ACCOUNT),
+            SECP256K1_ACCOUNT_ADDR,
+            U512::from(ACCOUNT_1_SEED_AMOUNT),
+        ),
+    )
+    .build();
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        SECP256K1_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (
+            String::from(TEST_BOND_FROM_MAIN_PURSE),
+            U512::from(ACCOUNT_1_STAKE),
+        ),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit();
+
+    let result = builder.exec(exec_request_2);
+    if !cfg!(feature = "enable-bonding") && result.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    // The validator is stored under its full secp256k1 key, not under its account address or a
+    // truncated form of it
+    let lookup_key = format!(
+        "v_{}_{}",
+        SECP256K1_ACCOUNT_ADDR.to_tagged_hex(),
+        ACCOUNT_1_STAKE
+    );
+    assert!(builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&lookup_key));
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE + ACCOUNT_1_STAKE)
+    );
+
+    let exec_request_3 = ExecuteRequestBuilder::standard(
+        SECP256K1_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_UNBOND), None as Option<U512>),
+    )
+    .build();
+    builder.exec(exec_request_3).expect_success().commit();
+
+    assert_eq!(
+        builder
+            .get_pos_contract()
+            .named_keys()
+            .keys()
+            .filter(|key| key.starts_with(&format!("v_{}", SECP256K1_ACCOUNT_ADDR.to_tagged_hex())))
+            .count(),
+        0
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(GENESIS_VALIDATOR_STAKE)
+    );
+}
+
 #[ignore]
 #[test]
 fn should_fail_bonding_with_insufficient_funds() {
@@ -1,5 +1,5 @@
 use num_traits::Zero;
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::TryFrom};
 
 use engine_core::engine_state::genesis::GenesisAccount;
 use engine_shared::motes::Motes;
@@ -69,3 +69,39 @@ fn should_return_bonded_validators() {
 
     assert_eq!(actual, expected);
 }
+
+#[ignore]
+#[test]
+fn should_report_era_validators() {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_1_ADDR,
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::new(ACCOUNT_1_BOND.into()),
+    ));
+    let run_genesis_request = utils::create_run_genesis_request(accounts.clone());
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let actual: HashMap<PublicKey, U512> = builder
+        .era_validators(None)
+        .expect("should get era validators")
+        .take_validators()
+        .into_iter()
+        .map(|bond| <(PublicKey, U512)>::try_from(bond).expect("should convert bond"))
+        .collect();
+
+    let expected: HashMap<PublicKey, U512> = accounts
+        .iter()
+        .filter(|genesis_account| genesis_account.bonded_amount() > Motes::zero())
+        .map(|genesis_account| {
+            (
+                genesis_account.public_key(),
+                genesis_account.bonded_amount().value(),
+            )
+        })
+        .collect();
+
+    assert_eq!(actual, expected);
+}
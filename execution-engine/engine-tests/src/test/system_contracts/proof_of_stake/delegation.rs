@@ -0,0 +1,148 @@
+use engine_core::engine_state::{
+    era_validators,
+    genesis::{GenesisAccount, POS_BONDING_PURSE},
+    CONV_RATE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key, URef, U512};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const VALIDATOR_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const VALIDATOR_STAKE: u64 = 50_000;
+const NOT_A_VALIDATOR_ADDR: PublicKey = PublicKey::ed25519_from([43; 32]);
+const DELEGATION: u64 = 10_000;
+
+const TEST_DELEGATE: &str = "delegate";
+const TEST_UNDELEGATE: &str = "undelegate";
+
+fn get_pos_bonding_purse(builder: &InMemoryWasmTestBuilder) -> URef {
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_BONDING_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS bonding purse")
+}
+
+/// Returns the validator's weight currently bonded in PoS, which only becomes its weight in an
+/// era once the era in progress ends.
+fn get_validator_weight(builder: &InMemoryWasmTestBuilder) -> U512 {
+    era_validators::era_validators_from_pos(builder.get_pos_contract().named_keys())
+        .get(&VALIDATOR_ADDR)
+        .expect("validator should be bonded")
+        .weight()
+}
+
+fn last_exec_cost(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let exec_response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have exec response");
+    Motes::from_gas(utils::get_exec_costs(exec_response)[0], CONV_RATE)
+        .expect("should convert")
+        .value()
+}
+
+#[ignore]
+#[test]
+fn should_add_delegations_to_validator_weight() {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        VALIDATOR_ADDR,
+        Motes::new(VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+        Motes::new(VALIDATOR_STAKE.into()),
+    ));
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let bonding_purse = get_pos_bonding_purse(&builder);
+    let balance_before = builder.get_purse_balance(main_purse);
+
+    let delegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (
+            String::from(TEST_DELEGATE),
+            VALIDATOR_ADDR,
+            U512::from(DELEGATION),
+        ),
+    )
+    .build();
+    let result = builder.exec(delegate_request);
+    if !cfg!(feature = "enable-bonding") && result.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let cost = last_exec_cost(&builder);
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        balance_before - cost - U512::from(DELEGATION)
+    );
+    assert_eq!(
+        builder.get_purse_balance(bonding_purse),
+        U512::from(VALIDATOR_STAKE + DELEGATION)
+    );
+    assert_eq!(
+        get_validator_weight(&builder),
+        U512::from(VALIDATOR_STAKE + DELEGATION)
+    );
+    let delegation_key = format!(
+        "d_{}_{}_{}",
+        DEFAULT_ACCOUNT_ADDR.to_tagged_hex(),
+        VALIDATOR_ADDR.to_tagged_hex(),
+        DELEGATION
+    );
+    assert!(builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&delegation_key));
+
+    // Delegating to an account which isn't bonded fails.
+    let delegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (
+            String::from(TEST_DELEGATE),
+            NOT_A_VALIDATOR_ADDR,
+            U512::from(DELEGATION),
+        ),
+    )
+    .build();
+    builder.exec(delegate_request).commit();
+    assert!(builder.is_error());
+
+    // Undelegating removes the weight and pays the delegation back once the (zero) unbonding
+    // delay has passed.
+    let balance_before = builder.get_purse_balance(main_purse);
+    let undelegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_UNDELEGATE), VALIDATOR_ADDR, None::<U512>),
+    )
+    .build();
+    builder.exec(undelegate_request).expect_success().commit();
+
+    let cost = last_exec_cost(&builder);
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        balance_before - cost + U512::from(DELEGATION)
+    );
+    assert_eq!(
+        builder.get_purse_balance(bonding_purse),
+        U512::from(VALIDATOR_STAKE)
+    );
+    assert_eq!(get_validator_weight(&builder), U512::from(VALIDATOR_STAKE));
+    assert!(!builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&delegation_key));
+}
@@ -0,0 +1,120 @@
+use engine_core::engine_state::{
+    era_rewards::EraReport, era_validators::ValidatorStake, genesis::GenesisAccount,
+    step::StepRequest,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, BlockTime, U512};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const VALIDATOR_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const VALIDATOR_STAKE: u64 = 50_000;
+const DELEGATION: u64 = 10_000;
+
+const TEST_DELEGATE: &str = "delegate";
+
+fn run_genesis(builder: &mut InMemoryWasmTestBuilder) {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        VALIDATOR_ADDR,
+        Motes::new(VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+        Motes::new(VALIDATOR_STAKE.into()),
+    ));
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+}
+
+fn validator_stake(builder: &InMemoryWasmTestBuilder, era_id: u64) -> ValidatorStake {
+    builder
+        .get_era_validators(era_id)
+        .expect("should have era validators")
+        .get(&VALIDATOR_ADDR)
+        .cloned()
+        .expect("validator should be bonded")
+}
+
+#[ignore]
+#[test]
+fn should_record_genesis_validators_as_first_era() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    run_genesis(&mut builder);
+
+    assert_eq!(builder.get_current_era_id(), 0);
+    let era_validators = builder
+        .get_era_validators(0)
+        .expect("should have genesis era validators");
+    assert_eq!(era_validators.len(), 1);
+    let stake = validator_stake(&builder, 0);
+    assert_eq!(stake.bonded_amount(), U512::from(VALIDATOR_STAKE));
+    assert!(stake.delegations().is_empty());
+    assert_eq!(builder.get_era_validators(1), None);
+}
+
+#[ignore]
+#[test]
+fn should_only_start_next_era_at_era_end() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    run_genesis(&mut builder);
+
+    builder.step(StepRequest::new(BlockTime::new(1)));
+    assert_eq!(builder.get_current_era_id(), 0);
+    assert_eq!(builder.get_era_validators(1), None);
+
+    builder.step(StepRequest::new(BlockTime::new(2)).with_era_end(EraReport::default()));
+    assert_eq!(builder.get_current_era_id(), 1);
+    assert_eq!(validator_stake(&builder, 1), validator_stake(&builder, 0));
+}
+
+#[ignore]
+#[test]
+fn should_count_delegations_from_next_era() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    run_genesis(&mut builder);
+
+    let delegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (
+            String::from(TEST_DELEGATE),
+            VALIDATOR_ADDR,
+            U512::from(DELEGATION),
+        ),
+    )
+    .build();
+    let result = builder.exec(delegate_request);
+    if !cfg!(feature = "enable-bonding") && result.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    // The delegation is bonded in PoS straight away, but the era in progress, and so the bonded
+    // validators reported on commit, are unchanged.
+    let bonded_validators = builder
+        .get_bonded_validators()
+        .last()
+        .cloned()
+        .expect("should have bonded validators");
+    assert_eq!(
+        bonded_validators.get(&VALIDATOR_ADDR),
+        Some(&U512::from(VALIDATOR_STAKE))
+    );
+    assert_eq!(
+        validator_stake(&builder, 0).weight(),
+        U512::from(VALIDATOR_STAKE)
+    );
+
+    builder.step(StepRequest::new(BlockTime::new(1)).with_era_end(EraReport::default()));
+
+    let stake = validator_stake(&builder, 1);
+    assert_eq!(stake.bonded_amount(), U512::from(VALIDATOR_STAKE));
+    assert_eq!(
+        stake.delegations().get(&DEFAULT_ACCOUNT_ADDR),
+        Some(&U512::from(DELEGATION))
+    );
+    assert_eq!(stake.weight(), U512::from(VALIDATOR_STAKE + DELEGATION));
+
+    // The previous era's validators are still readable.
+    assert!(validator_stake(&builder, 0).delegations().is_empty());
+}
@@ -2,23 +2,25 @@ use std::convert::TryInto;
 
 use engine_core::engine_state::{
     genesis::{POS_PAYMENT_PURSE, POS_REWARDS_PURSE},
+    upgrade::ActivationPoint,
     CONV_RATE,
 };
 use engine_shared::{account::Account, motes::Motes};
 use engine_test_support::{
     internal::{
-        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
-        DEFAULT_RUN_GENESIS_REQUEST,
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        UpgradeRequestBuilder, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
     },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::PublicKey, Key, URef, U512};
+use types::{account::PublicKey, Key, ProtocolVersion, URef, U512};
 
 const CONTRACT_FINALIZE_PAYMENT: &str = "pos_finalize_payment.wasm";
 const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
 const FINALIZE_PAYMENT: &str = "pos_finalize_payment.wasm";
 const LOCAL_REFUND_PURSE: &str = "local_refund_purse";
 const POS_REFUND_PURSE_NAME: &str = "pos_refund_purse";
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
 
 const SYSTEM_ADDR: PublicKey = PublicKey::ed25519_from([0u8; 32]);
 const ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
@@ -160,6 +162,88 @@ fn finalize_payment_should_refund_to_specified_purse() {
     );
 }
 
+#[ignore]
+#[test]
+fn finalize_payment_should_refund_according_to_refund_ratio() {
+    const NEW_REFUND_RATIO: u8 = 40;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let payment_amount = *DEFAULT_PAYMENT;
+    let refund_purse_flag: u8 = 1;
+    let args: (U512, u8, Option<U512>, Option<PublicKey>) =
+        (payment_amount, refund_purse_flag, None, None);
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = ProtocolVersion::V1_0_0.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(ProtocolVersion::V1_0_0)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_refund_ratio(NEW_REFUND_RATIO)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let rewards_pre_balance = get_pos_rewards_purse_balance(&builder);
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code("do_nothing.wasm", ())
+            .with_payment_code(FINALIZE_PAYMENT, args)
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .build();
+
+        ExecuteRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_protocol_version(new_protocol_version)
+            .build()
+    };
+    builder.exec(exec_request).expect_success().commit();
+
+    let spent_amount: U512 = {
+        let response = builder
+            .get_exec_response(0)
+            .expect("there should be a response");
+
+        let success_result = utils::get_success_result(response);
+        Motes::from_gas(success_result.cost(), CONV_RATE)
+            .expect("should have motes")
+            .value()
+    };
+
+    let expected_refund =
+        (payment_amount - spent_amount) * U512::from(NEW_REFUND_RATIO) / U512::from(100);
+    let refund_post_balance =
+        get_named_account_balance(&builder, DEFAULT_ACCOUNT_ADDR, LOCAL_REFUND_PURSE)
+            .expect("should have refund balance");
+    assert_eq!(
+        refund_post_balance, expected_refund,
+        "user should get partial refund"
+    );
+
+    let rewards_post_balance = get_pos_rewards_purse_balance(&builder);
+    assert_eq!(
+        rewards_post_balance,
+        rewards_pre_balance + payment_amount - expected_refund,
+        "validators should keep the unrefunded payment"
+    );
+    assert!(
+        get_pos_payment_purse_balance(&builder).is_zero(),
+        "payment purse should end with zero balance"
+    );
+}
+
 // ------------- utility functions -------------------- //
 
 fn get_pos_payment_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
@@ -1,5 +1,11 @@
 mod bonding;
 mod commit_validators;
+mod delegation;
+mod era_validators;
 mod finalize_payment;
 mod get_payment_purse;
 mod refund_purse;
+mod rewards;
+mod slashing;
+mod step;
+mod unbonding_delay;
@@ -7,7 +7,10 @@ use engine_test_support::{
 };
 use types::{account::PublicKey, U512};
 
+const CONTRACT_CREATE_PURSE_01: &str = "create_purse_01.wasm";
 const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const REFUND_PURSE_1: &str = "refund_purse_1";
+const REFUND_PURSE_2: &str = "refund_purse_2";
 const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
 
 #[ignore]
@@ -21,7 +24,7 @@ fn should_run_pos_refund_purse_contract_default_account() {
 #[test]
 fn should_run_pos_refund_purse_contract_account_1() {
     let mut builder = initialize();
-    transfer(&mut builder, ACCOUNT_1_ADDR, *DEFAULT_PAYMENT * 2);
+    transfer(&mut builder, ACCOUNT_1_ADDR, *DEFAULT_PAYMENT * 4);
     refund_tests(&mut builder, ACCOUNT_1_ADDR);
 }
 
@@ -46,7 +49,19 @@ fn transfer(builder: &mut InMemoryWasmTestBuilder, public_key: PublicKey, amount
     builder.exec(exec_request).expect_success().commit();
 }
 
+fn create_purse(builder: &mut InMemoryWasmTestBuilder, public_key: PublicKey, name: &str) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(public_key, CONTRACT_CREATE_PURSE_01, (name.to_string(),))
+            .build();
+
+    builder.exec(exec_request).expect_success().commit();
+}
+
 fn refund_tests(builder: &mut InMemoryWasmTestBuilder, public_key: PublicKey) {
+    // Payment code can't create purses, so the refund purses are created beforehand
+    create_purse(builder, public_key, REFUND_PURSE_1);
+    create_purse(builder, public_key, REFUND_PURSE_2);
+
     let exec_request = {
         let deploy = DeployItemBuilder::new()
             .with_address(public_key)
@@ -0,0 +1,119 @@
+use engine_core::engine_state::{
+    era_rewards::EraReport,
+    genesis::{GenesisAccount, POS_REWARDS_PURSE},
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_1_BALANCE: u64 = 2000;
+const ACCOUNT_1_BOND: u64 = 1000;
+
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+const ACCOUNT_2_BALANCE: u64 = 4000;
+const ACCOUNT_2_BOND: u64 = 3000;
+
+/// Bonds two validators at genesis and executes a deploy, so that its payment ends up in the
+/// rewards purse.
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_1_ADDR,
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::new(ACCOUNT_1_BOND.into()),
+    ));
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_2_ADDR,
+        Motes::new(ACCOUNT_2_BALANCE.into()),
+        Motes::new(ACCOUNT_2_BOND.into()),
+    ));
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn get_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_REWARDS_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS rewards purse");
+    builder.get_purse_balance(purse)
+}
+
+fn get_account_balance(builder: &InMemoryWasmTestBuilder, public_key: PublicKey) -> U512 {
+    let account = builder
+        .get_account(public_key)
+        .expect("should have account");
+    builder.get_purse_balance(account.main_purse())
+}
+
+#[ignore]
+#[test]
+fn should_distribute_rewards_pro_rata_to_stakes() {
+    let mut builder = setup();
+
+    let rewards = get_rewards_purse_balance(&builder);
+    assert!(!rewards.is_zero(), "payment should have been collected");
+    let account_1_pre_balance = get_account_balance(&builder, ACCOUNT_1_ADDR);
+    let account_2_pre_balance = get_account_balance(&builder, ACCOUNT_2_ADDR);
+
+    builder.distribute_rewards(EraReport::default());
+
+    let total_bond = ACCOUNT_1_BOND + ACCOUNT_2_BOND;
+    let account_1_reward = rewards * ACCOUNT_1_BOND / total_bond;
+    let account_2_reward = rewards * ACCOUNT_2_BOND / total_bond;
+    assert_eq!(
+        get_account_balance(&builder, ACCOUNT_1_ADDR),
+        account_1_pre_balance + account_1_reward
+    );
+    assert_eq!(
+        get_account_balance(&builder, ACCOUNT_2_ADDR),
+        account_2_pre_balance + account_2_reward
+    );
+    assert_eq!(
+        get_rewards_purse_balance(&builder),
+        rewards - account_1_reward - account_2_reward
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_reward_equivocators() {
+    let mut builder = setup();
+
+    let rewards = get_rewards_purse_balance(&builder);
+    let account_1_pre_balance = get_account_balance(&builder, ACCOUNT_1_ADDR);
+    let account_2_pre_balance = get_account_balance(&builder, ACCOUNT_2_ADDR);
+
+    builder.distribute_rewards(EraReport {
+        equivocators: vec![ACCOUNT_1_ADDR],
+    });
+
+    assert_eq!(
+        get_account_balance(&builder, ACCOUNT_1_ADDR),
+        account_1_pre_balance
+    );
+    assert_eq!(
+        get_account_balance(&builder, ACCOUNT_2_ADDR),
+        account_2_pre_balance + rewards
+    );
+    assert!(get_rewards_purse_balance(&builder).is_zero());
+}
@@ -0,0 +1,124 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::{
+    era_rewards::EraReport,
+    genesis::{GenesisAccount, POS_BONDING_PURSE, POS_REWARDS_PURSE},
+    slashing::{EquivocationEvidence, SignedMessageHeader, SlashingResult},
+    step::StepRequest,
+};
+use engine_shared::{
+    motes::Motes,
+    newtypes::{Blake2bHash, CorrelationId},
+};
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_PROTOCOL_VERSION,
+};
+use types::{account::PublicKey, BlockTime, Key, U512};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_1_BALANCE: u64 = 2000;
+const ACCOUNT_1_BOND: u64 = 1000;
+/// 10% of `ACCOUNT_1_BOND`.
+const ACCOUNT_1_SLASHED: u64 = 100;
+
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+
+fn equivocation_by(validator: PublicKey) -> EquivocationEvidence {
+    let header = |message: &[u8]| SignedMessageHeader {
+        message_hash: Blake2bHash::new(message),
+        validator,
+        validator_seq_num: 1,
+    };
+    EquivocationEvidence::new(header(b"first"), header(b"second"))
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_1_ADDR,
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::new(ACCOUNT_1_BOND.into()),
+    ));
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn get_pos_purse_balance(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS purse");
+    builder.get_purse_balance(purse)
+}
+
+fn get_era_weight(builder: &InMemoryWasmTestBuilder, validator: PublicKey) -> Option<U512> {
+    builder
+        .era_validators(None)
+        .expect("should get era validators")
+        .take_validators()
+        .into_iter()
+        .map(|bond| <(PublicKey, U512)>::try_from(bond).expect("should convert bond"))
+        .find(|(public_key, _)| *public_key == validator)
+        .map(|(_, weight)| weight)
+}
+
+#[ignore]
+#[test]
+fn should_slash_equivocating_validator() {
+    let mut builder = setup();
+
+    let bonding_pre_balance = get_pos_purse_balance(&builder, POS_BONDING_PURSE);
+    let rewards_pre_balance = get_pos_purse_balance(&builder, POS_REWARDS_PURSE);
+
+    builder.commit_slashing(equivocation_by(ACCOUNT_1_ADDR));
+
+    // The slashed stake leaves the bonding purse straight away...
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        bonding_pre_balance - ACCOUNT_1_SLASHED
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        rewards_pre_balance + ACCOUNT_1_SLASHED
+    );
+    assert_eq!(
+        get_era_weight(&builder, ACCOUNT_1_ADDR),
+        Some(U512::from(ACCOUNT_1_BOND))
+    );
+
+    // ...but only lowers the validator's weight from the next era on.
+    builder.step(StepRequest::new(BlockTime::new(1)).with_era_end(EraReport::default()));
+    assert_eq!(
+        get_era_weight(&builder, ACCOUNT_1_ADDR),
+        Some(U512::from(ACCOUNT_1_BOND - ACCOUNT_1_SLASHED))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_slash_unbonded_validator() {
+    let builder = setup();
+    let pre_state_hash = Blake2bHash::try_from(builder.get_post_state_hash().as_slice())
+        .expect("should convert post-state hash");
+
+    let result = builder
+        .get_engine_state()
+        .commit_slashing(
+            CorrelationId::new(),
+            *DEFAULT_PROTOCOL_VERSION,
+            pre_state_hash,
+            equivocation_by(ACCOUNT_2_ADDR),
+        )
+        .expect("should run slashing");
+
+    match result {
+        SlashingResult::Failure(_) => {}
+        other => panic!("Expected slashing to fail, but instead got: {}", other),
+    }
+}
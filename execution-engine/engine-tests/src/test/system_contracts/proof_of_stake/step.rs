@@ -0,0 +1,144 @@
+use engine_core::engine_state::{
+    era_rewards::EraReport,
+    genesis::{ExecConfig, GenesisAccount, POS_BONDING_PURSE, POS_REWARDS_PURSE},
+    run_genesis_request::RunGenesisRequest,
+    step::StepRequest,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, TestClock, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
+        KEY_RECOVERY_INSTALL_CONTRACT, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+        STANDARD_PAYMENT_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, BlockTime, Key, URef, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const GENESIS_VALIDATOR_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const ACCOUNT_STAKE: u64 = 100_000;
+const UNBONDING_DELAY: u64 = 1_000;
+
+const TEST_BOND: &str = "bond";
+const TEST_UNBOND: &str = "unbond";
+
+fn run_genesis_request() -> RunGenesisRequest {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        GENESIS_VALIDATOR_ADDR,
+        Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+        Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+    ));
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT),
+        accounts,
+        *DEFAULT_WASM_COSTS,
+        UNBONDING_DELAY,
+    );
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn get_pos_purse(builder: &InMemoryWasmTestBuilder, name: &str) -> URef {
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .get(name)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS purse")
+}
+
+fn step_request(clock: &TestClock) -> StepRequest {
+    StepRequest::new(BlockTime::new(clock.block_time()))
+}
+
+#[ignore]
+#[test]
+fn should_distribute_rewards_only_at_era_end() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request())
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    let rewards_purse = get_pos_purse(&builder, POS_REWARDS_PURSE);
+    let rewards = builder.get_purse_balance(rewards_purse);
+    assert!(!rewards.is_zero(), "payment should have been collected");
+
+    let mut clock = TestClock::default();
+    builder.step(step_request(&clock));
+    assert_eq!(builder.get_purse_balance(rewards_purse), rewards);
+
+    clock.advance(1);
+    builder.step(step_request(&clock).with_era_end(EraReport::default()));
+    assert!(builder.get_purse_balance(rewards_purse) < rewards);
+}
+
+#[ignore]
+#[test]
+fn should_pay_out_unbonded_funds_in_step_after_delay() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request());
+    let mut clock = TestClock::default();
+
+    let bond_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_BOND), U512::from(ACCOUNT_STAKE)),
+    )
+    .with_clock(&clock)
+    .build();
+    let result = builder.exec(bond_request);
+    if !cfg!(feature = "enable-bonding") && result.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let unbond_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_UNBOND), Some(U512::from(ACCOUNT_STAKE))),
+    )
+    .with_clock(&clock)
+    .build();
+    builder.exec(unbond_request).expect_success().commit();
+
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let bonding_purse = get_pos_purse(&builder, POS_BONDING_PURSE);
+    let total_stakes = U512::from(GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE);
+    let balance_before = builder.get_purse_balance(main_purse);
+
+    // Stepping before the delay has passed releases nothing.
+    clock.advance(UNBONDING_DELAY - 1);
+    builder.step(step_request(&clock));
+    assert_eq!(builder.get_purse_balance(main_purse), balance_before);
+    assert_eq!(builder.get_purse_balance(bonding_purse), total_stakes);
+
+    // Once the delay has passed, the funds are paid out without any deploy.
+    clock.advance(1);
+    builder.step(step_request(&clock));
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        balance_before + U512::from(ACCOUNT_STAKE)
+    );
+    assert_eq!(
+        builder.get_purse_balance(bonding_purse),
+        U512::from(GENESIS_VALIDATOR_STAKE)
+    );
+}
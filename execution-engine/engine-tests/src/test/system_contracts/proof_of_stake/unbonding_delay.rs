@@ -0,0 +1,207 @@
+use std::convert::TryFrom;
+
+use contract::args_parser::ArgsParser;
+use engine_core::engine_state::{
+    genesis::{ExecConfig, GenesisAccount, POS_BONDING_PURSE},
+    run_genesis_request::RunGenesisRequest,
+    CONV_RATE,
+};
+use engine_shared::{
+    additive_map::AdditiveMap, motes::Motes, stored_value::StoredValue, transform::Transform,
+};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, TestClock, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
+        KEY_RECOVERY_INSTALL_CONTRACT, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+        STANDARD_PAYMENT_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, bytesrepr::ToBytes, CLValue, Key, URef, U512};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const GENESIS_VALIDATOR_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const GENESIS_VALIDATOR_STAKE: u64 = 50_000;
+const ACCOUNT_STAKE: u64 = 100_000;
+const UNBONDING_DELAY: u64 = 1_000;
+const UNBOND_BLOCK_TIME: u64 = 100;
+
+const TEST_BOND: &str = "bond";
+const TEST_UNBOND: &str = "unbond";
+const TEST_WITHDRAW: &str = "withdraw";
+
+fn run_genesis_request(unbonding_delay: u64) -> RunGenesisRequest {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        GENESIS_VALIDATOR_ADDR,
+        Motes::new(GENESIS_VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+        Motes::new(GENESIS_VALIDATOR_STAKE.into()),
+    ));
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(KEY_RECOVERY_INSTALL_CONTRACT),
+        accounts,
+        *DEFAULT_WASM_COSTS,
+        unbonding_delay,
+    );
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn get_pos_bonding_purse(builder: &InMemoryWasmTestBuilder) -> URef {
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_BONDING_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS bonding purse")
+}
+
+fn get_balance_key(builder: &InMemoryWasmTestBuilder, purse: URef) -> Key {
+    let mint = builder.get_mint_contract_uref();
+    let purse_bytes = purse
+        .addr()
+        .to_bytes()
+        .expect("should serialize purse address");
+    match builder.query(None, Key::local(mint.addr(), &purse_bytes), &[]) {
+        Ok(StoredValue::CLValue(cl_value)) => cl_value.into_t().expect("should be a key"),
+        _ => panic!("should find balance key"),
+    }
+}
+
+/// Executes `args` against the pos-bonding contract and returns the cost of the deploy in motes.
+fn exec_pos_bonding(
+    builder: &mut InMemoryWasmTestBuilder,
+    clock: &TestClock,
+    args: impl ArgsParser,
+) -> U512 {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_POS_BONDING, args)
+            .with_clock(clock)
+            .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have exec response");
+    Motes::from_gas(utils::get_exec_costs(exec_response)[0], CONV_RATE)
+        .expect("should convert")
+        .value()
+}
+
+#[ignore]
+#[test]
+fn should_release_unbonded_funds_only_after_delay() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(UNBONDING_DELAY));
+    let mut clock = TestClock::default();
+
+    let bond_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (String::from(TEST_BOND), U512::from(ACCOUNT_STAKE)),
+    )
+    .with_clock(&clock)
+    .build();
+    let result = builder.exec(bond_request);
+    if !cfg!(feature = "enable-bonding") && result.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let bonding_purse = get_pos_bonding_purse(&builder);
+    let total_stakes = U512::from(GENESIS_VALIDATOR_STAKE + ACCOUNT_STAKE);
+    assert_eq!(builder.get_purse_balance(bonding_purse), total_stakes);
+
+    // Unbonding drops the stake immediately but keeps the funds in the bonding purse.
+    let balance_before = builder.get_purse_balance(main_purse);
+    let unbond_block_time = clock.advance(UNBOND_BLOCK_TIME);
+    let cost = exec_pos_bonding(
+        &mut builder,
+        &clock,
+        (String::from(TEST_UNBOND), Some(U512::from(ACCOUNT_STAKE))),
+    );
+    assert_eq!(builder.get_purse_balance(main_purse), balance_before - cost);
+    assert_eq!(builder.get_purse_balance(bonding_purse), total_stakes);
+    let lookup_key = format!(
+        "v_{}_{}",
+        DEFAULT_ACCOUNT_ADDR.to_tagged_hex(),
+        ACCOUNT_STAKE
+    );
+    assert!(!builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&lookup_key));
+    let unbonding_queue = builder
+        .unbonding_queue(None)
+        .expect("should get unbonding queue");
+    assert_eq!(unbonding_queue.get_entries().len(), 1);
+    let entry = &unbonding_queue.get_entries()[0];
+    assert_eq!(entry.get_public_key(), DEFAULT_ACCOUNT_ADDR.as_bytes());
+    assert_eq!(
+        U512::try_from(entry.get_amount().clone()).expect("should convert amount"),
+        U512::from(ACCOUNT_STAKE)
+    );
+    assert_eq!(entry.get_timestamp(), unbond_block_time);
+
+    // Withdrawing before the delay has passed releases nothing.
+    let balance_before = builder.get_purse_balance(main_purse);
+    clock.advance(UNBONDING_DELAY - 1);
+    let cost = exec_pos_bonding(&mut builder, &clock, (String::from(TEST_WITHDRAW),));
+    assert_eq!(builder.get_purse_balance(main_purse), balance_before - cost);
+    assert_eq!(builder.get_purse_balance(bonding_purse), total_stakes);
+
+    // Once the delay has passed, the funds are paid out.
+    let balance_before = builder.get_purse_balance(main_purse);
+    clock.advance(1);
+    let cost = exec_pos_bonding(&mut builder, &clock, (String::from(TEST_WITHDRAW),));
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        balance_before - cost + U512::from(ACCOUNT_STAKE)
+    );
+    assert_eq!(
+        builder.get_purse_balance(bonding_purse),
+        U512::from(GENESIS_VALIDATOR_STAKE)
+    );
+    assert!(builder
+        .unbonding_queue(None)
+        .expect("should get unbonding queue")
+        .get_entries()
+        .is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_reject_commit_draining_bonding_purse() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(UNBONDING_DELAY));
+
+    let bonding_purse = get_pos_bonding_purse(&builder);
+    let balance_key = get_balance_key(&builder, bonding_purse);
+    let drained_balance =
+        CLValue::from_t(U512::from(GENESIS_VALIDATOR_STAKE - 1)).expect("should create CLValue");
+
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        balance_key.normalize(),
+        Transform::Write(StoredValue::CLValue(drained_balance)),
+    );
+    let commit_response = builder.commit_transforms(builder.get_post_state_hash(), effects);
+    assert!(!commit_response.has_success());
+
+    assert_eq!(
+        builder.get_purse_balance(bonding_purse),
+        U512::from(GENESIS_VALIDATOR_STAKE)
+    );
+}
@@ -132,15 +132,13 @@ fn should_raise_insufficient_payment_when_payment_code_does_not_pay_enough() {
         .expect("there should be a response");
 
     let execution_result = utils::get_success_result(response);
-    let error_message = format!(
-        "{}",
-        execution_result.as_error().expect("should have error")
-    );
-
-    assert_eq!(
-        error_message, "Insufficient payment",
-        "expected insufficient payment"
+    let error = execution_result.as_error().expect("should have error");
+    let (cause, amount_taken) = assert_matches!(
+        error,
+        Error::PaymentFailure { error, amount_taken, .. } => (error, amount_taken)
     );
+    assert_matches!(**cause, Error::InsufficientPayment);
+    assert_eq!(*amount_taken, Motes::new(U512::from(MAX_PAYMENT)));
 }
 
 #[cfg(feature = "use-system-contracts")]
@@ -207,15 +205,13 @@ fn should_raise_insufficient_payment_error_when_out_of_gas() {
         .expect("there should be a response");
 
     let execution_result = utils::get_success_result(response);
-    let error_message = format!(
-        "{}",
-        execution_result.as_error().expect("should have error")
-    );
-
-    assert_eq!(
-        error_message, "Insufficient payment",
-        "expected insufficient payment"
+    let error = execution_result.as_error().expect("should have error");
+    let (cause, amount_taken) = assert_matches!(
+        error,
+        Error::PaymentFailure { error, amount_taken, .. } => (error, amount_taken)
     );
+    assert_matches!(**cause, Error::InsufficientPayment);
+    assert_eq!(*amount_taken, Motes::new(U512::from(MAX_PAYMENT)));
 }
 
 #[ignore]
@@ -281,10 +277,15 @@ fn should_forward_payment_execution_runtime_error() {
 
     let execution_result = utils::get_success_result(response);
     let error = execution_result.as_error().expect("should have error");
-    assert_matches!(
+    let (cause, payment_gas) = assert_matches!(
         error,
+        Error::PaymentFailure { error, payment_gas, .. } => (error, payment_gas)
+    );
+    assert_matches!(
+        **cause,
         Error::Exec(execution::Error::Revert(ApiError::User(100)))
     );
+    assert!(payment_gas.value() > U512::zero());
 }
 
 #[ignore]
@@ -350,7 +351,8 @@ fn should_forward_payment_execution_gas_limit_error() {
 
     let execution_result = utils::get_success_result(response);
     let error = execution_result.as_error().expect("should have error");
-    assert_matches!(error, Error::Exec(execution::Error::GasLimit));
+    let cause = assert_matches!(error, Error::PaymentFailure { error, .. } => error);
+    assert_matches!(**cause, Error::Exec(execution::Error::GasLimit));
 }
 
 #[ignore]
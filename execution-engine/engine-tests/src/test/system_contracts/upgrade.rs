@@ -8,7 +8,7 @@ use engine_test_support::internal::{
 };
 #[cfg(feature = "use-system-contracts")]
 use engine_test_support::{internal::ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_features::WasmFeatures};
 use types::ProtocolVersion;
 #[cfg(feature = "use-system-contracts")]
 use types::{CLValue, Key, U512};
@@ -33,6 +33,18 @@ fn get_upgraded_wasm_costs() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        max_memory_pages: 64,
+        max_table_size: 4096,
+        hash_per_byte: 2,
+        verify_ed25519: 25_000,
+        verify_secp256k1: 50_000,
+        bignum_op: 10,
+        storage_bucket_size: 128,
+        storage_read_per_bucket: 50,
+        storage_write_per_bucket: 500,
+        max_module_size: 4 * 1024 * 1024,
+        max_function_count: 10_000,
+        max_named_keys_per_write: 10_000,
     }
 }
 
@@ -636,3 +648,61 @@ fn should_fail_major_upgrade_without_installer() {
         Error::InvalidUpgradeConfig.to_string()
     );
 }
+
+#[ignore]
+#[test]
+fn should_enable_wasm_features_on_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let genesis_protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(PROTOCOL_VERSION)
+        .expect("should have result")
+        .expect("should have protocol data");
+    assert_eq!(
+        genesis_protocol_data.wasm_features(),
+        WasmFeatures::default(),
+        "only the MVP should be enabled at genesis"
+    );
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch);
+
+    let new_wasm_features = WasmFeatures {
+        sign_ext: true,
+        bulk_memory: true,
+        ..Default::default()
+    };
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_wasm_features(new_wasm_features)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded protocol data");
+
+    assert_eq!(
+        new_wasm_features,
+        upgraded_protocol_data.wasm_features(),
+        "upgraded features should equal new features"
+    );
+}
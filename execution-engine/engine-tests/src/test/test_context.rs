@@ -0,0 +1,72 @@
+use engine_test_support::{
+    Code, PublicKey, Session, SessionBuilder, TestContextBuilder, Value, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{bytesrepr::ToBytes, Key, U512};
+
+const CONTRACT_LOCAL_STATE: &str = "local_state.wasm";
+const REVERT_WASM: &str = "revert.wasm";
+const LOCAL_KEY: [u8; 32] = [66u8; 32];
+
+fn session(wasm: &str) -> Session {
+    SessionBuilder::new(Code::from(wasm), ())
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_expose_writes_and_balances_of_successful_session() {
+    let mut context = TestContextBuilder::new().build();
+
+    let initial_balance = context
+        .get_account_balance(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert_eq!(initial_balance, U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE));
+
+    context
+        .try_run(session(CONTRACT_LOCAL_STATE))
+        .expect("should run session");
+
+    let local_key = Key::local(
+        DEFAULT_ACCOUNT_ADDR.value(),
+        &LOCAL_KEY.to_bytes().expect("should serialize"),
+    );
+    let written = context
+        .written_value(local_key)
+        .expect("should have written local key");
+    let expected = Value::from_t(String::from("Hello, world!")).expect("should construct Value");
+    assert_eq!(written, expected);
+
+    let final_balance = context
+        .get_account_balance(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert!(
+        final_balance < initial_balance,
+        "should have paid for the session"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_report_failed_session_without_committing() {
+    let mut context = TestContextBuilder::new().build();
+    let initial_balance = context.get_account_balance(DEFAULT_ACCOUNT_ADDR);
+
+    let error = context.try_run(session(REVERT_WASM)).err();
+    assert!(error.is_some(), "reverting session should fail");
+
+    assert_eq!(
+        context.get_account_balance(DEFAULT_ACCOUNT_ADDR),
+        initial_balance
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_find_balance_of_unknown_account() {
+    let context = TestContextBuilder::new().build();
+    let unknown = PublicKey::ed25519_from([255u8; 32]);
+    assert!(context.main_purse_address(unknown).is_none());
+}
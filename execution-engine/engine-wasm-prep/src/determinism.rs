@@ -0,0 +1,174 @@
+//! Validation that a module can only behave deterministically.
+//!
+//! Floating point arithmetic is the main source of non-determinism in Wasm: NaN bit patterns and
+//! some rounding behaviour differ between platforms, so validators could disagree on the result of
+//! a deploy.  The gas rules already forbid float instructions inside function bodies, but this pass
+//! also rejects float values which never reach an instruction (in signatures, locals and globals)
+//! and reports which construct was at fault.
+//!
+//! Shared memories are the other non-deterministic construct in the spec.  The Wasm parser is built
+//! without support for the threads proposal, so modules declaring one fail to deserialize and
+//! never reach this pass.
+
+use parity_wasm::elements::{External, Instruction, Module, Type, ValueType};
+use pwasm_utils::rules::InstructionType;
+
+use crate::PreprocessingError;
+
+/// Rejects modules which use floating point values or SIMD instructions anywhere.
+pub(crate) fn check(module: &Module) -> Result<(), PreprocessingError> {
+    if let Some(section) = module.type_section() {
+        for ty in section.types() {
+            let Type::Function(function_type) = ty;
+            let return_type = function_type.return_type();
+            for value_type in function_type.params().iter().chain(return_type.iter()) {
+                check_value_type(*value_type, "function signature")?;
+            }
+        }
+    }
+
+    if let Some(section) = module.import_section() {
+        for entry in section.entries() {
+            if let External::Global(global_type) = entry.external() {
+                check_value_type(global_type.content_type(), "imported global")?;
+            }
+        }
+    }
+
+    if let Some(section) = module.global_section() {
+        for entry in section.entries() {
+            check_value_type(entry.global_type().content_type(), "global")?;
+            for instruction in entry.init_expr().code() {
+                check_instruction(instruction)?;
+            }
+        }
+    }
+
+    if let Some(section) = module.code_section() {
+        for body in section.bodies() {
+            for local in body.locals() {
+                check_value_type(local.value_type(), "local")?;
+            }
+            for instruction in body.code().elements() {
+                check_instruction(instruction)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn check_value_type(value_type: ValueType, context: &str) -> Result<(), PreprocessingError> {
+    match value_type {
+        ValueType::F32 | ValueType::F64 | ValueType::V128 => Err(
+            PreprocessingError::NonDeterministic(format!("{} of type {}", context, value_type)),
+        ),
+        ValueType::I32 | ValueType::I64 => Ok(()),
+    }
+}
+
+fn check_instruction(instruction: &Instruction) -> Result<(), PreprocessingError> {
+    let is_float = match instruction {
+        // Classified as plain loads and stores by the gas rules.
+        Instruction::F32Load(..)
+        | Instruction::F64Load(..)
+        | Instruction::F32Store(..)
+        | Instruction::F64Store(..)
+        | Instruction::Simd(_) => true,
+        _ => match InstructionType::op(instruction) {
+            InstructionType::Float
+            | InstructionType::FloatConst
+            | InstructionType::FloatComparison
+            | InstructionType::FloatConversion
+            | InstructionType::Reinterpretation => true,
+            _ => false,
+        },
+    };
+    if is_float {
+        return Err(PreprocessingError::NonDeterministic(format!(
+            "instruction {}",
+            instruction
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{Instruction, Instructions, Module, ValueType},
+    };
+
+    use super::*;
+
+    fn module_with_body(param: ValueType, instructions: Vec<Instruction>) -> Module {
+        builder::module()
+            .function()
+            .signature()
+            .with_param(param)
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .build()
+    }
+
+    #[test]
+    fn should_accept_integer_module() {
+        let module = module_with_body(
+            ValueType::I32,
+            vec![
+                Instruction::GetLocal(0),
+                Instruction::I32Const(1),
+                Instruction::I32Add,
+                Instruction::Drop,
+                Instruction::End,
+            ],
+        );
+        assert!(check(&module).is_ok());
+    }
+
+    #[test]
+    fn should_reject_float_instruction() {
+        let module = module_with_body(
+            ValueType::I32,
+            vec![
+                Instruction::F32Const(0),
+                Instruction::Drop,
+                Instruction::End,
+            ],
+        );
+        assert!(matches!(
+            check(&module),
+            Err(PreprocessingError::NonDeterministic(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_float_load() {
+        let module = module_with_body(
+            ValueType::I32,
+            vec![
+                Instruction::GetLocal(0),
+                Instruction::F64Load(3, 0),
+                Instruction::Drop,
+                Instruction::End,
+            ],
+        );
+        assert!(matches!(
+            check(&module),
+            Err(PreprocessingError::NonDeterministic(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_float_signature() {
+        let module = module_with_body(ValueType::F64, vec![Instruction::End]);
+        assert!(matches!(
+            check(&module),
+            Err(PreprocessingError::NonDeterministic(_))
+        ));
+    }
+}
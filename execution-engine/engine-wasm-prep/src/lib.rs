@@ -1,20 +1,31 @@
+mod determinism;
 pub mod wasm_costs;
+pub mod wasm_features;
 
 use std::fmt::{self, Display, Formatter};
 
-use parity_wasm::elements::{self, Module};
+use parity_wasm::elements::{
+    self, External, Instruction, MemoryType, Module, ResizableLimits, TableType,
+};
 use pwasm_utils::{self, stack_height};
 
-use crate::wasm_costs::WasmCosts;
+use crate::{
+    wasm_costs::WasmCosts,
+    wasm_features::{WasmFeature, WasmFeatures},
+};
 
-//NOTE: size of Wasm memory page is 64 KiB
-pub const MEM_PAGES: u32 = 64;
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PreprocessingError {
     Deserialize(String),
     OperationForbiddenByGasRules,
     StackLimiter,
+    FeatureDisabled(WasmFeature),
+    ExcessiveMemory { pages: u32, max: u32 },
+    ExcessiveTableSize { size: u32, max: u32 },
+    NonDeterministic(String),
+    ExcessiveModuleSize { size: usize, max: u32 },
+    ExcessiveFunctionCount { count: usize, max: u32 },
+    UnknownImport { module: String, field: String },
 }
 
 impl From<elements::Error> for PreprocessingError {
@@ -29,36 +40,418 @@ impl Display for PreprocessingError {
             PreprocessingError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
             PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
             PreprocessingError::StackLimiter => write!(f, "Stack limiter error"),
+            PreprocessingError::FeatureDisabled(feature) => {
+                write!(f, "Wasm feature not enabled by the protocol: {}", feature)
+            }
+            PreprocessingError::ExcessiveMemory { pages, max } => write!(
+                f,
+                "Module declares {} memory pages, more than the maximum of {}",
+                pages, max
+            ),
+            PreprocessingError::ExcessiveTableSize { size, max } => write!(
+                f,
+                "Module declares a table of {} elements, more than the maximum of {}",
+                size, max
+            ),
+            PreprocessingError::NonDeterministic(construct) => {
+                write!(f, "Module uses non-deterministic {}", construct)
+            }
+            PreprocessingError::ExcessiveModuleSize { size, max } => write!(
+                f,
+                "Module of {} bytes exceeds the maximum of {}",
+                size, max
+            ),
+            PreprocessingError::ExcessiveFunctionCount { count, max } => write!(
+                f,
+                "Module defines {} functions, more than the maximum of {}",
+                count, max
+            ),
+            PreprocessingError::UnknownImport { module, field } => {
+                write!(f, "Module imports unknown host function {}.{}", module, field)
+            }
         }
     }
 }
 
+/// The module from which contracts import host functions.
+pub const HOST_MODULE: &str = "env";
+
 pub struct Preprocessor {
     wasm_costs: WasmCosts,
-    // Number of memory pages.
-    mem_pages: u32,
+    wasm_features: WasmFeatures,
+    check_determinism: bool,
+    is_host_function: Option<fn(&str) -> bool>,
 }
 
 impl Preprocessor {
-    pub fn new(wasm_costs: WasmCosts) -> Self {
+    pub fn new(wasm_costs: WasmCosts, wasm_features: WasmFeatures) -> Self {
         Self {
             wasm_costs,
-            mem_pages: MEM_PAGES,
+            wasm_features,
+            check_determinism: false,
+            is_host_function: None,
         }
     }
 
+    /// Sets whether modules using floating point values, or anything else whose behaviour can
+    /// differ between validators, are rejected.
+    pub fn with_determinism_checks(mut self, check_determinism: bool) -> Self {
+        self.check_determinism = check_determinism;
+        self
+    }
+
+    /// Sets the whitelist of host functions modules may import: every import must come from
+    /// [`HOST_MODULE`], and imported functions must be named such that `is_host_function`
+    /// returns `true`.
+    pub fn with_import_whitelist(mut self, is_host_function: fn(&str) -> bool) -> Self {
+        self.is_host_function = Some(is_host_function);
+        self
+    }
+
     pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
-        let module = deserialize(module_bytes)?;
-        let module = pwasm_utils::externalize_mem(module, None, self.mem_pages);
+        let max = self.wasm_costs.max_module_size;
+        if module_bytes.len() > max as usize {
+            return Err(PreprocessingError::ExcessiveModuleSize {
+                size: module_bytes.len(),
+                max,
+            });
+        }
+        let mut module = deserialize(module_bytes)?;
+        self.check_imports(&module)?;
+        self.check_features(&module)?;
+        if self.check_determinism {
+            determinism::check(&module)?;
+        }
+        self.check_limits(&module)?;
+        self.cap_memory(&mut module);
+        let module = pwasm_utils::externalize_mem(module, None, self.wasm_costs.max_memory_pages);
         let module = pwasm_utils::inject_gas_counter(module, &self.wasm_costs.to_set())
             .map_err(|_| PreprocessingError::OperationForbiddenByGasRules)?;
         let module = stack_height::inject_limiter(module, self.wasm_costs.max_stack_height)
             .map_err(|_| PreprocessingError::StackLimiter)?;
         Ok(module)
     }
+
+    /// Rejects modules importing anything other than the whitelisted host functions, if a whitelist
+    /// is set.  Other imports would only fail later, when the module is instantiated.
+    fn check_imports(&self, module: &Module) -> Result<(), PreprocessingError> {
+        let is_host_function = match self.is_host_function {
+            Some(is_host_function) => is_host_function,
+            None => return Ok(()),
+        };
+        let entries = module
+            .import_section()
+            .map(|section| section.entries())
+            .unwrap_or_default();
+        for entry in entries {
+            let is_known = entry.module() == HOST_MODULE
+                && match entry.external() {
+                    External::Function(_) => is_host_function(entry.field()),
+                    _ => true,
+                };
+            if !is_known {
+                return Err(PreprocessingError::UnknownImport {
+                    module: entry.module().to_string(),
+                    field: entry.field().to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects modules using instructions from post-MVP proposals which are not enabled.
+    fn check_features(&self, module: &Module) -> Result<(), PreprocessingError> {
+        let bodies = match module.code_section() {
+            Some(code_section) => code_section.bodies(),
+            None => return Ok(()),
+        };
+        for body in bodies {
+            for instruction in body.code().elements() {
+                let feature = match instruction {
+                    Instruction::SignExt(_) => WasmFeature::SignExt,
+                    Instruction::Bulk(_) => WasmFeature::BulkMemory,
+                    Instruction::Simd(_) => WasmFeature::Simd,
+                    _ => continue,
+                };
+                if !self.wasm_features.is_enabled(feature) {
+                    return Err(PreprocessingError::FeatureDisabled(feature));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects modules which define more functions, or whose memories or tables, whether defined
+    /// or imported, declare more than the protocol allows.
+    fn check_limits(&self, module: &Module) -> Result<(), PreprocessingError> {
+        let count = module
+            .function_section()
+            .map(|section| section.entries().len())
+            .unwrap_or_default();
+        let max = self.wasm_costs.max_function_count;
+        if count > max as usize {
+            return Err(PreprocessingError::ExcessiveFunctionCount { count, max });
+        }
+
+        let imports = module
+            .import_section()
+            .map(|section| section.entries())
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| entry.external());
+
+        let mut memory_limits: Vec<&ResizableLimits> = Vec::new();
+        let mut table_limits: Vec<&ResizableLimits> = Vec::new();
+        for external in imports {
+            match external {
+                External::Memory(memory_type) => memory_limits.push(memory_type.limits()),
+                External::Table(table_type) => table_limits.push(table_type.limits()),
+                _ => (),
+            }
+        }
+        if let Some(section) = module.memory_section() {
+            memory_limits.extend(section.entries().iter().map(MemoryType::limits));
+        }
+        if let Some(section) = module.table_section() {
+            table_limits.extend(section.entries().iter().map(TableType::limits));
+        }
+
+        let max = self.wasm_costs.max_memory_pages;
+        for limits in memory_limits {
+            let pages = limits.maximum().unwrap_or(0).max(limits.initial());
+            if pages > max {
+                return Err(PreprocessingError::ExcessiveMemory { pages, max });
+            }
+        }
+
+        let max = self.wasm_costs.max_table_size;
+        for limits in table_limits {
+            let size = limits.maximum().unwrap_or(0).max(limits.initial());
+            if size > max {
+                return Err(PreprocessingError::ExcessiveTableSize { size, max });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gives a maximum to defined memories which don't declare one, so they can't grow past the
+    /// protocol's limit.
+    fn cap_memory(&self, module: &mut Module) {
+        if let Some(section) = module.memory_section_mut() {
+            for memory_type in section.entries_mut() {
+                if memory_type.limits().maximum().is_none() {
+                    *memory_type = MemoryType::new(
+                        memory_type.limits().initial(),
+                        Some(self.wasm_costs.max_memory_pages),
+                    );
+                }
+            }
+        }
+    }
 }
 
 // Returns a parity Module from bytes without making modifications or limits
 pub fn deserialize(module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
     parity_wasm::deserialize_buffer::<Module>(module_bytes).map_err(Into::into)
 }
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{Instruction, Instructions, Module, SignExtInstruction, ValueType},
+    };
+
+    use super::*;
+
+    fn sign_ext_module() -> Module {
+        builder::module()
+            .function()
+            .signature()
+            .with_param(ValueType::I32)
+            .with_return_type(Some(ValueType::I32))
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::GetLocal(0),
+                Instruction::SignExt(SignExtInstruction::I32Extend8S),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .build()
+    }
+
+    #[test]
+    fn should_reject_disabled_feature() {
+        let preprocessor = Preprocessor::new(WasmCosts::default(), WasmFeatures::default());
+        let module = sign_ext_module();
+        assert!(matches!(
+            preprocessor.check_features(&module),
+            Err(PreprocessingError::FeatureDisabled(WasmFeature::SignExt))
+        ));
+    }
+
+    #[test]
+    fn should_accept_enabled_feature() {
+        let wasm_features = WasmFeatures {
+            sign_ext: true,
+            ..Default::default()
+        };
+        let preprocessor = Preprocessor::new(WasmCosts::default(), wasm_features);
+        let module = sign_ext_module();
+        assert!(preprocessor.check_features(&module).is_ok());
+    }
+
+    fn limited_wasm_costs() -> WasmCosts {
+        WasmCosts {
+            max_memory_pages: 16,
+            max_table_size: 32,
+            max_module_size: 1024,
+            max_function_count: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_reject_excessive_memory() {
+        let preprocessor = Preprocessor::new(limited_wasm_costs(), WasmFeatures::default());
+        let module = builder::module().memory().with_min(17).build().build();
+        assert!(matches!(
+            preprocessor.check_limits(&module),
+            Err(PreprocessingError::ExcessiveMemory { pages: 17, max: 16 })
+        ));
+
+        let module = builder::module()
+            .memory()
+            .with_min(1)
+            .with_max(Some(17))
+            .build()
+            .build();
+        assert!(matches!(
+            preprocessor.check_limits(&module),
+            Err(PreprocessingError::ExcessiveMemory { pages: 17, max: 16 })
+        ));
+    }
+
+    #[test]
+    fn should_reject_excessive_table_size() {
+        let preprocessor = Preprocessor::new(limited_wasm_costs(), WasmFeatures::default());
+        let module = builder::module()
+            .table()
+            .with_min(1)
+            .with_max(Some(33))
+            .build()
+            .build();
+        assert!(matches!(
+            preprocessor.check_limits(&module),
+            Err(PreprocessingError::ExcessiveTableSize { size: 33, max: 32 })
+        ));
+    }
+
+    #[test]
+    fn should_accept_memory_and_table_within_limits() {
+        let preprocessor = Preprocessor::new(limited_wasm_costs(), WasmFeatures::default());
+        let module = builder::module()
+            .memory()
+            .with_min(16)
+            .with_max(Some(16))
+            .build()
+            .table()
+            .with_min(32)
+            .build()
+            .build();
+        assert!(preprocessor.check_limits(&module).is_ok());
+    }
+
+    #[test]
+    fn should_cap_unbounded_memory() {
+        let preprocessor = Preprocessor::new(limited_wasm_costs(), WasmFeatures::default());
+        let mut module = builder::module().memory().with_min(1).build().build();
+        preprocessor.cap_memory(&mut module);
+        let limits = module.memory_section().unwrap().entries()[0].limits();
+        assert_eq!(limits.initial(), 1);
+        assert_eq!(limits.maximum(), Some(16));
+    }
+
+    #[test]
+    fn should_reject_excessive_module_size() {
+        let preprocessor = Preprocessor::new(limited_wasm_costs(), WasmFeatures::default());
+        let module_bytes = vec![0u8; 1025];
+        assert!(matches!(
+            preprocessor.preprocess(&module_bytes),
+            Err(PreprocessingError::ExcessiveModuleSize {
+                size: 1025,
+                max: 1024
+            })
+        ));
+    }
+
+    #[test]
+    fn should_reject_excessive_function_count() {
+        let preprocessor = Preprocessor::new(limited_wasm_costs(), WasmFeatures::default());
+        let mut module_builder = builder::module();
+        for _ in 0..3 {
+            module_builder = module_builder
+                .function()
+                .signature()
+                .build()
+                .body()
+                .build()
+                .build();
+        }
+        let module = module_builder.build();
+        assert!(matches!(
+            preprocessor.check_limits(&module),
+            Err(PreprocessingError::ExcessiveFunctionCount { count: 3, max: 2 })
+        ));
+    }
+
+    fn module_importing(module: &str, field: &str) -> Module {
+        builder::module()
+            .import()
+            .module(module)
+            .field(field)
+            .external()
+            .func(0)
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .build()
+            .build()
+            .build()
+    }
+
+    fn is_host_function(name: &str) -> bool {
+        name == "read_value"
+    }
+
+    #[test]
+    fn should_reject_unknown_imports() {
+        let preprocessor = Preprocessor::new(limited_wasm_costs(), WasmFeatures::default())
+            .with_import_whitelist(is_host_function);
+
+        assert!(preprocessor
+            .check_imports(&module_importing(HOST_MODULE, "read_value"))
+            .is_ok());
+        assert!(matches!(
+            preprocessor.check_imports(&module_importing(HOST_MODULE, "launch_missiles")),
+            Err(PreprocessingError::UnknownImport { .. })
+        ));
+        assert!(matches!(
+            preprocessor.check_imports(&module_importing("other", "read_value")),
+            Err(PreprocessingError::UnknownImport { .. })
+        ));
+    }
+
+    #[test]
+    fn should_accept_any_import_without_whitelist() {
+        let preprocessor = Preprocessor::new(limited_wasm_costs(), WasmFeatures::default());
+        assert!(preprocessor
+            .check_imports(&module_importing("other", "launch_missiles"))
+            .is_ok());
+    }
+}
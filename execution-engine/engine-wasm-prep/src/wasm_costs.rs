@@ -1,10 +1,10 @@
-use std::collections::BTreeMap;
+use std::{cmp, collections::BTreeMap};
 
 use pwasm_utils::rules::{InstructionType, Metering, Set};
 
 use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
 
-const NUM_FIELDS: usize = 10;
+const NUM_FIELDS: usize = 22;
 pub const WASM_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
 
 // Taken (partially) from parity-ethereum
@@ -33,6 +33,33 @@ pub struct WasmCosts {
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
     pub opcodes_div: u32,
+    /// Max number of memory pages (64kb) a contract may declare, either initially or as its
+    /// maximum
+    pub max_memory_pages: u32,
+    /// Max number of elements a contract's table may declare, either initially or as its maximum
+    pub max_table_size: u32,
+    /// Cost of hashing on the host, per byte
+    pub hash_per_byte: u32,
+    /// Cost of verifying an Ed25519 signature on the host, excluding hashing the message
+    pub verify_ed25519: u32,
+    /// Cost of verifying a secp256k1 signature on the host, excluding hashing the message
+    pub verify_secp256k1: u32,
+    /// Cost of an arithmetic operation on a `U256` or `U512` on the host
+    pub bignum_op: u32,
+    /// Size in bytes of the buckets in which the serialized size of a value read from or written
+    /// to global state is measured
+    pub storage_bucket_size: u32,
+    /// Cost of reading a value from global state, per started bucket of its serialized size
+    pub storage_read_per_bucket: u32,
+    /// Cost of writing a value to global state, per started bucket of its serialized size
+    pub storage_write_per_bucket: u32,
+    /// Max size in bytes of a contract's Wasm module
+    pub max_module_size: u32,
+    /// Max number of functions a contract's Wasm module may define
+    pub max_function_count: u32,
+    /// Max number of named keys a contract may be stored with, or an account or contract may hold
+    /// after adding one
+    pub max_named_keys_per_write: u32,
 }
 
 impl WasmCosts {
@@ -49,6 +76,13 @@ impl WasmCosts {
             .with_grow_cost(self.grow_mem)
             .with_forbidden_floats()
     }
+
+    /// Returns the number of `storage_bucket_size` byte buckets started by a value with a
+    /// serialized size of `size` bytes.  Every value occupies at least one bucket.
+    pub fn storage_buckets(&self, size: usize) -> u64 {
+        let bucket_size = u64::from(cmp::max(self.storage_bucket_size, 1));
+        cmp::max((size as u64 + bucket_size - 1) / bucket_size, 1)
+    }
 }
 
 impl ToBytes for WasmCosts {
@@ -64,6 +98,18 @@ impl ToBytes for WasmCosts {
         ret.append(&mut self.max_stack_height.to_bytes()?);
         ret.append(&mut self.opcodes_mul.to_bytes()?);
         ret.append(&mut self.opcodes_div.to_bytes()?);
+        ret.append(&mut self.max_memory_pages.to_bytes()?);
+        ret.append(&mut self.max_table_size.to_bytes()?);
+        ret.append(&mut self.hash_per_byte.to_bytes()?);
+        ret.append(&mut self.verify_ed25519.to_bytes()?);
+        ret.append(&mut self.verify_secp256k1.to_bytes()?);
+        ret.append(&mut self.bignum_op.to_bytes()?);
+        ret.append(&mut self.storage_bucket_size.to_bytes()?);
+        ret.append(&mut self.storage_read_per_bucket.to_bytes()?);
+        ret.append(&mut self.storage_write_per_bucket.to_bytes()?);
+        ret.append(&mut self.max_module_size.to_bytes()?);
+        ret.append(&mut self.max_function_count.to_bytes()?);
+        ret.append(&mut self.max_named_keys_per_write.to_bytes()?);
         Ok(ret)
     }
 
@@ -84,6 +130,18 @@ impl FromBytes for WasmCosts {
         let (max_stack_height, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_memory_pages, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_table_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (hash_per_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (verify_ed25519, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (verify_secp256k1, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (bignum_op, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (storage_bucket_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (storage_read_per_bucket, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (storage_write_per_bucket, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_module_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_function_count, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_named_keys_per_write, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let wasm_costs = WasmCosts {
             regular,
             div,
@@ -95,6 +153,18 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            max_memory_pages,
+            max_table_size,
+            hash_per_byte,
+            verify_ed25519,
+            verify_secp256k1,
+            bignum_op,
+            storage_bucket_size,
+            storage_read_per_bucket,
+            storage_write_per_bucket,
+            max_module_size,
+            max_function_count,
+            max_named_keys_per_write,
         };
         Ok((wasm_costs, rem))
     }
@@ -117,6 +187,18 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            max_memory_pages in num::u32::ANY,
+            max_table_size in num::u32::ANY,
+            hash_per_byte in num::u32::ANY,
+            verify_ed25519 in num::u32::ANY,
+            verify_secp256k1 in num::u32::ANY,
+            bignum_op in num::u32::ANY,
+            storage_bucket_size in num::u32::ANY,
+            storage_read_per_bucket in num::u32::ANY,
+            storage_write_per_bucket in num::u32::ANY,
+            max_module_size in num::u32::ANY,
+            max_function_count in num::u32::ANY,
+            max_named_keys_per_write in num::u32::ANY,
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -129,6 +211,18 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                max_memory_pages,
+                max_table_size,
+                hash_per_byte,
+                verify_ed25519,
+                verify_secp256k1,
+                bignum_op,
+                storage_bucket_size,
+                storage_read_per_bucket,
+                storage_write_per_bucket,
+                max_module_size,
+                max_function_count,
+                max_named_keys_per_write,
             }
         }
     }
@@ -155,6 +249,18 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            max_memory_pages: 64,
+            max_table_size: 4096,
+            hash_per_byte: 2,
+            verify_ed25519: 25_000,
+            verify_secp256k1: 50_000,
+            bignum_op: 10,
+            storage_bucket_size: 128,
+            storage_read_per_bucket: 50,
+            storage_write_per_bucket: 500,
+            max_module_size: 4 * 1024 * 1024,
+            max_function_count: 10_000,
+            max_named_keys_per_write: 10_000,
         }
     }
 
@@ -170,9 +276,31 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            max_memory_pages: 64,
+            max_table_size: 4096,
+            hash_per_byte: 0,
+            verify_ed25519: 0,
+            verify_secp256k1: 0,
+            bignum_op: 0,
+            storage_bucket_size: 128,
+            storage_read_per_bucket: 0,
+            storage_write_per_bucket: 0,
+            max_module_size: 4 * 1024 * 1024,
+            max_function_count: 10_000,
+            max_named_keys_per_write: 10_000,
         }
     }
 
+    #[test]
+    fn should_count_started_storage_buckets() {
+        let costs = wasm_costs_mock();
+        assert_eq!(costs.storage_buckets(0), 1);
+        assert_eq!(costs.storage_buckets(1), 1);
+        assert_eq!(costs.storage_buckets(128), 1);
+        assert_eq!(costs.storage_buckets(129), 2);
+        assert_eq!(costs.storage_buckets(1024), 8);
+    }
+
     #[test]
     fn should_serialize_and_deserialize() {
         let mock = wasm_costs_mock();
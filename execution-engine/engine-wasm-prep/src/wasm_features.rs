@@ -0,0 +1,124 @@
+use std::fmt::{self, Display, Formatter};
+
+use types::bytesrepr::{self, FromBytes, ToBytes, BOOL_SERIALIZED_LENGTH};
+
+const NUM_FIELDS: usize = 4;
+pub const WASM_FEATURES_SERIALIZED_LENGTH: usize = NUM_FIELDS * BOOL_SERIALIZED_LENGTH;
+
+/// A post-MVP WebAssembly proposal which can be enabled by a protocol upgrade.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WasmFeature {
+    SignExt,
+    SatFloatToInt,
+    BulkMemory,
+    Simd,
+}
+
+impl Display for WasmFeature {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WasmFeature::SignExt => write!(f, "sign-extension operators"),
+            WasmFeature::SatFloatToInt => write!(f, "non-trapping float-to-int conversions"),
+            WasmFeature::BulkMemory => write!(f, "bulk memory operations"),
+            WasmFeature::Simd => write!(f, "SIMD"),
+        }
+    }
+}
+
+/// The set of post-MVP WebAssembly features which contracts may use.
+///
+/// The default only allows the MVP instruction set.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct WasmFeatures {
+    /// Sign-extension operators (`i32.extend8_s` etc.)
+    pub sign_ext: bool,
+    /// Non-trapping float-to-int conversions (`i32.trunc_sat_f32_s` etc.).  These are not yet
+    /// understood by the Wasm parser, so modules using them are rejected even when enabled.
+    pub sat_float_to_int: bool,
+    /// Bulk memory operations (`memory.copy`, `memory.fill` etc.)
+    pub bulk_memory: bool,
+    /// 128-bit SIMD instructions
+    pub simd: bool,
+}
+
+impl WasmFeatures {
+    pub fn is_enabled(&self, feature: WasmFeature) -> bool {
+        match feature {
+            WasmFeature::SignExt => self.sign_ext,
+            WasmFeature::SatFloatToInt => self.sat_float_to_int,
+            WasmFeature::BulkMemory => self.bulk_memory,
+            WasmFeature::Simd => self.simd,
+        }
+    }
+}
+
+impl ToBytes for WasmFeatures {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.sign_ext.to_bytes()?);
+        ret.append(&mut self.sat_float_to_int.to_bytes()?);
+        ret.append(&mut self.bulk_memory.to_bytes()?);
+        ret.append(&mut self.simd.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        WASM_FEATURES_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for WasmFeatures {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (sign_ext, rem): (bool, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (sat_float_to_int, rem): (bool, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (bulk_memory, rem): (bool, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (simd, rem): (bool, &[u8]) = FromBytes::from_bytes(rem)?;
+        let wasm_features = WasmFeatures {
+            sign_ext,
+            sat_float_to_int,
+            bulk_memory,
+            simd,
+        };
+        Ok((wasm_features, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{bool, prop_compose};
+
+    use crate::wasm_features::WasmFeatures;
+
+    prop_compose! {
+        pub fn wasm_features_arb()(
+            sign_ext in bool::ANY,
+            sat_float_to_int in bool::ANY,
+            bulk_memory in bool::ANY,
+            simd in bool::ANY,
+        ) -> WasmFeatures {
+            WasmFeatures {
+                sign_ext,
+                sat_float_to_int,
+                bulk_memory,
+                simd,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr;
+
+    use super::gens;
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            wasm_features in gens::wasm_features_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&wasm_features);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use engine_shared::stored_value::StoredValue;
+
+fuzz_target!(|data: &[u8]| {
+    casperlabs_engine_fuzz::check_canonical::<StoredValue>(data);
+});
@@ -0,0 +1,28 @@
+//! Writes the seed corpus of every fuzz target to `corpus/<target>/`, where `cargo fuzz run`
+//! picks it up.
+
+use std::{env, fs, path::PathBuf};
+
+use casperlabs_engine_fuzz::{generate_corpus, TARGETS};
+
+const SEEDS_PER_TARGET: usize = 256;
+
+fn main() {
+    let corpus_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("corpus");
+    for target in TARGETS {
+        let target_dir = corpus_dir.join(target);
+        fs::create_dir_all(&target_dir).expect("should create corpus directory");
+        for (index, data) in generate_corpus(target, SEEDS_PER_TARGET)
+            .into_iter()
+            .enumerate()
+        {
+            fs::write(target_dir.join(format!("seed-{:04}", index)), data)
+                .expect("should write corpus entry");
+        }
+        println!(
+            "wrote {} seeds to {}",
+            SEEDS_PER_TARGET,
+            target_dir.display()
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Fuzzing support for `bytesrepr`.
+//!
+//! Every value must have exactly one encoding: if two validators could serialize the same value
+//! differently, or deserialize different bytes to the same value, their global state hashes would
+//! diverge.  The fuzz targets in `fuzz_targets/` feed arbitrary bytes to [`check_canonical`],
+//! which fails whenever deserialization accepts bytes that don't re-serialize to themselves.
+//!
+//! [`generate_corpus`] builds a deterministic seed corpus for each target from the proptest
+//! generators of the serialized types, so the fuzzer starts from well-formed values rather than
+//! having to discover the encodings from scratch.
+
+use std::fmt::Debug;
+
+use proptest::{
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+
+use engine_shared::{
+    account::{gens::account_arb, Account},
+    contract::{gens::contract_arb, Contract},
+    stored_value::{gens::stored_value_arb, StoredValue},
+};
+use types::{
+    bytesrepr::{FromBytes, ToBytes},
+    gens::{cl_value_arb, key_arb},
+    CLValue, Key,
+};
+
+/// The names of the fuzz targets, which are also the names of their corpus directories.
+pub const TARGETS: &[&str] = &["key", "cl_value", "stored_value", "account", "contract"];
+
+/// Deserializes a `T` from the start of `data` and, if that succeeds, checks that the value
+/// re-serializes to exactly the bytes which were consumed and round-trips.
+///
+/// # Panics
+///
+/// Panics if `data` holds a non-canonical encoding of a `T`, or if the value's serialization is
+/// inconsistent with itself.
+pub fn check_canonical<T>(data: &[u8])
+where
+    T: ToBytes + FromBytes + PartialEq + Debug,
+{
+    let (value, remainder) = match T::from_bytes(data) {
+        Ok(result) => result,
+        Err(_) => return,
+    };
+    let consumed = &data[..data.len() - remainder.len()];
+
+    let serialized = value
+        .to_bytes()
+        .expect("should serialize a deserialized value");
+    assert_eq!(
+        serialized, consumed,
+        "deserialization accepted a non-canonical encoding of {:?}",
+        value
+    );
+    assert_eq!(value.serialized_length(), serialized.len());
+
+    let (deserialized, remainder) =
+        T::from_bytes(&serialized).expect("should deserialize a serialized value");
+    assert!(remainder.is_empty());
+    assert_eq!(deserialized, value);
+}
+
+/// Runs the check of the fuzz target called `target` on `data`.
+///
+/// # Panics
+///
+/// Panics if `target` is not one of [`TARGETS`], or if the check fails.
+pub fn check_target(target: &str, data: &[u8]) {
+    match target {
+        "key" => check_canonical::<Key>(data),
+        "cl_value" => check_canonical::<CLValue>(data),
+        "stored_value" => check_canonical::<StoredValue>(data),
+        "account" => check_canonical::<Account>(data),
+        "contract" => check_canonical::<Contract>(data),
+        _ => panic!("unknown fuzz target {}", target),
+    }
+}
+
+/// Returns `count` serialized values for the fuzz target called `target`.  The same values are
+/// returned on every call.
+///
+/// # Panics
+///
+/// Panics if `target` is not one of [`TARGETS`].
+pub fn generate_corpus(target: &str, count: usize) -> Vec<Vec<u8>> {
+    match target {
+        "key" => sample(key_arb(), count),
+        "cl_value" => sample(cl_value_arb(), count),
+        "stored_value" => sample(stored_value_arb(), count),
+        "account" => sample(account_arb(), count),
+        "contract" => sample(contract_arb(), count),
+        _ => panic!("unknown fuzz target {}", target),
+    }
+}
+
+fn sample<S>(strategy: S, count: usize) -> Vec<Vec<u8>>
+where
+    S: Strategy,
+    S::Value: ToBytes,
+{
+    let mut runner = TestRunner::deterministic();
+    (0..count)
+        .map(|_| {
+            strategy
+                .new_tree(&mut runner)
+                .expect("should generate a value")
+                .current()
+                .to_bytes()
+                .expect("should serialize a generated value")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNT: usize = 32;
+
+    #[test]
+    fn generated_corpus_should_be_canonical() {
+        for target in TARGETS {
+            for data in generate_corpus(target, COUNT) {
+                check_target(target, &data);
+            }
+        }
+    }
+
+    #[test]
+    fn generated_corpus_should_be_deterministic() {
+        for target in TARGETS {
+            assert_eq!(
+                generate_corpus(target, COUNT),
+                generate_corpus(target, COUNT)
+            );
+        }
+    }
+
+    #[test]
+    fn truncated_corpus_should_not_be_accepted_non_canonically() {
+        for target in TARGETS {
+            for data in generate_corpus(target, COUNT) {
+                for length in 0..data.len() {
+                    check_target(target, &data[..length]);
+                }
+            }
+        }
+    }
+}
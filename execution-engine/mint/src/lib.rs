@@ -1,20 +1,30 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 mod runtime_provider;
 mod storage_provider;
 
+use alloc::vec::Vec;
 use core::convert::TryFrom;
 
-use types::{account::PublicKey, system_contract_errors::mint::Error, Key, URef, U512};
+use types::{
+    account::PublicKey, system_contract_errors::mint::Error, Key, URef, VestingSchedule, U512,
+};
 
 pub use crate::{runtime_provider::RuntimeProvider, storage_provider::StorageProvider};
 
-const SYSTEM_ACCOUNT: PublicKey = PublicKey::ed25519_from([0; 32]);
+/// The key in the mint's local state under which the total amount of motes minted so far is kept.
+pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
+
+/// Paired with a purse's address, the key in the mint's local state under which the purse's
+/// vesting schedule is kept.
+const VESTING_SCHEDULE_KEY: &str = "vesting_schedule";
 
 pub trait Mint: RuntimeProvider + StorageProvider {
     fn mint(&mut self, initial_balance: U512) -> Result<URef, Error> {
         let caller = self.get_caller();
-        if !initial_balance.is_zero() && caller != SYSTEM_ACCOUNT {
+        if !initial_balance.is_zero() && caller != self.get_system_account() {
             return Err(Error::InvalidNonEmptyPurseCreation);
         }
 
@@ -28,9 +38,52 @@ pub trait Mint: RuntimeProvider + StorageProvider {
         // store association between purse id and balance uref
         self.write_local(purse_key.addr(), balance_uref);
 
+        if !initial_balance.is_zero() {
+            let total_supply = self.total_supply()?;
+            self.write_local(TOTAL_SUPPLY_KEY, total_supply + initial_balance);
+        }
+
         Ok(purse_key)
     }
 
+    /// Returns the total amount of motes minted and not yet burned.
+    fn total_supply(&mut self) -> Result<U512, Error> {
+        Ok(self.read_local(&TOTAL_SUPPLY_KEY)?.unwrap_or_default())
+    }
+
+    /// Removes `amount` of motes from `purse`, reducing the total supply accordingly.
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        if !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        let balance_uref: URef = match self.read_local(&purse.addr())? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::PurseNotFound),
+        };
+        let balance: U512 = match self.read(balance_uref)? {
+            Some(balance) => balance,
+            None => return Err(Error::PurseNotFound),
+        };
+        if amount > balance {
+            return Err(Error::InsufficientFunds);
+        }
+        self.check_vesting(purse, balance - amount)?;
+        self.check_min_main_purse_balance(purse, balance - amount)?;
+        self.write(balance_uref, balance - amount)?;
+
+        let total_supply = self.total_supply()?;
+        self.write_local(TOTAL_SUPPLY_KEY, total_supply.saturating_sub(amount));
+        Ok(())
+    }
+
+    /// Returns the balance of `purse`, which must be held with at least READ rights.
+    fn balance_of(&mut self, purse: URef) -> Result<Option<U512>, Error> {
+        if !purse.is_readable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        self.balance(purse)
+    }
+
     fn balance(&mut self, purse: URef) -> Result<Option<U512>, Error> {
         let balance_uref: URef = match self.read_local(&purse.addr())? {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
@@ -61,8 +114,116 @@ pub trait Mint: RuntimeProvider + StorageProvider {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
             None => return Err(Error::DestNotFound),
         };
+        self.check_vesting(source, source_value - amount)?;
+        self.check_min_main_purse_balance(source, source_value - amount)?;
         self.write(source_bal, source_value - amount)?;
         self.add(dest_bal, amount)?;
         Ok(())
     }
+
+    /// Applies each `(source, dest, amount)` transfer in order, each one seeing the balances left
+    /// by those before it.  Either every transfer succeeds or no balance is changed.
+    fn batch_transfer(&mut self, transfers: Vec<(URef, URef, U512)>) -> Result<(), Error> {
+        // Pending balances keyed by balance URef, written out only once the whole batch is valid.
+        let mut balances: Vec<(URef, U512)> = Vec::new();
+        for (source, dest, amount) in transfers {
+            if !source.is_writeable() || !dest.is_addable() {
+                return Err(Error::InvalidAccessRights);
+            }
+            let source_bal: URef = match self.read_local(&source.addr())? {
+                Some(key) => {
+                    TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?
+                }
+                None => return Err(Error::SourceNotFound),
+            };
+            let dest_bal: URef = match self.read_local(&dest.addr())? {
+                Some(key) => {
+                    TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?
+                }
+                None => return Err(Error::DestNotFound),
+            };
+
+            let source_index = self.pending_balance_index(&mut balances, source_bal)?;
+            let source_value = balances[source_index].1;
+            if amount > source_value {
+                return Err(Error::InsufficientFunds);
+            }
+            self.check_vesting(source, source_value - amount)?;
+            self.check_min_main_purse_balance(source, source_value - amount)?;
+            balances[source_index].1 = source_value - amount;
+
+            let dest_index = self.pending_balance_index(&mut balances, dest_bal)?;
+            balances[dest_index].1 += amount;
+        }
+
+        for (balance_uref, balance) in balances {
+            self.write(balance_uref, balance)?;
+        }
+        Ok(())
+    }
+
+    /// Locks the motes in `purse` according to `schedule`.  Only the system account may do this,
+    /// which it does for the genesis accounts given a schedule.
+    fn set_vesting_schedule(
+        &mut self,
+        purse: URef,
+        schedule: VestingSchedule,
+    ) -> Result<(), Error> {
+        if self.get_caller() != self.get_system_account() {
+            return Err(Error::InvalidVestingScheduleCreation);
+        }
+        let balance_key: Option<Key> = self.read_local(&purse.addr())?;
+        if balance_key.is_none() {
+            return Err(Error::PurseNotFound);
+        }
+        self.write_local((VESTING_SCHEDULE_KEY, purse.addr()), schedule);
+        Ok(())
+    }
+
+    /// Returns an error if leaving `purse` with a balance of `remaining` would dip below the amount
+    /// its vesting schedule still locks at the current block time.
+    fn check_vesting(&mut self, purse: URef, remaining: U512) -> Result<(), Error> {
+        let schedule: Option<VestingSchedule> =
+            self.read_local(&(VESTING_SCHEDULE_KEY, purse.addr()))?;
+        match schedule {
+            Some(schedule) if remaining < schedule.locked_amount_at(self.get_block_time()) => {
+                Err(Error::FundsLocked)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns an error if leaving `purse` with a balance of `remaining` would take the main purse
+    /// of the deploy's account below the minimum balance it is held to.
+    fn check_min_main_purse_balance(&mut self, purse: URef, remaining: U512) -> Result<(), Error> {
+        match self.get_min_main_purse_balance() {
+            Some((main_purse, min_balance))
+                if main_purse.addr() == purse.addr() && remaining < min_balance =>
+            {
+                Err(Error::BelowMinimumBalance)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the index of `balance_uref` in `balances`, reading its current value from storage
+    /// first if it isn't there yet.
+    fn pending_balance_index(
+        &mut self,
+        balances: &mut Vec<(URef, U512)>,
+        balance_uref: URef,
+    ) -> Result<usize, Error> {
+        if let Some(index) = balances
+            .iter()
+            .position(|(pending, _)| pending.addr() == balance_uref.addr())
+        {
+            return Ok(index);
+        }
+        let balance: U512 = match self.read(balance_uref)? {
+            Some(balance) => balance,
+            None => return Err(Error::PurseNotFound),
+        };
+        balances.push((balance_uref, balance));
+        Ok(balances.len() - 1)
+    }
 }
@@ -1,7 +1,16 @@
-use types::{account::PublicKey, Key};
+use types::{account::PublicKey, BlockTime, Key, URef, U512};
 
 pub trait RuntimeProvider {
     fn get_caller(&self) -> PublicKey;
 
+    /// Returns the public key of the system account configured for the chain.
+    fn get_system_account(&self) -> PublicKey;
+
     fn put_key(&mut self, name: &str, key: Key);
+
+    fn get_block_time(&self) -> BlockTime;
+
+    /// Returns the main purse of the account the deploy runs as, together with the fewest motes
+    /// which may be left in it, or `None` if the purse isn't held to a minimum at this point.
+    fn get_min_main_purse_balance(&self) -> Option<(URef, U512)>;
 }
@@ -0,0 +1,200 @@
+use alloc::{
+    collections::{btree_map::Iter, BTreeMap},
+    format,
+    string::String,
+};
+
+use types::{
+    account::PublicKey,
+    system_contract_errors::pos::{Error, Result},
+    U512,
+};
+
+/// The delegations map, assigning the amount of motes delegated by each delegator to each
+/// validator, keyed by `(delegator, validator)`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Delegations(pub BTreeMap<(PublicKey, PublicKey), U512>);
+
+impl Delegations {
+    pub fn new(map: BTreeMap<(PublicKey, PublicKey), U512>) -> Delegations {
+        Delegations(map)
+    }
+
+    pub fn iter(&self) -> Iter<(PublicKey, PublicKey), U512> {
+        self.0.iter()
+    }
+
+    /// Parses the delegations from the names of the contract's known urefs, formatted as
+    /// "d_<tagged-hex-formatted-delegator>_<tagged-hex-formatted-validator>_<amount>". Names
+    /// without the "d_" prefix are ignored.
+    pub fn from_names<'a>(names: impl Iterator<Item = &'a str>) -> Result<Delegations> {
+        let mut delegations = BTreeMap::new();
+        for name in names {
+            let mut split_name = name.split('_');
+            if Some("d") != split_name.next() {
+                continue;
+            }
+            let delegator = parse_public_key(split_name.next())?;
+            let validator = parse_public_key(split_name.next())?;
+            let amount = split_name
+                .next()
+                .and_then(|a| U512::from_dec_str(a).ok())
+                .ok_or(Error::DelegationsDeserializationFailed)?;
+            delegations.insert((delegator, validator), amount);
+        }
+        Ok(Delegations(delegations))
+    }
+
+    pub fn strings(&self) -> impl Iterator<Item = String> + '_ {
+        self.iter().map(|((delegator, validator), amount)| {
+            format!(
+                "d_{}_{}_{}",
+                delegator.to_tagged_hex(),
+                validator.to_tagged_hex(),
+                amount
+            )
+        })
+    }
+
+    /// Returns the total amount delegated to `validator`.
+    pub fn delegated_to(&self, validator: &PublicKey) -> U512 {
+        self.iter()
+            .filter(|((_, v), _)| v == validator)
+            .fold(U512::zero(), |total, (_, amount)| total + amount)
+    }
+
+    /// Adds `amount` to the motes `delegator` has delegated to `validator`.
+    pub fn delegate(&mut self, delegator: &PublicKey, validator: &PublicKey, amount: U512) {
+        self.0
+            .entry((*delegator, *validator))
+            .and_modify(|x| *x += amount)
+            .or_insert(amount);
+    }
+
+    /// If `maybe_amount` is `None`, removes the whole delegation of `delegator` to `validator`,
+    /// otherwise subtracts the given amount from it.
+    ///
+    /// Returns the amount that was actually subtracted, or an error if there is no such
+    /// delegation or it is smaller than `maybe_amount`.
+    pub fn undelegate(
+        &mut self,
+        delegator: &PublicKey,
+        validator: &PublicKey,
+        maybe_amount: Option<U512>,
+    ) -> Result<U512> {
+        let key = (*delegator, *validator);
+        let delegated = *self.0.get(&key).ok_or(Error::NotDelegated)?;
+        let amount = maybe_amount.unwrap_or(delegated);
+        if amount > delegated {
+            return Err(Error::UnbondTooLarge);
+        }
+        if amount == delegated {
+            self.0.remove(&key);
+        } else {
+            self.0.insert(key, delegated - amount);
+        }
+        Ok(amount)
+    }
+}
+
+fn parse_public_key(maybe_tagged_hex: Option<&str>) -> Result<PublicKey> {
+    maybe_tagged_hex
+        .and_then(PublicKey::from_tagged_hex)
+        .ok_or(Error::DelegationsKeyDeserializationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec::Vec};
+    use core::iter;
+
+    use types::{account::PublicKey, system_contract_errors::pos::Error, U512};
+
+    use super::Delegations;
+
+    const DELEGATOR: [u8; 32] = [1; 32];
+    const VALIDATOR1: [u8; 32] = [2; 32];
+    const VALIDATOR2: [u8; 32] = [3; 32];
+
+    fn new_delegations(delegations: &[([u8; 32], [u8; 32], u64)]) -> Delegations {
+        Delegations(
+            delegations
+                .iter()
+                .map(|&(delegator, validator, amount)| {
+                    (
+                        (
+                            PublicKey::ed25519_from(delegator),
+                            PublicKey::ed25519_from(validator),
+                        ),
+                        U512::from(amount),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_delegate_and_undelegate() {
+        let delegator = PublicKey::ed25519_from(DELEGATOR);
+        let validator1 = PublicKey::ed25519_from(VALIDATOR1);
+        let validator2 = PublicKey::ed25519_from(VALIDATOR2);
+
+        let mut delegations = Delegations::default();
+        delegations.delegate(&delegator, &validator1, U512::from(10));
+        delegations.delegate(&delegator, &validator1, U512::from(5));
+        delegations.delegate(&delegator, &validator2, U512::from(7));
+        assert_eq!(
+            new_delegations(&[(DELEGATOR, VALIDATOR1, 15), (DELEGATOR, VALIDATOR2, 7)]),
+            delegations
+        );
+        assert_eq!(U512::from(15), delegations.delegated_to(&validator1));
+
+        assert_eq!(
+            Err(Error::UnbondTooLarge),
+            delegations.undelegate(&delegator, &validator2, Some(U512::from(8)))
+        );
+        assert_eq!(
+            Ok(U512::from(3)),
+            delegations.undelegate(&delegator, &validator2, Some(U512::from(3)))
+        );
+        assert_eq!(
+            Ok(U512::from(15)),
+            delegations.undelegate(&delegator, &validator1, None)
+        );
+        assert_eq!(
+            Err(Error::NotDelegated),
+            delegations.undelegate(&delegator, &validator1, None)
+        );
+        assert_eq!(new_delegations(&[(DELEGATOR, VALIDATOR2, 4)]), delegations);
+    }
+
+    #[test]
+    fn test_names_roundtrip() {
+        let mut delegations =
+            new_delegations(&[(DELEGATOR, VALIDATOR1, 15), (VALIDATOR2, VALIDATOR1, 1)]);
+        let secp256k1_key = PublicKey::secp256k1_from([2; 33]);
+        delegations.delegate(&secp256k1_key, &secp256k1_key, U512::from(7));
+        let mut names: Vec<_> = delegations.strings().collect();
+        names.push(format!("v_00{}_5", "01".repeat(32)));
+        assert_eq!(
+            Ok(delegations),
+            Delegations::from_names(names.iter().map(String::as_str))
+        );
+
+        let bad_amount = format!("d_00{}_00{}_a", "01".repeat(32), "02".repeat(32));
+        assert_eq!(
+            Err(Error::DelegationsDeserializationFailed),
+            Delegations::from_names(iter::once(bad_amount.as_str()))
+        );
+        let short_key = format!("d_01_00{}_1", "02".repeat(32));
+        assert_eq!(
+            Err(Error::DelegationsKeyDeserializationFailed),
+            Delegations::from_names(iter::once(short_key.as_str()))
+        );
+        let untagged_key = format!("d_{}_00{}_1", "01".repeat(32), "02".repeat(32));
+        assert_eq!(
+            Err(Error::DelegationsKeyDeserializationFailed),
+            Delegations::from_names(iter::once(untagged_key.as_str()))
+        );
+    }
+}
@@ -2,6 +2,8 @@
 
 extern crate alloc;
 
+mod delegations;
+mod delegations_provider;
 mod mint_provider;
 mod queue;
 mod queue_provider;
@@ -9,21 +11,32 @@ mod runtime_provider;
 mod stakes;
 mod stakes_provider;
 
+use alloc::vec::Vec;
 use core::marker::Sized;
 
 use types::{
     account::PublicKey,
     system_contract_errors::pos::{Error, Result},
-    AccessRights, TransferredTo, URef, U512,
+    AccessRights, URef, U512,
 };
 
 pub use crate::{
+    delegations::Delegations, delegations_provider::DelegationsProvider,
     mint_provider::MintProvider, queue::Queue, queue_provider::QueueProvider,
     runtime_provider::RuntimeProvider, stakes::Stakes, stakes_provider::StakesProvider,
 };
 
+/// The key in the PoS contract's local state under which the bonding queue is kept.
+pub const BONDING_KEY: u8 = 1;
+
+/// The key in the PoS contract's local state under which the unbonding queue is kept.
+pub const UNBONDING_KEY: u8 = 2;
+
+/// The name of the PoS contract's named key holding the unbonding delay.
+pub const UNBONDING_DELAY_KEY: &str = "pos_unbonding_delay";
+
 pub trait ProofOfStake:
-    MintProvider + QueueProvider + RuntimeProvider + StakesProvider + Sized
+    DelegationsProvider + MintProvider + QueueProvider + RuntimeProvider + StakesProvider + Sized
 {
     fn bond(&mut self, validator: PublicKey, amount: U512, source: URef) -> Result<()> {
         if amount.is_zero() {
@@ -37,30 +50,62 @@ pub trait ProofOfStake:
             .map_err(|_| Error::BondTransferFailed)?;
         internal::bond(self, amount, validator, timestamp)?;
 
-        // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
-        let unbonds = internal::step(self, timestamp)?;
-        for entry in unbonds {
-            let _: TransferredTo = self
-                .transfer_purse_to_account(source, entry.validator, entry.amount)
-                .map_err(|_| Error::BondTransferFailed)?;
-        }
-        Ok(())
+        // TODO: Remove this and set a nonzero bond delay once the system calls `step` in each
+        // block.
+        internal::step_and_pay_out(self, timestamp)
     }
 
+    /// Enqueues the validator's stake for withdrawal. Its funds stay in the bonding purse until
+    /// the unbonding delay has passed and the request is paid out by `withdraw`.
     fn unbond(&mut self, validator: PublicKey, maybe_amount: Option<U512>) -> Result<()> {
-        let pos_purse = internal::get_bonding_purse(self)?;
         let timestamp = self.get_block_time();
         internal::unbond(self, maybe_amount, validator, timestamp)?;
+        internal::step_and_pay_out(self, timestamp)
+    }
 
-        // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
-        let unbonds = internal::step(self, timestamp)?;
-        for entry in unbonds {
-            self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
-                .map_err(|_| Error::UnbondTransferFailed)?;
+    /// Pays out all unbonding requests whose delay has passed.
+    fn withdraw(&mut self) -> Result<()> {
+        let timestamp = self.get_block_time();
+        internal::step_and_pay_out(self, timestamp)
+    }
+
+    /// Delegates `amount` from the purse `source` to the bonded `validator`, adding it to the
+    /// validator's vote weight immediately.
+    fn delegate(
+        &mut self,
+        delegator: PublicKey,
+        validator: PublicKey,
+        amount: U512,
+        source: URef,
+    ) -> Result<()> {
+        if amount.is_zero() {
+            return Err(Error::BondTooSmall);
+        }
+        if !self.read()?.0.contains_key(&validator) {
+            return Err(Error::NotBonded);
         }
+        let target = internal::get_bonding_purse(self)?;
+        self.transfer_purse_to_purse(source, target, amount)
+            .map_err(|_| Error::DelegateTransferFailed)?;
+        let mut delegations = self.read_delegations()?;
+        delegations.delegate(&delegator, &validator, amount);
+        self.write_delegations(&delegations);
         Ok(())
     }
 
+    /// Withdraws the delegation of `delegator` to `validator`, or `maybe_amount` of it. Like
+    /// unbonded stakes, the funds stay in the bonding purse until the unbonding delay has passed.
+    fn undelegate(
+        &mut self,
+        delegator: PublicKey,
+        validator: PublicKey,
+        maybe_amount: Option<U512>,
+    ) -> Result<()> {
+        let timestamp = self.get_block_time();
+        internal::undelegate(self, maybe_amount, delegator, validator, timestamp)?;
+        internal::step_and_pay_out(self, timestamp)
+    }
+
     fn get_payment_purse(&self) -> Result<URef> {
         let purse = internal::get_payment_purse(self)?;
         // Limit the access rights so only balance query and deposit are allowed.
@@ -79,8 +124,39 @@ pub trait ProofOfStake:
         Ok(maybe_purse.map(|p| p.remove_access_rights()))
     }
 
-    fn finalize_payment(&mut self, amount_spent: U512, account: PublicKey) -> Result<()> {
-        internal::finalize_payment(self, amount_spent, account)
+    fn finalize_payment(
+        &mut self,
+        amount_spent: U512,
+        account: PublicKey,
+        refund_ratio: u8,
+    ) -> Result<()> {
+        internal::finalize_payment(self, amount_spent, account, refund_ratio)
+    }
+
+    /// Pays out all unbonding requests whose delay has passed as of the current block time. Unlike
+    /// `withdraw`, this is available whether or not bonding is enabled, and can only be called by
+    /// the system account, once per block.
+    fn step(&mut self) -> Result<()> {
+        let timestamp = self.get_block_time();
+        internal::step_and_pay_out(self, timestamp)
+    }
+
+    /// Slashes a portion of `validator`'s stake and of its pending unbonds as a penalty for
+    /// misbehavior, redistributing the slashed motes to the rewards purse. Can only be called by
+    /// the system account, once the engine has validated the evidence against the validator.
+    ///
+    /// Returns the amount slashed.
+    fn slash(&mut self, validator: PublicKey) -> Result<U512> {
+        internal::slash(self, validator)
+    }
+
+    /// Pays out the rewards purse to the bonded validators pro rata to their stakes, skipping the
+    /// validators in `excluded` (e.g. the ones slashed during the era). Can only be called by the
+    /// system account.
+    ///
+    /// Returns the amount paid out; any remainder due to rounding stays in the rewards purse.
+    fn distribute_rewards(&mut self, excluded: Vec<PublicKey>) -> Result<U512> {
+        internal::distribute_rewards(self, &excluded)
     }
 }
 
@@ -90,17 +166,15 @@ mod internal {
     use types::{
         account::PublicKey,
         system_contract_errors::pos::{Error, PurseLookupError, Result},
-        BlockTime, Key, Phase, URef, U512,
+        BlockTime, Key, Phase, TransferredTo, URef, U512,
     };
 
     use crate::{
-        mint_provider::MintProvider, queue::QueueEntry, queue_provider::QueueProvider,
-        runtime_provider::RuntimeProvider, stakes_provider::StakesProvider,
+        delegations_provider::DelegationsProvider, mint_provider::MintProvider, queue::QueueEntry,
+        queue_provider::QueueProvider, runtime_provider::RuntimeProvider,
+        stakes_provider::StakesProvider,
     };
 
-    /// Account used to run system functions (in particular `finalize_payment`).
-    const SYSTEM_ACCOUNT: PublicKey = PublicKey::ed25519_from([0u8; 32]);
-
     /// The uref name where the PoS purse is stored. It contains all staked motes, and all unbonded
     /// motes that are yet to be paid out.
     const BONDING_PURSE_KEY: &str = "pos_bonding_purse";
@@ -115,11 +189,15 @@ mod internal {
     /// corresponds to is set by the user.
     const REFUND_PURSE_KEY: &str = "pos_refund_purse";
 
+    /// The refund ratio, in percent, at which all unspent payment is returned to the user.
+    const FULL_REFUND_RATIO: u8 = 100;
+
     /// The time from a bonding request until the bond becomes effective and part of the stake.
     const BOND_DELAY: u64 = 0;
 
-    /// The time from an unbonding request until the stakes are paid out.
-    const UNBOND_DELAY: u64 = 0;
+    /// The portion of a misbehaving validator's stake and pending unbonds which is slashed, in
+    /// millionths.
+    const SLASH_RATE: u64 = 100_000;
 
     /// The maximum number of pending bonding requests.
     const MAX_BOND_LEN: usize = 100;
@@ -177,6 +255,116 @@ mod internal {
         Ok(())
     }
 
+    /// Removes the delegation of `delegator` to `validator`, or `maybe_amount` of it, and enqueues
+    /// the funds for withdrawal to the delegator.
+    pub fn undelegate<P: DelegationsProvider + QueueProvider>(
+        provider: &mut P,
+        maybe_amount: Option<U512>,
+        delegator: PublicKey,
+        validator: PublicKey,
+        timestamp: BlockTime,
+    ) -> Result<()> {
+        let mut queue = provider.read_unbonding();
+        if queue.0.len() >= MAX_UNBOND_LEN {
+            return Err(Error::TooManyEventsInQueue);
+        }
+
+        let mut delegations = provider.read_delegations()?;
+        let payout = delegations.undelegate(&delegator, &validator, maybe_amount)?;
+        provider.write_delegations(&delegations);
+        queue.push(delegator, payout, timestamp)?;
+        provider.write_unbonding(queue);
+        Ok(())
+    }
+
+    /// Slashes `SLASH_RATE` of `validator`'s stake plus pending unbonds, taking it from the stake
+    /// first, and moves the slashed motes from the bonding purse to the rewards purse.
+    pub fn slash<P>(provider: &mut P, validator: PublicKey) -> Result<U512>
+    where
+        P: MintProvider + QueueProvider + RuntimeProvider + StakesProvider,
+    {
+        if provider.get_caller() != provider.get_system_account() {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let mut stakes = provider.read()?;
+        let mut unbonding = provider.read_unbonding();
+        let stake = stakes.0.get(&validator).cloned().unwrap_or_default();
+        let pending = unbonding
+            .0
+            .iter()
+            .filter(|entry| entry.validator == validator)
+            .fold(U512::zero(), |total, entry| total + entry.amount);
+        if stake.is_zero() && pending.is_zero() {
+            return Err(Error::NotBonded);
+        }
+        let to_slash = (stake + pending) * SLASH_RATE / 1_000_000;
+
+        let mut slashed = stakes.slash(&validator, to_slash);
+        for entry in unbonding
+            .0
+            .iter_mut()
+            .filter(|entry| entry.validator == validator)
+        {
+            let cut = entry.amount.min(to_slash - slashed);
+            entry.amount -= cut;
+            slashed += cut;
+        }
+        unbonding.0.retain(|entry| !entry.amount.is_zero());
+        provider.write(&stakes);
+        provider.write_unbonding(unbonding);
+
+        if !slashed.is_zero() {
+            let bonding_purse = get_bonding_purse(provider)?;
+            let rewards_purse = get_rewards_purse(provider)?;
+            provider
+                .transfer_purse_to_purse(bonding_purse, rewards_purse, slashed)
+                .map_err(|_| Error::FailedTransferToRewardsPurse)?;
+        }
+        Ok(slashed)
+    }
+
+    /// Splits the balance of the rewards purse between the validators not in `excluded`, pro rata
+    /// to their stakes, and transfers each share to the validator's account.
+    pub fn distribute_rewards<P>(provider: &mut P, excluded: &[PublicKey]) -> Result<U512>
+    where
+        P: MintProvider + RuntimeProvider + StakesProvider,
+    {
+        if provider.get_caller() != provider.get_system_account() {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let rewards_purse = get_rewards_purse(provider)?;
+        let rewards = provider
+            .balance(rewards_purse)
+            .ok_or(Error::RewardsPurseBalanceNotFound)?;
+        let stakes = provider.read()?;
+        let rewarded: Vec<(PublicKey, U512)> = stakes
+            .0
+            .into_iter()
+            .filter(|(validator, _)| !excluded.contains(validator))
+            .collect();
+        let total_stake = rewarded
+            .iter()
+            .fold(U512::zero(), |total, (_, stake)| total + *stake);
+        if rewards.is_zero() || total_stake.is_zero() {
+            return Ok(U512::zero());
+        }
+
+        let mut distributed = U512::zero();
+        for (validator, stake) in rewarded {
+            let reward = rewards * stake / total_stake;
+            if reward.is_zero() {
+                continue;
+            }
+            let _: TransferredTo = provider
+                .transfer_purse_to_account(rewards_purse, validator, reward)
+                .map_err(|_| Error::FailedTransferToAccountPurse)?;
+            distributed += reward;
+        }
+        Ok(distributed)
+    }
+
     /// Removes all due requests from the queues and applies them.
     pub fn step<P: QueueProvider + StakesProvider>(
         provider: &mut P,
@@ -186,8 +374,9 @@ mod internal {
         let mut unbonding_queue = provider.read_unbonding();
 
         let bonds = bonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(BOND_DELAY)));
+        let unbond_delay = provider.read_unbonding_delay();
         let unbonds =
-            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(UNBOND_DELAY)));
+            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(unbond_delay)));
 
         if !unbonds.is_empty() {
             provider.write_unbonding(unbonding_queue);
@@ -205,6 +394,21 @@ mod internal {
         Ok(unbonds)
     }
 
+    /// Applies all due requests and transfers the funds of the due unbonding requests from the
+    /// bonding purse to the validators' accounts.
+    pub fn step_and_pay_out<P>(provider: &mut P, timestamp: BlockTime) -> Result<()>
+    where
+        P: MintProvider + QueueProvider + RuntimeProvider + StakesProvider,
+    {
+        let bonding_purse = get_bonding_purse(provider)?;
+        for entry in step(provider, timestamp)? {
+            let _: TransferredTo = provider
+                .transfer_purse_to_account(bonding_purse, entry.validator, entry.amount)
+                .map_err(|_| Error::UnbondTransferFailed)?;
+        }
+        Ok(())
+    }
+
     /// Attempts to look up a purse from the named_keys
     fn get_purse<R: RuntimeProvider>(
         runtime_provider: &R,
@@ -255,18 +459,24 @@ mod internal {
     }
 
     /// Transfers funds from the payment purse to the validator rewards purse, as well as to the
-    /// refund purse, depending on how much was spent on the computation. This function maintains
-    /// the invariant that the balance of the payment purse is zero at the beginning and end of each
-    /// deploy and that the refund purse is unset at the beginning and end of each deploy.
+    /// refund purse, depending on how much was spent on the computation. Only `refund_ratio`
+    /// percent of the unspent payment is refunded; the remainder goes to the rewards purse along
+    /// with the amount spent. This function maintains the invariant that the balance of the
+    /// payment purse is zero at the beginning and end of each deploy and that the refund purse is
+    /// unset at the beginning and end of each deploy.
     pub fn finalize_payment<P: MintProvider + RuntimeProvider>(
         provider: &mut P,
         amount_spent: U512,
         account: PublicKey,
+        refund_ratio: u8,
     ) -> Result<()> {
         let caller = provider.get_caller();
-        if caller != SYSTEM_ACCOUNT {
+        if caller != provider.get_system_account() {
             return Err(Error::SystemFunctionCalledByUserAccount);
         }
+        if refund_ratio > FULL_REFUND_RATIO {
+            return Err(Error::InvalidRefundRatio);
+        }
 
         let payment_purse = get_payment_purse(provider)?;
         let total = match provider.balance(payment_purse) {
@@ -276,7 +486,9 @@ mod internal {
         if total < amount_spent {
             return Err(Error::InsufficientPaymentForAmountSpent);
         }
-        let refund_amount = total - amount_spent;
+        let refund_amount =
+            (total - amount_spent) * U512::from(refund_ratio) / U512::from(FULL_REFUND_RATIO);
+        let amount_kept = total - refund_amount;
 
         let rewards_purse = get_rewards_purse(provider)?;
         let refund_purse = get_refund_purse(provider)?;
@@ -284,7 +496,7 @@ mod internal {
 
         // pay validators
         provider
-            .transfer_purse_to_purse(payment_purse, rewards_purse, amount_spent)
+            .transfer_purse_to_purse(payment_purse, rewards_purse, amount_kept)
             .map_err(|_| Error::FailedTransferToRewardsPurse)?;
 
         if refund_amount.is_zero() {
@@ -324,14 +536,17 @@ mod internal {
     mod tests {
         extern crate std;
 
-        use std::{cell::RefCell, iter, thread_local};
+        use std::{
+            cell::{Cell, RefCell},
+            iter, thread_local,
+        };
 
         use types::{account::PublicKey, system_contract_errors::pos::Result, BlockTime, U512};
 
-        use super::{bond, step, unbond, BOND_DELAY, UNBOND_DELAY};
+        use super::{bond, step, unbond, undelegate, BOND_DELAY};
         use crate::{
-            queue::Queue, queue_provider::QueueProvider, stakes::Stakes,
-            stakes_provider::StakesProvider,
+            delegations::Delegations, delegations_provider::DelegationsProvider, queue::Queue,
+            queue_provider::QueueProvider, stakes::Stakes, stakes_provider::StakesProvider,
         };
 
         const KEY1: [u8; 32] = [1; 32];
@@ -340,6 +555,8 @@ mod internal {
         thread_local! {
             static BONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
             static UNBONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
+            static UNBOND_DELAY: Cell<u64> = Cell::new(0);
+            static DELEGATIONS: RefCell<Delegations> = RefCell::new(Delegations::default());
             static STAKES: RefCell<Stakes> = RefCell::new(
                 Stakes(iter::once((PublicKey::ed25519_from(KEY1), U512::from(1_000))).collect())
             );
@@ -363,6 +580,10 @@ mod internal {
             fn write_unbonding(&mut self, queue: Queue) {
                 UNBONDING.with(|ub| ub.replace(queue));
             }
+
+            fn read_unbonding_delay(&mut self) -> u64 {
+                UNBOND_DELAY.with(Cell::get)
+            }
         }
 
         impl StakesProvider for Provider {
@@ -375,6 +596,16 @@ mod internal {
             }
         }
 
+        impl DelegationsProvider for Provider {
+            fn read_delegations(&self) -> Result<Delegations> {
+                DELEGATIONS.with(|d| Ok(d.borrow().clone()))
+            }
+
+            fn write_delegations(&mut self, delegations: &Delegations) {
+                DELEGATIONS.with(|d| d.replace(delegations.clone()));
+            }
+        }
+
         fn assert_stakes(stakes: &[([u8; 32], usize)]) {
             let expected = Stakes(
                 stakes
@@ -413,8 +644,77 @@ mod internal {
 
             // Unbonding becomes effective immediately.
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
-            step::<Provider>(&mut provider, BlockTime::new(2 + UNBOND_DELAY)).expect("step 3");
+            step::<Provider>(&mut provider, BlockTime::new(2)).expect("step 3");
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
         }
+
+        #[test]
+        fn test_unbond_pays_out_after_delay() {
+            const DELAY: u64 = 10;
+
+            let mut provider = Provider;
+            provider.write(&Stakes(
+                iter::once((PublicKey::ed25519_from(KEY1), U512::from(1_000))).collect(),
+            ));
+            provider.write_unbonding(Queue(Default::default()));
+            UNBOND_DELAY.with(|delay| delay.set(DELAY));
+
+            unbond::<Provider>(
+                &mut provider,
+                Some(U512::from(300)),
+                PublicKey::ed25519_from(KEY1),
+                BlockTime::new(5),
+            )
+            .expect("partly unbond validator 1");
+            assert_stakes(&[(KEY1, 700)]);
+
+            // The funds are held back until the delay has passed.
+            let unbonds = step(&mut provider, BlockTime::new(4 + DELAY)).expect("step 1");
+            assert!(unbonds.is_empty());
+            assert_eq!(1, provider.read_unbonding().0.len());
+
+            let unbonds = step(&mut provider, BlockTime::new(5 + DELAY)).expect("step 2");
+            assert_eq!(1, unbonds.len());
+            assert_eq!(PublicKey::ed25519_from(KEY1), unbonds[0].validator);
+            assert_eq!(U512::from(300), unbonds[0].amount);
+            assert!(provider.read_unbonding().0.is_empty());
+            assert_stakes(&[(KEY1, 700)]);
+
+            UNBOND_DELAY.with(|delay| delay.set(0));
+        }
+
+        #[test]
+        fn test_undelegate_enqueues_payout_to_delegator() {
+            let mut provider = Provider;
+            provider.write_unbonding(Queue(Default::default()));
+            let delegator = PublicKey::ed25519_from(KEY2);
+            let validator = PublicKey::ed25519_from(KEY1);
+            let mut delegations = Delegations::default();
+            delegations.delegate(&delegator, &validator, U512::from(200));
+            provider.write_delegations(&delegations);
+
+            undelegate(
+                &mut provider,
+                Some(U512::from(50)),
+                delegator,
+                validator,
+                BlockTime::new(3),
+            )
+            .expect("partly undelegate");
+            assert_eq!(
+                U512::from(150),
+                provider
+                    .read_delegations()
+                    .expect("read delegations")
+                    .delegated_to(&validator)
+            );
+
+            let unbonds = step(&mut provider, BlockTime::new(3)).expect("step");
+            assert_eq!(1, unbonds.len());
+            assert_eq!(delegator, unbonds[0].validator);
+            assert_eq!(U512::from(50), unbonds[0].amount);
+
+            provider.write_delegations(&Delegations::default());
+        }
     }
 }
@@ -12,4 +12,7 @@ pub trait QueueProvider {
 
     /// Writes unbonding queue.
     fn write_unbonding(&mut self, queue: Queue);
+
+    /// Reads the time from an unbonding request until its funds are paid out.
+    fn read_unbonding_delay(&mut self) -> u64;
 }
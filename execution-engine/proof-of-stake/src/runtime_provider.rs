@@ -12,4 +12,7 @@ pub trait RuntimeProvider {
     fn get_block_time(&self) -> BlockTime;
 
     fn get_caller(&self) -> PublicKey;
+
+    /// Returns the public key of the system account configured for the chain.
+    fn get_system_account(&self) -> PublicKey;
 }
@@ -48,12 +48,34 @@ impl Stakes {
         self.0.values()
     }
 
+    /// Parses the stakes from the names of the contract's known urefs, formatted as
+    /// "v_<tagged-hex-formatted-validator>_<stake>". Names without the "v_" prefix are ignored.
+    pub fn from_names<'a>(names: impl Iterator<Item = &'a str>) -> Result<Stakes> {
+        let mut stakes = BTreeMap::new();
+        for name in names {
+            let mut split_name = name.split('_');
+            if Some("v") != split_name.next() {
+                continue;
+            }
+            let validator = split_name
+                .next()
+                .and_then(PublicKey::from_tagged_hex)
+                .ok_or(Error::StakesKeyDeserializationFailed)?;
+            let stake = split_name
+                .next()
+                .and_then(|s| U512::from_dec_str(s).ok())
+                .ok_or(Error::StakesDeserializationFailed)?;
+            stakes.insert(validator, stake);
+        }
+        if stakes.is_empty() {
+            return Err(Error::StakesNotFound);
+        }
+        Ok(Stakes(stakes))
+    }
+
     pub fn strings(&self) -> impl Iterator<Item = String> + '_ {
-        self.iter().map(|(public_key, balance)| {
-            let key_bytes = public_key.as_bytes();
-            let hex_key = base16::encode_lower(&key_bytes);
-            format!("v_{}_{}", hex_key, balance)
-        })
+        self.iter()
+            .map(|(public_key, balance)| format!("v_{}_{}", public_key.to_tagged_hex(), balance))
     }
 
     pub fn total_bonds(&self) -> U512 {
@@ -109,6 +131,25 @@ impl Stakes {
         Ok(stake)
     }
 
+    /// Subtracts up to `amount` from the validator's stakes, bypassing the limits which apply to
+    /// voluntary unbonding. A validator whose stakes drop to zero is removed, unless it is the last
+    /// one.
+    ///
+    /// Returns the amount that was actually subtracted, which is zero if the validator was not
+    /// bonded.
+    pub fn slash(&mut self, validator: &PublicKey, amount: U512) -> U512 {
+        let stake = match self.0.get_mut(validator) {
+            Some(stake) => stake,
+            None => return U512::zero(),
+        };
+        let slashed = amount.min(*stake);
+        *stake -= slashed;
+        if stake.is_zero() && self.0.len() > 1 {
+            self.0.remove(validator);
+        }
+        slashed
+    }
+
     /// Adds `amount` to the validator's stakes.
     pub fn bond(&mut self, validator: &PublicKey, amount: U512) {
         self.0
@@ -170,6 +211,9 @@ impl Stakes {
 
 #[cfg(test)]
 mod tests {
+    use alloc::{format, string::String, vec::Vec};
+    use core::iter;
+
     use types::{account::PublicKey, system_contract_errors::pos::Error, U512};
 
     use super::Stakes;
@@ -186,6 +230,48 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_names_roundtrip() {
+        let mut stakes = new_stakes(&[(KEY1, 100), (KEY2, 5)]);
+        stakes
+            .0
+            .insert(PublicKey::secp256k1_from([3; 33]), U512::from(7));
+        let mut names: Vec<_> = stakes.strings().collect();
+        names.push(format!("d_00{}_00{}_5", "01".repeat(32), "02".repeat(32)));
+        assert_eq!(
+            Ok(stakes),
+            Stakes::from_names(names.iter().map(String::as_str))
+        );
+
+        let bad_stake = format!("v_00{}_a", "01".repeat(32));
+        assert_eq!(
+            Err(Error::StakesDeserializationFailed),
+            Stakes::from_names(iter::once(bad_stake.as_str()))
+        );
+        let untagged_key = format!("v_{}_1", "01".repeat(32));
+        assert_eq!(
+            Err(Error::StakesKeyDeserializationFailed),
+            Stakes::from_names(iter::once(untagged_key.as_str()))
+        );
+        assert_eq!(
+            Err(Error::StakesNotFound),
+            Stakes::from_names(iter::empty())
+        );
+    }
+
+    #[test]
+    fn test_slash() {
+        let mut stakes = new_stakes(&[(KEY1, 100), (KEY2, 5)]);
+        let key1 = PublicKey::ed25519_from(KEY1);
+        let key2 = PublicKey::ed25519_from(KEY2);
+        assert_eq!(U512::from(90), stakes.slash(&key1, U512::from(90)));
+        assert_eq!(U512::from(5), stakes.slash(&key2, U512::from(50)));
+        assert_eq!(new_stakes(&[(KEY1, 10)]), stakes);
+        assert_eq!(U512::from(10), stakes.slash(&key1, U512::from(50)));
+        assert_eq!(new_stakes(&[(KEY1, 0)]), stakes);
+        assert_eq!(U512::zero(), stakes.slash(&key2, U512::from(1)));
+    }
+
     #[test]
     fn test_bond() {
         let mut stakes = new_stakes(&[(KEY2, 100)]);
@@ -6,7 +6,7 @@ mod proof_of_stake_provider;
 
 use core::marker::Sized;
 
-use types::{ApiError, U512};
+use types::{ApiError, URef, U512};
 
 pub use crate::{
     account_provider::AccountProvider, mint_provider::MintProvider,
@@ -16,8 +16,15 @@ pub use crate::{
 pub trait StandardPayment: AccountProvider + MintProvider + ProofOfStakeProvider + Sized {
     fn pay(&mut self, amount: U512) -> Result<(), ApiError> {
         let main_purse = self.get_main_purse()?;
+        self.pay_from(main_purse, amount)
+    }
+
+    /// Pays `amount` into the PoS payment purse from `source` rather than the main purse. Unspent
+    /// payment is refunded to `source`.
+    fn pay_from(&mut self, source: URef, amount: U512) -> Result<(), ApiError> {
         let payment_purse = self.get_payment_purse()?;
-        self.transfer_purse_to_purse(main_purse, payment_purse, amount)
+        self.set_refund_purse(source)?;
+        self.transfer_purse_to_purse(source, payment_purse, amount)
             .map_err(|_| ApiError::Transfer)
     }
 }
@@ -2,4 +2,6 @@ use types::{ApiError, URef};
 
 pub trait ProofOfStakeProvider {
     fn get_payment_purse(&mut self) -> Result<URef, ApiError>;
+
+    fn set_refund_purse(&mut self, purse: URef) -> Result<(), ApiError>;
 }
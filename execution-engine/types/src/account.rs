@@ -1,17 +1,19 @@
 //! Contains types and constants associated with user accounts.
 
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
 use core::{
+    cmp::Ordering,
     convert::TryFrom,
     fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
 };
 
 use failure::Fail;
 use hex_fmt::HexFmt;
 
 use crate::{
-    bytesrepr::{Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
-    CLType, CLTyped,
+    bytesrepr::{self, Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    key, CLType, CLTyped, URef,
 };
 
 // This error type is not intended to be used by third party crates.
@@ -25,6 +27,7 @@ pub struct TryFromSliceForPublicKeyError(());
 
 /// The various types of action which can be performed in the context of a given account.
 #[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ActionType {
     /// Represents performing a deploy.
     Deployment = 0,
@@ -143,18 +146,45 @@ impl CLTyped for Weight {
         CLType::U8
     }
 }
-/// The length in bytes of a [`PublicKey`].
+/// The length in bytes of an [`Ed25519`] public key.
 pub const ED25519_LENGTH: usize = 32;
 
 /// The number of bytes in a serialized [`Ed25519`].
 pub const ED25519_SERIALIZED_LENGTH: usize = ED25519_LENGTH;
 
+/// The length in bytes of a compressed [`Secp256k1`] public key.
+pub const SECP256K1_LENGTH: usize = 33;
+
+/// The length in bytes of an uncompressed secp256k1 public key, as accepted by
+/// [`PublicKey::secp256k1_try_from`].
+pub const SECP256K1_UNCOMPRESSED_LENGTH: usize = 65;
+
+/// The number of bytes in a serialized [`Secp256k1`].
+pub const SECP256K1_SERIALIZED_LENGTH: usize = SECP256K1_LENGTH;
+
 /// The upper bound of bytes in a serialized [`PublicKey`].
-pub const PUBLIC_KEY_SERIALIZED_MAX_LENGTH: usize = ED25519_SERIALIZED_LENGTH;
+pub const PUBLIC_KEY_SERIALIZED_MAX_LENGTH: usize =
+    U8_SERIALIZED_LENGTH + U32_SERIALIZED_LENGTH + SECP256K1_SERIALIZED_LENGTH;
+
+/// The number of bytes in an account address, i.e. [`PublicKey::value`].
+pub const ACCOUNT_ADDRESS_LENGTH: usize = 32;
+
+const ED25519_TAG: u8 = 0;
+const SECP256K1_TAG: u8 = 1;
+
+const SECP256K1_EVEN_TAG: u8 = 0x02;
+const SECP256K1_ODD_TAG: u8 = 0x03;
+const SECP256K1_UNCOMPRESSED_TAG: u8 = 0x04;
 
 /// A type alias for the raw bytes of an Ed25519 public key.
 pub type Ed25519Bytes = [u8; ED25519_LENGTH];
 
+/// A type alias for the raw bytes of a compressed secp256k1 public key.
+pub type Secp256k1Bytes = [u8; SECP256K1_LENGTH];
+
+/// A type alias for the bytes of an account address.
+pub type AccountAddress = [u8; ACCOUNT_ADDRESS_LENGTH];
+
 /// A newtype wrapping a [`Ed25519Bytes`] which is the raw bytes of
 /// the public key of an Ed25519 key pair.
 #[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
@@ -200,17 +230,105 @@ impl FromBytes for Ed25519 {
     }
 }
 
+/// A newtype wrapping a [`Secp256k1Bytes`] which is the raw bytes of the compressed public key of
+/// a secp256k1 key pair.
+// Arrays longer than 32 elements don't derive the comparison traits, so they're implemented below
+// in terms of the underlying slice.
+#[derive(Clone, Copy)]
+pub struct Secp256k1(Secp256k1Bytes);
+
+impl Secp256k1 {
+    /// Constructs a new `Secp256k1` instance from the raw bytes of a compressed secp256k1 public
+    /// key.
+    pub const fn new(value: Secp256k1Bytes) -> Secp256k1 {
+        Secp256k1(value)
+    }
+
+    /// Returns the raw bytes of the compressed public key as an array.
+    pub fn value(&self) -> Secp256k1Bytes {
+        self.0
+    }
+
+    /// Returns the raw bytes of the compressed public key as a `slice`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq for Secp256k1 {
+    fn eq(&self, other: &Self) -> bool {
+        self.0[..] == other.0[..]
+    }
+}
+
+impl Eq for Secp256k1 {}
+
+impl PartialOrd for Secp256k1 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Secp256k1 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0[..].cmp(&other.0[..])
+    }
+}
+
+impl Hash for Secp256k1 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0[..].hash(state)
+    }
+}
+
+impl Display for Secp256k1 {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "Secp256k1({})", HexFmt(&self.0[..]))
+    }
+}
+
+impl ToBytes for Secp256k1 {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.0.to_vec())
+    }
+
+    fn serialized_length(&self) -> usize {
+        SECP256K1_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for Secp256k1 {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (bytes, rem) = bytesrepr::safe_split_at(bytes, SECP256K1_LENGTH)?;
+        let mut value = [0u8; SECP256K1_LENGTH];
+        value.copy_from_slice(bytes);
+        Ok((Secp256k1::new(value), rem))
+    }
+}
+
 /// An enum of supported public key types.
+///
+/// A `PublicKey` is serialized as its algorithm tag followed by the length-prefixed raw bytes of
+/// the key, i.e. in the same way as a `(u8, Vec<u8>)`.
+///
+/// This format is a hard fork.  Ed25519 keys used to be serialized as their 32 raw bytes, so every
+/// `Key::Account` in global state, and with it every state root hash, differs from the ones an
+/// earlier engine computed.  Global state written by an earlier engine can't be read by this one
+/// and isn't migrated by an upgrade, so a network adopting it has to start again from genesis.
 #[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum PublicKey {
     /// An Ed25519 public key type.
     Ed25519(Ed25519),
+    /// A compressed secp256k1 public key type.
+    Secp256k1(Secp256k1),
 }
 
 impl Display for PublicKey {
     fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-        let PublicKey::Ed25519(ed25519) = self;
-        write!(f, "PublicKey({})", ed25519)
+        match self {
+            PublicKey::Ed25519(ed25519) => write!(f, "PublicKey({})", ed25519),
+            PublicKey::Secp256k1(secp256k1) => write!(f, "PublicKey({})", secp256k1),
+        }
     }
 }
 
@@ -228,17 +346,95 @@ impl PublicKey {
             .map_err(|_| TryFromSliceForPublicKeyError(()))
     }
 
-    /// Returns the raw bytes of the public key as an array.
+    /// Constructs a new `PublicKey` using compressed secp256k1 bytes.
+    pub const fn secp256k1_from(key: Secp256k1Bytes) -> PublicKey {
+        let secp256k1 = Secp256k1::new(key);
+        PublicKey::Secp256k1(secp256k1)
+    }
+
+    /// Attempts a new `PublicKey` creation using a slice of bytes holding either a compressed or an
+    /// uncompressed secp256k1 public key.  An uncompressed key is converted to its compressed form.
+    pub fn secp256k1_try_from(bytes: &[u8]) -> Result<PublicKey, TryFromSliceForPublicKeyError> {
+        let mut key = [0u8; SECP256K1_LENGTH];
+        match (bytes.len(), bytes.first()) {
+            (SECP256K1_LENGTH, Some(&SECP256K1_EVEN_TAG))
+            | (SECP256K1_LENGTH, Some(&SECP256K1_ODD_TAG)) => key.copy_from_slice(bytes),
+            (SECP256K1_UNCOMPRESSED_LENGTH, Some(&SECP256K1_UNCOMPRESSED_TAG)) => {
+                let y_is_odd = bytes[SECP256K1_UNCOMPRESSED_LENGTH - 1] & 1 == 1;
+                key[0] = if y_is_odd {
+                    SECP256K1_ODD_TAG
+                } else {
+                    SECP256K1_EVEN_TAG
+                };
+                key[1..].copy_from_slice(&bytes[1..SECP256K1_LENGTH]);
+            }
+            _ => return Err(TryFromSliceForPublicKeyError(())),
+        }
+        Ok(PublicKey::secp256k1_from(key))
+    }
+
+    /// Attempts a new `PublicKey` creation using the raw bytes of a key of any supported
+    /// algorithm, which is inferred from the length of `bytes`.
+    pub fn try_from_raw(bytes: &[u8]) -> Result<PublicKey, TryFromSliceForPublicKeyError> {
+        if bytes.len() == ED25519_LENGTH {
+            PublicKey::ed25519_try_from(bytes)
+        } else {
+            PublicKey::secp256k1_try_from(bytes)
+        }
+    }
+
+    /// Returns the address of the account identified by the public key.
+    ///
+    /// This is the raw bytes of an Ed25519 key, and the Blake2b hash of the raw bytes of a
+    /// secp256k1 key.
     #[doc(hidden)]
-    pub fn value(self) -> Ed25519Bytes {
-        let PublicKey::Ed25519(ed25519) = self;
-        ed25519.value()
+    pub fn value(self) -> AccountAddress {
+        match self {
+            PublicKey::Ed25519(ed25519) => ed25519.value(),
+            PublicKey::Secp256k1(secp256k1) => key::hash(secp256k1.as_bytes()),
+        }
     }
 
     /// Returns the raw bytes of the public key as a `slice`.
     pub fn as_bytes(&self) -> &[u8] {
-        let PublicKey::Ed25519(ed25519) = self;
-        ed25519.as_bytes()
+        match self {
+            PublicKey::Ed25519(ed25519) => ed25519.as_bytes(),
+            PublicKey::Secp256k1(secp256k1) => secp256k1.as_bytes(),
+        }
+    }
+
+    /// Returns the public key as a lowercase hex string of its algorithm's tag followed by its raw
+    /// bytes.  Unlike the raw bytes alone, this tells keys of different algorithms apart, so it is
+    /// how public keys are encoded in the names of the Proof of Stake contract's named keys.
+    pub fn to_tagged_hex(&self) -> String {
+        let mut bytes = Vec::with_capacity(U8_SERIALIZED_LENGTH + self.as_bytes().len());
+        bytes.push(self.tag());
+        bytes.extend_from_slice(self.as_bytes());
+        base16::encode_lower(&bytes)
+    }
+
+    /// Parses a public key from a string returned by [`to_tagged_hex`](PublicKey::to_tagged_hex).
+    pub fn from_tagged_hex(tagged_hex: &str) -> Option<PublicKey> {
+        let mut bytes = [0u8; U8_SERIALIZED_LENGTH + SECP256K1_LENGTH];
+        if tagged_hex.len() > 2 * bytes.len() {
+            return None;
+        }
+        let length = base16::decode_slice(tagged_hex, &mut bytes).ok()?;
+        let (tag, raw_bytes) = bytes[..length].split_first()?;
+        match *tag {
+            ED25519_TAG => PublicKey::ed25519_try_from(raw_bytes).ok(),
+            SECP256K1_TAG if raw_bytes.len() == SECP256K1_LENGTH => {
+                PublicKey::secp256k1_try_from(raw_bytes).ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            PublicKey::Ed25519(_) => ED25519_TAG,
+            PublicKey::Secp256k1(_) => SECP256K1_TAG,
+        }
     }
 }
 
@@ -250,7 +446,10 @@ impl Debug for PublicKey {
 
 impl CLTyped for PublicKey {
     fn cl_type() -> CLType {
-        CLType::FixedList(Box::new(CLType::U8), 32)
+        CLType::Tuple2([
+            Box::new(CLType::U8),
+            Box::new(CLType::List(Box::new(CLType::U8))),
+        ])
     }
 }
 
@@ -260,23 +459,234 @@ impl From<Ed25519> for PublicKey {
     }
 }
 
+impl From<Secp256k1> for PublicKey {
+    fn from(secp256k1: Secp256k1) -> PublicKey {
+        PublicKey::Secp256k1(secp256k1)
+    }
+}
+
 impl ToBytes for PublicKey {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let PublicKey::Ed25519(ed25519) = self;
-        let mut bytes = Vec::with_capacity(PUBLIC_KEY_SERIALIZED_MAX_LENGTH);
-        bytes.append(&mut ed25519.to_bytes()?);
+        let mut bytes = Vec::with_capacity(self.serialized_length());
+        bytes.push(self.tag());
+        bytes.append(&mut (self.as_bytes().len() as u32).to_bytes()?);
+        bytes.extend_from_slice(self.as_bytes());
         Ok(bytes)
     }
 
     fn serialized_length(&self) -> usize {
-        PUBLIC_KEY_SERIALIZED_MAX_LENGTH
+        U8_SERIALIZED_LENGTH + U32_SERIALIZED_LENGTH + self.as_bytes().len()
     }
 }
 
 impl FromBytes for PublicKey {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (ed25519, rem) = Ed25519::from_bytes(bytes)?;
-        Ok((PublicKey::from(ed25519), rem))
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        let (length, rem) = u32::from_bytes(rem)?;
+        match (tag, length as usize) {
+            (ED25519_TAG, ED25519_SERIALIZED_LENGTH) => {
+                let (ed25519, rem) = Ed25519::from_bytes(rem)?;
+                Ok((PublicKey::from(ed25519), rem))
+            }
+            (SECP256K1_TAG, SECP256K1_SERIALIZED_LENGTH) => {
+                let (secp256k1, rem) = Secp256k1::from_bytes(rem)?;
+                Ok((PublicKey::from(secp256k1), rem))
+            }
+            _ => Err(Error::Formatting),
+        }
+    }
+}
+
+const ADD_TAG: u8 = 0;
+const REMOVE_TAG: u8 = 1;
+const UPDATE_WEIGHT_TAG: u8 = 2;
+const SET_THRESHOLD_TAG: u8 = 3;
+
+/// A single change to an account's associated keys or action thresholds.
+///
+/// A batch of these is applied by `update_associated_keys`, which only checks that the thresholds
+/// are satisfiable once the whole batch has been applied.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AssociatedKeysUpdate {
+    /// Associates a new [`PublicKey`] with the account.
+    Add(PublicKey, Weight),
+    /// Removes an associated [`PublicKey`] from the account.
+    Remove(PublicKey),
+    /// Changes the [`Weight`] of an associated [`PublicKey`].
+    UpdateWeight(PublicKey, Weight),
+    /// Changes the threshold of the given [`ActionType`].
+    SetThreshold(ActionType, Weight),
+}
+
+impl ToBytes for AssociatedKeysUpdate {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            AssociatedKeysUpdate::Add(public_key, weight) => {
+                result.push(ADD_TAG);
+                result.append(&mut public_key.to_bytes()?);
+                result.append(&mut weight.to_bytes()?);
+            }
+            AssociatedKeysUpdate::Remove(public_key) => {
+                result.push(REMOVE_TAG);
+                result.append(&mut public_key.to_bytes()?);
+            }
+            AssociatedKeysUpdate::UpdateWeight(public_key, weight) => {
+                result.push(UPDATE_WEIGHT_TAG);
+                result.append(&mut public_key.to_bytes()?);
+                result.append(&mut weight.to_bytes()?);
+            }
+            AssociatedKeysUpdate::SetThreshold(action_type, weight) => {
+                result.push(SET_THRESHOLD_TAG);
+                result.append(&mut (*action_type as u32).to_bytes()?);
+                result.append(&mut weight.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                AssociatedKeysUpdate::Add(public_key, _)
+                | AssociatedKeysUpdate::UpdateWeight(public_key, _) => {
+                    public_key.serialized_length() + WEIGHT_SERIALIZED_LENGTH
+                }
+                AssociatedKeysUpdate::Remove(public_key) => public_key.serialized_length(),
+                AssociatedKeysUpdate::SetThreshold(..) => {
+                    U32_SERIALIZED_LENGTH + WEIGHT_SERIALIZED_LENGTH
+                }
+            }
+    }
+}
+
+impl FromBytes for AssociatedKeysUpdate {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            ADD_TAG => {
+                let (public_key, rem) = PublicKey::from_bytes(rem)?;
+                let (weight, rem) = Weight::from_bytes(rem)?;
+                Ok((AssociatedKeysUpdate::Add(public_key, weight), rem))
+            }
+            REMOVE_TAG => {
+                let (public_key, rem) = PublicKey::from_bytes(rem)?;
+                Ok((AssociatedKeysUpdate::Remove(public_key), rem))
+            }
+            UPDATE_WEIGHT_TAG => {
+                let (public_key, rem) = PublicKey::from_bytes(rem)?;
+                let (weight, rem) = Weight::from_bytes(rem)?;
+                Ok((AssociatedKeysUpdate::UpdateWeight(public_key, weight), rem))
+            }
+            SET_THRESHOLD_TAG => {
+                let (action_type, rem) = u32::from_bytes(rem)?;
+                let action_type =
+                    ActionType::try_from(action_type).map_err(|_| Error::Formatting)?;
+                let (weight, rem) = Weight::from_bytes(rem)?;
+                Ok((AssociatedKeysUpdate::SetThreshold(action_type, weight), rem))
+            }
+            _ => Err(Error::Formatting),
+        }
+    }
+}
+
+/// The publicly visible configuration of an account, as returned to contracts by the host.
+///
+/// The account's main purse has its access rights removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountInfo {
+    public_key: PublicKey,
+    associated_keys: BTreeMap<PublicKey, Weight>,
+    deployment_threshold: Weight,
+    key_management_threshold: Weight,
+    main_purse: URef,
+}
+
+impl AccountInfo {
+    /// Constructs an `AccountInfo`.  Access rights are removed from `main_purse`.
+    pub fn new(
+        public_key: PublicKey,
+        associated_keys: BTreeMap<PublicKey, Weight>,
+        deployment_threshold: Weight,
+        key_management_threshold: Weight,
+        main_purse: URef,
+    ) -> Self {
+        AccountInfo {
+            public_key,
+            associated_keys,
+            deployment_threshold,
+            key_management_threshold,
+            main_purse: main_purse.remove_access_rights(),
+        }
+    }
+
+    /// Returns the account's public key.
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Returns the keys associated with the account and their weights.
+    pub fn associated_keys(&self) -> &BTreeMap<PublicKey, Weight> {
+        &self.associated_keys
+    }
+
+    /// Returns the total weight of associated keys needed to send a deploy from the account.
+    pub fn deployment_threshold(&self) -> Weight {
+        self.deployment_threshold
+    }
+
+    /// Returns the total weight of associated keys needed to change the account's keys or
+    /// thresholds.
+    pub fn key_management_threshold(&self) -> Weight {
+        self.key_management_threshold
+    }
+
+    /// Returns the account's main purse.
+    pub fn main_purse(&self) -> URef {
+        self.main_purse
+    }
+}
+
+impl CLTyped for AccountInfo {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for AccountInfo {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.public_key.to_bytes()?);
+        result.append(&mut self.associated_keys.to_bytes()?);
+        result.append(&mut self.deployment_threshold.to_bytes()?);
+        result.append(&mut self.key_management_threshold.to_bytes()?);
+        result.append(&mut self.main_purse.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.public_key.serialized_length()
+            + self.associated_keys.serialized_length()
+            + WEIGHT_SERIALIZED_LENGTH * 2
+            + self.main_purse.serialized_length()
+    }
+}
+
+impl FromBytes for AccountInfo {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (public_key, rem) = FromBytes::from_bytes(bytes)?;
+        let (associated_keys, rem) = FromBytes::from_bytes(rem)?;
+        let (deployment_threshold, rem) = FromBytes::from_bytes(rem)?;
+        let (key_management_threshold, rem) = FromBytes::from_bytes(rem)?;
+        let (main_purse, rem) = FromBytes::from_bytes(rem)?;
+        let account_info = AccountInfo {
+            public_key,
+            associated_keys,
+            deployment_threshold,
+            key_management_threshold,
+            main_purse,
+        };
+        Ok((account_info, rem))
     }
 }
 
@@ -389,6 +799,7 @@ mod tests {
     use std::{convert::TryFrom, vec::Vec};
 
     use super::*;
+    use crate::AccessRights;
 
     #[test]
     fn ed25519_public_key_from_slice() {
@@ -408,6 +819,113 @@ mod tests {
             PublicKey::ed25519_try_from(&[0u8; 33][..]).expect_err("should not create public key");
     }
 
+    #[test]
+    fn secp256k1_public_key_from_compressed_slice() {
+        let mut bytes = [7u8; SECP256K1_LENGTH];
+        bytes[0] = SECP256K1_ODD_TAG;
+        let public_key =
+            PublicKey::secp256k1_try_from(&bytes[..]).expect("should create public key");
+        assert_eq!(&bytes[..], public_key.as_bytes());
+    }
+
+    #[test]
+    fn secp256k1_public_key_from_uncompressed_slice() {
+        let mut uncompressed = [7u8; SECP256K1_UNCOMPRESSED_LENGTH];
+        uncompressed[0] = SECP256K1_UNCOMPRESSED_TAG;
+        uncompressed[SECP256K1_UNCOMPRESSED_LENGTH - 1] = 2;
+        let public_key =
+            PublicKey::secp256k1_try_from(&uncompressed[..]).expect("should create public key");
+        let mut compressed = [7u8; SECP256K1_LENGTH];
+        compressed[0] = SECP256K1_EVEN_TAG;
+        assert_eq!(public_key, PublicKey::secp256k1_from(compressed));
+    }
+
+    #[test]
+    fn secp256k1_public_key_from_invalid_slice() {
+        let _public_key = PublicKey::secp256k1_try_from(&[7u8; SECP256K1_LENGTH][..])
+            .expect_err("should not create public key");
+        let _public_key = PublicKey::secp256k1_try_from(&[SECP256K1_EVEN_TAG; 34][..])
+            .expect_err("should not create public key");
+    }
+
+    #[test]
+    fn public_key_from_raw_slice() {
+        let ed25519 = PublicKey::try_from_raw(&[1u8; ED25519_LENGTH][..]).unwrap();
+        assert_eq!(ed25519, PublicKey::ed25519_from([1u8; ED25519_LENGTH]));
+        let secp256k1 =
+            PublicKey::try_from_raw(&[SECP256K1_EVEN_TAG; SECP256K1_LENGTH][..]).unwrap();
+        assert_eq!(
+            secp256k1,
+            PublicKey::secp256k1_from([SECP256K1_EVEN_TAG; SECP256K1_LENGTH])
+        );
+    }
+
+    #[test]
+    fn public_key_serialization_is_tagged() {
+        let ed25519 = PublicKey::ed25519_from([1u8; ED25519_LENGTH]);
+        let secp256k1 = PublicKey::secp256k1_from([2u8; SECP256K1_LENGTH]);
+        assert_eq!(ed25519.to_bytes().unwrap()[0], ED25519_TAG);
+        assert_eq!(secp256k1.to_bytes().unwrap()[0], SECP256K1_TAG);
+        assert_eq!(
+            secp256k1.serialized_length(),
+            PUBLIC_KEY_SERIALIZED_MAX_LENGTH
+        );
+        bytesrepr::test_serialization_roundtrip(&ed25519);
+        bytesrepr::test_serialization_roundtrip(&secp256k1);
+
+        // A public key must serialize like the `(u8, Vec<u8>)` its `CLType` describes.
+        let as_tuple = (ED25519_TAG, vec![1u8; ED25519_LENGTH]);
+        assert_eq!(ed25519.to_bytes().unwrap(), as_tuple.to_bytes().unwrap());
+        assert_eq!(PublicKey::cl_type(), <(u8, Vec<u8>)>::cl_type());
+
+        let mut mismatched_length = secp256k1.to_bytes().unwrap();
+        mismatched_length[0] = ED25519_TAG;
+        assert_eq!(
+            PublicKey::from_bytes(&mismatched_length).unwrap_err(),
+            Error::Formatting
+        );
+    }
+
+    #[test]
+    fn public_key_tagged_hex_roundtrip() {
+        let ed25519 = PublicKey::ed25519_from([1u8; ED25519_LENGTH]);
+        let secp256k1 = PublicKey::secp256k1_from([SECP256K1_ODD_TAG; SECP256K1_LENGTH]);
+        assert_eq!(
+            ed25519.to_tagged_hex(),
+            format!("00{}", "01".repeat(ED25519_LENGTH))
+        );
+        assert_eq!(
+            secp256k1.to_tagged_hex(),
+            format!("01{}", "03".repeat(SECP256K1_LENGTH))
+        );
+        for public_key in &[ed25519, secp256k1] {
+            assert_eq!(
+                PublicKey::from_tagged_hex(&public_key.to_tagged_hex()),
+                Some(*public_key)
+            );
+        }
+        // The untagged raw bytes of either kind of key don't parse
+        assert_eq!(
+            PublicKey::from_tagged_hex(&"01".repeat(ED25519_LENGTH)),
+            None
+        );
+        assert_eq!(
+            PublicKey::from_tagged_hex(&"03".repeat(SECP256K1_LENGTH)),
+            None
+        );
+        assert_eq!(PublicKey::from_tagged_hex(&"00".repeat(64)), None);
+        assert_eq!(PublicKey::from_tagged_hex(""), None);
+        assert_eq!(PublicKey::from_tagged_hex("0g"), None);
+    }
+
+    #[test]
+    fn secp256k1_account_address_is_hash_of_key() {
+        let ed25519 = PublicKey::ed25519_from([1u8; ED25519_LENGTH]);
+        let secp256k1 = PublicKey::secp256k1_from([2u8; SECP256K1_LENGTH]);
+        assert_eq!(ed25519.value(), [1u8; ED25519_LENGTH]);
+        assert_eq!(secp256k1.value(), key::hash(&[2u8; SECP256K1_LENGTH]));
+    }
+
     #[test]
     fn try_from_i32_for_set_threshold_failure() {
         let max_valid_value_for_variant = SetThresholdFailure::InsufficientTotalWeight as i32;
@@ -419,6 +937,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn associated_keys_update_serialization_roundtrip() {
+        let public_key = PublicKey::ed25519_from([42; 32]);
+        let updates = vec![
+            AssociatedKeysUpdate::Add(public_key, Weight::new(3)),
+            AssociatedKeysUpdate::Remove(public_key),
+            AssociatedKeysUpdate::UpdateWeight(public_key, Weight::new(255)),
+            AssociatedKeysUpdate::SetThreshold(ActionType::Deployment, Weight::new(1)),
+            AssociatedKeysUpdate::SetThreshold(ActionType::KeyManagement, Weight::new(2)),
+        ];
+        for update in &updates {
+            bytesrepr::test_serialization_roundtrip(update);
+        }
+        bytesrepr::test_serialization_roundtrip(&updates);
+    }
+
+    #[test]
+    fn account_info_serialization_roundtrip() {
+        let public_key = PublicKey::ed25519_from([42; 32]);
+        let other_key = PublicKey::ed25519_from([43; 32]);
+        let associated_keys = vec![(public_key, Weight::new(1)), (other_key, Weight::new(2))]
+            .into_iter()
+            .collect();
+        let main_purse = URef::new([7; 32], AccessRights::READ_ADD_WRITE);
+        let account_info = AccountInfo::new(
+            public_key,
+            associated_keys,
+            Weight::new(1),
+            Weight::new(3),
+            main_purse,
+        );
+        assert_eq!(
+            account_info.main_purse().access_rights(),
+            AccessRights::NONE
+        );
+        bytesrepr::test_serialization_roundtrip(&account_info);
+    }
+
     #[test]
     fn try_from_i32_for_add_key_failure() {
         let max_valid_value_for_variant = AddKeyFailure::PermissionDenied as i32;
@@ -11,7 +11,7 @@ use crate::{
         TryFromSliceForPublicKeyError, UpdateKeyFailure,
     },
     bytesrepr,
-    system_contract_errors::{mint, pos},
+    system_contract_errors::{key_recovery, mint, pos},
     CLValueError,
 };
 
@@ -27,6 +27,10 @@ const POS_ERROR_OFFSET: u32 = RESERVED_ERROR_MAX - u8::MAX as u32; // 65280..=65
 /// added to them when being converted to a `u32`.
 const MINT_ERROR_OFFSET: u32 = (POS_ERROR_OFFSET - 1) - u8::MAX as u32; // 65024..=65279
 
+/// Key Recovery errors (defined in "types/src/system_contract_errors/key_recovery.rs") will have
+/// this value added to them when being converted to a `u32`.
+const KEY_RECOVERY_ERROR_OFFSET: u32 = (MINT_ERROR_OFFSET - 1) - u8::MAX as u32; // 64768..=65023
+
 /// Minimum value of user error's inclusive range.
 const USER_ERROR_MIN: u32 = RESERVED_ERROR_MAX + 1;
 
@@ -39,6 +43,12 @@ const MINT_ERROR_MIN: u32 = MINT_ERROR_OFFSET;
 /// Maximum value of Mint error's inclusive range.
 const MINT_ERROR_MAX: u32 = POS_ERROR_OFFSET - 1;
 
+/// Minimum value of Key Recovery error's inclusive range.
+const KEY_RECOVERY_ERROR_MIN: u32 = KEY_RECOVERY_ERROR_OFFSET;
+
+/// Maximum value of Key Recovery error's inclusive range.
+const KEY_RECOVERY_ERROR_MAX: u32 = MINT_ERROR_OFFSET - 1;
+
 /// Minimum value of Proof of Stake error's inclusive range.
 const POS_ERROR_MIN: u32 = POS_ERROR_OFFSET;
 
@@ -52,12 +62,13 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 ///
 /// The variants are split into numeric ranges as follows:
 ///
-/// | Inclusive range | Variant(s)                                   |
-/// | ----------------| ---------------------------------------------|
-/// | [1, 65023]      | all except `Mint`, `ProofOfStake` and `User` |
-/// | [65024, 65279]  | `Mint`                                       |
-/// | [65280, 65535]  | `ProofOfStake`                               |
-/// | [65536, 131071] | `User`                                       |
+/// | Inclusive range | Variant(s)                                                  |
+/// | ----------------| ------------------------------------------------------------|
+/// | [1, 64767]      | all except `KeyRecovery`, `Mint`, `ProofOfStake` and `User` |
+/// | [64768, 65023]  | `KeyRecovery`                                               |
+/// | [65024, 65279]  | `Mint`                                                      |
+/// | [65280, 65535]  | `ProofOfStake`                                              |
+/// | [65536, 131071] | `User`                                                      |
 ///
 /// ## Mappings
 ///
@@ -172,6 +183,51 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 /// # show_and_check!(
 /// 34 => HostBufferFull
 /// # );
+/// # show_and_check!(
+/// 35 => AllocLayout
+/// # );
+/// # show_and_check!(
+/// 36 => ArithmeticOverflow
+/// # );
+/// # show_and_check!(
+/// 37 => DivisionByZero
+/// # );
+///
+/// // Key recovery errors:
+/// use casperlabs_types::system_contract_errors::key_recovery::Error as KeyRecoveryError;
+/// # show_and_check!(
+/// 64_768 => KeyRecoveryError::KeyManagementThreshold
+/// # );
+/// # show_and_check!(
+/// 64_769 => KeyRecoveryError::InvalidThreshold
+/// # );
+/// # show_and_check!(
+/// 64_770 => KeyRecoveryError::NotConfigured
+/// # );
+/// # show_and_check!(
+/// 64_771 => KeyRecoveryError::NotRecoveryKey
+/// # );
+/// # show_and_check!(
+/// 64_772 => KeyRecoveryError::ConflictingRequest
+/// # );
+/// # show_and_check!(
+/// 64_773 => KeyRecoveryError::RequestNotFound
+/// # );
+/// # show_and_check!(
+/// 64_774 => KeyRecoveryError::InsufficientApprovals
+/// # );
+/// # show_and_check!(
+/// 64_775 => KeyRecoveryError::DelayNotElapsed
+/// # );
+/// # show_and_check!(
+/// 64_776 => KeyRecoveryError::MissingKey
+/// # );
+/// # show_and_check!(
+/// 64_777 => KeyRecoveryError::DuplicateKey
+/// # );
+/// # show_and_check!(
+/// 64_778 => KeyRecoveryError::RotationFailed
+/// # );
 ///
 /// // Mint errors:
 /// use casperlabs_types::system_contract_errors::mint::Error as MintError;
@@ -199,6 +255,15 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 /// # show_and_check!(
 /// 65_031 => MintError::PurseNotFound
 /// # );
+/// # show_and_check!(
+/// 65_032 => MintError::FundsLocked
+/// # );
+/// # show_and_check!(
+/// 65_033 => MintError::InvalidVestingScheduleCreation
+/// # );
+/// # show_and_check!(
+/// 65_034 => MintError::BelowMinimumBalance
+/// # );
 ///
 /// // Proof of stake errors:
 /// use casperlabs_types::system_contract_errors::pos::Error as PosError;
@@ -283,6 +348,30 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 /// # show_and_check!(
 /// 65_306 => PosError::SetRefundPurseCalledOutsidePayment
 /// # );
+/// # show_and_check!(
+/// 65_307 => PosError::UnbondingDelayNotFound
+/// # );
+/// # show_and_check!(
+/// 65_308 => PosError::UnbondingDelayKeyUnexpectedType
+/// # );
+/// # show_and_check!(
+/// 65_309 => PosError::NotDelegated
+/// # );
+/// # show_and_check!(
+/// 65_310 => PosError::DelegateTransferFailed
+/// # );
+/// # show_and_check!(
+/// 65_311 => PosError::DelegationsKeyDeserializationFailed
+/// # );
+/// # show_and_check!(
+/// 65_312 => PosError::DelegationsDeserializationFailed
+/// # );
+/// # show_and_check!(
+/// 65_313 => PosError::RewardsPurseBalanceNotFound
+/// # );
+/// # show_and_check!(
+/// 65_314 => PosError::InvalidRefundRatio
+/// # );
 ///
 /// // User-defined errors:
 /// # show_and_check!(
@@ -400,6 +489,12 @@ pub enum ApiError {
     HostBufferFull,
     /// Could not lay out an array in memory
     AllocLayout,
+    /// The result of an arithmetic operation overflowed or underflowed its type.
+    ArithmeticOverflow,
+    /// An arithmetic operation attempted to divide by zero.
+    DivisionByZero,
+    /// Error specific to Key Recovery contract.
+    KeyRecovery(u8),
     /// Error specific to Mint contract.
     Mint(u8),
     /// Error specific to Proof of Stake contract.
@@ -484,6 +579,12 @@ impl From<TryFromSliceForPublicKeyError> for ApiError {
     }
 }
 
+impl From<key_recovery::Error> for ApiError {
+    fn from(error: key_recovery::Error) -> Self {
+        ApiError::KeyRecovery(error as u8)
+    }
+}
+
 impl From<mint::Error> for ApiError {
     fn from(error: mint::Error) -> Self {
         ApiError::Mint(error as u8)
@@ -534,6 +635,9 @@ impl From<ApiError> for u32 {
             ApiError::HostBufferEmpty => 33,
             ApiError::HostBufferFull => 34,
             ApiError::AllocLayout => 35,
+            ApiError::ArithmeticOverflow => 36,
+            ApiError::DivisionByZero => 37,
+            ApiError::KeyRecovery(value) => KEY_RECOVERY_ERROR_OFFSET + u32::from(value),
             ApiError::Mint(value) => MINT_ERROR_OFFSET + u32::from(value),
             ApiError::ProofOfStake(value) => POS_ERROR_OFFSET + u32::from(value),
             ApiError::User(value) => RESERVED_ERROR_MAX + 1 + u32::from(value),
@@ -579,9 +683,12 @@ impl From<u32> for ApiError {
             33 => ApiError::HostBufferEmpty,
             34 => ApiError::HostBufferFull,
             35 => ApiError::AllocLayout,
+            36 => ApiError::ArithmeticOverflow,
+            37 => ApiError::DivisionByZero,
             USER_ERROR_MIN..=USER_ERROR_MAX => ApiError::User(value as u16),
             POS_ERROR_MIN..=POS_ERROR_MAX => ApiError::ProofOfStake(value as u8),
             MINT_ERROR_MIN..=MINT_ERROR_MAX => ApiError::Mint(value as u8),
+            KEY_RECOVERY_ERROR_MIN..=KEY_RECOVERY_ERROR_MAX => ApiError::KeyRecovery(value as u8),
             _ => ApiError::Unhandled,
         }
     }
@@ -627,6 +734,9 @@ impl Debug for ApiError {
             ApiError::HostBufferEmpty => write!(f, "ApiError::HostBufferEmpty")?,
             ApiError::HostBufferFull => write!(f, "ApiError::HostBufferFull")?,
             ApiError::AllocLayout => write!(f, "ApiError::AllocLayout")?,
+            ApiError::ArithmeticOverflow => write!(f, "ApiError::ArithmeticOverflow")?,
+            ApiError::DivisionByZero => write!(f, "ApiError::DivisionByZero")?,
+            ApiError::KeyRecovery(value) => write!(f, "ApiError::KeyRecovery({})", value)?,
             ApiError::Mint(value) => write!(f, "ApiError::Mint({})", value)?,
             ApiError::ProofOfStake(value) => write!(f, "ApiError::ProofOfStake({})", value)?,
             ApiError::User(value) => write!(f, "ApiError::User({})", value)?,
@@ -639,6 +749,7 @@ impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ApiError::User(value) => write!(f, "User error: {}", value),
+            ApiError::KeyRecovery(value) => write!(f, "Key Recovery error: {}", value),
             ApiError::Mint(value) => write!(f, "Mint error: {}", value),
             ApiError::ProofOfStake(value) => write!(f, "PoS error: {}", value),
             _ => <Self as Debug>::fmt(&self, f),
@@ -676,6 +787,24 @@ mod tests {
         assert_eq!(result, result_from(code));
     }
 
+    #[test]
+    fn key_recovery_error() {
+        assert_eq!(64_768_u32, ApiError::KeyRecovery(0).into()); // KEY_RECOVERY_ERROR_OFFSET
+        assert_eq!(65_023_u32, ApiError::KeyRecovery(u8::MAX).into());
+        assert_eq!(
+            "ApiError::KeyRecovery(0) [64768]",
+            &format!("{:?}", ApiError::KeyRecovery(0))
+        );
+        assert_eq!(
+            "Key Recovery error: 0",
+            &format!("{}", ApiError::KeyRecovery(0))
+        );
+        assert_eq!(
+            Err(ApiError::Unhandled),
+            result_from(KEY_RECOVERY_ERROR_OFFSET as i32 - 1)
+        );
+    }
+
     #[test]
     fn error() {
         assert_eq!(65_024_u32, ApiError::Mint(0).into()); // MINT_ERROR_OFFSET == 65,024
@@ -717,10 +846,6 @@ mod tests {
         );
 
         assert_eq!(Err(ApiError::Unhandled), result_from(i32::MAX));
-        assert_eq!(
-            Err(ApiError::Unhandled),
-            result_from(MINT_ERROR_OFFSET as i32 - 1)
-        );
         assert_eq!(Err(ApiError::Unhandled), result_from(-1));
         assert_eq!(Err(ApiError::Unhandled), result_from(i32::MIN));
 
@@ -760,6 +885,10 @@ mod tests {
         round_trip(Err(ApiError::HostBufferEmpty));
         round_trip(Err(ApiError::HostBufferFull));
         round_trip(Err(ApiError::AllocLayout));
+        round_trip(Err(ApiError::ArithmeticOverflow));
+        round_trip(Err(ApiError::DivisionByZero));
+        round_trip(Err(ApiError::KeyRecovery(0)));
+        round_trip(Err(ApiError::KeyRecovery(u8::MAX)));
         round_trip(Err(ApiError::Mint(0)));
         round_trip(Err(ApiError::Mint(u8::MAX)));
         round_trip(Err(ApiError::ProofOfStake(0)));
@@ -0,0 +1,65 @@
+//! Home of the arithmetic operations on `U256` and `U512` which contracts can run on the host.
+
+use core::convert::TryFrom;
+
+use crate::ApiError;
+
+/// An arithmetic operation on two `U256`s or two `U512`s.
+///
+/// Used by converting to a `u32` and passing as the `op` argument of `ext_ffi::bignum_op()`.
+/// Checked operations fail with [`ApiError::ArithmeticOverflow`] or [`ApiError::DivisionByZero`]
+/// where the corresponding operator would panic, while saturating operations clamp the result to
+/// the bounds of the type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BigNumOp {
+    /// Addition which fails on overflow.
+    CheckedAdd,
+    /// Subtraction which fails on underflow.
+    CheckedSub,
+    /// Multiplication which fails on overflow.
+    CheckedMul,
+    /// Division which fails if the divisor is zero.
+    CheckedDiv,
+    /// Remainder which fails if the divisor is zero.
+    CheckedRem,
+    /// Addition which saturates at the maximum value.
+    SaturatingAdd,
+    /// Subtraction which saturates at zero.
+    SaturatingSub,
+    /// Multiplication which saturates at the maximum value.
+    SaturatingMul,
+}
+
+impl From<BigNumOp> for u32 {
+    fn from(op: BigNumOp) -> u32 {
+        match op {
+            BigNumOp::CheckedAdd => 0,
+            BigNumOp::CheckedSub => 1,
+            BigNumOp::CheckedMul => 2,
+            BigNumOp::CheckedDiv => 3,
+            BigNumOp::CheckedRem => 4,
+            BigNumOp::SaturatingAdd => 5,
+            BigNumOp::SaturatingSub => 6,
+            BigNumOp::SaturatingMul => 7,
+        }
+    }
+}
+
+// This conversion is not intended to be used by third party crates.
+#[doc(hidden)]
+impl TryFrom<u32> for BigNumOp {
+    type Error = ApiError;
+    fn try_from(value: u32) -> Result<BigNumOp, Self::Error> {
+        match value {
+            0 => Ok(BigNumOp::CheckedAdd),
+            1 => Ok(BigNumOp::CheckedSub),
+            2 => Ok(BigNumOp::CheckedMul),
+            3 => Ok(BigNumOp::CheckedDiv),
+            4 => Ok(BigNumOp::CheckedRem),
+            5 => Ok(BigNumOp::SaturatingAdd),
+            6 => Ok(BigNumOp::SaturatingSub),
+            7 => Ok(BigNumOp::SaturatingMul),
+            _ => Err(ApiError::InvalidArgument),
+        }
+    }
+}
@@ -6,7 +6,7 @@ use crate::bytesrepr::{Error, FromBytes, ToBytes, U64_SERIALIZED_LENGTH};
 pub const BLOCKTIME_SERIALIZED_LENGTH: usize = U64_SERIALIZED_LENGTH;
 
 /// A newtype wrapping a [`u64`] which represents the block time.
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd)]
+#[derive(Clone, Copy, Default, Debug, Hash, PartialEq, Eq, PartialOrd)]
 pub struct BlockTime(u64);
 
 impl BlockTime {
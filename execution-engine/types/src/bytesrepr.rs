@@ -887,6 +887,21 @@ mod proptests {
             bytesrepr::test_serialization_roundtrip(&result);
         }
 
+        #[test]
+        fn test_transfer_receipt(receipt in transfer_receipt_arb()) {
+            bytesrepr::test_serialization_roundtrip(&receipt);
+        }
+
+        #[test]
+        fn test_named_arg(named_arg in named_arg_arb()) {
+            bytesrepr::test_serialization_roundtrip(&named_arg);
+        }
+
+        #[test]
+        fn test_contract_abi(contract_abi in contract_abi_arb()) {
+            bytesrepr::test_serialization_roundtrip(&contract_abi);
+        }
+
         #[test]
         fn test_phase_serialization(phase in phase_arb()) {
             bytesrepr::test_serialization_roundtrip(&phase);
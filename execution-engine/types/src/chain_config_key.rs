@@ -0,0 +1,79 @@
+//! Home of the chain configuration key enum.
+
+use core::convert::TryFrom;
+
+use crate::ApiError;
+
+/// Chain-wide configuration values which may be read by contracts.
+///
+/// Used by converting to a `u32` and passing as the `key` argument of
+/// `ext_ffi::get_chain_config()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ChainConfigKey {
+    /// The name of the chain given in its chainspec, as a `String`.  Empty if the chainspec
+    /// doesn't name the chain.
+    ChainName,
+    /// The number of motes paid for one unit of gas, as a `u64`.
+    ConvRate,
+    /// The amount of motes set aside from the payment purse before executing payment code, as a
+    /// `U512`.
+    MaxPayment,
+    /// The current protocol version, as a `(u32, u32, u32)` of its major, minor and patch
+    /// components.
+    ProtocolVersion,
+    /// The public key of the account which runs the system's own deploys, such as genesis and
+    /// finalizing payment, as a `PublicKey`.
+    SystemAccount,
+}
+
+impl From<ChainConfigKey> for u32 {
+    fn from(key: ChainConfigKey) -> u32 {
+        match key {
+            ChainConfigKey::ChainName => 0,
+            ChainConfigKey::ConvRate => 1,
+            ChainConfigKey::MaxPayment => 2,
+            ChainConfigKey::ProtocolVersion => 3,
+            ChainConfigKey::SystemAccount => 4,
+        }
+    }
+}
+
+// This conversion is not intended to be used by third party crates.
+#[doc(hidden)]
+impl TryFrom<u32> for ChainConfigKey {
+    type Error = ApiError;
+    fn try_from(value: u32) -> Result<ChainConfigKey, Self::Error> {
+        match value {
+            0 => Ok(ChainConfigKey::ChainName),
+            1 => Ok(ChainConfigKey::ConvRate),
+            2 => Ok(ChainConfigKey::MaxPayment),
+            3 => Ok(ChainConfigKey::ProtocolVersion),
+            4 => Ok(ChainConfigKey::SystemAccount),
+            _ => Err(ApiError::InvalidArgument),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_round_trip_through_u32() {
+        for key in &[
+            ChainConfigKey::ChainName,
+            ChainConfigKey::ConvRate,
+            ChainConfigKey::MaxPayment,
+            ChainConfigKey::ProtocolVersion,
+            ChainConfigKey::SystemAccount,
+        ] {
+            assert_eq!(ChainConfigKey::try_from(u32::from(*key)), Ok(*key));
+        }
+    }
+
+    #[test]
+    fn should_fail_to_convert_unknown_key() {
+        assert!(ChainConfigKey::try_from(5).is_err());
+        assert!(ChainConfigKey::try_from(u32::max_value()).is_err());
+    }
+}
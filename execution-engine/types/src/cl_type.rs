@@ -180,6 +180,18 @@ impl CLType {
     }
 }
 
+impl ToBytes for CLType {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(CLType::serialized_length(self));
+        self.append_bytes(&mut result);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        CLType::serialized_length(self)
+    }
+}
+
 #[allow(clippy::cognitive_complexity)]
 impl FromBytes for CLType {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
@@ -682,4 +694,19 @@ mod tests {
         let any = Any("Any test".to_string());
         round_trip(&any);
     }
+
+    #[test]
+    fn cl_type_should_serialize_like_cl_value_tag() {
+        type Nested = BTreeMap<String, Vec<Option<(U512, Key)>>>;
+
+        let cl_type = Nested::cl_type();
+        let serialized = cl_type.to_bytes().unwrap();
+        assert_eq!(serialized.len(), ToBytes::serialized_length(&cl_type));
+
+        let cl_value = CLValue::from_t(Nested::new()).unwrap();
+        assert!(cl_value.to_bytes().unwrap().ends_with(&serialized));
+
+        let parsed: CLType = bytesrepr::deserialize(serialized).unwrap();
+        assert_eq!(parsed, cl_type);
+    }
 }
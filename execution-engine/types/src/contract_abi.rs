@@ -0,0 +1,335 @@
+//! A description of how to call a stored contract.
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{
+    account::PublicKey,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+const PUBLIC_TAG: u8 = 0;
+const GROUPS_TAG: u8 = 1;
+const CALLER_TAG: u8 = 2;
+
+/// A named and typed parameter of an [`EntryPoint`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Parameter {
+    name: String,
+    cl_type: CLType,
+}
+
+impl Parameter {
+    /// Constructs a `Parameter`.
+    pub fn new(name: String, cl_type: CLType) -> Self {
+        Parameter { name, cl_type }
+    }
+
+    /// Returns the name of the parameter.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the parameter.
+    pub fn cl_type(&self) -> &CLType {
+        &self.cl_type
+    }
+}
+
+impl ToBytes for Parameter {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.name.to_bytes()?);
+        self.cl_type.append_bytes(&mut result);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length() + self.cl_type.serialized_length()
+    }
+}
+
+impl FromBytes for Parameter {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (name, remainder) = String::from_bytes(bytes)?;
+        let (cl_type, remainder) = CLType::from_bytes(remainder)?;
+        Ok((Parameter { name, cl_type }, remainder))
+    }
+}
+
+/// Who may call an [`EntryPoint`] of a contract stored with named entry points.
+///
+/// The caller is the account which sent the deploy, however deep the call stack.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum EntryPointAccess {
+    /// Any account.
+    Public,
+    /// Members of any of the named groups of the contract's [`ContractAbi`].
+    Groups(Vec<String>),
+    /// Only the given account.
+    Caller(PublicKey),
+}
+
+impl Default for EntryPointAccess {
+    fn default() -> Self {
+        EntryPointAccess::Public
+    }
+}
+
+impl ToBytes for EntryPointAccess {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            EntryPointAccess::Public => result.push(PUBLIC_TAG),
+            EntryPointAccess::Groups(groups) => {
+                result.push(GROUPS_TAG);
+                result.append(&mut groups.to_bytes()?);
+            }
+            EntryPointAccess::Caller(caller) => {
+                result.push(CALLER_TAG);
+                result.append(&mut caller.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            EntryPointAccess::Public => 0,
+            EntryPointAccess::Groups(groups) => groups.serialized_length(),
+            EntryPointAccess::Caller(caller) => caller.serialized_length(),
+        }
+    }
+}
+
+impl FromBytes for EntryPointAccess {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            PUBLIC_TAG => Ok((EntryPointAccess::Public, remainder)),
+            GROUPS_TAG => {
+                let (groups, remainder) = Vec::<String>::from_bytes(remainder)?;
+                Ok((EntryPointAccess::Groups(groups), remainder))
+            }
+            CALLER_TAG => {
+                let (caller, remainder) = PublicKey::from_bytes(remainder)?;
+                Ok((EntryPointAccess::Caller(caller), remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A method of a stored contract.
+///
+/// A contract stored with a single function selects the method by the name passed as its first
+/// argument.  A contract stored with named entry points exports a function under each name, which
+/// the host calls directly after checking the entry point's [`EntryPointAccess`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct EntryPoint {
+    name: String,
+    parameters: Vec<Parameter>,
+    ret: CLType,
+    access: EntryPointAccess,
+}
+
+impl EntryPoint {
+    /// Constructs a public `EntryPoint` taking `parameters`, in order, and returning a value of
+    /// type `ret`.
+    pub fn new(name: String, parameters: Vec<Parameter>, ret: CLType) -> Self {
+        EntryPoint {
+            name,
+            parameters,
+            ret,
+            access: EntryPointAccess::Public,
+        }
+    }
+
+    /// Restricts who may call the entry point.
+    pub fn with_access(mut self, access: EntryPointAccess) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// Returns the name of the entry point.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the parameters of the entry point.
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    /// Returns the type of the value returned by the entry point, [`CLType::Unit`] if it returns
+    /// nothing.
+    pub fn ret(&self) -> &CLType {
+        &self.ret
+    }
+
+    /// Returns who may call the entry point.
+    pub fn access(&self) -> &EntryPointAccess {
+        &self.access
+    }
+}
+
+impl ToBytes for EntryPoint {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.name.to_bytes()?);
+        result.append(&mut self.parameters.to_bytes()?);
+        self.ret.append_bytes(&mut result);
+        result.append(&mut self.access.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length()
+            + self.parameters.serialized_length()
+            + self.ret.serialized_length()
+            + self.access.serialized_length()
+    }
+}
+
+impl FromBytes for EntryPoint {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (name, remainder) = String::from_bytes(bytes)?;
+        let (parameters, remainder) = Vec::<Parameter>::from_bytes(remainder)?;
+        let (ret, remainder) = CLType::from_bytes(remainder)?;
+        let (access, remainder) = EntryPointAccess::from_bytes(remainder)?;
+        let entry_point = EntryPoint {
+            name,
+            parameters,
+            ret,
+            access,
+        };
+        Ok((entry_point, remainder))
+    }
+}
+
+/// Describes the entry points of a stored contract so that tooling can discover how to call it.
+///
+/// The descriptor is supplied by the contract's author when the contract is stored.  It is only
+/// checked against the contract's code, and enforced when calling it, if the contract is stored
+/// with named entry points.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ContractAbi {
+    entry_points: Vec<EntryPoint>,
+    groups: BTreeMap<String, Vec<PublicKey>>,
+}
+
+impl ContractAbi {
+    /// Constructs a `ContractAbi` without any groups.
+    pub fn new(entry_points: Vec<EntryPoint>) -> Self {
+        ContractAbi {
+            entry_points,
+            groups: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the groups of accounts referred to by [`EntryPointAccess::Groups`].
+    pub fn with_groups(mut self, groups: BTreeMap<String, Vec<PublicKey>>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Returns the entry points of the contract.
+    pub fn entry_points(&self) -> &[EntryPoint] {
+        &self.entry_points
+    }
+
+    /// Returns the entry point called `name`, if there is one.
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPoint> {
+        self.entry_points
+            .iter()
+            .find(|entry_point| entry_point.name == name)
+    }
+
+    /// Returns the groups of accounts referred to by [`EntryPointAccess::Groups`].
+    pub fn groups(&self) -> &BTreeMap<String, Vec<PublicKey>> {
+        &self.groups
+    }
+
+    /// Returns whether `caller` may call `entry_point`.  A group which isn't defined has no
+    /// members.
+    pub fn can_call(&self, entry_point: &EntryPoint, caller: PublicKey) -> bool {
+        match &entry_point.access {
+            EntryPointAccess::Public => true,
+            EntryPointAccess::Groups(groups) => groups.iter().any(|group| {
+                self.groups
+                    .get(group)
+                    .map(|members| members.contains(&caller))
+                    .unwrap_or(false)
+            }),
+            EntryPointAccess::Caller(account) => *account == caller,
+        }
+    }
+}
+
+impl CLTyped for ContractAbi {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ContractAbi {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.entry_points.to_bytes()?);
+        result.append(&mut self.groups.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.entry_points.serialized_length() + self.groups.serialized_length()
+    }
+}
+
+impl FromBytes for ContractAbi {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (entry_points, remainder) = Vec::<EntryPoint>::from_bytes(bytes)?;
+        let (groups, remainder) = BTreeMap::<String, Vec<PublicKey>>::from_bytes(remainder)?;
+        Ok((
+            ContractAbi {
+                entry_points,
+                groups,
+            },
+            remainder,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    const MEMBER: PublicKey = PublicKey::ed25519_from([1; 32]);
+    const OTHER: PublicKey = PublicKey::ed25519_from([2; 32]);
+
+    fn entry_point(access: EntryPointAccess) -> EntryPoint {
+        EntryPoint::new(String::from("entry_point"), vec![], CLType::Unit).with_access(access)
+    }
+
+    #[test]
+    fn should_check_entry_point_access() {
+        let mut groups = BTreeMap::new();
+        groups.insert(String::from("admins"), vec![MEMBER]);
+        let abi = ContractAbi::new(vec![]).with_groups(groups);
+
+        let public = entry_point(EntryPointAccess::Public);
+        assert!(abi.can_call(&public, OTHER));
+
+        let admins = entry_point(EntryPointAccess::Groups(vec![String::from("admins")]));
+        assert!(abi.can_call(&admins, MEMBER));
+        assert!(!abi.can_call(&admins, OTHER));
+
+        let undefined = entry_point(EntryPointAccess::Groups(vec![String::from("missing")]));
+        assert!(!abi.can_call(&undefined, MEMBER));
+
+        let caller = entry_point(EntryPointAccess::Caller(OTHER));
+        assert!(abi.can_call(&caller, OTHER));
+        assert!(!abi.can_call(&caller, MEMBER));
+    }
+}
@@ -13,8 +13,9 @@ use proptest::{
 };
 
 use crate::{
-    account::{PublicKey, Weight},
-    AccessRights, CLType, CLValue, Key, Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
+    account::{PublicKey, Weight, SECP256K1_LENGTH},
+    AccessRights, CLType, CLValue, ContractAbi, EntryPoint, EntryPointAccess, Key, NamedArg,
+    Parameter, Phase, ProtocolVersion, SemVer, TransferReceipt, URef, U128, U256, U512,
 };
 
 pub fn u8_slice_32() -> impl Strategy<Value = [u8; 32]> {
@@ -60,12 +61,21 @@ pub fn key_arb() -> impl Strategy<Value = Key> {
         public_key_arb().prop_map(Key::Account),
         u8_slice_32().prop_map(Key::Hash),
         uref_arb().prop_map(Key::URef),
-        (u8_slice_32(), u8_slice_32()).prop_map(|(seed, key)| Key::local(seed, &key))
+        (u8_slice_32(), u8_slice_32()).prop_map(|(seed, key)| Key::local(seed, &key)),
+        u8_slice_32().prop_map(Key::ContractPackage),
+        u8_slice_32().prop_map(Key::DeployInfo)
     ]
 }
 
 pub fn public_key_arb() -> impl Strategy<Value = PublicKey> {
-    u8_slice_32().prop_map(PublicKey::ed25519_from)
+    prop_oneof![
+        u8_slice_32().prop_map(PublicKey::ed25519_from),
+        (prop_oneof![Just(2u8), Just(3u8)], u8_slice_32()).prop_map(|(prefix, x)| {
+            let mut key = [prefix; SECP256K1_LENGTH];
+            key[1..].copy_from_slice(&x);
+            PublicKey::secp256k1_from(key)
+        })
+    ]
 }
 
 pub fn weight_arb() -> impl Strategy<Value = Weight> {
@@ -153,6 +163,43 @@ pub fn cl_value_arb() -> impl Strategy<Value = CLValue> {
     ]
 }
 
+pub fn transfer_receipt_arb() -> impl Strategy<Value = TransferReceipt> {
+    (u8_slice_32(), uref_arb(), uref_arb(), u512_arb()).prop_map(
+        |(deploy_hash, source, target, amount)| {
+            TransferReceipt::new(deploy_hash, source, target, amount)
+        },
+    )
+}
+
+pub fn named_arg_arb() -> impl Strategy<Value = NamedArg> {
+    ("\\PC*", cl_value_arb()).prop_map(|(name, value)| NamedArg::new(name, value))
+}
+
+pub fn entry_point_access_arb() -> impl Strategy<Value = EntryPointAccess> {
+    prop_oneof![
+        Just(EntryPointAccess::Public),
+        vec("\\PC*", 0..5).prop_map(EntryPointAccess::Groups),
+        public_key_arb().prop_map(EntryPointAccess::Caller),
+    ]
+}
+
+pub fn contract_abi_arb() -> impl Strategy<Value = ContractAbi> {
+    let parameter = ("\\PC*", cl_value_arb())
+        .prop_map(|(name, value)| Parameter::new(name, value.cl_type().clone()));
+    let entry_point = (
+        "\\PC*",
+        vec(parameter, 0..5),
+        cl_value_arb(),
+        entry_point_access_arb(),
+    )
+        .prop_map(|(name, parameters, ret, access)| {
+            EntryPoint::new(name, parameters, ret.cl_type().clone()).with_access(access)
+        });
+    let groups = btree_map("\\PC*", vec(public_key_arb(), 0..5), 0..5);
+    (vec(entry_point, 0..5), groups)
+        .prop_map(|(entry_points, groups)| ContractAbi::new(entry_points).with_groups(groups))
+}
+
 pub fn result_arb() -> impl Strategy<Value = Result<u32, u32>> {
     result::maybe_ok(any::<u32>(), any::<u32>())
 }
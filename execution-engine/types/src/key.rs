@@ -17,24 +17,36 @@ const ACCOUNT_ID: u8 = 0;
 const HASH_ID: u8 = 1;
 const UREF_ID: u8 = 2;
 const LOCAL_ID: u8 = 3;
+const CONTRACT_PACKAGE_ID: u8 = 4;
+const DEPLOY_INFO_ID: u8 = 5;
 
 /// The number of bytes in a Blake2b hash
 pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
 /// The number of bytes in a [`Key::Hash`].
 pub const KEY_HASH_LENGTH: usize = 32;
+/// The number of bytes in a [`Key::ContractPackage`].
+pub const KEY_CONTRACT_PACKAGE_LENGTH: usize = 32;
+/// The number of bytes in a [`Key::DeployInfo`].
+pub const KEY_DEPLOY_INFO_LENGTH: usize = 32;
 /// The number of bytes in a [`Key::Local`].
 pub const KEY_LOCAL_LENGTH: usize = 64;
 /// The number of bytes in the seed for a new [`Key::Local`].
 pub const KEY_LOCAL_SEED_LENGTH: usize = 32;
+/// The maximum number of [`Key::Local`] entries returned by a single call to the `list_local` host
+/// function.
+pub const MAX_LIST_LOCAL_ENTRIES: u32 = 100;
 
 const KEY_ID_SERIALIZED_LENGTH: usize = 1; // u8 used to determine the ID
 const KEY_HASH_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_HASH_LENGTH;
 const KEY_UREF_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + UREF_SERIALIZED_LENGTH;
 const KEY_LOCAL_SERIALIZED_LENGTH: usize =
     KEY_ID_SERIALIZED_LENGTH + KEY_LOCAL_SEED_LENGTH + BLAKE2B_DIGEST_LENGTH;
+const KEY_CONTRACT_PACKAGE_SERIALIZED_LENGTH: usize =
+    KEY_ID_SERIALIZED_LENGTH + KEY_CONTRACT_PACKAGE_LENGTH;
+const KEY_DEPLOY_INFO_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_DEPLOY_INFO_LENGTH;
 
 /// Creates a 32-byte BLAKE2b hash digest from a given a piece of data
-fn hash(bytes: &[u8]) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+pub(crate) fn hash(bytes: &[u8]) -> [u8; BLAKE2B_DIGEST_LENGTH] {
     let mut ret = [0u8; BLAKE2B_DIGEST_LENGTH];
     // Safe to unwrap here because our digest length is constant and valid
     let mut hasher = VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).unwrap();
@@ -63,6 +75,10 @@ pub enum Key {
         /// A hash identifying the stored data.
         hash: [u8; BLAKE2B_DIGEST_LENGTH],
     },
+    /// A `Key` under which a package grouping the versions of a smart contract is stored.
+    ContractPackage([u8; KEY_CONTRACT_PACKAGE_LENGTH]),
+    /// A `Key` under which the record of an executed deploy is stored, which is the deploy's hash.
+    DeployInfo([u8; KEY_DEPLOY_INFO_LENGTH]),
 }
 
 impl Key {
@@ -83,6 +99,8 @@ impl Key {
             Key::Hash(_) => String::from("Key::Hash"),
             Key::URef(_) => String::from("Key::URef"),
             Key::Local { .. } => String::from("Key::Local"),
+            Key::ContractPackage(_) => String::from("Key::ContractPackage"),
+            Key::DeployInfo(_) => String::from("Key::DeployInfo"),
         }
     }
 
@@ -106,9 +124,17 @@ impl Key {
             Key::Account(PublicKey::Ed25519(addr)) => {
                 format!("account-ed25519-{}", base16::encode_lower(&addr.value()))
             }
+            Key::Account(PublicKey::Secp256k1(addr)) => format!(
+                "account-secp256k1-{}",
+                base16::encode_lower(addr.as_bytes())
+            ),
             Key::Hash(addr) => format!("hash-{}", base16::encode_lower(addr)),
             Key::URef(uref) => uref.as_string(),
             Key::Local { hash, .. } => format!("local-{}", base16::encode_lower(hash)),
+            Key::ContractPackage(addr) => {
+                format!("contract-package-{}", base16::encode_lower(addr))
+            }
+            Key::DeployInfo(deploy_hash) => format!("deploy-{}", base16::encode_lower(deploy_hash)),
         }
     }
 
@@ -170,16 +196,39 @@ impl Key {
             _ => None,
         }
     }
+
+    /// Returns the inner bytes of `self` if `self` is of type [`Key::ContractPackage`], otherwise
+    /// returns `None`.
+    pub fn into_contract_package(self) -> Option<[u8; KEY_CONTRACT_PACKAGE_LENGTH]> {
+        match self {
+            Key::ContractPackage(addr) => Some(addr),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner deploy hash of `self` if `self` is of type [`Key::DeployInfo`], otherwise
+    /// returns `None`.
+    pub fn into_deploy_info(self) -> Option<[u8; KEY_DEPLOY_INFO_LENGTH]> {
+        match self {
+            Key::DeployInfo(deploy_hash) => Some(deploy_hash),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Key {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             Key::Account(PublicKey::Ed25519(ed25519)) => write!(f, "Key::Account({})", ed25519),
+            Key::Account(PublicKey::Secp256k1(secp256k1)) => {
+                write!(f, "Key::Account({})", secp256k1)
+            }
             Key::Hash(addr) => write!(f, "Key::Hash({})", HexFmt(addr)),
             Key::URef(uref) => write!(f, "Key::{}", uref), /* Display impl for URef will append */
             // URef(…).
             Key::Local { seed, hash } => write!(f, "Key::Local({}{})", HexFmt(seed), HexFmt(hash)),
+            Key::ContractPackage(addr) => write!(f, "Key::ContractPackage({})", HexFmt(addr)),
+            Key::DeployInfo(deploy_hash) => write!(f, "Key::DeployInfo({})", HexFmt(deploy_hash)),
         }
     }
 }
@@ -217,6 +266,14 @@ impl ToBytes for Key {
                 result.append(&mut seed.to_bytes()?);
                 result.append(&mut hash.to_bytes()?);
             }
+            Key::ContractPackage(addr) => {
+                result.push(CONTRACT_PACKAGE_ID);
+                result.append(&mut addr.to_bytes()?);
+            }
+            Key::DeployInfo(deploy_hash) => {
+                result.push(DEPLOY_INFO_ID);
+                result.append(&mut deploy_hash.to_bytes()?);
+            }
         }
         Ok(result)
     }
@@ -227,6 +284,8 @@ impl ToBytes for Key {
             Key::Hash(_) => KEY_HASH_SERIALIZED_LENGTH,
             Key::URef(_) => KEY_UREF_SERIALIZED_LENGTH,
             Key::Local { .. } => KEY_LOCAL_SERIALIZED_LENGTH,
+            Key::ContractPackage(_) => KEY_CONTRACT_PACKAGE_SERIALIZED_LENGTH,
+            Key::DeployInfo(_) => KEY_DEPLOY_INFO_SERIALIZED_LENGTH,
         }
     }
 }
@@ -252,6 +311,14 @@ impl FromBytes for Key {
                 let (hash, remainder) = <[u8; BLAKE2B_DIGEST_LENGTH]>::from_bytes(remainder)?;
                 Ok((Key::Local { seed, hash }, remainder))
             }
+            CONTRACT_PACKAGE_ID => {
+                let (addr, rem) = <[u8; KEY_CONTRACT_PACKAGE_LENGTH]>::from_bytes(remainder)?;
+                Ok((Key::ContractPackage(addr), rem))
+            }
+            DEPLOY_INFO_ID => {
+                let (deploy_hash, rem) = <[u8; KEY_DEPLOY_INFO_LENGTH]>::from_bytes(remainder)?;
+                Ok((Key::DeployInfo(deploy_hash), rem))
+            }
             _ => Err(Error::Formatting),
         }
     }
@@ -339,6 +406,17 @@ mod tests {
             format!("{}", local_key),
             format!("Key::Local({})", expected_hash)
         );
+        let expected_hash = core::iter::repeat("0").take(64).collect::<String>();
+        let contract_package_key = Key::ContractPackage(addr_array);
+        assert_eq!(
+            format!("{}", contract_package_key),
+            format!("Key::ContractPackage({})", expected_hash)
+        );
+        let deploy_info_key = Key::DeployInfo(addr_array);
+        assert_eq!(
+            format!("{}", deploy_info_key),
+            format!("Key::DeployInfo({})", expected_hash)
+        );
     }
 
     #[test]
@@ -396,17 +474,65 @@ mod tests {
         assert_eq!(key1.into_local().map(|x| x.to_vec()), Some(local.to_vec()));
     }
 
+    #[test]
+    fn check_key_contract_package_getters() {
+        let addr = [42; KEY_CONTRACT_PACKAGE_LENGTH];
+        let key1 = Key::ContractPackage(addr);
+        assert!(key1.into_hash().is_none());
+        assert!(key1.into_deploy_info().is_none());
+        assert_eq!(key1.into_contract_package(), Some(addr));
+        assert_eq!(key1.normalize(), key1);
+    }
+
+    #[test]
+    fn check_key_deploy_info_getters() {
+        let deploy_hash = [42; KEY_DEPLOY_INFO_LENGTH];
+        let key1 = Key::DeployInfo(deploy_hash);
+        assert!(key1.into_hash().is_none());
+        assert!(key1.into_contract_package().is_none());
+        assert_eq!(key1.into_deploy_info(), Some(deploy_hash));
+        assert_eq!(key1.normalize(), key1);
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_new_key_variants() {
+        let contract_package_key = Key::ContractPackage([42; KEY_CONTRACT_PACKAGE_LENGTH]);
+        bytesrepr::test_serialization_roundtrip(&contract_package_key);
+        let deploy_info_key = Key::DeployInfo([42; KEY_DEPLOY_INFO_LENGTH]);
+        bytesrepr::test_serialization_roundtrip(&deploy_info_key);
+
+        // Variants must not share a serialized prefix, so each can be listed from the trie alone
+        let hash_key = Key::Hash([42; KEY_HASH_LENGTH]);
+        assert_ne!(
+            contract_package_key.to_bytes().unwrap()[0],
+            hash_key.to_bytes().unwrap()[0]
+        );
+        assert_ne!(
+            deploy_info_key.to_bytes().unwrap()[0],
+            contract_package_key.to_bytes().unwrap()[0]
+        );
+    }
+
     #[test]
     fn key_max_serialized_length() {
         let key_account = Key::Account(PublicKey::ed25519_from([42; 32]));
         assert!(key_account.serialized_length() < Key::max_serialized_length());
 
+        let key_account = Key::Account(PublicKey::secp256k1_from([42; 33]));
+        assert!(key_account.serialized_length() < Key::max_serialized_length());
+
         let key_hash = Key::Hash([42; 32]);
         assert!(key_hash.serialized_length() < Key::max_serialized_length());
 
         let key_uref = Key::URef(URef::new([42; 32], AccessRights::READ));
         assert!(key_uref.serialized_length() < Key::max_serialized_length());
 
+        let key_contract_package = Key::ContractPackage([42; 32]);
+        assert!(key_contract_package.serialized_length() < Key::max_serialized_length());
+
+        let key_deploy_info = Key::DeployInfo([42; 32]);
+        assert!(key_deploy_info.serialized_length() < Key::max_serialized_length());
+
         let key_local = Key::local([42; 32], &[42; 32]);
         assert_eq!(key_local.serialized_length(), Key::max_serialized_length());
     }
@@ -23,38 +23,51 @@ extern crate std;
 mod access_rights;
 pub mod account;
 pub mod api_error;
+mod bignum_op;
 mod block_time;
 pub mod bytesrepr;
+mod chain_config_key;
 mod cl_type;
 mod cl_value;
+mod contract_abi;
 mod contract_ref;
 #[cfg(any(feature = "gens", test))]
 pub mod gens;
 mod key;
 mod phase;
 mod protocol_version;
+mod runtime_args;
 mod semver;
 pub mod system_contract_errors;
 pub mod system_contract_type;
 mod transfer_result;
 mod uint;
 mod uref;
+mod vesting_schedule;
 
 pub use crate::uint::{UIntParseError, U128, U256, U512};
 pub use access_rights::{AccessRights, ACCESS_RIGHTS_SERIALIZED_LENGTH};
 #[doc(inline)]
 pub use api_error::ApiError;
+pub use bignum_op::BigNumOp;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
+pub use chain_config_key::ChainConfigKey;
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
+pub use contract_abi::{ContractAbi, EntryPoint, EntryPointAccess, Parameter};
 pub use contract_ref::ContractRef;
 #[doc(inline)]
 pub use key::{
-    Key, BLAKE2B_DIGEST_LENGTH, KEY_HASH_LENGTH, KEY_LOCAL_LENGTH, KEY_LOCAL_SEED_LENGTH,
+    Key, BLAKE2B_DIGEST_LENGTH, KEY_CONTRACT_PACKAGE_LENGTH, KEY_DEPLOY_INFO_LENGTH,
+    KEY_HASH_LENGTH, KEY_LOCAL_LENGTH, KEY_LOCAL_SEED_LENGTH, MAX_LIST_LOCAL_ENTRIES,
 };
 pub use phase::{Phase, PHASE_SERIALIZED_LENGTH};
 pub use protocol_version::{ProtocolVersion, VersionCheckResult};
+pub use runtime_args::{NamedArg, NamedArgs, NamedArgsError, RuntimeArgs};
 pub use semver::SemVer;
 pub use system_contract_type::SystemContractType;
-pub use transfer_result::{TransferResult, TransferredTo};
+pub use transfer_result::{
+    TransferReceipt, TransferResult, TransferredTo, TRANSFER_RECEIPT_DEPLOY_HASH_LENGTH,
+};
 pub use uref::{URef, UREF_ADDR_LENGTH, UREF_SERIALIZED_LENGTH};
+pub use vesting_schedule::VestingSchedule;
@@ -0,0 +1,229 @@
+//! Arguments passed to session code and stored contracts.
+
+use alloc::{string::String, vec::Vec};
+
+use failure::Fail;
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLTyped, CLValue, CLValueError,
+};
+
+/// An argument identified by name, holding a [`CLValue`] and hence tagged with its [`CLType`].
+///
+/// [`CLType`]: crate::CLType
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct NamedArg {
+    name: String,
+    value: CLValue,
+}
+
+impl NamedArg {
+    /// Constructs a `NamedArg`.
+    pub fn new(name: String, value: CLValue) -> Self {
+        NamedArg { name, value }
+    }
+
+    /// Returns the name of the argument.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of the argument.
+    pub fn value(&self) -> &CLValue {
+        &self.value
+    }
+}
+
+impl ToBytes for NamedArg {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.name.to_bytes()?);
+        result.append(&mut self.value.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length() + self.value.serialized_length()
+    }
+}
+
+impl FromBytes for NamedArg {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (name, remainder) = String::from_bytes(bytes)?;
+        let (value, remainder) = CLValue::from_bytes(remainder)?;
+        Ok((NamedArg { name, value }, remainder))
+    }
+}
+
+/// Error returned when a list of named arguments is not valid.
+#[derive(Fail, PartialEq, Eq, Clone, Debug)]
+pub enum NamedArgsError {
+    /// An argument has an empty name.
+    #[fail(display = "Argument name is empty")]
+    EmptyName,
+    /// More than one argument has the given name.
+    #[fail(display = "Argument name {} is used more than once", _0)]
+    DuplicateName(String),
+}
+
+/// Builds a list of [`NamedArg`]s, e.g. for the session code of a deploy.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct NamedArgs(Vec<NamedArg>);
+
+impl NamedArgs {
+    /// Constructs an empty `NamedArgs`.
+    pub fn new() -> Self {
+        NamedArgs::default()
+    }
+
+    /// Appends an argument called `name` holding `value`.
+    pub fn insert<T: CLTyped + ToBytes>(
+        &mut self,
+        name: &str,
+        value: T,
+    ) -> Result<(), CLValueError> {
+        let value = CLValue::from_t(value)?;
+        self.0.push(NamedArg::new(String::from(name), value));
+        Ok(())
+    }
+
+    /// Returns the arguments in the order they were inserted.
+    pub fn into_inner(self) -> Vec<NamedArg> {
+        self.0
+    }
+}
+
+impl ToBytes for NamedArgs {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for NamedArgs {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (args, remainder) = Vec::<NamedArg>::from_bytes(bytes)?;
+        Ok((NamedArgs(args), remainder))
+    }
+}
+
+/// The arguments passed to a module invocation.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum RuntimeArgs {
+    /// Arguments identified only by their position.
+    Positional(Vec<CLValue>),
+    /// Arguments identified by name.  They can also be read by position, in the order given.
+    Named(Vec<NamedArg>),
+}
+
+impl RuntimeArgs {
+    /// Constructs `RuntimeArgs::Named`, checking that every argument has a non-empty name which
+    /// is not used by any other argument.
+    pub fn named(args: NamedArgs) -> Result<Self, NamedArgsError> {
+        let args = args.into_inner();
+        for (index, arg) in args.iter().enumerate() {
+            if arg.name.is_empty() {
+                return Err(NamedArgsError::EmptyName);
+            }
+            if args[..index].iter().any(|other| other.name == arg.name) {
+                return Err(NamedArgsError::DuplicateName(arg.name.clone()));
+            }
+        }
+        Ok(RuntimeArgs::Named(args))
+    }
+
+    /// Returns the argument at `index`.
+    pub fn get(&self, index: usize) -> Option<&CLValue> {
+        match self {
+            RuntimeArgs::Positional(args) => args.get(index),
+            RuntimeArgs::Named(args) => args.get(index).map(NamedArg::value),
+        }
+    }
+
+    /// Returns the argument called `name`, or `None` if there is none or the arguments are
+    /// positional.
+    pub fn get_named(&self, name: &str) -> Option<&CLValue> {
+        match self {
+            RuntimeArgs::Positional(_) => None,
+            RuntimeArgs::Named(args) => args
+                .iter()
+                .find(|arg| arg.name == name)
+                .map(NamedArg::value),
+        }
+    }
+
+    /// Returns the number of arguments.
+    pub fn len(&self) -> usize {
+        match self {
+            RuntimeArgs::Positional(args) => args.len(),
+            RuntimeArgs::Named(args) => args.len(),
+        }
+    }
+
+    /// Returns `true` if there are no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the argument values in order.
+    pub fn values(&self) -> impl Iterator<Item = &CLValue> {
+        (0..self.len()).filter_map(move |index| self.get(index))
+    }
+}
+
+impl Default for RuntimeArgs {
+    fn default() -> Self {
+        RuntimeArgs::Positional(Vec::new())
+    }
+}
+
+impl From<Vec<CLValue>> for RuntimeArgs {
+    fn from(args: Vec<CLValue>) -> Self {
+        RuntimeArgs::Positional(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named_args(names: &[&str]) -> NamedArgs {
+        let mut args = NamedArgs::new();
+        for (value, name) in names.iter().enumerate() {
+            args.insert(name, value as u64).unwrap();
+        }
+        args
+    }
+
+    #[test]
+    fn should_get_named_args_by_name_and_position() {
+        let args = RuntimeArgs::named(named_args(&["a", "b"])).unwrap();
+        assert_eq!(args.get_named("b"), Some(&CLValue::from_t(1u64).unwrap()));
+        assert_eq!(args.get(0), Some(&CLValue::from_t(0u64).unwrap()));
+        assert_eq!(args.get_named("c"), None);
+        assert_eq!(args.len(), 2);
+    }
+
+    #[test]
+    fn should_not_get_positional_args_by_name() {
+        let args = RuntimeArgs::from(vec![CLValue::from_t(0u64).unwrap()]);
+        assert_eq!(args.get_named(""), None);
+        assert!(args.get(0).is_some());
+    }
+
+    #[test]
+    fn should_reject_invalid_names() {
+        assert_eq!(
+            RuntimeArgs::named(named_args(&["a", ""])),
+            Err(NamedArgsError::EmptyName)
+        );
+        assert_eq!(
+            RuntimeArgs::named(named_args(&["a", "b", "a"])),
+            Err(NamedArgsError::DuplicateName(String::from("a")))
+        );
+    }
+}
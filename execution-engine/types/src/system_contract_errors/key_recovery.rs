@@ -0,0 +1,69 @@
+//! Home of the Key Recovery contract's [`Error`] type.
+use failure::Fail;
+
+use alloc::vec::Vec;
+use core::result;
+
+use crate::{
+    bytesrepr::{self, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+/// Errors which can occur while executing the Key Recovery contract.
+#[derive(Fail, Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Error {
+    /// The caller's deploy was not signed with enough weight to manage the account's keys.
+    #[fail(display = "Key management threshold not met")]
+    KeyManagementThreshold = 0,
+    /// The recovery threshold was zero or exceeded the number of recovery keys.
+    #[fail(display = "Invalid recovery threshold")]
+    InvalidThreshold = 1,
+    /// The given account has not configured any recovery keys.
+    #[fail(display = "Recovery not configured")]
+    NotConfigured = 2,
+    /// The caller is not one of the account's recovery keys.
+    #[fail(display = "Not a recovery key")]
+    NotRecoveryKey = 3,
+    /// A recovery request replacing a different key is already pending for the account.
+    #[fail(display = "Conflicting recovery request")]
+    ConflictingRequest = 4,
+    /// There is no pending recovery request for the account.
+    #[fail(display = "Recovery request not found")]
+    RequestNotFound = 5,
+    /// Fewer recovery keys than the configured threshold have approved the request.
+    #[fail(display = "Insufficient approvals")]
+    InsufficientApprovals = 6,
+    /// The configured delay has not yet elapsed since the request was made.
+    #[fail(display = "Recovery delay not elapsed")]
+    DelayNotElapsed = 7,
+    /// The key to be replaced is not associated with the account.
+    #[fail(display = "Key to be replaced not found")]
+    MissingKey = 8,
+    /// The replacement key is already associated with the account.
+    #[fail(display = "Replacement key already associated")]
+    DuplicateKey = 9,
+    /// The host refused to rotate the account's key.
+    #[fail(display = "Key rotation failed")]
+    RotationFailed = 10,
+}
+
+impl CLTyped for Error {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl ToBytes for Error {
+    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
+        let value = *self as u8;
+        value.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+/// An alias for `Result<T, key_recovery::Error>`.
+pub type Result<T> = result::Result<T, Error>;
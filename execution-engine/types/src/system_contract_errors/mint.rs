@@ -38,6 +38,16 @@ pub enum Error {
     /// Purse not found while trying to get balance.
     #[fail(display = "Purse not found")]
     PurseNotFound = 7,
+    /// The transfer would leave the source purse holding less than its vesting schedule still
+    /// locks.
+    #[fail(display = "Funds locked")]
+    FundsLocked = 8,
+    /// Tried to set a vesting schedule other than as the system account.
+    #[fail(display = "Invalid vesting schedule creation")]
+    InvalidVestingScheduleCreation = 9,
+    /// Tried to leave an account's main purse holding less than the configured minimum balance.
+    #[fail(display = "Below minimum balance")]
+    BelowMinimumBalance = 10,
 }
 
 impl From<PurseError> for Error {
@@ -79,6 +89,11 @@ impl TryFrom<u8> for Error {
             d if d == Error::InvalidNonEmptyPurseCreation as u8 => {
                 Ok(Error::InvalidNonEmptyPurseCreation)
             }
+            d if d == Error::FundsLocked as u8 => Ok(Error::FundsLocked),
+            d if d == Error::InvalidVestingScheduleCreation as u8 => {
+                Ok(Error::InvalidVestingScheduleCreation)
+            }
+            d if d == Error::BelowMinimumBalance as u8 => Ok(Error::BelowMinimumBalance),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -1,6 +1,7 @@
 //! Home of error types returned by system contracts.
 use failure::Fail;
 
+pub mod key_recovery;
 pub mod mint;
 pub mod pos;
 
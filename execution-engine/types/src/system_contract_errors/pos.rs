@@ -105,6 +105,31 @@ pub enum Error {
     /// deploy, but was called by the session code.
     #[fail(display = "Set refund purse was called outside payment")]
     SetRefundPurseCalledOutsidePayment,
+    /// Internal error: the PoS contract's unbonding delay wasn't found.
+    #[fail(display = "Unbonding delay not found")]
+    UnbondingDelayNotFound,
+    /// Internal error: the PoS contract's unbonding delay key was the wrong type.
+    #[fail(display = "Unbonding delay key has unexpected type")]
+    UnbondingDelayKeyUnexpectedType,
+    /// The given delegator has not delegated to the given validator.
+    #[fail(display = "Not delegated")]
+    NotDelegated,
+    /// While delegating, the transfer from source purse to the Proof of Stake internal purse
+    /// failed.
+    #[fail(display = "Delegate transfer failed")]
+    DelegateTransferFailed,
+    /// Internal error: failed to deserialize the delegation's keys.
+    #[fail(display = "Failed to deserialize delegation's keys")]
+    DelegationsKeyDeserializationFailed,
+    /// Internal error: failed to deserialize the delegation's amount.
+    #[fail(display = "Failed to deserialize delegation's amount")]
+    DelegationsDeserializationFailed,
+    /// Internal error: couldn't retrieve the balance for the PoS contract's rewards purse.
+    #[fail(display = "Rewards purse balance not found")]
+    RewardsPurseBalanceNotFound,
+    /// Internal error: while finalizing payment, the refund ratio exceeded 100 percent.
+    #[fail(display = "Invalid refund ratio")]
+    InvalidRefundRatio,
 }
 
 impl CLTyped for Error {
@@ -19,6 +19,8 @@ pub enum SystemContractType {
     ProofOfStake,
     /// Standard Payment contract.
     StandardPayment,
+    /// Key Recovery contract.
+    KeyRecovery,
 }
 
 /// Name of mint system contract
@@ -27,6 +29,8 @@ pub const MINT: &str = "mint";
 pub const PROOF_OF_STAKE: &str = "proof of stake";
 /// Name of standard payment system contract
 pub const STANDARD_PAYMENT: &str = "standard payment";
+/// Name of key recovery system contract
+pub const KEY_RECOVERY: &str = "key recovery";
 
 impl From<SystemContractType> for u32 {
     fn from(system_contract_type: SystemContractType) -> u32 {
@@ -34,6 +38,7 @@ impl From<SystemContractType> for u32 {
             SystemContractType::Mint => 0,
             SystemContractType::ProofOfStake => 1,
             SystemContractType::StandardPayment => 2,
+            SystemContractType::KeyRecovery => 3,
         }
     }
 }
@@ -47,6 +52,7 @@ impl TryFrom<u32> for SystemContractType {
             0 => Ok(SystemContractType::Mint),
             1 => Ok(SystemContractType::ProofOfStake),
             2 => Ok(SystemContractType::StandardPayment),
+            3 => Ok(SystemContractType::KeyRecovery),
             _ => Err(ApiError::InvalidSystemContract),
         }
     }
@@ -58,6 +64,7 @@ impl Display for SystemContractType {
             SystemContractType::Mint => write!(f, "{}", MINT),
             SystemContractType::ProofOfStake => write!(f, "{}", PROOF_OF_STAKE),
             SystemContractType::StandardPayment => write!(f, "{}", STANDARD_PAYMENT),
+            SystemContractType::KeyRecovery => write!(f, "{}", KEY_RECOVERY),
         }
     }
 }
@@ -92,6 +99,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_index_of_key_recovery_contract() {
+        let index: u32 = SystemContractType::KeyRecovery.into();
+        assert_eq!(index, 3u32);
+        assert_eq!(SystemContractType::KeyRecovery.to_string(), KEY_RECOVERY);
+    }
+
     #[test]
     fn create_mint_variant_from_int() {
         let mint = SystemContractType::try_from(0).ok().unwrap();
@@ -110,9 +124,14 @@ mod tests {
         assert_eq!(pos, SystemContractType::StandardPayment);
     }
 
+    #[test]
+    fn create_key_recovery_variant_from_int() {
+        let key_recovery = SystemContractType::try_from(3).ok().unwrap();
+        assert_eq!(key_recovery, SystemContractType::KeyRecovery);
+    }
+
     #[test]
     fn create_unknown_system_contract_variant() {
-        assert!(SystemContractType::try_from(3).is_err());
         assert!(SystemContractType::try_from(4).is_err());
         assert!(SystemContractType::try_from(10).is_err());
         assert!(SystemContractType::try_from(u32::max_value()).is_err());
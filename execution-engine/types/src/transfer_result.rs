@@ -1,6 +1,13 @@
+use alloc::vec::Vec;
 use core::fmt::Debug;
 
-use crate::ApiError;
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    ApiError, CLType, CLTyped, URef, U512, UREF_SERIALIZED_LENGTH,
+};
+
+/// The number of bytes in a deploy hash held by a [`TransferReceipt`].
+pub const TRANSFER_RECEIPT_DEPLOY_HASH_LENGTH: usize = 32;
 
 /// The result of an attempt to transfer between purses.
 pub type TransferResult = Result<TransferredTo, ApiError>;
@@ -37,3 +44,91 @@ impl TransferredTo {
         }
     }
 }
+
+/// A record of a successful transfer between purses, written to global state by the host under a
+/// new read-only [`URef`] so that it can be referenced by later deploys.
+///
+/// The purses held by the receipt have their access rights removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferReceipt {
+    deploy_hash: [u8; TRANSFER_RECEIPT_DEPLOY_HASH_LENGTH],
+    source: URef,
+    target: URef,
+    amount: U512,
+}
+
+impl TransferReceipt {
+    /// Constructs a `TransferReceipt`.  Access rights are removed from `source` and `target`.
+    pub fn new(
+        deploy_hash: [u8; TRANSFER_RECEIPT_DEPLOY_HASH_LENGTH],
+        source: URef,
+        target: URef,
+        amount: U512,
+    ) -> Self {
+        TransferReceipt {
+            deploy_hash,
+            source: source.remove_access_rights(),
+            target: target.remove_access_rights(),
+            amount,
+        }
+    }
+
+    /// Returns the hash of the deploy in which the transfer was made.
+    pub fn deploy_hash(&self) -> [u8; TRANSFER_RECEIPT_DEPLOY_HASH_LENGTH] {
+        self.deploy_hash
+    }
+
+    /// Returns the purse the motes were transferred from.
+    pub fn source(&self) -> URef {
+        self.source
+    }
+
+    /// Returns the purse the motes were transferred to.
+    pub fn target(&self) -> URef {
+        self.target
+    }
+
+    /// Returns the number of motes transferred.
+    pub fn amount(&self) -> U512 {
+        self.amount
+    }
+}
+
+impl CLTyped for TransferReceipt {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for TransferReceipt {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.deploy_hash.to_bytes()?);
+        result.append(&mut self.source.to_bytes()?);
+        result.append(&mut self.target.to_bytes()?);
+        result.append(&mut self.amount.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        TRANSFER_RECEIPT_DEPLOY_HASH_LENGTH
+            + UREF_SERIALIZED_LENGTH * 2
+            + self.amount.serialized_length()
+    }
+}
+
+impl FromBytes for TransferReceipt {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (deploy_hash, rem) = FromBytes::from_bytes(bytes)?;
+        let (source, rem) = FromBytes::from_bytes(rem)?;
+        let (target, rem) = FromBytes::from_bytes(rem)?;
+        let (amount, rem) = FromBytes::from_bytes(rem)?;
+        let receipt = TransferReceipt {
+            deploy_hash,
+            source,
+            target,
+            amount,
+        };
+        Ok((receipt, rem))
+    }
+}
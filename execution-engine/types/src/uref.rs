@@ -42,6 +42,16 @@ impl URef {
         URef(self.0, access_rights)
     }
 
+    /// Returns a new [`URef`] with the same address, keeping only those of this `URef`'s access
+    /// rights which are also in `access_rights`.
+    ///
+    /// Unlike [`with_access_rights`](URef::with_access_rights), this can never grant rights which
+    /// this `URef` doesn't already have, so it's suitable for narrowing a `URef` before passing it
+    /// on to another contract.
+    pub fn attenuate(self, access_rights: AccessRights) -> Self {
+        URef(self.0, self.1 & access_rights)
+    }
+
     /// Removes the access rights from this [`URef`].
     pub fn remove_access_rights(self) -> Self {
         URef(self.0, AccessRights::NONE)
@@ -165,4 +175,22 @@ mod tests {
             "uref-0000000000000000000000000000000000000000000000000000000000000000-000"
         );
     }
+
+    #[test]
+    fn attenuate_should_not_grant_new_rights() {
+        let uref = URef::new([1u8; 32], AccessRights::READ_ADD);
+        assert_eq!(
+            uref.attenuate(AccessRights::READ).access_rights(),
+            AccessRights::READ
+        );
+        assert_eq!(
+            uref.attenuate(AccessRights::READ_WRITE).access_rights(),
+            AccessRights::READ
+        );
+        assert_eq!(
+            uref.attenuate(AccessRights::WRITE).access_rights(),
+            AccessRights::NONE
+        );
+        assert_eq!(uref.attenuate(AccessRights::READ).addr(), uref.addr());
+    }
 }
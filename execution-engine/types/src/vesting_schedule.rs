@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes, U64_SERIALIZED_LENGTH},
+    BlockTime, CLType, CLTyped, BLOCKTIME_SERIALIZED_LENGTH, U512,
+};
+
+/// An amount of motes in a purse which can't be spent until a cliff, after which it is released
+/// linearly over a given duration.
+///
+/// The mint refuses any transfer or burn which would leave the purse holding less than
+/// [`locked_amount_at`](VestingSchedule::locked_amount_at) the current block time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VestingSchedule {
+    locked_amount: U512,
+    cliff: BlockTime,
+    release_duration: u64,
+}
+
+impl VestingSchedule {
+    /// Constructs a `VestingSchedule` locking `locked_amount` until `cliff`, then releasing it in
+    /// equal parts over the following `release_duration` milliseconds.
+    pub fn new(locked_amount: U512, cliff: BlockTime, release_duration: u64) -> Self {
+        VestingSchedule {
+            locked_amount,
+            cliff,
+            release_duration,
+        }
+    }
+
+    /// Returns the amount locked until the cliff.
+    pub fn locked_amount(&self) -> U512 {
+        self.locked_amount
+    }
+
+    /// Returns the time at which the locked amount starts to be released.
+    pub fn cliff(&self) -> BlockTime {
+        self.cliff
+    }
+
+    /// Returns the number of milliseconds after the cliff over which the locked amount is
+    /// released.
+    pub fn release_duration(&self) -> u64 {
+        self.release_duration
+    }
+
+    /// Returns the amount still locked at `blocktime`.
+    pub fn locked_amount_at(&self, blocktime: BlockTime) -> U512 {
+        let now: u64 = blocktime.into();
+        let cliff: u64 = self.cliff.into();
+        if now < cliff {
+            return self.locked_amount;
+        }
+        let elapsed = now - cliff;
+        if elapsed >= self.release_duration {
+            return U512::zero();
+        }
+        let released = self.locked_amount * U512::from(elapsed) / U512::from(self.release_duration);
+        self.locked_amount - released
+    }
+}
+
+impl CLTyped for VestingSchedule {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for VestingSchedule {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.locked_amount.to_bytes()?);
+        result.append(&mut self.cliff.to_bytes()?);
+        result.append(&mut self.release_duration.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.locked_amount.serialized_length() + BLOCKTIME_SERIALIZED_LENGTH + U64_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for VestingSchedule {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (locked_amount, rem) = FromBytes::from_bytes(bytes)?;
+        let (cliff, rem) = FromBytes::from_bytes(rem)?;
+        let (release_duration, rem) = FromBytes::from_bytes(rem)?;
+        let schedule = VestingSchedule {
+            locked_amount,
+            cliff,
+            release_duration,
+        };
+        Ok((schedule, rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_release_linearly_after_cliff() {
+        let schedule = VestingSchedule::new(U512::from(1_000), BlockTime::new(100), 10);
+        let locked_at = |time: u64| schedule.locked_amount_at(BlockTime::new(time));
+
+        assert_eq!(locked_at(0), U512::from(1_000));
+        assert_eq!(locked_at(99), U512::from(1_000));
+        assert_eq!(locked_at(100), U512::from(1_000));
+        assert_eq!(locked_at(101), U512::from(900));
+        assert_eq!(locked_at(105), U512::from(500));
+        assert_eq!(locked_at(110), U512::zero());
+        assert_eq!(locked_at(u64::max_value()), U512::zero());
+    }
+
+    #[test]
+    fn should_release_everything_at_cliff_without_release_duration() {
+        let schedule = VestingSchedule::new(U512::from(1_000), BlockTime::new(100), 0);
+        assert_eq!(
+            schedule.locked_amount_at(BlockTime::new(99)),
+            U512::from(1_000)
+        );
+        assert_eq!(schedule.locked_amount_at(BlockTime::new(100)), U512::zero());
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let schedule = VestingSchedule::new(U512::from(1_000), BlockTime::new(100), 10);
+        bytesrepr::test_serialization_roundtrip(&schedule);
+    }
+}
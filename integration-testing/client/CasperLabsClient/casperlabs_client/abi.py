@@ -52,13 +52,47 @@ class ABI:
             ),
         )
 
+    ED25519_TAG = 0
+    SECP256K1_TAG = 1
+
     @staticmethod
     def account(name, a):
-        if type(a) == bytes and len(a) == 32:
-            return ABI.bytes_value(name, a)
-        if type(a) == str and len(a) == 64:
-            return ABI.bytes_value(name, bytes.fromhex(a))
-        raise Exception("account must be 32 bytes or 64 characters long string")
+        """
+        Public keys are passed to contracts tagged with their algorithm, as a
+        tuple of the tag and the list of the bytes of the key.
+        """
+        if type(a) == str:
+            a = bytes.fromhex(a)
+        if type(a) != bytes:
+            raise Exception("account must be bytes or a hex string")
+        if len(a) == 32:
+            tag = ABI.ED25519_TAG
+        elif len(a) == 33 and a[0] in (2, 3):
+            tag = ABI.SECP256K1_TAG
+        elif len(a) == 65 and a[0] == 4:
+            # Compress an uncompressed secp256k1 key.
+            tag = ABI.SECP256K1_TAG
+            a = bytes([2 | (a[-1] & 1)]) + a[1:33]
+        else:
+            raise Exception(
+                "account must be a 32-byte Ed25519 or a 33- or 65-byte secp256k1 key"
+            )
+        u8 = Type(simple_type=Type.Simple.U8)
+        return Arg(
+            name=name,
+            value=Instance(
+                cl_type=Type(
+                    tuple2_type=Type.Tuple2(
+                        type0=u8, type1=Type(list_type=Type.List(inner=u8))
+                    )
+                ),
+                value=Value(
+                    tuple2_value=Instance.Tuple2(
+                        value_1=Value(u8=tag), value_2=Value(bytes_value=a)
+                    )
+                ),
+            ),
+        )
 
     @staticmethod
     def int_value(name, i: int):
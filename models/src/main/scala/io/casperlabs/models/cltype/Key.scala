@@ -9,9 +9,9 @@ sealed trait Key {
 }
 
 object Key {
-  case class Account(address: ByteArray32) extends Key {
+  case class Account(address: PublicKey) extends Key {
     override protected val tag: Byte        = Account.tag
-    protected def innerToBytes: Array[Byte] = ToBytes[ByteArray32].toBytes(address)
+    protected def innerToBytes: Array[Byte] = ToBytes[PublicKey].toBytes(address)
   }
 
   object Account {
@@ -46,6 +46,24 @@ object Key {
     val tag: Byte = 3
   }
 
+  case class ContractPackage(address: ByteArray32) extends Key {
+    override protected val tag: Byte        = ContractPackage.tag
+    protected def innerToBytes: Array[Byte] = ToBytes[ByteArray32].toBytes(address)
+  }
+
+  object ContractPackage {
+    val tag: Byte = 4
+  }
+
+  case class DeployInfo(deployHash: ByteArray32) extends Key {
+    override protected val tag: Byte        = DeployInfo.tag
+    protected def innerToBytes: Array[Byte] = ToBytes[ByteArray32].toBytes(deployHash)
+  }
+
+  object DeployInfo {
+    val tag: Byte = 5
+  }
+
   implicit val toBytesKey: ToBytes[Key] = new ToBytes[Key] {
     override def toBytes(k: Key): Array[Byte] =
       k.tag +: k.innerToBytes
@@ -54,7 +72,7 @@ object Key {
   val deserializer: FromBytes.Deserializer[Key] =
     FromBytes.byte.flatMap {
       case tag if tag == Account.tag =>
-        ByteArray32.deserializer.map[Key](address => Account(address))
+        PublicKey.deserializer.map[Key](address => Account(address))
       case tag if tag == Hash.tag => ByteArray32.deserializer.map[Key](address => Hash(address))
       case tag if tag == URef.tag => cltype.URef.deserializer.map[Key](uref => URef(uref))
       case tag if tag == Local.tag =>
@@ -62,6 +80,10 @@ object Key {
           seed <- ByteArray32.deserializer
           hash <- ByteArray32.deserializer
         } yield Local(seed, hash)
+      case tag if tag == ContractPackage.tag =>
+        ByteArray32.deserializer.map[Key](address => ContractPackage(address))
+      case tag if tag == DeployInfo.tag =>
+        ByteArray32.deserializer.map[Key](deployHash => DeployInfo(deployHash))
       case other => FromBytes.raise(FromBytes.Error.InvalidVariantTag(other, "Key"))
     }
 }
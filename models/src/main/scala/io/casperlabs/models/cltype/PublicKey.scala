@@ -0,0 +1,75 @@
+package io.casperlabs.models.cltype
+
+import io.casperlabs.models.bytesrepr.{FromBytes, ToBytes}
+
+/** An account's public key, serialized as the tag of its algorithm followed by the
+  * length-prefixed raw bytes of the key. */
+sealed trait PublicKey {
+  protected val tag: Byte
+  def bytes: IndexedSeq[Byte]
+}
+
+object PublicKey {
+  case class Ed25519(key: ByteArray32) extends PublicKey {
+    override protected val tag: Byte      = Ed25519.tag
+    override def bytes: IndexedSeq[Byte] = key.bytes
+  }
+
+  object Ed25519 {
+    val tag: Byte = 0
+  }
+
+  /** A compressed secp256k1 public key. */
+  case class Secp256k1 private (bytes: IndexedSeq[Byte]) extends PublicKey {
+    override protected val tag: Byte = Secp256k1.tag
+  }
+
+  object Secp256k1 {
+    val tag: Byte               = 1
+    val length: Int             = 33
+    val uncompressedLength: Int = 65
+
+    /** Accepts either a compressed or an uncompressed key, converting the latter to the former. */
+    def apply(bytes: IndexedSeq[Byte]): Option[Secp256k1] =
+      if (bytes.length == length && (bytes.head == 2 || bytes.head == 3))
+        Some(new Secp256k1(bytes))
+      else if (bytes.length == uncompressedLength && bytes.head == 4) {
+        val prefix: Byte = if ((bytes.last & 1) == 1) 3 else 2
+        Some(new Secp256k1(prefix +: bytes.slice(1, length)))
+      } else None
+  }
+
+  /** Infers the algorithm of the raw bytes of a public key from their length. */
+  def apply(bytes: IndexedSeq[Byte]): Option[PublicKey] =
+    ByteArray32(bytes).map(Ed25519(_)) orElse Secp256k1(bytes)
+
+  def lt(a: PublicKey, b: PublicKey): Boolean =
+    if (a.tag != b.tag) a.tag < b.tag
+    else
+      a.bytes.iterator.zip(b.bytes.iterator).find { case (x, y) => x != y } match {
+        case None         => false // a == b since all elements were equal
+        case Some((x, y)) => x < y
+      }
+
+  implicit val ordering: Ordering[PublicKey] = Ordering.fromLessThan(lt)
+
+  implicit val toBytesPublicKey: ToBytes[PublicKey] = new ToBytes[PublicKey] {
+    override def toBytes(k: PublicKey): Array[Byte] =
+      k.tag +: ToBytes.toBytes(k.bytes)
+  }
+
+  val deserializer: FromBytes.Deserializer[PublicKey] =
+    for {
+      tag   <- FromBytes.byte
+      bytes <- FromBytes.bytes
+      publicKey <- {
+        val maybeKey =
+          if (tag == Ed25519.tag) ByteArray32(bytes).map[PublicKey](Ed25519(_))
+          else if (tag == Secp256k1.tag && bytes.length == Secp256k1.length) Secp256k1(bytes)
+          else None
+        maybeKey.fold(
+          FromBytes.raise[PublicKey](FromBytes.Error.InvalidVariantTag(tag, "PublicKey"))
+        )(FromBytes.pure)
+      }
+    } yield publicKey
+}
@@ -92,6 +92,20 @@ object Mappings {
         state.Key.Value
           .Local(state.Key.Local(ByteString.copyFrom(address)))
       )
+
+    case Key.ContractPackage(address) =>
+      state.Key(
+        state.Key.Value.ContractPackage(
+          state.Key.ContractPackage(ByteString.copyFrom(address.bytes.toArray))
+        )
+      )
+
+    case Key.DeployInfo(deployHash) =>
+      state.Key(
+        state.Key.Value.DeployInfo(
+          state.Key.DeployInfo(ByteString.copyFrom(deployHash.bytes.toArray))
+        )
+      )
   }
 
   def toProto(version: SemVer): state.ProtocolVersion = state.ProtocolVersion(
@@ -267,7 +281,7 @@ object Mappings {
     case state.Key.Value.Empty => Left(Error.EmptyKeyVariant)
 
     case state.Key.Value.Address(state.Key.Address(address)) =>
-      toByteArray32(address).map(Key.Account.apply)
+      toPublicKey(address).map(Key.Account.apply)
 
     case state.Key.Value.Hash(state.Key.Hash(address)) =>
       toByteArray32(address).map(Key.Hash.apply)
@@ -284,6 +298,12 @@ object Mappings {
           hash <- toByteArray32(hashBytes)
         } yield Key.Local(seed, hash)
       }
+
+    case state.Key.Value.ContractPackage(state.Key.ContractPackage(address)) =>
+      toByteArray32(address).map(Key.ContractPackage.apply)
+
+    case state.Key.Value.DeployInfo(state.Key.DeployInfo(deployHash)) =>
+      toByteArray32(deployHash).map(Key.DeployInfo.apply)
   }
 
   def fromProto(proto: state.CLType): Either[Error, CLType] =
@@ -590,6 +610,12 @@ object Mappings {
       case Some(bytes32) => Right(bytes32)
     }
 
+  private def toPublicKey(bytes: ByteString): Either[Error, PublicKey] =
+    PublicKey(bytes.toByteArray) match {
+      case None            => Left(Error.InvalidPublicKeyLength(foundLength = bytes.size))
+      case Some(publicKey) => Right(publicKey)
+    }
+
   private def validateBigInt(s: String): Either[Error, BigInt Refined NonNegative] =
     Try(BigInt(s)) match {
       case Failure(_) => Left(Error.InvalidBigIntValue(s))
@@ -610,6 +636,7 @@ object Mappings {
 
     case class Expected32Bytes(foundLength: Int) extends Error
     case class Expected64Bytes(foundLength: Int) extends Error
+    case class InvalidPublicKeyLength(foundLength: Int) extends Error
 
     case object MissingType          extends Error
     case object MissingInstance      extends Error
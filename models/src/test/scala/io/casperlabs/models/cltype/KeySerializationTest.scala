@@ -14,7 +14,7 @@ class KeySerializationTest extends FlatSpec with Matchers with PropertyChecks {
 
 object KeySerializationTest {
   val genAccountKey: Gen[Key.Account] =
-    ByteArray32SerializationTest.genByteArray32.map(Key.Account(_))
+    PublicKeySerializationTest.genPublicKey.map(Key.Account(_))
 
   val genHashKey: Gen[Key.Hash] = ByteArray32SerializationTest.genByteArray32.map(Key.Hash(_))
   val genURefKey: Gen[Key.URef] = URefSerializationTest.genURef.map(Key.URef(_))
@@ -22,8 +22,19 @@ object KeySerializationTest {
     seed <- ByteArray32SerializationTest.genByteArray32
     hash <- ByteArray32SerializationTest.genByteArray32
   } yield Key.Local(seed, hash)
+  val genContractPackageKey: Gen[Key.ContractPackage] =
+    ByteArray32SerializationTest.genByteArray32.map(Key.ContractPackage(_))
+  val genDeployInfoKey: Gen[Key.DeployInfo] =
+    ByteArray32SerializationTest.genByteArray32.map(Key.DeployInfo(_))
 
-  val genKey: Gen[Key] = Gen.oneOf(genAccountKey, genHashKey, genURefKey, genLocalKey)
+  val genKey: Gen[Key] = Gen.oneOf(
+    genAccountKey,
+    genHashKey,
+    genURefKey,
+    genLocalKey,
+    genContractPackageKey,
+    genDeployInfoKey
+  )
 
   implicit val arbKey: Arbitrary[Key] = Arbitrary(genKey)
 }
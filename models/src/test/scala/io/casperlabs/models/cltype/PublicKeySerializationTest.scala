@@ -0,0 +1,43 @@
+package io.casperlabs.models.cltype
+
+import io.casperlabs.models.bytesrepr.{FromBytes, ToBytes}
+import io.casperlabs.models.bytesrepr.SerializationTest.roundTrip
+import org.scalacheck.{Arbitrary, Gen}
+import org.scalatest.{FlatSpec, Matchers}
+import org.scalatest.prop.PropertyChecks
+import PublicKeySerializationTest.arbPublicKey
+
+class PublicKeySerializationTest extends FlatSpec with Matchers with PropertyChecks {
+  "PublicKey" should "serialize properly" in forAll { (k: PublicKey) =>
+    roundTrip(k, PublicKey.deserializer)
+  }
+
+  it should "compress uncompressed secp256k1 keys" in {
+    val x            = Array.fill[Byte](32)(7)
+    val y            = Array.fill[Byte](31)(9) :+ 3.toByte
+    val uncompressed = (4.toByte +: x) ++ y
+
+    PublicKey(uncompressed.toIndexedSeq) shouldBe PublicKey.Secp256k1((3.toByte +: x).toIndexedSeq)
+  }
+
+  it should "reject a key whose length doesn't match its algorithm" in {
+    val secp256k1Bytes = ToBytes.toBytes(IndexedSeq.fill[Byte](PublicKey.Secp256k1.length)(2))
+    val ed25519Tagged  = PublicKey.Ed25519.tag +: secp256k1Bytes
+    FromBytes.deserialize(PublicKey.deserializer, ed25519Tagged) shouldBe a[Left[_, _]]
+  }
+}
+
+object PublicKeySerializationTest {
+  val genSecp256k1: Gen[PublicKey] =
+    for {
+      prefix <- Gen.oneOf[Byte](2, 3)
+      x      <- Gen.listOfN[Byte](32, Gen.choose[Byte](-128, 127))
+    } yield PublicKey.Secp256k1((prefix +: x).toIndexedSeq).get
+
+  val genPublicKey: Gen[PublicKey] = Gen.oneOf(
+    ByteArray32SerializationTest.genByteArray32.map(PublicKey.Ed25519(_)),
+    genSecp256k1
+  )
+
+  implicit val arbPublicKey: Arbitrary[PublicKey] = Arbitrary(genPublicKey)
+}
@@ -107,13 +107,37 @@ package object types {
     )
   )
 
+  lazy val KeyContractPackage = ObjectType(
+    "KeyContractPackage",
+    fields[Unit, cltype.Key.ContractPackage](
+      Field(
+        "value",
+        StringType,
+        resolve = c => Base16.encode(c.value.address.bytes.toArray)
+      )
+    )
+  )
+
+  lazy val KeyDeployInfo = ObjectType(
+    "KeyDeployInfo",
+    fields[Unit, cltype.Key.DeployInfo](
+      Field(
+        "deployHash",
+        StringType,
+        resolve = c => Base16.encode(c.value.deployHash.bytes.toArray)
+      )
+    )
+  )
+
   lazy val KeyUnion = UnionType(
     "KeyUnion",
     types = List(
       KeyAddress,
       KeyHash,
       KeyURef,
-      KeyLocal
+      KeyLocal,
+      KeyContractPackage,
+      KeyDeployInfo
     )
   )
 
@@ -124,10 +148,12 @@ package object types {
         "value",
         KeyUnion,
         resolve = _.value.value match {
-          case value: cltype.Key.Local   => value
-          case value: cltype.Key.Hash    => value
-          case value: cltype.Key.Account => value
-          case value: cltype.Key.URef    => value
+          case value: cltype.Key.Local           => value
+          case value: cltype.Key.Hash            => value
+          case value: cltype.Key.Account         => value
+          case value: cltype.Key.URef            => value
+          case value: cltype.Key.ContractPackage => value
+          case value: cltype.Key.DeployInfo      => value
         }
       )
     )
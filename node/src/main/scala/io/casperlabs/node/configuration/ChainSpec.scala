@@ -138,7 +138,19 @@ object ChainSpec extends ParserImplicits {
       memCopyPerByte: Int Refined NonNegative,
       maxStackHeight: Int Refined NonNegative,
       opcodesMultiplier: Int Refined NonNegative,
-      opcodesDivisor: Int Refined Positive
+      opcodesDivisor: Int Refined Positive,
+      maxMemoryPages: Int Refined NonNegative,
+      maxTableSize: Int Refined NonNegative,
+      hashPerByte: Int Refined NonNegative,
+      verifyEd25519: Int Refined NonNegative,
+      verifySecp256k1: Int Refined NonNegative,
+      bignumOp: Int Refined NonNegative,
+      storageBucketSize: Int Refined Positive,
+      storageReadPerBucket: Int Refined NonNegative,
+      storageWritePerBucket: Int Refined NonNegative,
+      maxModuleSize: Int Refined Positive,
+      maxFunctionCount: Int Refined Positive,
+      maxNamedKeysPerWrite: Int Refined Positive
   ) extends SubConfig
 
   final case class Account(
@@ -415,6 +427,18 @@ object ChainSpecReader {
           .withMaxStackHeight(wasmCosts.maxStackHeight.value)
           .withOpcodesMul(wasmCosts.opcodesMultiplier.value)
           .withOpcodesDiv(wasmCosts.opcodesDivisor.value)
+          .withMaxMemoryPages(wasmCosts.maxMemoryPages.value)
+          .withMaxTableSize(wasmCosts.maxTableSize.value)
+          .withHashPerByte(wasmCosts.hashPerByte.value)
+          .withVerifyEd25519(wasmCosts.verifyEd25519.value)
+          .withVerifySecp256K1(wasmCosts.verifySecp256k1.value)
+          .withBignumOp(wasmCosts.bignumOp.value)
+          .withStorageBucketSize(wasmCosts.storageBucketSize.value)
+          .withStorageReadPerBucket(wasmCosts.storageReadPerBucket.value)
+          .withStorageWritePerBucket(wasmCosts.storageWritePerBucket.value)
+          .withMaxModuleSize(wasmCosts.maxModuleSize.value)
+          .withMaxFunctionCount(wasmCosts.maxFunctionCount.value)
+          .withMaxNamedKeysPerWrite(wasmCosts.maxNamedKeysPerWrite.value)
       )
 
   private def toDeployConfig(deployConfig: Deploy): ipc.ChainSpec.DeployConfig =
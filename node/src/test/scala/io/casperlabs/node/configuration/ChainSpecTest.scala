@@ -141,6 +141,18 @@ class ChainSpecTest extends WordSpecLike with Matchers with Inspectors {
           wasmCosts.maxStackHeight shouldBe 8
           wasmCosts.opcodesMul shouldBe 9
           wasmCosts.opcodesDiv shouldBe 10
+          wasmCosts.maxMemoryPages shouldBe 11
+          wasmCosts.maxTableSize shouldBe 12
+          wasmCosts.hashPerByte shouldBe 13
+          wasmCosts.verifyEd25519 shouldBe 14
+          wasmCosts.verifySecp256K1 shouldBe 15
+          wasmCosts.bignumOp shouldBe 16
+          wasmCosts.storageBucketSize shouldBe 17
+          wasmCosts.storageReadPerBucket shouldBe 18
+          wasmCosts.storageWritePerBucket shouldBe 19
+          wasmCosts.maxModuleSize shouldBe 20
+          wasmCosts.maxFunctionCount shouldBe 21
+          wasmCosts.maxNamedKeysPerWrite shouldBe 22
         }
       }
 
@@ -165,6 +177,18 @@ class ChainSpecTest extends WordSpecLike with Matchers with Inspectors {
           wasmCosts.maxStackHeight shouldBe 28
           wasmCosts.opcodesMul shouldBe 29
           wasmCosts.opcodesDiv shouldBe 210
+          wasmCosts.maxMemoryPages shouldBe 211
+          wasmCosts.maxTableSize shouldBe 212
+          wasmCosts.hashPerByte shouldBe 213
+          wasmCosts.verifyEd25519 shouldBe 214
+          wasmCosts.verifySecp256K1 shouldBe 215
+          wasmCosts.bignumOp shouldBe 216
+          wasmCosts.storageBucketSize shouldBe 217
+          wasmCosts.storageReadPerBucket shouldBe 218
+          wasmCosts.storageWritePerBucket shouldBe 219
+          wasmCosts.maxModuleSize shouldBe 220
+          wasmCosts.maxFunctionCount shouldBe 221
+          wasmCosts.maxNamedKeysPerWrite shouldBe 222
         }
       }
 